@@ -1,6 +1,6 @@
 use std::str::FromStr;
 
-/// This build script checks for the presence of a configuration file and two default styles and creates them if not present.
+/// This build script checks for the presence of a configuration file, two default styles and a mobile-friendly stylesheet and creates them if not present.
 fn main() {
     // Step 1: Get the supposed location for the config file
     if let Ok(target) = confy::get_configuration_file_path("rucola", "config") {
@@ -52,6 +52,15 @@ fn main() {
             css_target,
         );
     }
+
+    // The mobile stylesheet only styles HTML files, so there is no toml style to go with it
+    if let Ok(mut target) = confy::get_configuration_file_path("rucola", "default_mobile") {
+        target.set_extension("css");
+        let _ = copy_with_create_if_not_present(
+            std::path::PathBuf::from_str("./default-config/default_mobile.css").unwrap(),
+            target,
+        );
+    }
 }
 
 /// Checks if the superfolder of the file at `target` is present.
@@ -0,0 +1,12 @@
+//! Finds all blocks marked in arbitrary content and the references to them.
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use rucola_core::data;
+
+fuzz_target!(|content: &str| {
+    for id in data::block_ids(content) {
+        let _ = data::find_block(content, &id);
+    }
+    let _ = data::block_references(content);
+});
@@ -0,0 +1,14 @@
+//! Reads arbitrary content as a markdown table and as a CSV file.
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use rucola_core::data;
+
+fuzz_target!(|content: &str| {
+    if let Some(mut table) = data::DataTable::from_markdown(content.lines()) {
+        if !table.columns.is_empty() {
+            table.sort(0, true);
+        }
+    }
+    let _ = data::DataTable::from_csv(content, std::path::PathBuf::from("data.csv"));
+});
@@ -0,0 +1,13 @@
+//! Parses arbitrary content as a note: Its frontmatter, markdown and math.
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use rucola_core::data;
+
+fuzz_target!(|content: &str| {
+    let _ = data::Note::from_content(
+        std::path::Path::new("Note.md"),
+        content,
+        &data::ParseOptions::default(),
+    );
+});
@@ -0,0 +1,11 @@
+//! Looks for a tag in arbitrary content, walking its frontmatter, code and typst blocks.
+//! The first line of the input is the tag, the rest the content of the note.
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use rucola_core::data;
+
+fuzz_target!(|input: &str| {
+    let (tag, content) = input.split_once('\n').unwrap_or((input, ""));
+    let _ = data::find_occurrences(content, tag);
+});
@@ -0,0 +1,74 @@
+//! Benchmarks of indexing and filtering synthetic vaults of different sizes.
+//! Run with `cargo bench -p rucola-core --features fixtures`, which compares the results with those of the previous run.
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use rucola_core::{data, fixtures, io, Config};
+
+/// The numbers of notes of the benchmarked vaults.
+const SIZES: [usize; 3] = [100, 1000, 5000];
+
+/// Indexes a vault, as on startup.
+fn index(vault: &std::path::Path) -> data::NoteIndex {
+    let config = Config::default();
+    data::NoteIndex::new(
+        io::FileTracker::new(&config, vault.to_path_buf()).expect("Default config to be valid."),
+        io::HtmlBuilder::new(&config, vault.to_path_buf()),
+        Default::default(),
+    )
+    .0
+}
+
+fn bench_index(c: &mut Criterion) {
+    let root = std::env::temp_dir().join(format!("rucola-bench-{}", std::process::id()));
+
+    let mut group = c.benchmark_group("index");
+    group.sample_size(10);
+    for notes in SIZES {
+        let vault = root.join(notes.to_string());
+        fixtures::generate_vault(
+            &vault,
+            &fixtures::VaultShape {
+                notes,
+                ..Default::default()
+            },
+        )
+        .expect("Synthetic vault to be written.");
+
+        group.bench_with_input(BenchmarkId::new("build", notes), &vault, |b, vault| {
+            b.iter(|| index(vault))
+        });
+
+        let index = std::rc::Rc::new(std::cell::RefCell::new(index(&vault)));
+        for (name, filter) in [
+            ("filter-title", "manifold"),
+            ("filter-tag", "#atlas0 chart"),
+            ("filter-links", ">group"),
+        ] {
+            group.bench_with_input(BenchmarkId::new(name, notes), &index, |b, index| {
+                b.iter(|| {
+                    data::EnvironmentStats::new_with_filter(index, data::Filter::new(filter, false))
+                })
+            });
+        }
+
+        // typing a filter character by character, refiltering the stats after each one
+        group.bench_with_input(
+            BenchmarkId::new("filter-typing", notes),
+            &index,
+            |b, index| {
+                let mut stats = data::EnvironmentStats::new_with_filter(index, Default::default());
+                b.iter(|| {
+                    for typed in ["m", "ma", "man", "mani", "manifold", ""] {
+                        stats.refilter(index, data::Filter::new(typed, false));
+                    }
+                })
+            },
+        );
+    }
+    group.finish();
+
+    let _ = std::fs::remove_dir_all(root);
+}
+
+criterion_group!(benches, bench_index);
+criterion_main!(benches);
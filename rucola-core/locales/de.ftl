@@ -0,0 +1,455 @@
+# Die Texte der Benutzeroberfläche von rucola, im Fluent-Format (https://projectfluent.org).
+# Tastenkürzel stehen in eckigen Klammern und werden hervorgehoben, z.B. `[F]ilter` oder `[Esc]: Verwerfen`. Die Tasten selbst hängen nicht von der Sprache ab.
+# Hinweise in einer Zeile werden durch `──` getrennt, das den Rahmen des umgebenden Blocks fortsetzt.
+
+## Gemeinsam
+
+hints-close = Schließen [C]
+prompt-name = Name der Notiz eingeben...
+prompt-rename = Neuen Namen der Notiz eingeben...
+prompt-move = Neuen Ort relativ zum Vault eingeben...
+error-name-empty = Der neue Name ist leer.
+delete-hints = [󰌑]: Löschen─[Andere]: Abbrechen
+notes-count = { $count ->
+    [one] { $count } Notiz
+   *[other] { $count } Notizen
+}
+column-note = Notiz
+column-reason = Grund
+column-file = Datei
+title-preview = Vorschau
+error-copy-hints = [Ctrl+Y]: Details kopieren
+
+## Auswahlbildschirm
+
+select-filter-title = [F]ilter
+select-filter-hints = Filter leeren [C]
+select-filter-hints-reset = Filter leeren [C]──Filter zurücksetzen [R]
+select-filter-hints-all = [A]lle Bedingungen──Hilfe [H]
+select-filter-hints-any = Beliebige Bedingung [A]──Hilfe [H]
+select-prompt-create = Name der neuen Notiz eingeben...
+select-prompt-copy = Zielordner relativ zum Vault eingeben...
+select-prompt-zip = Pfad des Zip-Archivs relativ zum Vault eingeben...
+select-prompt-anki = Pfad der Anki-Importdatei relativ zum Vault eingeben...
+select-prompt-email = E-Mail-Adressen der Empfänger eingeben...
+select-error-create-empty = Die neue Notiz darf nicht leer sein.
+select-error-move-empty = Das Ziel der Verschiebung ist leer.
+select-error-export-empty = Das Ziel des Exports ist leer.
+select-error-email-empty = Keine Empfänger angegeben.
+select-scope-hints = [O]: Auf Ordner eingrenzen──[Z]: Auf Filter eingrenzen
+select-scope-hints-up = [O]: Auf Ordner eingrenzen──[Z]: Auf Filter eingrenzen──[U]: Zurück
+select-title-global = Globale Statistiken
+select-title-scope = Statistiken des Bereichs
+select-title-local = Lokale Statistiken
+select-searching = Suche läuft… { $percent } %
+select-title-notes = Notizen
+select-hints-navigation = [J]/[]: Runter──[K]/[]: Hoch──[L]/[]/[󰌑]: Öffnen──
+select-hints-actions = [E]: Bearbeiten──[V]: Ansehen──[S]ortieren──[D]: Verteilungen──[P]: Probleme──[B]: Defekte Links──[N]: Veraltete Notizen──[T]ags──[@]: Personen──[I]: Sammlungen──E[x]port──[G]it─[M]: Dateien──[Q]: Beenden
+select-column-name = N[a]me
+select-column-words = [W]örter
+select-column-chars = [C]: Zeichen
+select-column-global-out = Global[O]ut
+select-column-local-out = LokalO[u]t
+select-column-global-in = Global[I]n
+select-column-local-in = LokalI[n]
+select-file-new = Neue Notiz
+select-file-rename = Ausgewählte Notiz umbenennen
+select-file-move = Ausgewählte Notiz verschieben
+select-file-delete = Ausgewählte Notiz löschen
+select-file-statistics = Statistiken als CSV exportieren
+select-file-graph = Linkgraph als DOT exportieren
+select-file-trash = Papierkorb öffnen
+select-file-backup = Vault sichern
+select-file-sync = Mit WebDAV-Server synchronisieren
+select-file-publish = HTML in S3-Bucket veröffentlichen
+select-file-email = Ausgewählte Notiz per Mail senden
+select-file-reveal = Ausgewählte Notiz im Dateimanager zeigen
+select-git-add = Alles hinzufügen
+select-git-commit = Commit
+select-git-push = Push
+select-git-fetch = Fetch
+select-git-pull = Pull
+select-git-history = Verlauf
+select-git-ahead = { $count ->
+    [one] { $count } Commit voraus
+   *[other] { $count } Commits voraus
+}
+select-git-behind = { $count ->
+    [one] { $count } Commit zurück
+   *[other] { $count } Commits zurück
+}
+select-git-up-to-date = Aktuell
+select-git-untracked = Nicht verfolgte Änderungen
+select-git-uncommited = Nicht committete Änderungen
+select-export-html = HTML-Dateien erstellen
+select-export-pdf = PDF-Dateien erstellen
+select-export-print-html = Druckfreundliche HTML-Dateien erstellen
+select-export-print-pdf = Druckfreundliche PDF-Dateien erstellen
+select-export-print = Zu druckfreundlichen Dateien wechseln
+select-export-regular = Zu normalen Dateien wechseln
+select-export-copy = Dateien in Ordner kopieren
+select-export-zip = Dateien zippen
+select-export-anki = Karteikarten für Anki exportieren
+select-export-reveal-slides = reveal.js-Folien erstellen
+select-export-typst-slides = typst-Folien erstellen
+select-export-profile = Mit Profil { $profile } exportieren
+select-sort-name = Nach Name sortieren
+select-sort-words = Nach Wörtern sortieren
+select-sort-chars = Nach Zeichen sortieren
+select-sort-global-out = Nach globalen ausgehenden Links sortieren
+select-sort-local-out = Nach lokalen ausgehenden Links sortieren
+select-sort-global-in = Nach globalen eingehenden Links sortieren
+select-sort-local-in = Nach lokalen eingehenden Links sortieren
+select-sort-broken = Nach defekten Links sortieren
+select-sort-date = Nach Datum sortieren
+select-sort-created = Nach Erstellungsdatum sortieren
+select-sort-modified = Nach letzter Änderung sortieren
+select-sort-visits = Nach Aufrufen sortieren
+select-stale = (veraltet)
+select-sort-reverse = Sortierung umkehren
+select-title-suggestions = Vorgeschlagene Links
+select-suggestions-hints = [Space]: Umschalten──[A]lle──[󰌑]: Einfügen──[Esc]: Verwerfen
+select-title-filter-syntax = Filtersyntax
+select-help-enter-key = / oder F
+select-help-enter = Das Filterfeld betreten.
+select-help-exit-key = 󰌑 oder Esc
+select-help-exit = Das Filterfeld verlassen.
+select-help-tag = Notizen mit dem Tag [tag] zeigen.
+select-help-not-tag = Notizen ohne das Tag [tag] zeigen.
+select-help-linking = Notizen mit Links zu [note] zeigen.
+select-help-linked = Notizen zeigen, auf die [note] verlinkt.
+select-help-not-linking = Notizen ohne Links zu [note] zeigen.
+select-help-not-linked = Notizen zeigen, auf die [note] nicht verlinkt.
+select-help-path = Notizen im Ordner [f] des Vaults zeigen.
+select-help-not-path = Notizen außerhalb des Ordners [f] zeigen.
+select-help-collection = Notizen der Sammlung [c] zeigen.
+select-help-not-collection = Notizen außerhalb der Sammlung [c] zeigen.
+select-help-content = Notizen mit einem Wort wie [w] zeigen.
+select-help-not-content = Notizen ohne ein Wort wie [w] zeigen.
+select-help-table = Tabellen mit einem Wert über [v] in Spalte [c] zeigen.
+select-help-table-operators = Auch =, !=, <, <=, >=, ~ (enthält) oder nur .[c].
+select-help-counts = Notizen mit mehr als [n] Wörtern zeigen. Auch chars, links und backlinks, mit =, !=, <, <=, >=.
+select-help-dates = Notizen zeigen, die nach dem Tag [d] geändert wurden, z.B. 2024-01-31. Auch created und date.
+select-help-negation = Kann in allem oben statt ! verwendet werden.
+select-help-or = Notizen zeigen, die die Bedingungen davor oder danach erfüllen.
+select-help-group = Notizen zeigen, die die Bedingungen in Klammern erfüllen.
+select-help-not-group = Notizen zeigen, die sie nicht erfüllen, wie NOT (...).
+select-help-full-text = Text nach | wird im gesamten Text gesucht.
+select-help-title = Sonstiger Text wird mit dem Titel abgeglichen.
+select-help-semantic = Notizen stattdessen nach Bedeutung suchen (semantische Suche).
+select-title-distributions = Verteilungen
+
+## Notizbildschirm
+
+display-hints = [V]: Ansehen──[P]: Vorschau──[E]: Bearbeiten──[R]: Umbenennen──[M]: Verschieben──[D]: Löschen──[T]ag-Vorschläge──[U]: Tag-Verwendung──[@]: Personen──[S]: Zusammenfassen──[I]: Snippet einfügen──[C]: Prüfen──[G]it-Versionen──[O]: Ordner öffnen
+display-title-statistics = Statistiken
+display-title-backlinks = Backlinks
+display-title-links = Links
+display-title-backlinks-2 = Backlinks 2. Grades
+display-title-links-2 = Links 2. Grades
+display-delete-confirm = Wirklich löschen?
+display-error-no-keywords = Es gibt keine Schlüsselwörter, die als Tags vorgeschlagen werden können.
+display-error-move-empty = Der neue Ort ist leer.
+display-preview-empty = Kein Text.
+display-preview-none = Kein Link ausgewählt.
+display-preview-hints = [Space]: Ausblenden
+display-title-tags = Vorgeschlagene Tags
+display-tags-hints = [Space]: Umschalten──[A]lle──[󰌑]: Hinzufügen──[Esc]: Verwerfen
+display-links-hints = [J]/[]: Runter──[K]/[]: Hoch──[L]/[]/[󰌑]: Öffnen──[H]/[]: Zurück──[F]: Start
+display-links-hints-tables = [Space]: Linkvorschau──[Tab]: Nächste Tabelle──[Shift+Tab]: Vorherige Tabelle
+
+## Sammlungen
+
+collections-title = Sammlungen
+collections-title-notes = Notizen in
+collections-profile = Exportprofil: { $profile }
+collections-hints = [N]eu──[D]: Löschen──[󰌑]: In der Liste zeigen──E[x]port──[Tab]: Liste wechseln──[Esc]: Zurück
+collections-hints-toggle = [Space]: Notiz hinzufügen/entfernen
+collections-notes-hints = [󰌑]: Öffnen──[R]: Aus der Sammlung entfernen
+collections-prompt = Name der neuen Sammlung eingeben...
+collections-empty = Es gibt noch keine Sammlungen.
+collections-missing = { $id } (fehlt)
+collections-error-name = Namen von Sammlungen dürfen weder leer sein noch Leerzeichen enthalten.
+collections-error-exists = Es gibt bereits eine Sammlung namens { $name }.
+
+## Export
+
+export-progress = { $done } / { $total } Notizen
+export-outdated = { $outdated } von { $total } Notizen haben fehlende oder veraltete Dateien
+export-done = Fertig: { $succeeded } erfolgreich, { $failed } fehlgeschlagen
+export-failed = Bisher { $failed } fehlgeschlagen
+export-hints-pending = [A]: Alle erstellen──[O]: Nur veraltete──[Esc]: Abbrechen
+export-hints-finished = [J]/[K]: Scrollen──[Esc]/[󰌑]: Schließen
+export-hints-running = [Esc]: Abbrechen
+export-kind-html = HTML-Dateien werden erstellt
+export-kind-pdf = PDF-Dateien werden erstellt
+export-kind-print-html = Druckfreundliche HTML-Dateien werden erstellt
+export-kind-print-pdf = Druckfreundliche PDF-Dateien werden erstellt
+export-kind-reveal-slides = reveal.js-Folien werden erstellt
+export-kind-typst-slides = typst-Folien werden erstellt
+export-kind-copy = Dateien werden nach { $target } kopiert
+export-kind-zip = Dateien werden nach { $target } gezippt
+export-kind-anki = Karteikarten werden nach { $target } exportiert
+
+## Ladefehler
+
+failures-watcher = Dateiüberwachung
+failures-title = Ladefehler
+failures-count = { $count ->
+    [one] { $count } Fehler
+   *[other] { $count } Fehler
+}
+failures-hints = [J]/[K]: Scrollen──[R]: Erneut versuchen──[Esc]: Zurück
+
+## Verlauf
+
+history-title = Verlauf
+history-hints = [J]/[]: Runter──[K]/[]: Hoch──[󰌑]: Vergleichen──[Esc]: Zurück
+history-column-date = Datum
+history-column-commit = Commit
+history-hint = Einen Commit auswählen, um den damaligen Vault mit seinem aktuellen Stand zu vergleichen.
+history-title-comparison = Vergleich
+history-title-then = Damals: { $commit }
+history-title-now = Jetzt
+history-title-added = Hinzugefügt
+history-title-removed = Entfernt
+history-title-links-added = Neue Links
+history-title-links-removed = Entfernte Links
+
+## Sprungliste
+
+jump-list-title = Sprungliste
+jump-list-hints = [J]/[]: Runter──[K]/[]: Hoch──[󰌑]: Öffnen──[Alt+]/[Alt+]: Zurück/Vor──[Esc]: Schließen
+
+## PDF-Erstellung
+
+pdf-title = PDF von { $name }
+pdf-hints-failed = [J]/[K]: Scrollen──[R]: Erneut versuchen──[Esc]: Schließen
+pdf-hints-building = [Esc]: Abbrechen
+pdf-hints-done = [Esc]: Schließen
+pdf-building = Wird erstellt… ({ $seconds }s)
+pdf-done = Fertig
+
+## Vorschau
+
+preview-prompt-search = In der Notiz suchen...
+preview-matches = { $count ->
+        [0] Keine Treffer
+       *[other] Treffer { $current }/{ $count }──
+    }[N]/[Shift+N]: Nächster/Vorheriger
+
+## Probleme
+
+problems-title-note = Probleme von
+problems-title = Probleme
+problems-count-error = { $count } Fehler
+problems-count-warning = { $count ->
+    [one] { $count } Warnung
+   *[other] { $count } Warnungen
+}
+problems-count-hint = { $count ->
+    [one] { $count } Hinweis
+   *[other] { $count } Hinweise
+}
+problems-hints = [J]/[K]: Scrollen──[Enter]: Öffnen──[F]: Notiz beheben──[A]: Alle beheben──[R]: Erneut prüfen──[Esc]: Zurück
+problems-hints-spelling = [J]/[K]: Scrollen──[Enter]: Öffnen──[F]: Notiz beheben──[A]: Alle beheben──[R]: Erneut prüfen──[I]: Wort ignorieren──[Esc]: Zurück
+severity-error = Fehler
+severity-warning = Warnung
+severity-hint = Hinweis
+problems-fixable = { $message } (behebbar)
+problems-column-level = Stufe
+problems-column-line = Zeile
+problems-column-problem = Problem
+severity-off = Aus
+
+## Semantische Suche
+
+semantic-prompt = Beschreiben, wonach gesucht wird...
+semantic-title = Semantische Suche
+semantic-embedding = Notizen werden eingebettet { $done } / { $total }
+semantic-results-failures = { $count } Ergebnisse, { $failures ->
+        [one] { $failures } Notiz konnte
+       *[other] { $failures } Notizen konnten
+    } nicht eingebettet werden
+semantic-results = { $count } Ergebnisse
+semantic-hints-typing = [Enter]: Suchen──[Esc]: Zurück
+semantic-hints = [J]/[K]: Scrollen──[Enter]: Öffnen──[/]: Neue Suche──[Esc]: Zurück
+semantic-column-similarity = Ähnlichkeit
+semantic-error-not-ready = Die Notizen werden noch eingebettet.
+
+## Snippets
+
+snippet-title-position = { $snippet } einfügen nach...
+snippet-title = Snippet in { $note } einfügen
+snippet-hints = [J]/[K]: Scrollen──[Enter]: Auswählen──[Esc]: Zurück
+snippet-hints-position = [J]/[K]: Scrollen──[Enter]: Einfügen──[Esc]: Zurück
+snippet-end = Ende der Notiz
+
+## Tag-Vorschläge
+
+tag-suggestions-title = Vorgeschlagene Tags für
+tag-suggestions-accepted = { $accepted }/{ $total } angenommen
+tag-suggestions-hints = [J]/[K]: Scrollen──[Space]: Umschalten──[A]: Alle umschalten──[Enter]: Zum Frontmatter hinzufügen──[Esc]: Verwerfen
+tag-suggestions-column-tag = Tag
+tag-suggestions-reason-keyword = Schlüsselwort
+tag-suggestions-reason-linked = { $count ->
+    [one] { $count } verlinkte Notiz
+   *[other] { $count } verlinkte Notizen
+}
+tag-suggestions-reason-both = Schlüsselwort, { $count ->
+    [one] { $count } verlinkte Notiz
+   *[other] { $count } verlinkte Notizen
+}
+
+## Tag-Verwendung
+
+tag-usage-title-tags = Tags
+tag-usage-tags = { $count ->
+    [one] { $count } Tag
+   *[other] { $count } Tags
+}
+tag-usage-title-tag = Verwendung von
+tag-usage-title = Verwendung
+tag-usage-lines = { $count ->
+    [one] { $count } Zeile
+   *[other] { $count } Zeilen
+}
+tag-usage-hints = [J]/[K]: Scrollen──[Tab]: Liste wechseln──[󰌑]: An der Zeile bearbeiten──[O]: Notiz öffnen──[Esc]: Zurück
+tag-usage-column-line = Zeile
+tag-usage-column-source = Quelle
+tag-usage-column-context = Kontext
+
+## Defekte Links
+
+broken-links-title = Defekte Links
+broken-links-count = { $count ->
+    [one] { $count } defekter Link
+   *[other] { $count } defekte Links
+}
+broken-links-hints = [J]/[K]: Scrollen──[󰌑]: An der Zeile bearbeiten──[O]: Notiz öffnen──[Esc]: Zurück
+broken-links-column-line = Zeile
+broken-links-column-link = Link
+
+## Veraltete Notizen
+
+stale-notes-title = Veraltete Notizen
+stale-notes-count = { $count ->
+    [one] { $count } veraltete Notiz
+   *[other] { $count } veraltete Notizen
+}
+stale-notes-hints = [J]/[K]: Scrollen──[󰌑]: Bearbeiten──[O]: Notiz öffnen──[Esc]: Zurück
+stale-notes-column-modified = Geändert
+stale-notes-column-days = Tage / Limit
+stale-notes-column-rule = Regel
+
+## Personen
+
+people-title = Personen
+people-count = { $count ->
+    [one] { $count } Person
+   *[other] { $count } Personen
+}
+people-title-mentions = Erwähnungen von
+people-title-none = Erwähnungen
+people-hints = [J]/[K]: Scrollen──[Tab]: Liste wechseln──[󰌑]: An der Zeile bearbeiten──[O]: Notiz öffnen──[Esc]: Zurück
+
+## Papierkorb
+
+trash-title = Papierkorb
+trash-hints = [J]/[]: Runter──[K]/[]: Hoch──[R]: Wiederherstellen──[D]: Endgültig löschen──[Esc]: Zurück
+trash-column-deleted = Gelöscht
+trash-delete-confirm = Wirklich endgültig löschen?
+
+## Versionen
+
+versions-title = Versionen von { $name }
+versions-hints = [J]/[K]: Auswählen──[R]: Wiederherstellen──[Esc]: Zurück
+versions-hints-scroll = [PgDn]/[PgUp]: Scrollen
+versions-none = Keine committeten Versionen
+versions-title-current = Aktuell
+versions-restore-hints = [󰌑]: Wiederherstellen─[Andere]: Abbrechen
+versions-restore-confirm = Die Notiz wirklich durch diese Version ersetzen?
+
+## Statistiken
+
+stats-notes = Notizen gesamt:
+stats-words = Wörter gesamt:
+stats-tags = Verschiedene Tags:
+stats-chars = Zeichen gesamt:
+stats-links = Links gesamt:
+stats-broken-links = Defekte Links:
+stats-incoming-links = Eingehende Links:
+stats-outgoing-links = Ausgehende Links:
+stats-internal-links = Interne Links:
+stats-note-words = Wörter:
+stats-note-tags = Tags:
+stats-note-chars = Zeichen:
+stats-note-path = Pfad:
+stats-note-keywords = Schlüsselwörter:
+stats-note-backlinks = Backlinks:
+
+## Synchronisierung
+
+sync-idle = Nicht synchronisiert
+sync-running = Synchronisiere...
+sync-done = Synchronisiert { $time }{ $conflicts ->
+        [0] {""}
+        [one] , { $conflicts } Konflikt
+       *[other] , { $conflicts } Konflikte
+    }
+sync-failed = Synchronisierung fehlgeschlagen
+
+## App
+
+loading-config = Konfiguration wird geladen...
+loading-styles = Stile werden geladen...
+loading-managers = Manager & Tracker werden erstellt...
+loading-app = Zustand der App wird initialisiert...
+loading-index = Indizieren...
+loading-index-home = rucola läuft in deinem Home-Verzeichnis. Das kann eine Weile dauern.
+    Starte rucola besser in deinem Notizen-Verzeichnis.
+error-no-snippets = Es sind keine Snippets konfiguriert, sie können in der Tabelle [snippets] der Konfigurationsdatei hinzugefügt werden.
+error-no-bucket = Es ist kein S3-Bucket konfiguriert.
+error-no-webdav = Es ist kein WebDAV-Server konfiguriert.
+error-no-summarizer = Es ist kein Befehl und kein Endpunkt zum Zusammenfassen konfiguriert.
+error-summary-running = Eine andere Notiz wird noch zusammengefasst.
+error-no-mailer = Es ist kein Mail-Befehl und kein SMTP-Server konfiguriert, oder mail_from fehlt.
+error-mail-running = Eine andere Notiz wird noch gesendet.
+error-no-embeddings = Es ist kein Befehl und kein Endpunkt für Embeddings konfiguriert.
+error-no-semantic-search = Dieser Build von rucola enthält keine semantische Suche.
+error-no-export-profile = Es gibt kein Exportprofil `{ $name }`.
+error-empty-export-profile = Das Exportprofil `{ $name }` exportiert keine Notizen.
+unsaved-edits = ● Ungespeicherte Änderungen
+banner-locked = [Nur lesen:] der Vault ist gesperrt durch { $holder }──[Alt+L]: Übernehmen
+banner-summarizing = Zusammenfassung von
+banner-sending = Sende
+banner-failures = { $count ->
+    [one] { $count } Datei konnte nicht indiziert werden
+   *[other] { $count } Dateien konnten nicht indiziert werden
+}
+banner-watcher = Dateiänderungen werden nicht verfolgt
+banner-degraded = [Eingeschränkter Modus:] { $problems }──[!]: Prüfen & erneut versuchen
+banner-tags-hints = ──[Alt+T]: Prüfen──[Alt+X]: Verwerfen
+banner-imports = { $notes ->
+    [one] Kopierte Notiz verweist
+   *[other] Kopierte Notizen verweisen
+} auf { $links ->
+    [one] eine Datei
+   *[other] { $links } Dateien
+} außerhalb des Vaults:
+banner-imports-hints = ──[Alt+I]: In den Vault kopieren & Links anpassen──[Alt+K]: Beibehalten
+
+## Bildschirmleser
+
+key-down = Runter
+key-up = Hoch
+key-left = Links
+key-right = Rechts
+key-enter = Eingabe
+key-or = oder
+announce-select = Liste der Notizen
+announce-note = Notiz { $name }
+announce-selection = { $location }. Ausgewählt: { $selection }
+select-selection = { $name }, { $position } von { $count }
+select-row = , { $words } Wörter, { $chars } Zeichen, { $global_out } global ausgehend, { $local_out } lokal ausgehend, { $global_in } global eingehend, { $local_in } lokal eingehend
+display-selection = { $table }: { $name }
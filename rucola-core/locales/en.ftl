@@ -0,0 +1,458 @@
+# The strings of the user interface of rucola, in the fluent format (https://projectfluent.org).
+# Hotkeys are enclosed in square brackets and highlighted, e.g. `[F]ilter` or `[Esc]: Dismiss`. The keys themselves do not change with the language.
+# Lines of hints are separated by `──`, which continues the border of the surrounding block.
+
+## Shared
+
+hints-close = [C]lose
+prompt-name = Enter note name...
+prompt-rename = Enter new name of note...
+prompt-move = Enter new location relative to vault...
+error-name-empty = New name is empty.
+delete-hints = [󰌑]: Delete─[Other]: Abort
+notes-count = { $count ->
+    [one] { $count } Note
+   *[other] { $count } Notes
+}
+column-note = Note
+column-reason = Reason
+column-file = File
+title-preview = Preview
+error-copy-hints = [Ctrl+Y]: Copy details
+
+## Select screen
+
+select-filter-title = [F]ilter
+select-filter-hints = [C]lear filter
+select-filter-hints-reset = [C]lear filter──[R]eset filter
+select-filter-hints-all = [A]ll Conditions──[H]elp
+select-filter-hints-any = [A]ny Conditions──[H]elp
+select-prompt-create = Enter name of new note...
+select-prompt-copy = Enter target folder relative to vault...
+select-prompt-zip = Enter path of zip archive relative to vault...
+select-prompt-anki = Enter path of Anki import file relative to vault...
+select-prompt-email = Enter email addresses to send the note to...
+select-error-create-empty = New note may not be empty.
+select-error-move-empty = Move target is empty.
+select-error-export-empty = Export target is empty.
+select-error-email-empty = No recipients given.
+select-scope-hints = [O]: Scope to folder──[Z]: Scope to filter
+select-scope-hints-up = [O]: Scope to folder──[Z]: Scope to filter──[U]p
+select-title-global = Global Statistics
+select-title-scope = Scope Statistics
+select-title-local = Local Statistics
+select-searching = Searching… { $percent }%
+select-title-notes = Notes
+select-hints-navigation = [J]/[]: Down──[K]/[]: Up──[L]/[]/[󰌑]: Open──
+select-hints-actions = [E]dit──[V]iew──[S]orting──[D]istributions──[P]roblems──[B]roken Links──Stale [N]otes──[T]ags──[@]: People──Collect[i]ons──E[x]port──[G]it─[M]anage Files──[Q]uit
+select-column-name = N[a]me
+select-column-words = [W]ords
+select-column-chars = [C]hars
+select-column-global-out = Global[O]ut
+select-column-local-out = LocalO[u]t
+select-column-global-in = Global[I]n
+select-column-local-in = LocalI[n]
+select-file-new = New note
+select-file-rename = Rename selected note
+select-file-move = Move selected note
+select-file-delete = Delete selected note
+select-file-statistics = Export statistics to CSV
+select-file-graph = Export link graph to DOT
+select-file-trash = Open trash
+select-file-backup = Back up vault
+select-file-sync = Sync with WebDAV server
+select-file-publish = Publish HTML to S3 bucket
+select-file-email = Send selected note by mail
+select-file-reveal = Show selected note in file manager
+select-git-add = Add All
+select-git-commit = Commit
+select-git-push = Push
+select-git-fetch = Fetch
+select-git-pull = Pull
+select-git-history = History
+select-git-ahead = { $count ->
+    [one] { $count } commit ahead
+   *[other] { $count } commits ahead
+}
+select-git-behind = { $count ->
+    [one] { $count } commit behind
+   *[other] { $count } commits behind
+}
+select-git-up-to-date = Up to Date
+select-git-untracked = Untracked changes
+select-git-uncommited = Uncommited changes
+select-export-html = Build HTML files
+select-export-pdf = Build PDF files
+select-export-print-html = Build print-friendly HTML files
+select-export-print-pdf = Build print-friendly PDF files
+select-export-print = Switch to print-friendly files
+select-export-regular = Switch to regular files
+select-export-copy = Copy files to folder
+select-export-zip = Zip files
+select-export-anki = Export flashcards for Anki
+select-export-reveal-slides = Build reveal.js slides
+select-export-typst-slides = Build typst slides
+select-export-profile = Export with profile { $profile }
+select-sort-name = Sort by name
+select-sort-words = Sort by words
+select-sort-chars = Sort by characters
+select-sort-global-out = Sort by global outlinks
+select-sort-local-out = Sort by local outlinks
+select-sort-global-in = Sort by global inlinks
+select-sort-local-in = Sort by local inlinks
+select-sort-broken = Sort by broken links
+select-sort-date = Sort by date
+select-sort-created = Sort by creation date
+select-sort-modified = Sort by last modification
+select-sort-visits = Sort by visits
+select-stale = (stale)
+select-sort-reverse = Reverse sorting
+select-title-suggestions = Suggested Links
+select-suggestions-hints = [Space]: Toggle──[A]ll──[󰌑]: Insert──[Esc]: Dismiss
+select-title-filter-syntax = Filter Syntax
+select-help-enter-key = / or F
+select-help-enter = Enter the filter text box.
+select-help-exit-key = 󰌑 or Esc
+select-help-exit = Exit the filter text box
+select-help-tag = Show notes with tag [tag].
+select-help-not-tag = Show notes without tag [tag].
+select-help-linking = Show notes linking to [note].
+select-help-linked = Show notes linked to from [note].
+select-help-not-linking = Show notes not linking to [note].
+select-help-not-linked = Show notes not linked to from [note].
+select-help-path = Show notes in the folder [f] of the vault.
+select-help-not-path = Show notes outside of the folder [f].
+select-help-collection = Show notes in the collection [c].
+select-help-not-collection = Show notes outside of the collection [c].
+select-help-content = Show notes containing a word like [w].
+select-help-not-content = Show notes not containing a word like [w].
+select-help-table = Show tables with a value above [v] in column [c].
+select-help-table-operators = Also =, !=, <, <=, >=, ~ (contains) or only .[c].
+select-help-counts = Show notes with more than [n] words. Also chars, links and backlinks, with =, !=, <, <=, >=.
+select-help-dates = Show notes modified after the day [d], e.g. 2024-01-31. Also created and date.
+select-help-negation = Can be used instead of ! in all of the above.
+select-help-or = Show notes matching the conditions before or after.
+select-help-group = Show notes matching the conditions in parentheses.
+select-help-not-group = Show notes not matching them, like NOT (...).
+select-help-full-text = All text after | will be searched in the full text.
+select-help-title = All other text will be matched against the title.
+select-help-semantic = Search notes by meaning instead (semantic search).
+select-title-distributions = Distributions
+
+## Display screen
+
+display-hints = [V]iew──[P]review──[E]dit──[R]ename──[M]ove──[D]elete──[T]ag Suggestions──[U]sage of Tags──[@]: People──[S]ummarize──[I]nsert Snippet──[C]heck──[G]it Versions──[O]pen Folder
+display-title-statistics = Statistics
+display-title-backlinks = Backlinks
+display-title-links = Links
+display-title-backlinks-2 = Level 2 Backlinks
+display-title-links-2 = Level 2 Links
+display-delete-confirm = Are you sure you want to delete?
+display-error-no-keywords = There are no keywords to suggest as tags.
+display-error-move-empty = Move location is empty.
+display-preview-empty = No text.
+display-preview-none = No link selected.
+display-preview-hints = [Space]: Hide
+display-title-tags = Suggested Tags
+display-tags-hints = [Space]: Toggle──[A]ll──[󰌑]: Add──[Esc]: Dismiss
+display-links-hints = [J]/[]: Down──[K]/[]: Up──[L]/[]/[󰌑]: Open──[H]/[]: Back──[F]: Home
+display-links-hints-tables = [Space]: Preview Link──[Tab]: Next Table──[Shift+Tab]: Previous Table
+
+## Collections screen
+
+collections-title = Collections
+collections-title-notes = Notes in
+collections-profile = Export profile: { $profile }
+collections-hints = [N]ew──[D]elete──[󰌑]: Show in List──E[x]port──[Tab]: Switch List──[Esc]: Back
+collections-hints-toggle = [Space]: Add/Remove Note
+collections-notes-hints = [󰌑]: Open──[R]emove from Collection
+collections-prompt = Enter name of new collection...
+collections-empty = There are no collections yet.
+collections-missing = { $id } (missing)
+collections-error-name = Collection names may neither be empty nor contain spaces.
+collections-error-exists = There already is a collection named { $name }.
+
+## Export screen
+
+export-progress = { $done } / { $total } Notes
+export-outdated = { $outdated } of { $total } notes have missing or outdated files
+export-done = Done: { $succeeded } succeeded, { $failed } failed
+export-failed = { $failed } failed so far
+export-hints-pending = [A]: Build all──[O]: Only outdated──[Esc]: Cancel
+export-hints-finished = [J]/[K]: Scroll──[Esc]/[󰌑]: Close
+export-hints-running = [Esc]: Cancel
+export-kind-html = Building HTML files
+export-kind-pdf = Building PDF files
+export-kind-print-html = Building print-friendly HTML files
+export-kind-print-pdf = Building print-friendly PDF files
+export-kind-reveal-slides = Building reveal.js slides
+export-kind-typst-slides = Building typst slides
+export-kind-copy = Copying files to { $target }
+export-kind-zip = Zipping files to { $target }
+export-kind-anki = Exporting flashcards to { $target }
+
+## Failures screen
+
+failures-watcher = File watcher
+failures-title = Loading failures
+failures-count = { $count ->
+    [one] { $count } Failure
+   *[other] { $count } Failures
+}
+failures-hints = [J]/[K]: Scroll──[R]: Retry──[Esc]: Back
+
+## History screen
+
+history-title = History
+history-hints = [J]/[]: Down──[K]/[]: Up──[󰌑]: Compare──[Esc]: Back
+history-column-date = Date
+history-column-commit = Commit
+history-hint = Select a commit to compare the vault at that point with its current state.
+history-title-comparison = Comparison
+history-title-then = Then: { $commit }
+history-title-now = Now
+history-title-added = Added
+history-title-removed = Removed
+history-title-links-added = Added links
+history-title-links-removed = Removed links
+
+## Jump list screen
+
+jump-list-title = Jump List
+jump-list-hints = [J]/[]: Down──[K]/[]: Up──[󰌑]: Open──[Alt+]/[Alt+]: Back/Forward──[Esc]: Close
+
+## PDF build screen
+
+pdf-title = PDF of { $name }
+pdf-hints-failed = [J]/[K]: Scroll──[R]: Retry──[Esc]: Close
+pdf-hints-building = [Esc]: Cancel
+pdf-hints-done = [Esc]: Close
+pdf-building = Building… ({ $seconds }s)
+pdf-done = Done
+
+## Preview screen
+
+preview-prompt-search = Search in note...
+preview-matches = { $count ->
+        [0] No matches
+       *[other] Match { $current }/{ $count }──
+    }[N]/[Shift+N]: Next/Previous
+
+## Problems screen
+
+problems-title-note = Problems of
+problems-title = Problems
+problems-count-error = { $count ->
+    [one] { $count } Error
+   *[other] { $count } Errors
+}
+problems-count-warning = { $count ->
+    [one] { $count } Warning
+   *[other] { $count } Warnings
+}
+problems-count-hint = { $count ->
+    [one] { $count } Hint
+   *[other] { $count } Hints
+}
+problems-hints = [J]/[K]: Scroll──[Enter]: Open──[F]: Fix note──[A]: Fix all──[R]: Recheck──[Esc]: Back
+problems-hints-spelling = [J]/[K]: Scroll──[Enter]: Open──[F]: Fix note──[A]: Fix all──[R]: Recheck──[I]: Ignore word──[Esc]: Back
+severity-error = Error
+severity-warning = Warning
+severity-hint = Hint
+problems-fixable = { $message } (fixable)
+problems-column-level = Level
+problems-column-line = Line
+problems-column-problem = Problem
+severity-off = Off
+
+## Semantic search screen
+
+semantic-prompt = Describe what you are looking for...
+semantic-title = Semantic search
+semantic-embedding = Embedding notes { $done } / { $total }
+semantic-results-failures = { $count } Results, { $failures ->
+        [one] { $failures } note
+       *[other] { $failures } notes
+    } could not be embedded
+semantic-results = { $count } Results
+semantic-hints-typing = [Enter]: Search──[Esc]: Back
+semantic-hints = [J]/[K]: Scroll──[Enter]: Open──[/]: New query──[Esc]: Back
+semantic-column-similarity = Similarity
+semantic-error-not-ready = The notes are still being embedded.
+
+## Snippet screen
+
+snippet-title-position = Insert { $snippet } after...
+snippet-title = Insert snippet into { $note }
+snippet-hints = [J]/[K]: Scroll──[Enter]: Choose──[Esc]: Back
+snippet-hints-position = [J]/[K]: Scroll──[Enter]: Insert──[Esc]: Back
+snippet-end = End of note
+
+## Tag suggestions screen
+
+tag-suggestions-title = Suggested tags for
+tag-suggestions-accepted = { $accepted }/{ $total } Accepted
+tag-suggestions-hints = [J]/[K]: Scroll──[Space]: Toggle──[A]: Toggle all──[Enter]: Add to frontmatter──[Esc]: Dismiss
+tag-suggestions-column-tag = Tag
+tag-suggestions-reason-keyword = keyword
+tag-suggestions-reason-linked = { $count ->
+    [one] { $count } linked note
+   *[other] { $count } linked notes
+}
+tag-suggestions-reason-both = keyword, { $count ->
+    [one] { $count } linked note
+   *[other] { $count } linked notes
+}
+
+## Tag usage screen
+
+tag-usage-title-tags = Tags
+tag-usage-tags = { $count ->
+    [one] { $count } Tag
+   *[other] { $count } Tags
+}
+tag-usage-title-tag = Usage of
+tag-usage-title = Usage
+tag-usage-lines = { $count ->
+    [one] { $count } Line
+   *[other] { $count } Lines
+}
+tag-usage-hints = [J]/[K]: Scroll──[Tab]: Switch List──[󰌑]: Edit at Line──[O]: Open Note──[Esc]: Back
+tag-usage-column-line = Line
+tag-usage-column-source = Source
+tag-usage-column-context = Context
+
+## Broken links screen
+
+broken-links-title = Broken Links
+broken-links-count = { $count ->
+    [one] { $count } broken link
+   *[other] { $count } broken links
+}
+broken-links-hints = [J]/[K]: Scroll──[󰌑]: Edit at Line──[O]: Open Note──[Esc]: Back
+broken-links-column-line = Line
+broken-links-column-link = Link
+
+## Stale notes screen
+
+stale-notes-title = Stale Notes
+stale-notes-count = { $count ->
+    [one] { $count } stale note
+   *[other] { $count } stale notes
+}
+stale-notes-hints = [J]/[K]: Scroll──[󰌑]: Edit──[O]: Open Note──[Esc]: Back
+stale-notes-column-modified = Modified
+stale-notes-column-days = Days / Limit
+stale-notes-column-rule = Rule
+
+## People screen
+
+people-title = People
+people-count = { $count ->
+    [one] { $count } Person
+   *[other] { $count } People
+}
+people-title-mentions = Mentions of
+people-title-none = Mentions
+people-hints = [J]/[K]: Scroll──[Tab]: Switch List──[󰌑]: Edit at Line──[O]: Open Note──[Esc]: Back
+
+## Trash screen
+
+trash-title = Trash
+trash-hints = [J]/[]: Down──[K]/[]: Up──[R]: Restore──[D]: Delete permanently──[Esc]: Back
+trash-column-deleted = Deleted
+trash-delete-confirm = Are you sure you want to delete permanently?
+
+## Versions screen
+
+versions-title = Versions of { $name }
+versions-hints = [J]/[K]: Select──[R]: Restore──[Esc]: Back
+versions-hints-scroll = [PgDn]/[PgUp]: Scroll
+versions-none = No committed versions
+versions-title-current = Current
+versions-restore-hints = [󰌑]: Restore─[Other]: Abort
+versions-restore-confirm = Are you sure you want to replace the note with this version?
+
+## Statistics
+
+stats-notes = Total notes:
+stats-words = Total words:
+stats-tags = Total unique tags:
+stats-chars = Total characters:
+stats-links = Total links:
+stats-broken-links = Broken links:
+stats-incoming-links = Incoming links:
+stats-outgoing-links = Outgoing links:
+stats-internal-links = Internal links:
+stats-note-words = Words:
+stats-note-tags = Tags:
+stats-note-chars = Chars:
+stats-note-path = Path:
+stats-note-keywords = Keywords:
+stats-note-backlinks = Backlinks:
+
+## Synchronization
+
+sync-idle = Not synced
+sync-running = Syncing...
+sync-done = Synced { $time }{ $conflicts ->
+        [0] {""}
+        [one] , { $conflicts } conflict
+       *[other] , { $conflicts } conflicts
+    }
+sync-failed = Sync failed
+
+## App
+
+loading-config = Loading configuration...
+loading-styles = Loading styles...
+loading-managers = Creating managers & trackers...
+loading-app = Initializing app state...
+loading-index = Indexing...
+loading-index-home = You are running rucola in your home directory. This might take a while.
+    Consider running in your notes directory instead.
+error-no-snippets = No snippets are configured, add them to the [snippets] table of the config file.
+error-no-bucket = No S3 bucket configured.
+error-no-webdav = No WebDAV server configured.
+error-no-summarizer = No summary command or endpoint configured.
+error-summary-running = Another note is still being summarized.
+error-no-mailer = No mail command or SMTP server configured, or no mail_from set.
+error-mail-running = Another note is still being sent.
+error-no-embeddings = No embedding command or endpoint configured.
+error-no-semantic-search = This build of rucola does not include semantic search.
+error-no-export-profile = There is no export profile `{ $name }`.
+error-empty-export-profile = The export profile `{ $name }` exports no notes.
+unsaved-edits = ● Unsaved changes
+banner-locked = [Read-only:] the vault is locked by { $holder }──[Alt+L]: Take over
+banner-summarizing = Summarizing
+banner-sending = Sending
+banner-failures = { $count ->
+    [one] { $count } file could not be indexed
+   *[other] { $count } files could not be indexed
+}
+banner-watcher = file changes are not tracked
+banner-degraded = [Degraded mode:] { $problems }──[!]: Review & retry
+banner-tags-hints = ──[Alt+T]: Review──[Alt+X]: Dismiss
+banner-imports = { $notes ->
+    [one] Copied note links
+   *[other] Copied notes link
+} { $links ->
+    [one] a file
+   *[other] { $links } files
+} outside the vault:
+banner-imports-hints = ──[Alt+I]: Copy into vault & rewrite links──[Alt+K]: Keep
+
+## Screen reader
+
+key-down = Down
+key-up = Up
+key-left = Left
+key-right = Right
+key-enter = Enter
+key-or = or
+announce-select = List of notes
+announce-note = Note { $name }
+announce-selection = { $location }. Selected: { $selection }
+select-selection = { $name }, { $position } of { $count }
+select-row = , { $words } words, { $chars } characters, { $global_out } global out, { $local_out } local out, { $global_in } global in, { $local_in } local in
+display-selection = { $table }: { $name }
@@ -0,0 +1,583 @@
+use std::{collections::HashMap, path};
+
+use crate::{data, error, io, styles};
+
+/// Validation of config files.
+mod check;
+pub use check::check_files;
+
+/// Prefix of environment variables that override options, e.g. `RUCOLA_VAULT_PATH` for `vault_path`.
+const ENV_PREFIX: &str = "RUCOLA_";
+
+/// The file format a viewer expects.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum ViewerType {
+    /// A viewer that displays HTML files and thus needs to be given paths to an HTML file.
+    #[default]
+    Html,
+    /// A viewer that displays markdown files and thus needs to be given paths to an markdown file.
+    Markdown,
+    /// A viewer that displays PDF files. The PDF file is built before the viewer is opened.
+    Pdf,
+}
+
+/// Describes when to show a which stats area.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, Default)]
+pub enum StatsShow {
+    // Always shows both stats
+    #[default]
+    Both,
+    // Shows local stats when filtering and nothing otherwise
+    Relevant,
+    // Always shows only local stats
+    Local,
+}
+
+/// The command line arguments the config depends on, as given to [`Config::load`].
+#[derive(Debug, Clone, Default)]
+pub struct ConfigArgs {
+    /// Target vault folder, overriding the path set in the config file.
+    pub target_folder: Option<String>,
+    /// The file containing the styles to use for the UI, overriding the theme set in the config file.
+    pub style: Option<String>,
+    /// Options to override, as `key=value`.
+    pub set: Vec<String>,
+}
+
+/// Groups data passed by the user in the config file.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct Config {
+    /// Path to the vault to index.
+    pub vault_path: Option<path::PathBuf>,
+    /// File types to consider notes
+    /// See the [default list](https://docs.rs/ignore/latest/src/ignore/default_types.rs.html) of the ignore crate for possible options.
+    /// The "all" option matches all files.
+    pub file_types: Vec<String>,
+    /// Default file ending for newly created notes
+    pub default_extension: String,
+    /// Selected theme
+    pub theme: String,
+    /// The language of the user interface, such as `de`. Uses the language of the system if unset.
+    pub language: Option<String>,
+    /// Wether the UI is simplified for screen readers, without borders and with the selection announced in the first line.
+    pub screen_reader: bool,
+    /// When to show the global stats area
+    pub stats_show: StatsShow,
+    /// Filter applied to the notes on the select screen when rucola starts.
+    pub default_filter: Option<String>,
+    /// Sorting mode of the notes on the select screen when rucola starts.
+    pub default_sorting: data::SortingMode,
+    /// Wether to sort the notes ascendingly when rucola starts.
+    pub default_sorting_ascending: bool,
+    /// The BCP 47 locale whose rules are used to sort notes by name. Requires the `collation` feature, uses the root collation if unset.
+    pub sort_locale: Option<String>,
+    /// Tags of notes that are hidden on the select screen unless the filter asks for them.
+    pub excluded_tags: Vec<String>,
+    /// Maximum width of the text column in the reading mode.
+    pub reading_width: u16,
+    /// The editor to use for notes.
+    pub editor: Option<Vec<String>>,
+    /// Main viewer to inspect rendered notes.
+    pub viewer: Option<Vec<String>>,
+    /// Preferred file type of the main viewer.
+    pub viewer_type: Option<ViewerType>,
+    /// Alternative viewer to inspect rendered notes.
+    pub secondary_viewer: Option<Vec<String>>,
+    /// Preferred file type of the alternative viewer.
+    pub secondary_viewer_type: Option<ViewerType>,
+    /// The file manager to reveal notes in, the system's default one if none.
+    pub file_manager: Option<Vec<String>>,
+    /// When set to true, HTML files are mass-created on start and continuously kept up to date with file changes instead of being created on-demand.
+    pub enable_html: bool,
+    /// Path to .css file to style htmls with.
+    pub css: Option<String>,
+    /// Path to .css file to style print-friendly HTML and PDF files with, instead of the built-in print stylesheet.
+    pub print_css: Option<String>,
+    /// String to prepend to all generated html documents (e.g. for MathJax)
+    pub html_prepend: Option<String>,
+    /// Wether to write a search index with HTML exports and add a search box to the HTML files.
+    pub html_search: bool,
+    /// Wether or not to insert a MathJax preamble in notes containing math code.
+    pub katex: bool,
+    /// Command to convert a note into a PDF file.
+    pub pdf_command: Vec<String>,
+    /// Argument added to the PDF command for notes specifying a template, `%t` is replaced by the template.
+    pub pdf_template_arg: Option<String>,
+    /// Argument added to the PDF command for print-friendly exports, `%c` is replaced by the path of the print stylesheet.
+    pub pdf_print_arg: Option<String>,
+    /// Command to compile typst slide decks into PDF files.
+    pub slides_command: Vec<String>,
+    /// Number of notes above which building their HTML or PDF files at once has to be confirmed.
+    pub export_confirm_threshold: usize,
+    /// Remembered ways to export a collection or the notes matching a filter, by name.
+    pub export_profiles: HashMap<String, io::ExportProfile>,
+    /// The tag of notes whose flashcards are exported for Anki, including its subtags.
+    pub flashcard_tag: String,
+    /// Maximum number of PDF commands running at once.
+    pub build_jobs: usize,
+    /// Number of seconds after which a PDF command is stopped. Never stop it if unset.
+    pub build_timeout: Option<u64>,
+    /// A list of strings to replace in math mode to mimic latex commands
+    pub math_replacements: HashMap<String, String>,
+    /// Minimum number of characters after the `#` for an inline tag to be recognized.
+    pub tag_min_length: usize,
+    /// If set, inline tags have to fully match this regex to be recognized.
+    pub tag_regex: Option<String>,
+    /// Wether to ignore inline tags within code blocks and inline code.
+    pub tags_ignore_code: bool,
+    /// Inline tags that are never recognized.
+    pub tag_blacklist: Vec<String>,
+    /// Wether notes are tagged with the path of the folder they are in, e.g. `#projects/alpha`.
+    pub folder_tags: bool,
+    /// Icons of notes with the given tags that do not specify an `icon` in their frontmatter.
+    pub tag_icons: HashMap<String, String>,
+    /// Colors of notes with the given tags that do not specify a `color` in their frontmatter.
+    pub tag_colors: HashMap<String, String>,
+    /// Styles of tags, by exact tag or by a prefix ending in a star such as `#project/*`.
+    pub tag_styles: HashMap<String, styles::TagStyle>,
+    /// Wether to suggest tags used elsewhere in the vault for notes that changed.
+    pub suggest_tags: bool,
+    /// Wether to offer rewriting links of notes copied into the vault that refer to files outside of it.
+    pub suggest_imports: bool,
+    /// The folder within the vault files referenced by copied notes are copied to.
+    pub import_assets_folder: String,
+    /// Number of days after which deleted notes are permanently removed from the trash. Never purge if unset.
+    pub trash_purge_days: Option<u64>,
+    /// Formats to try when parsing the `date` or `created` field of the frontmatter.
+    pub date_formats: Vec<String>,
+    /// Wether to recognize creation dates at the start of file names.
+    pub dates_from_filenames: bool,
+    /// The frontmatter field listing the notes a note depends on, which are to be read before it.
+    pub dependency_key: String,
+    /// The IANA name of the timezone to use for timestamps rucola writes. Uses the system timezone if unset.
+    pub timezone: Option<String>,
+    /// Format (in chrono syntax) of timestamps rucola writes.
+    pub timestamp_format: String,
+    /// Wether to update the `modified` field in the frontmatter of notes whenever rucola changes their content.
+    pub update_modified: bool,
+    /// Wether to add a `created` field to the frontmatter of notes rucola creates or changes, if there is none.
+    pub update_created: bool,
+    /// How carefully files written by rucola are flushed to the disk.
+    pub write_durability: io::Durability,
+    /// Milliseconds a note edited from the UI has to stay unchanged before it is written. Edits are written immediately if zero.
+    pub autosave_delay: u64,
+    /// Folder backups of the vault are written to. Relative paths are relative to the vault.
+    pub backup_location: Option<String>,
+    /// Archive format of backups.
+    pub backup_format: io::BackupFormat,
+    /// Wether to leave the generated HTML and PDF files out of backups.
+    pub backup_exclude_generated: bool,
+    /// URL of a folder on a WebDAV server to synchronize the vault with.
+    pub sync_url: Option<String>,
+    /// User name to authenticate with at the WebDAV server. The password is read from the `RUCOLA_SYNC_PASSWORD` environment variable.
+    pub sync_user: Option<String>,
+    /// Wether to synchronize the vault when rucola starts.
+    pub sync_on_start: bool,
+    /// URL of an S3-compatible object storage to publish the HTML files to.
+    pub publish_endpoint: Option<String>,
+    /// The bucket to publish the HTML files to.
+    pub publish_bucket: Option<String>,
+    /// The region of the bucket, used to sign requests.
+    pub publish_region: String,
+    /// Prefix of the keys the HTML files are published at.
+    pub publish_prefix: Option<String>,
+    /// The sender of notes sent by mail, such as `Jane Doe <jane@example.com>`.
+    pub mail_from: Option<String>,
+    /// Sendmail-style command to send notes by mail with, receiving the message on stdin.
+    pub mail_command: Option<Vec<String>>,
+    /// Host name of the SMTP server to send notes by mail through.
+    pub smtp_server: Option<String>,
+    /// Port of the SMTP server.
+    pub smtp_port: u16,
+    /// How the connection to the SMTP server is secured.
+    pub smtp_security: io::SmtpSecurity,
+    /// User name to authenticate with at the SMTP server. The password is read from the `RUCOLA_SMTP_PASSWORD` environment variable.
+    pub smtp_user: Option<String>,
+    /// Wether to send the credentials to an SMTP server without encryption, if `smtp_security` is `None`.
+    pub smtp_allow_insecure_auth: bool,
+    /// Command to summarize notes with, receiving the prompt and the note on stdin.
+    pub summary_command: Option<Vec<String>>,
+    /// URL of an HTTP endpoint following the generate API of ollama to summarize notes with.
+    pub summary_endpoint: Option<String>,
+    /// The model to request summaries from at the endpoint.
+    pub summary_model: String,
+    /// Instruction placed before the content of notes when requesting a summary.
+    pub summary_prompt: String,
+    /// The severities of the lint rules and their options.
+    pub lint: data::LintConfig,
+    /// Snippets that can be inserted into notes, by name.
+    pub snippets: HashMap<String, String>,
+    /// Notes created regularly from snippets, by name.
+    pub recurring_notes: HashMap<String, io::RecurringNote>,
+    /// Rules flagging notes not modified for too long, by name.
+    pub stale_notes: HashMap<String, data::StaleRule>,
+    /// Command to create embeddings for semantic search with, receiving a text on stdin.
+    pub embedding_command: Option<Vec<String>>,
+    /// URL of an HTTP endpoint following the embed API of ollama to create embeddings for semantic search with.
+    pub embedding_endpoint: Option<String>,
+    /// The model to request embeddings from at the endpoint.
+    pub embedding_model: String,
+    /// Command to check spelling with, receiving words on stdin and writing the misspelled ones to stdout.
+    pub spellcheck_command: Option<Vec<String>>,
+    /// Path to a hunspell dictionary (`.dic`) to check spelling with, its affix file (`.aff`) next to it.
+    pub spellcheck_dictionary: Option<path::PathBuf>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            vault_path: None,
+            file_types: vec![String::from("markdown")],
+            default_extension: String::from("md"),
+            theme: "default_dark".to_string(),
+            language: None,
+            screen_reader: false,
+            stats_show: StatsShow::Both,
+            default_filter: None,
+            default_sorting: data::SortingMode::Name,
+            default_sorting_ascending: true,
+            sort_locale: None,
+            excluded_tags: Vec::new(),
+            reading_width: 80,
+            editor: None,
+            viewer_type: Some(ViewerType::Html),
+            viewer: Some(vec![String::from("firefox"), String::from("%p")]),
+            secondary_viewer_type: None,
+            secondary_viewer: None,
+            file_manager: None,
+            enable_html: true,
+            css: Some("default_dark".to_string()),
+            print_css: None,
+            html_prepend: None,
+            html_search: true,
+            katex: true,
+            pdf_command: vec![
+                String::from("pandoc"),
+                String::from("%p"),
+                String::from("-o"),
+                String::from("%o"),
+            ],
+            pdf_template_arg: Some(String::from("--template=%t")),
+            pdf_print_arg: None,
+            slides_command: vec![
+                String::from("typst"),
+                String::from("compile"),
+                String::from("--root"),
+                String::from("%v"),
+                String::from("%p"),
+                String::from("%o"),
+            ],
+            export_confirm_threshold: 100,
+            export_profiles: HashMap::new(),
+            flashcard_tag: String::from("#flashcards"),
+            build_jobs: 4,
+            build_timeout: Some(300),
+            math_replacements: HashMap::from_iter(vec![(
+                "\\field".to_string(),
+                "\\mathbb".to_string(),
+            )]),
+            tag_min_length: 0,
+            tag_regex: None,
+            tags_ignore_code: true,
+            tag_blacklist: Vec::new(),
+            folder_tags: false,
+            tag_icons: HashMap::new(),
+            tag_colors: HashMap::new(),
+            tag_styles: HashMap::new(),
+            suggest_tags: true,
+            suggest_imports: true,
+            import_assets_folder: String::from("assets"),
+            trash_purge_days: Some(30),
+            date_formats: vec![
+                String::from("%Y-%m-%d"),
+                String::from("%Y-%m-%d %H:%M"),
+                String::from("%Y-%m-%d %H:%M:%S"),
+                String::from("%d.%m.%Y"),
+            ],
+            dates_from_filenames: true,
+            dependency_key: String::from("depends-on"),
+            timezone: None,
+            timestamp_format: String::from("%Y-%m-%dT%H:%M:%S%:z"),
+            update_modified: false,
+            update_created: false,
+            write_durability: io::Durability::File,
+            autosave_delay: 1000,
+            backup_location: None,
+            backup_format: io::BackupFormat::Zip,
+            backup_exclude_generated: true,
+            sync_url: None,
+            sync_user: None,
+            sync_on_start: false,
+            publish_endpoint: None,
+            publish_bucket: None,
+            publish_region: String::from("us-east-1"),
+            publish_prefix: None,
+            mail_from: None,
+            mail_command: None,
+            smtp_server: None,
+            smtp_port: 587,
+            smtp_security: io::SmtpSecurity::StartTls,
+            smtp_user: None,
+            smtp_allow_insecure_auth: false,
+            summary_command: None,
+            summary_endpoint: None,
+            summary_model: String::from("llama3.2"),
+            summary_prompt: String::from(
+                "Summarize the following note in two or three sentences. Reply with the summary only.",
+            ),
+            lint: Default::default(),
+            snippets: HashMap::new(),
+            recurring_notes: HashMap::new(),
+            stale_notes: HashMap::new(),
+            embedding_command: None,
+            embedding_endpoint: None,
+            embedding_model: String::from("nomic-embed-text"),
+            spellcheck_command: None,
+            spellcheck_dictionary: None,
+        }
+    }
+}
+
+impl Config {
+    /// Creates a config file and vault path by combining the passed cli arguments with the loaded file from comfy.
+    /// Options are taken from the following layers, each overriding the ones before:
+    ///  1. The defaults.
+    ///  2. The config file.
+    ///  3. A `.rucola.toml` file in the vault folder.
+    ///  4. Environment variables named like the options with a `RUCOLA_` prefix, e.g. `RUCOLA_VAULT_PATH`.
+    ///  5. `--set key=value` arguments.
+    ///  6. Dedicated command line arguments, i.e. the target folder and `--style`.
+    pub fn load(mut args: ConfigArgs) -> error::Result<(Self, path::PathBuf)> {
+        // === Step 0: Collect overrides from the environment and command line ===
+        let mut overrides = Self::overrides(
+            std::env::vars_os().filter_map(|(key, value)| {
+                Some((key.into_string().ok()?, value.into_string().ok()?))
+            }),
+            &args.set,
+        )?;
+        if let Some(style) = args.style.take() {
+            overrides.insert(String::from("theme"), toml::Value::String(style));
+        }
+
+        // === Step 1: Load config file ===
+        let mut config: Config =
+            confy::load("rucola", "config").map_err(
+                |e| match confy::get_configuration_file_path("rucola", "config") {
+                    Ok(path) => error::RucolaError::from(e).in_file(&path, None),
+                    Err(_) => e.into(),
+                },
+            )?;
+
+        // === Step 2: Fix vault path ===
+
+        // The vault path has to be known to find the vault config, so apply its override right away.
+        if let Some(vault_path) = overrides.remove("vault_path") {
+            config.vault_path = Some(path::PathBuf::from(match vault_path {
+                toml::Value::String(vault_path) => vault_path,
+                other => other.to_string(),
+            }));
+        }
+
+        // Get current dir & extract vault path.
+        let mut full_vault_path = Self::vault_path(std::env::current_dir()?, args, &mut config);
+
+        // make sure path is absolute
+        if !full_vault_path.is_absolute() {
+            full_vault_path = std::env::current_dir()?.join(full_vault_path);
+        }
+
+        // === Step 3: Apply vault config file ===
+        let vault_config_path = full_vault_path.join(".rucola.toml");
+        if vault_config_path.exists() {
+            let vault_config = std::fs::read_to_string(&vault_config_path)?;
+            config = config.with_vault_config(&vault_config).map_err(|e| {
+                // Find the line of syntax errors.
+                let line = match &e {
+                    error::RucolaError::VaultConfigLoad(toml_error) => toml_error
+                        .span()
+                        .map(|span| vault_config[..span.start].matches('\n').count() + 1),
+                    _ => None,
+                };
+                e.in_file(&vault_config_path, line)
+            })?;
+        }
+
+        // === Step 4: Apply overrides ===
+        if !overrides.is_empty() {
+            config = config.with_table(overrides).map_err(|e| match e {
+                error::RucolaError::VaultConfigLoad(e) => {
+                    error::RucolaError::ConfigOverride(e.message().trim().to_owned())
+                }
+                e => e,
+            })?;
+        }
+
+        Ok((config, full_vault_path))
+    }
+
+    /// Overrides the options of this config with all options set in the given TOML string.
+    /// The vault path cannot be overridden this way.
+    fn with_vault_config(self, vault_config: &str) -> error::Result<Self> {
+        let mut vault_config = vault_config.parse::<toml::Table>()?;
+        vault_config.remove("vault_path");
+
+        self.with_table(vault_config)
+    }
+
+    /// Overrides the options of this config with all options set in the given table.
+    fn with_table(self, overrides: toml::Table) -> error::Result<Self> {
+        let mut table = toml::Table::try_from(&self)?;
+        table.extend(overrides);
+
+        Ok(table.try_into()?)
+    }
+
+    /// Collects the options set by the given environment variables and `key=value` assignments, the latter taking precedence.
+    /// Environment variables that do not name an option are ignored, while unknown keys in assignments are an error.
+    fn overrides(
+        env: impl Iterator<Item = (String, String)>,
+        assignments: &[String],
+    ) -> error::Result<toml::Table> {
+        let known_keys = check::known_keys();
+        let mut overrides = toml::Table::new();
+
+        for (name, value) in env {
+            if let Some(key) = name
+                .strip_prefix(ENV_PREFIX)
+                .map(str::to_lowercase)
+                .filter(|key| known_keys.contains(key))
+            {
+                overrides.insert(key, Self::override_value(&value));
+            }
+        }
+
+        for assignment in assignments {
+            let (key, value) = assignment.split_once('=').ok_or_else(|| {
+                error::RucolaError::ConfigOverride(format!(
+                    "`{}` is not of the form key=value.",
+                    assignment
+                ))
+            })?;
+            let key = key.trim();
+
+            if !known_keys.iter().any(|known| known == key) {
+                return Err(error::RucolaError::ConfigOverride(format!(
+                    "Unknown option `{}`.{}",
+                    key,
+                    check::closest(key, known_keys.iter().map(String::as_str))
+                        .map(|known| format!(" Did you mean `{}`?", known))
+                        .unwrap_or_default()
+                )));
+            }
+
+            overrides.insert(key.to_owned(), Self::override_value(value.trim()));
+        }
+
+        Ok(overrides)
+    }
+
+    /// Interprets the value of an override as a TOML value, e.g. a number or an array, or as a plain string if it is none.
+    fn override_value(value: &str) -> toml::Value {
+        match format!("value = {}", value)
+            .parse::<toml::Table>()
+            .ok()
+            .and_then(|mut table| table.remove("value"))
+        {
+            // dates are only used as strings in the config
+            Some(toml::Value::Datetime(_)) | None => toml::Value::String(value.to_owned()),
+            Some(value) => value,
+        }
+    }
+
+    /// Not expansion on windows
+    #[cfg(not(target_family = "unix"))]
+    fn vault_path(pwd: path::PathBuf, args: ConfigArgs, config: &mut Config) -> path::PathBuf {
+        args.target_folder
+            .map(|folder_string| path::PathBuf::from(folder_string))
+            .or(config.vault_path.take())
+            .unwrap_or_else(|| pwd.clone())
+    }
+
+    /// Expanduser expands `~` to the correct user home directory and similar, on unix systems.
+    #[cfg(target_family = "unix")]
+    fn vault_path(pwd: path::PathBuf, args: ConfigArgs, config: &mut Config) -> path::PathBuf {
+        args.target_folder
+            // first attempt to extend the command line given path if one was passed
+            .and_then(|arg_string| expanduser::expanduser(arg_string).ok())
+            // if none was given, expand the path given from the config file
+            .or_else(|| {
+                config.vault_path.take().and_then(|conf_path_buf| {
+                    expanduser::expanduser(conf_path_buf.to_string_lossy()).ok()
+                })
+            })
+            .unwrap_or_else(|| pwd.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn test_vault_config() {
+        let config = super::Config {
+            vault_path: Some(std::path::PathBuf::from("~/notes")),
+            update_modified: true,
+            ..Default::default()
+        };
+
+        let config = config
+            .with_vault_config(
+                "vault_path = \"~/other\"\nupdate_modified = false\ntimezone = \"UTC\"\n",
+            )
+            .unwrap();
+
+        assert_eq!(config.vault_path, Some(std::path::PathBuf::from("~/notes")));
+        assert!(!config.update_modified);
+        assert_eq!(config.timezone, Some(String::from("UTC")));
+        assert_eq!(config.default_extension, "md");
+
+        assert!(super::Config::default()
+            .with_vault_config("update_modified = 3")
+            .is_err());
+    }
+
+    #[test]
+    fn test_overrides() {
+        let env = [
+            ("RUCOLA_READING_WIDTH", "100"),
+            ("RUCOLA_THEME", "default_light"),
+            ("RUCOLA_EDITOR", "[\"nvim\", \"%p\"]"),
+            ("RUCOLA_SYNC_PASSWORD", "secret"),
+            ("HOME", "/home/user"),
+        ]
+        .map(|(key, value)| (key.to_owned(), value.to_owned()));
+
+        let overrides = super::Config::overrides(
+            env.into_iter(),
+            &[
+                String::from("reading_width=120"),
+                String::from("timestamp_format = %Y-%m-%d"),
+            ],
+        )
+        .unwrap();
+
+        // Only options are taken from the environment.
+        assert_eq!(overrides.len(), 4);
+
+        let config = super::Config::default().with_table(overrides).unwrap();
+        // Assignments take precedence over the environment.
+        assert_eq!(config.reading_width, 120);
+        assert_eq!(config.theme, "default_light");
+        assert_eq!(
+            config.editor,
+            Some(vec![String::from("nvim"), String::from("%p")])
+        );
+        assert_eq!(config.timestamp_format, "%Y-%m-%d");
+
+        assert!(
+            super::Config::overrides(std::iter::empty(), &[String::from("reading_widht=3")])
+                .is_err()
+        );
+        assert!(super::Config::overrides(std::iter::empty(), &[String::from("katex")]).is_err());
+    }
+}
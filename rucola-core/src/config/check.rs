@@ -0,0 +1,702 @@
+use std::{fmt, path};
+
+use itertools::Itertools;
+
+use crate::error;
+
+/// A problem found while checking a config file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigProblem {
+    /// The file the problem was found in.
+    pub path: path::PathBuf,
+    /// The line of the problem, if it can be attributed to one.
+    pub line: Option<usize>,
+    /// What is wrong.
+    pub message: String,
+    /// How the problem could be fixed, if known.
+    pub suggestion: Option<String>,
+}
+
+impl fmt::Display for ConfigProblem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.path.display())?;
+        if let Some(line) = self.line {
+            write!(f, ":{}", line)?;
+        }
+        write!(f, ": {}", self.message)
+    }
+}
+
+impl From<ConfigProblem> for error::RucolaError {
+    fn from(value: ConfigProblem) -> Self {
+        error::RucolaError::ConfigProblem(value.to_string())
+    }
+}
+
+/// Checks the config file and, if present, the `.rucola.toml` file of the given vault.
+/// Returns all problems found, in the order of the files and lines they occur in.
+pub fn check_files(vault_path: &path::Path) -> Vec<ConfigProblem> {
+    let mut problems = Vec::new();
+
+    if let Ok(config_path) = confy::get_configuration_file_path("rucola", "config") {
+        if let Ok(content) = std::fs::read_to_string(&config_path) {
+            problems.extend(check(&config_path, &content, false));
+        }
+    }
+
+    let vault_config_path = vault_path.join(".rucola.toml");
+    if let Ok(content) = std::fs::read_to_string(&vault_config_path) {
+        problems.extend(check(&vault_config_path, &content, true));
+    }
+
+    problems
+}
+
+/// Checks the given content of a config file for unknown keys and invalid values.
+/// Vault config files cannot set the vault path.
+pub fn check(path: &path::Path, content: &str, vault_config: bool) -> Vec<ConfigProblem> {
+    let problem =
+        |line: Option<usize>, message: String, suggestion: Option<String>| ConfigProblem {
+            path: path.to_path_buf(),
+            line,
+            message,
+            suggestion,
+        };
+
+    let table = match content.parse::<toml::Table>() {
+        Ok(table) => table,
+        Err(e) => {
+            return vec![problem(
+                e.span().map(|span| line_of(content, span.start)),
+                format!("The file is not valid TOML: {}", e.message()),
+                None,
+            )]
+        }
+    };
+
+    let known_keys = known_keys();
+    // All options left unset take their default values.
+    let Ok(defaults) = toml::Table::try_from(crate::Config::default()) else {
+        return Vec::new();
+    };
+
+    let mut problems = Vec::new();
+
+    for (key, value) in table {
+        let line = key_line(content, &key);
+
+        if !known_keys.contains(&key) {
+            problems.push(problem(
+                line,
+                format!("Unknown option `{}`.", key),
+                closest(&key, known_keys.iter().map(String::as_str))
+                    .map(|known| format!("Did you mean `{}`?", known)),
+            ));
+            continue;
+        }
+
+        if vault_config && key == "vault_path" {
+            problems.push(problem(
+                line,
+                String::from("The vault path cannot be set in a vault config file and is ignored."),
+                Some(String::from("Remove the option from this file.")),
+            ));
+            continue;
+        }
+
+        // Check the value on its own, so every invalid value is reported.
+        let mut single = defaults.clone();
+        single.insert(key.clone(), value);
+        match single.try_into::<crate::Config>() {
+            Ok(config) => problems.extend(
+                check_value(&key, &config)
+                    .into_iter()
+                    .map(|(message, suggestion)| problem(line, message, suggestion)),
+            ),
+            Err(e) => problems.push(problem(
+                line,
+                format!("Invalid value for `{}`: {}", key, e.message().trim()),
+                default_hint(&key, &defaults),
+            )),
+        }
+    }
+
+    problems.sort_by_key(|problem| problem.line);
+    problems
+}
+
+/// Checks the value of the given key, which has already been read successfully into the given config.
+/// Returns a message and a suggestion for every problem.
+fn check_value(key: &str, config: &crate::Config) -> Vec<(String, Option<String>)> {
+    let mut problems = Vec::new();
+
+    match key {
+        "file_types" => {
+            let mut types_builder = ignore::types::TypesBuilder::new();
+            types_builder.add_defaults();
+            for name in config.file_types.iter() {
+                types_builder.select(name);
+            }
+            if let Err(e) = types_builder.build() {
+                problems.push((
+                    format!("Invalid file types: {}", e),
+                    Some(String::from(
+                        "Use the names of the ignore crate, such as \"markdown\" or \"all\".",
+                    )),
+                ));
+            }
+        }
+        "default_extension" if config.default_extension.starts_with('.') => {
+            problems.push((
+                String::from("The default extension should not start with a dot."),
+                Some(format!(
+                    "Use \"{}\".",
+                    config.default_extension.trim_start_matches('.')
+                )),
+            ));
+        }
+        "theme" => {
+            if let Some(message) = missing_config_file(
+                &config.theme,
+                "toml",
+                "The theme file",
+                "the default styles will be written to it",
+            ) {
+                problems.push(message);
+            }
+        }
+        "css" => {
+            if let Some(message) = config.css.as_deref().and_then(|css| {
+                missing_config_file(
+                    css.trim_end_matches(".css"),
+                    "css",
+                    "The css file",
+                    "an empty one will be created",
+                )
+            }) {
+                problems.push(message);
+            }
+        }
+        "print_css" => {
+            if let Some(message) = config.print_css.as_deref().and_then(|css| {
+                missing_config_file(
+                    css.trim_end_matches(".css"),
+                    "css",
+                    "The print css file",
+                    "the built-in print stylesheet will be used",
+                )
+            }) {
+                problems.push(message);
+            }
+        }
+        "editor" | "viewer" | "secondary_viewer" | "file_manager" => {
+            let command = match key {
+                "editor" => &config.editor,
+                "viewer" => &config.viewer,
+                "file_manager" => &config.file_manager,
+                _ => &config.secondary_viewer,
+            };
+            problems.extend(command.as_deref().and_then(check_program));
+        }
+        "pdf_command" | "slides_command" => {
+            let (command, name, source) = match key {
+                "pdf_command" => (&config.pdf_command, "PDF", "note"),
+                _ => (&config.slides_command, "slides", "typst file"),
+            };
+            problems.extend(check_program(command));
+            for (placeholder, meaning) in [("%p", source), ("%o", "PDF file")] {
+                if !command.iter().any(|arg| arg == placeholder) {
+                    problems.push((
+                        format!(
+                            "The {} command does not contain `{}`, so the path of the {} is not passed to it.",
+                            name, placeholder, meaning
+                        ),
+                        Some(format!("Add \"{}\" as a separate argument.", placeholder)),
+                    ));
+                }
+            }
+        }
+        "pdf_template_arg"
+            if config
+                .pdf_template_arg
+                .as_ref()
+                .is_some_and(|arg| !arg.contains("%t")) =>
+        {
+            problems.push((
+                String::from(
+                    "The PDF template argument does not contain `%t`, so the template is not passed.",
+                ),
+                Some(String::from("Use e.g. \"--template=%t\".")),
+            ));
+        }
+        "pdf_print_arg"
+            if config
+                .pdf_print_arg
+                .as_ref()
+                .is_some_and(|arg| !arg.contains("%c")) =>
+        {
+            problems.push((
+                String::from(
+                    "The PDF print argument does not contain `%c`, so the print stylesheet is not passed.",
+                ),
+                Some(String::from("Use e.g. \"--css=%c\".")),
+            ));
+        }
+        "tag_regex" => {
+            if let Some(Err(e)) = config.tag_regex.as_deref().map(regex::Regex::new) {
+                problems.push((
+                    format!(
+                        "Invalid tag regex: {}",
+                        e.to_string().lines().last().unwrap_or_default()
+                    ),
+                    None,
+                ));
+            }
+        }
+        "timezone" => {
+            if let Some(Err(e)) = config
+                .timezone
+                .as_deref()
+                .map(|timezone| timezone.parse::<chrono_tz::Tz>())
+            {
+                problems.push((
+                    format!("Invalid timezone: {}", e),
+                    Some(String::from(
+                        "Use an IANA name such as \"Europe/Berlin\" or remove the option to use the system timezone.",
+                    )),
+                ));
+            }
+        }
+        "language" => {
+            if let Err(e) = crate::i18n::Catalog::new(config) {
+                problems.push((
+                    match e {
+                        error::RucolaError::ConfigProblem(message) => message,
+                        e => e.to_string(),
+                    },
+                    Some(String::from(
+                        "Use \"en\" or \"de\", or add a file `<language>.ftl` translating rucola to the config folder.",
+                    )),
+                ));
+            }
+        }
+        #[cfg(feature = "collation")]
+        "sort_locale" => {
+            if let Some(Err(e)) = config
+                .sort_locale
+                .as_deref()
+                .map(|locale| locale.parse::<icu_locid::Locale>())
+            {
+                problems.push((
+                    format!("Invalid sort locale: {}", e),
+                    Some(String::from(
+                        "Use a BCP 47 locale such as \"de\" or \"sv-SE\".",
+                    )),
+                ));
+            }
+        }
+        #[cfg(not(feature = "collation"))]
+        "sort_locale" if config.sort_locale.is_some() => {
+            problems.push((
+                String::from(
+                    "The sort locale is ignored, as rucola was built without the `collation` feature.",
+                ),
+                Some(String::from(
+                    "Install rucola with `--features collation` or remove the option.",
+                )),
+            ));
+        }
+        "date_formats" | "timestamp_format" => {
+            let formats = if key == "date_formats" {
+                config.date_formats.iter().collect_vec()
+            } else {
+                vec![&config.timestamp_format]
+            };
+            for format in formats {
+                if chrono::format::StrftimeItems::new(format)
+                    .any(|item| item == chrono::format::Item::Error)
+                {
+                    problems.push((
+                        format!("Invalid date format \"{}\".", format),
+                        Some(String::from(
+                            "See https://docs.rs/chrono/latest/chrono/format/strftime for the supported syntax.",
+                        )),
+                    ));
+                }
+            }
+        }
+        "summary_command" | "embedding_command" | "spellcheck_command" | "mail_command" => {
+            let command = match key {
+                "summary_command" => &config.summary_command,
+                "embedding_command" => &config.embedding_command,
+                "mail_command" => &config.mail_command,
+                _ => &config.spellcheck_command,
+            };
+            problems.extend(command.as_deref().and_then(check_program));
+        }
+        "spellcheck_dictionary"
+            if config
+                .spellcheck_dictionary
+                .as_ref()
+                .is_some_and(|dictionary| !dictionary.is_file()) =>
+        {
+            problems.push((
+                String::from("The spellcheck dictionary does not exist."),
+                Some(String::from(
+                    "Use the full path of a hunspell .dic file, e.g. /usr/share/hunspell/en_US.dic.",
+                )),
+            ));
+        }
+        "export_profiles" => {
+            for (name, profile) in config
+                .export_profiles
+                .iter()
+                .sorted_by_key(|(name, _)| *name)
+            {
+                if profile.filter_string().is_none() {
+                    problems.push((
+                        format!("The export profile `{}` exports no notes.", name),
+                        Some(String::from(
+                            "Set the `collection` or the `filter` whose notes to export.",
+                        )),
+                    ));
+                } else if profile.collection.is_some() && profile.filter.is_some() {
+                    problems.push((
+                        format!(
+                            "The export profile `{}` sets both a collection and a filter, the filter is ignored.",
+                            name
+                        ),
+                        Some(String::from("Remove one of them.")),
+                    ));
+                }
+            }
+        }
+        "recurring_notes" => {
+            for (name, rule) in config
+                .recurring_notes
+                .iter()
+                .sorted_by_key(|(name, _)| *name)
+            {
+                if let Some(problem) = rule.problem() {
+                    problems.push((
+                        format!("The recurring note `{}` cannot be created. {}", name, problem),
+                        Some(String::from(
+                            "Weekdays are English names like \"Monday\", paths use the syntax of https://docs.rs/chrono/latest/chrono/format/strftime.",
+                        )),
+                    ));
+                }
+            }
+        }
+        "stale_notes" => {
+            for (name, _rule) in config
+                .stale_notes
+                .iter()
+                .filter(|(_name, rule)| rule.days == 0)
+                .sorted_by_key(|(name, _)| *name)
+            {
+                problems.push((
+                    format!(
+                        "The stale notes rule `{}` flags all matching notes not modified today.",
+                        name
+                    ),
+                    Some(String::from(
+                        "Set the number of `days` notes may go without modification.",
+                    )),
+                ));
+            }
+        }
+        "sync_url" | "publish_endpoint" | "summary_endpoint" | "embedding_endpoint" => {
+            let url = match key {
+                "sync_url" => &config.sync_url,
+                "publish_endpoint" => &config.publish_endpoint,
+                "summary_endpoint" => &config.summary_endpoint,
+                _ => &config.embedding_endpoint,
+            };
+            if url
+                .as_ref()
+                .is_some_and(|url| !url.starts_with("http://") && !url.starts_with("https://"))
+            {
+                problems.push((
+                    String::from("The URL has to start with http:// or https://."),
+                    None,
+                ));
+            }
+        }
+        _ => {}
+    }
+
+    problems
+}
+
+/// Checks wether the program of the given command can be found.
+fn check_program(command: &[String]) -> Option<(String, Option<String>)> {
+    let Some(program) = command.first() else {
+        return Some((
+            String::from("The command is empty."),
+            Some(String::from("Name a program and its arguments.")),
+        ));
+    };
+
+    let found = if program.contains(std::path::MAIN_SEPARATOR) || program.contains('/') {
+        path::Path::new(program).is_file()
+    } else {
+        std::env::var_os("PATH").is_some_and(|paths| {
+            std::env::split_paths(&paths).any(|dir| {
+                dir.join(program).is_file() || dir.join(format!("{}.exe", program)).is_file()
+            })
+        })
+    };
+
+    (!found).then(|| {
+        (
+            format!("The program `{}` was not found.", program),
+            Some(String::from(
+                "Install it, add it to your PATH or use its full path.",
+            )),
+        )
+    })
+}
+
+/// Checks wether a file with the given name and extension exists in the config directory.
+fn missing_config_file(
+    name: &str,
+    extension: &str,
+    description: &str,
+    consequence: &str,
+) -> Option<(String, Option<String>)> {
+    let mut file = confy::get_configuration_file_path("rucola", name).ok()?;
+    file.set_extension(extension);
+    if file.exists() {
+        return None;
+    }
+
+    // suggest similarly named files in the config directory
+    let existing = file
+        .parent()
+        .and_then(|dir| std::fs::read_dir(dir).ok())
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == extension))
+        .filter_map(|path| {
+            path.file_stem()
+                .map(|stem| stem.to_string_lossy().to_string())
+        })
+        .collect_vec();
+
+    Some((
+        format!(
+            "{} {} does not exist, {}.",
+            description,
+            file.display(),
+            consequence
+        ),
+        closest(name, existing.iter().map(String::as_str))
+            .map(|existing| format!("Did you mean \"{}\"?", existing)),
+    ))
+}
+
+/// Describes the default value of the given key, to suggest a valid value.
+fn default_hint(key: &str, defaults: &toml::Table) -> Option<String> {
+    defaults
+        .get(key)
+        .map(|value| format!("The default is `{} = {}`.", key, value))
+}
+
+/// Returns the names of all options of the config file.
+pub(super) fn known_keys() -> Vec<String> {
+    // Unlike TOML, JSON keeps options without a value.
+    match serde_json::to_value(crate::Config::default()) {
+        Ok(serde_json::Value::Object(object)) => object.keys().cloned().collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Finds the candidate most similar to the given word, if any is similar enough to be a likely typo.
+pub(super) fn closest<'a>(
+    word: &str,
+    candidates: impl Iterator<Item = &'a str>,
+) -> Option<&'a str> {
+    candidates
+        .map(|candidate| (edit_distance(word, candidate), candidate))
+        .filter(|(distance, candidate)| *distance <= (word.len().max(candidate.len()) / 3).max(1))
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| candidate)
+}
+
+/// Calculates the number of insertions, deletions and substitutions needed to turn one word into the other.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b = b.chars().collect_vec();
+    let mut row = (0..=b.len()).collect_vec();
+
+    for (i, ca) in a.chars().enumerate() {
+        let mut previous = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let current = row[j + 1];
+            row[j + 1] = (previous + usize::from(ca != *cb))
+                .min(row[j] + 1)
+                .min(current + 1);
+            previous = current;
+        }
+    }
+
+    row[b.len()]
+}
+
+/// Returns the line the given byte offset lies in, counting from 1.
+fn line_of(content: &str, offset: usize) -> usize {
+    content[..offset.min(content.len())].matches('\n').count() + 1
+}
+
+/// Returns the line the given top-level key or table is defined in, counting from 1.
+fn key_line(content: &str, key: &str) -> Option<usize> {
+    content
+        .lines()
+        .map(str::trim)
+        .position(|line| {
+            line.strip_prefix(key)
+                .map(str::trim_start)
+                .is_some_and(|rest| rest.starts_with('='))
+                || line == format!("[{}]", key)
+        })
+        .map(|index| index + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unknown_keys() {
+        let problems = check(
+            path::Path::new("config.toml"),
+            "theme = \"default_dark\"\nupdate_modifed = true\nfoo = 3\n",
+            false,
+        );
+
+        // the theme exists or not depending on the machine, so only look at the unknown keys
+        let problems = problems
+            .into_iter()
+            .filter(|problem| problem.message.starts_with("Unknown"))
+            .collect_vec();
+
+        assert_eq!(problems.len(), 2);
+        assert_eq!(problems[0].line, Some(2));
+        assert_eq!(
+            problems[0].suggestion.as_deref(),
+            Some("Did you mean `update_modified`?")
+        );
+        assert_eq!(problems[1].line, Some(3));
+        assert!(problems[1].suggestion.is_none());
+    }
+
+    #[test]
+    fn test_invalid_values() {
+        let problems = check(
+            path::Path::new(".rucola.toml"),
+            "vault_path = \"~/notes\"\nreading_width = \"wide\"\ntag_regex = \"[a-z\"\n\ntimezone = \"Mars/Olympus\"\ntimestamp_format = \"%Y-%Q\"\npdf_command = [\"pandoc\", \"%p\"]\n",
+            true,
+        );
+
+        let lines = problems
+            .iter()
+            .map(|problem| problem.line.unwrap_or_default())
+            .collect_vec();
+
+        // The PDF command might additionally miss its program.
+        assert!(lines.starts_with(&[1, 2, 3, 5, 6, 7]));
+        assert!(problems[1].message.contains("reading_width"));
+        assert_eq!(
+            problems[1].suggestion.as_deref(),
+            Some("The default is `reading_width = 80`.")
+        );
+        assert!(problems.last().unwrap().message.contains("%o"));
+    }
+
+    #[test]
+    fn test_sort_locale() {
+        // valid locales are only reported if they are ignored
+        let problems = check(
+            path::Path::new("config.toml"),
+            "sort_locale = \"de\"\n",
+            false,
+        );
+        assert_eq!(
+            problems.len(),
+            usize::from(cfg!(not(feature = "collation")))
+        );
+
+        let problems = check(
+            path::Path::new("config.toml"),
+            "sort_locale = \"not a locale!\"\n",
+            false,
+        );
+        assert_eq!(problems.len(), 1);
+    }
+
+    #[test]
+    fn test_language() {
+        let problems = check(path::Path::new("config.toml"), "language = \"de\"\n", false);
+        assert!(problems.is_empty());
+
+        let problems = check(
+            path::Path::new("config.toml"),
+            "language = \"tlh\"\n",
+            false,
+        );
+        assert_eq!(problems.len(), 1);
+        assert!(problems[0].message.contains("tlh"));
+    }
+
+    #[test]
+    fn test_export_profiles() {
+        let problems = check(
+            path::Path::new("config.toml"),
+            "[export_profiles.handouts]\ncollection = \"algebra\"\nformat = \"Pdf\"\n\n[export_profiles.empty]\nformat = \"Zip\"\n",
+            false,
+        );
+        assert_eq!(problems.len(), 1);
+        assert!(problems[0].message.contains("`empty`"));
+
+        let problems = check(
+            path::Path::new("config.toml"),
+            "[export_profiles.handouts]\nformat = \"Handouts\"\n",
+            false,
+        );
+        assert_eq!(problems.len(), 1);
+        assert!(problems[0].message.contains("export_profiles"));
+    }
+
+    #[test]
+    fn test_recurring_notes() {
+        let problems = check(
+            path::Path::new("config.toml"),
+            "[snippets]\nreview = \"# Review\"\n\n[recurring_notes.review]\nevery = \"Weekly\"\npath = \"reviews/%G-W%V\"\nsnippet = \"review\"\n\n[recurring_notes.friday]\nevery = \"Weekly\"\nweekday = \"Freitag\"\npath = \"%F\"\n\n[recurring_notes.budget]\nevery = \"Monthly\"\npath = \"budget/%Y-%m\"\n",
+            false,
+        );
+        assert_eq!(problems.len(), 1);
+        assert!(problems[0].message.contains("Freitag"));
+    }
+
+    #[test]
+    fn test_syntax_error() {
+        let problems = check(
+            path::Path::new("config.toml"),
+            "theme = \"default_dark\"\nkatex = \n",
+            false,
+        );
+
+        assert_eq!(problems.len(), 1);
+        assert_eq!(problems[0].line, Some(2));
+    }
+
+    #[test]
+    fn test_edit_distance() {
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+        assert_eq!(edit_distance("", "abc"), 3);
+        assert_eq!(closest("them", ["theme", "css"].into_iter()), Some("theme"));
+        assert_eq!(closest("zzz", ["theme", "css"].into_iter()), None);
+    }
+}
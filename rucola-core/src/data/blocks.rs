@@ -0,0 +1,151 @@
+use itertools::Itertools;
+
+use super::lint::{heading_level, split_lines};
+
+/// Returns the id of the block marked in the given line, i.e. the `id` of a trailing ` ^id`, if there is one.
+/// Ids consist of letters, digits and dashes.
+pub fn block_id(line: &str) -> Option<&str> {
+    let line = line.trim_end();
+    let start = line.rfind('^')?;
+    let id = &line[start + 1..];
+
+    (!id.is_empty()
+        && id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+        && line[..start]
+            .chars()
+            .next_back()
+            .is_none_or(char::is_whitespace))
+    .then_some(id)
+}
+
+/// Returns the ids of all blocks marked in the given content, outside of its frontmatter and code blocks.
+pub fn block_ids(content: &str) -> Vec<String> {
+    let (_frontmatter, body, _body_start) = split_lines(content);
+    body.into_iter()
+        .flat_map(|(_, line)| block_id(line))
+        .map(str::to_owned)
+        .unique()
+        .collect()
+}
+
+/// Returns the text of the block with the given id in the given content, without its id.
+/// An id at the end of a line marks the paragraph or list item it ends, an id on a line of its own the block right before it, such as a list or quote.
+pub fn find_block(content: &str, id: &str) -> Option<String> {
+    let (_frontmatter, body, _body_start) = split_lines(content);
+    let position = body
+        .iter()
+        .position(|(_, line)| block_id(line) == Some(id))?;
+
+    // The lines of a block are consecutive and not empty.
+    let is_block_line = |index: usize| {
+        let (number, line) = body[index];
+        !line.trim().is_empty() && (index == position || body[index + 1].0 == number + 1)
+    };
+
+    let standalone = body[position].1.trim() == format!("^{}", id);
+    let end = if standalone {
+        position.checked_sub(1).filter(|end| is_block_line(*end))?
+    } else {
+        position
+    };
+
+    let mut start = end;
+    // Marked list items and headings are blocks of their own.
+    if standalone || !(is_list_item(body[end].1) || heading_level(body[end].1).is_some()) {
+        while start > 0 && is_block_line(start - 1) && heading_level(body[start - 1].1).is_none() {
+            start -= 1;
+        }
+    }
+
+    let mut lines = body[start..=end]
+        .iter()
+        .map(|(_, line)| line.to_string())
+        .collect_vec();
+    if let Some(last) = lines.last_mut().filter(|_| !standalone) {
+        *last = last.trim_end()[..last.trim_end().len() - id.len() - 1]
+            .trim_end()
+            .to_owned();
+    }
+
+    Some(lines.join("\n"))
+}
+
+/// Returns the targets and block ids of all references to blocks (`[[note#^id]]`) in the given text.
+pub fn block_references(text: &str) -> Vec<(String, String)> {
+    text.split("[[")
+        .skip(1)
+        .flat_map(|rest| {
+            let link = rest.split("]]").next()?;
+            let link = link.split('|').next()?;
+            let (target, id) = link.split_once("#^")?;
+            Some((target.trim().to_owned(), id.trim().to_owned()))
+        })
+        .collect()
+}
+
+/// Wether the given line is an item of a bulleted or numbered list.
+fn is_list_item(line: &str) -> bool {
+    let line = line.trim_start();
+    line.starts_with("- ")
+        || line.starts_with("* ")
+        || line.starts_with("+ ")
+        || line.split_once(". ").is_some_and(|(number, _)| {
+            !number.is_empty() && number.chars().all(|c| c.is_ascii_digit())
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_block_id() {
+        assert_eq!(
+            block_id("A chart is a homeomorphism. ^chart-def"),
+            Some("chart-def")
+        );
+        assert_eq!(block_id("^quote"), Some("quote"));
+        assert_eq!(block_id("x^2 is a square"), None);
+        assert_eq!(block_id("Powers like x^2"), None);
+        assert_eq!(block_id("A caret ^"), None);
+    }
+
+    #[test]
+    fn test_find_block() {
+        let content = "---\ntitle: Chart\n---\n# Chart\nA chart maps\nan open set. ^def\n\n- first\n- second ^item\n\n> quoted\n> twice\n\n^quote\n```\nnot ^code\n```\n";
+
+        assert_eq!(block_ids(content), vec!["def", "item", "quote"]);
+        assert_eq!(
+            find_block(content, "def").unwrap(),
+            "A chart maps\nan open set."
+        );
+        assert_eq!(find_block(content, "item").unwrap(), "- second");
+        assert_eq!(find_block(content, "quote"), None);
+        assert_eq!(find_block(content, "code"), None);
+
+        let content = "> quoted\n> twice\n^quote\n";
+        assert_eq!(find_block(content, "quote").unwrap(), "> quoted\n> twice");
+    }
+
+    #[test]
+    fn test_block_references() {
+        assert_eq!(
+            block_references(
+                "See [[Chart#^def]] and [[Atlas#^item|the item]], not [[Chart#Section]]."
+            ),
+            vec![
+                (String::from("Chart"), String::from("def")),
+                (String::from("Atlas"), String::from("item")),
+            ]
+        );
+    }
+
+    proptest::proptest! {
+        #[test]
+        fn test_find_arbitrary_blocks(content in "(---\n)?([-#>`^ a-z1]{0,12}\n){0,8}") {
+            for id in block_ids(&content) {
+                let _ = find_block(&content, &id);
+            }
+        }
+    }
+}
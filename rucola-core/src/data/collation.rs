@@ -0,0 +1,125 @@
+use std::cmp;
+
+use unicode_normalization::UnicodeNormalization;
+
+use crate::error;
+
+/// Orders the names of notes as readers expect them in a dictionary, rather than by their bytes, so e.g. "Äpfel" is sorted next to "Apfel".
+/// With the `collation` feature, the unicode collation algorithm is used with the rules of the configured locale.
+/// Without it, names are compared ignoring case and accents, so non-latin scripts are sorted by their code points.
+#[derive(Debug, Clone, Default)]
+pub struct Collation {
+    /// The collator of the configured locale, or of the root locale if none is configured. Unset for collations ignoring case and accents.
+    #[cfg(feature = "collation")]
+    collator: Option<std::rc::Rc<icu_collator::Collator>>,
+}
+
+impl Collation {
+    /// Creates a collation for the locale configured in the given config, failing if it is no valid locale.
+    pub fn new(config: &crate::Config) -> error::Result<Self> {
+        Self::for_locale(config.sort_locale.as_deref())
+    }
+
+    /// Creates a collation for the given BCP 47 locale, such as `de` or `sv-SE`, or the root collation if none is given.
+    #[cfg(feature = "collation")]
+    pub fn for_locale(locale: Option<&str>) -> error::Result<Self> {
+        let locale = locale
+            .map(|locale| {
+                locale.parse::<icu_locid::Locale>().map_err(|e| {
+                    error::RucolaError::ConfigProblem(format!(
+                        "Invalid sort locale {}: {}",
+                        locale, e
+                    ))
+                })
+            })
+            .transpose()?
+            .unwrap_or_default();
+
+        let collator = icu_collator::Collator::try_new(&locale.into(), Default::default())
+            .map_err(|e| {
+                error::RucolaError::ConfigProblem(format!("Invalid sort locale: {}", e))
+            })?;
+
+        Ok(Self {
+            collator: Some(std::rc::Rc::new(collator)),
+        })
+    }
+
+    /// Creates the collation ignoring case and accents, as the locale can only be respected with the `collation` feature.
+    #[cfg(not(feature = "collation"))]
+    pub fn for_locale(_locale: Option<&str>) -> error::Result<Self> {
+        Ok(Self::default())
+    }
+
+    /// Compares the given names. Names only differing in case or accents are ordered by their bytes, so the order is total.
+    pub fn compare(&self, name1: &str, name2: &str) -> cmp::Ordering {
+        #[cfg(feature = "collation")]
+        if let Some(collator) = &self.collator {
+            return collator
+                .compare(name1, name2)
+                .then_with(|| name1.cmp(name2));
+        }
+
+        fold(name1).cmp(fold(name2)).then_with(|| name1.cmp(name2))
+    }
+}
+
+/// Decomposes the given name, removing accents and case, so it can be compared by its code points.
+fn fold(name: &str) -> impl Iterator<Item = char> + '_ {
+    name.nfd()
+        .filter(|c| !unicode_normalization::char::is_combining_mark(*c))
+        .flat_map(char::to_lowercase)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sorted(collation: &Collation, names: &[&str]) -> Vec<String> {
+        let mut names = names
+            .iter()
+            .map(|name| name.to_string())
+            .collect::<Vec<_>>();
+        names.sort_by(|name1, name2| collation.compare(name1, name2));
+        names
+    }
+
+    #[test]
+    fn test_collation() {
+        let collation = Collation::default();
+
+        assert_eq!(
+            sorted(
+                &collation,
+                &["Zebra", "Birne", "apfel", "Äpfel", "Apfel", "Öl", "Ozean"]
+            ),
+            vec!["Apfel", "apfel", "Äpfel", "Birne", "Öl", "Ozean", "Zebra"]
+        );
+
+        // decomposed and composed accents are sorted the same
+        assert_eq!(
+            collation.compare("A\u{0308}pfel", "Apfelsine"),
+            cmp::Ordering::Less
+        );
+        assert_eq!(collation.compare("Apfel", "Apfel"), cmp::Ordering::Equal);
+    }
+
+    #[cfg(feature = "collation")]
+    #[test]
+    fn test_locale_collation() {
+        // swedish sorts letters with accents after z
+        let swedish = Collation::for_locale(Some("sv")).unwrap();
+        assert_eq!(
+            sorted(&swedish, &["Öl", "Zebra", "Ozean"]),
+            vec!["Ozean", "Zebra", "Öl"]
+        );
+
+        let german = Collation::for_locale(Some("de")).unwrap();
+        assert_eq!(
+            sorted(&german, &["Öl", "Zebra", "Ozean", "東京", "Apfel"]),
+            vec!["Apfel", "Öl", "Ozean", "Zebra", "東京"]
+        );
+
+        assert!(Collation::for_locale(Some("not a locale!")).is_err());
+    }
+}
@@ -0,0 +1,1555 @@
+use std::collections::{HashMap, HashSet};
+
+use fuzzy_matcher::FuzzyMatcher;
+use itertools::Itertools;
+
+/// Words left out of title searches, unless the search consists of nothing else.
+const STOPWORDS: [&str; 16] = [
+    "a", "an", "and", "as", "at", "by", "for", "from", "in", "is", "of", "on", "or", "the", "to",
+    "with",
+];
+
+/// How much more matches in the display title count than matches in the tags.
+const TITLE_WEIGHT: i64 = 4;
+/// How much more matches in the file name count than matches in the tags.
+const NAME_WEIGHT: i64 = 2;
+/// Added to the match score for every search word a title starts with.
+const PREFIX_BONUS: i64 = 100;
+/// Added to the match score for every search word another word of a title starts with.
+const WORD_PREFIX_BONUS: i64 = 30;
+/// Added to the match score of the most recently opened note, less for notes opened before it.
+const RECENT_BONUS: i64 = 40;
+/// Added to the match score of the most visited note, proportionally less for notes visited less often.
+const VISIT_BONUS: i64 = 20;
+/// Added to the match score for every content word that is a term of a note.
+const TERM_EXACT_BONUS: i64 = 30;
+/// Added to the match score for every content word a term of a note starts with.
+const TERM_PREFIX_BONUS: i64 = 20;
+/// Added to the match score for every content word that fuzzily matches a term of a note.
+const TERM_FUZZY_BONUS: i64 = 10;
+/// Content words shorter than this only match terms exactly or by their start, as nearly every term fuzzily matches them.
+const TERM_FUZZY_MIN_LENGTH: usize = 4;
+/// How many letters a term may have beyond a content word fuzzily matching it.
+const TERM_FUZZY_SLACK: usize = 2;
+/// How many occurences of a term count towards the match score.
+const TERM_OCCURENCES_CAP: usize = 10;
+
+/// Describes a way to filter notes by their contained tags and/or title
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Filter {
+    /// Wether or not all specified tags must be contained in the note in order to match the filter, or only any (=at least one) of them.
+    pub any: bool,
+    /// The tags to include and exclude by, hash included.
+    pub tags: Vec<(String, bool)>,
+    /// The links to look for or exclude, already converted to ids.
+    pub links: Vec<(String, bool)>,
+    /// The backlinks to look for or exclude, already converted to ids.
+    pub blinks: Vec<(String, bool)>,
+    /// The folders to look in or exclude, relative to the vault.
+    pub paths: Vec<(std::path::PathBuf, bool)>,
+    /// The conditions on columns of the data tables of notes to look for or exclude.
+    pub columns: Vec<(super::ColumnCondition, bool)>,
+    /// The conditions on the numbers of words, characters, links or backlinks of notes to look for or exclude.
+    pub counts: Vec<(CountCondition, bool)>,
+    /// The conditions on the creation or modification dates of notes to look for or exclude.
+    pub dates: Vec<(DateCondition, bool)>,
+    /// The names of the collections to look in or exclude.
+    pub collections: Vec<(String, bool)>,
+    /// The ids of the notes in the collections looked in or excluded, see [`Filter::with_collections`].
+    pub collection_members: HashMap<String, HashSet<String>>,
+    /// The words to search the note title for, separated by spaces. Will be fuzzy matched with the note title, file name and tags.
+    pub title: String,
+    /// Everything to be searched for in the full text of the notes, in lowercase.
+    pub full_text: Option<String>,
+    /// The words to look for or exclude in the content of the notes, in lowercase. Matched against the terms indexed for every note, so no note is read.
+    pub content: Vec<(String, bool)>,
+    /// The ids of recently opened notes, oldest first. Matching notes among them are ranked higher.
+    pub recent: Vec<String>,
+    /// How often notes have been opened, by their id. Matching notes visited often are ranked higher.
+    pub visits: HashMap<String, usize>,
+    /// The tags of notes never matching the filter unless it asks for them, hash included.
+    pub excluded_tags: Vec<String>,
+    /// Filters all matching notes have to match as well, e.g. restricting them to a folder.
+    pub scope: Vec<Filter>,
+    /// The filters in parentheses to look for or exclude, each counting as one condition.
+    pub groups: Vec<(Filter, bool)>,
+    /// Filters notes may match instead of this one, as joined by `OR`. Matching notes get the best score of all filters they match.
+    pub alternatives: Vec<Filter>,
+}
+
+impl Filter {
+    pub fn new(filter_string: &str, any: bool) -> Self {
+        let (filters, full_text) = filter_string
+            .split_once('|')
+            .map(|(filters, rest)| (filters, Some(rest.to_lowercase())))
+            .unwrap_or((filter_string, None));
+
+        let mut words = split_words(filters).into_iter().peekable();
+        let mut filter = Self::parse_alternatives(&mut words, any, 0);
+        // the full text has to be contained whichever alternative matches
+        if !filter.alternatives.is_empty() {
+            filter = Self {
+                any,
+                groups: vec![(filter, true)],
+                ..Default::default()
+            };
+        }
+        filter.full_text = full_text;
+        filter
+    }
+
+    /// Parses the given words of a filter, joining the filters between `OR` as alternatives.
+    /// Stops at the closing parenthesis of a group, if nested in the given number of groups.
+    fn parse_alternatives<'a>(
+        words: &mut std::iter::Peekable<impl Iterator<Item = &'a str>>,
+        any: bool,
+        depth: usize,
+    ) -> Self {
+        let mut filter = Self::parse_conditions(words, any, depth);
+        while words.next_if_eq(&"OR").is_some() {
+            let alternative = Self::parse_conditions(words, any, depth);
+            filter.alternatives.push(alternative);
+        }
+        filter
+    }
+
+    /// Parses the given words of a filter into conditions until the next `OR` or the closing parenthesis of a group.
+    fn parse_conditions<'a>(
+        words: &mut std::iter::Peekable<impl Iterator<Item = &'a str>>,
+        any: bool,
+        depth: usize,
+    ) -> Self {
+        let mut filter = Self {
+            any,
+            ..Default::default()
+        };
+        let mut title = Vec::new();
+        // NOT negates the following condition or group
+        let mut negated = false;
+
+        while let Some(word) = words.next_if(|word| *word != "OR" && (depth == 0 || *word != ")")) {
+            match word {
+                // conditions have to be fulfilled anyway, unless any condition suffices
+                "AND" | ")" => {}
+                "NOT" => negated = !negated,
+                "(" | "!(" | "-(" => {
+                    let group = Self::parse_alternatives(words, any, depth + 1);
+                    words.next_if_eq(&")");
+                    filter.groups.push((group, (word == "(") != negated));
+                    negated = false;
+                }
+                _ if negated => {
+                    let group =
+                        Self::parse_conditions(&mut std::iter::once(word).peekable(), any, depth);
+                    filter.groups.push((group, false));
+                    negated = false;
+                }
+                _ => filter.push_condition(word, &mut title),
+            }
+        }
+
+        filter.title = title.join(" ");
+        filter
+    }
+
+    /// Adds the condition described by the given word of a filter. Words describing no other condition are added to the given title search.
+    fn push_condition<'a>(&mut self, word: &'a str, title: &mut Vec<&'a str>) {
+        // a leading ! or - turns a condition into an exclusion
+        let (word, included) = match word.strip_prefix(['!', '-']) {
+            Some(rest)
+                if rest.starts_with(['#', '>', '<', '.'])
+                    || rest.starts_with("path:")
+                    || rest.starts_with("in:")
+                    || rest.starts_with("text:")
+                    || CountCondition::parse(rest).is_some()
+                    || DateCondition::parse(rest).is_some() =>
+            {
+                (rest, false)
+            }
+            _ => (word, true),
+        };
+
+        if word.starts_with('#') {
+            self.tags.push((word.to_string(), included));
+        } else if let Some(link) = word.strip_prefix('>') {
+            self.links
+                .push((super::name_to_id(link).to_string(), included));
+        } else if let Some(blink) = word.strip_prefix('<') {
+            self.blinks
+                .push((super::name_to_id(blink).to_string(), included));
+        } else if let Some(folder) = word.strip_prefix("path:") {
+            self.paths
+                .push((std::path::PathBuf::from(folder), included));
+        } else if let Some(collection) = word.strip_prefix("in:") {
+            self.collections.push((collection.to_string(), included));
+        } else if let Some(text) = word.strip_prefix("text:") {
+            self.content.push((text.to_lowercase(), included));
+        } else if let Some(condition) = word
+            .strip_prefix('.')
+            .and_then(super::ColumnCondition::parse)
+        {
+            self.columns.push((condition, included));
+        } else if let Some(condition) = CountCondition::parse(word) {
+            self.counts.push((condition, included));
+        } else if let Some(condition) = DateCondition::parse(word) {
+            self.dates.push((condition, included));
+        } else {
+            // if nothing else fits
+            title.push(word);
+        }
+    }
+
+    /// Creates a filter matching only the notes in the given folder, relative to the vault.
+    pub fn folder(folder: std::path::PathBuf) -> Self {
+        Self {
+            paths: vec![(folder, true)],
+            ..Default::default()
+        }
+    }
+
+    /// Restricts the filter to notes matching all of the given filters as well.
+    pub fn with_scope(mut self, scope: &[Filter]) -> Self {
+        self.scope = scope.to_vec();
+        self
+    }
+
+    /// Hides notes with any of the given tags or their subtags, unless the filter explicitly asks for them.
+    pub fn with_excluded_tags(mut self, tags: &[String]) -> Self {
+        self.excluded_tags = tags
+            .iter()
+            .map(|tag| format!("#{}", tag.trim_start_matches('#')))
+            .collect();
+        self
+    }
+
+    /// Wether the filter, its scope, its groups or its alternatives include notes with the given tag, one of its subtags or one of its parent tags.
+    fn asks_for(&self, tag: &str) -> bool {
+        self.tags.iter().any(|(other, included)| {
+            *included
+                && (other == tag
+                    || other.starts_with(&format!("{}/", tag))
+                    || tag.starts_with(&format!("{}/", other)))
+        }) || self.scope.iter().any(|scope| scope.asks_for(tag))
+            || self
+                .groups
+                .iter()
+                .any(|(group, included)| *included && group.asks_for(tag))
+            || self
+                .alternatives
+                .iter()
+                .any(|alternative| alternative.asks_for(tag))
+    }
+
+    /// Applies the given change to the groups and alternatives of this filter as well.
+    fn nested(mut self, change: impl Fn(Self) -> Self + Copy) -> Self {
+        self.groups = std::mem::take(&mut self.groups)
+            .into_iter()
+            .map(|(group, included)| (group.nested(change), included))
+            .collect();
+        self.alternatives = std::mem::take(&mut self.alternatives)
+            .into_iter()
+            .map(|alternative| alternative.nested(change))
+            .collect();
+        change(self)
+    }
+
+    /// Looks up the notes of the collections the filter looks in or excludes. Collections that do not exist contain no notes.
+    pub fn with_collections(self, collections: &crate::io::Collections) -> Self {
+        self.nested(|mut filter| {
+            filter.collection_members = filter
+                .collections
+                .iter()
+                .filter_map(|(name, _included)| Some((name.clone(), collections.members(name)?)))
+                .collect();
+            filter
+        })
+    }
+
+    /// Wether the filter, its groups or its alternatives look in or exclude any collections.
+    pub fn uses_collections(&self) -> bool {
+        !self.collections.is_empty()
+            || self
+                .groups
+                .iter()
+                .any(|(group, _)| group.uses_collections())
+            || self
+                .alternatives
+                .iter()
+                .any(|alternative| alternative.uses_collections())
+    }
+
+    /// Ranks the given notes higher when they match, the more recently opened the higher. Expects the oldest note first.
+    pub fn with_recent(self, recent: &[String]) -> Self {
+        self.nested(|mut filter| {
+            filter.recent = recent.to_vec();
+            filter
+        })
+    }
+
+    /// Ranks notes higher when they match, the more often they have been opened the higher.
+    pub fn with_visits(self, visits: &crate::io::Visits) -> Self {
+        let counts = visits.counts();
+        self.nested(|mut filter| {
+            filter.visits = counts.clone();
+            filter
+        })
+    }
+
+    /// Returns the score of the given note matching the title search, if it does.
+    /// Matches in the display title outrank those in the file name, which outrank those in the tags.
+    /// Within each, titles starting with a search word outrank titles containing a word starting with it, which outrank any other match.
+    fn title_score(&self, note: &super::Note) -> Option<i64> {
+        let words = self.title_words();
+
+        let matcher = fuzzy_matcher::skim::SkimMatcherV2::default();
+        let score = |field: &str| -> Option<i64> {
+            let lower = field.to_lowercase();
+            words.iter().try_fold(0, |score, word| {
+                let bonus = if lower.starts_with(word) {
+                    PREFIX_BONUS
+                } else if lower
+                    .split(|c: char| !c.is_alphanumeric())
+                    .any(|part| part.starts_with(word))
+                {
+                    WORD_PREFIX_BONUS
+                } else {
+                    0
+                };
+                matcher
+                    .fuzzy_match(field, word)
+                    .map(|fuzzy| score + fuzzy + bonus)
+            })
+        };
+
+        let tags = note
+            .tags
+            .iter()
+            .map(|tag| tag.trim_start_matches('#'))
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        [
+            score(&note.display_name).map(|score| score * TITLE_WEIGHT),
+            score(&note.name).map(|score| score * NAME_WEIGHT),
+            score(&tags),
+        ]
+        .into_iter()
+        .flatten()
+        .max()
+        .map(|score| {
+            let id = super::name_to_id(&note.name);
+            score
+                + self
+                    .recent
+                    .iter()
+                    .position(|recent| *recent == id)
+                    .map(|position| RECENT_BONUS * (position as i64 + 1) / self.recent.len() as i64)
+                    .unwrap_or_default()
+                + self
+                    .visits
+                    .get(&id)
+                    .zip(self.visits.values().max())
+                    .map(|(visits, max)| VISIT_BONUS * *visits as i64 / *max as i64)
+                    .unwrap_or_default()
+        })
+    }
+
+    /// Returns the score of the given lowercase content word matching one of the terms of the given note, if it does.
+    /// Terms equal to the word outrank terms starting with it, which outrank terms fuzzily matching it. Among these, terms occuring more often rank higher.
+    fn term_score(note: &super::Note, word: &str) -> Option<i64> {
+        let matcher = fuzzy_matcher::skim::SkimMatcherV2::default();
+        let length = word.chars().count();
+
+        note.terms
+            .iter()
+            .filter_map(|(term, occurences)| {
+                let bonus = if term == word {
+                    TERM_EXACT_BONUS
+                } else if term.starts_with(word) {
+                    TERM_PREFIX_BONUS
+                } else if length >= TERM_FUZZY_MIN_LENGTH
+                    && term.chars().count() <= length + TERM_FUZZY_SLACK
+                    && matcher.fuzzy_match(term, word).is_some()
+                {
+                    TERM_FUZZY_BONUS
+                } else {
+                    return None;
+                };
+                Some(bonus + (*occurences).min(TERM_OCCURENCES_CAP) as i64)
+            })
+            .max()
+    }
+
+    /// Returns the lowercase words of the title search, without stopwords unless there are only stopwords.
+    fn title_words(&self) -> Vec<String> {
+        let mut words = self
+            .title
+            .to_lowercase()
+            .split_whitespace()
+            .map(str::to_owned)
+            .collect::<Vec<_>>();
+        if words.iter().any(|word| !STOPWORDS.contains(&word.as_str())) {
+            words.retain(|word| !STOPWORDS.contains(&word.as_str()));
+        }
+        words
+    }
+
+    /// Wether every note matching this filter is known to match the given broader filter as well, so only the notes matching that one need to be checked against this one.
+    /// Detects filters refined by typing, i.e. by extending search words or adding conditions (or, if any condition suffices, removing them).
+    /// May miss other refinements, but never claims one wrongly.
+    pub fn refines(&self, broader: &Filter) -> bool {
+        let conditions = self.conditions();
+        let broader_conditions = broader.conditions();
+
+        // notes matching an alternative may match no condition
+        if !broader.alternatives.is_empty() {
+            return false;
+        }
+        // a filter without conditions matches all notes in its scope
+        if broader_conditions.is_empty() {
+            return self.scope == broader.scope && self.excludes_all_of(broader);
+        }
+        if !self.alternatives.is_empty()
+            || self.any != broader.any
+            || self.scope != broader.scope
+            || !self.excludes_all_of(broader)
+            || conditions.is_empty()
+        {
+            return false;
+        }
+
+        if self.any {
+            // every condition fulfilled must fulfill one of the broader filter
+            conditions.iter().all(|condition| {
+                broader_conditions
+                    .iter()
+                    .any(|broader| condition.implies(broader))
+            })
+        } else {
+            // every condition of the broader filter must be fulfilled by one of this filter
+            broader_conditions.iter().all(|broader| {
+                conditions
+                    .iter()
+                    .any(|condition| condition.implies(broader))
+            })
+        }
+    }
+
+    /// Wether this filter hides all notes with excluded tags the given filter hides.
+    fn excludes_all_of(&self, other: &Filter) -> bool {
+        self.excluded_tags == other.excluded_tags
+            && self
+                .excluded_tags
+                .iter()
+                .all(|tag| !self.asks_for(tag) || other.asks_for(tag))
+    }
+
+    /// Lists the conditions of this filter, see [`Filter::refines`].
+    fn conditions(&self) -> Vec<Condition<'_>> {
+        let mut conditions = self.tags.iter().map(Condition::Tag).collect::<Vec<_>>();
+        conditions.extend(self.links.iter().map(Condition::Link));
+        conditions.extend(self.blinks.iter().map(Condition::Backlink));
+        conditions.extend(self.paths.iter().map(Condition::Path));
+        conditions.extend(self.columns.iter().map(Condition::Column));
+        conditions.extend(self.counts.iter().map(Condition::Count));
+        conditions.extend(self.dates.iter().map(Condition::Date));
+        conditions.extend(self.collections.iter().map(|collection| {
+            Condition::Collection(collection, self.collection_members.get(&collection.0))
+        }));
+        if let Some(text) = &self.full_text {
+            conditions.push(Condition::FullText(text));
+        }
+        conditions.extend(self.content.iter().map(Condition::Content));
+        conditions.extend(self.groups.iter().map(Condition::Group));
+        if !self.title.is_empty() {
+            conditions.push(Condition::Title(self.title_words()));
+        }
+        conditions
+    }
+
+    pub fn apply(&self, note: &super::Note, index: &super::NoteIndex) -> Option<i64> {
+        self.evaluate(note, index, |text| {
+            std::fs::read_to_string(&note.path)
+                .map(|content| content.to_lowercase().contains(text))
+                .unwrap_or(false)
+        })
+    }
+
+    /// Like [`Filter::apply`], but told wether the full text of the note contains the searched text instead of reading it.
+    pub fn apply_with_text(
+        &self,
+        note: &super::Note,
+        index: &super::NoteIndex,
+        contains_text: bool,
+    ) -> Option<i64> {
+        self.evaluate(note, index, |_text| contains_text)
+    }
+
+    /// Like [`Filter::apply`], but without reading the full text of the note.
+    /// Returns `None` if the result depends on wether it contains the searched text, which is then left to [`Filter::apply_with_text`].
+    pub fn apply_without_reading(
+        &self,
+        note: &super::Note,
+        index: &super::NoteIndex,
+    ) -> Option<Option<i64>> {
+        let without = self.apply_with_text(note, index, false);
+        if self.full_text.is_none() || self.apply_with_text(note, index, true) == without {
+            Some(without)
+        } else {
+            None
+        }
+    }
+
+    /// Matches the note against the filter and its alternatives, checking its full text for the searched text with the given function.
+    fn evaluate(
+        &self,
+        note: &super::Note,
+        index: &super::NoteIndex,
+        contains_text: impl FnOnce(&str) -> bool,
+    ) -> Option<i64> {
+        self.evaluate_conditions(note, index, contains_text)
+            .into_iter()
+            .chain(
+                self.alternatives
+                    .iter()
+                    .filter_map(|alternative| alternative.apply_with_text(note, index, false)),
+            )
+            .max()
+    }
+
+    /// Matches the note against the conditions of the filter itself, see [`Filter::evaluate`].
+    fn evaluate_conditions(
+        &self,
+        note: &super::Note,
+        index: &super::NoteIndex,
+        contains_text: impl FnOnce(&str) -> bool,
+    ) -> Option<i64> {
+        // notes outside of the scope never match
+        if self
+            .scope
+            .iter()
+            .any(|scope| scope.apply(note, index).is_none())
+        {
+            return None;
+        }
+
+        // === === TAGS === ===
+
+        // notes with excluded tags only match if asked for explicitly
+        if self
+            .excluded_tags
+            .iter()
+            .any(|tag| !self.asks_for(tag) && has_tag(note, tag))
+        {
+            return None;
+        }
+
+        let mut any = false;
+        let mut all = true;
+        for (tag, included) in self.tags.iter() {
+            // inclusion: We _want_ the note to have the tag
+            // exclusion: We _dont_ want the note to have the tag
+            if has_tag(note, tag) == *included {
+                // this did match our expectation, so at least one condition (this one) is true
+                any = true;
+            } else {
+                // this did not match our expectation, so not all conditions can be true
+                all = false;
+            }
+        }
+
+        // === === PATHS === ===
+
+        if !self.paths.is_empty() {
+            let relative = index.relative_path(&note.path);
+            for (folder, included) in self.paths.iter() {
+                if relative.starts_with(folder) == *included {
+                    any = true;
+                } else {
+                    all = false;
+                }
+            }
+        }
+
+        // === === LINKS === ===
+
+        // go through all links
+        for (link, included) in self.links.iter() {
+            // if the links is contained and we want it to be contained or not contained and we want it to be not contained
+            if note.links.contains(link) == *included {
+                // at least one condition (this one) is true
+                any = true;
+            } else {
+                // else, at least one condition is false, so not all of them are true
+                all = false;
+            }
+        }
+
+        // go through all backlinks
+        for (blink, included) in self.blinks.iter() {
+            // check if the note with the blink-ID links to the main one passed to this function
+            let exists_and_contains = if let Some(other_note) = index.inner.get(blink) {
+                other_note.links.contains(&super::name_to_id(&note.name))
+            } else {
+                false
+            };
+
+            // if the backlink exists and we want that, set any/all as above
+            if exists_and_contains == *included {
+                any = true;
+            } else {
+                all = false;
+            }
+        }
+
+        // === === COLLECTIONS === ===
+
+        for (name, included) in self.collections.iter() {
+            if self
+                .collection_members
+                .get(name)
+                .is_some_and(|ids| ids.contains(&super::name_to_id(&note.name)))
+                == *included
+            {
+                any = true;
+            } else {
+                all = false;
+            }
+        }
+
+        // === === COLUMNS === ===
+
+        // notes without a data table fulfill no column conditions
+        for (condition, included) in self.columns.iter() {
+            if note
+                .data
+                .as_ref()
+                .is_some_and(|data| condition.matches(data))
+                == *included
+            {
+                any = true;
+            } else {
+                all = false;
+            }
+        }
+
+        if let Some(text) = &self.full_text {
+            if contains_text(text) {
+                any = true;
+            } else {
+                all = false;
+            }
+        }
+
+        // === === COUNTS === ===
+
+        for (condition, included) in self.counts.iter() {
+            if condition.matches(note, index) == *included {
+                any = true;
+            } else {
+                all = false;
+            }
+        }
+
+        // === === DATES === ===
+
+        // notes without the date fulfill no date conditions
+        for (condition, included) in self.dates.iter() {
+            if condition.matches(note) == *included {
+                any = true;
+            } else {
+                all = false;
+            }
+        }
+
+        // === === GROUPS === ===
+
+        // notes matching a group looked for rank higher the better they match it
+        let mut group_score = 0;
+        for (group, included) in self.groups.iter() {
+            let score = group.apply_with_text(note, index, false);
+            if score.is_some() == *included {
+                any = true;
+                group_score += score.unwrap_or_default();
+            } else {
+                all = false;
+            }
+        }
+
+        // === === CONTENT === ===
+
+        // notes containing the words looked for rank higher the better they match
+        let mut content_score = 0;
+        for (word, included) in self.content.iter() {
+            let score = Self::term_score(note, word);
+            if score.is_some() == *included {
+                any = true;
+                content_score += score.unwrap_or_default();
+            } else {
+                all = false;
+            }
+        }
+
+        let fuz_match = if self.title.is_empty() {
+            None
+        } else {
+            let fuzzy_match = self.title_score(note);
+            if fuzzy_match.is_some() {
+                any = true;
+            } else {
+                all = false;
+            }
+            fuzzy_match
+        };
+        // if all conditions are empty, return match score (only title search)
+        if self.tags.is_empty() && self.links.is_empty() && self.blinks.is_empty() && self.paths.is_empty() && self.columns.is_empty() && self.counts.is_empty() && self.dates.is_empty() && self.collections.is_empty() && self.full_text.is_none() && self.content.is_empty() && self.groups.is_empty() && self.title.is_empty()  ||
+            // also return match score if the required amount of conditions are fulfilled
+            (!self.any && all || self.any && any)
+        {
+            fuz_match
+                .or(Some(0))
+                .map(|score| score + content_score + group_score)
+        } else {
+            // else, an exclusion criterion was triggered
+            None
+        }
+    }
+}
+
+/// A number describing a note, which filters can compare.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NoteCount {
+    /// `words`: The number of words.
+    Words,
+    /// `chars`: The number of characters.
+    Characters,
+    /// `links`: The number of distinct notes linked to, existing or not.
+    Links,
+    /// `backlinks`: The number of distinct notes linking to the note.
+    Backlinks,
+}
+
+/// A condition on a number describing a note, like `words>500`, written in the filter bar.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CountCondition {
+    /// The number compared.
+    pub count: NoteCount,
+    /// How the number is compared to the value. Never checks for existence or containment.
+    pub comparison: super::Comparison,
+    /// The value the number is compared to.
+    pub value: usize,
+}
+
+impl CountCondition {
+    /// Parses a condition of the form `words>500`, with any comparison of column conditions except `~` instead of `>`.
+    pub fn parse(condition: &str) -> Option<Self> {
+        use super::Comparison;
+
+        let parsed = super::ColumnCondition::parse(condition)?;
+        let count = match parsed.column.as_str() {
+            "words" => NoteCount::Words,
+            "chars" => NoteCount::Characters,
+            "links" => NoteCount::Links,
+            "backlinks" => NoteCount::Backlinks,
+            _ => return None,
+        };
+        if matches!(parsed.comparison, Comparison::Exists | Comparison::Contains) {
+            return None;
+        }
+
+        Some(Self {
+            count,
+            comparison: parsed.comparison,
+            value: parsed.value.trim().parse().ok()?,
+        })
+    }
+
+    /// Wether the given note of the given index fulfills the condition.
+    pub fn matches(&self, note: &super::Note, index: &super::NoteIndex) -> bool {
+        use super::Comparison;
+
+        let number = match self.count {
+            NoteCount::Words => note.words,
+            NoteCount::Characters => note.characters,
+            NoteCount::Links => note.links.iter().unique().count(),
+            NoteCount::Backlinks => index.backlink_count(&super::name_to_id(&note.name)),
+        };
+
+        match self.comparison {
+            Comparison::Equal => number == self.value,
+            Comparison::NotEqual => number != self.value,
+            Comparison::Less => number < self.value,
+            Comparison::LessOrEqual => number <= self.value,
+            Comparison::Greater => number > self.value,
+            Comparison::GreaterOrEqual => number >= self.value,
+            Comparison::Exists | Comparison::Contains => false,
+        }
+    }
+}
+
+/// A date of a note, which filters can compare.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NoteDate {
+    /// `created`: The creation date, as specified in the frontmatter or file name.
+    Created,
+    /// `modified`: The day of the last modification of the note file.
+    Modified,
+    /// `date`: The creation date if known, otherwise the day of the last modification, see [`super::Note::date`].
+    Date,
+}
+
+/// A condition on a date of a note, like `modified>2024-01-01`, written in the filter bar.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DateCondition {
+    /// The date compared.
+    pub date: NoteDate,
+    /// How the date is compared to the value. Never checks for existence or containment.
+    pub comparison: super::Comparison,
+    /// The day the date is compared to.
+    pub value: chrono::NaiveDate,
+}
+
+impl DateCondition {
+    /// Parses a condition of the form `modified>2024-01-01`, with any comparison of column conditions except `~` instead of `>`.
+    pub fn parse(condition: &str) -> Option<Self> {
+        use super::Comparison;
+
+        let parsed = super::ColumnCondition::parse(condition)?;
+        let date = match parsed.column.as_str() {
+            "created" => NoteDate::Created,
+            "modified" => NoteDate::Modified,
+            "date" => NoteDate::Date,
+            _ => return None,
+        };
+        if matches!(parsed.comparison, Comparison::Exists | Comparison::Contains) {
+            return None;
+        }
+
+        Some(Self {
+            date,
+            comparison: parsed.comparison,
+            value: chrono::NaiveDate::parse_from_str(parsed.value.trim(), "%Y-%m-%d").ok()?,
+        })
+    }
+
+    /// Wether the given note fulfills the condition. Notes without the compared date never do.
+    pub fn matches(&self, note: &super::Note) -> bool {
+        use super::Comparison;
+
+        let date = match self.date {
+            NoteDate::Created => note.created,
+            NoteDate::Modified => note.modified.map(|modified| modified.date_naive()),
+            NoteDate::Date => note.date(),
+        };
+        let Some(date) = date else {
+            return false;
+        };
+
+        match self.comparison {
+            Comparison::Equal => date == self.value,
+            Comparison::NotEqual => date != self.value,
+            Comparison::Less => date < self.value,
+            Comparison::LessOrEqual => date <= self.value,
+            Comparison::Greater => date > self.value,
+            Comparison::GreaterOrEqual => date >= self.value,
+            Comparison::Exists | Comparison::Contains => false,
+        }
+    }
+}
+
+/// A single condition of a filter, see [`Filter::refines`].
+#[derive(Debug, PartialEq)]
+enum Condition<'a> {
+    Tag(&'a (String, bool)),
+    Link(&'a (String, bool)),
+    Backlink(&'a (String, bool)),
+    Path(&'a (std::path::PathBuf, bool)),
+    Column(&'a (super::ColumnCondition, bool)),
+    Count(&'a (CountCondition, bool)),
+    Date(&'a (DateCondition, bool)),
+    /// The members are part of the condition, so filters are not mistaken for refinements once collections change.
+    Collection(&'a (String, bool), Option<&'a HashSet<String>>),
+    FullText(&'a str),
+    Content(&'a (String, bool)),
+    Group(&'a (Filter, bool)),
+    Title(Vec<String>),
+}
+
+impl Condition<'_> {
+    /// Wether every note fulfilling this condition fulfills the given one as well.
+    fn implies(&self, other: &Condition) -> bool {
+        match (self, other) {
+            // a longer text can only be contained where the shorter one is
+            (Condition::FullText(text), Condition::FullText(other)) => text.contains(other),
+            // a word fuzzy matches wherever a longer word starting with it does
+            (Condition::Title(words), Condition::Title(other)) => other
+                .iter()
+                .all(|other| words.iter().any(|word| word.starts_with(other.as_str()))),
+            _ => self == other,
+        }
+    }
+}
+
+/// Splits the given filter into its words, with opening and closing parentheses as words of their own.
+/// An exclamation mark or dash directly before an opening parenthesis stays attached to it.
+fn split_words(filters: &str) -> Vec<&str> {
+    let mut words = Vec::new();
+    for mut word in filters.split_whitespace() {
+        loop {
+            if let Some(rest) = word.strip_prefix('(') {
+                words.push("(");
+                word = rest;
+            } else if let Some(rest) = word.strip_prefix("!(").or_else(|| word.strip_prefix("-(")) {
+                words.push(&word[..2]);
+                word = rest;
+            } else {
+                break;
+            }
+        }
+        let inner = word.trim_end_matches(')');
+        if !inner.is_empty() {
+            words.push(inner);
+        }
+        words.extend(std::iter::repeat_n(")", word.len() - inner.len()));
+    }
+    words
+}
+
+/// Wether the given note has the given tag or one of its subtags.
+fn has_tag(note: &super::Note, tag: &str) -> bool {
+    note
+        // go over all tags
+        .tags
+        .iter()
+        // split each tag into..
+        .flat_map(|tag| {
+            // an iterator of substring starting at 0 and going to every appearance to /
+            tag.match_indices('/')
+                .map(|(index, _match)| &tag[0..index])
+                // and appended just a substring that is the whole tag
+                .chain(std::iter::once(tag.as_str()))
+            // flatten this so we have just an iterator over (sub)strs
+        })
+        // check if any of these substring is the searched tag
+        .any(|subtag| subtag == tag)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{data, io};
+
+    #[test]
+    fn test_filter() {
+        let config = crate::Config::default();
+        let tracker = io::FileTracker::new(&config, std::path::PathBuf::from("./tests")).unwrap();
+        let builder = io::HtmlBuilder::new(&config, std::path::PathBuf::from("./tests"));
+        let index = data::NoteIndex::new(tracker, builder, Default::default()).0;
+
+        assert_eq!(index.inner.len(), 12);
+
+        let linux = index.inner.get("linux").unwrap();
+        let win = index.inner.get("windows").unwrap();
+        let osx = index.inner.get("osx").unwrap();
+
+        // === Filter 1 ===
+
+        let filter1 = Filter {
+            any: false,
+            tags: vec![("#os".to_string(), true), ("#os/win".to_string(), false)],
+            links: vec![],
+            blinks: vec![],
+            paths: vec![],
+            columns: vec![],
+            counts: vec![],
+            dates: vec![],
+            collections: vec![],
+            collection_members: Default::default(),
+            title: String::new(),
+            full_text: None,
+            content: vec![],
+            recent: vec![],
+            visits: Default::default(),
+            excluded_tags: vec![],
+            scope: vec![],
+            groups: vec![],
+            alternatives: vec![],
+        };
+
+        assert!(filter1.apply(linux, &index).is_some());
+        assert!(filter1.apply(osx, &index).is_some());
+        assert!(filter1.apply(win, &index).is_none());
+    }
+
+    #[test]
+    fn test_filter_from_string() {
+        let config = crate::Config::default();
+        let tracker = io::FileTracker::new(&config, std::path::PathBuf::from("./tests")).unwrap();
+        let builder = io::HtmlBuilder::new(&config, std::path::PathBuf::from("./tests"));
+        let index = data::NoteIndex::new(tracker, builder, Default::default()).0;
+
+        assert_eq!(index.inner.len(), 12);
+
+        // === Filter 2 ===
+
+        let filter2 = Filter::new("!#lietheo #diffgeo >Manifold !>atlas", false);
+
+        assert_eq!(
+            filter2.tags,
+            vec![
+                ("#lietheo".to_string(), false),
+                ("#diffgeo".to_string(), true)
+            ]
+        );
+        assert_eq!(
+            filter2.links,
+            vec![("manifold".to_string(), true), ("atlas".to_string(), false)]
+        );
+        assert_eq!(filter2.title, "");
+
+        let liegroup = index.inner.get("lie-group").unwrap();
+        let chart = index.inner.get("chart").unwrap();
+        let manifold = index.inner.get("manifold").unwrap();
+        let smoothmap = index.inner.get("smooth-map").unwrap();
+        let topology = index.inner.get("topology").unwrap();
+
+        assert!(filter2.apply(liegroup, &index).is_none());
+        assert!(filter2.apply(chart, &index).is_some());
+        assert!(filter2.apply(manifold, &index).is_none());
+        assert!(filter2.apply(smoothmap, &index).is_none());
+        assert!(filter2.apply(topology, &index).is_none());
+    }
+
+    #[test]
+    fn test_filter_from_string_all() {
+        // === Filter 3 ===
+        let filter3 = Filter::new(
+            "!#topology #os >TopologY !>Smooth-mAp <atlas !<linux |equivalent",
+            false,
+        );
+
+        assert_eq!(
+            filter3.tags,
+            vec![("#topology".to_string(), false), ("#os".to_string(), true)]
+        );
+        assert_eq!(
+            filter3.links,
+            vec![
+                ("topology".to_string(), true),
+                ("smooth-map".to_string(), false)
+            ]
+        );
+        assert_eq!(
+            filter3.blinks,
+            vec![("atlas".to_string(), true), ("linux".to_string(), false)]
+        );
+        assert_eq!(filter3.title, "");
+
+        assert_eq!(filter3.full_text, Some(String::from("equivalent")));
+    }
+
+    #[test]
+    fn test_filter_from_string_case_insensitive() {
+        // === Filter 4 ===
+        let filter4 = Filter::new("<aTlas >Smooth-mAp", true);
+
+        assert_eq!(filter4.tags, vec![]);
+        assert_eq!(filter4.links, vec![("smooth-map".to_string(), true),]);
+        assert_eq!(filter4.blinks, vec![("atlas".to_string(), true)]);
+        assert_eq!(filter4.title, "");
+    }
+
+    #[test]
+    fn test_filter_ranking() {
+        let config = crate::Config::default();
+        let tracker = io::FileTracker::new(&config, std::path::PathBuf::from("./tests")).unwrap();
+        let builder = io::HtmlBuilder::new(&config, std::path::PathBuf::from("./tests"));
+        let index = data::NoteIndex::new(tracker, builder, Default::default()).0;
+
+        let score = |filter: &Filter, id: &str| filter.apply(index.inner.get(id).unwrap(), &index);
+
+        // stopwords are left out, unless there is nothing else
+        let filter = Filter::new("the lie of", false);
+        assert_eq!(filter.title, "the lie of");
+        assert!(score(&filter, "lie-group").is_some());
+        assert!(score(&filter, "topology").is_none());
+
+        // prefix matches outrank infix matches
+        let filter = Filter::new("at", false);
+        assert!(score(&filter, "atlas") > score(&filter, "chart"));
+        assert!(score(&filter, "atlas") > score(&filter, "operating-systems"));
+
+        // title matches outrank tag matches, file names are matched as well
+        let filter = Filter::new("os", false);
+        assert!(score(&filter, "linux").is_some());
+        assert!(score(&filter, "osx") > score(&filter, "linux"));
+        assert!(score(&Filter::new("note25", false), "note25").is_some());
+
+        // recently opened notes get a boost
+        let recent = Filter::new("at", false).with_recent(&[String::from("atlas")]);
+        assert_eq!(
+            score(&recent, "atlas"),
+            score(&Filter::new("at", false), "atlas").map(|score| score + RECENT_BONUS)
+        );
+
+        // so do frequently visited notes, relative to the most visited one
+        let visited = Filter {
+            visits: HashMap::from([(String::from("atlas"), 2), (String::from("chart"), 4)]),
+            ..Filter::new("at", false)
+        };
+        assert_eq!(
+            score(&visited, "atlas"),
+            score(&Filter::new("at", false), "atlas").map(|score| score + VISIT_BONUS / 2)
+        );
+    }
+
+    #[test]
+    fn test_filter_exclusions() {
+        let config = crate::Config::default();
+        let tracker = io::FileTracker::new(&config, std::path::PathBuf::from("./tests")).unwrap();
+        let builder = io::HtmlBuilder::new(&config, std::path::PathBuf::from("./tests"));
+        let index = data::NoteIndex::new(tracker, builder, Default::default()).0;
+
+        let filter = Filter::new(
+            "-#draft !path:archive/ path:common/notes/math -<atlas -word",
+            false,
+        );
+        assert_eq!(filter.tags, vec![("#draft".to_string(), false)]);
+        assert_eq!(
+            filter.paths,
+            vec![
+                (std::path::PathBuf::from("archive"), false),
+                (std::path::PathBuf::from("common/notes/math"), true)
+            ]
+        );
+        assert_eq!(filter.blinks, vec![("atlas".to_string(), false)]);
+        assert_eq!(filter.title, "-word");
+
+        let matches = |filter: &Filter, id: &str| {
+            filter.apply(index.inner.get(id).unwrap(), &index).is_some()
+        };
+
+        // folders
+        let filter = Filter::new("path:common/notes/math", false);
+        assert!(matches(&filter, "chart"));
+        assert!(!matches(&filter, "linux"));
+        let filter = Filter::new("-path:common/notes/math/", false);
+        assert!(!matches(&filter, "chart"));
+        assert!(matches(&filter, "linux"));
+
+        // excluded tags are hidden unless asked for
+        let excluded = [String::from("os")];
+        let filter = Filter::new("", false).with_excluded_tags(&excluded);
+        assert!(!matches(&filter, "linux"));
+        assert!(!matches(&filter, "osx"));
+        assert!(matches(&filter, "chart"));
+        let filter = Filter::new("#os/linux", false).with_excluded_tags(&excluded);
+        assert!(matches(&filter, "linux"));
+        let filter = Filter::new("#os", false).with_excluded_tags(&excluded);
+        assert!(matches(&filter, "linux"));
+        assert!(matches(&filter, "osx"));
+    }
+
+    #[test]
+    fn test_filter_scope() {
+        let config = crate::Config::default();
+        let tracker = io::FileTracker::new(&config, std::path::PathBuf::from("./tests")).unwrap();
+        let builder = io::HtmlBuilder::new(&config, std::path::PathBuf::from("./tests"));
+        let index = data::NoteIndex::new(tracker, builder, Default::default()).0;
+
+        let matches = |filter: &Filter, id: &str| {
+            filter.apply(index.inner.get(id).unwrap(), &index).is_some()
+        };
+
+        let math = [Filter::folder(std::path::PathBuf::from(
+            "common/notes/math",
+        ))];
+        let filter = Filter::new("", false).with_scope(&math);
+        assert!(matches(&filter, "chart"));
+        assert!(matches(&filter, "topology"));
+        assert!(!matches(&filter, "linux"));
+
+        // the filter is applied within the scope
+        let filter = Filter::new("#topology", false).with_scope(&math);
+        assert!(!matches(&filter, "atlas"));
+        assert!(matches(&filter, "topology"));
+
+        // scopes can ask for excluded tags
+        let filter = Filter::new("", false)
+            .with_scope(&[Filter::new("#os", false)])
+            .with_excluded_tags(&[String::from("#os")]);
+        assert!(matches(&filter, "linux"));
+        assert!(!matches(&filter, "chart"));
+    }
+
+    #[test]
+    fn test_filter_columns() {
+        let config = crate::Config::default();
+        let tracker = io::FileTracker::new(&config, std::path::PathBuf::from("./tests")).unwrap();
+        let builder = io::HtmlBuilder::new(&config, std::path::PathBuf::from("./tests"));
+        let index = data::NoteIndex::new(tracker, builder, Default::default()).0;
+
+        let inventory = data::Note::from_content(
+            std::path::Path::new("Inventory.md"),
+            "| Item | Count |\n| --- | --- |\n| Ruler | 12 |\n| Compass | 3 |\n",
+            &Default::default(),
+        )
+        .unwrap();
+        let chart = index.inner.get("chart").unwrap();
+
+        let filter = Filter::new(".count>10 .item~pass -.price", false);
+        assert_eq!(filter.columns.len(), 3);
+        assert_eq!(filter.title, "");
+        assert!(filter.apply(&inventory, &index).is_some());
+        assert!(filter.apply(chart, &index).is_none());
+
+        let filter = Filter::new(".count>12", false);
+        assert!(filter.apply(&inventory, &index).is_none());
+
+        // notes without data only match exclusions
+        let filter = Filter::new("!.count", false);
+        assert!(filter.apply(&inventory, &index).is_none());
+        assert!(filter.apply(chart, &index).is_some());
+    }
+
+    #[test]
+    fn test_filter_refines() {
+        let refines = |narrow: &str, broad: &str, any: bool| {
+            Filter::new(narrow, any).refines(&Filter::new(broad, any))
+        };
+
+        // typing extends words and adds conditions
+        assert!(refines("topo", "", false));
+        assert!(refines("topo", "top", false));
+        assert!(refines("topology #math", "topology", false));
+        assert!(refines("the atlas", "atl", false));
+        // stopwords are only searched for on their own
+        assert!(!refines("the atlas", "the", false));
+        assert!(refines("#math | chart", "#math | char", false));
+        assert!(refines("#math", "#math", false));
+        assert!(!refines("top", "topo", false));
+        assert!(!refines("#mat", "#ma", false));
+        assert!(!refines("#math | char", "#math | chart", false));
+        assert!(!refines("", "topo", false));
+
+        // if any condition suffices, only removing conditions refines
+        assert!(refines("#math", "#math #os", true));
+        assert!(refines("topology", "topo #os", true));
+        assert!(!refines("#math #os", "#math", true));
+        assert!(!refines("#math", "#os", true));
+
+        // both filters need to hide the same notes
+        let excluded = [String::from("#os")];
+        let broad = Filter::new("", false).with_excluded_tags(&excluded);
+        assert!(Filter::new("linux", false)
+            .with_excluded_tags(&excluded)
+            .refines(&broad));
+        assert!(!Filter::new("#os", false)
+            .with_excluded_tags(&excluded)
+            .refines(&broad));
+        assert!(!Filter::new("linux", false).refines(&broad));
+        let scope = [Filter::folder(std::path::PathBuf::from("common"))];
+        assert!(!Filter::new("linux", false)
+            .with_scope(&scope)
+            .refines(&Filter::new("linux", false)));
+    }
+
+    #[test]
+    fn test_filter_without_reading() {
+        let config = crate::Config::default();
+        let tracker = io::FileTracker::new(&config, std::path::PathBuf::from("./tests")).unwrap();
+        let builder = io::HtmlBuilder::new(&config, std::path::PathBuf::from("./tests"));
+        let index = data::NoteIndex::new(tracker, builder, Default::default()).0;
+
+        let chart = index.inner.get("chart").unwrap();
+        let linux = index.inner.get("linux").unwrap();
+
+        // the full text only decides for notes fulfilling the other conditions
+        let filter = Filter::new("#topology |change of charts", false);
+        assert_eq!(filter.apply_without_reading(chart, &index), None);
+        assert_eq!(filter.apply_without_reading(linux, &index), Some(None));
+        assert!(filter.apply_with_text(chart, &index, true).is_some());
+        assert!(filter.apply(chart, &index).is_some());
+
+        // if any condition suffices, only for notes fulfilling none of them
+        let filter = Filter::new("#topology |change of charts", true);
+        assert_eq!(filter.apply_without_reading(chart, &index), Some(Some(0)));
+        assert_eq!(filter.apply_without_reading(linux, &index), None);
+        assert!(filter.apply_with_text(linux, &index, false).is_none());
+
+        let filter = Filter::new("#topology", false);
+        assert_eq!(
+            filter.apply_without_reading(chart, &index),
+            Some(filter.apply(chart, &index))
+        );
+    }
+
+    #[test]
+    fn test_filter_content() {
+        let config = crate::Config::default();
+        let tracker = io::FileTracker::new(&config, std::path::PathBuf::from("./tests")).unwrap();
+        let builder = io::HtmlBuilder::new(&config, std::path::PathBuf::from("./tests"));
+        let index = data::NoteIndex::new(tracker, builder, Default::default()).0;
+
+        let manifold = index.inner.get("manifold").unwrap();
+        let linux = index.inner.get("linux").unwrap();
+
+        let filter = Filter::new("text:Paracompact !text:kernel", false);
+        assert_eq!(
+            filter.content,
+            vec![
+                ("paracompact".to_string(), true),
+                ("kernel".to_string(), false)
+            ]
+        );
+        assert!(filter.apply(manifold, &index).is_some());
+        assert!(filter.apply(linux, &index).is_none());
+        // the content is never read, only its indexed terms
+        assert_eq!(
+            filter.apply_without_reading(manifold, &index),
+            Some(filter.apply(manifold, &index))
+        );
+
+        // exact terms outrank the start of terms, which outrank fuzzy matches
+        let exact = Filter::new("text:paracompact", false).apply(manifold, &index);
+        let prefix = Filter::new("text:paracomp", false).apply(manifold, &index);
+        let fuzzy = Filter::new("text:paracmpact", false).apply(manifold, &index);
+        assert!(exact > prefix);
+        assert!(prefix > fuzzy);
+        assert!(fuzzy.is_some());
+
+        // short words are not matched fuzzily
+        assert!(Filter::new("text:pct", false)
+            .apply(manifold, &index)
+            .is_none());
+    }
+
+    #[test]
+    fn test_filter_counts() {
+        let config = crate::Config::default();
+        let tracker = io::FileTracker::new(&config, std::path::PathBuf::from("./tests")).unwrap();
+        let builder = io::HtmlBuilder::new(&config, std::path::PathBuf::from("./tests"));
+        let index = data::NoteIndex::new(tracker, builder, Default::default()).0;
+
+        let filter = Filter::new("words>=41 !links>5 chars<100000 wordsy>3 links~2", false);
+        assert_eq!(
+            filter.counts,
+            vec![
+                (
+                    CountCondition {
+                        count: NoteCount::Words,
+                        comparison: data::Comparison::GreaterOrEqual,
+                        value: 41
+                    },
+                    true
+                ),
+                (
+                    CountCondition {
+                        count: NoteCount::Links,
+                        comparison: data::Comparison::Greater,
+                        value: 5
+                    },
+                    false
+                ),
+                (
+                    CountCondition {
+                        count: NoteCount::Characters,
+                        comparison: data::Comparison::Less,
+                        value: 100000
+                    },
+                    true
+                ),
+            ]
+        );
+        // anything else is searched in the title
+        assert_eq!(filter.title, "wordsy>3 links~2");
+
+        let matching = |filter: &str| {
+            let filter = Filter::new(filter, false);
+            index
+                .inner
+                .iter()
+                .filter(|(_id, note)| filter.apply(note, &index).is_some())
+                .map(|(id, _note)| id.as_str())
+                .sorted()
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(matching("words=34"), ["operating-systems"]);
+        assert!(matching("links=3").contains(&"lie-group"));
+        assert!(!matching("links!=3").contains(&"lie-group"));
+        // notes without links or backlinks
+        let orphans = matching("links=0 backlinks=0");
+        assert!(orphans
+            .iter()
+            .all(|id| { index.inner[*id].links.is_empty() && index.backlinks(id).is_empty() }));
+        assert!(orphans.contains(&"books"));
+        assert_eq!(matching("backlinks>100"), Vec::<&str>::new());
+    }
+
+    #[test]
+    fn test_filter_dates() {
+        let vault = testdir::testdir!();
+        std::fs::write(
+            vault.join("Spring.md"),
+            "---\ncreated: 2024-03-01\n---\n# Spring\n",
+        )
+        .unwrap();
+        std::fs::write(vault.join("2023-12-24 Eve.md"), "# Eve\n").unwrap();
+        std::fs::write(vault.join("Undated.md"), "# Undated\n").unwrap();
+
+        let config = crate::Config::default();
+        let tracker = io::FileTracker::new(&config, vault.clone()).unwrap();
+        let builder = io::HtmlBuilder::new(&config, vault);
+        let index = data::NoteIndex::new(tracker, builder, Default::default()).0;
+        let today = chrono::Local::now().date_naive().format("%Y-%m-%d");
+
+        let filter = Filter::new(
+            "created>=2024-01-01 !modified<2000-01-01 date>2024-13-01",
+            false,
+        );
+        assert_eq!(
+            filter.dates,
+            vec![
+                (
+                    DateCondition {
+                        date: NoteDate::Created,
+                        comparison: data::Comparison::GreaterOrEqual,
+                        value: chrono::NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
+                    },
+                    true
+                ),
+                (
+                    DateCondition {
+                        date: NoteDate::Modified,
+                        comparison: data::Comparison::Less,
+                        value: chrono::NaiveDate::from_ymd_opt(2000, 1, 1).unwrap(),
+                    },
+                    false
+                ),
+            ]
+        );
+        // invalid dates are searched in the title
+        assert_eq!(filter.title, "date>2024-13-01");
+
+        let matching = |filter: &str| {
+            let filter = Filter::new(filter, false);
+            index
+                .inner
+                .iter()
+                .filter(|(_id, note)| filter.apply(note, &index).is_some())
+                .map(|(id, _note)| id.as_str())
+                .sorted()
+                .collect::<Vec<_>>()
+        };
+
+        // creation dates come from the frontmatter or the file name, notes without one never match
+        assert_eq!(matching("created>2024-01-01"), ["spring"]);
+        assert_eq!(matching("created<2024-01-01"), ["2023-12-24-eve"]);
+        assert_eq!(matching("created=2024-03-01"), ["spring"]);
+        // all notes were just modified, and dates fall back to that
+        assert_eq!(
+            matching(&format!("modified={}", today)),
+            ["2023-12-24-eve", "spring", "undated"]
+        );
+        assert_eq!(matching(&format!("date>={}", today)), ["undated"]);
+        assert_eq!(matching("modified<2024-01-01"), Vec::<&str>::new());
+    }
+
+    #[test]
+    fn test_filter_boolean() {
+        let config = crate::Config::default();
+        let tracker = io::FileTracker::new(&config, std::path::PathBuf::from("./tests")).unwrap();
+        let builder = io::HtmlBuilder::new(&config, std::path::PathBuf::from("./tests"));
+        let index = data::NoteIndex::new(tracker, builder, Default::default()).0;
+
+        let matches = |filter: &str| {
+            let filter = Filter::new(filter, false);
+            ["linux", "windows", "osx", "books", "topology", "chart"]
+                .into_iter()
+                .filter(|id| {
+                    filter
+                        .apply_with_text(index.inner.get(*id).unwrap(), &index, true)
+                        .is_some()
+                })
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            split_words("!(#a OR -(#b)) c)"),
+            ["!(", "#a", "OR", "-(", "#b", ")", ")", "c", ")"]
+        );
+
+        // alternatives are joined by OR, conditions within them by AND
+        assert_eq!(
+            matches("#os !#os/win OR #topology"),
+            ["linux", "osx", "topology", "chart"]
+        );
+        assert_eq!(
+            matches("#os AND !#os/win OR #topology #diffgeo"),
+            ["linux", "osx", "chart"]
+        );
+        // groups count as single conditions and can be excluded
+        assert_eq!(
+            matches("(#os/win OR #topology) !#diffgeo"),
+            ["windows", "topology"]
+        );
+        assert_eq!(matches("!(#os OR #topology)"), ["books"]);
+        assert_eq!(matches("NOT (#os OR #topology)"), ["books"]);
+        assert_eq!(matches("#os NOT #os/linux"), ["windows", "osx"]);
+        // unbalanced parentheses are tolerated
+        assert_eq!(matches("(#os/linux OR #os/osx"), ["linux", "osx"]);
+        assert_eq!(matches("#os/linux) OR #os/osx"), ["linux", "osx"]);
+
+        // the full text has to be contained by notes matching any alternative
+        let filter = Filter::new("#os/linux OR #os/osx |great", false);
+        let linux = index.inner.get("linux").unwrap();
+        assert!(filter.apply_with_text(linux, &index, true).is_some());
+        assert!(filter.apply_with_text(linux, &index, false).is_none());
+        assert!(filter.apply(linux, &index).is_some());
+
+        // notes matching an alternative need not fulfill the conditions of the others
+        assert!(!Filter::new("#os OR #diffgeo", false).refines(&Filter::new("#os", false)));
+        assert!(!Filter::new("#os", false).refines(&Filter::new("#os OR #diffgeo", false)));
+        assert!(Filter::new("#os (#os/win OR #os/osx)", false).refines(&Filter::new("#os", false)));
+    }
+
+    #[test]
+    fn test_filter_collections() {
+        let config = crate::Config::default();
+        let tracker = io::FileTracker::new(&config, std::path::PathBuf::from("./tests")).unwrap();
+        let builder = io::HtmlBuilder::new(&config, std::path::PathBuf::from("./tests"));
+        let index = data::NoteIndex::new(tracker, builder, Default::default()).0;
+
+        let matches = |filter: &Filter, id: &str| {
+            filter.apply(index.inner.get(id).unwrap(), &index).is_some()
+        };
+
+        let (collections, _error) = io::Collections::load(&testdir::testdir!());
+        collections.create("reading").unwrap();
+        collections.toggle("reading", "atlas").unwrap();
+        collections.toggle("reading", "linux").unwrap();
+
+        let filter = Filter::new("in:reading", false).with_collections(&collections);
+        assert_eq!(filter.collections, vec![(String::from("reading"), true)]);
+        assert!(matches(&filter, "atlas"));
+        assert!(matches(&filter, "linux"));
+        assert!(!matches(&filter, "chart"));
+
+        let filter = Filter::new("#topology !in:reading", false).with_collections(&collections);
+        assert!(!matches(&filter, "atlas"));
+        assert!(matches(&filter, "chart"));
+
+        // unknown collections contain no notes
+        let filter = Filter::new("in:unknown", false).with_collections(&collections);
+        assert!(!matches(&filter, "atlas"));
+
+        // a filter is no refinement once the notes of its collections changed
+        let before = Filter::new("in:reading", false).with_collections(&collections);
+        let filter = Filter::new("in:reading atl", false).with_collections(&collections);
+        assert!(filter.refines(&before));
+        collections.toggle("reading", "chart").unwrap();
+        let filter = Filter::new("in:reading atl", false).with_collections(&collections);
+        assert!(!filter.refines(&before));
+    }
+}
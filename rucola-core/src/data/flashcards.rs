@@ -0,0 +1,238 @@
+use sha2::Digest;
+
+use super::lint::{heading_level, split_lines};
+use crate::error;
+
+/// The header of Anki import files, describing the columns of the lines written by `write_anki`.
+pub const ANKI_HEADER: &str =
+    "#separator:tab\n#html:true\n#notetype column:1\n#guid column:2\n#tags column:5\n";
+
+/// A card for spaced repetition found in a note.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Flashcard {
+    /// The question, or the text with cloze deletions.
+    pub front: String,
+    /// The answer, if this is not a cloze card.
+    pub back: Option<String>,
+}
+
+/// Finds the flashcards in the given content of a note, leaving out its frontmatter and code blocks.
+/// A question starts with `Q:` and its answer with `A:` at the start of a line. Both continue on the following lines until an empty line, a heading or the next question.
+/// Paragraphs containing cloze deletions in the style of Anki, such as `{{c1::Paris}}`, become cloze cards.
+pub fn find_flashcards(content: &str) -> Vec<Flashcard> {
+    let (_frontmatter, body, _body_start) = split_lines(content);
+
+    let mut cards = Vec::new();
+    let mut question: Option<Vec<&str>> = None;
+    let mut answer: Option<Vec<&str>> = None;
+    let mut paragraph = Vec::new();
+    let mut previous = None;
+
+    for (number, line) in body {
+        let trimmed = line.trim();
+        // lines of code blocks are left out, so a gap ends the paragraph before them
+        let gap = previous.is_some_and(|previous| number != previous + 1);
+        previous = Some(number);
+
+        if gap || trimmed.is_empty() || heading_level(line).is_some() {
+            finish(&mut cards, &mut question, &mut answer, &mut paragraph);
+        }
+
+        if let Some(rest) = trimmed.strip_prefix("Q:") {
+            finish(&mut cards, &mut question, &mut answer, &mut paragraph);
+            question = Some(vec![rest.trim_start()]);
+        } else if let (Some(rest), Some(_), None) = (trimmed.strip_prefix("A:"), &question, &answer)
+        {
+            answer = Some(vec![rest.trim_start()]);
+        } else if let Some(lines) = answer.as_mut().or(question.as_mut()) {
+            lines.push(trimmed);
+        } else if !trimmed.is_empty() && heading_level(line).is_none() {
+            paragraph.push(line);
+        }
+    }
+    finish(&mut cards, &mut question, &mut answer, &mut paragraph);
+
+    cards
+}
+
+/// Turns the question and answer or the paragraph collected so far into a card, if they form one, and clears them.
+fn finish(
+    cards: &mut Vec<Flashcard>,
+    question: &mut Option<Vec<&str>>,
+    answer: &mut Option<Vec<&str>>,
+    paragraph: &mut Vec<&str>,
+) {
+    let join = |lines: Vec<&str>| lines.join("\n").trim().to_owned();
+
+    if let (Some(front), Some(back)) = (question.take().map(join), answer.take().map(join)) {
+        if !front.is_empty() && !back.is_empty() {
+            cards.push(Flashcard {
+                front,
+                back: Some(back),
+            });
+        }
+    }
+
+    let text = join(std::mem::take(paragraph));
+    if is_cloze(&text) {
+        cards.push(Flashcard {
+            front: text,
+            back: None,
+        });
+    }
+}
+
+/// Wether the given text contains a cloze deletion such as `{{c1::...}}`.
+fn is_cloze(text: &str) -> bool {
+    text.match_indices("{{c").any(|(i, marker)| {
+        let number = text[i + marker.len()..]
+            .chars()
+            .take_while(char::is_ascii_digit)
+            .count();
+        number > 0 && text[i + marker.len() + number..].starts_with("::")
+    })
+}
+
+/// Writes the given flashcards of the note with the given id and tags as lines of an Anki import file starting with `ANKI_HEADER`.
+/// The markdown of the cards is converted to HTML. The tags of the note are passed on, nested ones with Anki's `::`, along with a tag for the note id.
+/// Every card is identified by the note id and its front, so importing the file again updates the cards instead of adding them again.
+pub fn write_anki(
+    writer: &mut impl std::io::Write,
+    id: &str,
+    tags: &[String],
+    cards: &[Flashcard],
+) -> error::Result<()> {
+    let tags = tags
+        .iter()
+        .map(|tag| tag.trim_start_matches('#').replace('/', "::"))
+        .chain(std::iter::once(format!("rucola::{}", id)))
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    let mut guids: Vec<String> = Vec::new();
+    for card in cards {
+        let hash = sha2::Sha256::digest(card.front.as_bytes())
+            .iter()
+            .take(6)
+            .map(|byte| format!("{:02x}", byte))
+            .collect::<String>();
+        let mut guid = format!("rucola-{}-{}", id, hash);
+        // the same question asked twice within a note still needs two cards
+        let repeated = guids
+            .iter()
+            .filter(|other| other.starts_with(&guid))
+            .count();
+        if repeated > 0 {
+            guid = format!("{}-{}", guid, repeated + 1);
+        }
+
+        writeln!(
+            writer,
+            "{}\t{}\t{}\t{}\t{}",
+            if card.back.is_some() {
+                "Basic"
+            } else {
+                "Cloze"
+            },
+            guid,
+            field(&to_html(&card.front)),
+            field(&card.back.as_deref().map(to_html).unwrap_or_default()),
+            tags,
+        )?;
+        guids.push(guid);
+    }
+
+    Ok(())
+}
+
+/// Converts the given markdown of a card to HTML.
+fn to_html(markdown: &str) -> String {
+    let options = comrak::Options {
+        extension: comrak::ExtensionOptions::builder()
+            .strikethrough(true)
+            .table(true)
+            .build(),
+        ..Default::default()
+    };
+    comrak::markdown_to_html(markdown, &options)
+        .trim_end()
+        .to_owned()
+}
+
+/// Quotes the given field of an Anki import file, as fields may contain tabs and line breaks.
+fn field(text: &str) -> String {
+    format!("\"{}\"", text.replace('"', "\"\""))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_flashcards() {
+        let content = "---\ntitle: \"Q: not a card\"\n---\n# Capitals\nQ: What is the capital\nof France?\nA: Paris\n\nQ: Without answer\n\nThe capital of {{c1::Italy}} is {{c2::Rome}}.\n## Details\nQ: Code?\nA: Like this:\n```\nQ: inside code\nA: ignored\n```\nNo {{cloze}} here.\n";
+        assert_eq!(
+            find_flashcards(content),
+            vec![
+                Flashcard {
+                    front: String::from("What is the capital\nof France?"),
+                    back: Some(String::from("Paris")),
+                },
+                Flashcard {
+                    front: String::from("The capital of {{c1::Italy}} is {{c2::Rome}}."),
+                    back: None,
+                },
+                Flashcard {
+                    front: String::from("Code?"),
+                    back: Some(String::from("Like this:")),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_write_anki() {
+        let cards = [
+            Flashcard {
+                front: String::from("What is *\"it\"*?"),
+                back: Some(String::from("A\tthing")),
+            },
+            Flashcard {
+                front: String::from("What is *\"it\"*?"),
+                back: Some(String::from("Still a thing")),
+            },
+            Flashcard {
+                front: String::from("{{c1::Rome}}"),
+                back: None,
+            },
+        ];
+        let mut out = Vec::new();
+        write_anki(
+            &mut out,
+            "capitals",
+            &[String::from("#flashcards"), String::from("#geo/europe")],
+            &cards,
+        )
+        .unwrap();
+        let out = String::from_utf8(out).unwrap();
+        let lines = out.lines().collect::<Vec<_>>();
+
+        assert_eq!(lines.len(), 3);
+        let basic = lines[0].split('\t').collect::<Vec<_>>();
+        assert_eq!(basic[0], "Basic");
+        assert!(basic[1].starts_with("rucola-capitals-"));
+        assert_eq!(basic[2], "\"<p>What is <em>&quot;it&quot;</em>?</p>\"");
+        assert_eq!(basic[3], "\"<p>A");
+        assert_eq!(
+            lines[0].rsplit('\t').next(),
+            Some("flashcards geo::europe rucola::capitals")
+        );
+        // repeated questions get their own id
+        assert_eq!(
+            lines[1].split('\t').nth(1),
+            Some(format!("{}-2", basic[1]).as_str())
+        );
+        assert!(lines[2].starts_with("Cloze\t"));
+        assert!(lines[2].contains("\t\"<p>{{c1::Rome}}</p>\"\t\"\"\t"));
+    }
+}
@@ -0,0 +1,135 @@
+use std::borrow::Cow;
+
+/// The label of the footnotes added for links, followed by their number.
+const LABEL: &str = "rucola-link-";
+
+/// Adds a footnote with the URL of every external link in the given content right after the link, so the targets of links remain readable on paper.
+/// Links to the same URL share a footnote. Autolinks already show their URL and are left alone, as are links to other notes.
+/// Returns the content unchanged if it contains no external links.
+pub fn footnote_links(content: &str) -> Cow<'_, str> {
+    let options = comrak::Options {
+        extension: comrak::ExtensionOptions::builder()
+            .front_matter_delimiter(String::from("---"))
+            .wikilinks_title_after_pipe(true)
+            .math_dollars(true)
+            .table(true)
+            .build(),
+        ..Default::default()
+    };
+    let arena = comrak::Arena::new();
+    let root = comrak::parse_document(&arena, content, &options);
+
+    // the byte offset at which every line starts, as positions are given by line and column
+    let line_starts = std::iter::once(0)
+        .chain(content.match_indices('\n').map(|(i, _)| i + 1))
+        .collect::<Vec<_>>();
+
+    let mut urls: Vec<String> = Vec::new();
+    // the byte offsets right after the links, with the number of their footnote
+    let mut references = Vec::new();
+
+    for node in root.descendants() {
+        let comrak::nodes::NodeValue::Link(ref link) = node.data.borrow().value else {
+            continue;
+        };
+        if !is_external(&link.url) {
+            continue;
+        }
+
+        let text = node
+            .children()
+            .filter_map(|child| match &child.data.borrow().value {
+                comrak::nodes::NodeValue::Text(text) => Some(text.clone()),
+                _ => None,
+            })
+            .collect::<String>();
+        if text == link.url || Some(text.as_str()) == link.url.strip_prefix("mailto:") {
+            continue;
+        }
+
+        let position = node.data.borrow().sourcepos;
+        let (Some(start), Some(end)) = (
+            line_starts
+                .get(position.start.line.wrapping_sub(1))
+                .map(|line| line + position.start.column - 1),
+            line_starts
+                .get(position.end.line.wrapping_sub(1))
+                .map(|line| line + position.end.column),
+        ) else {
+            continue;
+        };
+        // only trust positions that actually enclose a link
+        if content.get(start..start + 1) != Some("[") || !content.is_char_boundary(end) {
+            continue;
+        }
+
+        let number = match urls.iter().position(|url| url == &link.url) {
+            Some(i) => i + 1,
+            None => {
+                urls.push(link.url.clone());
+                urls.len()
+            }
+        };
+        references.push((end, number));
+    }
+
+    if references.is_empty() {
+        return Cow::Borrowed(content);
+    }
+
+    let mut result = String::with_capacity(content.len());
+    let mut last = 0;
+    for (end, number) in references {
+        result.push_str(&content[last..end]);
+        result.push_str(&format!("[^{}{}]", LABEL, number));
+        last = end;
+    }
+    result.push_str(&content[last..]);
+
+    result.truncate(result.trim_end().len());
+    result.push('\n');
+    for (i, url) in urls.iter().enumerate() {
+        result.push_str(&format!("\n[^{}{}]: {}\n", LABEL, i + 1, escape(url)));
+    }
+
+    Cow::Owned(result)
+}
+
+/// Wether the given URL of a link points outside of the vault.
+fn is_external(url: &str) -> bool {
+    url.contains("://") || url.starts_with("mailto:")
+}
+
+/// Writes the given URL such that it shows up as is, as an autolink if possible.
+fn escape(url: &str) -> String {
+    if url.contains(|c: char| c.is_whitespace() || c == '<' || c == '>') {
+        url.chars()
+            .flat_map(|c| {
+                c.is_ascii_punctuation()
+                    .then_some('\\')
+                    .into_iter()
+                    .chain(std::iter::once(c))
+            })
+            .collect()
+    } else {
+        format!("<{}>", url)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_footnote_links() {
+        // without external links, nothing changes
+        let content = "# Atlas\nSee [[Chart]] and [the map](map) or <https://example.com>.\n";
+        assert!(matches!(footnote_links(content), Cow::Borrowed(_)));
+
+        let content = "---\ntitle: \"[f](https://f.org)\"\n---\n# Atlas [maps](https://maps.org)\n\n- äöü *[again](https://maps.org \"title\")* and [mail](mailto:jane@example.com)\n> [ref][r] too\n\n[r]: <https://ref.org/a b>\n";
+        assert_eq!(
+            footnote_links(content),
+            "---\ntitle: \"[f](https://f.org)\"\n---\n# Atlas [maps](https://maps.org)[^rucola-link-1]\n\n- äöü *[again](https://maps.org \"title\")[^rucola-link-1]* and [mail](mailto:jane@example.com)[^rucola-link-2]\n> [ref][r][^rucola-link-3] too\n\n[r]: <https://ref.org/a b>\n\n[^rucola-link-1]: <https://maps.org>\n\n[^rucola-link-2]: <mailto:jane@example.com>\n\n[^rucola-link-3]: https\\:\\/\\/ref\\.org\\/a b\n"
+        );
+    }
+}
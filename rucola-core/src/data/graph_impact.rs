@@ -0,0 +1,296 @@
+use std::{
+    collections::{HashMap, HashSet},
+    path,
+};
+
+use itertools::Itertools;
+
+use crate::error;
+
+use super::{
+    migration::{Edit, Rename},
+    name_to_id, NoteIndex,
+};
+
+/// Statistics of the links between the notes of an index.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GraphStats {
+    /// The number of notes.
+    pub notes: usize,
+    /// The number of links between notes, including broken ones.
+    pub links: usize,
+    /// The number of links to notes that do not exist.
+    pub broken_links: usize,
+    /// The paths of all notes without links to or from other notes.
+    pub orphans: HashSet<path::PathBuf>,
+}
+
+impl GraphStats {
+    /// Calculates the statistics of the given index. Links to an alias of a note count as links to the note.
+    pub fn new(index: &NoteIndex) -> Self {
+        let aliases = index
+            .inner
+            .iter()
+            .flat_map(|(id, note)| {
+                note.aliases
+                    .iter()
+                    .map(move |alias| (name_to_id(alias), id))
+            })
+            .collect::<HashMap<_, _>>();
+
+        let mut linked = HashSet::new();
+        let mut links = 0;
+        let mut broken_links = 0;
+        for (id, note) in &index.inner {
+            for link in note.links.iter().filter(|link| !link.is_empty()) {
+                links += 1;
+                let target = match index.inner.get_key_value(link) {
+                    Some((target, _)) => target,
+                    None => match aliases.get(link) {
+                        Some(target) => *target,
+                        None => {
+                            broken_links += 1;
+                            continue;
+                        }
+                    },
+                };
+                // notes linking to themselves are still orphans
+                if target != id {
+                    linked.insert(id);
+                    linked.insert(target);
+                }
+            }
+        }
+
+        Self {
+            notes: index.inner.len(),
+            links,
+            broken_links,
+            orphans: index
+                .inner
+                .iter()
+                .filter(|(id, _note)| !linked.contains(id))
+                .map(|(_id, note)| note.path.clone())
+                .collect(),
+        }
+    }
+}
+
+/// How an operation changing many notes at once changes the links between the notes, to confirm it before applying it.
+/// Calculated from a copy of the index with the changes applied, without touching the file system.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GraphImpact {
+    /// The statistics before the operation.
+    pub before: GraphStats,
+    /// The statistics after the operation.
+    pub after: GraphStats,
+    /// The number of notes changed, renamed or created.
+    pub notes_affected: usize,
+    /// The number of links that are changed or added.
+    pub links_rewritten: usize,
+    /// The names of the notes without links after the operation that had some before, sorted.
+    pub orphans_created: Vec<String>,
+    /// The names of the notes with links after the operation that had none before, sorted.
+    pub orphans_resolved: Vec<String>,
+}
+
+impl GraphImpact {
+    /// Calculates the impact of changing the content of notes and renaming note files of the given index.
+    /// Edits refer to the paths of the notes before renaming them.
+    pub(super) fn new(
+        index: &NoteIndex,
+        edits: &[Edit],
+        renames: &[Rename],
+    ) -> error::Result<Self> {
+        let after = index.dry_run(edits, renames)?;
+
+        // where each note ends up after the operation
+        let moved = |path: &path::Path| {
+            renames
+                .iter()
+                .find(|rename| rename.from == path)
+                .map_or_else(|| path.to_path_buf(), |rename| rename.to.clone())
+        };
+        let names = |paths: HashSet<path::PathBuf>| {
+            paths
+                .into_iter()
+                .filter_map(|path| {
+                    after
+                        .inner
+                        .values()
+                        .find(|note| note.path == path)
+                        .map(|note| note.name.clone())
+                })
+                .sorted()
+                .collect_vec()
+        };
+
+        let before = GraphStats::new(index);
+        let after_stats = GraphStats::new(&after);
+        let moved_orphans = before.orphans.iter().map(|path| moved(path)).collect();
+
+        let links_rewritten = edits
+            .iter()
+            .map(|edit| {
+                let old = link_texts(&edit.old);
+                link_texts(&edit.new)
+                    .into_iter()
+                    .map(|(link, count)| count.saturating_sub(old.get(link).copied().unwrap_or(0)))
+                    .sum::<usize>()
+            })
+            .sum();
+
+        Ok(Self {
+            notes_affected: edits
+                .iter()
+                .map(|edit| &edit.path)
+                .chain(renames.iter().map(|rename| &rename.from))
+                .unique()
+                .count(),
+            links_rewritten,
+            orphans_created: names(
+                after_stats
+                    .orphans
+                    .difference(&moved_orphans)
+                    .cloned()
+                    .collect(),
+            ),
+            orphans_resolved: names(
+                moved_orphans
+                    .difference(&after_stats.orphans)
+                    .cloned()
+                    .collect(),
+            ),
+            before,
+            after: after_stats,
+        })
+    }
+
+    /// Describes the impact: The statistics before and after the operation side by side, and the notes becoming or ceasing to be orphans.
+    pub fn describe(&self) -> String {
+        let mut description = format!("{:<14}{:>8}{:>8}\n", "", "before", "after");
+        for (label, before, after) in [
+            ("notes", self.before.notes, self.after.notes),
+            ("links", self.before.links, self.after.links),
+            (
+                "broken links",
+                self.before.broken_links,
+                self.after.broken_links,
+            ),
+            (
+                "orphans",
+                self.before.orphans.len(),
+                self.after.orphans.len(),
+            ),
+        ] {
+            description.push_str(&format!("{:<14}{:>8}{:>8}", label, before, after));
+            if after != before {
+                description.push_str(&format!("  ({:+})", after as i64 - before as i64));
+            }
+            description.push('\n');
+        }
+
+        description.push_str(&format!(
+            "{} note{} affected, {} link{} rewritten.\n",
+            self.notes_affected,
+            if self.notes_affected == 1 { "" } else { "s" },
+            self.links_rewritten,
+            if self.links_rewritten == 1 { "" } else { "s" },
+        ));
+        if !self.orphans_created.is_empty() {
+            description.push_str(&format!(
+                "Orphans created: {}\n",
+                self.orphans_created.join(", ")
+            ));
+        }
+        if !self.orphans_resolved.is_empty() {
+            description.push_str(&format!(
+                "Orphans resolved: {}\n",
+                self.orphans_resolved.join(", ")
+            ));
+        }
+
+        description
+    }
+}
+
+/// Counts how often each wiki or markdown link occurs in the given content, by its full text.
+/// Links only differing in their spelling, e.g. of the same note, are told apart.
+fn link_texts(content: &str) -> HashMap<&str, usize> {
+    let regex =
+        regex::Regex::new(r"\[\[[^\]]+\]\]|\]\([^)\s]+\)").expect("Pre-defined regex to be valid.");
+    regex.find_iter(content).map(|link| link.as_str()).counts()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_graph_impact() {
+        let vault = testdir::testdir!();
+        std::fs::write(vault.join("Lie Group.md"), "# Lie Group\n").unwrap();
+        std::fs::write(vault.join("Atlas.md"), "# Atlas\nOf a [[Lie Group]].\n").unwrap();
+        std::fs::write(vault.join("Chart.md"), "# Chart\nSee [[Map]].\n").unwrap();
+        std::fs::write(vault.join("Shelf.md"), "# Shelf\n").unwrap();
+
+        let config = crate::Config::default();
+        let tracker = crate::io::FileTracker::new(&config, vault.clone()).unwrap();
+        let builder = crate::io::HtmlBuilder::new(&config, vault.clone());
+        let index = NoteIndex::new(tracker, builder, Default::default()).0;
+
+        let stats = GraphStats::new(&index);
+        assert_eq!(stats.notes, 4);
+        assert_eq!(stats.links, 2);
+        assert_eq!(stats.broken_links, 1);
+        assert_eq!(stats.orphans.len(), 2);
+
+        // renaming keeps the links intact
+        let migration =
+            super::super::Migration::plan(&index, &[super::super::NamingScheme::Dashes]).unwrap();
+        let impact = migration.impact(&index).unwrap();
+        assert_eq!(impact.notes_affected, 2);
+        assert_eq!(impact.links_rewritten, 1);
+        assert_eq!(
+            impact.after,
+            GraphStats {
+                orphans: impact.after.orphans.clone(),
+                ..stats.clone()
+            }
+        );
+        assert!(impact.orphans_created.is_empty());
+        assert!(impact.orphans_resolved.is_empty());
+        assert!(impact
+            .describe()
+            .contains("2 notes affected, 1 link rewritten."));
+
+        // linking a note resolves it as an orphan, unlinking another creates one
+        let edits = [
+            Edit {
+                path: index.get("chart").unwrap().path.clone(),
+                old: String::new(),
+                new: String::from("# Chart\nSee [[Shelf]].\n"),
+            },
+            Edit {
+                path: index.get("atlas").unwrap().path.clone(),
+                old: String::new(),
+                new: String::from("# Atlas\n"),
+            },
+        ];
+        let impact = GraphImpact::new(&index, &edits, &[]).unwrap();
+        assert_eq!(impact.after.broken_links, 0);
+        assert_eq!(impact.links_rewritten, 1);
+        assert_eq!(impact.orphans_created, vec!["Atlas", "Lie Group"]);
+        assert_eq!(impact.orphans_resolved, vec!["Chart", "Shelf"]);
+        assert!(impact
+            .describe()
+            .contains("broken links         1       0  (-1)"));
+
+        // the vault is left untouched
+        assert!(vault.join("Lie Group.md").exists());
+        assert_eq!(
+            std::fs::read_to_string(vault.join("Atlas.md")).unwrap(),
+            "# Atlas\nOf a [[Lie Group]].\n"
+        );
+    }
+}
@@ -0,0 +1,150 @@
+use std::{borrow::Cow, collections::HashMap};
+
+use itertools::Itertools;
+
+/// Matches the targets of markdown links and images, written plainly or in angle brackets, followed by an optional title.
+fn inline_regex() -> regex::Regex {
+    regex::Regex::new(r#"\]\((<[^>\n]*>|[^)\s]+)(\s+(?:"[^"\n]*"|'[^'\n]*'))?\)"#)
+        .expect("Pre-defined regex to be valid.")
+}
+
+/// Matches link reference definitions such as `[logo]: images/logo.png`.
+fn reference_regex() -> regex::Regex {
+    regex::Regex::new(r"(?m)^( {0,3}\[[^\]\n]+\]:[ \t]*)(<[^>\n]*>|\S+)")
+        .expect("Pre-defined regex to be valid.")
+}
+
+/// Removes the angle brackets around a link target, if it is written in them.
+fn unbracket(target: &str) -> &str {
+    target
+        .strip_prefix('<')
+        .and_then(|target| target.strip_suffix('>'))
+        .unwrap_or(target)
+}
+
+/// Wether the given link target refers to a file, rather than to a web page, an anchor within the note or another scheme like `mailto:`.
+/// `file://` URLs refer to files.
+fn is_local(target: &str) -> bool {
+    if target.is_empty() || target.starts_with('#') {
+        return false;
+    }
+    if target.starts_with("file://") {
+        return true;
+    }
+    // a scheme consists of at least two letters, so drive letters are not mistaken for one
+    !target.split_once(':').is_some_and(|(scheme, _rest)| {
+        scheme.len() > 1
+            && scheme
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || "+-.".contains(c))
+    })
+}
+
+/// Returns the targets of all markdown links, images and link reference definitions in the given content that refer to files, as written but without angle brackets.
+/// Each target is returned once, in the order of its first occurrence. Wiki links are left out, as they refer to notes by name.
+pub fn local_link_targets(content: &str) -> Vec<String> {
+    let inline = inline_regex();
+    let reference = reference_regex();
+
+    inline
+        .captures_iter(content)
+        .map(|captures| captures.get(1))
+        .chain(
+            reference
+                .captures_iter(content)
+                .map(|captures| captures.get(2)),
+        )
+        .flatten()
+        .sorted_by_key(|target| target.start())
+        .map(|target| unbracket(target.as_str()))
+        .filter(|target| is_local(target))
+        .map(str::to_owned)
+        .unique()
+        .collect()
+}
+
+/// Replaces the targets of markdown links, images and link reference definitions in the given content by the targets given for them.
+/// Titles of links are kept, new targets containing whitespace are written in angle brackets.
+pub fn rewrite_link_targets<'a>(
+    content: &'a str,
+    targets: &HashMap<String, String>,
+) -> Cow<'a, str> {
+    let write = |target: &str| match targets.get(unbracket(target)) {
+        Some(new) if new.chars().any(char::is_whitespace) => Some(format!("<{}>", new)),
+        Some(new) => Some(new.to_owned()),
+        None => None,
+    };
+
+    let content = inline_regex().replace_all(content, |captures: &regex::Captures| {
+        match write(&captures[1]) {
+            Some(new) => format!(
+                "]({}{})",
+                new,
+                captures.get(2).map_or("", |title| title.as_str())
+            ),
+            None => captures[0].to_owned(),
+        }
+    });
+
+    let replaced = match reference_regex().replace_all(&content, |captures: &regex::Captures| {
+        match write(&captures[2]) {
+            Some(new) => format!("{}{}", &captures[1], new),
+            None => captures[0].to_owned(),
+        }
+    }) {
+        Cow::Borrowed(_) => None,
+        Cow::Owned(replaced) => Some(replaced),
+    };
+    replaced.map_or(content, Cow::Owned)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_local_link_targets() {
+        let content = "# Trip\n![Map](/home/jane/Pictures/map.png \"The map\") and [notes](../Downloads/plan.pdf#page=2).\n\
+            See [the site](https://example.com), [mail](mailto:jane@example.com), [top](#trip) and [[Other Note]].\n\
+            ![Again](/home/jane/Pictures/map.png) ![Spaced](<../My Pictures/sea.jpg>)\n\
+            [logo]: file:///home/jane/logo.svg\n";
+        assert_eq!(
+            local_link_targets(content),
+            vec![
+                "/home/jane/Pictures/map.png",
+                "../Downloads/plan.pdf#page=2",
+                "../My Pictures/sea.jpg",
+                "file:///home/jane/logo.svg",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_rewrite_link_targets() {
+        let content = "![Map](/home/jane/map.png \"The map\") ![Sea](<../My Pictures/sea.jpg>) [web](https://example.com)\n\n[logo]: /home/jane/logo.svg\n";
+        let targets = HashMap::from([
+            (
+                String::from("/home/jane/map.png"),
+                String::from("assets/map.png"),
+            ),
+            (
+                String::from("../My Pictures/sea.jpg"),
+                String::from("assets/sea.jpg"),
+            ),
+            (
+                String::from("/home/jane/logo.svg"),
+                String::from("assets/the logo.svg"),
+            ),
+        ]);
+        assert_eq!(
+            rewrite_link_targets(content, &targets),
+            "![Map](assets/map.png \"The map\") ![Sea](assets/sea.jpg) [web](https://example.com)\n\n[logo]: <assets/the logo.svg>\n"
+        );
+
+        // other links are kept
+        assert_eq!(
+            rewrite_link_targets("[web](https://example.com) [home](/home/jane)", &targets),
+            "[web](https://example.com) [home](/home/jane)"
+        );
+    }
+}
@@ -0,0 +1,1308 @@
+use std::collections::{HashMap, HashSet};
+
+use itertools::Itertools;
+
+use crate::{error, error::ErrorContext, io};
+
+use super::Note;
+
+/// The number of keywords of a note compared against the tags of the vault.
+const KEYWORD_CANDIDATES: usize = 20;
+
+/// Contains a NoteIndex and wraps it to provide easy mutable access from different areas of the code.
+pub type NoteIndexContainer = std::rc::Rc<std::cell::RefCell<NoteIndex>>;
+
+/// Contains an indexed and hashed list of notes
+pub struct NoteIndex {
+    /// The wrapped HashMap, available only in the data module.
+    pub(super) inner: HashMap<String, Note>,
+
+    /// === Config ===
+    /// The file tracker that sends file events and watches the structure of the vault of this index.
+    /// Snapshots of past states of the vault have no tracker, as they are never updated.
+    tracker: Option<io::FileTracker>,
+    /// The HtmlBuilder this index uses to create its HTML files.
+    builder: io::HtmlBuilder,
+    /// The options used to parse notes.
+    options: super::ParseOptions,
+
+    /// === State ===
+    /// Files that could not be parsed into notes, with the reason.
+    failures: Vec<(std::path::PathBuf, String)>,
+    /// The reason the vault is not watched for file changes, if watching could not be started.
+    watcher_failure: Option<String>,
+    /// The ids of notes created or modified by file events since they were last taken.
+    changed: Vec<String>,
+    /// The ids of notes created, moved or copied into the vault by file events since they were last taken.
+    created: Vec<String>,
+    /// The notes linking to each note.
+    backlinks: Backlinks,
+}
+
+impl std::fmt::Debug for NoteIndex {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.inner.fmt(f)
+    }
+}
+
+impl NoteIndex {
+    /// Reads a passed directory recursively, returning a hashmap containing
+    ///  - An entry for every '.md' file in the directory or any subdirectories
+    ///  - The key will be the file name, without the file extension, in lowercase and with spaces replaced by dashes
+    ///  - The value will be an instance of Note containing metadata of the file.
+    ///
+    /// All IO errors that happeded during the creation or the (potential) HTML conversion are returned alongside.
+    pub fn new(
+        mut tracker: io::FileTracker,
+        builder: io::HtmlBuilder,
+        options: super::ParseOptions,
+    ) -> (Self, Vec<error::RucolaError>) {
+        // folder tags are relative to the vault
+        let options = options.with_vault_path(builder.vault_path());
+        // create an error struct
+        let mut errors = vec![];
+        // remember the files that could not be indexed to offer retrying them later
+        let mut failures = vec![];
+        // collect all the notes from the vault folder
+        let mut inner = tracker
+            .get_walker() // Check only OKs
+            .flatten()
+            // Convert tiles to notes and skip errors
+            .filter(|entry| entry.metadata().is_ok_and(|md| md.is_file()))
+            .flat_map(|entry| match Note::from_path(entry.path(), &options) {
+                Ok(note) => Some(note),
+                Err(e) => {
+                    failures.push((canonical(entry.path()), e.reason()));
+                    errors.push(e);
+                    None
+                }
+            })
+            // Extract name and convert to id
+            .map(|note| (super::name_to_id(&note.name), note))
+            // Collect into hash map
+            .collect::<HashMap<_, _>>();
+
+        let mut index = Self {
+            backlinks: Backlinks::new(&mut inner),
+            inner,
+            tracker: None,
+            builder,
+            options,
+            failures,
+            watcher_failure: None,
+            changed: Vec::new(),
+            created: Vec::new(),
+        };
+
+        // create htmls once all notes are known, so queries within them see the whole vault, and save errors
+        errors.extend(
+            index
+                .inner
+                .values()
+                .map(|note| {
+                    index
+                        .builder
+                        .create_html(note, Some(&index), false)
+                        .in_file(&note.path, None)
+                })
+                .flat_map(Result::err),
+        );
+
+        // let the watcher start watching _after_ all htmls have been re-done
+        index.watcher_failure = match tracker.initialize_watching() {
+            Ok(_) => None,
+            Err(e) => {
+                let reason = e.to_string();
+                errors.push(e.into());
+                Some(reason)
+            }
+        };
+        index.tracker = Some(tracker);
+
+        (index, errors)
+    }
+
+    /// Creates the HTML file of the note with the given id, if it exists, replacing queries within it by their results in this index.
+    fn create_html(&self, id: &str) -> error::Result<()> {
+        match self.inner.get(id) {
+            Some(note) => self
+                .builder
+                .create_html(note, Some(self), false)
+                .in_file(&note.path, None),
+            None => Ok(()),
+        }
+    }
+
+    /// Reconstructs the state of this index at the given commit of the given git repository.
+    /// The notes are parsed directly from the repository, without touching the files in the vault.
+    /// The returned index is a snapshot that is not kept up to date with changes to the file system.
+    pub fn at_commit(
+        &self,
+        git_manager: &io::GitManager,
+        commit: git2::Oid,
+    ) -> error::Result<Self> {
+        let mut inner = git_manager
+            .files_at(commit)?
+            .into_iter()
+            // Only consider files that would be tracked if they were in the vault now.
+            .filter(|(path, _content)| {
+                self.tracker
+                    .as_ref()
+                    .is_some_and(|tracker| tracker.is_note_file(path))
+            })
+            .flat_map(|(path, content)| Note::from_content(&path, &content, &self.options))
+            .map(|note| (super::name_to_id(&note.name), note))
+            .collect();
+
+        Ok(Self {
+            backlinks: Backlinks::new(&mut inner),
+            inner,
+            tracker: None,
+            builder: self.builder.clone(),
+            options: self.options.clone(),
+            failures: Vec::new(),
+            watcher_failure: None,
+            changed: Vec::new(),
+            created: Vec::new(),
+        })
+    }
+
+    /// Simulates changing the content of the given notes and then renaming the given note files, without touching the file system.
+    /// The returned index is a snapshot that is not kept up to date with changes to the file system, like the state at a commit.
+    /// Edits may create new notes and refer to the paths of notes before renaming them.
+    pub(super) fn dry_run(
+        &self,
+        edits: &[super::migration::Edit],
+        renames: &[super::migration::Rename],
+    ) -> error::Result<Self> {
+        let mut inner = self.inner.clone();
+
+        for edit in edits {
+            let note = Note::from_content(&edit.path, &edit.new, &self.options)?;
+            inner.retain(|_id, other| other.path != edit.path);
+            inner.insert(super::name_to_id(&note.name), note);
+        }
+
+        for rename in renames {
+            if inner.values().all(|note| note.path != rename.from) {
+                continue;
+            }
+            inner.retain(|_id, note| note.path != rename.from);
+            let content = match edits.iter().find(|edit| edit.path == rename.from) {
+                Some(edit) => edit.new.clone(),
+                None => std::fs::read_to_string(&rename.from)?,
+            };
+            let note = Note::from_content(&rename.to, &content, &self.options)?;
+            inner.insert(super::name_to_id(&note.name), note);
+        }
+
+        Ok(Self {
+            backlinks: Backlinks::new(&mut inner),
+            inner,
+            tracker: None,
+            builder: self.builder.clone(),
+            options: self.options.clone(),
+            failures: Vec::new(),
+            watcher_failure: None,
+            changed: Vec::new(),
+            created: Vec::new(),
+        })
+    }
+
+    /// Returns all files that could not be indexed, with the reason.
+    pub fn failures(&self) -> &[(std::path::PathBuf, String)] {
+        &self.failures
+    }
+
+    /// Returns the reason the vault is not watched for file changes, if it is not.
+    pub fn watcher_failure(&self) -> Option<&str> {
+        self.watcher_failure.as_deref()
+    }
+
+    /// Wether parts of the vault could not be loaded, so the index is incomplete or not kept up to date.
+    pub fn is_degraded(&self) -> bool {
+        !self.failures.is_empty() || self.watcher_failure.is_some()
+    }
+
+    /// Tries again to index all files that failed before and to start watching the vault.
+    /// Files that no longer exist are forgotten.
+    ///
+    /// Returns wether the index has changed, and all errors that occured while retrying.
+    pub fn retry_failures(&mut self) -> (bool, Vec<error::RucolaError>) {
+        let mut modifications = false;
+        let mut errors = vec![];
+
+        for (path, _reason) in std::mem::take(&mut self.failures) {
+            if !path.exists() {
+                continue;
+            }
+            match Note::from_path(&path, &self.options) {
+                Ok(note) => {
+                    let id = super::name_to_id(&note.name);
+                    self.insert(id.clone(), note);
+                    errors.extend(self.create_html(&id).err());
+                    modifications = true;
+                }
+                Err(e) => {
+                    // keep the failure, but with the latest reason
+                    self.failures.push((path, e.reason()));
+                    errors.push(e);
+                }
+            }
+        }
+
+        if let Some(tracker) = self.tracker.as_mut() {
+            self.watcher_failure = match tracker.initialize_watching() {
+                Ok(_) => None,
+                Err(e) => {
+                    let reason = e.to_string();
+                    errors.push(e.into());
+                    Some(reason)
+                }
+            };
+        }
+
+        (modifications, errors)
+    }
+
+    /// Returns the display names of all notes in this index that are not contained in the other index, in alphabetical order.
+    pub fn notes_not_in(&self, other: &Self) -> Vec<String> {
+        self.inner
+            .iter()
+            .filter(|(id, _note)| !other.inner.contains_key(*id))
+            .map(|(_id, note)| note.display_name.clone())
+            .sorted()
+            .collect()
+    }
+
+    /// Returns all links between notes in this index that are not contained in the other index, as pairs of the display names of their source and target, in alphabetical order.
+    /// Links to notes that do not exist are listed with the id of their target.
+    pub fn links_not_in(&self, other: &Self) -> Vec<(String, String)> {
+        self.inner
+            .iter()
+            .flat_map(|(source_id, note)| {
+                note.links
+                    .iter()
+                    .unique()
+                    .filter(move |target_id| {
+                        other
+                            .inner
+                            .get(source_id)
+                            .is_none_or(|other_note| !other_note.links.contains(target_id))
+                    })
+                    .map(move |target_id| {
+                        (
+                            note.display_name.clone(),
+                            self.inner
+                                .get(target_id)
+                                .map(|target| target.display_name.clone())
+                                .unwrap_or_else(|| target_id.clone()),
+                        )
+                    })
+            })
+            .sorted()
+            .collect()
+    }
+
+    /// Wrapper of the HashMap::get() Function
+    pub fn get(&self, key: &str) -> Option<&Note> {
+        self.inner.get(key)
+    }
+
+    /// Iterates over all notes in the index, in no particular order.
+    pub fn notes(&self) -> impl Iterator<Item = &Note> {
+        self.inner.values()
+    }
+
+    /// Returns the given path relative to the vault, or unchanged if it is outside of it.
+    /// The paths of notes are canonical, so the vault path is canonicalized if it is not already.
+    pub fn relative_path<'a>(&self, path: &'a std::path::Path) -> &'a std::path::Path {
+        let vault = self.builder.vault_path();
+        path.strip_prefix(vault)
+            .ok()
+            .or_else(|| {
+                vault
+                    .canonicalize()
+                    .ok()
+                    .and_then(|vault| path.strip_prefix(vault).ok())
+            })
+            .unwrap_or(path)
+    }
+
+    /// Handle all file events on notes, as found by the contained tracker.
+    ///  - Renames and moves are tracked
+    ///  - new file creations with in the vault folder are checked for notes and added if appropriate
+    ///  - removed files are removed from the index (if they were present)
+    ///  - Modifications of files are checked for being notes and if so, the respective index entries are updated with the new data.
+    ///
+    /// Returns wether the index has changed, and a list of all IdChanges.
+    pub fn handle_file_events(&mut self) -> error::Result<(bool, Vec<String>)> {
+        let mut modifications = false;
+        let mut id_changes = vec![];
+        // HTML files are only created once all events are handled, so queries within them see the new state of the vault
+        let mut rebuild = vec![];
+        let Some(tracker) = &self.tracker else {
+            return Ok((modifications, id_changes));
+        };
+        for event in tracker.try_events_iter().flatten() {
+            match event.kind {
+                notify::EventKind::Create(_)
+                // also trigger on the target of a rename (new location)
+                | notify::EventKind::Modify(notify::event::ModifyKind::Name(
+                    notify::event::RenameMode::To,
+                ))
+                     => {
+                    // Creations:
+                    // - Check if a file was created (we don't care about folders)
+                    // - Check for each path if we are interested in it (gitignore + extensions from config)
+                    // - Try to load the note and index it
+                    for path in event.paths {
+                        if tracker.is_tracked(&path) {
+                            let path = canonical(&path);
+                            self.failures.retain(|(failed, _)| *failed != path);
+                            match super::Note::from_path(&path, &self.options) {
+                                Ok(note) => {
+                                    // insert the note and create its html
+                                    let id = super::name_to_id(&note.name);
+                                    self.changed.push(id.clone());
+                                    self.created.push(id.clone());
+                                    rebuild.push(id.clone());
+                                    self.backlinks.insert(&mut self.inner, id, note);
+                                    modifications = true;
+                                }
+                                // remember the failure so it can be retried
+                                Err(e) => self.failures.push((path, e.reason())),
+                            }
+                        }
+                    }
+                }
+                // Remove events: Keep only those notes whose path was not removed
+                notify::EventKind::Remove(_)
+                // also trigger on the source of a renamed file (former location)
+                | notify::EventKind::Modify(notify::event::ModifyKind::Name(
+                    notify::event::RenameMode::From,
+                ))
+                => {
+                    let deleted_path = event
+                        .paths
+                        .first()
+                        .ok_or_else(|| error::RucolaError::NotifyEventError(event.clone()))?.to_owned();
+                    let deleted_path = deleted_path
+                        .canonicalize()
+                        .unwrap_or(deleted_path);
+
+                    // a deleted file can no longer fail
+                    self.failures.retain(|(failed, _)| *failed != deleted_path);
+
+                    if let Some(old_id) = self
+                        .inner
+                        .iter()
+                        .find(|(_id, note)| note.path.to_path_buf() == *deleted_path)
+                        .map(|(id, _n)| id.to_owned())
+                    {
+                        self.backlinks.remove(&mut self.inner, &old_id);
+                        modifications = true;
+                        id_changes.push(old_id);
+                    }
+                }
+                notify::EventKind::Modify(_kind) => {
+                    // Modifications
+                    // - For modifications, reload the entire note
+                            let modified = self
+                                .inner
+                                .iter()
+                                .filter(|(_id, note)| event.paths.iter().flat_map(|path| path.canonicalize()).contains(&note.path))
+                                .map(|(id, note)| (id.clone(), note.path.clone()))
+                                .collect::<Vec<_>>();
+                            for (id, path) in modified {
+                                if let Ok(new_note) = Note::from_path(&path, &self.options) {
+                                    // replace the index entry and recreate its html
+                                    self.changed.push(id.clone());
+                                    rebuild.push(id.clone());
+                                    self.backlinks.insert(&mut self.inner, id, new_note);
+                                    modifications = true;
+                                }
+                            }
+                    // - Files that failed before might be fixed now
+                    for path in event.paths.iter().map(|path| canonical(path)) {
+                        if let Some(position) = self.failures.iter().position(|(failed, _)| *failed == path) {
+                            match Note::from_path(&path, &self.options) {
+                                Ok(note) => {
+                                    self.failures.remove(position);
+                                    let id = super::name_to_id(&note.name);
+                                    self.changed.push(id.clone());
+                                    rebuild.push(id.clone());
+                                    self.backlinks.insert(&mut self.inner, id, note);
+                                    modifications = true;
+                                }
+                                Err(e) => self.failures[position].1 = e.reason(),
+                            }
+                        }
+                    }
+                }
+                // Do nothing in the other cases
+                notify::EventKind::Access(_) => {}
+                notify::EventKind::Other => {}
+                notify::EventKind::Any => {}
+            }
+        }
+        for id in rebuild.into_iter().unique() {
+            self.create_html(&id)?;
+        }
+        // just to be sure
+        modifications |= !id_changes.is_empty();
+        Ok((modifications, id_changes))
+    }
+
+    /// Returns the ids of all notes created or modified by file events since the last call, without duplicates.
+    pub fn take_changed(&mut self) -> Vec<String> {
+        std::mem::take(&mut self.changed)
+            .into_iter()
+            .unique()
+            .collect()
+    }
+
+    /// Returns the ids of all notes created by file events since the last call, without duplicates.
+    /// Files moved into the vault or within it count as created, as do files replaced by editors writing to a new file first.
+    pub fn take_created(&mut self) -> Vec<String> {
+        std::mem::take(&mut self.created)
+            .into_iter()
+            .unique()
+            .collect()
+    }
+
+    /// Returns the ids of all notes whose links or backlinks changed since the last call, without duplicates:
+    /// Notes created, modified or removed, the notes they link to before and after, and the notes linking to them.
+    /// Ids of missing notes may be contained.
+    pub fn take_touched(&mut self) -> Vec<String> {
+        std::mem::take(&mut self.backlinks.touched)
+            .into_iter()
+            .unique()
+            .collect()
+    }
+
+    /// Returns the ids of the notes linking to the note with the given id, sorted and without duplicates.
+    pub fn backlinks(&self, id: &str) -> Vec<String> {
+        self.backlinks
+            .of(id)
+            .iter()
+            .unique()
+            .sorted()
+            .cloned()
+            .collect()
+    }
+
+    /// Returns the ids of the notes linking to the note with the given id, once per link, for counting links.
+    pub(super) fn backlink_sources(&self, id: &str) -> &[String] {
+        self.backlinks.of(id)
+    }
+
+    /// Returns the number of notes linking to the note with the given id, counting each note once no matter how often it does.
+    pub fn backlink_count(&self, id: &str) -> usize {
+        self.backlinks.of(id).iter().unique().count()
+    }
+
+    /// Inserts the given note, replacing the note with the same id, and keeps the backlinks up to date.
+    pub(super) fn insert(&mut self, id: String, note: Note) {
+        self.backlinks.insert(&mut self.inner, id, note);
+    }
+
+    /// Suggests tags already used by other notes of the vault for the note with the given id, best first:
+    ///  - Tags matching one of the keywords of the note, ignoring the parents of nested tags.
+    ///  - Tags carried by at least two and at least half of the notes it links to.
+    ///
+    /// Tags the note already has are never suggested.
+    pub fn vault_tag_suggestions(&self, id: &str) -> Vec<super::TagSuggestion> {
+        let Some(note) = self.inner.get(id) else {
+            return Vec::new();
+        };
+        let own_tags = note.tags.iter().map(|tag| tag.to_lowercase()).collect_vec();
+
+        // all tags of other notes, by their lowercase form
+        let vault_tags = self
+            .inner
+            .iter()
+            .filter(|(other_id, _)| *other_id != id)
+            .flat_map(|(_, other)| other.tags.iter())
+            .map(|tag| (tag.to_lowercase(), tag.to_owned()))
+            .filter(|(lower, _)| !own_tags.contains(lower))
+            .collect::<HashMap<_, _>>();
+
+        let keywords = self
+            .keywords(id, KEYWORD_CANDIDATES)
+            .into_iter()
+            .map(|keyword| keyword.term)
+            .collect_vec();
+
+        let linked = note
+            .links
+            .iter()
+            .unique()
+            .filter(|link| *link != id)
+            .flat_map(|link| self.inner.get(link))
+            .collect_vec();
+        let counts = linked
+            .iter()
+            .flat_map(|other| other.tags.iter().map(|tag| tag.to_lowercase()).unique())
+            .counts();
+
+        vault_tags
+            .into_iter()
+            .map(|(lower, tag)| {
+                let leaf = lower.rsplit(['/', '#']).next().unwrap_or_default();
+                super::TagSuggestion {
+                    keyword: keywords.iter().any(|term| term == leaf),
+                    linked: counts
+                        .get(&lower)
+                        .copied()
+                        .filter(|count| *count >= 2 && 2 * count >= linked.len())
+                        .unwrap_or_default(),
+                    tag,
+                }
+            })
+            .filter(|suggestion| suggestion.keyword || suggestion.linked > 0)
+            .sorted_by(|s1, s2| {
+                s2.keyword
+                    .cmp(&s1.keyword)
+                    .then(s2.linked.cmp(&s1.linked))
+                    .then(s1.tag.cmp(&s2.tag))
+            })
+            .collect()
+    }
+
+    /// Returns an iterator over pairs of (id, name) of notes linked from this note.
+    pub fn links_vec(&self, source_id: &str) -> Vec<(String, String)> {
+        self.inner
+            .get(source_id)
+            .map(|source| {
+                source
+                    .links
+                    .iter()
+                    .flat_map(|link_id| {
+                        self.inner
+                            .get(link_id)
+                            .map(|note| note.name.clone())
+                            .map(|name| (link_id.to_owned(), name))
+                    })
+                    .unique()
+                    .sorted_by(|(id1, _), (id2, _)| id1.cmp(id2))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Returns an iterator over pairs of (id, name) of notes linking to this note.
+    pub fn blinks_vec(&self, target_id: &str) -> Vec<(String, String)> {
+        let id_copy = target_id.to_string();
+        self.inner
+            .iter()
+            .filter(|(_other_id, note)| note.links.contains(&id_copy))
+            .map(|(id, note)| (id.to_owned(), note.name.to_owned()))
+            .unique()
+            .sorted_by(|(id1, _), (id2, _)| id1.cmp(id2))
+            .collect()
+    }
+
+    /// Returns the ids of all notes linked to but missing from the index, together with the notes linking to them, sorted by id.
+    /// Links to an alias of a note are not considered missing.
+    pub fn dangling_links(&self) -> Vec<(String, Vec<&Note>)> {
+        let aliases = self
+            .inner
+            .values()
+            .flat_map(|note| note.aliases.iter().map(|alias| super::name_to_id(alias)))
+            .collect::<HashSet<_>>();
+
+        self.inner
+            .values()
+            .flat_map(|note| note.links.iter().map(move |link| (link, note)))
+            .filter(|(link, _note)| {
+                !link.is_empty() && !self.inner.contains_key(*link) && !aliases.contains(*link)
+            })
+            .into_group_map()
+            .into_iter()
+            .map(|(link, notes)| {
+                (
+                    link.to_owned(),
+                    notes
+                        .into_iter()
+                        .unique_by(|note| &note.path)
+                        .sorted_by(|n1, n2| n1.name.cmp(&n2.name))
+                        .collect(),
+                )
+            })
+            .sorted_by(|(id1, _), (id2, _)| id1.cmp(id2))
+            .collect()
+    }
+
+    /// Returns all notes of the index as a JSON array sorted by id, for external scripts.
+    /// Each note is an object with its id, display name, path, tags, links (as ids, each once), word count and character count, the latter two both of the prose and of the raw content.
+    pub fn to_json(&self) -> String {
+        serde_json::Value::Array(
+            self.inner
+                .iter()
+                .sorted_by_key(|(id, _)| *id)
+                .map(|(id, note)| {
+                    serde_json::json!({
+                        "id": id,
+                        "display_name": note.display_name,
+                        "path": note.path,
+                        "tags": note.tags,
+                        "links": note.links.iter().unique().collect_vec(),
+                        "words": note.words,
+                        "characters": note.characters,
+                        "raw_words": note.raw_words,
+                        "raw_characters": note.raw_characters,
+                    })
+                })
+                .collect(),
+        )
+        .to_string()
+    }
+
+    /// Returns the given number of terms most characteristic for the note with the given id, best first.
+    /// Terms are weighted by TF-IDF: Their frequency in the note, weighted by how few notes of the index contain them.
+    pub fn keywords(&self, id: &str, count: usize) -> Vec<super::Keyword> {
+        let Some(note) = self.inner.get(id) else {
+            return Vec::new();
+        };
+
+        super::keywords::keywords(
+            &note.terms,
+            |term| {
+                self.inner
+                    .values()
+                    .filter(|other| other.terms.contains_key(term))
+                    .count()
+            },
+            self.inner.len(),
+            count,
+        )
+    }
+
+    /// Returns up to the given number of keywords of the note with the given id that are not yet among its tags, best first.
+    pub fn tag_suggestions(&self, id: &str, count: usize) -> Vec<super::Keyword> {
+        let tags = self
+            .inner
+            .get(id)
+            .map(|note| note.tags.iter().map(|tag| tag.to_lowercase()).collect_vec())
+            .unwrap_or_default();
+
+        self.keywords(id, usize::MAX)
+            .into_iter()
+            .filter(|keyword| !tags.contains(&keyword.to_tag()))
+            .take(count)
+            .collect()
+    }
+
+    /// Scans the given content of the note with the given id for mentions of the titles of other notes.
+    /// Returns a suggestion for every mentioned note that is not yet linked, in order of appearance.
+    /// Fails if the titles of the notes cannot be searched for, see [`super::find_first_mentions`].
+    pub fn link_suggestions(
+        &self,
+        source_id: &str,
+        content: &str,
+    ) -> error::Result<Vec<super::LinkSuggestion>> {
+        let linked = self
+            .inner
+            .get(source_id)
+            .map(|note| note.links.clone())
+            .unwrap_or_default();
+
+        let candidates = self
+            .inner
+            .iter()
+            .filter(|(id, _note)| *id != source_id && !linked.contains(id))
+            .collect_vec();
+
+        // check both the display name and the file name for mentions
+        let titles = candidates
+            .iter()
+            .flat_map(|(_id, note)| [note.display_name.as_str(), note.name.as_str()])
+            .collect_vec();
+        let mentions = super::suggestions::find_first_mentions(content, &titles)?;
+
+        Ok(candidates
+            .into_iter()
+            .zip(mentions.chunks(2))
+            .flat_map(|((id, note), mentions)| {
+                mentions
+                    .iter()
+                    .flatten()
+                    .min_by_key(|range| range.start)
+                    .map(|range| {
+                        (
+                            range.start,
+                            super::LinkSuggestion {
+                                target_id: id.to_owned(),
+                                target_name: note.name.to_owned(),
+                                mention: content[range.clone()].to_owned(),
+                            },
+                        )
+                    })
+            })
+            .sorted_by(|(start1, s1), (start2, s2)| {
+                start1.cmp(start2).then(s1.target_id.cmp(&s2.target_id))
+            })
+            .map(|(_start, suggestion)| suggestion)
+            .collect())
+    }
+}
+
+/// The notes linking to each note, kept up to date as notes are inserted into and removed from an index.
+/// Mentions of person notes count as links to them, see [`Note::mention_links`].
+#[derive(Debug, Default)]
+struct Backlinks {
+    /// The ids of the notes linking to each id, once per link.
+    /// Contains the ids of missing notes as well, so the notes linking to a note are known as soon as it is created.
+    sources: HashMap<String, Vec<String>>,
+    /// The ids of the person notes by the ids they can be mentioned by.
+    persons: HashMap<String, String>,
+    /// The ids of the notes mentioning each name, once per mention.
+    mentioned_in: HashMap<String, Vec<String>>,
+    /// The ids of notes whose links or backlinks changed since they were last taken, see [`NoteIndex::take_touched`].
+    touched: Vec<String>,
+}
+
+impl Backlinks {
+    /// Collects the notes linking to each note of the given notes, and links the notes to the person notes they mention.
+    fn new(inner: &mut HashMap<String, Note>) -> Self {
+        let mut backlinks = Self {
+            persons: super::people::person_ids(inner),
+            ..Default::default()
+        };
+        for (id, note) in inner.iter_mut() {
+            backlinks.link_mentions(id, note);
+            backlinks.add_links(id, note);
+        }
+        backlinks
+    }
+
+    /// Returns the ids of the notes linking to the note with the given id, once per link.
+    fn of(&self, id: &str) -> &[String] {
+        self.sources.get(id).map(Vec::as_slice).unwrap_or_default()
+    }
+
+    /// Inserts the given note into the given notes, replacing the note with the same id.
+    fn insert(&mut self, inner: &mut HashMap<String, Note>, id: String, mut note: Note) {
+        self.remove(inner, &id);
+        self.link_mentions(&id, &mut note);
+        self.add_links(&id, &note);
+        // the links to the note are no longer broken
+        self.touched.extend(self.of(&id).to_vec());
+        self.touched.extend(note.links.iter().cloned());
+        self.touched.push(id.clone());
+
+        // notes mentioning a person note link to it
+        if note.person {
+            for name in super::people::person_names(&id, &note) {
+                for source in self.mentioned_in.get(&name).cloned().unwrap_or_default() {
+                    let Some(other) = inner.get_mut(&source) else {
+                        continue;
+                    };
+                    if !other.links.contains(&id) {
+                        other.links.push(id.clone());
+                        other.mention_links.push(id.clone());
+                        self.sources
+                            .entry(id.clone())
+                            .or_default()
+                            .push(source.clone());
+                        self.touched.push(source);
+                    }
+                }
+                self.persons.insert(name, id.clone());
+            }
+        }
+        inner.insert(id, note);
+    }
+
+    /// Removes the note with the given id from the given notes, if there is one.
+    fn remove(&mut self, inner: &mut HashMap<String, Note>, id: &str) -> Option<Note> {
+        let note = inner.remove(id)?;
+        for link in &note.links {
+            self.remove_source(link, id);
+        }
+        for mention in &note.mentions {
+            if let Some(sources) = self.mentioned_in.get_mut(mention) {
+                if let Some(position) = sources.iter().position(|source| source == id) {
+                    sources.swap_remove(position);
+                }
+                if sources.is_empty() {
+                    self.mentioned_in.remove(mention);
+                }
+            }
+        }
+
+        // mentions no longer link to a removed person note
+        if note.person {
+            self.persons.retain(|_name, person| person != id);
+            for source in self.of(id).to_vec() {
+                let Some(other) = inner.get_mut(&source) else {
+                    continue;
+                };
+                if let Some(position) = other.mention_links.iter().position(|link| link == id) {
+                    other.mention_links.remove(position);
+                    if let Some(position) = other.links.iter().position(|link| link == id) {
+                        other.links.remove(position);
+                    }
+                    self.remove_source(id, &source);
+                    self.touched.push(source);
+                }
+            }
+        }
+
+        // the links to the note are broken now
+        self.touched.extend(self.of(id).to_vec());
+        self.touched.extend(note.links.iter().cloned());
+        self.touched.push(id.to_owned());
+        Some(note)
+    }
+
+    /// Adds a link to every person note the given note mentions but does not link to yet, and records its mentions.
+    fn link_mentions(&mut self, id: &str, note: &mut Note) {
+        for mention in &note.mentions {
+            self.mentioned_in
+                .entry(mention.clone())
+                .or_default()
+                .push(id.to_owned());
+            if let Some(person) = self.persons.get(mention) {
+                if person != id && !note.links.contains(person) {
+                    note.links.push(person.clone());
+                    note.mention_links.push(person.clone());
+                }
+            }
+        }
+    }
+
+    /// Records the links of the given note.
+    fn add_links(&mut self, id: &str, note: &Note) {
+        for link in &note.links {
+            self.sources
+                .entry(link.clone())
+                .or_default()
+                .push(id.to_owned());
+        }
+    }
+
+    /// Forgets one link from the note with the given source id to the given link.
+    fn remove_source(&mut self, link: &str, source: &str) {
+        if let Some(sources) = self.sources.get_mut(link) {
+            if let Some(position) = sources.iter().position(|other| other == source) {
+                sources.swap_remove(position);
+            }
+            if sources.is_empty() {
+                self.sources.remove(link);
+            }
+        }
+    }
+}
+
+/// Canonicalizes the given path, keeping it as it is if that is not possible.
+fn canonical(path: &std::path::Path) -> std::path::PathBuf {
+    path.canonicalize().unwrap_or_else(|_| path.to_path_buf())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io;
+
+    #[test]
+    fn test_index() {
+        let config = crate::Config::default();
+        let tracker = io::FileTracker::new(&config, std::path::PathBuf::from("./tests")).unwrap();
+        let builder = io::HtmlBuilder::new(&config, std::path::PathBuf::from("./tests"));
+        let index = NoteIndex::new(tracker, builder, Default::default()).0;
+
+        assert_eq!(index.inner.len(), 12);
+
+        assert!(!index.inner.contains_key("booksold"));
+
+        let os = index.inner.get("operating-systems").unwrap();
+        let lg = index.inner.get("lie-group").unwrap();
+        let ma = index.inner.get("manifold").unwrap();
+
+        assert_eq!(os.links.len(), 6);
+        assert_eq!(os.tags, ["#os"]);
+        assert_eq!(os.name, "Operating Systems");
+        assert_eq!(os.display_name, "Operating Systems");
+        assert_eq!(os.words, 34);
+        assert_eq!(os.raw_words, 41);
+
+        assert_eq!(lg.links, ["manifold", "smooth-map", "topology"]);
+        assert_eq!(ma.tags.len(), 2);
+    }
+
+    #[test]
+    fn test_to_json() {
+        let config = crate::Config::default();
+        let tracker = io::FileTracker::new(&config, std::path::PathBuf::from("./tests")).unwrap();
+        let builder = io::HtmlBuilder::new(&config, std::path::PathBuf::from("./tests"));
+        let index = NoteIndex::new(tracker, builder, Default::default()).0;
+
+        let json = serde_json::from_str::<serde_json::Value>(&index.to_json()).unwrap();
+        let notes = json.as_array().unwrap();
+        assert_eq!(notes.len(), 12);
+        // sorted by id
+        assert!(notes
+            .iter()
+            .map(|note| note["id"].as_str().unwrap())
+            .tuple_windows()
+            .all(|(a, b)| a < b));
+
+        let lg = notes.iter().find(|note| note["id"] == "lie-group").unwrap();
+        let note = index.get("lie-group").unwrap();
+        assert_eq!(lg["display_name"], note.display_name.as_str());
+        assert_eq!(lg["path"], note.path.to_string_lossy().as_ref());
+        assert_eq!(lg["tags"], serde_json::json!(note.tags));
+        assert_eq!(
+            lg["links"],
+            serde_json::json!(["manifold", "smooth-map", "topology"])
+        );
+        assert_eq!(lg["words"], note.words);
+        assert_eq!(lg["characters"], note.characters);
+        assert_eq!(lg["raw_words"], note.raw_words);
+    }
+
+    #[test]
+    fn test_links() {
+        let config = crate::Config::default();
+        let tracker = io::FileTracker::new(&config, std::path::PathBuf::from("./tests")).unwrap();
+        let builder = io::HtmlBuilder::new(&config, std::path::PathBuf::from("./tests"));
+        let index = NoteIndex::new(tracker, builder, Default::default()).0;
+
+        assert_eq!(index.inner.len(), 12);
+
+        assert_eq!(
+            index.links_vec("lie-group"),
+            vec![
+                ("manifold".to_string(), "Manifold".to_string()),
+                ("smooth-map".to_string(), "Smooth Map".to_string()),
+                ("topology".to_string(), "Topology".to_string()),
+            ]
+        );
+
+        assert_eq!(
+            index.links_vec("atlas"),
+            vec![
+                ("chart".to_string(), "Chart".to_string()),
+                ("manifold".to_string(), "Manifold".to_string()),
+                ("topology".to_string(), "Topology".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_blinks() {
+        let config = crate::Config::default();
+        let tracker = io::FileTracker::new(&config, std::path::PathBuf::from("./tests")).unwrap();
+        let builder = io::HtmlBuilder::new(&config, std::path::PathBuf::from("./tests"));
+        let index = NoteIndex::new(tracker, builder, Default::default()).0;
+
+        assert_eq!(index.inner.len(), 12);
+
+        assert_eq!(
+            index.blinks_vec("lie-group"),
+            vec![("manifold".to_string(), "Manifold".to_string())]
+        );
+
+        assert_eq!(
+            index.blinks_vec("manifold"),
+            vec![
+                ("atlas".to_string(), "Atlas".to_string()),
+                ("chart".to_string(), "Chart".to_string()),
+                ("lie-group".to_string(), "Lie Group".to_string()),
+                ("smooth-map".to_string(), "Smooth Map".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_backlinks() {
+        let tmp = testdir::testdir!();
+        let config = crate::Config::default();
+        let tracker = io::FileTracker::new(&config, tmp.clone()).unwrap();
+        let builder = io::HtmlBuilder::new(&config, tmp.clone());
+        let mut index = NoteIndex::new(tracker, builder, Default::default()).0;
+
+        let mut insert = |name: &str, content: &str| {
+            let note = Note::from_content(
+                &tmp.join(format!("{}.md", name)),
+                content,
+                &Default::default(),
+            )
+            .unwrap();
+            index.insert(crate::data::name_to_id(name), note);
+        };
+
+        insert("Atlas", "# Atlas");
+        insert("Manifold", "Covered by an [[Atlas]].");
+        insert("Chart", "Part of an [[Atlas]], see [[Atlas]].");
+
+        // each note is listed once, sorted by id
+        assert_eq!(index.backlinks("atlas"), ["chart", "manifold"]);
+        assert_eq!(index.backlink_sources("atlas").len(), 3);
+        assert!(index.backlinks("chart").is_empty());
+    }
+
+    #[test]
+    fn test_person_mentions() {
+        let tmp = testdir::testdir!();
+        let config = crate::Config::default();
+        let tracker = io::FileTracker::new(&config, tmp.clone()).unwrap();
+        let builder = io::HtmlBuilder::new(&config, tmp.clone());
+        let mut index = NoteIndex::new(tracker, builder, Default::default()).0;
+
+        let insert = |index: &mut NoteIndex, name: &str, content: &str| {
+            let note = Note::from_content(
+                &tmp.join(format!("{}.md", name)),
+                content,
+                &Default::default(),
+            )
+            .unwrap();
+            index.insert(crate::data::name_to_id(name), note);
+        };
+        let person = "---\ntype: person\naliases: [Jane]\n---\n# Jane Doe\n";
+
+        insert(&mut index, "Standup", "@jane presented.");
+        insert(&mut index, "Review", "[[Jane Doe]] and @jane reviewed.");
+        insert(&mut index, "Jane Doe", person);
+
+        // mentions link to the person note once it exists
+        assert_eq!(index.get("standup").unwrap().links, ["jane-doe"]);
+        assert_eq!(index.get("standup").unwrap().mention_links, ["jane-doe"]);
+        assert!(index.get("review").unwrap().mention_links.is_empty());
+        assert_eq!(index.backlinks("jane-doe"), ["review", "standup"]);
+
+        // no longer a person
+        insert(&mut index, "Jane Doe", "# Jane Doe\n");
+        assert!(index.get("standup").unwrap().links.is_empty());
+        assert_eq!(index.backlinks("jane-doe"), ["review"]);
+
+        // deleted person note
+        insert(&mut index, "Jane Doe", person);
+        assert_eq!(index.backlink_count("jane-doe"), 2);
+        index.backlinks.remove(&mut index.inner, "jane-doe");
+        assert!(index.get("standup").unwrap().links.is_empty());
+        assert!(index.get("standup").unwrap().mention_links.is_empty());
+        assert_eq!(index.get("review").unwrap().links, ["jane-doe"]);
+        assert_eq!(index.backlinks("jane-doe"), ["review"]);
+
+        // mentions in notes inserted later
+        insert(&mut index, "Jane Doe", person);
+        insert(&mut index, "Retro", "Thanks @Jane_Doe.");
+        assert_eq!(index.get("retro").unwrap().links, ["jane-doe"]);
+        assert_eq!(index.backlinks("jane-doe"), ["retro", "review", "standup"]);
+    }
+
+    #[test]
+    fn test_links_not_in() {
+        let config = crate::Config::default();
+        let tracker = io::FileTracker::new(&config, std::path::PathBuf::from("./tests")).unwrap();
+        let builder = io::HtmlBuilder::new(&config, std::path::PathBuf::from("./tests"));
+        let now = NoteIndex::new(tracker, builder, Default::default()).0;
+
+        let tracker = io::FileTracker::new(&config, std::path::PathBuf::from("./tests")).unwrap();
+        let builder = io::HtmlBuilder::new(&config, std::path::PathBuf::from("./tests"));
+        let mut then = NoteIndex::new(tracker, builder, Default::default()).0;
+
+        assert!(now.links_not_in(&then).is_empty());
+
+        // the atlas did not exist back then, and the chart still linked to the lie group
+        then.inner.remove("atlas");
+        then.inner
+            .get_mut("chart")
+            .unwrap()
+            .links
+            .push("lie-group".to_string());
+
+        assert_eq!(now.notes_not_in(&then), vec!["Atlas"]);
+        assert!(then.notes_not_in(&now).is_empty());
+
+        assert_eq!(
+            now.links_not_in(&then),
+            vec![
+                ("Atlas".to_string(), "Chart".to_string()),
+                ("Atlas".to_string(), "Manifold".to_string()),
+                ("Atlas".to_string(), "Topology".to_string()),
+            ]
+        );
+
+        assert_eq!(
+            then.links_not_in(&now),
+            vec![("Chart".to_string(), "Lie Group".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_links_yaml() {
+        let config = crate::Config::default();
+        let tracker = io::FileTracker::new(&config, std::path::PathBuf::from("./tests")).unwrap();
+        let builder = io::HtmlBuilder::new(&config, std::path::PathBuf::from("./tests"));
+        let index = NoteIndex::new(tracker, builder, Default::default()).0;
+
+        assert_eq!(index.inner.len(), 12);
+
+        assert_eq!(
+            index.links_vec("windows"),
+            vec![("note25".to_string(), "note25".to_string()),]
+        );
+
+        assert_eq!(
+            index.blinks_vec("note25"),
+            vec![("windows".to_string(), "Windows".to_string()),]
+        );
+    }
+
+    #[test]
+    fn test_link_suggestions() {
+        let config = crate::Config::default();
+        let tracker = io::FileTracker::new(&config, std::path::PathBuf::from("./tests")).unwrap();
+        let builder = io::HtmlBuilder::new(&config, std::path::PathBuf::from("./tests"));
+        let index = NoteIndex::new(tracker, builder, Default::default()).0;
+
+        let suggestions = index
+            .link_suggestions(
+                "new-note",
+                "# New Note\nEvery smooth manifold has an atlas, see [[Chart]] and YAML format.",
+            )
+            .unwrap();
+
+        assert_eq!(
+            suggestions
+                .iter()
+                .map(|s| (s.target_id.as_str(), s.mention.as_str()))
+                .collect_vec(),
+            vec![
+                ("manifold", "manifold"),
+                ("atlas", "atlas"),
+                ("note25", "YAML format"),
+            ]
+        );
+
+        // links that already exist in the note are not suggested again
+        assert!(index
+            .link_suggestions("lie-group", "A lie group is a smooth manifold.")
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_retry_failures() {
+        let vault = testdir::testdir!().join("vault");
+        std::fs::create_dir_all(&vault).unwrap();
+        std::fs::write(vault.join("Atlas.md"), "# Atlas").unwrap();
+        std::fs::write(
+            vault.join("Broken.md"),
+            "---\ntags: [unclosed\n---\n# Broken",
+        )
+        .unwrap();
+
+        let config = crate::Config::default();
+        let tracker = io::FileTracker::new(&config, vault.clone()).unwrap();
+        let builder = io::HtmlBuilder::new(&config, vault.clone());
+        let (mut index, errors) = NoteIndex::new(tracker, builder, Default::default());
+
+        // The broken note is left out, but everything else is indexed.
+        assert_eq!(errors.len(), 1);
+        assert_eq!(index.inner.len(), 1);
+        assert!(index.is_degraded());
+        assert_eq!(index.failures().len(), 1);
+        assert!(index.failures()[0].0.ends_with("Broken.md"));
+
+        // Retrying without a fix keeps the failure.
+        let (modifications, errors) = index.retry_failures();
+        assert!(!modifications);
+        assert_eq!(errors.len(), 1);
+        assert_eq!(index.failures().len(), 1);
+
+        // Once fixed, the note is indexed.
+        std::fs::write(
+            vault.join("Broken.md"),
+            "---\ntags: [closed]\n---\n# Broken",
+        )
+        .unwrap();
+        let (modifications, errors) = index.retry_failures();
+        assert!(modifications);
+        assert!(errors.is_empty());
+        assert!(!index.is_degraded());
+        assert!(index.inner.contains_key("broken"));
+    }
+
+    #[test]
+    fn test_keywords() {
+        let config = crate::Config::default();
+        let tracker = io::FileTracker::new(&config, std::path::PathBuf::from("./tests")).unwrap();
+        let builder = io::HtmlBuilder::new(&config, std::path::PathBuf::from("./tests"));
+        let index = NoteIndex::new(tracker, builder, Default::default()).0;
+
+        let keywords = index.keywords("chart", 5);
+        assert_eq!(keywords.len(), 5);
+        assert!(keywords
+            .windows(2)
+            .all(|pair| pair[0].score >= pair[1].score));
+        assert!(index.keywords("no-such-note", 5).is_empty());
+
+        // Tags the note already has are not suggested again.
+        let chart = index.get("chart").unwrap();
+        assert!(index
+            .tag_suggestions("chart", 20)
+            .iter()
+            .all(|keyword| !chart.tags.contains(&keyword.to_tag())));
+    }
+
+    #[test]
+    fn test_dangling_links() {
+        let config = crate::Config::default();
+        let tracker = io::FileTracker::new(&config, std::path::PathBuf::from("./tests")).unwrap();
+        let builder = io::HtmlBuilder::new(&config, std::path::PathBuf::from("./tests"));
+        let index = NoteIndex::new(tracker, builder, Default::default()).0;
+
+        let dangling = index
+            .dangling_links()
+            .into_iter()
+            .map(|(id, notes)| (id, notes.iter().map(|note| note.name.clone()).collect_vec()))
+            .collect_vec();
+        assert_eq!(
+            dangling,
+            vec![(String::from("diffeomorphism"), vec![String::from("Chart")])]
+        );
+    }
+
+    #[test]
+    fn test_vault_tag_suggestions() {
+        let vault = testdir::testdir!().join("vault");
+        std::fs::create_dir_all(&vault).unwrap();
+        std::fs::write(
+            vault.join("Atlas.md"),
+            "# Atlas\nAn atlas is a collection of charts covering a manifold.\nEvery manifold has one.\n[[Chart]] [[Map]] [[Group]]",
+        )
+        .unwrap();
+        std::fs::write(
+            vault.join("Chart.md"),
+            "---\ntags: [geometry, topology/manifold]\n---\n# Chart",
+        )
+        .unwrap();
+        std::fs::write(vault.join("Map.md"), "# Map\n#geometry").unwrap();
+        std::fs::write(vault.join("Group.md"), "# Group\n#algebra").unwrap();
+
+        let config = crate::Config::default();
+        let tracker = io::FileTracker::new(&config, vault.clone()).unwrap();
+        let builder = io::HtmlBuilder::new(&config, vault.clone());
+        let index = NoteIndex::new(tracker, builder, Default::default()).0;
+
+        // keyword matches come first, tags of only a single linked note are not suggested on their own
+        let suggestions = index.vault_tag_suggestions("atlas");
+        assert_eq!(
+            suggestions
+                .iter()
+                .map(|suggestion| suggestion.tag.as_str())
+                .collect_vec(),
+            vec!["#topology/manifold", "#geometry"]
+        );
+        assert!(suggestions[0].keyword);
+        assert_eq!(suggestions[1].linked, 2);
+
+        // tags the note already has are not suggested
+        assert!(index.vault_tag_suggestions("map").is_empty());
+    }
+}
@@ -0,0 +1,253 @@
+use std::collections::HashMap;
+
+/// Words shorter than this are never considered keywords.
+const MIN_TERM_LENGTH: usize = 3;
+
+/// Common words that carry no meaning on their own and are never considered keywords.
+const STOPWORDS: &[&str] = &[
+    "about",
+    "above",
+    "after",
+    "again",
+    "against",
+    "all",
+    "also",
+    "and",
+    "any",
+    "are",
+    "because",
+    "been",
+    "before",
+    "being",
+    "below",
+    "between",
+    "both",
+    "but",
+    "can",
+    "could",
+    "did",
+    "does",
+    "doing",
+    "down",
+    "during",
+    "each",
+    "either",
+    "else",
+    "even",
+    "every",
+    "few",
+    "for",
+    "from",
+    "further",
+    "had",
+    "has",
+    "have",
+    "having",
+    "her",
+    "here",
+    "hers",
+    "herself",
+    "him",
+    "himself",
+    "his",
+    "how",
+    "however",
+    "into",
+    "its",
+    "itself",
+    "just",
+    "let",
+    "like",
+    "may",
+    "more",
+    "most",
+    "much",
+    "must",
+    "need",
+    "not",
+    "now",
+    "off",
+    "once",
+    "one",
+    "only",
+    "other",
+    "our",
+    "ours",
+    "ourselves",
+    "out",
+    "over",
+    "own",
+    "same",
+    "see",
+    "she",
+    "should",
+    "since",
+    "some",
+    "such",
+    "than",
+    "that",
+    "the",
+    "their",
+    "theirs",
+    "them",
+    "themselves",
+    "then",
+    "there",
+    "these",
+    "they",
+    "this",
+    "those",
+    "through",
+    "thus",
+    "too",
+    "two",
+    "under",
+    "until",
+    "upon",
+    "use",
+    "used",
+    "using",
+    "very",
+    "was",
+    "way",
+    "well",
+    "were",
+    "what",
+    "when",
+    "where",
+    "whether",
+    "which",
+    "while",
+    "who",
+    "whom",
+    "why",
+    "will",
+    "with",
+    "within",
+    "without",
+    "would",
+    "yet",
+    "you",
+    "your",
+    "yours",
+    "yourself",
+    "yourselves",
+];
+
+/// A term characteristic for a note, as found by comparing its frequency in the note with its frequency in the vault.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Keyword {
+    /// The term, in lowercase.
+    pub term: String,
+    /// The TF-IDF score of the term: How often it occurs in the note, weighted by how rare it is in the vault.
+    pub score: f64,
+}
+
+impl Keyword {
+    /// Returns the tag this keyword would be added to notes as.
+    pub fn to_tag(&self) -> String {
+        format!("#{}", self.term)
+    }
+}
+
+/// Counts how often each term occurs in the given text.
+/// Terms are words of letters (and digits or dashes after the first letter), in lowercase, excluding stop words, very short words and tags.
+pub fn count_terms(text: &str, counts: &mut HashMap<String, usize>) {
+    for word in text
+        .split_whitespace()
+        .filter(|chunk| !chunk.starts_with('#'))
+        .flat_map(|chunk| chunk.split(|c: char| !(c.is_alphanumeric() || c == '-')))
+    {
+        let word = word.trim_matches('-');
+        if word.chars().count() < MIN_TERM_LENGTH
+            || !word.chars().next().is_some_and(char::is_alphabetic)
+        {
+            continue;
+        }
+
+        let term = word.to_lowercase();
+        if STOPWORDS.binary_search(&term.as_str()).is_err() {
+            *counts.entry(term).or_default() += 1;
+        }
+    }
+}
+
+/// Scores the terms of a note by TF-IDF and returns the given number of best ones, best first.
+///  - `terms` are the term counts of the note.
+///  - `document_frequency` returns the number of notes in the vault containing a term.
+///  - `documents` is the number of notes in the vault.
+pub fn keywords(
+    terms: &HashMap<String, usize>,
+    document_frequency: impl Fn(&str) -> usize,
+    documents: usize,
+    count: usize,
+) -> Vec<Keyword> {
+    let total = terms.values().sum::<usize>().max(1) as f64;
+
+    let mut keywords = terms
+        .iter()
+        .map(|(term, occurences)| {
+            let tf = *occurences as f64 / total;
+            // smoothed, so terms occuring in every note still get a small weight
+            let idf = ((1 + documents) as f64 / (1 + document_frequency(term)) as f64).ln() + 1.0;
+            Keyword {
+                term: term.to_owned(),
+                score: tf * idf,
+            }
+        })
+        .collect::<Vec<_>>();
+
+    keywords.sort_by(|k1, k2| {
+        k2.score
+            .total_cmp(&k1.score)
+            .then_with(|| k1.term.cmp(&k2.term))
+    });
+    keywords.truncate(count);
+    keywords
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stopwords_sorted() {
+        assert!(STOPWORDS.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+
+    #[test]
+    fn test_count_terms() {
+        let mut counts = HashMap::new();
+        count_terms(
+            "The Lie group acts on the manifold; a smooth-manifold is a manifold. 42 is an #answer answer.",
+            &mut counts,
+        );
+
+        assert_eq!(counts.get("manifold"), Some(&2));
+        assert_eq!(counts.get("smooth-manifold"), Some(&1));
+        assert_eq!(counts.get("group"), Some(&1));
+        assert_eq!(counts.get("answer"), Some(&1));
+        // stop words, short words, numbers and tags are skipped
+        assert!(!counts.contains_key("the"));
+        assert!(!counts.contains_key("on"));
+        assert!(!counts.contains_key("42"));
+    }
+
+    #[test]
+    fn test_keywords() {
+        let terms = HashMap::from([
+            (String::from("manifold"), 3),
+            (String::from("group"), 3),
+            (String::from("chart"), 1),
+        ]);
+
+        // "group" occurs in every note, "manifold" and "chart" only in this one
+        let keywords = keywords(&terms, |term| if term == "group" { 10 } else { 1 }, 10, 2);
+
+        assert_eq!(
+            keywords.iter().map(|k| k.term.as_str()).collect::<Vec<_>>(),
+            vec!["manifold", "group"]
+        );
+        assert!(keywords[0].score > keywords[1].score);
+    }
+}
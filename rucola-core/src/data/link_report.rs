@@ -0,0 +1,112 @@
+use std::collections::HashSet;
+
+use itertools::Itertools;
+
+use super::NoteIndex;
+
+/// A link of a note to a note that does not exist.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BrokenLink {
+    /// The id of the note containing the link.
+    pub id: String,
+    /// The target of the link as written in the note.
+    pub target: String,
+    /// The number of the line containing the link within the note file, starting at 1, if it was found.
+    pub line: Option<usize>,
+}
+
+/// Finds all links of the notes of the given index to notes missing from it, reading the notes from the file system.
+/// Links to an alias of a note are not considered broken. Links that cannot be found in the file are reported with their id as the target.
+/// The links are sorted by the name of their note and their line.
+pub fn broken_links(index: &NoteIndex) -> Vec<BrokenLink> {
+    let missing = index
+        .dangling_links()
+        .into_iter()
+        .map(|(target, _notes)| target)
+        .collect::<HashSet<_>>();
+
+    index
+        .inner
+        .iter()
+        .filter(|(_, note)| note.links.iter().any(|link| missing.contains(link)))
+        .sorted_by_key(|(_, note)| note.name.to_lowercase())
+        .flat_map(|(id, note)| {
+            let content = std::fs::read_to_string(&note.path).unwrap_or_default();
+            let mut found = HashSet::new();
+
+            // all occurrences of the missing targets in the text
+            let mut links = content
+                .lines()
+                .enumerate()
+                .flat_map(|(number, line)| {
+                    super::lint::link_targets(line)
+                        .into_iter()
+                        .map(move |target| (number + 1, target))
+                })
+                .filter(|(_, target)| missing.contains(&super::name_to_id(target)))
+                .map(|(number, target)| {
+                    found.insert(super::name_to_id(target));
+                    BrokenLink {
+                        id: id.to_owned(),
+                        target: target.trim().to_owned(),
+                        line: Some(number),
+                    }
+                })
+                .collect_vec();
+
+            // links written in another way, e.g. in the syntax of Org-mode
+            links.extend(
+                note.links
+                    .iter()
+                    .filter(|link| missing.contains(*link) && !found.contains(*link))
+                    .unique()
+                    .map(|link| BrokenLink {
+                        id: id.to_owned(),
+                        target: link.to_owned(),
+                        line: None,
+                    }),
+            );
+            links
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_broken_links() {
+        let vault = testdir::testdir!();
+        std::fs::write(
+            vault.join("Trip.md"),
+            "# Trip\nSee [[Rome]] and [[Paris|the capital]].\n\nAgain [[Paris#Louvre]] and [Milan](Milan).\n",
+        )
+        .unwrap();
+        std::fs::write(vault.join("Rome.md"), "# Rome\nBack to [[Trip]].\n").unwrap();
+        std::fs::write(
+            vault.join("Alps.md"),
+            "---\naliases: [Mountains]\n---\n# Alps\n[[Mountains]] [[Zurich]]\n",
+        )
+        .unwrap();
+
+        let config = crate::Config::default();
+        let tracker = crate::io::FileTracker::new(&config, vault.clone()).unwrap();
+        let builder = crate::io::HtmlBuilder::new(&config, vault);
+        let index = NoteIndex::new(tracker, builder, Default::default()).0;
+
+        let links = broken_links(&index);
+        assert_eq!(
+            links
+                .iter()
+                .map(|link| (link.id.as_str(), link.target.as_str(), link.line))
+                .collect_vec(),
+            vec![
+                ("alps", "Zurich", Some(5)),
+                ("trip", "Paris", Some(2)),
+                ("trip", "Paris", Some(4)),
+                ("trip", "Milan", Some(4)),
+            ]
+        );
+    }
+}
@@ -0,0 +1,558 @@
+use std::fmt;
+
+use itertools::Itertools;
+
+use crate::{error, io};
+
+use super::{Note, NoteIndex};
+
+/// How severe the problems found by a lint rule are. Rules set to `Off` are not checked.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, serde::Serialize, serde::Deserialize,
+)]
+pub enum Severity {
+    Off,
+    Hint,
+    Warning,
+    Error,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Severity::Off => "Off",
+                Severity::Hint => "Hint",
+                Severity::Warning => "Warning",
+                Severity::Error => "Error",
+            }
+        )
+    }
+}
+
+/// The checks the linter runs on every note.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LintRule {
+    /// The note has neither a title in its frontmatter nor a level 1 heading.
+    MissingTitle,
+    /// The note has no tags.
+    MissingTags,
+    /// A heading is more than one level deeper than the previous one.
+    HeadingJump,
+    /// A line ends in whitespace other than a markdown line break.
+    TrailingWhitespace,
+    /// A line is longer than the configured maximum.
+    LongLine,
+    /// A link has an empty target or points to a note or block that does not exist.
+    BrokenLink,
+    /// A word is not known to the spellchecker.
+    Spelling,
+}
+
+impl LintRule {
+    /// Wether problems found by this rule can be fixed automatically.
+    pub fn fixable(&self) -> bool {
+        matches!(
+            self,
+            LintRule::MissingTitle | LintRule::HeadingJump | LintRule::TrailingWhitespace
+        )
+    }
+}
+
+/// The severities of the lint rules and their options, as set in the config file.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct LintConfig {
+    /// Notes without a title in their frontmatter or a level 1 heading.
+    pub missing_title: Severity,
+    /// Notes without tags.
+    pub missing_tags: Severity,
+    /// Headings more than one level deeper than the previous one.
+    pub heading_jump: Severity,
+    /// Lines ending in whitespace other than a markdown line break.
+    pub trailing_whitespace: Severity,
+    /// Lines longer than `max_line_length`.
+    pub long_line: Severity,
+    /// Links with an empty target or to notes or blocks that do not exist.
+    pub broken_link: Severity,
+    /// Words not known to the spellchecker, if one is configured.
+    pub spelling: Severity,
+    /// The maximum number of characters in a line.
+    pub max_line_length: usize,
+}
+
+impl Default for LintConfig {
+    fn default() -> Self {
+        Self {
+            missing_title: Severity::Warning,
+            missing_tags: Severity::Hint,
+            heading_jump: Severity::Warning,
+            trailing_whitespace: Severity::Hint,
+            long_line: Severity::Off,
+            broken_link: Severity::Error,
+            spelling: Severity::Hint,
+            max_line_length: 120,
+        }
+    }
+}
+
+/// A problem found in a note.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    /// The rule that found the problem.
+    pub rule: LintRule,
+    /// How severe the problem is.
+    pub severity: Severity,
+    /// The line of the problem, starting at 1, if it can be attributed to one.
+    pub line: Option<usize>,
+    /// What is wrong.
+    pub message: String,
+    /// The misspelled word, for problems found by the spellchecker.
+    pub word: Option<String>,
+}
+
+/// Checks notes for common problems according to the configured rules and fixes some of them.
+#[derive(Debug, Clone, Default)]
+pub struct Linter {
+    /// The severities of the rules and their options.
+    config: LintConfig,
+}
+
+impl Linter {
+    /// Creates a linter with the rules of the given config.
+    pub fn new(config: &crate::Config) -> Self {
+        Self {
+            config: config.lint.clone(),
+        }
+    }
+
+    /// Returns the configured severity of the given rule.
+    fn severity(&self, rule: LintRule) -> Severity {
+        match rule {
+            LintRule::MissingTitle => self.config.missing_title,
+            LintRule::MissingTags => self.config.missing_tags,
+            LintRule::HeadingJump => self.config.heading_jump,
+            LintRule::TrailingWhitespace => self.config.trailing_whitespace,
+            LintRule::LongLine => self.config.long_line,
+            LintRule::BrokenLink => self.config.broken_link,
+            LintRule::Spelling => self.config.spelling,
+        }
+    }
+
+    /// Checks all notes of the given index, or only the one with the given id, reading their content from the file system.
+    /// Their spelling is checked as well if a spellchecker is given.
+    /// Returns the ids of all notes with problems and their problems, most severe first, and the errors of the spellchecker.
+    pub fn lint_index(
+        &self,
+        index: &NoteIndex,
+        only: Option<&str>,
+        spellchecker: Option<&io::Spellchecker>,
+    ) -> (Vec<(String, Vec<Diagnostic>)>, Vec<error::RucolaError>) {
+        let mut errors = Vec::new();
+
+        let problems = index
+            .inner
+            .iter()
+            .filter(|(id, _)| only.is_none_or(|only| only == id.as_str()))
+            .flat_map(|(id, note)| {
+                let content = std::fs::read_to_string(&note.path).ok()?;
+                let mut diagnostics = self.lint(note, &content, index);
+
+                if let Some(spellchecker) = spellchecker.filter(|_| self.checks_spelling()) {
+                    match spellchecker.check(&content) {
+                        Ok(misspellings) => diagnostics.extend(self.spelling(misspellings)),
+                        Err(e) => errors.push(e.in_file(&note.path, None)),
+                    }
+                    diagnostics.sort_by_key(|diagnostic| diagnostic.line);
+                }
+
+                (!diagnostics.is_empty()).then(|| (id.to_owned(), diagnostics))
+            })
+            .sorted_by(|(id1, d1), (id2, d2)| {
+                let worst =
+                    |diagnostics: &[Diagnostic]| diagnostics.iter().map(|d| d.severity).max();
+                worst(d2).cmp(&worst(d1)).then(id1.cmp(id2))
+            })
+            .collect();
+
+        (problems, errors)
+    }
+
+    /// Checks the given note with the given content, resolving links with the given index.
+    /// Returns all problems found by enabled rules, in the order of their lines.
+    pub fn lint(&self, note: &Note, content: &str, index: &NoteIndex) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+        let mut report = |rule: LintRule, line: Option<usize>, message: String| {
+            let severity = self.severity(rule);
+            if severity != Severity::Off {
+                diagnostics.push(Diagnostic {
+                    rule,
+                    severity,
+                    line,
+                    message,
+                    word: None,
+                });
+            }
+        };
+
+        let (frontmatter, body, _) = split_lines(content);
+
+        if !frontmatter
+            .iter()
+            .any(|(_, line)| line.starts_with("title:"))
+            && !body.iter().any(|(_, line)| heading_level(line) == Some(1))
+        {
+            report(
+                LintRule::MissingTitle,
+                None,
+                String::from(
+                    "The note has neither a title in its frontmatter nor a level 1 heading.",
+                ),
+            );
+        }
+
+        if note.tags.is_empty() {
+            report(
+                LintRule::MissingTags,
+                None,
+                String::from("The note has no tags."),
+            );
+        }
+
+        let mut previous = None;
+        for (number, line) in body.iter() {
+            if let Some(level) = heading_level(line) {
+                if previous.is_some_and(|previous| level > previous + 1) {
+                    report(
+                        LintRule::HeadingJump,
+                        Some(*number),
+                        format!(
+                            "Level {} heading follows a level {} heading.",
+                            level,
+                            previous.unwrap_or_default()
+                        ),
+                    );
+                }
+                previous = Some(level);
+            }
+        }
+
+        for (number, line) in content.lines().enumerate() {
+            if trailing_whitespace(line) > 0 {
+                report(
+                    LintRule::TrailingWhitespace,
+                    Some(number + 1),
+                    String::from("The line ends in whitespace."),
+                );
+            }
+        }
+
+        for (number, line) in body.iter() {
+            let length = line.chars().count();
+            // lines without spaces, such as long URLs, cannot be broken anyway
+            if length > self.config.max_line_length && line.trim().contains(' ') {
+                report(
+                    LintRule::LongLine,
+                    Some(*number),
+                    format!(
+                        "The line is {} characters long, more than {}.",
+                        length, self.config.max_line_length
+                    ),
+                );
+            }
+        }
+
+        for link in note.links.iter().unique() {
+            if link.is_empty() || index.get(link).is_none() {
+                let line = body
+                    .iter()
+                    .find(|(_, line)| {
+                        link_targets(line)
+                            .iter()
+                            .any(|target| super::name_to_id(target) == *link)
+                    })
+                    .map(|(number, _)| *number);
+                report(
+                    LintRule::BrokenLink,
+                    line,
+                    if link.is_empty() {
+                        String::from("The link has no target.")
+                    } else {
+                        format!("The linked note `{}` does not exist.", link)
+                    },
+                );
+            }
+        }
+
+        // references to blocks of existing notes have to find the block
+        for (number, line) in body.iter() {
+            for (target, block) in super::block_references(line) {
+                if let Some(target) = index
+                    .get(&super::name_to_id(&target))
+                    .filter(|target| !target.blocks.contains(&block))
+                {
+                    report(
+                        LintRule::BrokenLink,
+                        Some(*number),
+                        format!(
+                            "The block `^{}` does not exist in `{}`.",
+                            block, target.name
+                        ),
+                    );
+                }
+            }
+        }
+
+        diagnostics.sort_by_key(|diagnostic| diagnostic.line);
+        diagnostics
+    }
+
+    /// Turns the given misspellings into problems, unless spelling problems are turned off.
+    pub fn spelling(&self, misspellings: Vec<super::Misspelling>) -> Vec<Diagnostic> {
+        let severity = self.severity(LintRule::Spelling);
+        if severity == Severity::Off {
+            return Vec::new();
+        }
+
+        misspellings
+            .into_iter()
+            .map(|misspelling| Diagnostic {
+                rule: LintRule::Spelling,
+                severity,
+                line: Some(misspelling.line),
+                message: format!(
+                    "`{}` may be misspelled: {}",
+                    misspelling.word, misspelling.context
+                ),
+                word: Some(misspelling.word),
+            })
+            .collect()
+    }
+
+    /// Wether spelling problems are reported at all.
+    pub fn checks_spelling(&self) -> bool {
+        self.severity(LintRule::Spelling) != Severity::Off
+    }
+
+    /// Fixes the problems of the given note with the given content that can be fixed automatically and whose rules are enabled:
+    ///  - A missing title is added as a level 1 heading with the name of the note.
+    ///  - Headings too deep for their predecessor are raised to one level below it.
+    ///  - Trailing whitespace is removed.
+    ///
+    /// Returns the fixed content.
+    pub fn fix(&self, note: &Note, content: &str) -> String {
+        let (frontmatter, body, body_start) = split_lines(content);
+
+        let mut lines = content
+            .split_inclusive('\n')
+            .map(|line| {
+                let text = line.trim_end_matches(['\n', '\r']);
+                (text.to_owned(), &line[text.len()..])
+            })
+            .collect::<Vec<_>>();
+
+        if self.severity(LintRule::HeadingJump) != Severity::Off {
+            let mut previous = None;
+            for (number, line) in body.iter() {
+                if let Some(mut level) = heading_level(line) {
+                    if let Some(previous) = previous.filter(|previous| level > previous + 1) {
+                        level = previous + 1;
+                        lines[number - 1].0 =
+                            format!("{}{}", "#".repeat(level), line.trim_start_matches('#'));
+                    }
+                    previous = Some(level);
+                }
+            }
+        }
+
+        if self.severity(LintRule::TrailingWhitespace) != Severity::Off {
+            for (line, _) in lines.iter_mut() {
+                let excess = trailing_whitespace(line);
+                line.truncate(line.len() - excess);
+            }
+        }
+
+        let mut fixed = lines
+            .into_iter()
+            .map(|(line, ending)| line + ending)
+            .collect::<Vec<_>>();
+
+        if self.severity(LintRule::MissingTitle) != Severity::Off
+            && !frontmatter
+                .iter()
+                .any(|(_, line)| line.starts_with("title:"))
+            && !body.iter().any(|(_, line)| heading_level(line) == Some(1))
+        {
+            fixed.insert(
+                (body_start - 1).min(fixed.len()),
+                format!("# {}\n\n", note.name),
+            );
+        }
+
+        fixed.concat()
+    }
+}
+
+/// Lines of a note, with their line numbers starting at 1.
+type Lines<'a> = Vec<(usize, &'a str)>;
+
+/// Splits the given content into the lines of the frontmatter and the lines of the body outside of code blocks.
+/// Also returns the number of the first line of the body.
+pub(super) fn split_lines(content: &str) -> (Lines<'_>, Lines<'_>, usize) {
+    let lines = content
+        .lines()
+        .enumerate()
+        .map(|(i, line)| (i + 1, line))
+        .collect_vec();
+
+    // The frontmatter has to start in the very first line.
+    let frontmatter_end = if lines.first().map(|(_, line)| line.trim_end()) == Some("---") {
+        lines
+            .iter()
+            .skip(1)
+            .position(|(_, line)| line.trim_end() == "---")
+            .map(|pos| pos + 1)
+    } else {
+        None
+    };
+
+    let (frontmatter, rest, body_start) = match frontmatter_end {
+        Some(end) => (lines[1..end].to_vec(), &lines[end + 1..], end + 2),
+        None => (Vec::new(), &lines[..], 1),
+    };
+
+    let mut in_code = false;
+    let body = rest
+        .iter()
+        .filter(|(_, line)| {
+            if line.trim_start().starts_with("```") || line.trim_start().starts_with("~~~") {
+                in_code = !in_code;
+                return false;
+            }
+            !in_code
+        })
+        .copied()
+        .collect();
+
+    (frontmatter, body, body_start)
+}
+
+/// Returns the level of the heading in the given line, if it is one.
+pub(super) fn heading_level(line: &str) -> Option<usize> {
+    let level = line.chars().take_while(|c| *c == '#').count();
+    ((1..=6).contains(&level) && line[level..].starts_with([' ', '\t'])).then_some(level)
+}
+
+/// Returns the number of bytes of whitespace at the end of the given line that are not a markdown line break (exactly two spaces).
+fn trailing_whitespace(line: &str) -> usize {
+    let excess = line.len() - line.trim_end().len();
+    if line.trim().is_empty() || (excess == 2 && line.ends_with("  ")) {
+        0
+    } else {
+        excess
+    }
+}
+
+/// Returns the raw targets of all wiki and markdown links in the given line.
+pub(super) fn link_targets(line: &str) -> Vec<&str> {
+    let wiki = line.split("[[").skip(1).flat_map(|rest| {
+        let link = rest.split("]]").next()?;
+        link.split(['|', '#']).next()
+    });
+    let markdown = line
+        .split("](")
+        .skip(1)
+        .flat_map(|rest| rest.split(')').next());
+    wiki.chain(markdown).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Returns an index of an empty vault.
+    fn empty_index() -> NoteIndex {
+        let vault = testdir::testdir!();
+        let config = crate::Config::default();
+        let tracker = crate::io::FileTracker::new(&config, vault.clone()).unwrap();
+        let builder = crate::io::HtmlBuilder::new(&config, vault);
+        NoteIndex::new(tracker, builder, Default::default()).0
+    }
+
+    #[test]
+    fn test_lint() {
+        let content = "---\ntags: [math]\n---\n## Charts  \nA chart.\t\n#### Atlas\n```\n# not a heading\n```\nSee [[Manifold]] and [[Missing|gone]] or [nothing]().\nAs in [[Manifold#^def]].\n";
+        let note = Note::from_content(
+            std::path::Path::new("Chart.md"),
+            content,
+            &Default::default(),
+        )
+        .unwrap();
+        let mut index = empty_index();
+        index
+            .inner
+            .insert(String::from("manifold"), Note::default());
+
+        let diagnostics = Linter::default().lint(&note, content, &index);
+        let found = diagnostics
+            .iter()
+            .map(|diagnostic| (diagnostic.rule, diagnostic.line))
+            .collect_vec();
+
+        assert_eq!(
+            found,
+            vec![
+                (LintRule::MissingTitle, None),
+                (LintRule::TrailingWhitespace, Some(5)),
+                (LintRule::HeadingJump, Some(6)),
+                (LintRule::BrokenLink, Some(10)),
+                (LintRule::BrokenLink, Some(10)),
+                (LintRule::BrokenLink, Some(11)),
+            ]
+        );
+        assert_eq!(diagnostics[0].severity, Severity::Warning);
+
+        // rules can be turned off
+        let linter = Linter {
+            config: LintConfig {
+                broken_link: Severity::Off,
+                ..Default::default()
+            },
+        };
+        assert!(linter
+            .lint(&note, content, &index)
+            .iter()
+            .all(|diagnostic| diagnostic.rule != LintRule::BrokenLink));
+    }
+
+    #[test]
+    fn test_fix() {
+        let content = "---\ntags: [math]\n---\n## Charts  \nA chart.\t\n#### Atlas\n";
+        let note = Note::from_content(
+            std::path::Path::new("Chart.md"),
+            content,
+            &Default::default(),
+        )
+        .unwrap();
+
+        let fixed = Linter::default().fix(&note, content);
+        assert_eq!(
+            fixed,
+            "---\ntags: [math]\n---\n# Chart\n\n## Charts  \nA chart.\n### Atlas\n"
+        );
+
+        // fixed notes have no fixable problems left
+        let note = Note::from_content(
+            std::path::Path::new("Chart.md"),
+            &fixed,
+            &Default::default(),
+        )
+        .unwrap();
+        assert!(Linter::default()
+            .lint(&note, &fixed, &empty_index())
+            .iter()
+            .all(|diagnostic| !diagnostic.rule.fixable()));
+    }
+}
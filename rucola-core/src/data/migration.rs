@@ -0,0 +1,352 @@
+use std::{borrow::Cow, collections::HashMap, fs, path};
+
+use itertools::Itertools;
+
+use crate::error;
+
+use super::{name_to_id, Note, NoteIndex};
+
+/// A date at the start of a note name, as in `2024-03-05 Lie Group` or `20240305-lie-group`, including a following separator.
+const DATE_PREFIX: &str = r"^\d{4}-?\d{2}-?\d{2}(?:[ _-]|$)";
+
+/// A way of naming note files that the notes of a vault can be migrated to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum NamingScheme {
+    /// Replace spaces and underscores by dashes, e.g. `Lie Group` becomes `Lie-Group`.
+    Dashes,
+    /// Replace spaces and dashes by underscores, e.g. `Lie Group` becomes `Lie_Group`.
+    Underscores,
+    /// Replace dashes and underscores by spaces, e.g. `Lie-Group` becomes `Lie Group`.
+    Spaces,
+    /// Turn all letters to lowercase, e.g. `Lie Group` becomes `lie group`.
+    Lowercase,
+    /// Prefix names with the creation date of the note, e.g. `Lie Group` becomes `2024-03-05 Lie Group`.
+    DatePrefix,
+    /// Remove a date at the start of names, e.g. `2024-03-05 Lie Group` becomes `Lie Group`.
+    NoDatePrefix,
+}
+
+impl NamingScheme {
+    /// Returns the name of a note with the given name and date in this scheme.
+    /// A date at the start of the name is kept as it is when replacing separators.
+    /// Names that already start with a date are not prefixed again, names without a date are not prefixed at all.
+    pub fn apply(self, name: &str, date: Option<chrono::NaiveDate>) -> String {
+        let regex = regex::Regex::new(DATE_PREFIX).expect("Pre-defined regex to be valid.");
+        let (prefix, rest) = match regex.find(name) {
+            Some(date) if date.end() < name.len() => name.split_at(date.end()),
+            _ => ("", name),
+        };
+
+        let replace = |from: &[char], to: &str| format!("{}{}", prefix, rest.replace(from, to));
+
+        match self {
+            NamingScheme::Dashes => replace(&[' ', '_'], "-"),
+            NamingScheme::Underscores => replace(&[' ', '-'], "_"),
+            NamingScheme::Spaces => replace(&['-', '_'], " "),
+            NamingScheme::Lowercase => name.to_lowercase(),
+            NamingScheme::DatePrefix => match date {
+                Some(date) if prefix.is_empty() => format!(
+                    "{}{}{}",
+                    date.format("%Y-%m-%d"),
+                    // keep names without spaces free of them
+                    if name.contains(' ') { ' ' } else { '-' },
+                    name
+                ),
+                _ => name.to_owned(),
+            },
+            NamingScheme::NoDatePrefix => rest.to_owned(),
+        }
+    }
+}
+
+/// A note file moved to a new name by a migration.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rename {
+    /// The current path of the note file.
+    pub from: path::PathBuf,
+    /// The path the note file is moved to.
+    pub to: path::PathBuf,
+}
+
+/// A note whose links change in a migration.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Edit {
+    /// The current path of the note file.
+    pub path: path::PathBuf,
+    /// The current content of the note.
+    pub old: String,
+    /// The content of the note with updated links.
+    pub new: String,
+}
+
+/// The changes moving all notes of a vault to other naming schemes: The renamed note files and the notes linking to them.
+#[derive(Debug, Clone, Default)]
+pub struct Migration {
+    /// The note files to rename, by path.
+    pub renames: Vec<Rename>,
+    /// The notes whose links change, by path.
+    pub edits: Vec<Edit>,
+}
+
+impl Migration {
+    /// Plans the migration of all notes of the given index to the given schemes, applied in order.
+    /// Fails if two notes would end up with the same id or a note would replace another file.
+    pub fn plan(index: &NoteIndex, schemes: &[NamingScheme]) -> error::Result<Self> {
+        // the new names of all renamed notes, by their current id
+        let mut names = HashMap::new();
+        let mut renames = Vec::new();
+        // which note takes which id after the migration
+        let mut taken: HashMap<String, &Note> = HashMap::new();
+
+        for note in index.notes().sorted_by(|a, b| a.path.cmp(&b.path)) {
+            let name = schemes.iter().fold(note.name.clone(), |name, scheme| {
+                scheme.apply(&name, note.date())
+            });
+
+            if let Some(other) = taken.insert(name_to_id(&name), note) {
+                return Err(error::RucolaError::Input(format!(
+                    "Both {} and {} would be named {}.",
+                    other.name, note.name, name
+                )));
+            }
+
+            if name == note.name {
+                continue;
+            }
+
+            let to = note.path.with_file_name(match note.path.extension() {
+                Some(extension) => format!("{}.{}", name, extension.to_string_lossy()),
+                None => name.clone(),
+            });
+            // the target may only exist if it is the note itself, e.g. on file systems ignoring case
+            if to.exists() && !is_same_file(&note.path, &to) {
+                return Err(error::RucolaError::Input(format!(
+                    "{} cannot be renamed to {}, the file already exists.",
+                    note.name, name
+                )));
+            }
+
+            names.insert(name_to_id(&note.name), name);
+            renames.push(Rename {
+                from: note.path.clone(),
+                to,
+            });
+        }
+
+        let mut edits = Vec::new();
+        for note in index.notes().sorted_by(|a, b| a.path.cmp(&b.path)) {
+            // only read notes the index knows to link to renamed ones
+            if !note.links.iter().any(|link| names.contains_key(link)) {
+                continue;
+            }
+            let old = fs::read_to_string(&note.path)?;
+            let new = rename_links(&old, &names).into_owned();
+            if new != old {
+                edits.push(Edit {
+                    path: note.path.clone(),
+                    old,
+                    new,
+                });
+            }
+        }
+
+        Ok(Self { renames, edits })
+    }
+
+    /// Wether the migration changes nothing.
+    pub fn is_empty(&self) -> bool {
+        self.renames.is_empty() && self.edits.is_empty()
+    }
+
+    /// Calculates how the migration changes the links between the notes of the given index, without applying it.
+    pub fn impact(&self, index: &NoteIndex) -> error::Result<super::GraphImpact> {
+        super::GraphImpact::new(index, &self.edits, &self.renames)
+    }
+
+    /// Describes all changes of the migration: First the renamed files, then the changed lines of every edited note.
+    /// Paths are shown relative to the vault of the given index.
+    pub fn diff(&self, index: &NoteIndex) -> String {
+        let mut diff = String::new();
+
+        for rename in &self.renames {
+            diff.push_str(&format!(
+                "rename {} -> {}\n",
+                index.relative_path(&rename.from).display(),
+                index.relative_path(&rename.to).display()
+            ));
+        }
+
+        for edit in &self.edits {
+            let path = index.relative_path(&edit.path).display();
+            diff.push_str(&format!("--- {}\n+++ {}\n", path, path));
+            // renaming links never adds or removes lines
+            for (number, (old, new)) in edit.old.lines().zip(edit.new.lines()).enumerate() {
+                if old != new {
+                    diff.push_str(&format!("@@ line {} @@\n-{}\n+{}\n", number + 1, old, new));
+                }
+            }
+        }
+
+        diff
+    }
+}
+
+/// Replaces the targets of all wiki and markdown links to renamed notes in the given content.
+/// The new names are given by the current ids of the notes. Headings, block references and titles of links are kept.
+pub fn rename_links<'a>(content: &'a str, names: &HashMap<String, String>) -> Cow<'a, str> {
+    let wiki =
+        regex::Regex::new(r"\[\[([^\]|#]+)([^\]]*)\]\]").expect("Pre-defined regex to be valid.");
+    // markdown links to notes use ids, without any dots or slashes
+    let markdown = regex::Regex::new(r"\]\(([^)\s/.#]+)(#[^)\s]*)?\)")
+        .expect("Pre-defined regex to be valid.");
+
+    let content = wiki.replace_all(content, |captures: &regex::Captures| {
+        match names.get(&name_to_id(&captures[1])) {
+            Some(name) => format!("[[{}{}]]", name, &captures[2]),
+            None => captures[0].to_owned(),
+        }
+    });
+
+    let replaced = match markdown.replace_all(&content, |captures: &regex::Captures| {
+        match names.get(&name_to_id(&captures[1])) {
+            Some(name) => format!(
+                "]({}{})",
+                name_to_id(name),
+                captures.get(2).map_or("", |anchor| anchor.as_str())
+            ),
+            None => captures[0].to_owned(),
+        }
+    }) {
+        Cow::Borrowed(_) => None,
+        Cow::Owned(replaced) => Some(replaced),
+    };
+    replaced.map_or(content, Cow::Owned)
+}
+
+/// Wether the given paths lead to the same file.
+fn is_same_file(a: &path::Path, b: &path::Path) -> bool {
+    match (a.canonicalize(), b.canonicalize()) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_naming_schemes() {
+        let date = chrono::NaiveDate::from_ymd_opt(2024, 3, 5);
+
+        assert_eq!(
+            NamingScheme::Dashes.apply("Lie Group_x", None),
+            "Lie-Group-x"
+        );
+        assert_eq!(
+            NamingScheme::Underscores.apply("Lie Group", None),
+            "Lie_Group"
+        );
+        assert_eq!(
+            NamingScheme::Lowercase.apply("Lie Group", None),
+            "lie group"
+        );
+        // dates at the start are kept when replacing separators
+        assert_eq!(
+            NamingScheme::Spaces.apply("2024-03-05-Lie-Group", None),
+            "2024-03-05-Lie Group"
+        );
+
+        assert_eq!(
+            NamingScheme::DatePrefix.apply("Lie Group", date),
+            "2024-03-05 Lie Group"
+        );
+        assert_eq!(
+            NamingScheme::DatePrefix.apply("lie-group", date),
+            "2024-03-05-lie-group"
+        );
+        assert_eq!(
+            NamingScheme::DatePrefix.apply("20240101 Lie Group", date),
+            "20240101 Lie Group"
+        );
+        assert_eq!(
+            NamingScheme::DatePrefix.apply("Lie Group", None),
+            "Lie Group"
+        );
+
+        assert_eq!(
+            NamingScheme::NoDatePrefix.apply("2024-03-05 Lie Group", None),
+            "Lie Group"
+        );
+        assert_eq!(
+            NamingScheme::NoDatePrefix.apply("2024-03-05", None),
+            "2024-03-05"
+        );
+    }
+
+    #[test]
+    fn test_rename_links() {
+        let names = HashMap::from([(String::from("lie-group"), String::from("lie_group"))]);
+
+        assert_eq!(
+            rename_links(
+                "See [[Lie Group]], [[lie-group#Definition|groups]], [[Lie Group#^def]] and [groups](lie-group#examples).",
+                &names
+            ),
+            "See [[lie_group]], [[lie_group#Definition|groups]], [[lie_group#^def]] and [groups](lie_group#examples)."
+        );
+
+        // other links stay untouched
+        assert_eq!(
+            rename_links("[[Atlas]] and [a file](lie-group.pdf)", &names),
+            "[[Atlas]] and [a file](lie-group.pdf)"
+        );
+    }
+
+    #[test]
+    fn test_migration() {
+        let vault = testdir::testdir!();
+        std::fs::write(vault.join("Lie Group.md"), "# Lie Group\n").unwrap();
+        std::fs::write(
+            vault.join("Atlas.md"),
+            "# Atlas\nCharts\nof a [[Lie Group|group]].\n",
+        )
+        .unwrap();
+        std::fs::write(vault.join("lie-algebra.md"), "# Lie Algebra\n").unwrap();
+
+        let config = crate::Config::default();
+        let tracker = crate::io::FileTracker::new(&config, vault.clone()).unwrap();
+        let builder = crate::io::HtmlBuilder::new(&config, vault.clone());
+        let index = NoteIndex::new(tracker, builder, Default::default()).0;
+
+        let migration = Migration::plan(&index, &[NamingScheme::Dashes]).unwrap();
+        assert_eq!(migration.renames.len(), 1);
+        assert_eq!(migration.edits.len(), 1);
+        assert_eq!(
+            migration.diff(&index),
+            "rename Lie Group.md -> Lie-Group.md\n--- Atlas.md\n+++ Atlas.md\n@@ line 3 @@\n-of a [[Lie Group|group]].\n+of a [[Lie-Group|group]].\n"
+        );
+
+        let manager = crate::io::FileManager::new(&config, vault.clone());
+        manager.apply_migration(&migration).unwrap();
+        assert!(vault.join("Lie-Group.md").exists());
+        assert!(!vault.join("Lie Group.md").exists());
+        assert!(std::fs::read_to_string(vault.join("Atlas.md"))
+            .unwrap()
+            .contains("[[Lie-Group|group]]"));
+
+        // migrating again changes nothing
+        let index = || {
+            let tracker = crate::io::FileTracker::new(&config, vault.clone()).unwrap();
+            let builder = crate::io::HtmlBuilder::new(&config, vault.clone());
+            NoteIndex::new(tracker, builder, Default::default()).0
+        };
+        assert!(Migration::plan(&index(), &[NamingScheme::Dashes])
+            .unwrap()
+            .is_empty());
+
+        // notes may not end up with the same id
+        std::fs::write(vault.join("Lie_Group.md"), "# Lie Group\n").unwrap();
+        assert!(Migration::plan(&index(), &[NamingScheme::Dashes]).is_err());
+    }
+}
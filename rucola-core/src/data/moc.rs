@@ -0,0 +1,394 @@
+use std::{fs, path};
+
+use itertools::Itertools;
+
+use crate::error;
+
+use super::{migration::Edit, name_to_id, Filter, NoteIndex};
+
+/// Starts the marker comments delimiting the generated lists in maps of content.
+const MARKER: &str = "<!-- rucola:moc";
+/// Closes the marker comments.
+const MARKER_END: &str = "-->";
+/// Marks the end of a generated list.
+const END_MARKER: &str = "<!-- rucola:moc end -->";
+
+/// What a map of content lists the notes of.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MocTarget {
+    /// All notes with the given tag or one of its subtags, hash included.
+    Tag(String),
+    /// All notes within the given folder, relative to the vault.
+    Folder(path::PathBuf),
+}
+
+impl std::str::FromStr for MocTarget {
+    type Err = String;
+
+    /// Parses a tag, starting with a `#`, or a folder relative to the vault.
+    fn from_str(target: &str) -> Result<Self, Self::Err> {
+        let target = target.trim();
+        if target.starts_with('#') {
+            if target.len() > 1 {
+                Ok(Self::Tag(target.to_owned()))
+            } else {
+                Err(String::from("Tags need a name after the #."))
+            }
+        } else {
+            let folder = target.trim_end_matches(['/', '\\']);
+            if folder.is_empty() {
+                Err(String::from("Enter a tag starting with # or a folder."))
+            } else {
+                Ok(Self::Folder(path::PathBuf::from(folder)))
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for MocTarget {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MocTarget::Tag(tag) => write!(f, "{}", tag),
+            MocTarget::Folder(folder) => write!(f, "{}/", folder.display()),
+        }
+    }
+}
+
+impl MocTarget {
+    /// Returns the filter matching the notes listed in the map of content.
+    fn filter(&self) -> Filter {
+        match self {
+            MocTarget::Tag(tag) => Filter::new(tag, false),
+            MocTarget::Folder(folder) => Filter::folder(folder.clone()),
+        }
+    }
+
+    /// Returns the path of a new map of content, relative to the vault and without extension:
+    /// Named after the tag at the top of the vault, or named after the folder within it.
+    pub fn note_path(&self) -> path::PathBuf {
+        match self {
+            MocTarget::Tag(tag) => {
+                path::PathBuf::from(tag.trim_start_matches('#').replace('/', "-"))
+            }
+            MocTarget::Folder(folder) => folder.join(folder.file_name().unwrap_or_default()),
+        }
+    }
+
+    /// Returns the title of a new map of content.
+    fn title(&self) -> String {
+        match self {
+            MocTarget::Tag(tag) => tag.trim_start_matches('#').to_owned(),
+            MocTarget::Folder(folder) => folder
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_default(),
+        }
+    }
+
+    /// Returns the marker comment starting the generated list of this target.
+    fn marker(&self) -> String {
+        format!("{} {} {}", MARKER, self, MARKER_END)
+    }
+
+    /// Returns the generated list: Links to all notes of the target except the map of content itself, by title.
+    fn list(&self, index: &NoteIndex, moc_id: &str) -> Vec<String> {
+        let filter = self.filter();
+        index
+            .notes()
+            .filter(|note| name_to_id(&note.name) != moc_id)
+            .filter(|note| filter.apply(note, index).is_some())
+            .sorted_by(|a, b| {
+                a.display_name
+                    .to_lowercase()
+                    .cmp(&b.display_name.to_lowercase())
+                    .then_with(|| a.name.cmp(&b.name))
+            })
+            .map(|note| format!("- {}", super::query::link(note)))
+            .collect()
+    }
+}
+
+/// Returns the targets of all generated lists in the given content, in order.
+fn moc_targets(content: &str) -> Vec<MocTarget> {
+    content
+        .lines()
+        .filter(|line| line.trim() != END_MARKER)
+        .filter_map(|line| {
+            line.trim()
+                .strip_prefix(MARKER)?
+                .strip_suffix(MARKER_END)?
+                .parse()
+                .ok()
+        })
+        .collect()
+}
+
+/// Replaces the generated list of the given target in the given content by the given lines, keeping everything around it.
+/// Content without a list of the target gets one at its end.
+/// Fails if the list is not closed by an end marker.
+fn refresh_list(content: &str, target: &MocTarget, list: &[String]) -> error::Result<String> {
+    let lines = content.lines().collect_vec();
+    let marker = target.marker();
+
+    let Some(start) = lines.iter().position(|line| line.trim() == marker) else {
+        let mut content = content.to_owned();
+        if !content.is_empty() && !content.ends_with('\n') {
+            content.push('\n');
+        }
+        if !content.is_empty() {
+            content.push('\n');
+        }
+        return Ok(format!(
+            "{}{}\n{}\n",
+            content,
+            std::iter::once(marker.as_str())
+                .chain(list.iter().map(String::as_str))
+                .join("\n"),
+            END_MARKER
+        ));
+    };
+
+    let end = lines[start + 1..]
+        .iter()
+        .position(|line| line.trim() == END_MARKER)
+        .map(|end| start + 1 + end)
+        .ok_or_else(|| {
+            error::RucolaError::Input(format!(
+                "The list of {} has no end marker {}.",
+                target, END_MARKER
+            ))
+        })?;
+
+    let mut refreshed = lines[..=start]
+        .iter()
+        .copied()
+        .chain(list.iter().map(String::as_str))
+        .chain(lines[end..].iter().copied())
+        .join("\n");
+    if content.ends_with('\n') {
+        refreshed.push('\n');
+    }
+    Ok(refreshed)
+}
+
+/// The changes creating or refreshing maps of content: Notes listing links to all notes with a tag or within a folder.
+/// The lists are written between marker comments, so the rest of the notes is kept.
+#[derive(Debug, Clone, Default)]
+pub struct MapsOfContent {
+    /// The notes whose lists change and the new notes, by path. New notes have no old content.
+    pub edits: Vec<Edit>,
+}
+
+impl MapsOfContent {
+    /// Plans refreshing the lists of the given targets in all notes of the given index, or of all targets if none are given.
+    /// Targets without a list anywhere get a new note, or are added to the end of an existing note at its path.
+    /// The full path of a new note is determined from its path relative to the vault by the given function.
+    pub fn plan(
+        index: &NoteIndex,
+        targets: &[MocTarget],
+        full_path: impl Fn(path::PathBuf) -> path::PathBuf,
+    ) -> error::Result<Self> {
+        let mut edits = Vec::new();
+        let mut missing = targets.to_vec();
+
+        for note in index.notes().sorted_by(|a, b| a.path.cmp(&b.path)) {
+            let old = fs::read_to_string(&note.path)?;
+            let id = name_to_id(&note.name);
+
+            let mut new = old.clone();
+            for target in moc_targets(&old)
+                .into_iter()
+                .filter(|target| targets.is_empty() || targets.contains(target))
+            {
+                missing.retain(|missing| *missing != target);
+                new = refresh_list(&new, &target, &target.list(index, &id))?;
+            }
+
+            if new != old {
+                edits.push(Edit {
+                    path: note.path.clone(),
+                    old,
+                    new,
+                });
+            }
+        }
+
+        for target in missing {
+            // the paths of notes are canonical
+            let path = full_path(target.note_path());
+            let path = path.canonicalize().unwrap_or(path);
+            let id = name_to_id(
+                &path
+                    .file_stem()
+                    .map(|stem| stem.to_string_lossy())
+                    .unwrap_or_default(),
+            );
+            let list = target.list(index, &id);
+
+            // several targets may end up in the same note
+            if let Some(edit) = edits.iter_mut().find(|edit| edit.path == path) {
+                edit.new = refresh_list(&edit.new, &target, &list)?;
+                continue;
+            }
+
+            let old = if path.exists() {
+                fs::read_to_string(&path)?
+            } else {
+                String::new()
+            };
+            let start = if old.is_empty() {
+                format!("# {}\n", target.title())
+            } else {
+                old.clone()
+            };
+            edits.push(Edit {
+                new: refresh_list(&start, &target, &list)?,
+                path,
+                old,
+            });
+        }
+
+        Ok(Self { edits })
+    }
+
+    /// Wether no list changes.
+    pub fn is_empty(&self) -> bool {
+        self.edits.is_empty()
+    }
+
+    /// Calculates how the maps of content change the links between the notes of the given index, without writing them.
+    pub fn impact(&self, index: &NoteIndex) -> error::Result<super::GraphImpact> {
+        super::GraphImpact::new(index, &self.edits, &[])
+    }
+
+    /// Describes all changes: The created notes, and the removed and added lines of every changed note.
+    /// Paths are shown relative to the vault of the given index.
+    pub fn diff(&self, index: &NoteIndex) -> String {
+        let mut diff = String::new();
+
+        for edit in &self.edits {
+            let path = index.relative_path(&edit.path).display();
+            if !edit.path.exists() {
+                diff.push_str(&format!("create {}\n", path));
+            } else {
+                diff.push_str(&format!("--- {}\n+++ {}\n", path, path));
+            }
+
+            let old = edit.old.lines().collect_vec();
+            let new = edit.new.lines().collect_vec();
+            for line in old.iter().filter(|line| !new.contains(line)) {
+                diff.push_str(&format!("-{}\n", line));
+            }
+            for line in new.iter().filter(|line| !old.contains(line)) {
+                diff.push_str(&format!("+{}\n", line));
+            }
+        }
+
+        diff
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io;
+
+    #[test]
+    fn test_moc_targets() {
+        assert_eq!(
+            "#book".parse::<MocTarget>(),
+            Ok(MocTarget::Tag(String::from("#book")))
+        );
+        assert_eq!(
+            "math/algebra/".parse::<MocTarget>(),
+            Ok(MocTarget::Folder(path::PathBuf::from("math/algebra")))
+        );
+        assert!("#".parse::<MocTarget>().is_err());
+        assert!("/".parse::<MocTarget>().is_err());
+
+        let content = "# Reading\n<!-- rucola:moc #book -->\n- [[Dune]]\n<!-- rucola:moc end -->\n<!-- rucola:moc math/ -->\n<!-- rucola:moc end -->\n";
+        assert_eq!(
+            moc_targets(content),
+            vec![
+                MocTarget::Tag(String::from("#book")),
+                MocTarget::Folder(path::PathBuf::from("math")),
+            ]
+        );
+        assert_eq!(
+            MocTarget::Tag(String::from("#book/fantasy")).note_path(),
+            path::PathBuf::from("book-fantasy")
+        );
+        assert_eq!(
+            MocTarget::Folder(path::PathBuf::from("math/algebra")).note_path(),
+            path::PathBuf::from("math/algebra/algebra")
+        );
+    }
+
+    #[test]
+    fn test_refresh_list() {
+        let target = MocTarget::Tag(String::from("#book"));
+        let list = [String::from("- [[Dune]]"), String::from("- [[Emma]]")];
+
+        // content around the list is kept
+        let content = "# Reading\nIntro\n<!-- rucola:moc #book -->\n- [[Old]]\n<!-- rucola:moc end -->\nOutro\n";
+        assert_eq!(
+            refresh_list(content, &target, &list).unwrap(),
+            "# Reading\nIntro\n<!-- rucola:moc #book -->\n- [[Dune]]\n- [[Emma]]\n<!-- rucola:moc end -->\nOutro\n"
+        );
+
+        // a missing list is added at the end
+        assert_eq!(
+            refresh_list("# Reading", &target, &list).unwrap(),
+            "# Reading\n\n<!-- rucola:moc #book -->\n- [[Dune]]\n- [[Emma]]\n<!-- rucola:moc end -->\n"
+        );
+
+        // a list without end is not replaced
+        assert!(refresh_list("<!-- rucola:moc #book -->\n- [[Old]]\n", &target, &list).is_err());
+    }
+
+    #[test]
+    fn test_maps_of_content() {
+        let config = crate::Config::default();
+        let vault = testdir::testdir!();
+        std::fs::create_dir_all(vault.join("math")).unwrap();
+        std::fs::write(vault.join("math/Chart.md"), "# Chart\n#maps").unwrap();
+        std::fs::write(vault.join("math/Atlas.md"), "# Atlas\n#maps").unwrap();
+        std::fs::write(
+            vault.join("Reading.md"),
+            "# Reading\nMy maps:\n<!-- rucola:moc #maps -->\n- [[Old]]\n<!-- rucola:moc end -->\nMore to come.\n",
+        )
+        .unwrap();
+
+        let tracker = io::FileTracker::new(&config, vault.clone()).unwrap();
+        let builder = io::HtmlBuilder::new(&config, vault.clone());
+        let index = NoteIndex::new(tracker, builder, Default::default()).0;
+        let full_path = |relative: path::PathBuf| vault.join(relative).with_extension("md");
+
+        // without targets, existing lists are refreshed
+        let mocs = MapsOfContent::plan(&index, &[], full_path).unwrap();
+        assert_eq!(mocs.edits.len(), 1);
+        assert_eq!(
+            mocs.edits[0].new,
+            "# Reading\nMy maps:\n<!-- rucola:moc #maps -->\n- [[Atlas]]\n- [[Chart]]\n<!-- rucola:moc end -->\nMore to come.\n"
+        );
+        assert_eq!(
+            mocs.diff(&index),
+            "--- Reading.md\n+++ Reading.md\n-- [[Old]]\n+- [[Atlas]]\n+- [[Chart]]\n"
+        );
+
+        // new targets get new notes, listing everything but the note itself
+        let mocs = MapsOfContent::plan(
+            &index,
+            &[MocTarget::Folder(path::PathBuf::from("math"))],
+            full_path,
+        )
+        .unwrap();
+        assert_eq!(mocs.edits.len(), 1);
+        assert_eq!(mocs.edits[0].path, vault.join("math/math.md"));
+        assert_eq!(mocs.edits[0].old, "");
+        assert_eq!(
+            mocs.edits[0].new,
+            "# math\n\n<!-- rucola:moc math/ -->\n- [[Atlas]]\n- [[Chart]]\n<!-- rucola:moc end -->\n"
+        );
+    }
+}
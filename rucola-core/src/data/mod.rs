@@ -1,17 +1,118 @@
 mod note;
 pub use note::Note;
+pub use note::ParseOptions;
 
 mod note_statistics;
 pub use note_statistics::EnvironmentStats;
 pub use note_statistics::SortingMode;
 
+mod blocks;
+pub use blocks::block_ids;
+pub use blocks::block_references;
+pub use blocks::find_block;
+
+mod collation;
+pub use collation::Collation;
+
 mod filter;
+pub use filter::CountCondition;
+pub use filter::DateCondition;
 pub use filter::Filter;
+pub use filter::NoteCount;
+pub use filter::NoteDate;
+
+mod flashcards;
+pub use flashcards::find_flashcards;
+pub use flashcards::write_anki;
+pub use flashcards::Flashcard;
+pub use flashcards::ANKI_HEADER;
+
+mod footnotes;
+pub use footnotes::footnote_links;
+
+mod graph_impact;
+pub use graph_impact::GraphImpact;
+
+mod imports;
+pub use imports::local_link_targets;
+pub use imports::rewrite_link_targets;
 
 mod index;
 pub use index::NoteIndex;
 pub use index::NoteIndexContainer;
 
+mod keywords;
+pub use keywords::Keyword;
+
+mod link_report;
+pub use link_report::broken_links;
+pub use link_report::BrokenLink;
+
+mod lint;
+pub use lint::Diagnostic;
+pub use lint::LintConfig;
+pub use lint::Linter;
+pub use lint::Severity;
+
+mod migration;
+pub use migration::rename_links;
+pub use migration::Migration;
+pub use migration::NamingScheme;
+
+mod moc;
+pub use moc::MapsOfContent;
+pub use moc::MocTarget;
+
+mod people;
+pub use people::find_mentions;
+pub use people::mention_occurrences;
+pub use people::people;
+pub use people::MentionOccurrence;
+pub use people::Person;
+
+mod query;
+pub use query::expand_queries;
+
+mod reading_order;
+pub use reading_order::ReadingOrder;
+
+mod slides;
+pub use slides::split_slides;
+
+mod staleness;
+pub use staleness::StaleNote;
+pub use staleness::StaleRule;
+pub use staleness::Staleness;
+
+mod snippets;
+pub use snippets::headings;
+pub use snippets::insert_snippet;
+pub use snippets::Heading;
+pub use snippets::Snippet;
+
+mod table;
+pub use table::table_cells;
+pub use table::ColumnCondition;
+pub use table::Comparison;
+pub use table::DataTable;
+
+mod tag_usage;
+pub use tag_usage::find_occurrences;
+pub use tag_usage::tag_counts;
+pub use tag_usage::tag_occurrences;
+pub use tag_usage::TagOccurrence;
+pub use tag_usage::TagSource;
+
+mod spelling;
+pub use spelling::distinct_words;
+pub use spelling::Misspelling;
+
+mod suggestions;
+pub use suggestions::find_first_mentions;
+pub use suggestions::find_mention;
+pub use suggestions::LinkSuggestion;
+pub use suggestions::TagSuggestion;
+
 use unicode_normalization::UnicodeNormalization;
 
 /// Turns a file name or link into its id in the following steps:
@@ -21,6 +122,7 @@ use unicode_normalization::UnicodeNormalization;
 ///  - Spaces ` ` are replaced by dashes `-`.
 ///  - A possible file extension is removed.
 /// ```
+/// # use rucola_core::data::name_to_id;
 ///  assert_eq!(name_to_id("Lie Theory#Definition"), "lie-theory");
 ///  assert_eq!(name_to_id("Lie Theory.md"), "lie-theory");
 ///  assert_eq!(name_to_id("Lie Theory"), "lie-theory");
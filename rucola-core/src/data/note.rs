@@ -0,0 +1,1266 @@
+use ratatui::{prelude::*, widgets::*};
+use std::{collections::HashMap, fmt::Debug, fs, path};
+
+use itertools::Itertools;
+
+use crate::{error, error::ErrorContext, i18n, styles};
+
+mod asciidoc;
+mod org;
+
+/// Configures how notes are parsed, in particular which inline tags are recognized.
+#[derive(Debug, Clone)]
+pub struct ParseOptions {
+    /// Minimum length of inline tags, excluding the leading `#`.
+    tag_min_length: usize,
+    /// If set, inline tags (including the leading `#`) have to fully match this regex to be recognized.
+    tag_regex: Option<regex::Regex>,
+    /// Wether to ignore tags within code blocks and inline code.
+    tags_ignore_code: bool,
+    /// Inline tags that are never recognized, including the leading `#`.
+    tag_blacklist: Vec<String>,
+    /// Wether notes are tagged with the path of the folder they are in.
+    folder_tags: bool,
+    /// The vault the paths of the folders start at, see [`ParseOptions::with_vault_path`].
+    vault_path: Option<path::PathBuf>,
+    /// Formats (in chrono syntax) to try when parsing dates from the frontmatter.
+    date_formats: Vec<String>,
+    /// Wether to recognize creation dates at the start of file names.
+    dates_from_filenames: bool,
+    /// The frontmatter field listing the notes a note depends on.
+    dependency_key: String,
+    /// Icons of notes with the given tags (including the leading `#`) that specify none themselves.
+    tag_icons: HashMap<String, String>,
+    /// Colors of notes with the given tags (including the leading `#`) that specify none themselves.
+    tag_colors: HashMap<String, String>,
+}
+
+impl Default for ParseOptions {
+    fn default() -> Self {
+        Self::new(&crate::Config::default()).expect("Default config to contain no tag regex.")
+    }
+}
+
+impl ParseOptions {
+    /// Creates parse options from the given config, failing if the tag regex is invalid.
+    pub fn new(config: &crate::Config) -> error::Result<Self> {
+        Ok(Self {
+            tag_min_length: config.tag_min_length,
+            tag_regex: config
+                .tag_regex
+                .as_ref()
+                // anchor the regex so it has to match the entire tag
+                .map(|tag_regex| regex::Regex::new(&format!("^(?:{})$", tag_regex)))
+                .transpose()?,
+            tags_ignore_code: config.tags_ignore_code,
+            tag_blacklist: config
+                .tag_blacklist
+                .iter()
+                // allow the user to specify blacklisted tags with or without the leading `#`
+                .map(|tag| format!("#{}", tag.trim_start_matches('#')))
+                .collect(),
+            folder_tags: config.folder_tags,
+            vault_path: None,
+            date_formats: config.date_formats.clone(),
+            dates_from_filenames: config.dates_from_filenames,
+            dependency_key: config.dependency_key.clone(),
+            tag_icons: with_hashes(&config.tag_icons),
+            tag_colors: with_hashes(&config.tag_colors),
+        })
+    }
+
+    /// Sets the vault the parsed notes are stored in, which folder tags are relative to.
+    /// Without it, notes are not tagged with their folders.
+    pub fn with_vault_path(mut self, vault_path: &path::Path) -> Self {
+        self.vault_path = Some(vault_path.to_path_buf());
+        self
+    }
+
+    /// Returns the tag of the folder the note at the given path is in, relative to the vault, if folder tags are enabled.
+    /// Notes directly within the vault have no folder tag.
+    fn folder_tag(&self, path: &path::Path) -> Option<String> {
+        let vault_path = self.vault_path.as_ref().filter(|_| self.folder_tags)?;
+        let folder = path.parent()?;
+        let relative = folder
+            .strip_prefix(vault_path)
+            .ok()
+            .map(path::Path::to_path_buf)
+            .or_else(|| {
+                // the vault may have been given as a relative path or through a symlink
+                let canonical = vault_path.canonicalize().ok()?;
+                folder
+                    .strip_prefix(canonical)
+                    .ok()
+                    .map(path::Path::to_path_buf)
+            })?;
+
+        let parts = relative
+            .components()
+            .filter_map(|component| match component {
+                path::Component::Normal(part) => Some(
+                    part.to_string_lossy()
+                        .split_whitespace()
+                        .collect::<Vec<_>>()
+                        .join("-"),
+                ),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+
+        (!parts.is_empty()).then(|| format!("#{}", parts.join("/")))
+    }
+
+    /// Returns the value the given mapping assigns to the first of the given tags that has one.
+    /// Subtags fall back to the value of their supertags.
+    fn tag_fallback(mapping: &HashMap<String, String>, tags: &[String]) -> Option<String> {
+        tags.iter().find_map(|tag| {
+            let mut tag = tag.as_str();
+            loop {
+                if let Some(value) = mapping.get(tag) {
+                    return Some(value.to_owned());
+                }
+                tag = &tag[..tag.rfind('/')?];
+            }
+        })
+    }
+
+    /// Parses a date from a frontmatter entry, trying RFC 3339 first and then all configured formats.
+    fn parse_date(&self, date: &str) -> Option<chrono::NaiveDate> {
+        let date = date.trim();
+        chrono::DateTime::parse_from_rfc3339(date)
+            .map(|datetime| datetime.date_naive())
+            .ok()
+            .or_else(|| {
+                self.date_formats
+                    .iter()
+                    .find_map(|format| chrono::NaiveDate::parse_from_str(date, format).ok())
+            })
+    }
+
+    /// Recognizes dates of the forms `YYYYMMDD` or `YYYY-MM-DD` at the start of a file name.
+    fn filename_date(&self, name: &str) -> Option<chrono::NaiveDate> {
+        if !self.dates_from_filenames {
+            return None;
+        }
+
+        let regex = regex::Regex::new(r"^(\d{4})-?(\d{2})-?(\d{2})(?:\D|$)").ok()?;
+        let captures = regex.captures(name)?;
+
+        chrono::NaiveDate::from_ymd_opt(
+            captures[1].parse().ok()?,
+            captures[2].parse().ok()?,
+            captures[3].parse().ok()?,
+        )
+    }
+
+    /// Splits the given text at whitespace and returns all words that are recognized as tags.
+    fn inline_tags(&self, text: &str) -> Vec<String> {
+        text.split_whitespace()
+            .filter(|s| s.starts_with('#'))
+            .filter(|s| s.chars().count() > self.tag_min_length)
+            .filter(|s| {
+                self.tag_regex
+                    .as_ref()
+                    .map(|tag_regex| tag_regex.is_match(s))
+                    .unwrap_or(true)
+            })
+            .filter(|s| !self.tag_blacklist.iter().any(|tag| tag == s))
+            .map(|s| s.to_owned())
+            .collect()
+    }
+}
+
+/// Prepends a `#` to all keys of the given mapping of tags that do not start with one yet.
+fn with_hashes(mapping: &HashMap<String, String>) -> HashMap<String, String> {
+    mapping
+        .iter()
+        .map(|(tag, value)| {
+            (
+                format!("#{}", tag.trim_start_matches('#')),
+                value.to_owned(),
+            )
+        })
+        .collect()
+}
+
+/// Per-note overrides of the global export settings, as specified in the YAML frontmatter.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ExportOptions {
+    /// Wether this note should be exported at all (`export`).
+    pub export: bool,
+    /// Name of a css file in the config folder to use instead of the global one (`css`).
+    pub css: Option<String>,
+    /// Name of the template to use when exporting this note as a PDF (`pdf-template`).
+    pub pdf_template: Option<String>,
+}
+
+impl Default for ExportOptions {
+    fn default() -> Self {
+        Self {
+            export: true,
+            css: None,
+            pdf_template: None,
+        }
+    }
+}
+
+/// An abstract representation of a note that contains statistics about it but _not_ the full text.
+#[derive(Clone, Debug, Default)]
+pub struct Note {
+    /// The title of the note.
+    pub display_name: String,
+    /// The name of the file the note is saved in.
+    pub name: String,
+    /// All tags contained at any part of the note.
+    pub tags: Vec<String>,
+    /// All links contained within the note - no external (e.g. web) links.
+    pub links: Vec<String>,
+    /// The number of words of prose, leaving out markup, code, math, HTML and URLs.
+    pub words: usize,
+    /// The number of characters of prose, with a single space between words.
+    pub characters: usize,
+    /// The number of words of the content after the frontmatter as it is, including markup.
+    pub raw_words: usize,
+    /// The number of bytes of the content after the frontmatter as it is, including markup.
+    pub raw_characters: usize,
+    /// How often each term occurs in the text, used to find keywords.
+    pub terms: HashMap<String, usize>,
+    /// A copy of the path leading to this note.
+    pub path: path::PathBuf,
+    /// Export settings specific to this note.
+    pub export: ExportOptions,
+    /// Other names of the note, as specified in the frontmatter, e.g. names it had before being renamed.
+    pub aliases: Vec<String>,
+    /// The ids of all blocks marked in the note (`^id`), which links can refer to.
+    pub blocks: Vec<String>,
+    /// The creation date of the note, as specified in the frontmatter or file name.
+    pub created: Option<chrono::NaiveDate>,
+    /// The time of the last modification of the note file, according to the file system.
+    pub modified: Option<chrono::DateTime<chrono::Local>>,
+    /// The structured data of the note: Its table if it contains nothing else, or a CSV file with the same name next to it.
+    pub data: Option<super::DataTable>,
+    /// The plain text of the first paragraph of the note, shown when previewing links to it.
+    pub snippet: String,
+    /// An icon or emoji shown next to the title, as specified in the frontmatter or for one of the tags in the config.
+    pub icon: Option<String>,
+    /// The color of the title, as specified in the frontmatter or for one of the tags in the config.
+    pub color: Option<String>,
+    /// Wether the note describes a person (`type: person` in the frontmatter), whose mentions count as links to it.
+    pub person: bool,
+    /// The ids of the names mentioned in the text as `@name`.
+    pub mentions: Vec<String>,
+    /// The ids of the person notes linked to by mentioning them, which the index adds to the links.
+    pub mention_links: Vec<String>,
+    /// The ids of the notes to read before this one, as listed in the frontmatter field configured as `dependency_key`.
+    pub dependencies: Vec<String>,
+}
+
+impl Note {
+    /// Opens the file from the given path (if possible) and extracts metadata.
+    pub fn from_path(path: &path::Path, options: &ParseOptions) -> error::Result<Self> {
+        // Open the file.
+        let content = fs::read_to_string(path).in_file(path, None)?;
+
+        let mut note = Self::from_content(path, &content, options).in_file(path, None)?;
+
+        // Modified: Ask the file system.
+        note.modified = fs::metadata(path)
+            .and_then(|metadata| metadata.modified())
+            .ok()
+            .map(chrono::DateTime::from);
+
+        // Data: Prefer a CSV file with the same name over a table within the note.
+        let csv = path.with_extension("csv");
+        if csv.is_file() {
+            let content = fs::read_to_string(&csv).in_file(&csv, None)?;
+            note.data = Some(super::DataTable::from_csv(&content, csv));
+        }
+
+        Ok(note)
+    }
+
+    /// Extracts metadata from the given content of a note stored at the given path, without accessing the file system.
+    /// The time of the last modification is thus left empty.
+    pub fn from_content(
+        path: &path::Path,
+        content: &str,
+        options: &ParseOptions,
+    ) -> error::Result<Self> {
+        // Org-mode and AsciiDoc files have a syntax of their own.
+        if org::is_org(path) {
+            return org::from_org(path, content, options);
+        }
+        if asciidoc::is_asciidoc(path) {
+            return asciidoc::from_asciidoc(path, content, options);
+        }
+
+        // Extract both the YAML front matter, if present, and the main content.
+        let (yaml, content) = extract_yaml(content);
+
+        // Parse markdown into AST.
+        // Math is parsed into separate nodes so its contents are never mistaken for tags or links.
+        let arena = comrak::Arena::new();
+        let root = comrak::parse_document(
+            &arena,
+            content,
+            &comrak::Options {
+                extension: comrak::ExtensionOptions::builder()
+                    .wikilinks_title_after_pipe(true)
+                    .math_dollars(true)
+                    .table(true)
+                    .build(),
+                ..Default::default()
+            },
+        );
+
+        // Parse YAML.
+        let (title, tags, export, date, aliases, icon, color, person, dependencies) =
+            if let Some(yaml) = yaml {
+                let doc = &parse_yaml(yaml, path)?;
+
+                // Check if there was a title specified.
+                let title = doc["title"].as_str().map(|s| s.to_owned());
+
+                // Check if tags were specified.
+                let tags = doc["tags"]
+                    // Convert the entry into a vec - if the entry isn't there, use an empty vec.
+                    .as_vec()
+                    .unwrap_or(&Vec::new())
+                    .iter()
+                    // Convert the individual entries into strs, as rust-yaml doesn't do nested lists.
+                    .flat_map(|v| v.as_str())
+                    // Convert those into Strings and prepend the #.
+                    .flat_map(|s| {
+                        // Entries of sublists will appear as separated by ` - `, so split by that.
+                        let parts = s.split(" - ").collect_vec();
+
+                        if parts.is_empty() {
+                            // This should not happen.
+                            Vec::new()
+                        } else if parts.len() == 1 {
+                            // Only one parts => There were not subtags. Simply prepend a `#`.
+                            vec![format!("#{}", s)]
+                        } else {
+                            // More than 1 part => There were subtags.
+                            let mut res = Vec::new();
+
+                            // Iterate through all of the substrings except for the first, which is the supertag.
+                            for subtag in parts.iter().skip(1) {
+                                res.push(format!("#{}/{}", parts[0], subtag));
+                            }
+
+                            res
+                        }
+                    })
+                    // Collect all tags in a vec.
+                    .collect_vec();
+
+                // Check for overrides of the export settings.
+                let export = ExportOptions {
+                    export: doc["export"].as_bool().unwrap_or(true),
+                    css: doc["css"].as_str().map(|s| s.to_owned()),
+                    pdf_template: doc["pdf-template"].as_str().map(|s| s.to_owned()),
+                };
+
+                // Check for a creation date, preferring `created` over `date`.
+                let date = ["created", "date"]
+                    .iter()
+                    .flat_map(|key| doc[*key].as_str())
+                    .find_map(|date| options.parse_date(date));
+
+                // Check for aliases, given either as a list or as a single name.
+                let aliases = ["aliases", "alias"]
+                    .iter()
+                    .flat_map(|key| match &doc[*key] {
+                        yaml_rust::Yaml::Array(aliases) => aliases
+                            .iter()
+                            .flat_map(|alias| alias.as_str())
+                            .map(|alias| alias.to_owned())
+                            .collect_vec(),
+                        alias => alias
+                            .as_str()
+                            .map(|alias| alias.to_owned())
+                            .into_iter()
+                            .collect(),
+                    })
+                    .collect_vec();
+
+                // Check for an icon and color of the title.
+                let icon = doc["icon"].as_str().map(|s| s.to_owned());
+                let color = doc["color"].as_str().map(|s| s.to_owned());
+
+                // Check if the note describes a person.
+                let person = doc["type"]
+                    .as_str()
+                    .is_some_and(|kind| kind.trim().eq_ignore_ascii_case("person"));
+
+                // Check for the notes this one depends on, given as names or links.
+                let dependencies = dependency_names(&doc[options.dependency_key.as_str()])
+                    .iter()
+                    .map(|name| super::name_to_id(name))
+                    .unique()
+                    .collect_vec();
+
+                (
+                    title,
+                    tags,
+                    export,
+                    date,
+                    aliases,
+                    icon,
+                    color,
+                    person,
+                    dependencies,
+                )
+            } else {
+                (
+                    None,
+                    Vec::new(),
+                    ExportOptions::default(),
+                    None,
+                    Vec::new(),
+                    None,
+                    None,
+                    false,
+                    Vec::new(),
+                )
+            };
+
+        // Get the name of the file, without extension.
+        let name = path
+            .file_stem()
+            .map(|os| os.to_string_lossy().to_string())
+            .ok_or_else(|| error::RucolaError::NoteNameCannotBeRead(path.to_path_buf()))?;
+
+        // Prose: Join all text nodes, so markup, code, math and HTML are skipped.
+        let prose = prose(root);
+
+        // Terms: Count the words of all text nodes, so code and math are skipped.
+        let mut terms = HashMap::new();
+        for node in root.descendants() {
+            if let comrak::nodes::NodeValue::Text(content) = &node.data.borrow().value {
+                super::keywords::count_terms(content, &mut terms);
+            }
+        }
+
+        // Data: Only notes consisting of a single table and possibly headings are recognized as tables.
+        let blocks = root.children().collect_vec();
+        let data = match blocks
+            .iter()
+            .filter(|node| {
+                !matches!(
+                    node.data.borrow().value,
+                    comrak::nodes::NodeValue::Heading(_)
+                )
+            })
+            .collect_vec()[..]
+        {
+            [table]
+                if matches!(
+                    table.data.borrow().value,
+                    comrak::nodes::NodeValue::Table(_)
+                ) =>
+            {
+                let sourcepos = table.data.borrow().sourcepos;
+                super::DataTable::from_markdown(
+                    content
+                        .lines()
+                        .skip(sourcepos.start.line.saturating_sub(1))
+                        .take((sourcepos.end.line + 1).saturating_sub(sourcepos.start.line)),
+                )
+            }
+            _ => None,
+        };
+
+        // Snippet: The text of the first paragraph, with line breaks replaced by spaces.
+        let snippet = blocks
+            .iter()
+            .find(|node| {
+                matches!(
+                    node.data.borrow().value,
+                    comrak::nodes::NodeValue::Paragraph
+                )
+            })
+            .map(|paragraph| {
+                paragraph
+                    .descendants()
+                    .filter_map(|node| match &node.data.borrow().value {
+                        comrak::nodes::NodeValue::Text(text) => Some(text.to_owned()),
+                        comrak::nodes::NodeValue::Code(code) => Some(code.literal.to_owned()),
+                        comrak::nodes::NodeValue::Math(math) => Some(math.literal.to_owned()),
+                        comrak::nodes::NodeValue::SoftBreak
+                        | comrak::nodes::NodeValue::LineBreak => Some(String::from(" ")),
+                        _ => None,
+                    })
+                    .collect::<String>()
+            })
+            .unwrap_or_default();
+
+        // Tags: Go though all text nodes (and code, if configured) in the AST, split them at whitespace and look for those starting with a hash.
+        // Math nodes are never considered, neither are code nodes by default.
+        // Then append tags specified in the YAML frontmatter, and finally the tag of the folder, if enabled.
+        let tags = root
+            .descendants()
+            .flat_map(|node| match &node.data.borrow().value {
+                comrak::nodes::NodeValue::Text(content) => options.inline_tags(content),
+                comrak::nodes::NodeValue::Code(code) if !options.tags_ignore_code => {
+                    options.inline_tags(&code.literal)
+                }
+                comrak::nodes::NodeValue::CodeBlock(code) if !options.tags_ignore_code => {
+                    options.inline_tags(&code.literal)
+                }
+                _ => vec![],
+            })
+            .chain(tags)
+            .chain(options.folder_tag(path))
+            .collect_vec();
+
+        Ok(Self {
+            // Icon & Color: Check the frontmatter first, then the tags.
+            icon: icon.or_else(|| ParseOptions::tag_fallback(&options.tag_icons, &tags)),
+            color: color.or_else(|| ParseOptions::tag_fallback(&options.tag_colors, &tags)),
+            // Name: Check if there was one specified in the YAML fronmatter.
+            // If not, use the file name.
+            display_name: title.unwrap_or(name.clone()),
+            // Created: Check the frontmatter first, then the file name.
+            created: date.or_else(|| options.filename_date(&name)),
+            // Modified: Unknown without the file system.
+            modified: None,
+            // File name: Remove file extension.
+            name,
+            // Path: Already given - convert to owned version.
+            path: path.canonicalize().unwrap_or(path.to_path_buf()),
+            // Tags: Already extracted from the AST and the YAML frontmatter.
+            tags,
+            // Links: Go though all wikilinks in the syntax tree and map them.
+            // Code and math are separate nodes without link children, so links within them are skipped.
+            links: root
+                .descendants()
+                .flat_map(|node| match &node.data.borrow().value {
+                    comrak::nodes::NodeValue::WikiLink(link) => Some(super::name_to_id(&link.url)),
+                    comrak::nodes::NodeValue::Link(link) => {
+                        if !link.url.contains('/') && !link.url.contains('.') {
+                            Some(super::name_to_id(&link.url))
+                        } else {
+                            None
+                        }
+                    }
+                    _ => None,
+                })
+                .collect(),
+            // Words & Characters: Count the prose only.
+            words: prose.split_whitespace().count(),
+            characters: prose.chars().count(),
+            // Raw Words: Split at whitespace, grouping multiple consecutive instances of whitespace together.
+            // See definition of `split_whitespace` for criteria.
+            raw_words: content.split_whitespace().count(),
+            // Raw Characters: Simply use the length of the string.
+            raw_characters: content.len(),
+            terms,
+            // Export: Already extracted from the YAML frontmatter.
+            export,
+            // Aliases: Already extracted from the YAML frontmatter.
+            aliases,
+            // Blocks: Look for ids at the end of lines outside of code blocks.
+            blocks: super::block_ids(content),
+            // Data: Already extracted from the AST.
+            data,
+            // Snippet: Already extracted from the AST.
+            snippet,
+            // Person: Already extracted from the YAML frontmatter.
+            person,
+            // Dependencies: Already extracted from the YAML frontmatter.
+            dependencies,
+            // Mentions: Go through all text nodes, so mentions in code and math are skipped.
+            mentions: root
+                .descendants()
+                .flat_map(|node| match &node.data.borrow().value {
+                    comrak::nodes::NodeValue::Text(text) => super::people::mentions(text),
+                    _ => vec![],
+                })
+                .collect(),
+            // Mention links: Added by the index once it knows the person notes.
+            mention_links: Vec::new(),
+        })
+    }
+
+    /// Returns the title of this note in the given style, preceded by its icon and in its color if it has any.
+    pub fn title_line(&self, style: Style) -> Line<'static> {
+        self.label(&self.display_name, style)
+    }
+
+    /// Returns the given text in the given style, preceded by the icon of this note and in its color if it has any.
+    /// Colors ratatui does not recognize are ignored.
+    pub fn label(&self, text: &str, style: Style) -> Line<'static> {
+        let style = match self.color.as_deref().map(str::parse::<Color>) {
+            Some(Ok(color)) => style.fg(color),
+            _ => style,
+        };
+
+        match &self.icon {
+            Some(icon) => Line::styled(format!("{} {}", icon, text), style),
+            None => Line::styled(text.to_owned(), style),
+        }
+    }
+
+    /// Returns the date of this note: Its creation date if known, otherwise the date of its last modification.
+    pub fn date(&self) -> Option<chrono::NaiveDate> {
+        self.created
+            .or_else(|| self.modified.map(|modified| modified.date_naive()))
+    }
+
+    /// Converts this note to a small ratatui table displaying its most vital stats, the given number of notes linking to it and the given keywords.
+    pub fn to_stats_table(
+        &self,
+        styles: &styles::UiStyles,
+        tag_styles: &styles::TagStyles,
+        backlinks: usize,
+        keywords: &[super::Keyword],
+    ) -> Table {
+        let stats_widths = [
+            Constraint::Length(10),
+            Constraint::Length(12),
+            Constraint::Length(9),
+            Constraint::Min(20),
+        ];
+
+        // Display the note's tags
+        let tags = self
+            .tags
+            .iter()
+            .enumerate()
+            .flat_map(|(index, s)| {
+                [
+                    Span::styled(if index == 0 { "" } else { ", " }, styles.text_style),
+                    tag_styles.span(s, styles.subtitle_style),
+                ]
+            })
+            .collect_vec();
+
+        // Stats Area
+        let stats_rows = [
+            Row::new(vec![
+                Cell::from(i18n::tr("stats-note-words")).style(styles.text_style),
+                Cell::from(format!("{:7}", self.words)).style(styles.text_style),
+                Cell::from(i18n::tr("stats-note-tags")).style(styles.text_style),
+                Cell::from(Line::from(tags)).style(styles.text_style),
+            ]),
+            Row::new(vec![
+                Cell::from(i18n::tr("stats-note-chars")).style(styles.text_style),
+                Cell::from(format!("{:7}", self.characters)).style(styles.text_style),
+                Cell::from(i18n::tr("stats-note-path")).style(styles.text_style),
+                Cell::from(self.path.to_str().unwrap_or_default()).style(styles.text_style),
+            ]),
+            Row::new(vec![
+                Cell::from(i18n::tr("stats-note-backlinks")).style(styles.text_style),
+                Cell::from(format!("{:7}", backlinks)).style(styles.text_style),
+                Cell::from(i18n::tr("stats-note-keywords")).style(styles.text_style),
+                Cell::from(
+                    keywords
+                        .iter()
+                        .map(|keyword| keyword.term.as_str())
+                        .join(", "),
+                )
+                .style(styles.subtitle_style),
+            ]),
+        ];
+
+        Table::new(stats_rows, stats_widths).column_spacing(1)
+    }
+}
+
+/// Returns the prose of the given markdown syntax tree: The words of its text nodes separated by single spaces.
+/// Code, math and HTML have no text nodes and the targets of links are not part of them, so these are left out, as are URLs written out in the text.
+fn prose<'a>(root: &'a comrak::nodes::AstNode<'a>) -> String {
+    let mut text = String::new();
+    for node in root.descendants() {
+        match &node.data.borrow().value {
+            comrak::nodes::NodeValue::Text(content) => text.push_str(content),
+            // formatting and links within a word do not split it
+            comrak::nodes::NodeValue::Emph
+            | comrak::nodes::NodeValue::Strong
+            | comrak::nodes::NodeValue::Strikethrough
+            | comrak::nodes::NodeValue::Link(_)
+            | comrak::nodes::NodeValue::WikiLink(_)
+            | comrak::nodes::NodeValue::Image(_) => {}
+            _ => text.push(' '),
+        }
+    }
+
+    text.split_whitespace()
+        .filter(|word| !word.contains("://") && !word.starts_with("www."))
+        .join(" ")
+}
+
+/// Returns the names of the notes listed in the given frontmatter entry, given as a list or as a single name.
+/// Unquoted wiki links such as `[[Topology]]` are read by YAML as nested lists, so these are flattened, and the brackets and titles of quoted links are removed.
+fn dependency_names(entry: &yaml_rust::Yaml) -> Vec<String> {
+    match entry {
+        yaml_rust::Yaml::Array(entries) => entries.iter().flat_map(dependency_names).collect(),
+        yaml_rust::Yaml::String(name) => {
+            let name = name.trim().trim_start_matches("[[").trim_end_matches("]]");
+            let name = name.split_once('|').map_or(name, |(name, _title)| name);
+            if name.trim().is_empty() {
+                Vec::new()
+            } else {
+                vec![name.trim().to_owned()]
+            }
+        }
+        _ => Vec::new(),
+    }
+}
+
+/// Splits the given content of a note into its YAML frontmatter, if present, and the main content.
+fn extract_yaml(content: &str) -> (Option<&str>, &str) {
+    let regex = regex::Regex::new("---\n((.|\n)*)\n---\n((.|\n)*)")
+        .expect("Pre-defined regex to be valid.");
+
+    match regex.captures(content) {
+        // The 1st capture group is the front matter, the 3rd the actual content.
+        Some(captures) => (
+            captures.get(1).map(|m| m.as_str()),
+            captures.get(3).map_or("", |m| m.as_str()),
+        ),
+        // If the regex didn't match, then just use the content.
+        None => (None, content),
+    }
+}
+
+/// Parses the given YAML frontmatter of the note at the given path into its first document.
+/// A frontmatter without any document, e.g. an empty one, is parsed as an empty document.
+fn parse_yaml(yaml: &str, path: &path::Path) -> error::Result<yaml_rust::Yaml> {
+    // Lines of the frontmatter are counted from the line after the opening dashes.
+    let docs = yaml_rust::YamlLoader::load_from_str(yaml).map_err(|e| {
+        let line = e.marker().line() + 1;
+        error::RucolaError::from(e).in_file(path, Some(line))
+    })?;
+    Ok(docs.into_iter().next().unwrap_or(yaml_rust::Yaml::Null))
+}
+
+#[cfg(test)]
+mod tests {
+
+    use ratatui::prelude::{Color, Line, Style};
+    use std::path::{Path, PathBuf};
+
+    #[test]
+    fn test_stats_table_backlinks() {
+        use ratatui::{buffer::Buffer, layout::Rect, widgets::Widget};
+
+        let tmp = testdir::testdir!();
+        let config = crate::Config::default();
+        let tracker = crate::io::FileTracker::new(&config, tmp.clone()).unwrap();
+        let builder = crate::io::HtmlBuilder::new(&config, tmp.clone());
+        let mut index = crate::data::NoteIndex::new(tracker, builder, Default::default()).0;
+
+        let insert = |index: &mut crate::data::NoteIndex, name: &str, content: &str| {
+            let note = crate::data::Note::from_content(
+                &tmp.join(format!("{}.md", name)),
+                content,
+                &Default::default(),
+            )
+            .unwrap();
+            index.insert(crate::data::name_to_id(name), note);
+        };
+
+        // Returns the row of the stats table of the atlas showing its backlinks.
+        let backlinks_row = |index: &crate::data::NoteIndex| {
+            let table = index.get("atlas").unwrap().to_stats_table(
+                &Default::default(),
+                &Default::default(),
+                index.backlink_count("atlas"),
+                &[],
+            );
+            let area = Rect::new(0, 0, 60, 3);
+            let mut buf = Buffer::empty(area);
+            Widget::render(table, area, &mut buf);
+            (0..area.width)
+                .map(|x| buf[(x, 2)].symbol())
+                .collect::<String>()
+        };
+
+        insert(&mut index, "Atlas", "# Atlas");
+        insert(&mut index, "Chart", "Part of an [[Atlas]], see [[Atlas]].");
+        // each note is counted once
+        assert_eq!(index.backlink_count("atlas"), 1);
+        assert!(backlinks_row(&index).starts_with("Backlinks:       1"));
+
+        // the count follows incremental updates of the index
+        insert(&mut index, "Manifold", "Covered by an [[Atlas]].");
+        assert!(backlinks_row(&index).starts_with("Backlinks:       2"));
+
+        insert(&mut index, "Chart", "No longer linked.");
+        assert!(backlinks_row(&index).starts_with("Backlinks:       1"));
+    }
+
+    #[test]
+    fn test_loading() {
+        let _note = crate::data::Note::from_path(
+            Path::new("./tests/common/notes/Books.md"),
+            &Default::default(),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_values() {
+        let note = crate::data::Note::from_path(
+            Path::new("./tests/common/notes/math/Chart.md"),
+            &Default::default(),
+        )
+        .unwrap();
+
+        assert_eq!(note.name, String::from("Chart"));
+        assert_eq!(
+            note.tags,
+            vec![String::from("#diffgeo"), String::from("#topology")]
+        );
+        assert_eq!(
+            note.links,
+            vec![String::from("manifold"), String::from("diffeomorphism")]
+        );
+        assert_eq!(note.words, 65);
+        assert_eq!(note.characters, 358);
+        assert_eq!(note.raw_words, 115);
+        assert_eq!(note.raw_characters, 678);
+        assert_eq!(
+            note.path,
+            PathBuf::from("./tests/common/notes/math/Chart.md")
+                .canonicalize()
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn test_yaml_name() {
+        let note = crate::data::Note::from_path(
+            Path::new("./tests/common/notes/note25.md"),
+            &Default::default(),
+        )
+        .unwrap();
+
+        assert_eq!(note.display_name, String::from("YAML Format"));
+        assert_eq!(note.name, String::from("note25"));
+
+        assert_eq!(
+            note.path,
+            PathBuf::from("./tests/common/notes/note25.md")
+                .canonicalize()
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn test_tag_options() {
+        let tmp = testdir::testdir!();
+        let path = tmp.join("Issues.md");
+        std::fs::write(
+            &path,
+            "#rust #a #draft #1 `#inline` issue #42\n\n```\n#include <stdio.h>\n```\n",
+        )
+        .unwrap();
+
+        let note = crate::data::Note::from_path(&path, &Default::default()).unwrap();
+        assert_eq!(note.tags, vec!["#rust", "#a", "#draft", "#1", "#42"]);
+
+        let options = crate::data::ParseOptions::new(&crate::Config {
+            tag_min_length: 2,
+            tag_regex: Some(String::from("#[^0-9].*")),
+            tag_blacklist: vec![String::from("draft")],
+            ..Default::default()
+        })
+        .unwrap();
+
+        let note = crate::data::Note::from_path(&path, &options).unwrap();
+        assert_eq!(note.tags, vec!["#rust"]);
+
+        let options = crate::data::ParseOptions::new(&crate::Config {
+            tags_ignore_code: false,
+            ..Default::default()
+        })
+        .unwrap();
+
+        let note = crate::data::Note::from_path(&path, &options).unwrap();
+        assert_eq!(
+            note.tags,
+            vec!["#rust", "#a", "#draft", "#1", "#inline", "#42", "#include"]
+        );
+    }
+
+    #[test]
+    fn test_folder_tags() {
+        let tmp = testdir::testdir!();
+        let folder = tmp.join("projects").join("alpha team");
+        std::fs::create_dir_all(&folder).unwrap();
+        let path = folder.join("Kickoff.md");
+        std::fs::write(&path, "# Kickoff #meeting\n").unwrap();
+        std::fs::write(tmp.join("Inbox.md"), "# Inbox\n").unwrap();
+
+        let options = crate::data::ParseOptions::new(&crate::Config {
+            folder_tags: true,
+            ..Default::default()
+        })
+        .unwrap();
+
+        // without a vault, folders are unknown
+        let note = crate::data::Note::from_path(&path, &options).unwrap();
+        assert_eq!(note.tags, vec!["#meeting"]);
+
+        let options = options.with_vault_path(&tmp);
+        let note = crate::data::Note::from_path(&path, &options).unwrap();
+        assert_eq!(note.tags, vec!["#meeting", "#projects/alpha-team"]);
+
+        let note = crate::data::Note::from_path(&tmp.join("Inbox.md"), &options).unwrap();
+        assert!(note.tags.is_empty());
+
+        // disabled by default
+        let options = crate::data::ParseOptions::default().with_vault_path(&tmp);
+        let note = crate::data::Note::from_path(&path, &options).unwrap();
+        assert_eq!(note.tags, vec!["#meeting"]);
+    }
+
+    #[test]
+    fn test_code_and_math_skipped() {
+        let tmp = testdir::testdir!();
+        let path = tmp.join("Programming.md");
+        std::fs::write(
+            &path,
+            "# Programming #cpp\n\
+            Include with `#include [[Header]]` and see [[Preprocessor]].\n\n\
+            ```cpp\n#pragma once\n[[not a link]]\n```\n\n\
+            The set $x #A$ has $[[a, b]]$ elements, while $$\\# B \\cup [[C]]$$ has more.\n",
+        )
+        .unwrap();
+
+        let note = crate::data::Note::from_path(&path, &Default::default()).unwrap();
+
+        assert_eq!(note.tags, vec!["#cpp"]);
+        assert_eq!(note.links, vec!["preprocessor"]);
+    }
+
+    #[test]
+    fn test_tag_regex_invalid() {
+        assert!(crate::data::ParseOptions::new(&crate::Config {
+            tag_regex: Some(String::from("#[")),
+            ..Default::default()
+        })
+        .is_err());
+    }
+
+    #[test]
+    fn test_dates() {
+        let tmp = testdir::testdir!();
+
+        let created = tmp.join("Frontmatter.md");
+        std::fs::write(
+            &created,
+            "---\ndate: 2023-01-01\ncreated: 05.03.2024\n---\n# Note\n",
+        )
+        .unwrap();
+        let rfc = tmp.join("20220101 Rfc.md");
+        std::fs::write(&rfc, "---\ndate: 2024-03-05T10:00:00+01:00\n---\n# Note\n").unwrap();
+        let dashed = tmp.join("2021-12-24 Christmas.md");
+        std::fs::write(&dashed, "# Christmas\n").unwrap();
+        let compact = tmp.join("20211224.md");
+        std::fs::write(&compact, "# Christmas\n").unwrap();
+        let none = tmp.join("123456789 Numbers.md");
+        std::fs::write(&none, "# Numbers\n").unwrap();
+
+        let options = super::ParseOptions::default();
+        let date = |y, m, d| chrono::NaiveDate::from_ymd_opt(y, m, d);
+
+        let note = crate::data::Note::from_path(&created, &options).unwrap();
+        assert_eq!(note.created, date(2024, 3, 5));
+
+        let note = crate::data::Note::from_path(&rfc, &options).unwrap();
+        assert_eq!(note.created, date(2024, 3, 5));
+
+        let note = crate::data::Note::from_path(&dashed, &options).unwrap();
+        assert_eq!(note.created, date(2021, 12, 24));
+        assert_eq!(note.date(), date(2021, 12, 24));
+
+        let note = crate::data::Note::from_path(&compact, &options).unwrap();
+        assert_eq!(note.created, date(2021, 12, 24));
+
+        // no date -> fall back to modification time
+        let note = crate::data::Note::from_path(&none, &options).unwrap();
+        assert_eq!(note.created, None);
+        assert!(note.modified.is_some());
+        assert_eq!(note.date(), note.modified.map(|m| m.date_naive()));
+
+        // file name dates can be disabled
+        let options = super::ParseOptions::new(&crate::Config {
+            dates_from_filenames: false,
+            ..Default::default()
+        })
+        .unwrap();
+        let note = crate::data::Note::from_path(&dashed, &options).unwrap();
+        assert_eq!(note.created, None);
+    }
+
+    #[test]
+    fn test_yaml_export_options() {
+        let tmp = testdir::testdir!();
+        let path = tmp.join("Handout.md");
+        std::fs::write(
+            &path,
+            "---\nexport: false\ncss: custom.css\npdf-template: handout\n---\n# Handout\n",
+        )
+        .unwrap();
+
+        let note = crate::data::Note::from_path(&path, &Default::default()).unwrap();
+
+        assert_eq!(
+            note.export,
+            super::ExportOptions {
+                export: false,
+                css: Some(String::from("custom.css")),
+                pdf_template: Some(String::from("handout")),
+            }
+        );
+
+        let note = crate::data::Note::from_path(
+            Path::new("./tests/common/notes/note25.md"),
+            &Default::default(),
+        )
+        .unwrap();
+
+        assert_eq!(note.export, super::ExportOptions::default());
+    }
+
+    #[test]
+    fn test_yaml_aliases() {
+        let note = crate::data::Note::from_content(
+            Path::new("Lie Group.md"),
+            "---\naliases: [Lie Groups, Continuous Group]\n---\n# Lie Group",
+            &Default::default(),
+        )
+        .unwrap();
+        assert_eq!(note.aliases, vec!["Lie Groups", "Continuous Group"]);
+
+        let note = crate::data::Note::from_content(
+            Path::new("Lie Group.md"),
+            "---\nalias: Lie Groups\n---\n# Lie Group",
+            &Default::default(),
+        )
+        .unwrap();
+        assert_eq!(note.aliases, vec!["Lie Groups"]);
+    }
+
+    #[test]
+    fn test_prose_counts() {
+        let note = crate::data::Note::from_content(
+            Path::new("Prose.md"),
+            "---\ntitle: Not counted\n---\n# Two words\n\nSee [the docs](https://example.com/docs) or [[Other Note|this]], un*believ*able.\n\n```rust\nlet code = 1;\n```\n\n<div>html</div>\n\nMath $x^2$ and `code` and https://example.com too\n",
+            &Default::default(),
+        )
+        .unwrap();
+
+        // Two words / See the docs or this, unbelievable. / Math and and too
+        assert_eq!(note.words, 12);
+        assert_eq!(
+            note.characters,
+            "Two words See the docs or this, unbelievable. Math and and too".len()
+        );
+        // the raw counts include markup, code and HTML, but not the frontmatter
+        assert_eq!(note.raw_words, 24);
+        assert!(note.raw_characters > note.characters);
+    }
+
+    #[test]
+    fn test_yaml_dependencies() {
+        let note = crate::data::Note::from_content(
+            Path::new("Lie Group.md"),
+            "---\ndepends-on:\n  - [[Manifold]]\n  - \"[[Smooth Map|maps]]\"\n  - Topology\n  - manifold\n---\n# Lie Group",
+            &Default::default(),
+        )
+        .unwrap();
+        assert_eq!(
+            note.dependencies,
+            vec!["manifold", "smooth-map", "topology"]
+        );
+
+        let note = crate::data::Note::from_content(
+            Path::new("Lie Group.md"),
+            "---\ndepends-on: Group Theory\n---\n# Lie Group",
+            &Default::default(),
+        )
+        .unwrap();
+        assert_eq!(note.dependencies, vec!["group-theory"]);
+    }
+
+    #[test]
+    fn test_blocks() {
+        let note = crate::data::Note::from_content(
+            Path::new("Chart.md"),
+            "---\ntitle: Chart\n---\nA chart. ^def\n\n- an item ^item\n```\nnot ^code\n```\n",
+            &Default::default(),
+        )
+        .unwrap();
+        assert_eq!(note.blocks, vec!["def", "item"]);
+    }
+
+    #[test]
+    fn test_icon_and_color() {
+        let mut config = crate::Config::default();
+        config
+            .tag_icons
+            .insert(String::from("book"), String::from("📚"));
+        config
+            .tag_icons
+            .insert(String::from("#project"), String::from("🚧"));
+        config
+            .tag_colors
+            .insert(String::from("#project/done"), String::from("green"));
+        let options = crate::data::ParseOptions::new(&config).unwrap();
+
+        // the frontmatter takes precedence
+        let note = crate::data::Note::from_content(
+            Path::new("Dune.md"),
+            "---\nicon: 🏜️\ncolor: '#c2b280'\n---\nA #book about sand.\n",
+            &options,
+        )
+        .unwrap();
+        assert_eq!(note.icon.as_deref(), Some("🏜️"));
+        assert_eq!(note.color.as_deref(), Some("#c2b280"));
+        assert_eq!(
+            note.title_line(Style::default()),
+            Line::styled("🏜️ Dune", Style::default().fg(Color::Rgb(0xc2, 0xb2, 0x80)))
+        );
+
+        // subtags fall back to their supertags
+        let note = crate::data::Note::from_content(
+            Path::new("Rucola.md"),
+            "A #project/done project.\n",
+            &options,
+        )
+        .unwrap();
+        assert_eq!(note.icon.as_deref(), Some("🚧"));
+        assert_eq!(note.color.as_deref(), Some("green"));
+
+        let note = crate::data::Note::from_content(
+            Path::new("Plain.md"),
+            "A #project-like tag.\n",
+            &options,
+        )
+        .unwrap();
+        assert_eq!(note.icon, None);
+        assert_eq!(note.title_line(Style::default()), Line::raw("Plain"));
+    }
+
+    #[test]
+    fn test_snippet() {
+        let note = crate::data::Note::from_content(
+            Path::new("Chart.md"),
+            "---\ntitle: Chart\n---\n# Chart\n\nA *chart* maps\nan open set to [[Euclidean Space]] via `phi`.\n\nMore text.\n",
+            &Default::default(),
+        )
+        .unwrap();
+        assert_eq!(
+            note.snippet,
+            "A chart maps an open set to Euclidean Space via phi."
+        );
+
+        let note = crate::data::Note::from_content(
+            Path::new("Empty.md"),
+            "# Empty\n",
+            &Default::default(),
+        )
+        .unwrap();
+        assert_eq!(note.snippet, "");
+    }
+
+    #[test]
+    fn test_data_table() {
+        let note = crate::data::Note::from_content(
+            Path::new("Inventory.md"),
+            "---\ntitle: Inventory\n---\n# Inventory\n\n| Item | Count |\n| --- | --- |\n| Ruler | 12 |\n| Compass | 3 |\n",
+            &Default::default(),
+        )
+        .unwrap();
+        let data = note.data.unwrap();
+        assert_eq!(data.columns, vec!["Item", "Count"]);
+        assert_eq!(data.rows.len(), 2);
+        assert_eq!(data.file, None);
+
+        // tables surrounded by text are not structured data
+        let note = crate::data::Note::from_content(
+            Path::new("Inventory.md"),
+            "Some text.\n\n| Item | Count |\n| --- | --- |\n| Ruler | 12 |\n",
+            &Default::default(),
+        )
+        .unwrap();
+        assert!(note.data.is_none());
+    }
+
+    #[test]
+    fn test_yaml_tags() {
+        let note = crate::data::Note::from_path(
+            Path::new("./tests/common/notes/note25.md"),
+            &Default::default(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            note.tags,
+            vec![
+                String::from("#test"),
+                String::from("#files/yaml"),
+                String::from("#files/markdown"),
+                String::from("#abbreviations")
+            ]
+        );
+    }
+
+    #[test]
+    fn test_yaml_error_location() {
+        let error = crate::data::Note::from_content(
+            Path::new("Broken.md"),
+            "---\ntitle: Broken\ntags: [unclosed\n---\n# Broken",
+            &Default::default(),
+        )
+        .unwrap_err();
+
+        assert_eq!(error.category(), crate::error::ErrorCategory::Parse);
+        assert!(error.hint().is_some());
+        match error {
+            crate::error::RucolaError::InFile { path, line, .. } => {
+                assert_eq!(path, PathBuf::from("Broken.md"));
+                assert!(line.is_some_and(|line| line >= 3));
+            }
+            _ => panic!("Expected the error to carry its location."),
+        }
+    }
+
+    #[test]
+    fn test_empty_frontmatter() {
+        // found by the property tests below, used to panic
+        let note = crate::data::Note::from_content(
+            Path::new("Empty.md"),
+            "---\n\n---\n# Empty",
+            &Default::default(),
+        )
+        .unwrap();
+        assert_eq!(note.display_name, "Empty");
+        assert!(note.tags.is_empty());
+    }
+
+    proptest::proptest! {
+        #[test]
+        fn test_parse_arbitrary_content(content in "\\PC*") {
+            let _ = crate::data::Note::from_content(Path::new("Note.md"), &content, &Default::default());
+        }
+
+        #[test]
+        fn test_parse_arbitrary_frontmatter(
+            frontmatter in "([a-z-]{0,8}:[ \\[\\]{}\\-a-z0-9:\"']{0,12}\n|[ -]{0,4}[a-z\\[\\]{}:]{0,6}\n){0,6}",
+            content in "[#\\[\\]|()$`a-z \n-]{0,40}",
+        ) {
+            let _ = crate::data::Note::from_content(
+                Path::new("Note.md"),
+                &format!("---\n{}---\n{}", frontmatter, content),
+                &Default::default(),
+            );
+        }
+    }
+}
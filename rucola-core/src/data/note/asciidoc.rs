@@ -0,0 +1,266 @@
+use std::{collections::HashMap, path};
+
+use itertools::Itertools;
+
+use crate::error;
+
+/// Wether the file at the given path is an AsciiDoc file, which is parsed by [`from_asciidoc`] instead of as markdown.
+pub(super) fn is_asciidoc(path: &path::Path) -> bool {
+    path.extension().is_some_and(|extension| {
+        ["adoc", "asciidoc", "asc"]
+            .iter()
+            .any(|adoc| extension.eq_ignore_ascii_case(adoc))
+    })
+}
+
+/// Returns the name (in lowercase) and value of an attribute entry such as `:tags: travel, italy`.
+fn attribute(line: &str) -> Option<(String, &str)> {
+    let (name, value) = line.strip_prefix(':')?.split_once(':')?;
+    (!name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_alphanumeric() || c == '-' || c == '_'))
+    .then(|| (name.to_lowercase(), value.trim()))
+}
+
+/// Wether the given line opens or closes a delimited block whose content is not text, such as a listing (`----`), literal (`....`), passthrough (`++++`) or comment (`////`) block.
+/// Returns the delimiter, which closes the block again.
+fn verbatim_delimiter(line: &str) -> Option<&str> {
+    let first = line.chars().next()?;
+    (line.len() >= 4 && "-.+/".contains(first) && line.chars().all(|c| c == first)).then_some(line)
+}
+
+/// Matches cross references, written as `xref:target[text]` or `<<target,text>>`.
+fn xref_regex() -> regex::Regex {
+    regex::Regex::new(r"xref:([^\[\s]+)\[([^\]]*)\]|<<([^,>]+)(?:,\s*([^>]*))?>>")
+        .expect("Pre-defined regex to be valid.")
+}
+
+/// Matches explicit anchors within the document, written as `[[id]]`, `[#id]` or `[id=...]`.
+fn anchor_regex() -> regex::Regex {
+    regex::Regex::new(r"\[\[([^\]\s,]+)(?:,[^\]]*)?\]\]|\[#([^\]\s.%,]+)|\[id=([^\]\s,]+)")
+        .expect("Pre-defined regex to be valid.")
+}
+
+/// Returns the id of the note the given target of a cross reference refers to.
+/// Targets are other documents, possibly with an anchor after `#`, or anchors within the document, which are left out.
+fn xref_target(target: &str, anchors: &[&str]) -> Option<String> {
+    let target = target.trim();
+    let (document, _anchor) = target.split_once('#').unwrap_or((target, ""));
+    if document.is_empty()
+        || (!target.contains('#') && (document.starts_with('_') || anchors.contains(&document)))
+    {
+        return None;
+    }
+    path::Path::new(document)
+        .file_name()
+        .map(|name| super::super::name_to_id(&name.to_string_lossy()))
+        .filter(|id| !id.is_empty())
+}
+
+/// Extracts metadata from the given content of an AsciiDoc file stored at the given path.
+/// The title is taken from the document title (`= Title`) or the `:doctitle:` attribute, the tags from the `:tags:` attribute and the date from `:created:`, `:date:` or `:revdate:`.
+/// Cross references to other documents count as links. Listing, literal, passthrough and comment blocks are skipped like code in markdown notes.
+pub(super) fn from_asciidoc(
+    path: &path::Path,
+    content: &str,
+    options: &super::ParseOptions,
+) -> error::Result<super::Note> {
+    // Get the name of the file, without extension.
+    let name = path
+        .file_stem()
+        .map(|os| os.to_string_lossy().to_string())
+        .ok_or_else(|| error::RucolaError::NoteNameCannotBeRead(path.to_path_buf()))?;
+
+    let xref_regex = xref_regex();
+    let anchor_regex = anchor_regex();
+    let anchors = anchor_regex
+        .captures_iter(content)
+        .filter_map(|captures| captures.iter().skip(1).flatten().next())
+        .map(|anchor| anchor.as_str())
+        .collect_vec();
+
+    let mut title = None;
+    let mut attributes = HashMap::new();
+    let mut tags = Vec::new();
+    let mut links = Vec::new();
+    let mut mentions = Vec::new();
+    let mut terms = HashMap::new();
+    // The lines of the first paragraph, once it has started.
+    let mut snippet: Option<Vec<&str>> = None;
+    let mut snippet_done = false;
+    // The text without attribute entries and comments, which is counted.
+    let mut text = Vec::new();
+    // The delimiter of the verbatim block the current line is in, if any.
+    let mut verbatim: Option<&str> = None;
+    // Wether any content has been seen yet, as the document title has to come first.
+    let mut started = false;
+
+    for line in content.lines() {
+        let trimmed = line.trim_end();
+
+        // Verbatim blocks: Only the tags of listings and literals are recognized, if configured.
+        if let Some(delimiter) = verbatim {
+            if trimmed == delimiter {
+                verbatim = None;
+            } else if !delimiter.starts_with('/') {
+                text.push(line);
+                if !options.tags_ignore_code {
+                    tags.extend(options.inline_tags(line));
+                }
+            }
+            continue;
+        }
+        if let Some(delimiter) = verbatim_delimiter(trimmed) {
+            verbatim = Some(delimiter);
+            snippet_done |= snippet.is_some();
+            continue;
+        }
+
+        // Attribute entries and comments.
+        if let Some((name, value)) = attribute(trimmed) {
+            attributes.insert(name, value);
+            continue;
+        }
+        if trimmed.starts_with("//") {
+            continue;
+        }
+
+        // The document title has to be the first line of content.
+        if !started {
+            if let Some(document_title) = trimmed.strip_prefix("= ") {
+                title = Some(document_title.trim().to_owned());
+                started = true;
+                continue;
+            }
+        }
+        started |= !trimmed.is_empty();
+
+        text.push(line);
+
+        if trimmed.starts_with('=') && trimmed.trim_start_matches('=').starts_with(' ')
+            || trimmed.is_empty()
+        {
+            // Section titles and blank lines end paragraphs.
+            snippet_done |= snippet.is_some();
+        } else if !snippet_done
+            && !trimmed.starts_with(['[', '.', '|', ':'])
+            && !trimmed.contains("::")
+        {
+            snippet.get_or_insert_with(Vec::new).push(trimmed);
+        }
+
+        // `#text#` highlights text rather than tagging it.
+        tags.extend(
+            options
+                .inline_tags(line)
+                .into_iter()
+                .filter(|tag| !tag.ends_with('#')),
+        );
+        links.extend(xref_regex.captures_iter(line).filter_map(|captures| {
+            captures
+                .get(1)
+                .or_else(|| captures.get(3))
+                .and_then(|target| xref_target(target.as_str(), &anchors))
+        }));
+        mentions.extend(super::super::people::mentions(line));
+        super::super::keywords::count_terms(line, &mut terms);
+    }
+
+    // Tags: Inline tags first, then the tags of the attribute, and finally the tag of the folder, if enabled.
+    let tags = tags
+        .into_iter()
+        .chain(
+            attributes
+                .get("tags")
+                .into_iter()
+                .flat_map(|tags| tags.split([',', ' ']))
+                .map(|tag| tag.trim().trim_start_matches('#'))
+                .filter(|tag| !tag.is_empty())
+                .map(|tag| format!("#{}", tag)),
+        )
+        .chain(options.folder_tag(path))
+        .collect_vec();
+
+    // Created: Check the attributes first, then the file name.
+    let date = ["created", "date", "revdate"]
+        .iter()
+        .flat_map(|name| attributes.get(*name))
+        .find_map(|date| options.parse_date(date));
+
+    let text = text.join("\n");
+
+    Ok(super::Note {
+        // Icon & Color: There is no frontmatter, so only the tags are checked.
+        icon: super::ParseOptions::tag_fallback(&options.tag_icons, &tags),
+        color: super::ParseOptions::tag_fallback(&options.tag_colors, &tags),
+        display_name: title
+            .or_else(|| attributes.get("doctitle").map(|title| title.to_string()))
+            .filter(|title| !title.is_empty())
+            .unwrap_or(name.clone()),
+        created: date.or_else(|| options.filename_date(&name)),
+        modified: None,
+        path: path.canonicalize().unwrap_or(path.to_path_buf()),
+        tags,
+        links,
+        words: text.split_whitespace().count(),
+        characters: text.len(),
+        raw_words: text.split_whitespace().count(),
+        raw_characters: text.len(),
+        terms,
+        // Snippet: Cross references are replaced by their texts, or their targets if they have none.
+        snippet: snippet
+            .map(|lines| {
+                xref_regex
+                    .replace_all(&lines.join(" "), |captures: &regex::Captures| {
+                        captures
+                            .get(2)
+                            .or_else(|| captures.get(4))
+                            .filter(|text| !text.as_str().is_empty())
+                            .or_else(|| captures.get(1))
+                            .or_else(|| captures.get(3))
+                            .map_or("", |text| text.as_str())
+                            .to_owned()
+                    })
+                    .to_string()
+            })
+            .unwrap_or_default(),
+        mentions,
+        name,
+        ..Default::default()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    #[test]
+    fn test_asciidoc() {
+        let content = "= Trip to Rome\n:tags: travel, italy\n:revdate: 2024-05-02\n\n\
+            We visited xref:sights/Colosseum.adoc[the Colosseum] and <<Pantheon.adoc#dome,the Pantheon>> with @anna.\n\
+            Also see <<day-1>>, <<_food>> and https://rome.it[the city].\n\n\
+            [[day-1]]\n== Day 1\n\n\
+            Pizza #recipe and #highlighted# text.\n\
+            // a comment about [[Secret]] #secret\n\
+            ----\n# #code <<Code>>\n----\n";
+        let note = crate::data::Note::from_content(
+            Path::new("./tests/Rome.adoc"),
+            content,
+            &Default::default(),
+        )
+        .unwrap();
+
+        assert_eq!(note.name, "Rome");
+        assert_eq!(note.display_name, "Trip to Rome");
+        // tags in code are ignored by default
+        assert_eq!(note.tags, vec!["#recipe", "#travel", "#italy"]);
+        assert_eq!(note.links, vec!["colosseum", "pantheon"]);
+        assert_eq!(note.mentions, vec!["anna"]);
+        assert_eq!(note.created, chrono::NaiveDate::from_ymd_opt(2024, 5, 2));
+        assert_eq!(
+            note.snippet,
+            "We visited the Colosseum and the Pantheon with @anna. Also see day-1, _food and https://rome.it[the city]."
+        );
+    }
+}
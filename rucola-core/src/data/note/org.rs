@@ -0,0 +1,256 @@
+use std::{collections::HashMap, path};
+
+use itertools::Itertools;
+
+use crate::error;
+
+/// Wether the file at the given path is an Org-mode file, which is parsed by [`from_org`] instead of as markdown.
+pub(super) fn is_org(path: &path::Path) -> bool {
+    path.extension()
+        .is_some_and(|extension| extension.eq_ignore_ascii_case("org"))
+}
+
+/// Returns the key (in lowercase) and value of an in-buffer setting such as `#+TITLE: Rome`.
+fn keyword(line: &str) -> Option<(String, &str)> {
+    let (key, value) = line.trim_start().strip_prefix("#+")?.split_once(':')?;
+    (!key.is_empty() && !key.contains(char::is_whitespace))
+        .then(|| (key.to_lowercase(), value.trim()))
+}
+
+/// Returns the tags at the end of a headline, such as `* Trip to Rome   :travel:italy:`, including the leading `#`.
+fn headline_tags(line: &str) -> Vec<String> {
+    line.split_whitespace()
+        .last()
+        .filter(|last| last.len() > 1 && last.starts_with(':') && last.ends_with(':'))
+        .map(tag_list)
+        .unwrap_or_default()
+}
+
+/// Splits a list of tags separated by colons or whitespace, as in `#+FILETAGS: :travel:italy:`, and prepends a `#` to each.
+fn tag_list(tags: &str) -> Vec<String> {
+    tags.split(|c: char| c == ':' || c.is_whitespace())
+        .filter(|tag| !tag.is_empty())
+        .map(|tag| format!("#{}", tag))
+        .collect()
+}
+
+/// Matches Org links, written as `[[target]]` or `[[target][description]]`.
+fn link_regex() -> regex::Regex {
+    regex::Regex::new(r"\[\[([^\[\]]+)\](?:\[([^\[\]]*)\])?\]")
+        .expect("Pre-defined regex to be valid.")
+}
+
+/// Returns the ids of the notes the links in the given line refer to.
+/// Targets are notes if they are names, as in wiki links, or `file:` links to other files of the vault.
+/// Links to headlines (`*`), custom ids (`#`) and other schemes such as `https:` are left out.
+fn links(regex: &regex::Regex, line: &str) -> Vec<String> {
+    regex
+        .captures_iter(line)
+        .filter_map(|captures| {
+            let target = captures[1].trim();
+            if let Some(file) = target.strip_prefix("file:") {
+                // only the file is relevant, not the search option after `::`
+                let file = file.split("::").next().unwrap_or(file);
+                path::Path::new(file)
+                    .file_name()
+                    .map(|name| super::super::name_to_id(&name.to_string_lossy()))
+            } else if target.starts_with(['*', '#', '/', '.', '~'])
+                || target.split_once(':').is_some_and(|(scheme, _rest)| {
+                    !scheme.is_empty() && !scheme.contains(char::is_whitespace)
+                })
+            {
+                None
+            } else {
+                Some(super::super::name_to_id(target))
+            }
+        })
+        .filter(|id| !id.is_empty())
+        .collect()
+}
+
+/// Extracts metadata from the given content of an Org-mode file stored at the given path.
+/// The title, date and tags are taken from the in-buffer settings `#+TITLE`, `#+DATE` and `#+FILETAGS` and from the tags of headlines.
+/// Source and example blocks are skipped like code in markdown notes.
+pub(super) fn from_org(
+    path: &path::Path,
+    content: &str,
+    options: &super::ParseOptions,
+) -> error::Result<super::Note> {
+    // Get the name of the file, without extension.
+    let name = path
+        .file_stem()
+        .map(|os| os.to_string_lossy().to_string())
+        .ok_or_else(|| error::RucolaError::NoteNameCannotBeRead(path.to_path_buf()))?;
+
+    let link_regex = link_regex();
+
+    let mut title = None;
+    let mut date = None;
+    let mut file_tags = Vec::new();
+    let mut tags = Vec::new();
+    let mut links = Vec::new();
+    let mut mentions = Vec::new();
+    let mut terms = HashMap::new();
+    // The lines of the first paragraph, once it has started.
+    let mut snippet: Option<Vec<&str>> = None;
+    let mut snippet_done = false;
+    // The text without in-buffer settings, which is counted.
+    let mut text = Vec::new();
+    // Wether the current line is within a source or example block.
+    let mut in_code = false;
+    // Wether the current line is within a property drawer.
+    let mut in_drawer = false;
+
+    for line in content.lines() {
+        let trimmed = line.trim();
+        let lower = trimmed.to_lowercase();
+
+        // Source and example blocks: Only their tags are recognized, if configured.
+        if in_code {
+            if lower.starts_with("#+end_src") || lower.starts_with("#+end_example") {
+                in_code = false;
+            } else {
+                text.push(line);
+                if !options.tags_ignore_code {
+                    tags.extend(options.inline_tags(line));
+                }
+            }
+            continue;
+        }
+        if lower.starts_with("#+begin_src") || lower.starts_with("#+begin_example") {
+            in_code = true;
+            snippet_done |= snippet.is_some();
+            continue;
+        }
+
+        // Drawers such as `:PROPERTIES:` hold metadata, not text.
+        if in_drawer {
+            in_drawer = !trimmed.eq_ignore_ascii_case(":end:");
+            continue;
+        }
+        if trimmed.len() > 2
+            && trimmed.starts_with(':')
+            && trimmed.ends_with(':')
+            && !trimmed.contains(char::is_whitespace)
+            && !trimmed.eq_ignore_ascii_case(":end:")
+        {
+            in_drawer = true;
+            continue;
+        }
+
+        // In-buffer settings and comments.
+        if let Some((key, value)) = keyword(trimmed) {
+            match key.as_str() {
+                "title" => title = Some(value.to_owned()).filter(|title| !title.is_empty()),
+                "date" => {
+                    date = options.parse_date(
+                        value
+                            .trim_matches(['<', '>', '[', ']'])
+                            .split_whitespace()
+                            .next()
+                            .unwrap_or_default(),
+                    )
+                }
+                "filetags" => file_tags.extend(tag_list(value)),
+                _ => {}
+            }
+            continue;
+        }
+        if trimmed.starts_with("#+") || trimmed == "#" || trimmed.starts_with("# ") {
+            continue;
+        }
+
+        text.push(line);
+
+        if line.starts_with('*') && line.trim_start_matches('*').starts_with(' ') {
+            // Headlines end paragraphs and may carry tags.
+            tags.extend(headline_tags(line));
+            snippet_done |= snippet.is_some();
+        } else if trimmed.is_empty() {
+            snippet_done |= snippet.is_some();
+        } else if !snippet_done && !trimmed.starts_with('|') {
+            snippet.get_or_insert_with(Vec::new).push(trimmed);
+        }
+
+        tags.extend(options.inline_tags(line));
+        links.extend(self::links(&link_regex, line));
+        mentions.extend(super::super::people::mentions(line));
+        super::super::keywords::count_terms(line, &mut terms);
+    }
+
+    // Tags: Headline and inline tags first, then the tags of the file, and finally the tag of the folder, if enabled.
+    let tags = tags
+        .into_iter()
+        .chain(file_tags)
+        .chain(options.folder_tag(path))
+        .collect_vec();
+
+    let text = text.join("\n");
+
+    Ok(super::Note {
+        // Icon & Color: There is no frontmatter, so only the tags are checked.
+        icon: super::ParseOptions::tag_fallback(&options.tag_icons, &tags),
+        color: super::ParseOptions::tag_fallback(&options.tag_colors, &tags),
+        display_name: title.unwrap_or(name.clone()),
+        created: date.or_else(|| options.filename_date(&name)),
+        modified: None,
+        path: path.canonicalize().unwrap_or(path.to_path_buf()),
+        tags,
+        links,
+        words: text.split_whitespace().count(),
+        characters: text.len(),
+        raw_words: text.split_whitespace().count(),
+        raw_characters: text.len(),
+        terms,
+        // Snippet: Links are replaced by their descriptions, or their targets if they have none.
+        snippet: snippet
+            .map(|lines| {
+                link_regex
+                    .replace_all(&lines.join(" "), |captures: &regex::Captures| {
+                        captures
+                            .get(2)
+                            .map_or(&captures[1], |description| description.as_str())
+                            .to_owned()
+                    })
+                    .to_string()
+            })
+            .unwrap_or_default(),
+        mentions,
+        name,
+        ..Default::default()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    #[test]
+    fn test_org() {
+        let content = "#+TITLE: Trip to Rome\n#+FILETAGS: :travel:italy:\n#+DATE: <2024-05-02 Thu>\n\n\
+            We visited [[Colosseum]] and [[file:../sights/Pantheon.org][the Pantheon]] with @anna.\n\
+            Also see [[https://rome.it][the city]] and [[*Day 1]].\n\n\
+            * Day 1 :food:\n:PROPERTIES:\n:ID: 1234\n:END:\n\
+            Pizza #recipe\n\
+            #+BEGIN_SRC python\n# #code [[Code]]\n#+END_SRC\n";
+        let note = crate::data::Note::from_content(
+            Path::new("./tests/Rome.org"),
+            content,
+            &Default::default(),
+        )
+        .unwrap();
+
+        assert_eq!(note.name, "Rome");
+        assert_eq!(note.display_name, "Trip to Rome");
+        // tags in code are ignored by default
+        assert_eq!(note.tags, vec!["#food", "#recipe", "#travel", "#italy"]);
+        assert_eq!(note.links, vec!["colosseum", "pantheon"]);
+        assert_eq!(note.mentions, vec!["anna"]);
+        assert_eq!(note.created, chrono::NaiveDate::from_ymd_opt(2024, 5, 2));
+        assert_eq!(
+            note.snippet,
+            "We visited Colosseum and the Pantheon with @anna. Also see the city and *Day 1."
+        );
+        assert_eq!(note.words, 24);
+    }
+}
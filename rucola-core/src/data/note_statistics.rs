@@ -0,0 +1,1470 @@
+use crate::{data, error, i18n, styles};
+use ratatui::{prelude::*, widgets::*};
+use std::collections::{HashMap, HashSet};
+
+/// How many recently used filters remember their matches, see [`EnvironmentStats::refilter`].
+const FILTER_CACHE_SIZE: usize = 8;
+
+/// The symbols after the titles of visited notes, from rarely to most often visited.
+const HEAT_SYMBOLS: [&str; 4] = ["▂", "▄", "▆", "█"];
+
+/// A struct describing statistics to a note in relation to a containing environment.
+#[derive(Debug, Clone)]
+pub struct NoteEnvStatistics {
+    /// The notes id
+    pub id: String,
+    /// The fuzzy match score of this note with the filter used to create the environment
+    match_score: i64,
+    /// The amount of links pointing to this note from anywhere.
+    inlinks_global: usize,
+    /// The amount of links pointing to this note from other notes within the environment.
+    inlinks_local: usize,
+    /// The amount of links going out from this note to other notes within the environment.
+    outlinks_local: usize,
+    /// The amount of links going out from this note to other notes.
+    /// Differs from the length of the 'links' table by not counting broken links.
+    outlinks_global: usize,
+    /// The amount of links originating from this note that do not have a valid target anywhere.
+    broken_links: usize,
+    /// The amount of words of the note, as counted into the environment.
+    words: usize,
+    /// The amount of characters of the note, as counted into the environment.
+    characters: usize,
+    /// The tags of the note, as counted into the environment.
+    tags: Vec<String>,
+}
+
+impl NoteEnvStatistics {
+    /// Calculates the statistics of the note with the given id, if it exists, in the environment of the notes for which `is_local` holds.
+    fn new(
+        index: &data::NoteIndex,
+        id: String,
+        match_score: i64,
+        is_local: impl Fn(&str) -> bool,
+    ) -> Option<Self> {
+        let note = index.inner.get(&id)?;
+
+        let mut stats = Self {
+            match_score,
+            inlinks_global: 0,
+            inlinks_local: 0,
+            outlinks_local: 0,
+            outlinks_global: 0,
+            broken_links: 0,
+            words: note.words,
+            characters: note.characters,
+            tags: note.tags.clone(),
+            id,
+        };
+
+        // Outgoing links count if their target exists, and locally if it is within the environment.
+        for link in &note.links {
+            if index.inner.contains_key(link) {
+                stats.outlinks_global += 1;
+                if is_local(link) {
+                    stats.outlinks_local += 1;
+                }
+            }
+        }
+        stats.broken_links = note.links.len() - stats.outlinks_global;
+
+        // Incoming links always come from existing notes.
+        for source in index.backlink_sources(&stats.id) {
+            stats.inlinks_global += 1;
+            if is_local(source) {
+                stats.inlinks_local += 1;
+            }
+        }
+
+        Some(stats)
+    }
+
+    /// Converts this note to a ratatui table row with its stats
+    /// Notes are shown in the style of the first of their tags that has one configured.
+    fn to_row(
+        &self,
+        index: data::NoteIndexContainer,
+        styles: &styles::UiStyles,
+        tag_styles: &styles::TagStyles,
+        heat: Option<&'static str>,
+        stale: bool,
+    ) -> Option<Row> {
+        // generate the stats row for each element
+        index.borrow().get(&self.id).map(|note| {
+            let mut title = note.title_line(tag_styles.first_style(&note.tags, styles.text_style));
+
+            // Screen readers read the table linearly, so all stats are named in a single column.
+            if styles::screen_reader() {
+                let mut line = title;
+                line.push_span(Span::raw(i18n::tr_args(
+                    "select-row",
+                    &[
+                        ("words", note.words.into()),
+                        ("chars", note.characters.into()),
+                        ("global_out", self.outlinks_global.into()),
+                        ("local_out", self.outlinks_local.into()),
+                        ("global_in", self.inlinks_global.into()),
+                        ("local_in", self.inlinks_local.into()),
+                    ],
+                )));
+                return Row::new(vec![Cell::from(line)]).style(styles.text_style);
+            }
+
+            // a subtle indicator of how often the note has been visited
+            if let Some(heat) = heat {
+                title.push_span(Span::styled(
+                    format!(" {}", heat),
+                    styles.text_style.add_modifier(Modifier::DIM),
+                ));
+            }
+            // a flag on notes in need of an update
+            if stale {
+                title.push_span(Span::styled(
+                    format!(" {}", i18n::tr("select-stale")),
+                    styles.hotkey_style,
+                ));
+            }
+
+            Row::new(vec![
+                Cell::from(title),
+                Cell::from(format!("{:7}", note.words)),
+                Cell::from(format!("{:7}", note.characters)),
+                Cell::from(format!("{:7}", self.outlinks_global)),
+                Cell::from(format!("{:7}", self.outlinks_local)),
+                Cell::from(format!("{:7}", self.inlinks_global)),
+                Cell::from(format!("{:7}", self.inlinks_local)),
+            ])
+            .style(styles.text_style)
+        })
+    }
+}
+
+/// Describes the current sorting mode of the displayed list.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
+pub enum SortingMode {
+    #[default]
+    Name,
+    Words,
+    Chars,
+    GlobalOutLinks,
+    LocalOutLinks,
+    GlobalInLinks,
+    LocalInLinks,
+    Score,
+    Broken,
+    Date,
+    Created,
+    Modified,
+    Visits,
+}
+
+/// A data struct containing statistical information about a (subset of a) user's notes.
+/// This subset is called an 'environment' and is described by a filter passed to the constructor.
+#[derive(Debug, Clone)]
+pub struct EnvironmentStats {
+    /// The total amount of words in the notes in this environment.
+    /// What is a word and what not mirrors the definition from Note.words.
+    word_count_total: usize,
+    /// The total amount of characters, including whitespace, in the notes of this environment.
+    char_count_total: usize,
+    /// The total amount of notes in this environment.
+    note_count_total: usize,
+    /// The total amount of _unique_ tags in this environment.
+    tag_count_total: usize,
+    /// Total amount of links from a note within the environment to another note within the environment.
+    local_local_links: usize,
+    /// Total amount of links from a note within the environment to any note.
+    local_global_links: usize,
+    /// Total amount of links from any note to a note within the environment.
+    global_local_links: usize,
+    /// A vector of all notes within the environment.
+    filtered_stats: Vec<NoteEnvStatistics>,
+    /// Counts how many links among notes within the environment do not have a valid target anywhere.
+    broken_links: usize,
+    /// How many notes within the environment carry each tag, to count the unique tags as notes enter and leave the environment.
+    tag_counts: HashMap<String, usize>,
+    /// The filter describing the environment.
+    filter: data::Filter,
+    /// The matches of the most recently used filters with their scores, oldest first, so returning to a filter, e.g. by deleting typed characters, needs no evaluation.
+    /// Cleared when notes change.
+    filter_cache: Vec<(data::Filter, HashMap<String, i64>)>,
+    /// Wether the environment contains all notes matching the filter, i.e. no search of their full text is in progress.
+    complete: bool,
+}
+
+impl EnvironmentStats {
+    /// Creates a new set of statistics from the subset of the passed index that matches the given filter.
+    pub fn new_with_filter(index: &super::NoteIndexContainer, filter: data::Filter) -> Self {
+        let mut stats = Self {
+            word_count_total: 0,
+            char_count_total: 0,
+            note_count_total: 0,
+            tag_count_total: 0,
+            local_local_links: 0,
+            local_global_links: 0,
+            global_local_links: 0,
+            filtered_stats: Vec::new(),
+            broken_links: 0,
+            tag_counts: HashMap::new(),
+            filter: data::Filter::default(),
+            filter_cache: Vec::new(),
+            complete: false,
+        };
+        stats.refilter(index, filter);
+        stats
+    }
+
+    /// Changes the filter describing the environment.
+    /// Only the stats of notes entering or leaving the environment and of the notes linked to or from them are recalculated, the totals are adjusted by the difference.
+    /// Recently used filters are not evaluated again, and filters refining the current one, e.g. by typing further characters, are only evaluated for the notes within the environment.
+    pub fn refilter(&mut self, index: &super::NoteIndexContainer, filter: data::Filter) {
+        let index = index.borrow();
+        let scores = match self.cached_scores(&filter) {
+            Some(scores) => scores,
+            None => {
+                let (scores, _pending) = self.evaluate(&index, &filter, true);
+                self.cache(filter.clone(), &scores);
+                scores
+            }
+        };
+        self.filter = filter;
+        self.complete = true;
+        self.update(&index, scores, &[]);
+    }
+
+    /// Changes the filter describing the environment like [`EnvironmentStats::refilter`], but without reading the full text of any note.
+    /// Returns the ids and paths of the notes whose full text decides wether they match. They are left out until reported by [`EnvironmentStats::text_found`].
+    pub fn refilter_streaming(
+        &mut self,
+        index: &super::NoteIndexContainer,
+        filter: data::Filter,
+    ) -> Vec<(String, std::path::PathBuf)> {
+        let index = index.borrow();
+        let (scores, pending) = match self.cached_scores(&filter) {
+            Some(scores) => (scores, Vec::new()),
+            None => {
+                let (scores, pending) = self.evaluate(&index, &filter, false);
+                if pending.is_empty() {
+                    self.cache(filter.clone(), &scores);
+                }
+                (scores, pending)
+            }
+        };
+        self.filter = filter;
+        self.complete = pending.is_empty();
+        self.update(&index, scores, &[]);
+
+        pending
+            .into_iter()
+            .filter_map(|id| {
+                let path = index.inner.get(&id)?.path.clone();
+                Some((id, path))
+            })
+            .collect()
+    }
+
+    /// Adds the notes with the given ids, found to contain the text searched for by the filter, see [`EnvironmentStats::refilter_streaming`].
+    pub fn text_found(&mut self, index: &super::NoteIndexContainer, ids: &[String]) {
+        let index = index.borrow();
+        let scores = ids
+            .iter()
+            .map(|id| {
+                (
+                    id.clone(),
+                    index
+                        .inner
+                        .get(id)
+                        .and_then(|note| self.filter.apply_with_text(note, &index, true)),
+                )
+            })
+            .collect();
+        self.update(&index, scores, &[]);
+    }
+
+    /// Marks the search started by [`EnvironmentStats::refilter_streaming`] as finished, so the environment contains all matching notes.
+    pub fn search_finished(&mut self) {
+        let scores = self
+            .filtered_stats
+            .iter()
+            .map(|env_stats| (env_stats.id.clone(), Some(env_stats.match_score)))
+            .collect();
+        self.cache(self.filter.clone(), &scores);
+        self.complete = true;
+    }
+
+    /// Returns the scores of all notes matching a recently used filter or leaving the environment for it, if the filter is cached.
+    fn cached_scores(&self, filter: &data::Filter) -> Option<HashMap<String, Option<i64>>> {
+        let (_filter, matches) = self
+            .filter_cache
+            .iter()
+            .find(|(cached, _matches)| cached == filter)?;
+        // notes not among the cached matches leave the environment
+        Some(
+            self.filtered_stats
+                .iter()
+                .map(|env_stats| (env_stats.id.clone(), None))
+                .chain(matches.iter().map(|(id, score)| (id.clone(), Some(*score))))
+                .collect(),
+        )
+    }
+
+    /// Matches the notes that could match the given filter against it, returning their scores.
+    /// Unless told to read the full text of notes, notes whose full text decides wether they match are returned separately and treated as not matching.
+    fn evaluate(
+        &self,
+        index: &data::NoteIndex,
+        filter: &data::Filter,
+        read: bool,
+    ) -> (HashMap<String, Option<i64>>, Vec<String>) {
+        // notes outside of a complete environment cannot match a filter refining it
+        let candidates = if self.complete && filter.refines(&self.filter) {
+            self.filtered_stats
+                .iter()
+                .map(|env_stats| env_stats.id.as_str())
+                .collect::<Vec<_>>()
+        } else {
+            index.inner.keys().map(String::as_str).collect()
+        };
+
+        let mut scores = HashMap::new();
+        let mut pending = Vec::new();
+        for id in candidates {
+            let score = match index.inner.get(id) {
+                Some(note) if read => filter.apply(note, index),
+                Some(note) => filter
+                    .apply_without_reading(note, index)
+                    .unwrap_or_else(|| {
+                        pending.push(id.to_owned());
+                        None
+                    }),
+                None => None,
+            };
+            scores.insert(id.to_owned(), score);
+        }
+        (scores, pending)
+    }
+
+    /// Remembers the matches of the given filter, forgetting the oldest filter if too many are remembered.
+    fn cache(&mut self, filter: data::Filter, scores: &HashMap<String, Option<i64>>) {
+        if self.filter_cache.len() >= FILTER_CACHE_SIZE {
+            self.filter_cache.remove(0);
+        }
+        let matches = scores
+            .iter()
+            .filter_map(|(id, score)| score.map(|score| (id.clone(), score)))
+            .collect();
+        self.filter_cache.push((filter, matches));
+    }
+
+    /// Updates the stats after the links or contents of the notes with the given ids changed, see [`data::NoteIndex::take_touched`].
+    /// The notes are matched against the filter again, all other notes keep their stats.
+    pub fn notes_changed(&mut self, index: &super::NoteIndexContainer, ids: &[String]) {
+        // the changed notes may match other filters now
+        self.filter_cache.clear();
+        let index = index.borrow();
+        let scores = ids
+            .iter()
+            .map(|id| {
+                (
+                    id.clone(),
+                    index
+                        .inner
+                        .get(id)
+                        .and_then(|note| self.filter.apply(note, &index)),
+                )
+            })
+            .collect();
+        self.update(&index, scores, ids);
+    }
+
+    /// Moves notes into or out of the environment according to the given scores, `None` meaning a note no longer matches.
+    /// Notes without a score keep theirs.
+    /// Recalculates the stats of the notes whose membership changed, of the notes linked to or from them and of the given touched notes.
+    fn update(
+        &mut self,
+        index: &data::NoteIndex,
+        scores: HashMap<String, Option<i64>>,
+        touched: &[String],
+    ) {
+        let mut members = std::mem::take(&mut self.filtered_stats)
+            .into_iter()
+            .map(|env_stats| (env_stats.id.clone(), env_stats))
+            .collect::<HashMap<_, _>>();
+
+        let score_after =
+            |id: &str, members: &HashMap<String, NoteEnvStatistics>| match scores.get(id) {
+                Some(score) => *score,
+                None => members.get(id).map(|env_stats| env_stats.match_score),
+            };
+
+        // Find all notes within the environment whose stats change.
+        let mut recalculate = touched.iter().cloned().collect::<HashSet<_>>();
+        for (id, score) in &scores {
+            if members.contains_key(id) != score.is_some() {
+                // The local links of the neighbours of notes entering or leaving change.
+                recalculate.insert(id.clone());
+                recalculate.extend(
+                    index
+                        .inner
+                        .get(id)
+                        .into_iter()
+                        .flat_map(|note| &note.links)
+                        .chain(index.backlink_sources(id))
+                        .filter(|neighbour| {
+                            members.contains_key(*neighbour)
+                                || score_after(neighbour, &members).is_some()
+                        })
+                        .cloned(),
+                );
+            }
+        }
+        let recalculate = recalculate
+            .into_iter()
+            .map(|id| {
+                let score = score_after(&id, &members);
+                (id, score)
+            })
+            .collect::<Vec<_>>();
+
+        // Remove their old stats from the totals...
+        for (id, _score) in &recalculate {
+            if let Some(env_stats) = members.remove(id) {
+                self.subtract(&env_stats);
+            }
+        }
+        // ...update the scores of all other notes...
+        for (id, score) in &scores {
+            if let (Some(env_stats), Some(score)) = (members.get_mut(id), score) {
+                env_stats.match_score = *score;
+            }
+        }
+        // ...and add the new stats of those still within the environment.
+        let entering = recalculate
+            .iter()
+            .filter(|(_id, score)| score.is_some())
+            .map(|(id, _score)| id.as_str())
+            .collect::<HashSet<_>>();
+        let is_local = |id: &str| members.contains_key(id) || entering.contains(id);
+        let recalculated = recalculate
+            .iter()
+            .filter_map(|(id, score)| {
+                score.and_then(|score| NoteEnvStatistics::new(index, id.clone(), score, is_local))
+            })
+            .collect::<Vec<_>>();
+        for env_stats in recalculated {
+            self.add(&env_stats);
+            members.insert(env_stats.id.clone(), env_stats);
+        }
+
+        // Default sort: By match score, descending.
+        self.filtered_stats = members.into_values().collect();
+        self.filtered_stats
+            .sort_by_cached_key(|env_stats| env_stats.match_score);
+        self.filtered_stats.reverse();
+    }
+
+    /// Adds the stats of a note entering the environment to the totals.
+    fn add(&mut self, env_stats: &NoteEnvStatistics) {
+        self.word_count_total += env_stats.words;
+        self.char_count_total += env_stats.characters;
+        self.note_count_total += 1;
+        for tag in &env_stats.tags {
+            *self.tag_counts.entry(tag.clone()).or_default() += 1;
+        }
+        self.tag_count_total = self.tag_counts.len();
+        self.local_local_links += env_stats.outlinks_local;
+        self.local_global_links += env_stats.outlinks_global;
+        self.global_local_links += env_stats.inlinks_global;
+        self.broken_links += env_stats.broken_links;
+    }
+
+    /// Removes the stats of a note leaving the environment from the totals.
+    fn subtract(&mut self, env_stats: &NoteEnvStatistics) {
+        self.word_count_total -= env_stats.words;
+        self.char_count_total -= env_stats.characters;
+        self.note_count_total -= 1;
+        for tag in &env_stats.tags {
+            if let Some(count) = self.tag_counts.get_mut(tag) {
+                *count -= 1;
+                if *count == 0 {
+                    self.tag_counts.remove(tag);
+                }
+            }
+        }
+        self.tag_count_total = self.tag_counts.len();
+        self.local_local_links -= env_stats.outlinks_local;
+        self.local_global_links -= env_stats.outlinks_global;
+        self.global_local_links -= env_stats.inlinks_global;
+        self.broken_links -= env_stats.broken_links;
+    }
+
+    /// Returns the nth element of the underlying sorted vector
+    pub fn get_selected(&self, index: usize) -> Option<&NoteEnvStatistics> {
+        self.filtered_stats.get(index)
+    }
+
+    /// Sorts the underlying vec. Names are compared according to the given collation.
+    pub fn sort(
+        &mut self,
+        index: data::NoteIndexContainer,
+        mode: SortingMode,
+        ascending: bool,
+        collation: &data::Collation,
+    ) {
+        // Always sort by name first
+        let mut named = std::mem::take(&mut self.filtered_stats)
+            .into_iter()
+            .map(|env_stats| {
+                let name = index
+                    .borrow()
+                    .get(&env_stats.id)
+                    .map_or_else(|| env_stats.id.clone(), |note| note.name.clone());
+                (name, env_stats)
+            })
+            .collect::<Vec<_>>();
+        named.sort_by(|(name1, env_stats1), (name2, env_stats2)| {
+            collation
+                .compare(name1, name2)
+                .then_with(|| env_stats1.id.cmp(&env_stats2.id))
+        });
+        self.filtered_stats = named
+            .into_iter()
+            .map(|(_name, env_stats)| env_stats)
+            .collect();
+
+        // If the sorting mode is not name, now sort by the actual sorting mode.
+        if mode != SortingMode::Name {
+            // If sorting in reverse is desired, pre-reverse this, so when reversing again later, the list will still be sub-sorted by name ascendingly.
+            if !ascending {
+                self.filtered_stats.reverse();
+            }
+            // all others are usize and can be done in one thing
+            let visits = &self.filter.visits;
+            self.filtered_stats.sort_by_cached_key(|env_stats| {
+                if let Some(note) = index.borrow().get(&env_stats.id) {
+                    match mode {
+                        // This should not appear
+                        SortingMode::Name => 0,
+                        // These should appear
+                        SortingMode::Words => note.words,
+                        SortingMode::Chars => note.characters,
+                        SortingMode::GlobalOutLinks => env_stats.outlinks_global,
+                        SortingMode::LocalOutLinks => env_stats.outlinks_local,
+                        SortingMode::GlobalInLinks => env_stats.inlinks_global,
+                        SortingMode::LocalInLinks => env_stats.inlinks_local,
+                        SortingMode::Score => env_stats.match_score as usize,
+                        SortingMode::Broken => env_stats.broken_links,
+                        SortingMode::Date => note
+                            .date()
+                            .map(|date| chrono::Datelike::num_days_from_ce(&date).max(0) as usize)
+                            .unwrap_or_default(),
+                        SortingMode::Created => note
+                            .created
+                            .map(|date| chrono::Datelike::num_days_from_ce(&date).max(0) as usize)
+                            .unwrap_or_default(),
+                        SortingMode::Modified => note
+                            .modified
+                            .map(|modified| modified.timestamp().max(0) as usize)
+                            .unwrap_or_default(),
+                        SortingMode::Visits => {
+                            visits.get(&env_stats.id).copied().unwrap_or_default()
+                        }
+                    }
+                } else {
+                    0
+                }
+            })
+        }
+
+        // Potentially reverse sorting
+        if !ascending {
+            self.filtered_stats.reverse();
+        }
+    }
+
+    /// Returns the amount of notes in this environment.
+    pub fn len(&self) -> usize {
+        self.filtered_stats.len()
+    }
+
+    /// Wether this environment contains no notes.
+    pub fn is_empty(&self) -> bool {
+        self.filtered_stats.is_empty()
+    }
+
+    /// Returns the ids of all notes in this environment, in their current order.
+    pub fn ids(&self) -> Vec<String> {
+        self.filtered_stats
+            .iter()
+            .map(|env_stats| env_stats.id.clone())
+            .collect()
+    }
+
+    /// Creates bar charts showing the distributions of note lengths (in words), of the number of tags per note and of the number of valid links (incoming and outgoing) per note within this environment.
+    pub fn to_histograms(
+        &self,
+        index: data::NoteIndexContainer,
+        styles: &styles::UiStyles,
+    ) -> [BarChart<'static>; 3] {
+        let index = index.borrow();
+
+        let notes = self
+            .filtered_stats
+            .iter()
+            .filter_map(|env_stats| index.get(&env_stats.id).map(|note| (env_stats, note)))
+            .collect::<Vec<_>>();
+
+        let words = histogram(notes.iter().map(|(_, note)| note.words));
+        let tags = histogram(notes.iter().map(|(_, note)| note.tags.len()));
+        let degrees = histogram(
+            notes
+                .iter()
+                .map(|(env_stats, _)| env_stats.inlinks_global + env_stats.outlinks_global),
+        );
+
+        [
+            (words, "Note Lengths (Words)"),
+            (tags, "Tags per Note"),
+            (degrees, "Links per Note"),
+        ]
+        .map(|(buckets, title)| {
+            // make the bars wide enough to fit their labels
+            let bar_width = buckets
+                .iter()
+                .map(|(label, _)| label.len())
+                .max()
+                .unwrap_or_default()
+                .max(3) as u16;
+
+            let bars = buckets
+                .into_iter()
+                .map(|(label, count)| {
+                    Bar::default()
+                        .value(count)
+                        .label(Line::from(label))
+                        .style(styles.subtitle_style)
+                        .value_style(styles.selected_style)
+                })
+                .collect::<Vec<_>>();
+
+            BarChart::default()
+                .block(
+                    styles::bordered().title(style::Styled::set_style(title, styles.title_style)),
+                )
+                .data(BarGroup::default().bars(&bars))
+                .bar_width(bar_width)
+                .bar_gap(1)
+                .label_style(styles.text_style)
+        })
+    }
+
+    /// Writes the (sorted) notes of this environment and their statistics to the given writer in CSV format.
+    /// The first line is a header naming the columns.
+    pub fn to_csv(
+        &self,
+        index: data::NoteIndexContainer,
+        writer: &mut impl std::io::Write,
+    ) -> error::Result<()> {
+        writeln!(
+            writer,
+            "name,path,words,characters,outlinks_global,outlinks_local,inlinks_global,inlinks_local,broken_links,tags,created,modified,raw_words,raw_characters"
+        )?;
+
+        let index = index.borrow();
+
+        for env_stats in &self.filtered_stats {
+            let Some(note) = index.get(&env_stats.id) else {
+                continue;
+            };
+
+            writeln!(
+                writer,
+                "{},{},{},{},{},{},{},{},{},{},{},{},{},{}",
+                csv_field(&note.display_name),
+                csv_field(&note.path.to_string_lossy()),
+                note.words,
+                note.characters,
+                env_stats.outlinks_global,
+                env_stats.outlinks_local,
+                env_stats.inlinks_global,
+                env_stats.inlinks_local,
+                env_stats.broken_links,
+                csv_field(&note.tags.join(" ")),
+                note.created
+                    .map(|created| created.to_string())
+                    .unwrap_or_default(),
+                note.modified
+                    .map(|modified| modified.to_rfc3339())
+                    .unwrap_or_default(),
+                note.raw_words,
+                note.raw_characters,
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// Converts this environemnt to a table of rows with the (sorted) notes contained in it.
+    /// Only the notes within the given range of positions are converted, as large vaults have far more notes than fit on the screen.
+    /// Notes with ids in the given set of stale notes are flagged.
+    pub fn to_note_table(
+        &self,
+        index: data::NoteIndexContainer,
+        styles: &styles::UiStyles,
+        tag_styles: &styles::TagStyles,
+        stale: &HashSet<String>,
+        rows: std::ops::Range<usize>,
+    ) -> Table {
+        // Calculate widths
+        let notes_table_widths = if styles::screen_reader() {
+            vec![Constraint::Fill(1)]
+        } else {
+            vec![
+                Constraint::Min(25),
+                Constraint::Length(8),
+                Constraint::Length(8),
+                Constraint::Length(10),
+                Constraint::Length(10),
+                Constraint::Length(10),
+                Constraint::Length(10),
+            ]
+        };
+
+        // Construct rows, with the heat of each note relative to the most visited one
+        let max_visits = self
+            .filter
+            .visits
+            .values()
+            .max()
+            .copied()
+            .unwrap_or_default();
+        let notes_rows = self
+            .filtered_stats
+            .iter()
+            .skip(rows.start)
+            .take(rows.len())
+            .flat_map(|note_env| {
+                let heat = self
+                    .filter
+                    .visits
+                    .get(&note_env.id)
+                    .filter(|visits| **visits > 0)
+                    .map(|visits| {
+                        HEAT_SYMBOLS[(visits * HEAT_SYMBOLS.len()).div_ceil(max_visits) - 1]
+                    });
+                note_env.to_row(
+                    index.clone(),
+                    styles,
+                    tag_styles,
+                    heat,
+                    stale.contains(&note_env.id),
+                )
+            })
+            .collect::<Vec<Row>>();
+
+        Table::new(notes_rows, notes_table_widths).column_spacing(1)
+    }
+
+    /// Converts this environment statistics struct to a ratatui table with the basic, global stats.
+    pub fn to_global_stats_table(&self, styles: &styles::UiStyles) -> Table {
+        // Horizontal layout
+        let stats_widths = [
+            Constraint::Length(20),
+            Constraint::Length(16),
+            Constraint::Length(20),
+            Constraint::Length(16),
+            Constraint::Min(0),
+        ];
+
+        //  === Global stats ===
+
+        let global_stats_rows = [
+            Row::new(vec![
+                Cell::from(i18n::tr("stats-notes")).style(styles.text_style),
+                Cell::from(format!("{:7}", self.note_count_total)).style(styles.text_style),
+                Cell::from(i18n::tr("stats-words")).style(styles.text_style),
+                Cell::from(format!("{:7}", self.word_count_total)).style(styles.text_style),
+            ]),
+            Row::new(vec![
+                Cell::from(i18n::tr("stats-tags")).style(styles.text_style),
+                Cell::from(format!("{:7}", self.tag_count_total)).style(styles.text_style),
+                Cell::from(i18n::tr("stats-chars")).style(styles.text_style),
+                Cell::from(format!("{:7}", self.char_count_total)).style(styles.text_style),
+            ]),
+            Row::new(vec![
+                Cell::from(i18n::tr("stats-links")).style(styles.text_style),
+                Cell::from(format!("{:7}", self.local_local_links)).style(styles.text_style),
+                Cell::from(i18n::tr("stats-broken-links")).style(styles.text_style),
+                Cell::from(format!("{:7}", self.broken_links)).style(styles.text_style),
+            ]),
+        ];
+
+        Table::new(global_stats_rows, stats_widths).column_spacing(1)
+    }
+
+    /// Converts this environment statistics struct to a ratatui table with the full, local stats.
+    pub fn to_local_stats_table(&self, global: &Self, styles: &styles::UiStyles) -> Table {
+        // Horizontal layout
+        let stats_widths = [
+            Constraint::Length(20),
+            Constraint::Length(16),
+            Constraint::Length(20),
+            Constraint::Length(16),
+            Constraint::Min(0),
+        ];
+
+        //  === Local stats ===
+        let local_stats_rows = [
+            Row::new(vec![
+                Cell::from(i18n::tr("stats-notes")).style(styles.text_style),
+                Cell::from(format!(
+                    "{:7} ({:3}%)",
+                    self.note_count_total,
+                    self.note_count_total * 100 / global.note_count_total.max(1)
+                ))
+                .style(styles.text_style),
+                Cell::from(i18n::tr("stats-words")).style(styles.text_style),
+                Cell::from(format!(
+                    "{:7} ({:3}%)",
+                    self.word_count_total,
+                    self.word_count_total * 100 / global.word_count_total.max(1)
+                ))
+                .style(styles.text_style),
+            ]),
+            Row::new(vec![
+                Cell::from(i18n::tr("stats-tags")).style(styles.text_style),
+                Cell::from(format!(
+                    "{:7} ({:3}%)",
+                    self.tag_count_total,
+                    self.tag_count_total * 100 / global.tag_count_total.max(1)
+                ))
+                .style(styles.text_style),
+                Cell::from(i18n::tr("stats-chars")).style(styles.text_style),
+                Cell::from(format!(
+                    "{:7} ({:3}%)",
+                    self.char_count_total,
+                    self.char_count_total * 100 / global.char_count_total.max(1)
+                ))
+                .style(styles.text_style),
+            ]),
+            Row::new(vec![
+                Cell::from(i18n::tr("stats-incoming-links")).style(styles.text_style),
+                Cell::from(format!(
+                    "{:7} ({:3}%)",
+                    self.global_local_links,
+                    self.global_local_links * 100 / global.local_local_links.max(1),
+                ))
+                .style(styles.text_style),
+                Cell::from(i18n::tr("stats-outgoing-links")).style(styles.text_style),
+                Cell::from(format!(
+                    "{:7} ({:3}%)",
+                    self.local_global_links,
+                    self.local_global_links * 100 / global.local_local_links.max(1),
+                ))
+                .style(styles.text_style),
+            ]),
+            Row::new(vec![
+                Cell::from(i18n::tr("stats-internal-links")).style(styles.text_style),
+                Cell::from(format!(
+                    "{:7} ({:3}%)",
+                    self.local_local_links,
+                    self.local_local_links * 100 / global.local_local_links.max(1),
+                ))
+                .style(styles.text_style),
+                Cell::from(i18n::tr("stats-broken-links")).style(styles.text_style),
+                Cell::from(format!(
+                    "{:7} ({:3}%)",
+                    self.broken_links,
+                    self.broken_links * 100 / global.broken_links.max(1)
+                ))
+                .style(styles.text_style),
+            ]),
+        ];
+
+        Table::new(local_stats_rows, stats_widths).column_spacing(1)
+    }
+}
+
+/// Sorts the given values into buckets of exponentially growing size and counts the values in each.
+/// The buckets are `0`, `1`, `2-3`, `4-7` and so on, up to the bucket containing the largest value.
+fn histogram(values: impl Iterator<Item = usize>) -> Vec<(String, u64)> {
+    let mut counts: Vec<u64> = Vec::new();
+
+    for value in values {
+        // bucket 0 contains only 0, bucket n contains the values from 2^(n-1) to 2^n - 1
+        let bucket = (usize::BITS - value.leading_zeros()) as usize;
+        if counts.len() <= bucket {
+            counts.resize(bucket + 1, 0);
+        }
+        counts[bucket] += 1;
+    }
+
+    counts
+        .into_iter()
+        .enumerate()
+        .map(|(bucket, count)| {
+            let label = match bucket {
+                0 => String::from("0"),
+                1 => String::from("1"),
+                _ => format!("{}-{}", 1usize << (bucket - 1), (1usize << bucket) - 1),
+            };
+            (label, count)
+        })
+        .collect()
+}
+
+/// Quotes a field for use in a CSV file if it contains separators, quotes or line breaks.
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_owned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{data, io};
+
+    #[test]
+    fn test_env_stats_1_tags_any() {
+        let config = crate::Config::default();
+        let tracker = io::FileTracker::new(&config, std::path::PathBuf::from("./tests")).unwrap();
+        let builder = io::HtmlBuilder::new(&config, std::path::PathBuf::from("./tests"));
+        let index = data::NoteIndex::new(tracker, builder, Default::default()).0;
+
+        assert_eq!(index.inner.len(), 12);
+
+        let index = std::rc::Rc::new(std::cell::RefCell::new(index));
+
+        // === Filter 1 ===
+
+        let filter1 = data::Filter {
+            any: true,
+            tags: vec![
+                ("#topology".to_string(), true),
+                ("#diffgeo".to_string(), true),
+            ],
+            links: vec![],
+            blinks: vec![],
+            paths: vec![],
+            columns: vec![],
+            counts: vec![],
+            dates: vec![],
+            collections: vec![],
+            collection_members: Default::default(),
+            title: String::new(),
+            full_text: None,
+            content: vec![],
+            recent: vec![],
+            visits: Default::default(),
+            excluded_tags: vec![],
+            scope: vec![],
+            groups: vec![],
+            alternatives: vec![],
+        };
+
+        let env1 = EnvironmentStats::new_with_filter(&index, filter1);
+
+        assert_eq!(env1.note_count_total, 5);
+        assert_eq!(env1.tag_count_total, 3);
+        assert_eq!(env1.local_local_links, 10);
+        assert_eq!(env1.local_global_links, 11);
+        assert_eq!(env1.global_local_links, 13);
+        assert_eq!(env1.broken_links, 1);
+    }
+
+    #[test]
+    fn test_env_stats_2_tags_all() {
+        let config = crate::Config::default();
+        let tracker = io::FileTracker::new(&config, std::path::PathBuf::from("./tests")).unwrap();
+        let builder = io::HtmlBuilder::new(&config, std::path::PathBuf::from("./tests"));
+        let index = data::NoteIndex::new(tracker, builder, Default::default()).0;
+
+        assert_eq!(index.inner.len(), 12);
+
+        let index = std::rc::Rc::new(std::cell::RefCell::new(index));
+        // === Filter 2 ===
+
+        let filter2 = data::Filter {
+            any: false,
+            tags: vec![
+                ("#topology".to_string(), true),
+                ("#diffgeo".to_string(), true),
+            ],
+            links: vec![],
+            blinks: vec![],
+            paths: vec![],
+            columns: vec![],
+            counts: vec![],
+            dates: vec![],
+            collections: vec![],
+            collection_members: Default::default(),
+            title: String::new(),
+            full_text: None,
+            content: vec![],
+            recent: vec![],
+            visits: Default::default(),
+            excluded_tags: vec![],
+            scope: vec![],
+            groups: vec![],
+            alternatives: vec![],
+        };
+        let env2 = EnvironmentStats::new_with_filter(&index, filter2);
+
+        assert_eq!(env2.note_count_total, 2);
+        assert_eq!(env2.tag_count_total, 2);
+        assert_eq!(env2.local_local_links, 1);
+        assert_eq!(env2.local_global_links, 5);
+        assert_eq!(env2.global_local_links, 6);
+        assert_eq!(env2.broken_links, 1);
+
+        env2.filtered_stats
+            .iter()
+            .filter(|env_stats| env_stats.id == "manifold")
+            .for_each(|ma| {
+                assert_eq!(ma.inlinks_global, 5);
+                assert_eq!(ma.inlinks_local, 1);
+                assert_eq!(ma.outlinks_local, 0);
+                assert_eq!(ma.outlinks_global, 4);
+                assert_eq!(ma.broken_links, 0);
+            });
+    }
+
+    #[test]
+    fn test_env_stats_3_title() {
+        let config = crate::Config::default();
+        let tracker = io::FileTracker::new(&config, std::path::PathBuf::from("./tests")).unwrap();
+        let builder = io::HtmlBuilder::new(&config, std::path::PathBuf::from("./tests"));
+        let index = data::NoteIndex::new(tracker, builder, Default::default()).0;
+
+        assert_eq!(index.inner.len(), 12);
+
+        let index = std::rc::Rc::new(std::cell::RefCell::new(index));
+
+        // === Filter 3 ===
+
+        let filter3 = data::Filter {
+            any: false,
+            tags: vec![],
+            links: vec![],
+            blinks: vec![],
+            paths: vec![],
+            columns: vec![],
+            counts: vec![],
+            dates: vec![],
+            collections: vec![],
+            collection_members: Default::default(),
+            title: "operating".to_string(),
+            full_text: None,
+            content: vec![],
+            recent: vec![],
+            visits: Default::default(),
+            excluded_tags: vec![],
+            scope: vec![],
+            groups: vec![],
+            alternatives: vec![],
+        };
+        let env3 = EnvironmentStats::new_with_filter(&index, filter3);
+
+        assert_eq!(env3.note_count_total, 1);
+        assert_eq!(env3.tag_count_total, 1);
+        assert_eq!(env3.local_local_links, 0);
+        assert_eq!(env3.local_global_links, 6);
+        assert_eq!(env3.global_local_links, 0);
+        assert_eq!(env3.broken_links, 0);
+    }
+
+    #[test]
+    fn test_env_stats_4_blinks() {
+        let config = crate::Config::default();
+        let tracker = io::FileTracker::new(&config, std::path::PathBuf::from("./tests")).unwrap();
+        let builder = io::HtmlBuilder::new(&config, std::path::PathBuf::from("./tests"));
+        let index = data::NoteIndex::new(tracker, builder, Default::default()).0;
+
+        assert_eq!(index.inner.len(), 12);
+
+        let index = std::rc::Rc::new(std::cell::RefCell::new(index));
+
+        // === Filter 4 ===
+
+        let filter4 = data::Filter {
+            any: true,
+            tags: vec![],
+            links: vec![],
+            blinks: vec![("atlas".to_string(), true)],
+            paths: vec![],
+            columns: vec![],
+            counts: vec![],
+            dates: vec![],
+            collections: vec![],
+            collection_members: Default::default(),
+            title: String::new(),
+            full_text: None,
+            content: vec![],
+            recent: vec![],
+            visits: Default::default(),
+            excluded_tags: vec![],
+            scope: vec![],
+            groups: vec![],
+            alternatives: vec![],
+        };
+        let env4 = EnvironmentStats::new_with_filter(&index, filter4);
+
+        assert_eq!(env4.note_count_total, 3);
+        assert_eq!(env4.tag_count_total, 2);
+        assert_eq!(env4.local_local_links, 2);
+        assert_eq!(env4.local_global_links, 5);
+        assert_eq!(env4.global_local_links, 9);
+        assert_eq!(env4.broken_links, 1);
+    }
+
+    #[test]
+    fn test_env_stats_5_links_blinks() {
+        let config = crate::Config::default();
+        let tracker = io::FileTracker::new(&config, std::path::PathBuf::from("./tests")).unwrap();
+        let builder = io::HtmlBuilder::new(&config, std::path::PathBuf::from("./tests"));
+        let index = data::NoteIndex::new(tracker, builder, Default::default()).0;
+
+        assert_eq!(index.inner.len(), 12);
+
+        let index = std::rc::Rc::new(std::cell::RefCell::new(index));
+
+        // === Filter 5 ===
+
+        let filter5 = data::Filter {
+            any: true,
+            tags: vec![],
+            links: vec![("smooth-map".to_string(), true)],
+            blinks: vec![("atlas".to_string(), true)],
+            paths: vec![],
+            columns: vec![],
+            counts: vec![],
+            dates: vec![],
+            collections: vec![],
+            collection_members: Default::default(),
+            title: String::new(),
+            full_text: None,
+            content: vec![],
+            recent: vec![],
+            visits: Default::default(),
+            excluded_tags: vec![],
+            scope: vec![],
+            groups: vec![],
+            alternatives: vec![],
+        };
+        let env5 = EnvironmentStats::new_with_filter(&index, filter5);
+
+        assert_eq!(env5.note_count_total, 4);
+        assert_eq!(env5.tag_count_total, 3);
+        assert_eq!(env5.local_local_links, 5);
+        assert_eq!(env5.local_global_links, 8);
+        assert_eq!(env5.global_local_links, 10);
+        assert_eq!(env5.broken_links, 1);
+    }
+
+    /// Describes the totals and the stats of all notes of the given environment, independent of their order.
+    fn describe(env: &EnvironmentStats) -> (Vec<usize>, Vec<String>) {
+        let mut notes = env
+            .filtered_stats
+            .iter()
+            .map(|env_stats| format!("{:?}", env_stats))
+            .collect::<Vec<_>>();
+        notes.sort();
+        (
+            vec![
+                env.word_count_total,
+                env.char_count_total,
+                env.note_count_total,
+                env.tag_count_total,
+                env.local_local_links,
+                env.local_global_links,
+                env.global_local_links,
+                env.broken_links,
+            ],
+            notes,
+        )
+    }
+
+    #[test]
+    fn test_env_stats_6_incremental() {
+        let config = crate::Config::default();
+        let tracker = io::FileTracker::new(&config, std::path::PathBuf::from("./tests")).unwrap();
+        let builder = io::HtmlBuilder::new(&config, std::path::PathBuf::from("./tests"));
+        let index = data::NoteIndex::new(tracker, builder, Default::default()).0;
+
+        let index = std::rc::Rc::new(std::cell::RefCell::new(index));
+
+        // refiltering results in the same stats as filtering from scratch
+        let mut env = EnvironmentStats::new_with_filter(&index, data::Filter::default());
+        for filter_string in [
+            "#topology",
+            "#topology #diffgeo",
+            "!#topology",
+            "m",
+            "ma",
+            "man",
+            "",
+            ">atlas",
+            "<manifold",
+            "<manifold #lietheo",
+            // refinements only evaluate the notes within the environment
+            "<manifold #lietheo lie",
+            "<manifold #lietheo lie group",
+            // and returning to earlier filters uses their cached matches
+            "<manifold",
+            "ma",
+            "#topology #diffgeo",
+        ] {
+            let filter = data::Filter::new(filter_string, false);
+            env.refilter(&index, filter.clone());
+            assert_eq!(
+                describe(&env),
+                describe(&EnvironmentStats::new_with_filter(&index, filter)),
+                "{}",
+                filter_string
+            );
+        }
+
+        // so does updating the notes touched by changes of the index
+        let filter = data::Filter::new("<manifold", false);
+        let mut env =
+            EnvironmentStats::new_with_filter(&index, data::Filter::new("#diffgeo", false));
+        env.refilter(&index, filter.clone());
+        for (name, content) in [
+            // links from a note within the environment to one outside of it
+            (
+                "Chart",
+                "#topology\nSee [[Manifold]], [[Atlas]] and [[Books]].",
+            ),
+            // a note fixing a broken link, linked from the environment
+            (
+                "Diffeomorphism",
+                "Not linked from [[Manifold]], but to [[Chart]].",
+            ),
+            // a note leaving the environment
+            ("Atlas", "Links to nothing."),
+        ] {
+            let note = data::Note::from_content(
+                std::path::Path::new(&format!("{}.md", name)),
+                content,
+                &Default::default(),
+            )
+            .unwrap();
+            index.borrow_mut().insert(data::name_to_id(name), note);
+            let touched = index.borrow_mut().take_touched();
+            env.notes_changed(&index, &touched);
+            assert_eq!(
+                describe(&env),
+                describe(&EnvironmentStats::new_with_filter(&index, filter.clone())),
+                "{}",
+                name
+            );
+        }
+
+        // cached matches are forgotten once notes change, here the chart loses its tag
+        let filter = data::Filter::new("#diffgeo", false);
+        env.refilter(&index, filter.clone());
+        assert_eq!(
+            describe(&env),
+            describe(&EnvironmentStats::new_with_filter(&index, filter))
+        );
+    }
+
+    #[test]
+    fn test_env_stats_7_streaming() {
+        let config = crate::Config::default();
+        let tracker = io::FileTracker::new(&config, std::path::PathBuf::from("./tests")).unwrap();
+        let builder = io::HtmlBuilder::new(&config, std::path::PathBuf::from("./tests"));
+        let index = data::NoteIndex::new(tracker, builder, Default::default()).0;
+
+        let index = std::rc::Rc::new(std::cell::RefCell::new(index));
+
+        let mut env = EnvironmentStats::new_with_filter(&index, data::Filter::default());
+        for (filter_string, any) in [
+            ("#topology | manifold", false),
+            ("#topology | manifolds", false),
+            ("#topology chart | manifold", true),
+            ("| zzz", false),
+        ] {
+            let filter = data::Filter::new(filter_string, any);
+            let pending = env.refilter_streaming(&index, filter.clone());
+
+            // notes only known to match after searching their full text are added once found
+            let found = pending
+                .iter()
+                .filter(|(_id, path)| {
+                    std::fs::read_to_string(path)
+                        .unwrap()
+                        .to_lowercase()
+                        .contains(filter.full_text.as_ref().unwrap())
+                })
+                .map(|(id, _path)| id.clone())
+                .collect::<Vec<_>>();
+            env.text_found(&index, &found);
+            env.search_finished();
+
+            assert_eq!(
+                describe(&env),
+                describe(&EnvironmentStats::new_with_filter(&index, filter)),
+                "{}",
+                filter_string
+            );
+        }
+
+        // the matches of a finished search are cached
+        let filter = data::Filter::new("#topology | manifold", false);
+        assert!(env.refilter_streaming(&index, filter.clone()).is_empty());
+        assert_eq!(
+            describe(&env),
+            describe(&EnvironmentStats::new_with_filter(&index, filter))
+        );
+    }
+
+    #[test]
+    fn test_csv_export() {
+        let config = crate::Config::default();
+        let tracker = io::FileTracker::new(&config, std::path::PathBuf::from("./tests")).unwrap();
+        let builder = io::HtmlBuilder::new(&config, std::path::PathBuf::from("./tests"));
+        let index = data::NoteIndex::new(tracker, builder, Default::default()).0;
+
+        let index = std::rc::Rc::new(std::cell::RefCell::new(index));
+
+        let mut env = EnvironmentStats::new_with_filter(&index, data::Filter::default());
+        env.sort(index.clone(), SortingMode::Name, true, &Default::default());
+
+        let mut csv = Vec::new();
+        env.to_csv(index.clone(), &mut csv).unwrap();
+        let csv = String::from_utf8(csv).unwrap();
+
+        let lines = csv.lines().collect::<Vec<_>>();
+
+        // header + one line per note
+        assert_eq!(lines.len(), 13);
+        assert!(lines[0].starts_with("name,path,words,characters,"));
+        assert!(lines.iter().all(|line| line.split(',').count() >= 12));
+
+        let atlas = lines
+            .iter()
+            .find(|line| line.starts_with("Atlas,"))
+            .unwrap();
+        assert!(atlas.contains(",#lietheo #diffgeo,"));
+    }
+
+    #[test]
+    fn test_note_table() {
+        let config = crate::Config::default();
+        let tracker = io::FileTracker::new(&config, std::path::PathBuf::from("./tests")).unwrap();
+        let builder = io::HtmlBuilder::new(&config, std::path::PathBuf::from("./tests"));
+        let index = data::NoteIndex::new(tracker, builder, Default::default()).0;
+
+        let index = std::rc::Rc::new(std::cell::RefCell::new(index));
+
+        let mut env = EnvironmentStats::new_with_filter(&index, data::Filter::default());
+        env.sort(index.clone(), SortingMode::Name, true, &Default::default());
+
+        // only the rows in the range are part of the table, starting with the note at its first position
+        let table = env.to_note_table(
+            index.clone(),
+            &styles::UiStyles::default(),
+            &Default::default(),
+            &Default::default(),
+            10..20,
+        );
+        let area = Rect::new(0, 0, 100, 5);
+        let mut buf = Buffer::empty(area);
+        Widget::render(table, area, &mut buf);
+
+        let lines = (0..area.height)
+            .map(|y| {
+                (0..area.width)
+                    .map(|x| buf[(x, y)].symbol())
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>();
+        let names = [10, 11].map(|position| {
+            index
+                .borrow()
+                .get(&env.get_selected(position).unwrap().id)
+                .unwrap()
+                .display_name
+                .clone()
+        });
+        assert!(lines[0].starts_with(&names[0]));
+        assert!(lines[1].starts_with(&names[1]));
+        // there are only 12 notes
+        assert!(lines[2].trim().is_empty());
+    }
+
+    #[test]
+    fn test_visits() {
+        let config = crate::Config::default();
+        let tracker = io::FileTracker::new(&config, std::path::PathBuf::from("./tests")).unwrap();
+        let builder = io::HtmlBuilder::new(&config, std::path::PathBuf::from("./tests"));
+        let index = data::NoteIndex::new(tracker, builder, Default::default()).0;
+
+        let index = std::rc::Rc::new(std::cell::RefCell::new(index));
+
+        let filter = data::Filter {
+            visits: HashMap::from([(String::from("linux"), 1), (String::from("windows"), 4)]),
+            ..Default::default()
+        };
+        let mut env = EnvironmentStats::new_with_filter(&index, filter);
+        env.sort(
+            index.clone(),
+            SortingMode::Visits,
+            false,
+            &Default::default(),
+        );
+
+        // the most visited notes come first, with their heat and whether they are stale after their titles
+        assert_eq!(env.get_selected(0).unwrap().id, "windows");
+        assert_eq!(env.get_selected(1).unwrap().id, "linux");
+
+        let table = env.to_note_table(
+            index.clone(),
+            &styles::UiStyles::default(),
+            &Default::default(),
+            &HashSet::from([String::from("linux")]),
+            0..3,
+        );
+        let area = Rect::new(0, 0, 100, 3);
+        let mut buf = Buffer::empty(area);
+        Widget::render(table, area, &mut buf);
+
+        let lines = (0..area.height)
+            .map(|y| {
+                (0..area.width)
+                    .map(|x| buf[(x, y)].symbol())
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>();
+        assert!(lines[0].starts_with("Windows █ "));
+        assert!(lines[1].starts_with(&format!("Linux ▂ {} ", i18n::tr("select-stale"))));
+        assert!(!HEAT_SYMBOLS.iter().any(|heat| lines[2].contains(heat)));
+    }
+
+    #[test]
+    fn test_histogram() {
+        assert!(histogram(std::iter::empty()).is_empty());
+
+        assert_eq!(
+            histogram([0, 1, 2, 3, 3, 9].into_iter()),
+            vec![
+                (String::from("0"), 1),
+                (String::from("1"), 1),
+                (String::from("2-3"), 3),
+                (String::from("4-7"), 0),
+                (String::from("8-15"), 1),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_csv_field() {
+        assert_eq!(csv_field("Lie Group"), "Lie Group");
+        assert_eq!(csv_field("Groups, Rings"), "\"Groups, Rings\"");
+        assert_eq!(csv_field("The \"best\" note"), "\"The \"\"best\"\" note\"");
+    }
+}
@@ -0,0 +1,243 @@
+use std::collections::HashMap;
+
+use itertools::Itertools;
+
+use super::{Note, NoteIndex};
+
+/// A person mentioned in the vault with `@name`, or described by a person note.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Person {
+    /// The id of the person note, or of the mentioned name if there is none.
+    pub id: String,
+    /// The title of the person note, or the name as mentioned if there is none.
+    pub name: String,
+    /// Wether the person has a person note (`type: person` in the frontmatter).
+    pub has_note: bool,
+    /// The ids under which the person is mentioned: The id, title and aliases of the person note, or the mentioned name.
+    pub names: Vec<String>,
+    /// The ids of the notes mentioning the person, sorted.
+    pub mentioned_in: Vec<String>,
+}
+
+/// A line of a note in which a person is mentioned.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MentionOccurrence {
+    /// The id of the note.
+    pub id: String,
+    /// The number of the line within the note file, starting at 1.
+    pub line: usize,
+    /// The content of the line, without surrounding whitespace.
+    pub context: String,
+}
+
+/// Returns the ids of the names mentioned in the given text as `@name`.
+/// Names consist of letters, digits, dashes and underscores, which stand for spaces: `@Jane_Doe` mentions 'Jane Doe'.
+/// An `@` within a word, as in mail addresses, does not start a mention.
+pub(super) fn mentions(text: &str) -> Vec<String> {
+    text.split_whitespace()
+        .filter_map(|word| {
+            word.trim_start_matches(['(', '[', '"', '\''])
+                .strip_prefix('@')
+        })
+        .map(|rest| {
+            rest.chars()
+                .take_while(|c| c.is_alphanumeric() || *c == '-' || *c == '_')
+                .collect::<String>()
+        })
+        .map(|name| name.trim_end_matches(['-', '_']).replace('_', " "))
+        .filter(|name| !name.is_empty())
+        .map(|name| super::name_to_id(&name))
+        .collect()
+}
+
+/// Returns the ids a person note can be mentioned by: Its own id and the ids of its title and aliases.
+pub(super) fn person_names(id: &str, note: &Note) -> Vec<String> {
+    std::iter::once(id.to_owned())
+        .chain(std::iter::once(super::name_to_id(&note.display_name)))
+        .chain(note.aliases.iter().map(|alias| super::name_to_id(alias)))
+        .filter(|name| !name.is_empty())
+        .unique()
+        .collect()
+}
+
+/// Returns the ids of all person notes of the given notes by the ids they can be mentioned by.
+pub(super) fn person_ids(notes: &HashMap<String, Note>) -> HashMap<String, String> {
+    notes
+        .iter()
+        .filter(|(_id, note)| note.person)
+        .flat_map(|(id, note)| {
+            person_names(id, note)
+                .into_iter()
+                .map(move |name| (name, id.clone()))
+        })
+        .collect()
+}
+
+/// Returns all people of the given index: The person notes and the names mentioned without one, sorted by name.
+pub fn people(index: &NoteIndex) -> Vec<Person> {
+    let persons = person_ids(&index.inner);
+
+    let mut people = index
+        .inner
+        .iter()
+        .filter(|(_id, note)| note.person)
+        .map(|(id, note)| {
+            (
+                id.clone(),
+                Person {
+                    id: id.clone(),
+                    name: note.display_name.clone(),
+                    has_note: true,
+                    names: person_names(id, note),
+                    mentioned_in: Vec::new(),
+                },
+            )
+        })
+        .collect::<HashMap<_, _>>();
+
+    for (id, note) in &index.inner {
+        for mention in note.mentions.iter().unique() {
+            let person = persons.get(mention).unwrap_or(mention);
+            people
+                .entry(person.clone())
+                .or_insert_with(|| Person {
+                    id: mention.clone(),
+                    name: mention.clone(),
+                    has_note: false,
+                    names: vec![mention.clone()],
+                    mentioned_in: Vec::new(),
+                })
+                .mentioned_in
+                .push(id.clone());
+        }
+    }
+
+    people
+        .into_values()
+        .map(|mut person| {
+            person.mentioned_in.sort();
+            person.mentioned_in.dedup();
+            person
+        })
+        .sorted_by(|a, b| {
+            a.name
+                .to_lowercase()
+                .cmp(&b.name.to_lowercase())
+                .then(a.id.cmp(&b.id))
+        })
+        .collect()
+}
+
+/// Finds all lines in which the given person is mentioned within the notes mentioning them, reading the notes from the file system.
+/// The occurrences are sorted by the name of their note and their line.
+pub fn mention_occurrences(index: &NoteIndex, person: &Person) -> Vec<MentionOccurrence> {
+    person
+        .mentioned_in
+        .iter()
+        .flat_map(|id| index.get(id).map(|note| (id, note)))
+        .sorted_by_key(|(_id, note)| note.name.to_lowercase())
+        .flat_map(|(id, note)| {
+            let content = std::fs::read_to_string(&note.path).unwrap_or_default();
+            find_mentions(&content, &person.names)
+                .into_iter()
+                .map(|(line, context)| MentionOccurrence {
+                    id: id.clone(),
+                    line,
+                    context,
+                })
+                .collect_vec()
+        })
+        .collect()
+}
+
+/// Finds the lines of the given content of a note that mention one of the given names, outside of code blocks.
+/// Returns the number of each line, starting at 1, and the trimmed line.
+pub fn find_mentions(content: &str, names: &[String]) -> Vec<(usize, String)> {
+    let mut in_code = false;
+    content
+        .lines()
+        .enumerate()
+        .filter(|(_index, line)| {
+            if line.trim_start().starts_with("```") || line.trim_start().starts_with("~~~") {
+                in_code = !in_code;
+                return false;
+            }
+            !in_code && mentions(line).iter().any(|mention| names.contains(mention))
+        })
+        .map(|(index, line)| (index + 1, line.trim().to_owned()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mentions() {
+        assert_eq!(
+            mentions("Met @Jane_Doe and (@bob), mail jane@example.com, @-- @Ünal."),
+            vec!["jane-doe", "bob", "ünal"]
+        );
+    }
+
+    #[test]
+    fn test_find_mentions() {
+        let content = "# Standup\n@jane-doe presented.\n```\n@jane-doe in code\n```\nAsked @Jane_Doe and @bob.\nNo mention of jane.\n";
+        assert_eq!(
+            find_mentions(content, &[String::from("jane-doe")]),
+            vec![
+                (2, String::from("@jane-doe presented.")),
+                (6, String::from("Asked @Jane_Doe and @bob.")),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_people() {
+        let vault = testdir::testdir!();
+        std::fs::write(
+            vault.join("Jane Doe.md"),
+            "---\ntype: person\naliases: [Jane]\n---\n# Jane Doe\nWorks with @bob.\n",
+        )
+        .unwrap();
+        std::fs::write(
+            vault.join("Standup.md"),
+            "# Standup\n@jane presented, @Jane_Doe asked @bob.\n",
+        )
+        .unwrap();
+        let config = crate::Config::default();
+        let index = NoteIndex::new(
+            crate::io::FileTracker::new(&config, vault.clone()).unwrap(),
+            crate::io::HtmlBuilder::new(&config, vault.clone()),
+            Default::default(),
+        )
+        .0;
+
+        // mentions of person notes count as links to them
+        assert_eq!(index.get("standup").unwrap().links, vec!["jane-doe"]);
+        assert_eq!(index.backlinks("jane-doe"), ["standup"]);
+
+        let people = people(&index);
+        assert_eq!(
+            people
+                .iter()
+                .map(|person| (
+                    person.name.as_str(),
+                    person.has_note,
+                    person.mentioned_in.len()
+                ))
+                .collect_vec(),
+            vec![("bob", false, 2), ("Jane Doe", true, 1)]
+        );
+
+        let occurrences = mention_occurrences(&index, &people[1]);
+        assert_eq!(
+            occurrences,
+            vec![MentionOccurrence {
+                id: String::from("standup"),
+                line: 2,
+                context: String::from("@jane presented, @Jane_Doe asked @bob."),
+            }]
+        );
+    }
+}
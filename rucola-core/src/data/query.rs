@@ -0,0 +1,383 @@
+use std::{borrow::Cow, collections::HashMap};
+
+use itertools::Itertools;
+
+/// What the results of a query are sorted by.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum QuerySort {
+    /// The display name of the notes.
+    Name,
+    /// The date of the notes: Their creation date if known, their last modification otherwise.
+    Created,
+    /// The time of the last modification of the note files.
+    Modified,
+    /// The number of words.
+    Words,
+    /// The number of characters.
+    Chars,
+    /// The number of links to other notes.
+    Links,
+    /// The number of links from other notes.
+    Backlinks,
+    /// A column of the data tables of the notes, by the value in their first row.
+    Column(String),
+}
+
+impl QuerySort {
+    /// Parses the name of a sort key, a leading dot denoting a column of data tables.
+    fn parse(key: &str) -> Option<Self> {
+        if let Some(column) = key.strip_prefix('.') {
+            return Some(Self::Column(column.to_owned()));
+        }
+
+        Some(match key.to_lowercase().as_str() {
+            "name" | "title" => Self::Name,
+            "created" | "date" => Self::Created,
+            "modified" => Self::Modified,
+            "words" => Self::Words,
+            "chars" | "characters" => Self::Chars,
+            "links" => Self::Links,
+            "backlinks" => Self::Backlinks,
+            _ => return None,
+        })
+    }
+
+    /// Wether results are sorted in ascending order unless specified otherwise.
+    /// Names and columns are, while the newest and largest notes come first.
+    fn ascending_by_default(&self) -> bool {
+        matches!(self, Self::Name | Self::Column(_))
+    }
+}
+
+/// A query embedded in a note as a fenced code block, like
+/// ````markdown
+/// ```rucola query tag:#book sort:modified
+/// ```
+/// ````
+/// Its results are inserted in place of the block when the note is exported.
+///
+/// Apart from the options `tag:`, `sort:`, `limit:` and `format:`, a query is written like a filter of the select screen.
+#[derive(Debug, Clone)]
+pub struct Query {
+    /// The filter notes have to match.
+    filter: super::Filter,
+    /// What the results are sorted by and wether ascending.
+    sort: (QuerySort, bool),
+    /// The maximum number of results, if any.
+    limit: Option<usize>,
+    /// Wether the results are shown as a table instead of a list.
+    table: bool,
+}
+
+impl Query {
+    /// Parses a query from its words:
+    ///  - `tag:[tag]` is a shorthand for `#[tag]`, `!tag:[tag]` for `!#[tag]`.
+    ///  - `sort:[key]` sorts by `name`, `created`, `modified`, `words`, `chars`, `links`, `backlinks` or a column like `.price`, optionally followed by `:asc` or `:desc`.
+    ///  - `limit:[n]` shows only the first n results.
+    ///  - `format:table` shows the results as a table instead of a list.
+    ///  - Everything else forms the filter.
+    pub fn parse(query: &str) -> Self {
+        let mut filter = Vec::new();
+        let mut sort = (QuerySort::Name, true);
+        let mut limit = None;
+        let mut table = false;
+
+        for word in query.split_whitespace() {
+            if let Some(tag) = word.strip_prefix("tag:") {
+                filter.push(format!("#{}", tag.trim_start_matches('#')));
+            } else if let Some(tag) = word
+                .strip_prefix(['!', '-'])
+                .and_then(|rest| rest.strip_prefix("tag:"))
+            {
+                filter.push(format!("!#{}", tag.trim_start_matches('#')));
+            } else if let Some(key) = word.strip_prefix("sort:") {
+                let (key, order) = key
+                    .rsplit_once(':')
+                    .filter(|(_key, order)| matches!(*order, "asc" | "desc"))
+                    .unwrap_or((key, ""));
+                if let Some(key) = QuerySort::parse(key) {
+                    let ascending = match order {
+                        "asc" => true,
+                        "desc" => false,
+                        _ => key.ascending_by_default(),
+                    };
+                    sort = (key, ascending);
+                }
+            } else if let Some(n) = word.strip_prefix("limit:") {
+                limit = n.parse().ok();
+            } else if let Some(format) = word.strip_prefix("format:") {
+                table = format == "table";
+            } else {
+                filter.push(word.to_owned());
+            }
+        }
+
+        Self {
+            filter: super::Filter::new(&filter.join(" "), false),
+            sort,
+            limit,
+            table,
+        }
+    }
+
+    /// Returns all notes of the given index matching this query, except the one with the given id, in order.
+    pub fn results<'a>(&self, index: &'a super::NoteIndex, exclude: &str) -> Vec<&'a super::Note> {
+        let notes = index
+            .inner
+            .iter()
+            .filter(|(id, _note)| *id != exclude)
+            .filter(|(_id, note)| self.filter.apply(note, index).is_some())
+            .map(|(_id, note)| note);
+
+        let (key, ascending) = &self.sort;
+        let backlinks = match key {
+            QuerySort::Backlinks => index
+                .inner
+                .values()
+                .flat_map(|note| note.links.iter().unique())
+                .counts(),
+            _ => HashMap::new(),
+        };
+
+        let sorted = notes.sorted_by(|a, b| {
+            let ordering = match key {
+                QuerySort::Name => a
+                    .display_name
+                    .to_lowercase()
+                    .cmp(&b.display_name.to_lowercase()),
+                QuerySort::Created => a.date().cmp(&b.date()),
+                QuerySort::Modified => a.modified.cmp(&b.modified),
+                QuerySort::Words => a.words.cmp(&b.words),
+                QuerySort::Chars => a.characters.cmp(&b.characters),
+                QuerySort::Links => a.links.len().cmp(&b.links.len()),
+                QuerySort::Backlinks => {
+                    let count = |note: &super::Note| {
+                        backlinks
+                            .get(&super::name_to_id(&note.name))
+                            .copied()
+                            .unwrap_or_default()
+                    };
+                    count(a).cmp(&count(b))
+                }
+                QuerySort::Column(column) => {
+                    let cell = |note: &super::Note| {
+                        note.data.as_ref().and_then(|data| {
+                            let column = data.column(column)?;
+                            data.rows.first().map(|row| row[column].clone())
+                        })
+                    };
+                    // notes without the column come last either way
+                    match (cell(a), cell(b)) {
+                        (Some(a), Some(b)) => super::table::compare_cells(&a, &b),
+                        (None, None) => std::cmp::Ordering::Equal,
+                        (a, b) => return b.is_some().cmp(&a.is_some()),
+                    }
+                }
+            }
+            // ties are broken by name
+            .then_with(|| a.name.cmp(&b.name));
+
+            if *ascending {
+                ordering
+            } else {
+                ordering.reverse()
+            }
+        });
+
+        sorted.take(self.limit.unwrap_or(usize::MAX)).collect()
+    }
+
+    /// Returns the results of this query as markdown: A list of links to the matching notes or a table of their stats.
+    pub fn to_markdown(&self, index: &super::NoteIndex, exclude: &str) -> String {
+        let results = self.results(index, exclude);
+
+        if results.is_empty() {
+            return String::from("*No matching notes.*");
+        }
+
+        if self.table {
+            super::DataTable {
+                columns: ["Note", "Date", "Words", "Tags"].map(String::from).to_vec(),
+                rows: results
+                    .iter()
+                    .map(|note| {
+                        vec![
+                            link(note),
+                            note.date()
+                                .map(|date| date.format("%Y-%m-%d").to_string())
+                                .unwrap_or_default(),
+                            note.words.to_string(),
+                            note.tags.iter().unique().join(" "),
+                        ]
+                    })
+                    .collect(),
+                file: None,
+            }
+            .to_markdown(None)
+            .join("\n")
+        } else {
+            results
+                .iter()
+                .map(|note| format!("- {}", link(note)))
+                .join("\n")
+        }
+    }
+}
+
+/// Returns a wiki link to the given note, titled with its display name.
+pub(super) fn link(note: &super::Note) -> String {
+    if note.display_name == note.name {
+        format!("[[{}]]", note.name)
+    } else {
+        format!("[[{}|{}]]", note.name, note.display_name)
+    }
+}
+
+/// Replaces all query blocks (```` ```rucola query ````) in the given content of the note with the given id by their results in the given index.
+/// The query is written after `rucola query` in the opening line of the block and may continue within the block.
+/// Returns the content unchanged if it contains no queries.
+pub fn expand_queries<'a>(
+    content: &'a str,
+    index: &super::NoteIndex,
+    note_id: &str,
+) -> Cow<'a, str> {
+    let mut lines = Vec::new();
+    let mut found = false;
+    // the fence of the current code block and, for queries, their text so far
+    let mut block: Option<(String, Option<String>)> = None;
+
+    for line in content.lines() {
+        let trimmed = line.trim_start();
+
+        match &mut block {
+            None => {
+                let fence = trimmed
+                    .chars()
+                    .take_while(|c| *c == '`' || *c == '~')
+                    .collect::<String>();
+                if fence.len() < 3 || fence.chars().unique().count() > 1 {
+                    lines.push(Cow::Borrowed(line));
+                    continue;
+                }
+
+                let mut info = trimmed[fence.len()..].split_whitespace();
+                let query = (info.next() == Some("rucola") && info.next() == Some("query"))
+                    .then(|| info.join(" "));
+                if query.is_none() {
+                    lines.push(Cow::Borrowed(line));
+                }
+                block = Some((fence, query));
+            }
+            Some((fence, query)) => {
+                // a closing fence is at least as long as the opening one and made of the same character
+                let closing = trimmed.trim_end();
+                if closing.len() >= fence.len()
+                    && closing.starts_with(fence.as_str())
+                    && closing.chars().all(|c| fence.starts_with(c))
+                {
+                    if let Some(query) = query {
+                        found = true;
+                        lines.push(Cow::Owned(Query::parse(query).to_markdown(index, note_id)));
+                    } else {
+                        lines.push(Cow::Borrowed(line));
+                    }
+                    block = None;
+                } else if let Some(query) = query {
+                    query.push(' ');
+                    query.push_str(line);
+                } else {
+                    lines.push(Cow::Borrowed(line));
+                }
+            }
+        }
+    }
+
+    if found {
+        Cow::Owned(lines.join("\n") + "\n")
+    } else {
+        Cow::Borrowed(content)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{data, io};
+
+    #[test]
+    fn test_queries() {
+        let config = crate::Config::default();
+        let tracker = io::FileTracker::new(&config, std::path::PathBuf::from("./tests")).unwrap();
+        let builder = io::HtmlBuilder::new(&config, std::path::PathBuf::from("./tests"));
+        let index = data::NoteIndex::new(tracker, builder, Default::default()).0;
+
+        let names = |query: &str, exclude: &str| {
+            Query::parse(query)
+                .results(&index, exclude)
+                .iter()
+                .map(|note| note.name.clone())
+                .collect_vec()
+        };
+
+        assert_eq!(
+            names("tag:#os", ""),
+            vec!["Linux", "Operating Systems", "OSX", "Windows"]
+        );
+        assert_eq!(
+            names("tag:os sort:name:desc", ""),
+            vec!["Windows", "OSX", "Operating Systems", "Linux"]
+        );
+        assert_eq!(names("tag:os limit:1", ""), vec!["Linux"]);
+        assert_eq!(
+            names("tag:os -tag:os/win limit:1 sort:name:desc", ""),
+            vec!["OSX"]
+        );
+        // the note containing the query is left out
+        assert_eq!(
+            names("tag:os", "linux"),
+            vec!["Operating Systems", "OSX", "Windows"]
+        );
+        // other words form a filter
+        assert_eq!(
+            names("#os !#os/win", ""),
+            vec!["Linux", "Operating Systems", "OSX"]
+        );
+
+        let words = Query::parse("tag:os sort:words").results(&index, "");
+        assert!(words.windows(2).all(|pair| pair[0].words >= pair[1].words));
+
+        assert_eq!(
+            Query::parse("tag:os !#os/win").to_markdown(&index, ""),
+            "- [[Linux]]\n- [[Operating Systems]]\n- [[OSX]]"
+        );
+        assert_eq!(
+            Query::parse("tag:nothing").to_markdown(&index, ""),
+            "*No matching notes.*"
+        );
+        let table = Query::parse("tag:os format:table").to_markdown(&index, "");
+        assert!(table.starts_with(
+            "| Note | Date | Words | Tags |\n| --- | --- | --- | --- |\n| [[Linux]] |"
+        ));
+    }
+
+    #[test]
+    fn test_expand_queries() {
+        let config = crate::Config::default();
+        let tracker = io::FileTracker::new(&config, std::path::PathBuf::from("./tests")).unwrap();
+        let builder = io::HtmlBuilder::new(&config, std::path::PathBuf::from("./tests"));
+        let index = data::NoteIndex::new(tracker, builder, Default::default()).0;
+
+        let content = "# Systems\n```rucola query tag:#os\n!#os/win\n```\nText\n````\n```rucola query tag:#os\n```\n````\n";
+        assert_eq!(
+            expand_queries(content, &index, "systems"),
+            "# Systems\n- [[Linux]]\n- [[Operating Systems]]\n- [[OSX]]\nText\n````\n```rucola query tag:#os\n```\n````\n"
+        );
+
+        // content without queries is left untouched
+        let content = "# Code\n```rust\nlet x = 1;\n```";
+        assert!(matches!(
+            expand_queries(content, &index, "code"),
+            Cow::Borrowed(_)
+        ));
+    }
+}
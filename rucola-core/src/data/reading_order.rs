@@ -0,0 +1,187 @@
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+use itertools::Itertools;
+
+use super::NoteIndex;
+
+/// An order to read notes in, so every note comes after the notes it depends on.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ReadingOrder {
+    /// The ids of the notes in the order to read them.
+    pub order: Vec<String>,
+    /// Notes depending on each other in a cycle, as ids of notes each depending on the next and the last depending on the first.
+    /// Notes within a cycle or depending on one have no place in the order and are left out.
+    pub cycles: Vec<Vec<String>>,
+}
+
+impl ReadingOrder {
+    /// Orders the notes with the given ids along with all notes they depend on, directly or indirectly.
+    /// Notes that could be read at the same time are ordered by their ids. Dependencies on missing notes are ignored.
+    pub fn new(index: &NoteIndex, ids: &[String]) -> Self {
+        // collect the notes and everything they depend on
+        let mut dependencies: HashMap<&str, Vec<&str>> = HashMap::new();
+        let mut stack = ids
+            .iter()
+            .filter_map(|id| index.inner.get_key_value(id))
+            .collect_vec();
+        while let Some((id, note)) = stack.pop() {
+            if dependencies.contains_key(id.as_str()) {
+                continue;
+            }
+            let existing = note
+                .dependencies
+                .iter()
+                .filter_map(|dependency| index.inner.get_key_value(dependency))
+                .collect_vec();
+            dependencies.insert(
+                id,
+                existing
+                    .iter()
+                    .map(|(dependency, _)| dependency.as_str())
+                    .collect(),
+            );
+            stack.extend(existing);
+        }
+
+        let mut dependents: HashMap<&str, Vec<&str>> = HashMap::new();
+        for (id, note_dependencies) in &dependencies {
+            for dependency in note_dependencies {
+                dependents.entry(dependency).or_default().push(id);
+            }
+        }
+
+        // repeatedly read the first note whose dependencies have all been read
+        let mut unread = dependencies
+            .iter()
+            .map(|(id, note_dependencies)| (*id, note_dependencies.len()))
+            .collect::<HashMap<_, _>>();
+        let mut ready = unread
+            .iter()
+            .filter(|(_id, count)| **count == 0)
+            .map(|(id, _count)| *id)
+            .collect::<BTreeSet<_>>();
+        let mut order = Vec::new();
+        while let Some(id) = ready.pop_first() {
+            unread.remove(id);
+            order.push(id.to_owned());
+            for dependent in dependents.get(id).into_iter().flatten() {
+                if let Some(count) = unread.get_mut(dependent) {
+                    *count -= 1;
+                    if *count == 0 {
+                        ready.insert(dependent);
+                    }
+                }
+            }
+        }
+
+        Self {
+            order,
+            cycles: find_cycles(&dependencies, unread.keys().copied().collect()),
+        }
+    }
+
+    /// Returns the order as a numbered markdown list of links to the notes.
+    pub fn to_markdown(&self, index: &NoteIndex) -> String {
+        self.order
+            .iter()
+            .filter_map(|id| index.get(id))
+            .enumerate()
+            .map(|(position, note)| format!("{}. [[{}]]\n", position + 1, note.name))
+            .collect()
+    }
+}
+
+/// Finds cycles among the given notes, each of which depends on another one of them.
+/// Every cycle is returned once, starting at its smallest id.
+fn find_cycles(
+    dependencies: &HashMap<&str, Vec<&str>>,
+    remaining: BTreeSet<&str>,
+) -> Vec<Vec<String>> {
+    let mut visited = HashSet::new();
+    let mut cycles = Vec::new();
+
+    for start in &remaining {
+        // follow the dependencies until a note repeats, which closes a cycle
+        let mut path = Vec::new();
+        let mut current = *start;
+        while !visited.contains(current) {
+            visited.insert(current);
+            path.push(current);
+            let Some(next) = dependencies
+                .get(current)
+                .into_iter()
+                .flatten()
+                .filter(|dependency| remaining.contains(*dependency))
+                .min()
+            else {
+                break;
+            };
+            current = next;
+        }
+
+        // the path may end in a cycle found from an earlier start
+        if let Some(position) = path.iter().position(|id| *id == current) {
+            let cycle = &path[position..];
+            let first = cycle
+                .iter()
+                .position_min()
+                .expect("Cycles to contain a note.");
+            cycles.push(
+                cycle[first..]
+                    .iter()
+                    .chain(&cycle[..first])
+                    .map(|id| id.to_string())
+                    .collect(),
+            );
+        }
+    }
+
+    cycles
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reading_order() {
+        let vault = testdir::testdir!();
+        for (name, content) in [
+            ("Sets", "# Sets\n"),
+            ("Groups", "---\ndepends-on: [Sets]\n---\n"),
+            ("Topology", "---\ndepends-on: [[Sets]]\n---\n"),
+            (
+                "Lie Groups",
+                "---\ndepends-on: [\"[[Groups]]\", Manifolds, Missing]\n---\n",
+            ),
+            ("Manifolds", "---\ndepends-on: [Topology]\n---\n"),
+            ("Chicken", "---\ndepends-on: [Egg]\n---\n"),
+            ("Egg", "---\ndepends-on: [Chicken]\n---\n"),
+            ("Omelette", "---\ndepends-on: [Egg, Sets]\n---\n"),
+        ] {
+            std::fs::write(vault.join(format!("{}.md", name)), content).unwrap();
+        }
+
+        let config = crate::Config::default();
+        let tracker = crate::io::FileTracker::new(&config, vault.clone()).unwrap();
+        let builder = crate::io::HtmlBuilder::new(&config, vault);
+        let index = NoteIndex::new(tracker, builder, Default::default()).0;
+
+        // dependencies come first, notes that could be read at the same time by id
+        let order = ReadingOrder::new(&index, &[String::from("lie-groups")]);
+        assert_eq!(
+            order.order,
+            ["sets", "groups", "topology", "manifolds", "lie-groups"]
+        );
+        assert!(order.cycles.is_empty());
+        assert_eq!(
+            order.to_markdown(&index),
+            "1. [[Sets]]\n2. [[Groups]]\n3. [[Topology]]\n4. [[Manifolds]]\n5. [[Lie Groups]]\n"
+        );
+
+        // notes in or depending on a cycle are left out, the cycle is reported once
+        let order = ReadingOrder::new(&index, &[String::from("omelette"), String::from("chicken")]);
+        assert_eq!(order.order, ["sets"]);
+        assert_eq!(order.cycles, [["chicken", "egg"]]);
+    }
+}
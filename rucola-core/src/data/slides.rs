@@ -0,0 +1,81 @@
+use super::lint::{heading_level, split_lines};
+
+/// Splits the given content of a note into the markdown of its slides, leaving out its frontmatter.
+/// Slides are separated by horizontal rules (`---` after an empty line) or, if there are none, start at every heading of the first or second level.
+/// Empty slides are left out.
+pub fn split_slides(content: &str) -> Vec<String> {
+    let lines = content.lines().collect::<Vec<_>>();
+    let (_frontmatter, body, body_start) = split_lines(content);
+
+    // a rule right below text would underline it as a heading instead
+    let rules = body
+        .iter()
+        .filter(|(number, line)| {
+            line.trim_end() == "---"
+                && (*number == body_start
+                    || lines
+                        .get(number - 2)
+                        .is_some_and(|above| above.trim().is_empty()))
+        })
+        .map(|(number, _)| *number)
+        .collect::<Vec<_>>();
+
+    // the numbers of the lines ending the previous slide and starting the next one
+    let breaks = if rules.is_empty() {
+        body.iter()
+            .filter(|(_, line)| heading_level(line).is_some_and(|level| level <= 2))
+            .map(|(number, _)| (*number - 1, *number))
+            .collect::<Vec<_>>()
+    } else {
+        rules
+            .into_iter()
+            .map(|number| (number - 1, number + 1))
+            .collect()
+    };
+
+    let mut slides = Vec::new();
+    let mut start = body_start;
+    for (end, next) in breaks
+        .into_iter()
+        .chain(std::iter::once((lines.len(), lines.len() + 1)))
+    {
+        let slide = lines
+            .get(start.saturating_sub(1)..end.min(lines.len()))
+            .unwrap_or_default()
+            .join("\n");
+        if !slide.trim().is_empty() {
+            slides.push(slide.trim_matches('\n').to_owned());
+        }
+        start = next;
+    }
+
+    slides
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_slides() {
+        // without rules, slides start at the top headings
+        assert_eq!(
+            split_slides("---\ntitle: Groups\n---\n# Groups\nIntro\n## Axioms\n### Details\n```\n# no heading\n```\n## Examples\n"),
+            vec![
+                "# Groups\nIntro",
+                "## Axioms\n### Details\n```\n# no heading\n```",
+                "## Examples",
+            ]
+        );
+
+        // rules take precedence, but not those underlining text or within code
+        assert_eq!(
+            split_slides(
+                "# Groups\nIntro\n\n---\n\n## Axioms\nSetext\n---\n```\n\n---\n```\n\n---\n\n---\n"
+            ),
+            vec!["# Groups\nIntro", "## Axioms\nSetext\n---\n```\n\n---\n```",]
+        );
+
+        assert!(split_slides("---\ntitle: Empty\n---\n\n").is_empty());
+    }
+}
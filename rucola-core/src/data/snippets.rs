@@ -0,0 +1,181 @@
+use itertools::Itertools;
+
+use super::lint::{heading_level, split_lines};
+
+/// A text declared in the `[snippets]` table of the config file that can be inserted into notes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Snippet {
+    /// The name the snippet is listed by.
+    pub name: String,
+    /// The text of the snippet, possibly containing placeholders like `{{title}}`.
+    pub text: String,
+}
+
+impl Snippet {
+    /// Returns the snippets declared in the given config, sorted by name.
+    pub fn from_config(config: &crate::Config) -> Vec<Self> {
+        config
+            .snippets
+            .iter()
+            .map(|(name, text)| Self {
+                name: name.to_owned(),
+                text: text.to_owned(),
+            })
+            .sorted_by(|a, b| a.name.cmp(&b.name))
+            .collect()
+    }
+
+    /// Returns the text of the snippet with every placeholder `{{key}}` replaced by the value given for its key.
+    /// Placeholders without a value are kept as they are.
+    pub fn expand(&self, values: &[(&str, &str)]) -> String {
+        values.iter().fold(self.text.clone(), |text, (key, value)| {
+            text.replace(&format!("{{{{{}}}}}", key), value)
+        })
+    }
+}
+
+/// A heading of a note, after whose section a snippet can be inserted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Heading {
+    /// The level of the heading, from 1 to 6.
+    pub level: usize,
+    /// The text of the heading, without the leading `#`s.
+    pub text: String,
+    /// The line of the heading, starting at 1.
+    pub line: usize,
+}
+
+/// Returns all headings of the given content outside of its frontmatter and code blocks.
+pub fn headings(content: &str) -> Vec<Heading> {
+    let (_frontmatter, body, _body_start) = split_lines(content);
+    body.into_iter()
+        .flat_map(|(line, text)| {
+            heading_level(text).map(|level| Heading {
+                level,
+                text: text[level..].trim().to_owned(),
+                line,
+            })
+        })
+        .collect()
+}
+
+/// Inserts the given text into the given content at the end of the section of the given heading, before the next heading of the same or a higher level.
+/// Without a heading, the text is appended to the end of the content. The text is separated from its surroundings by blank lines.
+/// The heading is searched for again in the content, so it does not matter if lines were added or removed since it was found.
+/// Returns `None` if the content no longer contains the heading.
+pub fn insert_snippet(content: &str, text: &str, heading: Option<&Heading>) -> Option<String> {
+    let lines = content.lines().collect_vec();
+    let headings = headings(content);
+
+    let (start, end) = match heading {
+        Some(heading) => {
+            // of several headings with the same text, take the one closest to where it was
+            let current = headings
+                .iter()
+                .filter(|other| other.level == heading.level && other.text == heading.text)
+                .min_by_key(|other| other.line.abs_diff(heading.line))?;
+            let end = headings
+                .iter()
+                .find(|other| other.line > current.line && other.level <= current.level)
+                .map(|next| next.line - 1)
+                .unwrap_or(lines.len());
+            (current.line, end)
+        }
+        None => (0, lines.len()),
+    };
+
+    // insert right after the last non-empty line of the section
+    let mut insert_at = end;
+    while insert_at > start && lines[insert_at - 1].trim().is_empty() {
+        insert_at -= 1;
+    }
+
+    let mut result = lines[..insert_at].to_vec();
+    if insert_at > 0 {
+        result.push("");
+    }
+    result.extend(text.trim_matches('\n').lines());
+
+    let rest = &lines[insert_at..];
+    if !rest.iter().all(|line| line.trim().is_empty()) {
+        if !rest[0].trim().is_empty() {
+            result.push("");
+        }
+        result.extend(rest);
+    }
+
+    Some(result.join("\n") + "\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expand() {
+        let snippet = Snippet {
+            name: String::from("meeting"),
+            text: String::from("## Meeting {{date}}\nAbout [[{{name}}]], {{unknown}}"),
+        };
+        assert_eq!(
+            snippet.expand(&[("date", "2024-05-01"), ("name", "Chart")]),
+            "## Meeting 2024-05-01\nAbout [[Chart]], {{unknown}}"
+        );
+    }
+
+    #[test]
+    fn test_headings() {
+        let content =
+            "---\ntitle: Atlas\n---\n# Atlas\ntext\n```\n# not a heading\n```\n## Charts\n#tag";
+        assert_eq!(
+            headings(content),
+            vec![
+                Heading {
+                    level: 1,
+                    text: String::from("Atlas"),
+                    line: 4
+                },
+                Heading {
+                    level: 2,
+                    text: String::from("Charts"),
+                    line: 9
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_insert_snippet() {
+        let content = "# Atlas\nintro\n\n## Charts\nA chart.\n\n### Smooth\nSmooth charts.\n\n## Groups\nGroups.\n";
+        let headings = headings(content);
+
+        // at the end of a section, including its subsections
+        assert_eq!(
+            insert_snippet(content, "- [ ] todo\n", Some(&headings[1])).unwrap(),
+            "# Atlas\nintro\n\n## Charts\nA chart.\n\n### Smooth\nSmooth charts.\n\n- [ ] todo\n\n## Groups\nGroups.\n"
+        );
+
+        // at the end of a section directly followed by a heading
+        assert_eq!(
+            insert_snippet("## Charts\n## Groups\n", "text", Some(&headings[1])).unwrap(),
+            "## Charts\n\ntext\n\n## Groups\n"
+        );
+
+        // headings that no longer exist are not found
+        assert_eq!(
+            insert_snippet("# A\n## B\n", "text", Some(&headings[0])),
+            None
+        );
+
+        // at the end of the note
+        assert_eq!(
+            insert_snippet(content, "text", None).unwrap(),
+            format!("{}\ntext\n", content)
+        );
+        assert_eq!(insert_snippet("", "text", None).unwrap(), "text\n");
+        assert_eq!(
+            insert_snippet("intro\n\n\n", "text", None).unwrap(),
+            "intro\n\ntext\n"
+        );
+    }
+}
@@ -0,0 +1,154 @@
+use itertools::Itertools;
+
+use super::lint::split_lines;
+
+/// The number of characters shown around a misspelled word.
+const CONTEXT_WIDTH: usize = 60;
+
+/// A word of a note that the spellchecker does not know.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Misspelling {
+    /// The misspelled word.
+    pub word: String,
+    /// The line of the word, starting at 1.
+    pub line: usize,
+    /// The text around the word, shortened to a few words before and after it.
+    pub context: String,
+}
+
+impl Misspelling {
+    /// Finds all occurrences of the given misspelled words in the given content.
+    pub fn find_all(content: &str, misspelled: &[String]) -> Vec<Self> {
+        words(content)
+            .into_iter()
+            .filter(|(_, word, _)| misspelled.iter().any(|m| m == word))
+            .map(|(line, word, text)| Self {
+                word: word.to_owned(),
+                line,
+                context: context(text, word),
+            })
+            .collect()
+    }
+}
+
+/// Returns the words of the prose of the given content, with their line and the full text of the line.
+/// Frontmatter, code, math, links, URLs, tags, HTML and block ids are left out, as are single letters.
+fn words(content: &str) -> Vec<(usize, &str, &str)> {
+    let (_frontmatter, body, _body_start) = split_lines(content);
+
+    body.into_iter()
+        .flat_map(|(number, line)| {
+            let mut words = Vec::new();
+            let mut start = None;
+            let masked = mask(line);
+            for (i, c) in masked.char_indices().chain([(masked.len(), ' ')]) {
+                let is_word = c.is_alphabetic() || c == '\'';
+                match (start, is_word) {
+                    (None, true) => start = Some(i),
+                    (Some(s), false) => {
+                        let word = line[s..i].trim_matches('\'');
+                        if word.chars().count() > 1 {
+                            words.push((number, word, line));
+                        }
+                        start = None;
+                    }
+                    _ => {}
+                }
+            }
+            words
+        })
+        .collect()
+}
+
+/// Returns the distinct words of the given content to check, in the order of their first occurrence.
+pub fn distinct_words(content: &str) -> Vec<&str> {
+    words(content)
+        .into_iter()
+        .map(|(_, word, _)| word)
+        .unique()
+        .collect()
+}
+
+/// Replaces all parts of the given line that are not prose by spaces, keeping the byte offsets of the rest.
+fn mask(line: &str) -> String {
+    let mut masked = line.to_owned();
+    // code, math, wiki links, targets of markdown links, HTML, URLs, tags and block ids
+    let patterns = [
+        r"`[^`]*`",
+        r"\$[^$]*\$",
+        r"\[\[[^\]]*\]\]",
+        r"\]\([^)]*\)",
+        r"<[^>]*>",
+        r"\S*://\S*",
+        r"(^|\s)[#^]\S*",
+    ];
+    for pattern in patterns {
+        let regex = regex::Regex::new(pattern).expect("Pre-defined regex to be valid.");
+        masked = regex
+            .replace_all(&masked, |captures: &regex::Captures| {
+                " ".repeat(captures[0].len())
+            })
+            .into_owned();
+    }
+    masked
+}
+
+/// Returns the text of the given line around the first occurrence of the given word.
+fn context(line: &str, word: &str) -> String {
+    let line = line.trim();
+    if line.chars().count() <= CONTEXT_WIDTH {
+        return line.to_owned();
+    }
+
+    let position = line[..line.find(word).unwrap_or_default()].chars().count();
+    let start = position.saturating_sub(CONTEXT_WIDTH / 2);
+    let text = line
+        .chars()
+        .skip(start)
+        .take(CONTEXT_WIDTH)
+        .collect::<String>();
+
+    format!(
+        "{}{}{}",
+        if start > 0 { "…" } else { "" },
+        text,
+        if start + CONTEXT_WIDTH < line.chars().count() {
+            "…"
+        } else {
+            ""
+        }
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_words() {
+        let content = "---\ntitle: Frontmattre\n---\n# Lie's Groups\nA `codde` and $mathh$ with [[Linkk]] to [a tezt](Targett) at https://exmple.org #tagg.\n```\nnot chekced\n```\nAn ending. ^blockk\n";
+        assert_eq!(
+            distinct_words(content),
+            vec!["Lie's", "Groups", "and", "with", "to", "tezt", "at", "An", "ending"]
+        );
+    }
+
+    #[test]
+    fn test_find_all() {
+        let content = "# Atlas\nA colection of charts.\n\nEvery colection covers it.";
+        let misspellings = Misspelling::find_all(content, &[String::from("colection")]);
+        assert_eq!(
+            misspellings
+                .iter()
+                .map(|misspelling| misspelling.line)
+                .collect_vec(),
+            vec![2, 4]
+        );
+        assert_eq!(misspellings[0].context, "A colection of charts.");
+
+        let long = format!("{} typo {}", "word ".repeat(20), "word ".repeat(20));
+        let misspellings = Misspelling::find_all(&long, &[String::from("typo")]);
+        assert_eq!(misspellings[0].context.chars().count(), CONTEXT_WIDTH + 2);
+        assert!(misspellings[0].context.contains("typo"));
+    }
+}
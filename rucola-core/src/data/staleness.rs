@@ -0,0 +1,186 @@
+use itertools::Itertools;
+
+use super::{Filter, Note, NoteIndex};
+
+/// A rule flagging notes that have not been modified for too long, as set in the `stale_notes` of the config.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct StaleRule {
+    /// The filter notes have to match to be checked, in the syntax of the filter box, e.g. `#process`.
+    pub filter: String,
+    /// After how many days without modification a matching note is stale.
+    pub days: u32,
+}
+
+impl Default for StaleRule {
+    fn default() -> Self {
+        Self {
+            filter: String::new(),
+            days: 90,
+        }
+    }
+}
+
+/// A note that has not been modified for longer than a stale rule allows.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StaleNote {
+    /// The id of the note.
+    pub id: String,
+    /// The name of the rule the note violates.
+    pub rule: String,
+    /// The number of days since the last modification of the note.
+    pub age: i64,
+    /// The number of days the rule allows.
+    pub days: u32,
+}
+
+/// Checks notes against the stale rules of the config.
+#[derive(Debug, Clone, Default)]
+pub struct Staleness {
+    /// The rules by name, with their parsed filters.
+    rules: Vec<(String, Filter, u32)>,
+}
+
+impl Staleness {
+    /// Creates a new staleness check from the rules of the given config.
+    pub fn new(config: &crate::Config) -> Self {
+        Self {
+            rules: config
+                .stale_notes
+                .iter()
+                .sorted_by_key(|(name, _rule)| *name)
+                .map(|(name, rule)| (name.clone(), Filter::new(&rule.filter, false), rule.days))
+                .collect(),
+        }
+    }
+
+    /// Returns how the given note with the given id is stale at the given time, if it is.
+    /// If it violates several rules, the one it exceeds by the most days is returned.
+    pub fn check(
+        &self,
+        id: &str,
+        note: &Note,
+        index: &NoteIndex,
+        now: chrono::DateTime<chrono::Local>,
+    ) -> Option<StaleNote> {
+        let age = (now.date_naive() - note.modified?.date_naive()).num_days();
+
+        self.rules
+            .iter()
+            .filter(|(_name, _filter, days)| age > *days as i64)
+            .filter(|(_name, filter, _days)| filter.apply(note, index).is_some())
+            .max_by_key(|(_name, _filter, days)| age - *days as i64)
+            .map(|(name, _filter, days)| StaleNote {
+                id: id.to_owned(),
+                rule: name.clone(),
+                age,
+                days: *days,
+            })
+    }
+
+    /// Returns all stale notes of the given index at the given time, those exceeding their rule by the most days first.
+    pub fn stale_notes(
+        &self,
+        index: &NoteIndex,
+        now: chrono::DateTime<chrono::Local>,
+    ) -> Vec<StaleNote> {
+        if self.rules.is_empty() {
+            return Vec::new();
+        }
+
+        index
+            .inner
+            .iter()
+            .filter_map(|(id, note)| self.check(id, note, index, now))
+            .sorted_by(|a, b| {
+                (b.age - b.days as i64)
+                    .cmp(&(a.age - a.days as i64))
+                    .then_with(|| a.id.cmp(&b.id))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_staleness() {
+        let config = crate::Config {
+            stale_notes: [
+                (
+                    String::from("processes"),
+                    StaleRule {
+                        filter: String::from("#os"),
+                        days: 30,
+                    },
+                ),
+                (
+                    String::from("everything"),
+                    StaleRule {
+                        filter: String::new(),
+                        days: 100,
+                    },
+                ),
+            ]
+            .into(),
+            ..Default::default()
+        };
+        let tracker =
+            crate::io::FileTracker::new(&config, std::path::PathBuf::from("./tests")).unwrap();
+        let builder = crate::io::HtmlBuilder::new(&config, std::path::PathBuf::from("./tests"));
+        let index = NoteIndex::new(tracker, builder, Default::default()).0;
+
+        let staleness = Staleness::new(&config);
+        let linux = index.get("linux").unwrap();
+        let modified = linux.modified.unwrap();
+
+        // notes are stale once they have not been modified for more days than a matching rule allows
+        assert_eq!(
+            staleness.check("linux", linux, &index, modified + chrono::Days::new(30)),
+            None
+        );
+        assert_eq!(
+            staleness.check("linux", linux, &index, modified + chrono::Days::new(31)),
+            Some(StaleNote {
+                id: String::from("linux"),
+                rule: String::from("processes"),
+                age: 31,
+                days: 30,
+            })
+        );
+        // the rule exceeded the most is reported, other notes are only checked by the rules they match
+        assert_eq!(
+            staleness
+                .check("linux", linux, &index, modified + chrono::Days::new(200))
+                .map(|stale| stale.rule),
+            Some(String::from("processes"))
+        );
+        let books = index.get("books").unwrap();
+        assert_eq!(
+            staleness
+                .check(
+                    "books",
+                    books,
+                    &index,
+                    books.modified.unwrap() + chrono::Days::new(200)
+                )
+                .map(|stale| stale.rule),
+            Some(String::from("everything"))
+        );
+
+        // notes not matching the filter of a rule are never stale by it
+        let stale = staleness.stale_notes(&index, modified + chrono::Days::new(50));
+        assert!(stale
+            .iter()
+            .any(|stale| stale.id == "linux" && stale.rule == "processes"));
+        assert!(!stale
+            .iter()
+            .any(|stale| stale.id == "books" && stale.rule == "processes"));
+
+        assert!(Staleness::default()
+            .stale_notes(&index, modified + chrono::Days::new(1000))
+            .is_empty());
+    }
+}
@@ -0,0 +1,178 @@
+use std::ops::Range;
+
+use crate::{error, i18n};
+
+/// A proposed link from a note to another note whose title is mentioned in its text without being linked.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LinkSuggestion {
+    /// The id of the note to link to.
+    pub target_id: String,
+    /// The file name of the note to link to, used as the target of the inserted wiki link.
+    pub target_name: String,
+    /// The text in the source note that mentions the target.
+    pub mention: String,
+}
+
+impl LinkSuggestion {
+    /// Returns the wiki link that will replace the mention in the source note.
+    /// If the mention differs from the file name of the target, it is kept as the link title.
+    pub fn to_wikilink(&self) -> String {
+        if self.mention == self.target_name {
+            format!("[[{}]]", self.target_name)
+        } else {
+            format!("[[{}|{}]]", self.target_name, self.mention)
+        }
+    }
+}
+
+/// A proposed tag for a note, taken from the tags already used in the vault.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TagSuggestion {
+    /// The tag, including the leading `#`.
+    pub tag: String,
+    /// Whether the tag matches one of the keywords of the note.
+    pub keyword: bool,
+    /// The number of notes linked from the note that carry the tag.
+    pub linked: usize,
+}
+
+impl TagSuggestion {
+    /// Describes why the tag was suggested.
+    pub fn reason(&self) -> String {
+        match (self.keyword, self.linked) {
+            (true, 0) => i18n::tr("tag-suggestions-reason-keyword"),
+            (keyword, linked) => i18n::tr_args(
+                if keyword {
+                    "tag-suggestions-reason-both"
+                } else {
+                    "tag-suggestions-reason-linked"
+                },
+                &[("count", linked.into())],
+            ),
+        }
+    }
+}
+
+/// Titles shorter than this are never suggested, as they match far too often.
+const MIN_MENTION_LENGTH: usize = 3;
+
+/// Finds the first mention of `title` in `content`.
+///  - Matching is case-insensitive and only considers whole words.
+///    Titles starting or ending with other characters, such as `C++`, have to be preceded or followed by a character that is not part of a word.
+///  - Mentions within existing wiki links or markdown links are ignored.
+///
+/// Returns the byte range of the mention within `content`.
+pub fn find_mention(content: &str, title: &str) -> error::Result<Option<Range<usize>>> {
+    Ok(find_first_mentions(content, &[title])?.pop().flatten())
+}
+
+/// Returns the pattern matching mentions of the given title as its first group, see [find_mention].
+fn mention_pattern(title: &str) -> String {
+    let is_word = |c: Option<char>| c.is_some_and(|c| c.is_alphanumeric() || c == '_');
+    format!(
+        r"(?i){}({}){}",
+        if is_word(title.chars().next()) {
+            r"\b"
+        } else {
+            r"(?:^|\W)"
+        },
+        regex::escape(title),
+        if is_word(title.chars().next_back()) {
+            r"\b"
+        } else {
+            r"(?:\W|$)"
+        },
+    )
+}
+
+/// Finds the first mention of each of the given `titles` in `content`, following the same rules as [find_mention].
+/// The content is scanned for all titles at once, so this should be preferred to repeatedly calling [find_mention].
+///
+/// Returns the byte range of the first mention within `content` for each title, in the order of `titles`.
+/// Fails if there are too many titles to search for at once.
+pub fn find_first_mentions(
+    content: &str,
+    titles: &[&str],
+) -> error::Result<Vec<Option<Range<usize>>>> {
+    // Blank out all regions that are already links and may not be linked again.
+    // They are replaced by spaces of the same length, so byte ranges in the result stay valid for the original content.
+    let links = regex::Regex::new(r"\[\[[^\]]*\]\]|\[[^\]]*\]\([^)]*\)")
+        .expect("Pre-defined regex to be valid.");
+    let unlinked = links.replace_all(content, |link: &regex::Captures| " ".repeat(link[0].len()));
+
+    // One pattern per title long enough to be suggested.
+    let (indices, patterns): (Vec<_>, Vec<_>) = titles
+        .iter()
+        .map(|title| title.trim())
+        .enumerate()
+        .filter(|(_index, title)| title.chars().count() >= MIN_MENTION_LENGTH)
+        .map(|(index, title)| (index, mention_pattern(title)))
+        .unzip();
+
+    let mut mentions = vec![None; titles.len()];
+
+    let set = regex::RegexSet::new(&patterns).map_err(error::RucolaError::MentionError)?;
+
+    // Only the titles that are mentioned at all need their position determined.
+    for pattern in set.matches(&unlinked).iter() {
+        mentions[indices[pattern]] = regex::Regex::new(&patterns[pattern])
+            .map_err(error::RucolaError::MentionError)?
+            .captures(&unlinked)
+            .and_then(|captures| captures.get(1))
+            .map(|m| m.range());
+    }
+
+    Ok(mentions)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_mention() {
+        let content =
+            "# Lie Group Representation\nA representation of a lie group on a [[Manifold]].";
+
+        assert_eq!(find_mention(content, "Lie Group").unwrap(), Some(2..11));
+        assert_eq!(find_mention(content, "manifold").unwrap(), None);
+        assert_eq!(find_mention(content, "Rep").unwrap(), None);
+        assert_eq!(find_mention(content, "on").unwrap(), None);
+        assert_eq!(find_mention(content, "Atlas").unwrap(), None);
+    }
+
+    #[test]
+    fn test_find_first_mentions() {
+        let content = "A manifold is covered by charts. See the [[Atlas]] of the manifold, or [the atlas](Atlas.md).\nAn atlas is a set of charts.";
+
+        assert_eq!(
+            find_first_mentions(content, &["Atlas", "Manifold", "Lie Group", "see", "at"]).unwrap(),
+            vec![Some(97..102), Some(2..10), None, Some(33..36), None]
+        );
+
+        assert!(find_first_mentions(content, &[]).unwrap().is_empty());
+
+        // titles starting or ending with characters that are not part of words
+        let content = "Written in C++, see (Draft). Not in C+++ or the(Draft) itself.\n(Draft)";
+        assert_eq!(
+            find_first_mentions(content, &["C++", "(Draft)", "++,"]).unwrap(),
+            vec![Some(11..14), Some(20..27), None]
+        );
+        assert_eq!(find_mention("(Draft)", "(draft)").unwrap(), Some(0..7));
+    }
+
+    #[test]
+    fn test_to_wikilink() {
+        let mut suggestion = LinkSuggestion {
+            target_id: "lie-group".to_string(),
+            target_name: "Lie Group".to_string(),
+            mention: "Lie Group".to_string(),
+        };
+
+        assert_eq!(suggestion.to_wikilink(), "[[Lie Group]]");
+
+        suggestion.mention = "lie group".to_string();
+
+        assert_eq!(suggestion.to_wikilink(), "[[Lie Group|lie group]]");
+    }
+}
@@ -0,0 +1,357 @@
+use std::{cmp::Ordering, path};
+
+use itertools::Itertools;
+
+/// A table of structured data, from a note consisting of nothing but a markdown table (and headings) or from a CSV file next to a note.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DataTable {
+    /// The names of the columns, from the header of the table.
+    pub columns: Vec<String>,
+    /// The cells of every row, as many as there are columns.
+    pub rows: Vec<Vec<String>>,
+    /// The CSV file the table was read from, if it is not part of the note itself.
+    pub file: Option<path::PathBuf>,
+}
+
+impl DataTable {
+    /// Creates a table from the given lines of a markdown table: Its header, delimiter row and body.
+    pub fn from_markdown<'a>(lines: impl IntoIterator<Item = &'a str>) -> Option<Self> {
+        let mut lines = lines.into_iter();
+        let columns = table_cells(lines.next()?);
+        // skip the delimiter row
+        lines.next()?;
+
+        let rows = lines
+            .map(|line| {
+                let mut cells = table_cells(line);
+                cells.resize(columns.len(), String::new());
+                cells
+            })
+            .collect();
+
+        Some(Self {
+            columns,
+            rows,
+            file: None,
+        })
+    }
+
+    /// Creates a table from the given content of a CSV file, the first record being its header.
+    /// Fields may be quoted, with quotes within them doubled.
+    pub fn from_csv(content: &str, file: path::PathBuf) -> Self {
+        let mut records = csv_records(content).into_iter();
+        let columns = records.next().unwrap_or_default();
+        let rows = records
+            .map(|mut cells| {
+                cells.resize(columns.len(), String::new());
+                cells
+            })
+            .collect();
+
+        Self {
+            columns,
+            rows,
+            file: Some(file),
+        }
+    }
+
+    /// Returns the index of the column with the given name, ignoring case.
+    pub fn column(&self, name: &str) -> Option<usize> {
+        self.columns
+            .iter()
+            .position(|column| column.to_lowercase() == name.to_lowercase())
+    }
+
+    /// Sorts the rows by the given column, numerically if both cells are numbers.
+    pub fn sort(&mut self, column: usize, ascending: bool) {
+        self.rows.sort_by(|a, b| {
+            let ordering = compare_cells(&a[column], &b[column]);
+            if ascending {
+                ordering
+            } else {
+                ordering.reverse()
+            }
+        });
+    }
+
+    /// Returns the header, delimiter row and body of the table in markdown.
+    /// The header of the given sorted column is marked with an arrow.
+    pub fn to_markdown(&self, sorted: Option<(usize, bool)>) -> Vec<String> {
+        let header = self
+            .columns
+            .iter()
+            .enumerate()
+            .map(|(index, column)| match sorted {
+                Some((sorted, ascending)) if sorted == index => {
+                    format!("{} {}", column, if ascending { "▲" } else { "▼" })
+                }
+                _ => column.clone(),
+            })
+            .collect_vec();
+
+        std::iter::once(markdown_row(&header))
+            .chain(std::iter::once(markdown_row(
+                &self.columns.iter().map(|_| "---").collect_vec(),
+            )))
+            .chain(self.rows.iter().map(|row| markdown_row(row)))
+            .collect()
+    }
+}
+
+/// How a column condition compares the cells of a column to its value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Comparison {
+    /// The table has the column at all.
+    Exists,
+    /// `=`: The cell equals the value, ignoring case.
+    Equal,
+    /// `!=`: The cell does not equal the value, ignoring case.
+    NotEqual,
+    /// `<`: The cell is smaller than the value.
+    Less,
+    /// `<=`: The cell is smaller than or equal to the value.
+    LessOrEqual,
+    /// `>`: The cell is greater than the value.
+    Greater,
+    /// `>=`: The cell is greater than or equal to the value.
+    GreaterOrEqual,
+    /// `~`: The cell contains the value, ignoring case.
+    Contains,
+}
+
+/// A condition on a column of the data table of a note, like `.price<10`, written in the filter bar.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ColumnCondition {
+    /// The name of the column.
+    pub column: String,
+    /// How cells are compared to the value.
+    pub comparison: Comparison,
+    /// The value cells are compared to.
+    pub value: String,
+}
+
+impl ColumnCondition {
+    /// Parses a condition of the form `.column`, `.column=value` or with any other operator instead of `=`, the leading dot already removed.
+    pub fn parse(condition: &str) -> Option<Self> {
+        const OPERATORS: [(&str, Comparison); 7] = [
+            ("!=", Comparison::NotEqual),
+            ("<=", Comparison::LessOrEqual),
+            (">=", Comparison::GreaterOrEqual),
+            ("=", Comparison::Equal),
+            ("<", Comparison::Less),
+            (">", Comparison::Greater),
+            ("~", Comparison::Contains),
+        ];
+
+        // the first operator in the condition separates the column from the value
+        let (column, comparison, value) = OPERATORS
+            .iter()
+            .filter_map(|(operator, comparison)| {
+                condition
+                    .find(operator)
+                    .map(|position| (position, operator.len(), *comparison))
+            })
+            .min_by_key(|(position, length, _)| (*position, std::cmp::Reverse(*length)))
+            .map(|(position, length, comparison)| {
+                (
+                    &condition[..position],
+                    comparison,
+                    &condition[position + length..],
+                )
+            })
+            .unwrap_or((condition, Comparison::Exists, ""));
+
+        (!column.is_empty()).then(|| Self {
+            column: column.to_owned(),
+            comparison,
+            value: value.to_owned(),
+        })
+    }
+
+    /// Wether any row of the given table fulfills the condition.
+    pub fn matches(&self, table: &DataTable) -> bool {
+        let Some(column) = table.column(&self.column) else {
+            return false;
+        };
+
+        if self.comparison == Comparison::Exists {
+            return true;
+        }
+
+        table.rows.iter().any(|row| {
+            let cell = &row[column];
+            let ordering = compare_cells(cell, &self.value);
+            match self.comparison {
+                Comparison::Exists => true,
+                Comparison::Equal => ordering.is_eq(),
+                Comparison::NotEqual => ordering.is_ne(),
+                Comparison::Less => ordering.is_lt(),
+                Comparison::LessOrEqual => ordering.is_le(),
+                Comparison::Greater => ordering.is_gt(),
+                Comparison::GreaterOrEqual => ordering.is_ge(),
+                Comparison::Contains => cell.to_lowercase().contains(&self.value.to_lowercase()),
+            }
+        })
+    }
+}
+
+/// Compares two cells of a table: As numbers if both are numbers, as text ignoring case otherwise.
+pub fn compare_cells(a: &str, b: &str) -> Ordering {
+    match (a.trim().parse::<f64>(), b.trim().parse::<f64>()) {
+        (Ok(a), Ok(b)) => a.total_cmp(&b),
+        _ => a.to_lowercase().cmp(&b.to_lowercase()),
+    }
+}
+
+/// Splits a row of a markdown table into its cells, keeping escaped pipes (`\|`) within them.
+pub fn table_cells(line: &str) -> Vec<String> {
+    let line = line.trim();
+    let line = line.strip_prefix('|').unwrap_or(line);
+    let line = match line.strip_suffix('|') {
+        Some(rest) if !rest.ends_with('\\') => rest,
+        _ => line,
+    };
+
+    let mut cells = vec![String::new()];
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' if chars.peek() == Some(&'|') => {
+                chars.next();
+                cells.last_mut().unwrap().push('|');
+            }
+            '|' => cells.push(String::new()),
+            c => cells.last_mut().unwrap().push(c),
+        }
+    }
+
+    cells.iter().map(|cell| cell.trim().to_owned()).collect()
+}
+
+/// Joins the given cells into a row of a markdown table, escaping pipes within them.
+fn markdown_row(cells: &[impl AsRef<str>]) -> String {
+    format!(
+        "| {} |",
+        cells
+            .iter()
+            .map(|cell| cell.as_ref().replace('|', "\\|").replace('\n', " "))
+            .join(" | ")
+    )
+}
+
+/// Splits the given content of a CSV file into its records, leaving out empty lines.
+fn csv_records(content: &str) -> Vec<Vec<String>> {
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+
+    let mut chars = content.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                chars.next();
+                field.push('"');
+            }
+            '"' if quoted => quoted = false,
+            '"' if field.is_empty() => quoted = true,
+            ',' if !quoted => record.push(std::mem::take(&mut field)),
+            '\r' if !quoted => {}
+            '\n' if !quoted => {
+                record.push(std::mem::take(&mut field));
+                if record.iter().any(|field| !field.is_empty()) {
+                    records.push(std::mem::take(&mut record));
+                }
+                record.clear();
+            }
+            c => field.push(c),
+        }
+    }
+
+    record.push(field);
+    if record.iter().any(|field| !field.is_empty()) {
+        records.push(record);
+    }
+
+    records
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_data_table() {
+        let table = DataTable::from_markdown([
+            "| Item | Count | Shelf |",
+            "| :--- | ---: | --- |",
+            "| Ruler | 12 | a \\| b |",
+            "| Compass | 3 |",
+        ])
+        .unwrap();
+        assert_eq!(table.columns, vec!["Item", "Count", "Shelf"]);
+        assert_eq!(table.rows[0], vec!["Ruler", "12", "a | b"]);
+        assert_eq!(table.rows[1], vec!["Compass", "3", ""]);
+
+        let csv = DataTable::from_csv(
+            "Item,Count,Note\r\nRuler,12,\"straight, long\"\n\n\"Compass \"\"Pro\"\"\",3,\"two\nlines\"\n",
+            path::PathBuf::from("inventory.csv"),
+        );
+        assert_eq!(csv.columns, vec!["Item", "Count", "Note"]);
+        assert_eq!(
+            csv.rows,
+            vec![
+                vec!["Ruler", "12", "straight, long"],
+                vec!["Compass \"Pro\"", "3", "two\nlines"],
+            ]
+        );
+
+        // numbers are sorted numerically
+        let mut sorted = csv.clone();
+        sorted.sort(1, true);
+        assert_eq!(sorted.rows[0][0], "Compass \"Pro\"");
+        assert_eq!(
+            sorted.to_markdown(Some((1, true))),
+            vec![
+                "| Item | Count ▲ | Note |",
+                "| --- | --- | --- |",
+                "| Compass \"Pro\" | 3 | two lines |",
+                "| Ruler | 12 | straight, long |",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_column_conditions() {
+        let table = DataTable::from_markdown([
+            "| Item | Count |",
+            "| --- | --- |",
+            "| Ruler | 12 |",
+            "| Compass | 3 |",
+        ])
+        .unwrap();
+
+        let matches = |condition: &str| ColumnCondition::parse(condition).unwrap().matches(&table);
+        assert!(matches("count"));
+        assert!(matches("count>10"));
+        assert!(!matches("count>12"));
+        assert!(matches("count>=12"));
+        assert!(matches("count<=3"));
+        assert!(!matches("count<3"));
+        assert!(matches("item=ruler"));
+        assert!(matches("item!=ruler"));
+        assert!(matches("item~pass"));
+        assert!(!matches("item~pencil"));
+        assert!(!matches("price>1"));
+
+        assert_eq!(
+            ColumnCondition::parse("count>=2"),
+            Some(ColumnCondition {
+                column: String::from("count"),
+                comparison: Comparison::GreaterOrEqual,
+                value: String::from("2"),
+            })
+        );
+        assert_eq!(ColumnCondition::parse("=2"), None);
+    }
+}
@@ -0,0 +1,319 @@
+use std::{collections::HashMap, fmt};
+
+use itertools::Itertools;
+
+use super::NoteIndex;
+
+/// Where in a note a tag occurs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TagSource {
+    /// In the text of the note.
+    Inline,
+    /// In the `tags` of the frontmatter, written without the leading `#`.
+    Frontmatter,
+    /// In inline code or a code block, where it is only a tag if code is not ignored.
+    Code,
+    /// In a typst code block, where it is rather a function call or variable.
+    Typst,
+}
+
+impl fmt::Display for TagSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                TagSource::Inline => "inline",
+                TagSource::Frontmatter => "frontmatter",
+                TagSource::Code => "code",
+                TagSource::Typst => "typst",
+            }
+        )
+    }
+}
+
+/// A line of a note in which a tag or one of its subtags occurs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TagOccurrence {
+    /// The id of the note.
+    pub id: String,
+    /// The number of the line within the note file, starting at 1.
+    pub line: usize,
+    /// The content of the line, without surrounding whitespace.
+    pub context: String,
+    /// Where in the note the line is.
+    pub source: TagSource,
+}
+
+/// Returns all tags used in the notes of the given index and the number of notes using each, sorted by tag.
+pub fn tag_counts(index: &NoteIndex) -> Vec<(String, usize)> {
+    index
+        .notes()
+        .flat_map(|note| note.tags.iter().unique())
+        .counts()
+        .into_iter()
+        .map(|(tag, count)| (tag.to_owned(), count))
+        .sorted()
+        .collect()
+}
+
+/// Finds all lines of all notes of the given index in which the given tag or one of its subtags occurs, reading the notes from the file system.
+/// Occurrences in code are found as well, even if code is ignored when looking for tags.
+/// The occurrences are sorted by the name of their note and their line.
+pub fn tag_occurrences(index: &NoteIndex, tag: &str) -> Vec<TagOccurrence> {
+    let tag = format!("#{}", tag.trim_start_matches('#'));
+    // the frontmatter names tags without the leading `#`
+    let name = tag.trim_start_matches('#');
+
+    let mut names = HashMap::new();
+    let mut occurrences = index
+        .inner
+        .iter()
+        .flat_map(|(id, note)| {
+            let content = std::fs::read_to_string(&note.path).ok()?;
+            // most notes do not mention the tag at all
+            if !content.contains(name) {
+                return None;
+            }
+            names.insert(id.to_owned(), note.name.to_lowercase());
+            Some(
+                find_occurrences(&content, &tag)
+                    .into_iter()
+                    .map(|(line, context, source)| TagOccurrence {
+                        id: id.to_owned(),
+                        line,
+                        context,
+                        source,
+                    })
+                    .collect_vec(),
+            )
+        })
+        .flatten()
+        .collect_vec();
+
+    occurrences.sort_by(|a, b| {
+        names
+            .get(&a.id)
+            .cmp(&names.get(&b.id))
+            .then(a.line.cmp(&b.line))
+    });
+    occurrences
+}
+
+/// Finds the lines of the given content of a note in which the given tag (including the leading `#`) or one of its subtags occurs.
+/// Returns the number of each line, starting at 1, the trimmed line and where in the note it is.
+pub fn find_occurrences(content: &str, tag: &str) -> Vec<(usize, String, TagSource)> {
+    let mut occurrences = Vec::new();
+
+    // the frontmatter is only recognized at the very start of the note
+    let mut in_frontmatter = content.starts_with("---\n");
+    let mut in_tags = false;
+    // the indentation and name of the last item of the tags, which more indented items are subtags of
+    let mut supertag: Option<(usize, String)> = None;
+    // the source of the code block the current line is in, if any
+    let mut code_block = None;
+
+    for (index, line) in content.lines().enumerate() {
+        let source = if in_frontmatter {
+            if index > 0 && line.trim_end() == "---" {
+                in_frontmatter = false;
+                continue;
+            }
+            // the tags are listed on the line of their key or on the indented lines after it
+            if !line.starts_with([' ', '\t', '-']) {
+                in_tags = line.starts_with("tags:");
+                supertag = None;
+            }
+            let indentation = line.len() - line.trim_start().len();
+            let tags = match &supertag {
+                Some((supertag_indentation, supertag)) if indentation > *supertag_indentation => {
+                    frontmatter_tags(line)
+                        .iter()
+                        .map(|subtag| format!("#{}/{}", supertag, subtag.trim_start_matches('#')))
+                        .collect()
+                }
+                _ => {
+                    let tags = frontmatter_tags(line);
+                    if line.trim_start().starts_with('-') {
+                        supertag = tags
+                            .first()
+                            .map(|tag| (indentation, tag.trim_start_matches('#').to_owned()));
+                    }
+                    tags
+                }
+            };
+            (in_tags && tags.iter().any(|other| is_tag(other, tag)))
+                .then_some(TagSource::Frontmatter)
+        } else if line.trim_start().starts_with("```") {
+            code_block = match code_block {
+                Some(_) => None,
+                None if line.trim_start()[3..].trim().starts_with("typst") => {
+                    Some(TagSource::Typst)
+                }
+                None => Some(TagSource::Code),
+            };
+            None
+        } else {
+            find_in_line(line, tag, code_block)
+        };
+
+        if let Some(source) = source {
+            occurrences.push((index + 1, line.trim().to_owned(), source));
+        }
+    }
+
+    occurrences
+}
+
+/// Finds the first occurrence of the given tag or one of its subtags as a word of the given line, within a code block of the given source if any.
+/// Returns where the occurrence is, distinguishing inline code from text.
+fn find_in_line(line: &str, tag: &str, code_block: Option<TagSource>) -> Option<TagSource> {
+    line.match_indices(tag).find_map(|(position, _)| {
+        let before = line[..position].chars().next_back();
+        let after = line[position + tag.len()..].chars().next();
+
+        let source = code_block.unwrap_or_else(|| {
+            // an odd number of backticks before the tag opens inline code
+            if line[..position].matches('`').count() % 2 == 1 {
+                TagSource::Code
+            } else {
+                TagSource::Inline
+            }
+        });
+
+        let ends = match after {
+            None => true,
+            Some(c) if c.is_whitespace() || c == '/' => true,
+            // typst calls functions with arguments in parentheses or brackets
+            Some('(' | '[') => source == TagSource::Typst,
+            // inline code may end right after the tag
+            Some('`') => source == TagSource::Code,
+            _ => false,
+        };
+
+        let starts = match before {
+            None => true,
+            Some(c) if c.is_whitespace() => true,
+            Some('`') => source == TagSource::Code,
+            _ => false,
+        };
+
+        (starts && ends).then_some(source)
+    })
+}
+
+/// Returns the tags listed on the given line of the `tags` of a frontmatter, with the leading `#`.
+/// Items like `os - linux` stand for subtags like `#os/linux`, as when parsing the frontmatter.
+/// Items on more indented lines after an item are subtags of it as well, which the caller takes care of.
+fn frontmatter_tags(line: &str) -> Vec<String> {
+    let items = line.strip_prefix("tags:").unwrap_or(line).trim();
+    let items = items.strip_prefix('-').unwrap_or(items);
+    let items = items.trim().trim_start_matches('[').trim_end_matches(']');
+
+    items
+        .split(',')
+        .map(|item| item.trim().trim_matches(['"', '\'']))
+        .filter(|item| !item.is_empty())
+        .flat_map(|item| {
+            let parts = item.split(" - ").collect_vec();
+            match parts[..] {
+                [tag] => vec![format!("#{}", tag)],
+                [supertag, ref subtags @ ..] => subtags
+                    .iter()
+                    .map(|subtag| format!("#{}/{}", supertag, subtag))
+                    .collect(),
+                [] => vec![],
+            }
+        })
+        .collect()
+}
+
+/// Wether the given tag is the other given tag or one of its subtags.
+fn is_tag(tag: &str, other: &str) -> bool {
+    tag == other
+        || tag
+            .strip_prefix(other)
+            .is_some_and(|rest| rest.starts_with('/'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_occurrences() {
+        let content = "---\ntitle: Shelf\ntags:\n  - book\n  - os - linux\nalias: book\n---\n# Shelf #book\nA #books shelf for #book/fiction and `#book`.\n\n```typst\n#book(title: [Dune])\n```\n```\nlet tag = \"#book\";\n```\n#book\n";
+
+        assert_eq!(
+            find_occurrences(content, "#book"),
+            vec![
+                (4, String::from("- book"), TagSource::Frontmatter),
+                (8, String::from("# Shelf #book"), TagSource::Inline),
+                (
+                    9,
+                    String::from("A #books shelf for #book/fiction and `#book`."),
+                    TagSource::Inline
+                ),
+                (12, String::from("#book(title: [Dune])"), TagSource::Typst),
+                (17, String::from("#book"), TagSource::Inline),
+            ]
+        );
+
+        // subtags in the frontmatter, on the same line or on more indented lines
+        assert_eq!(
+            find_occurrences(content, "#os"),
+            vec![(5, String::from("- os - linux"), TagSource::Frontmatter)]
+        );
+        assert_eq!(
+            find_occurrences(
+                "---\ntags:\n  - files\n    - yaml\n  - yaml\n---\n",
+                "#files/yaml"
+            ),
+            vec![(4, String::from("- yaml"), TagSource::Frontmatter)]
+        );
+        assert_eq!(
+            find_occurrences("Use `#book` in code.\n", "#book"),
+            vec![(1, String::from("Use `#book` in code."), TagSource::Code)]
+        );
+        assert_eq!(
+            find_occurrences("---\ntags: [draft, \"book\"]\n---\n", "#book"),
+            vec![(
+                2,
+                String::from("tags: [draft, \"book\"]"),
+                TagSource::Frontmatter
+            )]
+        );
+    }
+
+    #[test]
+    fn test_tag_occurrences() {
+        let index = NoteIndex::new(
+            crate::io::FileTracker::new(&crate::Config::default(), "./tests".into()).unwrap(),
+            crate::io::HtmlBuilder::new(&crate::Config::default(), "./tests".into()),
+            Default::default(),
+        )
+        .0;
+
+        let counts = tag_counts(&index);
+        assert!(counts.contains(&(String::from("#os"), 1)));
+
+        let occurrences = tag_occurrences(&index, "os");
+        assert!(!occurrences.is_empty());
+        assert!(occurrences
+            .iter()
+            .all(|occurrence| occurrence.context.contains("os")));
+    }
+
+    proptest::proptest! {
+        #[test]
+        fn test_find_arbitrary_occurrences(
+            content in "(---\ntags:)?([-#`/ a-z\\[\\](),\"']{0,16}\n|---\n|```(typst)?\n){0,8}",
+            tag in "#[a-z]{0,2}(/[a-z]{0,2})?",
+        ) {
+            let lines = content.lines().count();
+            for (line, _context, _source) in find_occurrences(&content, &tag) {
+                proptest::prop_assert!((1..=lines).contains(&line));
+            }
+        }
+    }
+}
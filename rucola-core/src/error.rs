@@ -0,0 +1,314 @@
+use ratatui::{style, text, widgets::*};
+use thiserror::Error;
+
+use crate::{i18n, styles};
+
+pub type Result<T> = std::result::Result<T, RucolaError>;
+
+/// The broad categories of errors, shown to the user to indicate where a problem originated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorCategory {
+    /// Reading or writing files.
+    IO,
+    /// Understanding the content of notes.
+    Parse,
+    /// Loading and applying the configuration.
+    Config,
+    /// Creating HTML or PDF files and running external programs.
+    Build,
+    /// Watching the vault for changes.
+    Watcher,
+    /// Interacting with the git repository of the vault.
+    Git,
+    /// Communicating with servers.
+    Network,
+    /// Invalid user input.
+    Input,
+    /// Displaying the user interface.
+    Display,
+}
+
+impl std::fmt::Display for ErrorCategory {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                ErrorCategory::IO => "IO",
+                ErrorCategory::Parse => "Parse",
+                ErrorCategory::Config => "Config",
+                ErrorCategory::Build => "Build",
+                ErrorCategory::Watcher => "Watcher",
+                ErrorCategory::Git => "Git",
+                ErrorCategory::Network => "Network",
+                ErrorCategory::Input => "Input",
+                ErrorCategory::Display => "Display",
+            }
+        )
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum RucolaError {
+    #[error("An IO operation failed: {0}")]
+    IO(#[from] std::io::Error),
+    #[error("Failed to find this note at the expected location: {0}.")]
+    NoteNotFound(String),
+    #[error("Could not read file name of note at {0}.")]
+    NoteNameCannotBeRead(std::path::PathBuf),
+    #[error("Failed to load config file, defaulting: {0}")]
+    ConfigLoad(#[from] confy::ConfyError),
+    #[error("Failed to load vault config file, ignoring it: {0}")]
+    VaultConfigLoad(#[from] toml::de::Error),
+    #[error("Failed to apply vault config file, ignoring it: {0}")]
+    VaultConfigApply(#[from] toml::ser::Error),
+    #[error("Problem in config: {0}")]
+    ConfigProblem(String),
+    #[error("Invalid config override: {0}")]
+    ConfigOverride(String),
+    #[error("Could not find a default application for this file type.")]
+    ApplicationMissing,
+    #[error("Area too small, main window might not display correctly.")]
+    SmallArea,
+    #[error("Invalid input: {0}")]
+    Input(String),
+    #[error("File name prevents renaming with regex: {0}")]
+    RegexError(#[from] regex::Error),
+    #[error("Failed to search for mentions of notes: {0}")]
+    MentionError(regex::Error),
+    #[error("Error when directory walking: {0}")]
+    IgnoreError(#[from] ignore::Error),
+    #[error("Error when when watching files for changes: {0}")]
+    NotifyError(#[from] notify::Error),
+    #[error("Event without accompanying paths: {0:?}")]
+    NotifyEventError(notify::Event),
+    #[error("Failed to create parse options.")]
+    ComrakError,
+    #[error("Failed to parse YAML frontmatter.")]
+    YamlError(#[from] yaml_rust::ScanError),
+    #[error("Failed to find Git Repository.")]
+    GitError(#[from] git2::Error),
+    #[error("External command failed: {0}")]
+    ExternalCommand(String),
+    #[error("Failed to write zip archive: {0}")]
+    ZipError(#[from] zip::result::ZipError),
+    #[error("WebDAV synchronization failed: {0}")]
+    SyncError(String),
+    #[error("Publishing failed: {0}")]
+    PublishError(String),
+    #[error("Summarization failed: {0}")]
+    SummaryError(String),
+    #[error("Sending the note failed: {0}")]
+    MailError(String),
+    #[error("Semantic search failed: {0}")]
+    EmbeddingError(String),
+    #[error("Failed to read stored state at {0}: {1}")]
+    StateFile(std::path::PathBuf, String),
+    #[error("The vault is read-only, as it is locked by {0}.")]
+    ReadOnly(String),
+    #[error("{}{}: {source}", .path.display(), .line.map(|line| format!(":{}", line)).unwrap_or_default())]
+    InFile {
+        /// The file the error occured in.
+        path: std::path::PathBuf,
+        /// The line of the file the error occured in, if known.
+        line: Option<usize>,
+        /// The actual error.
+        source: Box<RucolaError>,
+    },
+}
+
+impl RucolaError {
+    /// Attaches the file (and possibly line) the error occured in, unless the error already carries a location.
+    pub fn in_file(self, path: &std::path::Path, line: Option<usize>) -> Self {
+        match self {
+            RucolaError::InFile { .. } => self,
+            error => RucolaError::InFile {
+                path: path.to_path_buf(),
+                line,
+                source: Box::new(error),
+            },
+        }
+    }
+
+    /// Returns the category of this error.
+    pub fn category(&self) -> ErrorCategory {
+        match self {
+            RucolaError::IO(_)
+            | RucolaError::NoteNotFound(_)
+            | RucolaError::NoteNameCannotBeRead(_)
+            | RucolaError::IgnoreError(_)
+            | RucolaError::ZipError(_)
+            | RucolaError::StateFile(..)
+            | RucolaError::ReadOnly(_) => ErrorCategory::IO,
+            RucolaError::ComrakError
+            | RucolaError::YamlError(_)
+            | RucolaError::RegexError(_)
+            | RucolaError::MentionError(_) => ErrorCategory::Parse,
+            RucolaError::ConfigLoad(_)
+            | RucolaError::VaultConfigLoad(_)
+            | RucolaError::VaultConfigApply(_)
+            | RucolaError::ConfigProblem(_)
+            | RucolaError::ConfigOverride(_)
+            | RucolaError::ApplicationMissing => ErrorCategory::Config,
+            RucolaError::ExternalCommand(_)
+            | RucolaError::SummaryError(_)
+            | RucolaError::EmbeddingError(_) => ErrorCategory::Build,
+            RucolaError::NotifyError(_) | RucolaError::NotifyEventError(_) => {
+                ErrorCategory::Watcher
+            }
+            RucolaError::GitError(_) => ErrorCategory::Git,
+            RucolaError::SyncError(_)
+            | RucolaError::PublishError(_)
+            | RucolaError::MailError(_) => ErrorCategory::Network,
+            RucolaError::Input(_) => ErrorCategory::Input,
+            RucolaError::SmallArea => ErrorCategory::Display,
+            RucolaError::InFile { source, .. } => source.category(),
+        }
+    }
+
+    /// Returns a suggestion how the user could resolve this error, if there is one.
+    pub fn hint(&self) -> Option<&'static str> {
+        match self {
+            RucolaError::IO(_) | RucolaError::ZipError(_) => {
+                Some("Check that the file exists and that rucola may read and write it.")
+            }
+            RucolaError::NoteNotFound(_) => {
+                Some("The note may have been moved or deleted outside of rucola, check the vault.")
+            }
+            RucolaError::NoteNameCannotBeRead(_) => {
+                Some("Rename the file so its name is valid UTF-8.")
+            }
+            RucolaError::ConfigLoad(_) => Some(
+                "Fix the syntax of the config file or delete it to have the defaults recreated.",
+            ),
+            RucolaError::VaultConfigLoad(_) | RucolaError::VaultConfigApply(_) => {
+                Some("Fix the .rucola.toml file in the vault, its keys and values have to match those of the config file.")
+            }
+            RucolaError::ConfigProblem(_) => {
+                Some("Run `rucola config check` to list all problems with suggested fixes.")
+            }
+            RucolaError::ConfigOverride(_) => Some(
+                "Check the RUCOLA_ environment variables and --set arguments, they take the names and values of the config file.",
+            ),
+            RucolaError::ApplicationMissing => {
+                Some("Set the editor, viewer or PDF command in the config file.")
+            }
+            RucolaError::SmallArea => Some("Enlarge the terminal to at least 90x25 characters."),
+            RucolaError::ReadOnly(_) => Some(
+                "Close the other instance of rucola, or take over the lock with Alt+L or by starting rucola with --take-over.",
+            ),
+            RucolaError::RegexError(_) => {
+                Some("Check the regular expressions in the config file, such as tag_regex.")
+            }
+            RucolaError::IgnoreError(_) => {
+                Some("Check that rucola may read all folders of the vault.")
+            }
+            RucolaError::MentionError(_) => Some(
+                "The vault contains too many notes to suggest links to all of them, exclude some of them from the vault.",
+            ),
+            RucolaError::NotifyError(_) | RucolaError::NotifyEventError(_) => Some(
+                "Changes to files will not show up until rucola is restarted. On Linux, the limit of inotify watches may have to be raised.",
+            ),
+            RucolaError::YamlError(_) => {
+                Some("Fix the frontmatter of the note, it has to be valid YAML between two lines of three dashes.")
+            }
+            RucolaError::GitError(_) => Some(
+                "Check that the vault is stored in a git repository with a remote and valid credentials.",
+            ),
+            RucolaError::ExternalCommand(_) => {
+                Some("Check that the configured command is installed and run it by hand on the note.")
+            }
+            RucolaError::SyncError(_) => Some(
+                "Check sync_url and sync_user in the config file and the RUCOLA_SYNC_PASSWORD environment variable.",
+            ),
+            RucolaError::PublishError(_) => Some(
+                "Check the publish options in the config file and the AWS_ACCESS_KEY_ID and AWS_SECRET_ACCESS_KEY environment variables.",
+            ),
+            RucolaError::MailError(_) => Some(
+                "Check mail_from and mail_command or the smtp options in the config file and the RUCOLA_SMTP_PASSWORD environment variable.",
+            ),
+            RucolaError::SummaryError(_) => Some(
+                "Check summary_command or summary_endpoint and summary_model in the config file, and that the command is installed or the server is running.",
+            ),
+            RucolaError::EmbeddingError(_) => Some(
+                "Check that rucola was built with the semantic-search feature, embedding_command or embedding_endpoint and embedding_model in the config file, and that the command is installed or the server is running.",
+            ),
+            RucolaError::Input(_)
+            | RucolaError::ComrakError
+            | RucolaError::StateFile(..) => None,
+            RucolaError::InFile { source, .. } => source.hint(),
+        }
+    }
+
+    /// Describes the error without naming the file it occured in, for places that show the file anyway.
+    pub fn reason(&self) -> String {
+        match self {
+            RucolaError::InFile {
+                line: Some(line),
+                source,
+                ..
+            } => format!("Line {}: {}", line, source),
+            RucolaError::InFile { source, .. } => source.to_string(),
+            error => error.to_string(),
+        }
+    }
+
+    /// Returns a full description of the error, to be copied and e.g. attached to bug reports.
+    pub fn details(&self) -> String {
+        format!(
+            "Category: {}\nError: {}\nHint: {}\nDetails: {:?}",
+            self.category(),
+            self,
+            self.hint().unwrap_or("-"),
+            self
+        )
+    }
+
+    /// Renders the error with its category in the first line and the hint, if any, in the second.
+    pub fn to_ratatui(&self) -> Paragraph<'_> {
+        let error_style = style::Style::new().fg(style::Color::Red);
+        let hint_style = style::Style::new().fg(style::Color::Gray);
+
+        Paragraph::new(vec![
+            text::Line::from(vec![
+                text::Span::styled(
+                    format!("[{}] ", self.category()),
+                    error_style.add_modifier(style::Modifier::BOLD),
+                ),
+                text::Span::styled(self.to_string(), error_style),
+            ]),
+            text::Line::from(
+                [
+                    text::Span::styled(self.hint().unwrap_or_default(), hint_style),
+                    text::Span::styled(
+                        if self.hint().is_some() {
+                            styles::separator()
+                        } else {
+                            ""
+                        },
+                        hint_style,
+                    ),
+                ]
+                .into_iter()
+                .chain(i18n::tr_hotkeys(
+                    "error-copy-hints",
+                    hint_style.add_modifier(style::Modifier::BOLD),
+                    hint_style,
+                ))
+                .collect::<Vec<_>>(),
+            ),
+        ])
+    }
+}
+
+/// Attaches locations to errors of results.
+pub trait ErrorContext<T> {
+    /// Attaches the file (and possibly line) an error occured in.
+    fn in_file(self, path: &std::path::Path, line: Option<usize>) -> Result<T>;
+}
+
+impl<T, E: Into<RucolaError>> ErrorContext<T> for std::result::Result<T, E> {
+    fn in_file(self, path: &std::path::Path, line: Option<usize>) -> Result<T> {
+        self.map_err(|e| e.into().in_file(path, line))
+    }
+}
@@ -0,0 +1,240 @@
+use std::{fs, path};
+
+use crate::error;
+
+/// Words the text, titles and tags of synthetic notes are made of.
+const WORDS: &[&str] = &[
+    "atlas", "chart", "group", "manifold", "vector", "field", "bundle", "space", "metric", "curve",
+    "surface", "tangent", "lie", "algebra", "ring", "module", "sheaf", "scheme", "functor",
+    "category", "limit", "series", "integral", "measure", "norm", "basis", "kernel", "image",
+    "orbit", "action", "symmetry", "torus", "sphere", "knot", "graph", "tree", "path", "cycle",
+    "flow", "energy",
+];
+
+/// The shape of a synthetic vault: How many notes, folders, links and tags it contains.
+/// Vaults of the same shape are identical, as all random choices depend on the seed only.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct VaultShape {
+    /// The number of notes.
+    pub notes: usize,
+    /// The number of folders the notes are spread over, besides the root of the vault.
+    pub folders: usize,
+    /// The average number of links from each note to other notes.
+    pub links_per_note: usize,
+    /// The share of links pointing to notes that do not exist, between 0 and 1.
+    pub broken_links: f64,
+    /// The number of distinct tags. Few tags are used by many notes and many tags by few, as in real vaults.
+    pub tags: usize,
+    /// The average number of tags of each note, half of them in the frontmatter and half in the text.
+    pub tags_per_note: usize,
+    /// The average number of words of each note.
+    pub words_per_note: usize,
+    /// The seed of all random choices.
+    pub seed: u64,
+}
+
+impl Default for VaultShape {
+    fn default() -> Self {
+        Self {
+            notes: 100,
+            folders: 5,
+            links_per_note: 3,
+            broken_links: 0.05,
+            tags: 20,
+            tags_per_note: 2,
+            words_per_note: 200,
+            seed: 0,
+        }
+    }
+}
+
+/// Writes a synthetic vault of the given shape to the given folder, creating it if necessary.
+/// Returns the paths of all written notes.
+pub fn generate_vault(
+    vault_path: &path::Path,
+    shape: &VaultShape,
+) -> error::Result<Vec<path::PathBuf>> {
+    let mut rng = Rng::new(shape.seed);
+
+    let names = (0..shape.notes)
+        .map(|index| format!("{} {} {}", capitalize(rng.word()), rng.word(), index))
+        .collect::<Vec<_>>();
+    let tags = (0..shape.tags)
+        .map(|index| format!("{}{}", rng.word(), index))
+        .collect::<Vec<_>>();
+    // the i-th tag is chosen with a weight of 1 / (i + 1)
+    let tag_weights = (0..shape.tags)
+        .scan(0.0, |sum, index| {
+            *sum += 1.0 / (index + 1) as f64;
+            Some(*sum)
+        })
+        .collect::<Vec<_>>();
+
+    let mut paths = Vec::with_capacity(shape.notes);
+    for name in &names {
+        let folder = match rng.below(shape.folders + 1) {
+            0 => vault_path.to_path_buf(),
+            folder => vault_path.join(format!("folder-{}", folder)),
+        };
+        fs::create_dir_all(&folder)?;
+
+        let mut note_tags = (0..rng.around(shape.tags_per_note))
+            .filter_map(|_| {
+                let total = tag_weights.last()?;
+                let target = rng.fraction() * total;
+                let index = tag_weights.partition_point(|weight| *weight < target);
+                tags.get(index.min(tags.len() - 1))
+            })
+            .collect::<Vec<_>>();
+        note_tags.sort();
+        note_tags.dedup();
+        let (frontmatter_tags, inline_tags) = note_tags.split_at(note_tags.len() / 2);
+
+        let mut content = format!(
+            "---\ntags: [{}]\n---\n",
+            frontmatter_tags
+                .iter()
+                .map(|tag| tag.as_str())
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+        content.push_str(&format!("# {}\n\n", name));
+
+        // links and inline tags are scattered through the words of the text
+        let words = rng.around(shape.words_per_note).max(1);
+        let extras = (0..rng.around(shape.links_per_note))
+            .map(|_| {
+                if rng.fraction() < shape.broken_links {
+                    format!("[[Missing {}]]", rng.below(shape.notes.max(1)))
+                } else if rng.below(2) == 0 {
+                    format!("[[{}]]", rng.choose(&names))
+                } else {
+                    format!(
+                        "[{}]({})",
+                        rng.word(),
+                        crate::data::name_to_id(rng.choose(&names).as_str())
+                    )
+                }
+            })
+            .chain(inline_tags.iter().map(|tag| format!("#{}", tag)))
+            .collect::<Vec<_>>();
+        let mut extras = extras
+            .into_iter()
+            .map(|extra| (rng.below(words), extra))
+            .collect::<Vec<_>>();
+        extras.sort();
+
+        let mut extras = extras.into_iter().peekable();
+        for word in 0..words {
+            content.push_str(rng.word());
+            while let Some((_, extra)) = extras.next_if(|(position, _)| *position == word) {
+                content.push(' ');
+                content.push_str(&extra);
+            }
+            // paragraphs of about 50 words
+            content.push_str(if rng.below(50) == 0 { "\n\n" } else { " " });
+        }
+        content.push('\n');
+
+        let path = folder.join(format!("{}.md", name));
+        fs::write(&path, content)?;
+        paths.push(path);
+    }
+
+    Ok(paths)
+}
+
+/// Turns the first letter of the given word to uppercase.
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+/// A small pseudo random number generator (SplitMix64), so synthetic vaults do not depend on the platform or other crates.
+struct Rng(u64);
+
+impl Rng {
+    /// Creates a generator with the given seed.
+    fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+
+    /// Returns the next random number.
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// Returns a random number below the given bound, or 0 if it is 0.
+    fn below(&mut self, bound: usize) -> usize {
+        match bound {
+            0 => 0,
+            bound => (self.next() % bound as u64) as usize,
+        }
+    }
+
+    /// Returns a random number between 0 and twice the given average.
+    fn around(&mut self, average: usize) -> usize {
+        self.below(2 * average + 1)
+    }
+
+    /// Returns a random number between 0 (inclusive) and 1 (exclusive).
+    fn fraction(&mut self) -> f64 {
+        (self.next() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// Returns a random word.
+    fn word(&mut self) -> &'static str {
+        WORDS[self.below(WORDS.len())]
+    }
+
+    /// Returns a random element of the given non-empty slice.
+    fn choose<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len())]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generate_vault() {
+        let shape = VaultShape {
+            notes: 50,
+            seed: 7,
+            ..Default::default()
+        };
+
+        let vault = testdir::testdir!();
+        let paths = generate_vault(&vault.join("first"), &shape).unwrap();
+        assert_eq!(paths.len(), 50);
+
+        // the same shape leads to the same vault
+        let other = generate_vault(&vault.join("second"), &shape).unwrap();
+        for (path, other) in paths.iter().zip(other) {
+            assert_eq!(
+                std::fs::read_to_string(path).unwrap(),
+                std::fs::read_to_string(other).unwrap()
+            );
+        }
+
+        let config = crate::Config::default();
+        let index = crate::data::NoteIndex::new(
+            crate::io::FileTracker::new(&config, vault.join("first")).unwrap(),
+            crate::io::HtmlBuilder::new(&config, vault.join("first")),
+            Default::default(),
+        )
+        .0;
+        assert_eq!(index.notes().count(), 50);
+        assert!(index.notes().any(|note| !note.links.is_empty()));
+        assert!(index.notes().any(|note| !note.tags.is_empty()));
+        assert!(!index.dangling_links().is_empty());
+    }
+}
@@ -0,0 +1,295 @@
+use std::sync::{LazyLock, RwLock};
+
+use fluent_bundle::{concurrent::FluentBundle, FluentArgs, FluentResource, FluentValue};
+use ratatui::{style::Style, text::Span};
+
+use crate::error;
+
+/// The built-in catalogs of UI strings, by language. English contains all messages, other languages fall back to it.
+const BUILT_IN: &[(&str, &str)] = &[
+    ("en", include_str!("../locales/en.ftl")),
+    ("de", include_str!("../locales/de.ftl")),
+];
+
+/// The catalog all UI strings are looked up in, English until another one is installed.
+static CATALOG: LazyLock<RwLock<Catalog>> = LazyLock::new(|| RwLock::new(Catalog::default()));
+
+/// A catalog of the strings of the user interface in the fluent format, see [projectfluent.org](https://projectfluent.org).
+/// Messages missing in the chosen language are taken from the English catalog.
+pub struct Catalog {
+    /// The bundles messages are looked up in, the chosen language first and English last.
+    bundles: Vec<FluentBundle<FluentResource>>,
+}
+
+impl Default for Catalog {
+    fn default() -> Self {
+        let mut english = bundle("en".parse().expect("Built-in language to be valid."));
+        english
+            .add_resource(
+                FluentResource::try_new(BUILT_IN[0].1.to_owned())
+                    .expect("Built-in catalog to be valid."),
+            )
+            .expect("Built-in catalog to contain no duplicates.");
+        Self {
+            bundles: vec![english],
+        }
+    }
+}
+
+impl Catalog {
+    /// Creates the catalog of the language set in the given config, or of the system if none is set.
+    /// A file `<language>.ftl` in the config folder adds to or replaces the messages of the built-in catalog, or translates the UI into a new language.
+    /// Fails if the configured language has neither a built-in catalog nor such a file, or the file is not valid.
+    pub fn new(config: &crate::Config) -> error::Result<Self> {
+        let mut catalog = Self::default();
+
+        let Some(language) = config.language.clone().or_else(system_language) else {
+            return Ok(catalog);
+        };
+        let id = language.parse().map_err(|_| {
+            error::RucolaError::ConfigProblem(format!("Invalid language: {}", language))
+        })?;
+        let mut translation = bundle(id);
+
+        let built_in = BUILT_IN
+            .iter()
+            .find(|(built_in, _)| language.split(['-', '_']).next() == Some(built_in));
+        if let Some((_, content)) = built_in {
+            translation
+                .add_resource(
+                    FluentResource::try_new(content.to_string())
+                        .expect("Built-in catalog to be valid."),
+                )
+                .expect("Built-in catalog to contain no duplicates.");
+        }
+
+        let user_file = confy::get_configuration_file_path("rucola", language.as_str())
+            .ok()
+            .map(|mut path| {
+                // confy appends .toml
+                path.set_extension("ftl");
+                path
+            })
+            .filter(|path| path.exists());
+
+        match (built_in, user_file) {
+            (_, Some(path)) => {
+                let content = std::fs::read_to_string(&path)?;
+                let resource = FluentResource::try_new(content).map_err(|(_, errors)| {
+                    error::RucolaError::ConfigProblem(format!(
+                        "Invalid translation {}: {}",
+                        path.display(),
+                        errors.first().map(|e| e.to_string()).unwrap_or_default()
+                    ))
+                })?;
+                translation.add_resource_overriding(resource);
+            }
+            (Some(("en", _)), None) => return Ok(catalog),
+            (Some(_), None) => {}
+            // an unknown language of the system is no mistake of the user
+            (None, None) if config.language.is_none() => return Ok(catalog),
+            (None, None) => {
+                return Err(error::RucolaError::ConfigProblem(format!(
+                    "There is no translation into {}, falling back to English.",
+                    language
+                )))
+            }
+        }
+
+        catalog.bundles.insert(0, translation);
+        Ok(catalog)
+    }
+
+    /// Formats the message with the given id and arguments.
+    /// Returns the id itself if no bundle contains the message, so missing messages are noticed but do not break the UI.
+    fn format(&self, id: &str, args: Option<&FluentArgs>) -> String {
+        self.bundles
+            .iter()
+            .find_map(|bundle| {
+                let pattern = bundle.get_message(id)?.value()?;
+                let mut errors = Vec::new();
+                Some(
+                    bundle
+                        .format_pattern(pattern, args, &mut errors)
+                        .into_owned(),
+                )
+            })
+            .unwrap_or_else(|| id.to_owned())
+    }
+}
+
+/// Creates an empty bundle of the given language.
+fn bundle(language: unic_langid::LanguageIdentifier) -> FluentBundle<FluentResource> {
+    let mut bundle = FluentBundle::new_concurrent(vec![language]);
+    // the isolation marks are not displayed correctly by all terminals
+    bundle.set_use_isolating(false);
+    bundle
+}
+
+/// Reads the language of the system from the locale environment variables, e.g. `de` from `LANG=de_DE.UTF-8`.
+fn system_language() -> Option<String> {
+    ["LC_ALL", "LC_MESSAGES", "LANG"]
+        .into_iter()
+        .filter_map(|variable| std::env::var(variable).ok())
+        .find(|value| !value.is_empty())
+        .and_then(|value| value.split(['_', '.', '@']).next().map(str::to_owned))
+        .filter(|language| language != "C" && language != "POSIX")
+}
+
+/// Replaces the catalog all UI strings are looked up in.
+pub fn set_catalog(catalog: Catalog) {
+    if let Ok(mut current) = CATALOG.write() {
+        *current = catalog;
+    }
+}
+
+/// Returns the UI string with the given id in the current language.
+pub fn tr(id: &str) -> String {
+    tr_args(id, &[])
+}
+
+/// Returns the UI string with the given id in the current language, with its variables replaced by the given values.
+pub fn tr_args(id: &str, args: &[(&str, FluentValue)]) -> String {
+    let args = args
+        .iter()
+        .map(|(name, value)| (*name, value.clone()))
+        .collect::<FluentArgs>();
+    match CATALOG.read() {
+        Ok(catalog) => catalog.format(id, Some(&args)),
+        Err(_) => id.to_owned(),
+    }
+}
+
+/// Returns the UI string with the given id in the current language as spans, see [`hotkeys`].
+pub fn tr_hotkeys(id: &str, key_style: Style, text_style: Style) -> Vec<Span<'static>> {
+    hotkeys(&tr(id), key_style, text_style)
+}
+
+/// Splits the given text into spans, styling the hotkeys enclosed in square brackets with the key style and all other text with the text style.
+/// For screen readers, the text is rewritten first, see [`crate::styles::set_screen_reader`].
+/// ```
+/// # use ratatui::style::{Style, Stylize};
+/// # use rucola_core::i18n::hotkeys;
+/// let spans = hotkeys("[F]ilter──[C]lose", Style::new().bold(), Style::new());
+/// assert_eq!(spans.len(), 4);
+/// assert_eq!(spans[0].content, "F");
+/// assert_eq!(spans[1].content, "ilter──");
+/// ```
+pub fn hotkeys(text: &str, key_style: Style, text_style: Style) -> Vec<Span<'static>> {
+    let text = if crate::styles::screen_reader() {
+        crate::styles::spoken_hotkeys(text)
+    } else {
+        text.to_owned()
+    };
+
+    let mut spans = Vec::new();
+    let mut rest = text.as_str();
+    while let Some((before, key, after)) = rest.split_once('[').and_then(|(before, rest)| {
+        rest.split_once(']')
+            .map(|(key, after)| (before, key, after))
+    }) {
+        if !before.is_empty() {
+            spans.push(Span::styled(before.to_owned(), text_style));
+        }
+        spans.push(Span::styled(key.to_owned(), key_style));
+        rest = after;
+    }
+    if !rest.is_empty() {
+        spans.push(Span::styled(rest.to_owned(), text_style));
+    }
+    spans
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Returns the ids of all messages of the given catalog.
+    fn message_ids(content: &str) -> Vec<String> {
+        // make sure the catalog is valid first
+        assert!(FluentResource::try_new(content.to_owned()).is_ok());
+        let regex = regex::Regex::new(r"(?m)^([a-zA-Z][a-zA-Z0-9_-]*) =").unwrap();
+        regex
+            .captures_iter(content)
+            .map(|captures| captures[1].to_owned())
+            .collect()
+    }
+
+    #[test]
+    fn test_built_in_catalogs() {
+        let english = message_ids(BUILT_IN[0].1);
+        assert!(english.len() > 100);
+
+        // all translations are valid and translate exactly the english messages
+        for (language, content) in BUILT_IN {
+            let mut ids = message_ids(content);
+            ids.sort();
+            let mut expected = english.clone();
+            expected.sort();
+            assert_eq!(ids, expected, "{}", language);
+        }
+    }
+
+    #[test]
+    fn test_catalog() {
+        let catalog = Catalog::new(&crate::Config {
+            language: Some(String::from("de")),
+            ..Default::default()
+        })
+        .unwrap();
+        assert_eq!(catalog.bundles.len(), 2);
+        assert_eq!(catalog.format("display-title-links", None), "Links");
+        assert_eq!(
+            catalog.format("display-title-statistics", None),
+            "Statistiken"
+        );
+        assert_eq!(catalog.format("no-such-message", None), "no-such-message");
+
+        let args = FluentArgs::from_iter([("count", 3)]);
+        assert_eq!(
+            Catalog::default().format("notes-count", Some(&args)),
+            "3 Notes"
+        );
+        let args = FluentArgs::from_iter([("count", 1)]);
+        assert_eq!(catalog.format("notes-count", Some(&args)), "1 Notiz");
+
+        // regions of built-in languages use their catalog
+        let catalog = Catalog::new(&crate::Config {
+            language: Some(String::from("de-AT")),
+            ..Default::default()
+        })
+        .unwrap();
+        assert_eq!(
+            catalog.format("display-title-statistics", None),
+            "Statistiken"
+        );
+
+        assert!(Catalog::new(&crate::Config {
+            language: Some(String::from("tlh")),
+            ..Default::default()
+        })
+        .is_err());
+    }
+
+    #[test]
+    fn test_hotkeys() {
+        let key = Style::new().fg(ratatui::style::Color::Red);
+        let text = Style::new().fg(ratatui::style::Color::Blue);
+
+        assert_eq!(
+            hotkeys("[F]ilter──[C]lose", key, text),
+            vec![
+                Span::styled("F", key),
+                Span::styled("ilter──", text),
+                Span::styled("C", key),
+                Span::styled("lose", text),
+            ]
+        );
+        assert_eq!(
+            hotkeys("Schließen [C]", key, text),
+            vec![Span::styled("Schließen ", text), Span::styled("C", key)]
+        );
+        // unclosed brackets are kept as text
+        assert_eq!(hotkeys("[x", key, text), vec![Span::styled("[x", text)]);
+    }
+}
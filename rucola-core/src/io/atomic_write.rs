@@ -0,0 +1,126 @@
+use std::{
+    fs,
+    io::Write,
+    path, process,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+/// Counts the files written by this process, so concurrent writes of the same file use different temporary files.
+static COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+/// How carefully written files are flushed to the disk, trading speed for safety against power losses.
+/// Files are always replaced atomically, so they are never left truncated, but may lose their most recent changes.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum Durability {
+    /// Leave flushing to the operating system.
+    Fast,
+    /// Flush the content of a file before it replaces the old one.
+    #[default]
+    File,
+    /// Also flush the folder containing the file, so the replacement itself survives a power loss.
+    Full,
+}
+
+/// Replaces the content of the file at the given path by writing it to a temporary file next to it first, which is then moved over the file.
+/// Thus, an interrupted write, e.g. by a power loss, leaves either the old or the new content, but never a truncated file.
+/// Symlinks are followed, so the file they point to is replaced instead of the link.
+pub fn write_atomically(
+    path: &path::Path,
+    content: impl AsRef<[u8]>,
+    durability: Durability,
+) -> std::io::Result<()> {
+    let path = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+
+    // the temporary file is hidden, so it is not mistaken for a note
+    let file_name = path.file_name().unwrap_or_default();
+    let mut name = std::ffi::OsString::new();
+    if !file_name.to_string_lossy().starts_with('.') {
+        name.push(".");
+    }
+    name.push(file_name);
+    name.push(format!(
+        ".{}.{}.tmp",
+        process::id(),
+        COUNTER.fetch_add(1, Ordering::Relaxed)
+    ));
+    let temporary = path.with_file_name(name);
+
+    let result = fs::File::create(&temporary)
+        .and_then(|mut file| {
+            file.write_all(content.as_ref())?;
+            match durability {
+                Durability::Fast => Ok(()),
+                Durability::File | Durability::Full => file.sync_all(),
+            }
+        })
+        // keep the permissions of the replaced file
+        .and_then(|_| match fs::metadata(&path) {
+            Ok(metadata) => fs::set_permissions(&temporary, metadata.permissions()),
+            Err(_) => Ok(()),
+        })
+        .and_then(|_| fs::rename(&temporary, &path));
+
+    if result.is_err() {
+        let _ = fs::remove_file(&temporary);
+        return result;
+    }
+
+    // folders can only be opened and flushed like files on unix
+    #[cfg(unix)]
+    if durability == Durability::Full {
+        if let Some(parent) = path.parent() {
+            fs::File::open(parent)?.sync_all()?;
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_write_atomically() {
+        let tmp = testdir::testdir!();
+        let path = tmp.join("Atlas.md");
+        std::fs::write(&path, "# Atlas\nA long text that is replaced.").unwrap();
+
+        write_atomically(&path, "# Atlas", Durability::File).unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "# Atlas");
+        // no temporary files are left behind
+        assert_eq!(std::fs::read_dir(&tmp).unwrap().count(), 1);
+
+        // new files are created
+        write_atomically(&tmp.join(".state.json"), "{}", Durability::Full).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(tmp.join(".state.json")).unwrap(),
+            "{}"
+        );
+        write_atomically(&tmp.join("Chart.md"), "# Chart", Durability::Fast).unwrap();
+        assert_eq!(std::fs::read_dir(&tmp).unwrap().count(), 3);
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+
+            // permissions are kept
+            std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o600)).unwrap();
+            write_atomically(&path, "# Chart", Durability::Full).unwrap();
+            assert_eq!(
+                std::fs::metadata(&path).unwrap().permissions().mode() & 0o777,
+                0o600
+            );
+
+            // links are kept, replacing the file they point to
+            let link = tmp.join("Link.md");
+            std::os::unix::fs::symlink(&path, &link).unwrap();
+            write_atomically(&link, "# Map", Durability::File).unwrap();
+            assert!(std::fs::symlink_metadata(&link)
+                .unwrap()
+                .file_type()
+                .is_symlink());
+            assert_eq!(std::fs::read_to_string(&path).unwrap(), "# Map");
+        }
+    }
+}
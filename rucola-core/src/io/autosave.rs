@@ -0,0 +1,122 @@
+use std::{
+    cell::RefCell,
+    collections::HashMap,
+    path,
+    rc::Rc,
+    time::{Duration, Instant},
+};
+
+/// Edits of notes made from the UI that have not been written yet.
+/// Every edit postpones writing its note until no further edit was made to it for the configured delay, so a series of quick edits is written only once.
+/// The edits are shared between clones, so all screens see the same content of a note.
+#[derive(Debug, Clone)]
+pub struct Autosave {
+    /// How long a note has to stay unchanged after an edit before it is written.
+    delay: Duration,
+    /// The edited content of each note, with the time of its last edit.
+    pending: Rc<RefCell<HashMap<path::PathBuf, (String, Instant)>>>,
+}
+
+impl Autosave {
+    /// Creates an empty set of edits, each written the given delay after the last edit of its note.
+    pub fn new(delay: Duration) -> Self {
+        Self {
+            delay,
+            pending: Rc::new(RefCell::new(HashMap::new())),
+        }
+    }
+
+    /// Stores the given content as the new content of the note at the given path, to be written once the delay has passed.
+    pub fn edit(&self, path: &path::Path, content: String) {
+        self.pending
+            .borrow_mut()
+            .insert(path.to_path_buf(), (content, Instant::now()));
+    }
+
+    /// Returns the edited content of the note at the given path, if it has not been written yet.
+    pub fn content(&self, path: &path::Path) -> Option<String> {
+        self.pending
+            .borrow()
+            .get(path)
+            .map(|(content, _edited)| content.clone())
+    }
+
+    /// Forgets the edits of the note at the given path, e.g. because it was written in the meantime.
+    pub fn discard(&self, path: &path::Path) {
+        self.pending.borrow_mut().remove(path);
+    }
+
+    /// Checks if there are edits that have not been written yet.
+    pub fn is_dirty(&self) -> bool {
+        !self.pending.borrow().is_empty()
+    }
+
+    /// Returns how long it takes until the next edit is due to be written, if there are any edits.
+    pub fn due_in(&self) -> Option<Duration> {
+        self.pending
+            .borrow()
+            .values()
+            .map(|(_content, edited)| self.delay.saturating_sub(edited.elapsed()))
+            .min()
+    }
+
+    /// Removes and returns all edits whose delay has passed, or all edits at all.
+    pub fn take(&self, all: bool) -> Vec<(path::PathBuf, String)> {
+        let mut pending = self.pending.borrow_mut();
+
+        let due = pending
+            .iter()
+            .filter(|(_path, (_content, edited))| all || edited.elapsed() >= self.delay)
+            .map(|(path, _)| path.clone())
+            .collect::<Vec<_>>();
+
+        due.into_iter()
+            .flat_map(|path| {
+                pending
+                    .remove(&path)
+                    .map(|(content, _edited)| (path, content))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_autosave() {
+        let autosave = Autosave::new(Duration::from_millis(50));
+        let path = path::Path::new("Atlas.md");
+
+        assert!(!autosave.is_dirty());
+        assert_eq!(autosave.due_in(), None);
+
+        autosave.edit(path, String::from("# Atlas"));
+        autosave.edit(path, String::from("# Atlas\n#math"));
+
+        // only the last edit is kept, and it is not due yet
+        assert!(autosave.is_dirty());
+        assert_eq!(autosave.content(path).unwrap(), "# Atlas\n#math");
+        assert!(autosave.due_in().unwrap() > Duration::ZERO);
+        assert!(autosave.take(false).is_empty());
+
+        // clones share the edits
+        std::thread::sleep(Duration::from_millis(60));
+        assert_eq!(autosave.clone().due_in(), Some(Duration::ZERO));
+        assert_eq!(
+            autosave.take(false),
+            vec![(path.to_path_buf(), String::from("# Atlas\n#math"))]
+        );
+        assert!(!autosave.is_dirty());
+
+        // all edits can be taken before they are due
+        autosave.edit(path, String::from("# Chart"));
+        assert_eq!(autosave.take(true).len(), 1);
+
+        autosave.edit(path, String::from("# Map"));
+        autosave.discard(path);
+        assert!(!autosave.is_dirty());
+        assert_eq!(autosave.content(path), None);
+    }
+}
@@ -0,0 +1,201 @@
+use std::{fs, io::Write, path};
+
+use crate::error;
+
+/// The archive formats backups of the vault can be written in.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum BackupFormat {
+    /// A zip archive.
+    #[default]
+    Zip,
+    /// A tar archive compressed with zstd.
+    TarZst,
+}
+
+impl BackupFormat {
+    /// The file extension of archives in this format.
+    fn extension(&self) -> &'static str {
+        match self {
+            BackupFormat::Zip => "zip",
+            BackupFormat::TarZst => "tar.zst",
+        }
+    }
+}
+
+/// Struct that keeps configuration details for creating backup archives of the vault.
+#[derive(Debug, Clone)]
+pub struct BackupManager {
+    /// Path to the vault to back up.
+    vault_path: path::PathBuf,
+    /// The folder backups are written to.
+    backup_path: path::PathBuf,
+    /// The format of created archives.
+    format: BackupFormat,
+    /// Wether to leave out the generated HTML and PDF files.
+    exclude_generated: bool,
+}
+
+impl BackupManager {
+    pub fn new(config: &crate::Config, vault_path: path::PathBuf) -> Self {
+        Self {
+            // relative locations are interpreted relative to the vault
+            backup_path: vault_path.join(config.backup_location.as_deref().unwrap_or(".backup")),
+            vault_path,
+            format: config.backup_format,
+            exclude_generated: config.backup_exclude_generated,
+        }
+    }
+
+    /// Writes all files of the vault to a new, timestamped archive in the backup folder and returns its path.
+    pub fn create_backup(&self) -> error::Result<path::PathBuf> {
+        fs::create_dir_all(&self.backup_path)?;
+
+        let vault_name = self
+            .vault_path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .unwrap_or("vault");
+
+        let archive_path = self.backup_path.join(format!(
+            "{}-{}.{}",
+            vault_name,
+            chrono::Local::now().format("%Y-%m-%d-%H%M%S"),
+            self.format.extension()
+        ));
+
+        let files = self.files()?;
+        let file = fs::File::create(&archive_path)?;
+
+        match self.format {
+            BackupFormat::Zip => {
+                let mut zip = zip::ZipWriter::new(file);
+                for (path, name) in files {
+                    zip.start_file(name, zip::write::SimpleFileOptions::default())?;
+                    zip.write_all(&fs::read(path)?)?;
+                }
+                zip.finish()?;
+            }
+            BackupFormat::TarZst => {
+                let mut tar = tar::Builder::new(zstd::Encoder::new(file, 0)?);
+                for (path, name) in files {
+                    tar.append_path_with_name(path, name)?;
+                }
+                tar.into_inner()?.finish()?;
+            }
+        }
+
+        Ok(archive_path)
+    }
+
+    /// Lists all files to back up, with their names within the archive.
+    fn files(&self) -> error::Result<Vec<(path::PathBuf, String)>> {
+        let excluded = [
+            Some(self.backup_path.clone()),
+            self.exclude_generated
+                .then(|| self.vault_path.join(".html")),
+            self.exclude_generated.then(|| self.vault_path.join(".pdf")),
+            self.exclude_generated
+                .then(|| self.vault_path.join(".print")),
+            self.exclude_generated
+                .then(|| self.vault_path.join(".slides")),
+        ]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>();
+
+        let mut files = Vec::new();
+
+        for entry in ignore::WalkBuilder::new(&self.vault_path)
+            .standard_filters(false)
+            .filter_entry(move |entry| !excluded.iter().any(|ex| entry.path() == ex))
+            .build()
+        {
+            let entry = entry?;
+            if !entry
+                .file_type()
+                .is_some_and(|file_type| file_type.is_file())
+            {
+                continue;
+            }
+
+            if let Ok(relative) = entry.path().strip_prefix(&self.vault_path) {
+                // archives always use forward slashes
+                let name = relative
+                    .components()
+                    .map(|component| component.as_os_str().to_string_lossy())
+                    .collect::<Vec<_>>()
+                    .join("/");
+                files.push((entry.path().to_path_buf(), name));
+            }
+        }
+
+        files.sort_by(|(_, a), (_, b)| a.cmp(b));
+
+        Ok(files)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BackupFormat, BackupManager};
+
+    /// Creates a small vault with a note, a nested note and generated files.
+    fn vault() -> std::path::PathBuf {
+        let vault = testdir::testdir!().join("vault");
+        std::fs::create_dir_all(vault.join("sub")).unwrap();
+        std::fs::create_dir_all(vault.join(".html")).unwrap();
+        std::fs::write(vault.join("Atlas.md"), "# Atlas").unwrap();
+        std::fs::write(vault.join("sub/Chart.md"), "# Chart").unwrap();
+        std::fs::write(vault.join(".html/atlas.html"), "<h1>Atlas</h1>").unwrap();
+        vault
+    }
+
+    #[test]
+    fn test_zip_backup() {
+        let vault = vault();
+        let config = crate::Config {
+            backup_format: BackupFormat::Zip,
+            ..Default::default()
+        };
+        std::fs::create_dir_all(vault.join(".backup")).unwrap();
+        std::fs::write(vault.join(".backup/earlier.zip"), "").unwrap();
+
+        let archive = BackupManager::new(&config, vault.clone())
+            .create_backup()
+            .unwrap();
+        assert!(archive.starts_with(vault.join(".backup")));
+        assert!(archive.to_string_lossy().ends_with(".zip"));
+
+        let archive = zip::ZipArchive::new(std::fs::File::open(&archive).unwrap()).unwrap();
+        let mut names = archive.file_names().collect::<Vec<_>>();
+        names.sort();
+        // Neither generated files nor earlier backups are included.
+        assert_eq!(names, vec!["Atlas.md", "sub/Chart.md"]);
+    }
+
+    #[test]
+    fn test_tar_zst_backup() {
+        let vault = vault();
+        let config = crate::Config {
+            backup_format: BackupFormat::TarZst,
+            backup_exclude_generated: false,
+            backup_location: Some(String::from("../backups")),
+            ..Default::default()
+        };
+
+        let archive = BackupManager::new(&config, vault.clone())
+            .create_backup()
+            .unwrap();
+        assert!(archive.to_string_lossy().ends_with(".tar.zst"));
+        assert_eq!(archive.parent(), Some(vault.join("../backups").as_path()));
+
+        let mut tar =
+            tar::Archive::new(zstd::Decoder::new(std::fs::File::open(&archive).unwrap()).unwrap());
+        let names = tar
+            .entries()
+            .unwrap()
+            .map(|entry| entry.unwrap().path().unwrap().to_string_lossy().to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec![".html/atlas.html", "Atlas.md", "sub/Chart.md"]);
+    }
+}
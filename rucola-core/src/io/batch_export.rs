@@ -0,0 +1,813 @@
+use std::{fs, io::Write, path, thread, time};
+
+use crate::{data, error, i18n, io};
+
+/// How long to wait for PDF commands to finish when none has finished yet.
+const POLL_INTERVAL: time::Duration = time::Duration::from_millis(10);
+
+/// The ways in which a set of notes can be exported at once.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ExportKind {
+    /// Build the HTML files of all notes.
+    Html,
+    /// Build the PDF files of all notes.
+    Pdf,
+    /// Build the print-friendly HTML files of all notes.
+    PrintHtml,
+    /// Build the print-friendly PDF files of all notes.
+    PrintPdf,
+    /// Build a reveal.js slide deck of every note.
+    RevealSlides,
+    /// Build a typst slide deck of every note and compile it into a PDF file.
+    TypstSlides,
+    /// Copy the note files to the given folder, keeping their paths relative to the vault.
+    Copy(path::PathBuf),
+    /// Write the note files to a zip archive at the given path, keeping their paths relative to the vault.
+    Zip(path::PathBuf),
+    /// Write the flashcards found in the notes to an Anki import file at the given path.
+    Anki(path::PathBuf),
+}
+
+impl ExportKind {
+    /// Wether this kind of export builds PDF files with external commands.
+    pub fn builds_pdf(&self) -> bool {
+        matches!(
+            self,
+            ExportKind::Pdf | ExportKind::PrintPdf | ExportKind::TypstSlides
+        )
+    }
+
+    /// Wether this kind of export builds HTML or PDF files, instead of exporting the note files themselves.
+    pub fn builds_files(&self) -> bool {
+        !matches!(
+            self,
+            ExportKind::Copy(_) | ExportKind::Zip(_) | ExportKind::Anki(_)
+        )
+    }
+
+    /// Returns the path of the file this kind of export builds for the note with the given name, if it builds files.
+    fn target(&self, name: &str, vault_path: &path::Path) -> Option<path::PathBuf> {
+        match self {
+            ExportKind::Html => Some(super::html_builder::name_to_html_path(name, vault_path)),
+            ExportKind::Pdf => Some(super::pdf_builder::name_to_pdf_path(name, vault_path)),
+            ExportKind::PrintHtml => {
+                Some(super::html_builder::name_to_print_path(name, vault_path))
+            }
+            ExportKind::PrintPdf => {
+                Some(super::pdf_builder::name_to_print_pdf_path(name, vault_path))
+            }
+            ExportKind::RevealSlides => {
+                Some(super::html_builder::name_to_slides_path(name, vault_path))
+            }
+            ExportKind::TypstSlides => Some(super::pdf_builder::name_to_slides_pdf_path(
+                name, vault_path,
+            )),
+            ExportKind::Copy(_) | ExportKind::Zip(_) | ExportKind::Anki(_) => None,
+        }
+    }
+}
+
+impl std::fmt::Display for ExportKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ExportKind::Html => write!(f, "{}", i18n::tr("export-kind-html")),
+            ExportKind::Pdf => write!(f, "{}", i18n::tr("export-kind-pdf")),
+            ExportKind::PrintHtml => write!(f, "{}", i18n::tr("export-kind-print-html")),
+            ExportKind::PrintPdf => write!(f, "{}", i18n::tr("export-kind-print-pdf")),
+            ExportKind::RevealSlides => write!(f, "{}", i18n::tr("export-kind-reveal-slides")),
+            ExportKind::TypstSlides => write!(f, "{}", i18n::tr("export-kind-typst-slides")),
+            ExportKind::Copy(target) => write!(
+                f,
+                "{}",
+                i18n::tr_args(
+                    "export-kind-copy",
+                    &[("target", target.display().to_string().into())]
+                )
+            ),
+            ExportKind::Zip(target) => write!(
+                f,
+                "{}",
+                i18n::tr_args(
+                    "export-kind-zip",
+                    &[("target", target.display().to_string().into())]
+                )
+            ),
+            ExportKind::Anki(target) => write!(
+                f,
+                "{}",
+                i18n::tr_args(
+                    "export-kind-anki",
+                    &[("target", target.display().to_string().into())]
+                )
+            ),
+        }
+    }
+}
+
+/// Exports a set of notes one at a time, so progress can be reported in between.
+/// Failures of single notes do not stop the export, but are collected to be reported at the end.
+pub struct BatchExport {
+    /// What to do with the notes.
+    kind: ExportKind,
+    /// The notes to export.
+    notes: Vec<data::Note>,
+    /// The number of notes already handled.
+    done: usize,
+    /// The display names of notes that failed to export, with the reason.
+    failures: Vec<(String, String)>,
+    /// The archive written to, when zipping.
+    zip: Option<zip::ZipWriter<fs::File>>,
+    /// The Anki import file written to, when exporting flashcards.
+    anki: Option<std::io::BufWriter<fs::File>>,
+    /// Path to the vault the notes are from.
+    vault_path: path::PathBuf,
+    /// The builder used to create HTML files.
+    builder: io::HtmlBuilder,
+    /// The builder used to create PDF files.
+    pdf_builder: io::PdfBuilder,
+    /// The ids of missing notes to create placeholder pages for when building HTML files, with the notes linking to them.
+    placeholders: Vec<(String, Vec<data::Note>)>,
+    /// The pool the PDF commands run in, in parallel.
+    pool: io::ProcessPool,
+    /// The number of notes whose PDF commands have been handed to the pool.
+    submitted: usize,
+    /// The index queries within the notes are answered from, if any.
+    index: Option<data::NoteIndexContainer>,
+    /// The folder the built HTML or PDF files are copied to once all are built, if any.
+    output: Option<path::PathBuf>,
+}
+
+impl BatchExport {
+    /// Prepares an export of the given notes.
+    /// Relative target paths are interpreted relative to the vault.
+    pub fn new(
+        kind: ExportKind,
+        notes: Vec<data::Note>,
+        vault_path: path::PathBuf,
+        builder: io::HtmlBuilder,
+        pdf_builder: io::PdfBuilder,
+    ) -> error::Result<Self> {
+        let kind = match kind {
+            ExportKind::Copy(target) => {
+                let target = vault_path.join(target);
+                fs::create_dir_all(&target)?;
+                ExportKind::Copy(target)
+            }
+            ExportKind::Zip(target) => ExportKind::Zip(vault_path.join(target)),
+            ExportKind::Anki(target) => ExportKind::Anki(vault_path.join(target)),
+            kind => kind,
+        };
+
+        let zip = match &kind {
+            ExportKind::Zip(target) => Some(zip::ZipWriter::new(fs::File::create(target)?)),
+            _ => None,
+        };
+
+        let anki = match &kind {
+            ExportKind::Anki(target) => {
+                let mut file = std::io::BufWriter::new(fs::File::create(target)?);
+                file.write_all(data::ANKI_HEADER.as_bytes())?;
+                Some(file)
+            }
+            _ => None,
+        };
+
+        let mut export = Self {
+            kind,
+            notes,
+            done: 0,
+            failures: Vec::new(),
+            zip,
+            anki,
+            vault_path,
+            builder,
+            pool: pdf_builder.pool(),
+            pdf_builder,
+            placeholders: Vec::new(),
+            submitted: 0,
+            index: None,
+            output: None,
+        };
+
+        // Without any notes, there will be no step to complete the export.
+        if export.is_finished() {
+            export.finish();
+        }
+
+        Ok(export)
+    }
+
+    /// Sets the missing notes to create placeholder pages for once all HTML files are built, so links to them do not break.
+    pub fn with_placeholders(mut self, placeholders: Vec<(String, Vec<data::Note>)>) -> Self {
+        self.placeholders = placeholders;
+        self
+    }
+
+    /// Sets the index to replace query blocks within the notes by their results from when building HTML or PDF files.
+    pub fn with_index(mut self, index: data::NoteIndexContainer) -> Self {
+        self.index = Some(index);
+        self
+    }
+
+    /// Sets the PDF template to build all notes with, instead of the ones set in their frontmatter.
+    pub fn with_template(mut self, template: Option<String>) -> Self {
+        if let Some(template) = template {
+            for note in self.notes.iter_mut() {
+                note.export.pdf_template = Some(template.clone());
+            }
+        }
+        self
+    }
+
+    /// Sets the folder to copy the built HTML or PDF files to once all are built. Relative paths are interpreted relative to the vault.
+    pub fn with_output(mut self, output: Option<path::PathBuf>) -> Self {
+        self.output = output.map(|output| self.vault_path.join(output));
+        self
+    }
+
+    /// Exports the next note, if there is one, and returns wether there are notes left to export.
+    /// PDF files are built in parallel instead, so this collects the PDF files finished in the meantime.
+    pub fn step(&mut self) -> bool {
+        if self.kind.builds_pdf() {
+            self.step_pdf();
+        } else if let Some(note) = self.notes.get(self.done).cloned() {
+            if let Err(e) = self.export(&note) {
+                self.failures
+                    .push((note.display_name.clone(), e.to_string()));
+            }
+            self.done += 1;
+
+            if self.is_finished() {
+                self.finish();
+            }
+        }
+
+        !self.is_finished()
+    }
+
+    /// Returns the number of notes whose HTML or PDF file is missing or older than the note, if this export builds them.
+    /// This is the work actually needed to bring the files up to date, which can be much less than the number of notes.
+    pub fn outdated(&self) -> Option<usize> {
+        self.kind.builds_files().then(|| {
+            self.notes[self.done..]
+                .iter()
+                .filter(|note| self.is_outdated(note))
+                .count()
+        })
+    }
+
+    /// Leaves out the remaining notes whose HTML or PDF files are up to date, if this export builds them.
+    pub fn skip_up_to_date(&mut self) {
+        if self.kind.builds_files() {
+            let remaining = self
+                .notes
+                .split_off(self.done)
+                .into_iter()
+                .filter(|note| self.is_outdated(note))
+                .collect::<Vec<_>>();
+            self.notes.extend(remaining);
+
+            if self.is_finished() {
+                self.finish();
+            }
+        }
+    }
+
+    /// Wether the file this export builds for the given note is missing or older than the note.
+    fn is_outdated(&self, note: &data::Note) -> bool {
+        let Some(target) = self.kind.target(&note.name, &self.vault_path) else {
+            return true;
+        };
+
+        let modified = |path: &path::Path| fs::metadata(path).and_then(|md| md.modified()).ok();
+        match (modified(&note.path), modified(&target)) {
+            (Some(source), Some(target)) => target < source,
+            _ => true,
+        }
+    }
+
+    /// Hands the commands of all remaining notes to the pool and collects the results of the finished ones.
+    /// Waits a little if none has finished, so polling does not keep the processor busy.
+    fn step_pdf(&mut self) {
+        while let Some(note) = self.notes.get(self.submitted).cloned() {
+            self.submitted += 1;
+            self.submit_pdf(&note);
+        }
+
+        let finished = self.pool.poll();
+        if finished.is_empty() && !self.pool.is_empty() {
+            thread::sleep(POLL_INTERVAL);
+        }
+
+        for job in finished {
+            self.done += 1;
+            if let Err(e) = job.result {
+                self.failures
+                    .push((self.display_name(&job.key), e.to_string()));
+            }
+        }
+
+        if self.is_finished() {
+            self.finish();
+        }
+    }
+
+    /// Hands the PDF command of the given note to the pool. Notes whose command cannot be created count as failed right away.
+    fn submit_pdf(&mut self, note: &data::Note) {
+        let index = self.index.as_ref().map(|index| index.borrow());
+        let command = match self.kind {
+            ExportKind::TypstSlides => self.pdf_builder.slides_command(note, index.as_deref()),
+            _ => {
+                self.pdf_builder
+                    .command(note, index.as_deref(), self.kind == ExportKind::PrintPdf)
+            }
+        };
+        match command {
+            Ok((command, _pdf)) => self.pool.submit(&data::name_to_id(&note.name), command),
+            Err(e) => {
+                self.done += 1;
+                self.failures
+                    .push((note.display_name.clone(), e.to_string()));
+            }
+        }
+    }
+
+    /// Returns the display name of the note with the given id among the exported ones.
+    fn display_name(&self, id: &str) -> String {
+        self.notes
+            .iter()
+            .find(|note| data::name_to_id(&note.name) == id)
+            .map(|note| note.display_name.clone())
+            .unwrap_or_else(|| id.to_owned())
+    }
+
+    /// Restarts building the PDF file of the note with the given id, if it is in progress, as the note changed since it was started.
+    pub fn source_changed(&mut self, id: &str) {
+        if self.pool.contains(id) {
+            if let Some(note) = self
+                .notes
+                .iter()
+                .find(|note| data::name_to_id(&note.name) == id)
+                .cloned()
+            {
+                self.submit_pdf(&note);
+            }
+        }
+    }
+
+    /// Stops the export, skipping all remaining notes.
+    pub fn cancel(&mut self) {
+        if !self.is_finished() {
+            self.pool.cancel_all();
+            self.notes.truncate(self.done);
+            self.placeholders.clear();
+            self.finish();
+        }
+    }
+
+    /// Returns the number of notes already handled and the total number of notes.
+    pub fn progress(&self) -> (usize, usize) {
+        (self.done, self.notes.len())
+    }
+
+    /// Checks if all notes have been handled.
+    pub fn is_finished(&self) -> bool {
+        self.done >= self.notes.len()
+    }
+
+    /// Returns what this export does with the notes.
+    pub fn kind(&self) -> &ExportKind {
+        &self.kind
+    }
+
+    /// Returns the ids of all notes of this export.
+    pub fn ids(&self) -> Vec<String> {
+        self.notes
+            .iter()
+            .map(|note| data::name_to_id(&note.name))
+            .collect()
+    }
+
+    /// Returns the display names of all notes that failed to export so far, together with the reason.
+    pub fn failures(&self) -> &[(String, String)] {
+        &self.failures
+    }
+
+    /// Exports a single note.
+    fn export(&mut self, note: &data::Note) -> error::Result<()> {
+        // keep the location of the note within the vault
+        let relative = note
+            .path
+            .strip_prefix(&self.vault_path)
+            .ok()
+            .map(|relative| relative.to_path_buf())
+            .or_else(|| note.path.file_name().map(path::PathBuf::from))
+            .ok_or_else(|| error::RucolaError::NoteNameCannotBeRead(note.path.clone()))?;
+
+        match &self.kind {
+            ExportKind::Html => {
+                let index = self.index.as_ref().map(|index| index.borrow());
+                self.builder.create_html(note, index.as_deref(), true)
+            }
+            ExportKind::PrintHtml => {
+                let index = self.index.as_ref().map(|index| index.borrow());
+                self.builder.create_print_html(note, index.as_deref())
+            }
+            ExportKind::RevealSlides => {
+                let index = self.index.as_ref().map(|index| index.borrow());
+                self.builder.create_slides(note, index.as_deref())
+            }
+            // PDF files are built in the pool instead
+            ExportKind::Pdf | ExportKind::PrintPdf | ExportKind::TypstSlides => Ok(()),
+            ExportKind::Copy(target) => {
+                let target = target.join(relative);
+                if let Some(parent) = target.parent() {
+                    fs::create_dir_all(parent)?;
+                }
+                fs::copy(&note.path, target)?;
+                Ok(())
+            }
+            ExportKind::Zip(_) => {
+                if let Some(zip) = &mut self.zip {
+                    // zip archives always use forward slashes
+                    let name = relative
+                        .components()
+                        .map(|component| component.as_os_str().to_string_lossy())
+                        .collect::<Vec<_>>()
+                        .join("/");
+                    zip.start_file(name, zip::write::SimpleFileOptions::default())?;
+                    zip.write_all(&fs::read(&note.path)?)?;
+                }
+                Ok(())
+            }
+            ExportKind::Anki(_) => {
+                if let Some(anki) = &mut self.anki {
+                    let cards = data::find_flashcards(&fs::read_to_string(&note.path)?);
+                    data::write_anki(anki, &data::name_to_id(&note.name), &note.tags, &cards)?;
+                }
+                Ok(())
+            }
+        }
+    }
+
+    /// Completes the export, writing the directory of the archive if zipping, the rest of the Anki import file and the placeholder pages and search index if building HTML files.
+    fn finish(&mut self) {
+        for (id, linked_from) in std::mem::take(&mut self.placeholders) {
+            if let Err(e) = self
+                .builder
+                .create_placeholder(&id, &linked_from.iter().collect::<Vec<_>>())
+            {
+                self.failures.push((id, e.to_string()));
+            }
+        }
+
+        if self.kind == ExportKind::Html {
+            if let Err(e) = self.builder.create_search_index(&self.notes) {
+                self.failures.push((self.kind.to_string(), e.to_string()));
+            }
+        }
+
+        if let Some(output) = self.output.take() {
+            self.copy_to(&output);
+        }
+
+        if let Some(zip) = self.zip.take() {
+            if let Err(e) = zip.finish() {
+                self.failures.push((
+                    self.kind.to_string(),
+                    error::RucolaError::from(e).to_string(),
+                ));
+            }
+        }
+
+        if let Some(mut anki) = self.anki.take() {
+            if let Err(e) = anki.flush() {
+                self.failures.push((
+                    self.kind.to_string(),
+                    error::RucolaError::from(e).to_string(),
+                ));
+            }
+        }
+    }
+
+    /// Copies the HTML or PDF files of all notes that were built successfully to the given folder, along with the search index of HTML files.
+    fn copy_to(&mut self, output: &path::Path) {
+        if !self.kind.builds_files() {
+            return;
+        }
+
+        if let Err(e) = fs::create_dir_all(output) {
+            self.failures.push((
+                output.display().to_string(),
+                error::RucolaError::from(e).to_string(),
+            ));
+            return;
+        }
+
+        for note in self.notes.clone() {
+            if self
+                .failures
+                .iter()
+                .any(|(name, _reason)| name == &note.display_name)
+            {
+                continue;
+            }
+            let Some(source) = self.kind.target(&note.name, &self.vault_path) else {
+                continue;
+            };
+            if let Some(file_name) = source.file_name() {
+                if let Err(e) = fs::copy(&source, output.join(file_name)) {
+                    self.failures.push((
+                        note.display_name.clone(),
+                        error::RucolaError::from(e).to_string(),
+                    ));
+                }
+            }
+        }
+
+        // the search index the copied pages load
+        let search_script = self
+            .vault_path
+            .join(".html")
+            .join(super::search_index::SEARCH_SCRIPT_NAME);
+        if self.kind == ExportKind::Html && search_script.exists() {
+            if let Err(e) = fs::copy(
+                &search_script,
+                output.join(super::search_index::SEARCH_SCRIPT_NAME),
+            ) {
+                self.failures.push((
+                    self.kind.to_string(),
+                    error::RucolaError::from(e).to_string(),
+                ));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BatchExport, ExportKind};
+
+    /// Creates a small vault with two notes in the given folder and returns them.
+    fn notes(vault: &std::path::Path) -> Vec<crate::data::Note> {
+        std::fs::create_dir_all(vault.join("sub")).unwrap();
+        std::fs::write(vault.join("Atlas.md"), "# Atlas").unwrap();
+        std::fs::write(vault.join("sub/Chart.md"), "# Chart").unwrap();
+
+        ["Atlas.md", "sub/Chart.md"]
+            .iter()
+            .map(|path| crate::data::Note::from_path(&vault.join(path), &Default::default()))
+            .collect::<Result<_, _>>()
+            .unwrap()
+    }
+
+    /// Runs an export to completion.
+    fn run(kind: ExportKind, vault: &std::path::Path) -> BatchExport {
+        let config = crate::Config::default();
+        let mut export = BatchExport::new(
+            kind,
+            notes(vault),
+            vault.to_path_buf(),
+            crate::io::HtmlBuilder::new(&config, vault.to_path_buf()),
+            crate::io::PdfBuilder::new(&config, vault.to_path_buf()),
+        )
+        .unwrap();
+
+        assert_eq!(export.progress(), (0, 2));
+        while export.step() {}
+        assert_eq!(export.progress(), (2, 2));
+
+        export
+    }
+
+    #[test]
+    fn test_copy_export() {
+        let vault = testdir::testdir!();
+        let export = run(ExportKind::Copy("copies".into()), &vault);
+
+        assert!(export.failures().is_empty());
+        assert!(vault.join("copies/Atlas.md").exists());
+        assert!(vault.join("copies/sub/Chart.md").exists());
+    }
+
+    #[test]
+    fn test_zip_export() {
+        let vault = testdir::testdir!();
+        let export = run(ExportKind::Zip("notes.zip".into()), &vault);
+
+        assert!(export.failures().is_empty());
+        let archive =
+            zip::ZipArchive::new(std::fs::File::open(vault.join("notes.zip")).unwrap()).unwrap();
+        let mut names = archive.file_names().collect::<Vec<_>>();
+        names.sort();
+        assert_eq!(names, vec!["Atlas.md", "sub/Chart.md"]);
+    }
+
+    #[test]
+    fn test_anki_export() {
+        let vault = testdir::testdir!();
+        let config = crate::Config::default();
+        let notes = notes(&vault);
+        std::fs::write(vault.join("Atlas.md"), "# Atlas\nQ: Scale?\nA: 1:1000\n").unwrap();
+        let mut export = BatchExport::new(
+            ExportKind::Anki("cards.txt".into()),
+            notes,
+            vault.to_path_buf(),
+            crate::io::HtmlBuilder::new(&config, vault.to_path_buf()),
+            crate::io::PdfBuilder::new(&config, vault.to_path_buf()),
+        )
+        .unwrap();
+        while export.step() {}
+
+        assert!(export.failures().is_empty());
+        let cards = std::fs::read_to_string(vault.join("cards.txt")).unwrap();
+        assert!(cards.starts_with(crate::data::ANKI_HEADER));
+        // only the note with a question contributes a card
+        assert_eq!(
+            cards.lines().count(),
+            crate::data::ANKI_HEADER.lines().count() + 1
+        );
+        assert!(cards.contains("\"<p>Scale?</p>\"\t\"<p>1:1000</p>\"\trucola::atlas\n"));
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_pdf_export() {
+        let vault = testdir::testdir!();
+
+        // Use a plain copy as the 'conversion', failing for one of the notes.
+        let config = crate::Config {
+            pdf_command: [
+                "sh",
+                "-c",
+                "case \"$0\" in *Chart.md) exit 1;; esac; cp \"$0\" \"$1\"",
+                "%p",
+                "%o",
+            ]
+            .map(String::from)
+            .to_vec(),
+            build_jobs: 2,
+            ..Default::default()
+        };
+        let mut export = BatchExport::new(
+            ExportKind::Pdf,
+            notes(&vault),
+            vault.to_path_buf(),
+            crate::io::HtmlBuilder::new(&config, vault.to_path_buf()),
+            crate::io::PdfBuilder::new(&config, vault.to_path_buf()),
+        )
+        .unwrap()
+        .with_template(Some(String::from("handout")))
+        .with_output(Some("handouts".into()));
+        while export.step() {}
+
+        assert_eq!(export.progress(), (2, 2));
+        assert!(vault.join(".pdf/atlas.pdf").exists());
+        assert_eq!(export.failures().len(), 1);
+        assert_eq!(export.failures()[0].0, "Chart");
+
+        // only the files built successfully are copied to the output folder
+        assert!(vault.join("handouts/atlas.pdf").exists());
+        assert!(!vault.join("handouts/chart.pdf").exists());
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_print_pdf_export() {
+        let vault = testdir::testdir!();
+
+        // Use a plain copy as the 'conversion', recording the print argument in another file.
+        let config = crate::Config {
+            pdf_command: [
+                "sh",
+                "-c",
+                "cp \"$0\" \"$1\" && echo \"$2\" > \"$1.arg\"",
+                "%p",
+                "%o",
+            ]
+            .map(String::from)
+            .to_vec(),
+            pdf_print_arg: Some(String::from("--css=%c")),
+            ..Default::default()
+        };
+        let notes = notes(&vault);
+        std::fs::write(
+            vault.join("Atlas.md"),
+            "# Atlas\n[Maps](https://maps.org)\n",
+        )
+        .unwrap();
+        let mut export = BatchExport::new(
+            ExportKind::PrintPdf,
+            notes,
+            vault.to_path_buf(),
+            crate::io::HtmlBuilder::new(&config, vault.to_path_buf()),
+            crate::io::PdfBuilder::new(&config, vault.to_path_buf()),
+        )
+        .unwrap();
+        while export.step() {}
+
+        assert!(export.failures().is_empty());
+        assert!(!vault.join(".pdf/atlas.pdf").exists());
+        assert_eq!(
+            std::fs::read_to_string(vault.join(".print/atlas.pdf")).unwrap(),
+            "# Atlas\n[Maps](https://maps.org)[^rucola-link-1]\n\n[^rucola-link-1]: <https://maps.org>\n"
+        );
+        assert_eq!(
+            std::fs::read_to_string(vault.join(".print/atlas.pdf.arg")).unwrap(),
+            format!("--css={}\n", vault.join(".print/print.css").display())
+        );
+        assert!(vault.join(".print/print.css").exists());
+        // notes without links are converted as they are
+        assert!(vault.join(".print/chart.pdf").exists());
+        assert!(!vault.join(".print/chart.md").exists());
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_typst_slides_export() {
+        let vault = testdir::testdir!();
+
+        // Use a plain copy as the 'compilation', to see the written deck.
+        let config = crate::Config {
+            slides_command: ["cp", "%p", "%o"].map(String::from).to_vec(),
+            ..Default::default()
+        };
+        let notes = notes(&vault);
+        let mut export = BatchExport::new(
+            ExportKind::TypstSlides,
+            notes,
+            vault.to_path_buf(),
+            crate::io::HtmlBuilder::new(&config, vault.to_path_buf()),
+            crate::io::PdfBuilder::new(&config, vault.to_path_buf()),
+        )
+        .unwrap();
+        while export.step() {}
+
+        assert!(export.failures().is_empty());
+        assert!(vault.join(".slides/atlas.typ").exists());
+        assert!(std::fs::read_to_string(vault.join(".slides/atlas.pdf"))
+            .unwrap()
+            .contains("#slide["));
+        assert!(vault.join(".slides/chart.pdf").exists());
+        assert!(!vault.join(".pdf/atlas.pdf").exists());
+    }
+
+    #[test]
+    fn test_outdated() {
+        let vault = testdir::testdir!();
+        let export = run(ExportKind::Html, &vault);
+        assert!(export.failures().is_empty());
+        assert!(vault.join(".html/rucola-search.js").exists());
+
+        // the notes are not written again, so they stay older than their files
+        let notes = ["Atlas.md", "sub/Chart.md"]
+            .map(|path| {
+                crate::data::Note::from_path(&vault.join(path), &Default::default()).unwrap()
+            })
+            .to_vec();
+        let config = crate::Config::default();
+        let new = |kind| {
+            BatchExport::new(
+                kind,
+                notes.clone(),
+                vault.to_path_buf(),
+                crate::io::HtmlBuilder::new(&config, vault.to_path_buf()),
+                crate::io::PdfBuilder::new(&config, vault.to_path_buf()),
+            )
+            .unwrap()
+        };
+
+        // all HTML files were just built, but none of the PDF files
+        assert_eq!(new(ExportKind::Html).outdated(), Some(0));
+        assert_eq!(new(ExportKind::Pdf).outdated(), Some(2));
+        assert_eq!(new(ExportKind::Zip("notes.zip".into())).outdated(), None);
+
+        std::fs::remove_file(vault.join(".html/chart.html")).unwrap();
+        let mut export = new(ExportKind::Html);
+        assert_eq!(export.outdated(), Some(1));
+        export.skip_up_to_date();
+        assert_eq!(export.progress(), (0, 1));
+    }
+
+    #[test]
+    fn test_export_failures() {
+        let vault = testdir::testdir!();
+        let mut notes = notes(&vault);
+        std::fs::remove_file(&notes[0].path).unwrap();
+        notes.truncate(1);
+
+        let config = crate::Config::default();
+        let mut export = BatchExport::new(
+            ExportKind::Copy("copies".into()),
+            notes,
+            vault.to_path_buf(),
+            crate::io::HtmlBuilder::new(&config, vault.to_path_buf()),
+            crate::io::PdfBuilder::new(&config, vault.to_path_buf()),
+        )
+        .unwrap();
+        while export.step() {}
+
+        assert_eq!(export.failures().len(), 1);
+        assert_eq!(export.failures()[0].0, "Atlas");
+    }
+}
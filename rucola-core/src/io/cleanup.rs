@@ -0,0 +1,527 @@
+use std::{collections::HashSet, fmt, fs, path, time};
+
+use itertools::Itertools;
+
+use crate::{data, error, io};
+
+/// How long a temporary file has to stay unchanged before it is cleaned up, as another instance of rucola may still be writing it.
+const TEMPORARY_FILE_AGE: time::Duration = time::Duration::from_secs(60 * 60);
+
+/// What kind of leftover a file or folder removed by a cleanup is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LeftoverKind {
+    /// The HTML file of a note, alias or missing note that no longer exists.
+    Html,
+    /// The PDF file of a note that no longer exists, or the copy of the note it was converted from.
+    Pdf,
+    /// The print-friendly HTML or PDF file of a note that no longer exists, or the copy of the note it was converted from.
+    Print,
+    /// A slide deck of a note that no longer exists, or the typst file it was compiled from.
+    Slides,
+    /// A temporary file or backup of a state file of the vault.
+    StateFile,
+    /// A folder of the vault that contains neither files nor hidden folders.
+    EmptyFolder,
+    /// A note that has been in the trash for longer than configured.
+    Trash,
+}
+
+impl fmt::Display for LeftoverKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                LeftoverKind::Html => "orphaned HTML file",
+                LeftoverKind::Pdf => "orphaned PDF file",
+                LeftoverKind::Print => "orphaned print file",
+                LeftoverKind::Slides => "orphaned slides",
+                LeftoverKind::StateFile => "state file leftover",
+                LeftoverKind::EmptyFolder => "empty folder",
+                LeftoverKind::Trash => "expired trash",
+            }
+        )
+    }
+}
+
+/// A file or folder removed by a cleanup.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Leftover {
+    /// The path of the file or folder.
+    pub path: path::PathBuf,
+    /// Why the file or folder is no longer needed.
+    pub kind: LeftoverKind,
+    /// The size of the file in bytes, 0 for folders.
+    pub size: u64,
+}
+
+/// The files, folders and cache entries left behind by deleted, renamed and moved notes, which can be removed without losing any notes.
+pub struct Cleanup {
+    /// The files and folders to remove, in the order they are removed in.
+    pub leftovers: Vec<Leftover>,
+    /// The number of cached completions of notes that no longer exist.
+    pub stale_completions: usize,
+    /// The number of stored embeddings of notes that no longer exist.
+    #[cfg(feature = "semantic-search")]
+    pub stale_embeddings: usize,
+    /// The completer whose cache is pruned.
+    completer: io::LinkCompleter,
+    /// The semantic search whose embeddings are pruned, if configured.
+    #[cfg(feature = "semantic-search")]
+    semantic_search: Option<io::SemanticSearch>,
+    /// Path to the vault that is cleaned.
+    vault_path: path::PathBuf,
+}
+
+impl Cleanup {
+    /// Finds everything that can be cleaned up in the vault of the given index without removing anything yet.
+    pub fn plan(
+        config: &crate::Config,
+        index: &data::NoteIndex,
+        manager: &io::FileManager,
+    ) -> error::Result<Self> {
+        let vault_path = manager.vault_path().to_path_buf();
+
+        // HTML files are kept for notes, their aliases and the placeholders of missing notes that are linked to
+        let ids = index
+            .notes()
+            .map(|note| data::name_to_id(&note.name))
+            .collect::<HashSet<_>>();
+        let html_ids = index
+            .notes()
+            .flat_map(|note| note.aliases.iter().map(|alias| data::name_to_id(alias)))
+            .chain(index.dangling_links().into_iter().map(|(id, _)| id))
+            .chain(ids.iter().cloned())
+            .collect::<HashSet<_>>();
+
+        let mut leftovers = Vec::new();
+        leftovers.extend(orphans(
+            &vault_path.join(".html"),
+            &["html"],
+            &html_ids,
+            LeftoverKind::Html,
+        )?);
+        leftovers.extend(orphans(
+            &vault_path.join(".pdf"),
+            &["pdf", "md"],
+            &ids,
+            LeftoverKind::Pdf,
+        )?);
+        leftovers.extend(orphans(
+            &vault_path.join(".print"),
+            &["html", "pdf", "md"],
+            &ids,
+            LeftoverKind::Print,
+        )?);
+        leftovers.extend(orphans(
+            &vault_path.join(".slides"),
+            &["html", "typ", "pdf"],
+            &ids,
+            LeftoverKind::Slides,
+        )?);
+
+        // state files are written via temporary files and moved to backups when they cannot be read
+        for entry in fs::read_dir(&vault_path)?.flatten() {
+            let name = entry.file_name().to_string_lossy().to_string();
+            if !name.starts_with(".rucola-") || !entry.file_type()?.is_file() {
+                continue;
+            }
+            let metadata = entry.metadata()?;
+            // temporary files still being written are kept
+            let abandoned = name.ends_with(".bak")
+                || (name.ends_with(".tmp")
+                    && metadata
+                        .modified()
+                        .ok()
+                        .and_then(|modified| modified.elapsed().ok())
+                        .is_some_and(|age| age >= TEMPORARY_FILE_AGE));
+            if abandoned {
+                leftovers.push(Leftover {
+                    path: entry.path(),
+                    kind: LeftoverKind::StateFile,
+                    size: metadata.len(),
+                });
+            }
+        }
+
+        for entry in manager.expired_trash_entries()? {
+            leftovers.push(Leftover {
+                size: fs::metadata(&entry.path)?.len(),
+                path: entry.path,
+                kind: LeftoverKind::Trash,
+            });
+        }
+
+        let mut folders = Vec::new();
+        for entry in fs::read_dir(&vault_path)?
+            .flatten()
+            .sorted_by_key(|entry| entry.path())
+        {
+            if entry.file_type()?.is_dir() && !is_hidden(&entry.path()) {
+                empty_folders(&entry.path(), &mut folders)?;
+            }
+        }
+        leftovers.extend(folders.into_iter().map(|path| Leftover {
+            path,
+            kind: LeftoverKind::EmptyFolder,
+            size: 0,
+        }));
+
+        let completer = io::LinkCompleter::new(config, vault_path.clone())?;
+        #[cfg(feature = "semantic-search")]
+        let semantic_search = io::SemanticSearch::new(config, &vault_path);
+
+        Ok(Self {
+            leftovers,
+            stale_completions: completer.stale_entries(),
+            #[cfg(feature = "semantic-search")]
+            stale_embeddings: semantic_search
+                .as_ref()
+                .map(|search| search.stale_entries(index))
+                .unwrap_or_default(),
+            completer,
+            #[cfg(feature = "semantic-search")]
+            semantic_search,
+            vault_path,
+        })
+    }
+
+    /// Wether there is nothing to clean up.
+    pub fn is_empty(&self) -> bool {
+        self.leftovers.is_empty() && self.stale_entries() == 0
+    }
+
+    /// The number of stale entries of all caches.
+    pub fn stale_entries(&self) -> usize {
+        let entries = self.stale_completions;
+        #[cfg(feature = "semantic-search")]
+        let entries = entries + self.stale_embeddings;
+        entries
+    }
+
+    /// The number of bytes the removed files take up.
+    pub fn size(&self) -> u64 {
+        self.leftovers.iter().map(|leftover| leftover.size).sum()
+    }
+
+    /// Describes everything that is cleaned up, one file, folder or cache per line, with paths relative to the vault.
+    pub fn describe(&self) -> String {
+        let mut lines = self
+            .leftovers
+            .iter()
+            .map(|leftover| {
+                let path = leftover
+                    .path
+                    .strip_prefix(&self.vault_path)
+                    .unwrap_or(&leftover.path);
+                match leftover.kind {
+                    LeftoverKind::EmptyFolder => {
+                        format!("{:<20} {}", leftover.kind.to_string(), path.display())
+                    }
+                    _ => format!(
+                        "{:<20} {} ({})",
+                        leftover.kind.to_string(),
+                        path.display(),
+                        format_size(leftover.size)
+                    ),
+                }
+            })
+            .collect_vec();
+
+        if self.stale_completions > 0 {
+            lines.push(format!(
+                "{:<20} {} completion{} of deleted notes",
+                "stale cache entries",
+                self.stale_completions,
+                if self.stale_completions == 1 { "" } else { "s" }
+            ));
+        }
+        #[cfg(feature = "semantic-search")]
+        if self.stale_embeddings > 0 {
+            lines.push(format!(
+                "{:<20} {} embedding{} of deleted notes",
+                "stale cache entries",
+                self.stale_embeddings,
+                if self.stale_embeddings == 1 { "" } else { "s" }
+            ));
+        }
+
+        lines
+            .into_iter()
+            .map(|line| format!("{}\n", line))
+            .collect()
+    }
+
+    /// Removes all leftovers and stale cache entries. Returns the number of bytes freed.
+    pub fn apply(
+        &mut self,
+        index: &data::NoteIndex,
+        manager: &io::FileManager,
+    ) -> error::Result<u64> {
+        manager.ensure_writable()?;
+
+        let mut freed = 0;
+
+        for leftover in &self.leftovers {
+            match leftover.kind {
+                LeftoverKind::EmptyFolder => fs::remove_dir(&leftover.path)?,
+                _ => fs::remove_file(&leftover.path)?,
+            }
+            freed += leftover.size;
+        }
+        manager.remove_empty_trash_folders()?;
+
+        let completions = self.vault_path.join(super::link_completer::STATE_FILE);
+        let before = file_size(&completions);
+        if self.stale_completions > 0 {
+            self.completer.prune()?;
+        }
+        freed += before.saturating_sub(file_size(&completions));
+
+        freed += self.prune_embeddings(index)?;
+
+        Ok(freed)
+    }
+
+    /// Drops the stored embeddings of deleted notes. Returns the number of bytes freed.
+    #[cfg(feature = "semantic-search")]
+    fn prune_embeddings(&mut self, index: &data::NoteIndex) -> error::Result<u64> {
+        let Some(search) = self.semantic_search.as_mut() else {
+            return Ok(0);
+        };
+
+        let embeddings = self.vault_path.join(super::semantic_search::STATE_FILE);
+        let before = file_size(&embeddings);
+        if self.stale_embeddings > 0 {
+            search.prune(index)?;
+        }
+        Ok(before.saturating_sub(file_size(&embeddings)))
+    }
+
+    /// Without semantic search, there are no embeddings to drop.
+    #[cfg(not(feature = "semantic-search"))]
+    fn prune_embeddings(&mut self, _index: &data::NoteIndex) -> error::Result<u64> {
+        Ok(0)
+    }
+}
+
+/// Returns the size of the file at the given path in bytes, 0 if it does not exist.
+fn file_size(path: &path::Path) -> u64 {
+    fs::metadata(path).map(|md| md.len()).unwrap_or_default()
+}
+
+/// Lists the files with one of the given extensions directly within the given folder whose name is none of the given ids.
+fn orphans(
+    folder: &path::Path,
+    extensions: &[&str],
+    ids: &HashSet<String>,
+    kind: LeftoverKind,
+) -> error::Result<Vec<Leftover>> {
+    if !folder.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut orphans = Vec::new();
+    for entry in fs::read_dir(folder)?.flatten() {
+        let path = entry.path();
+        let extension = path
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .unwrap_or_default();
+        let stem = path
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .unwrap_or_default();
+
+        if entry.file_type()?.is_file() && extensions.contains(&extension) && !ids.contains(stem) {
+            orphans.push(Leftover {
+                size: entry.metadata()?.len(),
+                path,
+                kind,
+            });
+        }
+    }
+
+    orphans.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(orphans)
+}
+
+/// Adds the given folder and all folders within it that contain neither files nor hidden folders to the given list, innermost folders first.
+/// Returns wether the given folder is empty.
+fn empty_folders(folder: &path::Path, empty: &mut Vec<path::PathBuf>) -> error::Result<bool> {
+    let mut is_empty = true;
+    for entry in fs::read_dir(folder)?
+        .flatten()
+        .sorted_by_key(|entry| entry.path())
+    {
+        if !(entry.file_type()?.is_dir()
+            && !is_hidden(&entry.path())
+            && empty_folders(&entry.path(), empty)?)
+        {
+            is_empty = false;
+        }
+    }
+    if is_empty {
+        empty.push(folder.to_path_buf());
+    }
+    Ok(is_empty)
+}
+
+/// Wether the name of the file or folder at the given path starts with a dot.
+fn is_hidden(path: &path::Path) -> bool {
+    path.file_name()
+        .is_some_and(|name| name.to_string_lossy().starts_with('.'))
+}
+
+/// Formats a number of bytes with a binary unit, e.g. `2.5 KiB`.
+pub fn format_size(bytes: u64) -> String {
+    let units = ["B", "KiB", "MiB", "GiB", "TiB"];
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < units.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} B", bytes)
+    } else {
+        format!("{:.1} {}", size, units[unit])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_size() {
+        assert_eq!(format_size(0), "0 B");
+        assert_eq!(format_size(1023), "1023 B");
+        assert_eq!(format_size(2560), "2.5 KiB");
+        assert_eq!(format_size(3 * 1024 * 1024), "3.0 MiB");
+    }
+
+    #[test]
+    fn test_cleanup() {
+        let vault = testdir::testdir!().join("vault");
+        std::fs::create_dir_all(&vault).unwrap();
+        std::fs::write(
+            vault.join("Atlas.md"),
+            "---\naliases: [Chart Atlas]\n---\n# Atlas\nSee [[Chart]].",
+        )
+        .unwrap();
+
+        let config = crate::Config {
+            enable_html: false,
+            ..Default::default()
+        };
+
+        // build the completion cache while the note about groups still exists
+        std::fs::write(vault.join("Group.md"), "# Group").unwrap();
+        let mut completer = io::LinkCompleter::new(&config, vault.clone()).unwrap();
+        assert!(completer.refresh().is_empty());
+        std::fs::remove_file(vault.join("Group.md")).unwrap();
+
+        // artifacts of the existing note, its alias and the missing note it links to are kept
+        for name in ["atlas", "chart-atlas", "chart", "group"] {
+            std::fs::create_dir_all(vault.join(".html")).unwrap();
+            std::fs::write(vault.join(format!(".html/{}.html", name)), "html").unwrap();
+        }
+        std::fs::create_dir_all(vault.join(".pdf")).unwrap();
+        for name in ["atlas.pdf", "group.pdf", "group.md"] {
+            std::fs::write(vault.join(".pdf").join(name), "pdf").unwrap();
+        }
+        std::fs::create_dir_all(vault.join(".print")).unwrap();
+        for name in ["atlas.html", "group.html"] {
+            std::fs::write(vault.join(".print").join(name), "print").unwrap();
+        }
+        // temporary files are only removed once no other instance can still be writing them
+        std::fs::write(vault.join(".rucola-spelling.json.tmp"), "{}").unwrap();
+        std::fs::File::options()
+            .write(true)
+            .open(vault.join(".rucola-spelling.json.tmp"))
+            .unwrap()
+            .set_modified(std::time::SystemTime::now() - TEMPORARY_FILE_AGE)
+            .unwrap();
+        std::fs::write(vault.join(".rucola-visits.json.tmp"), "{}").unwrap();
+
+        let old = chrono::Local::now() - chrono::Duration::days(40);
+        let trash = vault.join(".trash").join(old.timestamp().to_string());
+        std::fs::create_dir_all(&trash).unwrap();
+        std::fs::write(trash.join("Old.md"), "# Old").unwrap();
+
+        std::fs::create_dir_all(vault.join("moved/away")).unwrap();
+        std::fs::create_dir_all(vault.join("kept/.git")).unwrap();
+
+        let manager = io::FileManager::new(&config, vault.clone());
+        let index = data::NoteIndex::new(
+            io::FileTracker::new(&config, vault.clone()).unwrap(),
+            io::HtmlBuilder::new(&config, vault.clone()),
+            data::ParseOptions::new(&config).unwrap(),
+        )
+        .0;
+
+        let mut cleanup = Cleanup::plan(&config, &index, &manager).unwrap();
+        let removed = cleanup
+            .leftovers
+            .iter()
+            .map(|leftover| {
+                (
+                    leftover
+                        .path
+                        .strip_prefix(&vault)
+                        .unwrap()
+                        .to_string_lossy()
+                        .to_string(),
+                    leftover.kind,
+                )
+            })
+            .collect_vec();
+        assert_eq!(
+            removed,
+            vec![
+                (String::from(".html/group.html"), LeftoverKind::Html),
+                (String::from(".pdf/group.md"), LeftoverKind::Pdf),
+                (String::from(".pdf/group.pdf"), LeftoverKind::Pdf),
+                (String::from(".print/group.html"), LeftoverKind::Print),
+                (
+                    String::from(".rucola-spelling.json.tmp"),
+                    LeftoverKind::StateFile
+                ),
+                (
+                    trash
+                        .join("Old.md")
+                        .strip_prefix(&vault)
+                        .unwrap()
+                        .to_string_lossy()
+                        .to_string(),
+                    LeftoverKind::Trash
+                ),
+                (String::from("moved/away"), LeftoverKind::EmptyFolder),
+                (String::from("moved"), LeftoverKind::EmptyFolder),
+            ]
+        );
+        assert_eq!(cleanup.stale_completions, 1);
+        assert_eq!(cleanup.size(), 4 + 3 + 3 + 5 + 2 + 5);
+        assert!(cleanup
+            .describe()
+            .contains("orphaned HTML file   .html/group.html (4 B)"));
+
+        let freed = cleanup.apply(&index, &manager).unwrap();
+        assert!(freed > cleanup.size());
+        assert!(!vault.join(".html/group.html").exists());
+        assert!(vault.join(".html/chart.html").exists());
+        assert!(vault.join(".pdf/atlas.pdf").exists());
+        assert!(vault.join(".print/atlas.html").exists());
+        assert!(!vault.join("moved").exists());
+        assert!(vault.join("kept").exists());
+        assert!(vault.join(".rucola-visits.json.tmp").exists());
+        assert!(!vault
+            .join(".trash")
+            .join(old.timestamp().to_string())
+            .exists());
+
+        let cleanup = Cleanup::plan(&config, &index, &manager).unwrap();
+        assert!(cleanup.is_empty());
+    }
+}
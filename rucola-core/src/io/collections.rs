@@ -0,0 +1,207 @@
+use std::{
+    cell::RefCell,
+    collections::{BTreeMap, HashSet},
+    path,
+    rc::Rc,
+};
+
+use crate::{error, i18n, io};
+
+/// The file the collections are stored in, relative to the vault.
+const STATE_FILE: &str = ".rucola-collections.json";
+
+/// The collections of a vault with the ids of their notes, in the order they were added.
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+struct CollectionList {
+    collections: BTreeMap<String, Vec<String>>,
+}
+
+impl io::VersionedState for CollectionList {
+    const VERSION: u32 = 1;
+}
+
+/// Named, manually curated sets of notes independent of their tags, such as reading lists or the notes of a project.
+/// They are stored in the vault and shared between clones, so changes are seen by all screens at once.
+#[derive(Debug, Clone)]
+pub struct Collections {
+    /// The file the collections are stored in.
+    state_file: io::StateFile<CollectionList>,
+    /// The collections, shared between clones.
+    collections: Rc<RefCell<CollectionList>>,
+}
+
+impl Collections {
+    /// Loads the collections of the given vault.
+    /// If they cannot be read, there are no collections and the error describing what happened is returned as well.
+    pub fn load(vault_path: &path::Path) -> (Self, Option<error::RucolaError>) {
+        let state_file = io::StateFile::new(vault_path.join(STATE_FILE));
+        let (collections, error) = state_file.load();
+        (
+            Self {
+                state_file,
+                collections: Rc::new(RefCell::new(collections)),
+            },
+            error,
+        )
+    }
+
+    /// Returns the names of all collections, sorted, with the number of their notes.
+    pub fn names(&self) -> Vec<(String, usize)> {
+        self.collections
+            .borrow()
+            .collections
+            .iter()
+            .map(|(name, ids)| (name.clone(), ids.len()))
+            .collect()
+    }
+
+    /// Returns the ids of the notes in the collection with the given name, in the order they were added.
+    pub fn notes(&self, name: &str) -> Vec<String> {
+        self.collections
+            .borrow()
+            .collections
+            .get(name)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Returns the ids of the notes in the collection with the given name, if it exists.
+    pub fn members(&self, name: &str) -> Option<HashSet<String>> {
+        self.collections
+            .borrow()
+            .collections
+            .get(name)
+            .map(|ids| ids.iter().cloned().collect())
+    }
+
+    /// Wether the collection with the given name contains the note with the given id.
+    pub fn contains(&self, name: &str, id: &str) -> bool {
+        self.collections
+            .borrow()
+            .collections
+            .get(name)
+            .is_some_and(|ids| ids.iter().any(|other| other == id))
+    }
+
+    /// Creates an empty collection with the given name.
+    /// Fails if the name is empty, contains whitespace, which could not be filtered for, or is already taken.
+    pub fn create(&self, name: &str) -> error::Result<()> {
+        if name.is_empty() || name.contains(char::is_whitespace) {
+            return Err(error::RucolaError::Input(i18n::tr(
+                "collections-error-name",
+            )));
+        }
+        if self.collections.borrow().collections.contains_key(name) {
+            return Err(error::RucolaError::Input(i18n::tr_args(
+                "collections-error-exists",
+                &[("name", name.into())],
+            )));
+        }
+        self.change(|collections| {
+            collections.insert(name.to_owned(), Vec::new());
+        })
+    }
+
+    /// Deletes the collection with the given name, leaving its notes untouched.
+    pub fn delete(&self, name: &str) -> error::Result<()> {
+        self.change(|collections| {
+            collections.remove(name);
+        })
+    }
+
+    /// Adds the note with the given id to the collection with the given name, or removes it if it is already contained.
+    /// Returns wether the note is contained afterwards.
+    pub fn toggle(&self, name: &str, id: &str) -> error::Result<bool> {
+        let mut contained = false;
+        self.change(|collections| {
+            if let Some(ids) = collections.get_mut(name) {
+                let before = ids.len();
+                ids.retain(|other| other != id);
+                if ids.len() == before {
+                    ids.push(id.to_owned());
+                    contained = true;
+                }
+            }
+        })?;
+        Ok(contained)
+    }
+
+    /// Replaces the given old id of a renamed or moved note by its new id in all collections.
+    pub fn rename_note(&self, old_id: &str, new_id: &str) -> error::Result<()> {
+        if old_id == new_id || !self.in_any(old_id) {
+            return Ok(());
+        }
+        self.change(|collections| {
+            for ids in collections.values_mut() {
+                for id in ids.iter_mut().filter(|id| *id == old_id) {
+                    *id = new_id.to_owned();
+                }
+            }
+        })
+    }
+
+    /// Wether any collection contains the note with the given id.
+    fn in_any(&self, id: &str) -> bool {
+        self.collections
+            .borrow()
+            .collections
+            .values()
+            .any(|ids| ids.iter().any(|other| other == id))
+    }
+
+    /// Applies the given change to the collections and stores them.
+    fn change(&self, change: impl FnOnce(&mut BTreeMap<String, Vec<String>>)) -> error::Result<()> {
+        change(&mut self.collections.borrow_mut().collections);
+        self.state_file.save(&self.collections.borrow())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_collections() {
+        let vault = testdir::testdir!();
+        let (collections, error) = Collections::load(&vault);
+        assert!(error.is_none());
+        assert!(collections.names().is_empty());
+
+        collections.create("reading").unwrap();
+        collections.create("thesis").unwrap();
+        assert!(collections.create("reading").is_err());
+        assert!(collections.create("reading list").is_err());
+        assert!(collections.create("").is_err());
+
+        assert!(collections.toggle("reading", "manifold").unwrap());
+        assert!(collections.toggle("reading", "atlas").unwrap());
+        assert!(collections.toggle("thesis", "atlas").unwrap());
+        assert!(!collections.toggle("thesis", "atlas").unwrap());
+        assert!(!collections.toggle("unknown", "atlas").unwrap());
+
+        // clones share the collections
+        let clone = collections.clone();
+        assert_eq!(
+            clone.names(),
+            vec![(String::from("reading"), 2), (String::from("thesis"), 0)]
+        );
+        assert!(clone.contains("reading", "atlas"));
+
+        collections.rename_note("atlas", "atlantis").unwrap();
+        assert_eq!(collections.notes("reading"), vec!["manifold", "atlantis"]);
+
+        // the collections are stored in the vault
+        collections.delete("thesis").unwrap();
+        let (loaded, error) = Collections::load(&vault);
+        assert!(error.is_none());
+        assert_eq!(loaded.names(), vec![(String::from("reading"), 2)]);
+        assert_eq!(
+            loaded.members("reading"),
+            Some(HashSet::from([
+                String::from("manifold"),
+                String::from("atlantis")
+            ]))
+        );
+        assert_eq!(loaded.members("thesis"), None);
+    }
+}
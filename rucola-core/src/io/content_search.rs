@@ -0,0 +1,146 @@
+use std::{
+    path,
+    sync::{atomic, mpsc, Arc},
+    time,
+};
+
+/// How often the search reports its progress at most, so the list of results grows smoothly without waking the UI for every note.
+const REPORT_INTERVAL: time::Duration = time::Duration::from_millis(50);
+
+/// Searches the full text of notes for a text on a separate thread, so the UI can show matches as they are found.
+/// The search is cancelled once it is dropped, e.g. because the filter changed.
+#[derive(Debug)]
+pub struct ContentSearch {
+    /// Receives the ids of the notes found to contain the text and how many notes were searched since the last message.
+    receiver: mpsc::Receiver<(Vec<String>, usize)>,
+    /// Set to stop the search early.
+    cancelled: Arc<atomic::AtomicBool>,
+    /// How many notes have been searched so far.
+    searched: usize,
+    /// How many notes are searched in total.
+    total: usize,
+}
+
+impl ContentSearch {
+    /// Starts searching the notes with the given ids and paths for the given text, ignoring case.
+    /// The text is expected in lowercase, as in [`crate::data::Filter`].
+    pub fn start(text: String, notes: Vec<(String, path::PathBuf)>) -> Self {
+        let (sender, receiver) = mpsc::channel();
+        let cancelled = Arc::new(atomic::AtomicBool::new(false));
+        let total = notes.len();
+
+        let stop = cancelled.clone();
+        std::thread::spawn(move || {
+            let mut found = Vec::new();
+            let mut searched = 0;
+            let mut last_report = time::Instant::now();
+            for (index, (id, path)) in notes.into_iter().enumerate() {
+                if stop.load(atomic::Ordering::Relaxed) {
+                    return;
+                }
+                if std::fs::read_to_string(&path)
+                    .map(|content| content.to_lowercase().contains(&text))
+                    .unwrap_or(false)
+                {
+                    found.push(id);
+                }
+                searched += 1;
+
+                if index + 1 == total || last_report.elapsed() >= REPORT_INTERVAL {
+                    if sender
+                        .send((std::mem::take(&mut found), std::mem::take(&mut searched)))
+                        .is_err()
+                    {
+                        return;
+                    }
+                    super::wake();
+                    last_report = time::Instant::now();
+                }
+            }
+        });
+
+        Self {
+            receiver,
+            cancelled,
+            searched: 0,
+            total,
+        }
+    }
+
+    /// Returns the ids of the notes found to contain the text since the last call, without waiting for further results.
+    pub fn take_found(&mut self) -> Vec<String> {
+        let mut found = Vec::new();
+        loop {
+            match self.receiver.try_recv() {
+                Ok((ids, searched)) => {
+                    found.extend(ids);
+                    self.searched += searched;
+                }
+                Err(mpsc::TryRecvError::Empty) => break,
+                // a search stopping unexpectedly is not going to find anything else
+                Err(mpsc::TryRecvError::Disconnected) => {
+                    self.searched = self.total;
+                    break;
+                }
+            }
+        }
+        found
+    }
+
+    /// Returns the share of the notes searched so far, in percent.
+    pub fn progress(&self) -> usize {
+        (self.searched * 100).checked_div(self.total).unwrap_or(100)
+    }
+
+    /// Wether all notes have been searched.
+    pub fn is_finished(&self) -> bool {
+        self.searched >= self.total
+    }
+}
+
+impl Drop for ContentSearch {
+    fn drop(&mut self) {
+        self.cancelled.store(true, atomic::Ordering::Relaxed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_content_search() {
+        let notes = [
+            "tests/common/notes/math/Chart.md",
+            "tests/common/notes/math/Atlas.md",
+            "tests/common/notes/No such note.md",
+        ]
+        .iter()
+        .map(|path| {
+            let path = path::PathBuf::from(path);
+            (
+                path.file_stem().unwrap().to_string_lossy().to_string(),
+                path,
+            )
+        })
+        .collect::<Vec<_>>();
+
+        let mut search = ContentSearch::start(String::from("change of charts"), notes);
+        let mut found = Vec::new();
+        let start = time::Instant::now();
+        while !search.is_finished() && start.elapsed() < time::Duration::from_secs(10) {
+            found.extend(search.take_found());
+            std::thread::sleep(time::Duration::from_millis(5));
+        }
+        found.extend(search.take_found());
+
+        assert!(search.is_finished());
+        assert_eq!(search.progress(), 100);
+        assert_eq!(found, vec![String::from("Chart")]);
+
+        // an empty search is finished right away
+        let search = ContentSearch::start(String::from("chart"), Vec::new());
+        assert!(search.is_finished());
+        assert_eq!(search.progress(), 100);
+    }
+}
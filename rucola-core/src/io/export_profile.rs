@@ -0,0 +1,193 @@
+use std::path;
+
+/// The formats an export profile can export notes in.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum ExportFormat {
+    /// Build the HTML files of the notes.
+    #[default]
+    Html,
+    /// Build the PDF files of the notes.
+    Pdf,
+    /// Copy the note files to a folder.
+    Copy,
+    /// Write the note files to a zip archive.
+    Zip,
+    /// Build reveal.js slide decks of the notes.
+    RevealSlides,
+    /// Build typst slide decks of the notes and compile them into PDF files.
+    TypstSlides,
+    /// Write the flashcards of the notes with the flashcard tag to an Anki import file.
+    Anki,
+}
+
+/// A remembered way to export a collection or the notes matching a saved filter, as set in the `export_profiles` of the config.
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct ExportProfile {
+    /// The collection whose notes are exported.
+    pub collection: Option<String>,
+    /// The filter whose matching notes are exported, if no collection is set.
+    pub filter: Option<String>,
+    /// What to do with the notes.
+    pub format: ExportFormat,
+    /// The PDF template to use for all notes, instead of the ones set in their frontmatter.
+    pub template: Option<String>,
+    /// Wether to build print-friendly HTML or PDF files.
+    pub print: bool,
+    /// The folder (HTML, PDF, slides, copies), archive (zip) or import file (Anki) to write the results to, relative to the vault.
+    /// HTML and PDF files stay in the vault if unset, copies, archives and import files are named after the profile.
+    pub output: Option<String>,
+    /// Wether to upload the HTML files of the notes to the configured bucket afterwards.
+    pub publish: bool,
+    /// Prefix of the keys to publish the HTML files at, instead of the configured `publish_prefix`.
+    pub publish_prefix: Option<String>,
+}
+
+impl ExportProfile {
+    /// Returns the filter selecting the notes of this profile, if it has a collection or filter.
+    pub fn filter_string(&self) -> Option<String> {
+        match (&self.collection, &self.filter) {
+            (Some(collection), _) => Some(format!("in:{}", collection)),
+            (None, Some(filter)) => Some(filter.trim().to_owned()),
+            (None, None) => None,
+        }
+    }
+
+    /// Returns the kind of export this profile with the given name runs.
+    pub fn kind(&self, name: &str) -> super::ExportKind {
+        match self.format {
+            ExportFormat::Html if self.print => super::ExportKind::PrintHtml,
+            ExportFormat::Html => super::ExportKind::Html,
+            ExportFormat::Pdf if self.print => super::ExportKind::PrintPdf,
+            ExportFormat::Pdf => super::ExportKind::Pdf,
+            ExportFormat::Copy => super::ExportKind::Copy(path::PathBuf::from(
+                self.output.clone().unwrap_or_else(|| name.to_owned()),
+            )),
+            ExportFormat::Zip => super::ExportKind::Zip(path::PathBuf::from(
+                self.output
+                    .clone()
+                    .unwrap_or_else(|| format!("{}.zip", name)),
+            )),
+            ExportFormat::RevealSlides => super::ExportKind::RevealSlides,
+            ExportFormat::TypstSlides => super::ExportKind::TypstSlides,
+            ExportFormat::Anki => super::ExportKind::Anki(path::PathBuf::from(
+                self.output
+                    .clone()
+                    .unwrap_or_else(|| format!("{}.txt", name)),
+            )),
+        }
+    }
+
+    /// Returns the folder to copy the built HTML, PDF or slide files to, if this profile builds them and sets one.
+    pub fn output_folder(&self) -> Option<path::PathBuf> {
+        match self.format {
+            ExportFormat::Copy | ExportFormat::Zip | ExportFormat::Anki => None,
+            _ => self.output.as_ref().map(path::PathBuf::from),
+        }
+    }
+}
+
+/// The export profiles configured by the user, sorted by name.
+#[derive(Debug, Clone, Default)]
+pub struct ExportProfiles {
+    /// The profiles with their names.
+    profiles: Vec<(String, ExportProfile)>,
+}
+
+impl ExportProfiles {
+    /// Creates the export profiles from the given config.
+    pub fn new(config: &crate::Config) -> Self {
+        let mut profiles = config
+            .export_profiles
+            .iter()
+            .map(|(name, profile)| (name.clone(), profile.clone()))
+            .collect::<Vec<_>>();
+        profiles.sort_by(|(a, _), (b, _)| a.cmp(b));
+        Self { profiles }
+    }
+
+    /// Returns the profile with the given name, if there is one.
+    pub fn get(&self, name: &str) -> Option<&ExportProfile> {
+        self.profiles
+            .iter()
+            .find(|(other, _)| other == name)
+            .map(|(_, profile)| profile)
+    }
+
+    /// Returns the names of all profiles with the profiles themselves.
+    pub fn iter(&self) -> impl Iterator<Item = &(String, ExportProfile)> {
+        self.profiles.iter()
+    }
+
+    /// Returns the name of the first profile attached to the given filter, ignoring surrounding whitespace.
+    pub fn for_filter(&self, filter: &str) -> Option<&str> {
+        self.profiles
+            .iter()
+            .find(|(_, profile)| profile.filter_string().as_deref() == Some(filter.trim()))
+            .map(|(name, _)| name.as_str())
+    }
+
+    /// Returns the name of the first profile attached to the collection with the given name.
+    pub fn for_collection(&self, collection: &str) -> Option<&str> {
+        self.profiles
+            .iter()
+            .find(|(_, profile)| profile.collection.as_deref() == Some(collection))
+            .map(|(name, _)| name.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_export_profiles() {
+        let config: crate::Config = toml::from_str(
+            r#"
+            [export_profiles.handouts]
+            collection = "algebra"
+            format = "Pdf"
+            template = "handout"
+            print = true
+            output = "handouts/algebra"
+
+            [export_profiles.archive]
+            filter = " #topology "
+            format = "Zip"
+            "#,
+        )
+        .unwrap();
+        let profiles = ExportProfiles::new(&config);
+
+        assert_eq!(
+            profiles
+                .iter()
+                .map(|(name, _)| name.as_str())
+                .collect::<Vec<_>>(),
+            vec!["archive", "handouts"]
+        );
+        assert_eq!(profiles.for_collection("algebra"), Some("handouts"));
+        assert_eq!(profiles.for_filter("in:algebra"), Some("handouts"));
+        assert_eq!(profiles.for_filter("#topology"), Some("archive"));
+        assert_eq!(profiles.for_filter("#algebra"), None);
+
+        let handouts = profiles.get("handouts").unwrap();
+        assert_eq!(
+            handouts.kind("handouts"),
+            super::super::ExportKind::PrintPdf
+        );
+        assert_eq!(
+            handouts.output_folder(),
+            Some(path::PathBuf::from("handouts/algebra"))
+        );
+
+        // archives are named after the profile unless an output is set
+        let archive = profiles.get("archive").unwrap();
+        assert_eq!(
+            archive.kind("archive"),
+            super::super::ExportKind::Zip(path::PathBuf::from("archive.zip"))
+        );
+        assert_eq!(archive.output_folder(), None);
+        assert!(!archive.publish);
+    }
+}
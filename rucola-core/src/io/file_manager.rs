@@ -0,0 +1,2036 @@
+use crate::{config, data, error};
+use std::{fs, path, process};
+
+/// A note that has been deleted and moved to the trash folder of the vault.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TrashEntry {
+    /// The current path of the deleted file within the trash folder.
+    pub path: path::PathBuf,
+    /// The path (relative to the vault) the note was deleted from and will be restored to.
+    pub original_path: path::PathBuf,
+    /// The time of deletion.
+    pub deleted: chrono::DateTime<chrono::Local>,
+}
+
+/// Saves configurations to manipulate the file system the notes are stored in.
+#[derive(Debug, Clone)]
+pub struct FileManager {
+    /// Path to the vault to index.
+    vault_path: path::PathBuf,
+    /// Default file ending for newly created notes
+    default_extension: String,
+    /// The editor to use for notes
+    editor: Option<Vec<String>>,
+    /// Main viewer to inspect rendered notes.
+    pub(crate) primary_viewer: Option<Vec<String>>,
+    /// Preferred file type of the main viewer.
+    pub(crate) primary_viewer_type: Option<config::ViewerType>,
+    /// Alternative viewer to inspect rendered notes.
+    pub(crate) secondary_viewer: Option<Vec<String>>,
+    /// Preferred file type of the alternative viewer.
+    pub(crate) secondary_viewer_type: Option<config::ViewerType>,
+    /// The file manager to reveal notes in, the system's default one if none.
+    file_manager: Option<Vec<String>>,
+    /// Number of days after which deleted notes are removed from the trash, if any.
+    trash_purge_days: Option<u64>,
+    /// The IANA name of the timezone to use for written timestamps, system timezone if none.
+    timezone: Option<String>,
+    /// Format of written timestamps.
+    timestamp_format: String,
+    /// Wether to update the `modified` field in the frontmatter of changed notes.
+    update_modified: bool,
+    /// Wether to add a `created` field to the frontmatter of created and changed notes.
+    update_created: bool,
+    /// How carefully written files are flushed to the disk.
+    durability: super::Durability,
+    /// The lock of the vault, which has to be held to change any files. Without one, files can always be changed.
+    lock: Option<super::VaultLock>,
+    /// The collections of the vault, kept up to date when notes are renamed.
+    collections: Option<super::Collections>,
+    /// The visit counts of the notes, kept up to date when notes are renamed.
+    visits: Option<super::Visits>,
+    /// Edits made from the UI that are written after a delay. Without one, edits are written immediately.
+    autosave: Option<super::Autosave>,
+}
+impl Default for FileManager {
+    fn default() -> Self {
+        Self::new(
+            &crate::Config::default(),
+            std::env::current_dir().expect("Current directory to exist and be accessible."),
+        )
+    }
+}
+
+impl FileManager {
+    pub fn new(config: &crate::Config, vault_path: path::PathBuf) -> Self {
+        Self {
+            vault_path,
+            default_extension: config.default_extension.clone(),
+            editor: config.editor.clone(),
+            primary_viewer: config.viewer.clone(),
+            primary_viewer_type: config.viewer_type,
+            secondary_viewer: config.secondary_viewer.clone(),
+            secondary_viewer_type: config.secondary_viewer_type,
+            file_manager: config.file_manager.clone(),
+            trash_purge_days: config.trash_purge_days,
+            timezone: config.timezone.clone(),
+            timestamp_format: config.timestamp_format.clone(),
+            update_modified: config.update_modified,
+            update_created: config.update_created,
+            durability: config.write_durability,
+            lock: None,
+            collections: None,
+            visits: None,
+            autosave: None,
+        }
+    }
+
+    /// Only allows changes to files while the given lock of the vault is held.
+    pub fn with_lock(mut self, lock: super::VaultLock) -> Self {
+        self.lock = Some(lock);
+        self
+    }
+
+    /// Replaces the ids of renamed notes in the given collections.
+    pub fn with_collections(mut self, collections: super::Collections) -> Self {
+        self.collections = Some(collections);
+        self
+    }
+
+    /// Moves the visits of renamed notes to their new ids in the given visit counts.
+    pub fn with_visits(mut self, visits: super::Visits) -> Self {
+        self.visits = Some(visits);
+        self
+    }
+
+    /// Delays writing notes edited from the UI until they have not been edited for a while, storing the edits in the given autosave until then.
+    pub fn with_autosave(mut self, autosave: super::Autosave) -> Self {
+        self.autosave = Some(autosave);
+        self
+    }
+
+    /// Returns an error if the vault is locked by another instance of rucola, so no files may be changed.
+    pub fn ensure_writable(&self) -> error::Result<()> {
+        match &self.lock {
+            Some(lock) => lock.ensure_writable(),
+            None => Ok(()),
+        }
+    }
+
+    /// Returns the current time, formatted according to the configured timezone and format.
+    pub fn timestamp(&self) -> error::Result<String> {
+        self.format_time(chrono::Utc::now(), &self.timestamp_format)
+    }
+
+    /// Returns the current date in the configured timezone.
+    pub fn today(&self) -> error::Result<chrono::NaiveDate> {
+        Ok(match &self.timezone {
+            Some(timezone) => {
+                let timezone = timezone.parse::<chrono_tz::Tz>().map_err(|_| {
+                    error::RucolaError::Input(format!("Unknown timezone: {}", timezone))
+                })?;
+                chrono::Utc::now().with_timezone(&timezone).date_naive()
+            }
+            None => chrono::Local::now().date_naive(),
+        })
+    }
+
+    /// Formats the given time in the configured timezone according to the given format.
+    fn format_time(
+        &self,
+        time: chrono::DateTime<chrono::Utc>,
+        format: &str,
+    ) -> error::Result<String> {
+        Ok(match &self.timezone {
+            Some(timezone) => {
+                let timezone = timezone.parse::<chrono_tz::Tz>().map_err(|_| {
+                    error::RucolaError::Input(format!("Unknown timezone: {}", timezone))
+                })?;
+                time.with_timezone(&timezone).format(format).to_string()
+            }
+            None => time
+                .with_timezone(&chrono::Local)
+                .format(format)
+                .to_string(),
+        })
+    }
+
+    /// Writes the given content to the note at the given path.
+    /// If configured, the `modified` field of the note's frontmatter is set to the current time first and a missing `created` field is set to the creation time of the file.
+    /// Notes without frontmatter are written as they are.
+    fn write_note(&self, path: &path::Path, content: &str) -> error::Result<()> {
+        self.ensure_writable()?;
+
+        let mut content = content.to_owned();
+
+        if self.update_created && frontmatter_field(&content, "created").is_none() {
+            // Not all file systems know when a file was created, skip the field then.
+            if let Ok(created) = fs::metadata(path).and_then(|metadata| metadata.created()) {
+                if let Some(updated) = set_frontmatter_field(
+                    &content,
+                    "created",
+                    &self.format_time(created.into(), &self.timestamp_format)?,
+                ) {
+                    content = updated;
+                }
+            }
+        }
+
+        if self.update_modified {
+            if let Some(updated) = set_frontmatter_field(&content, "modified", &self.timestamp()?) {
+                content = updated;
+            }
+        }
+
+        super::write_atomically(path, content, self.durability)?;
+
+        // the written content replaces any edits not written yet
+        if let Some(autosave) = &self.autosave {
+            autosave.discard(path);
+        }
+        Ok(())
+    }
+
+    /// Reads the content of the note at the given path, including edits made from the UI that have not been written yet.
+    fn read_note(&self, path: &path::Path) -> error::Result<String> {
+        match self
+            .autosave
+            .as_ref()
+            .and_then(|autosave| autosave.content(path))
+        {
+            Some(content) => Ok(content),
+            None => Ok(fs::read_to_string(path)?),
+        }
+    }
+
+    /// Changes the content of the note at the given path as the result of an edit made from the UI.
+    /// With autosave, the note is written once it has not been edited for the configured delay, otherwise immediately.
+    fn edit_note(&self, path: &path::Path, content: String) -> error::Result<()> {
+        self.ensure_writable()?;
+
+        match &self.autosave {
+            Some(autosave) => {
+                autosave.edit(path, content);
+                Ok(())
+            }
+            None => self.write_note(path, &content),
+        }
+    }
+
+    /// Writes all edits made from the UI that have not been changed for the autosave delay.
+    /// Returns wether any note was written.
+    pub fn save_due_edits(&self) -> error::Result<bool> {
+        self.save_edits(false)
+    }
+
+    /// Writes all edits made from the UI that have not been written yet, e.g. before quitting or opening an editor.
+    pub fn save_all_edits(&self) -> error::Result<()> {
+        self.save_edits(true).map(|_| ())
+    }
+
+    /// Writes the edits of the autosave that are due, or all of them.
+    fn save_edits(&self, all: bool) -> error::Result<bool> {
+        let Some(autosave) = &self.autosave else {
+            return Ok(false);
+        };
+
+        let edits = autosave.take(all);
+        let written = !edits.is_empty();
+
+        let mut edits = edits.into_iter();
+        while let Some((path, content)) = edits.next() {
+            if let Err(e) = self.write_note(&path, &content) {
+                // keep the edits that could not be written, to try again later
+                autosave.edit(&path, content);
+                for (path, content) in edits {
+                    autosave.edit(&path, content);
+                }
+                return Err(e);
+            }
+        }
+        Ok(written)
+    }
+
+    /// Checks if there are edits made from the UI that have not been written yet.
+    pub fn has_unsaved_edits(&self) -> bool {
+        self.autosave
+            .as_ref()
+            .is_some_and(|autosave| autosave.is_dirty())
+    }
+
+    /// Returns how long it takes until the next edit made from the UI is written, if there are any.
+    pub fn next_save_in(&self) -> Option<std::time::Duration> {
+        self.autosave
+            .as_ref()
+            .and_then(|autosave| autosave.due_in())
+    }
+
+    /// Replaces the content of the note at the given path, e.g. to restore a previous version of it.
+    pub fn restore_note_content(&self, path: &path::Path, content: &str) -> error::Result<()> {
+        self.write_note(path, content)
+    }
+
+    /// Returns the title of the managed vault
+    pub fn get_vault_title(&self) -> String {
+        format!(
+            "Notes in {}",
+            self.vault_path
+                .as_path()
+                .file_name()
+                .and_then(|folder| folder.to_str())
+                .unwrap_or("Unknown Folder")
+        )
+    }
+
+    /// Returns the path of the managed vault.
+    pub fn vault_path(&self) -> &path::Path {
+        &self.vault_path
+    }
+
+    /// Takes in a PathBuf and, if the current file extension is not set, append the default one.
+    pub fn ensure_file_extension(&self, path: &mut path::PathBuf) {
+        if path.extension().is_none() {
+            path.set_extension(&self.default_extension);
+        }
+    }
+
+    /// Checks if 'new_name' is a valid new file name, in particular not a path.
+    /// Then retrieves the note of the given id from the index.
+    /// Creates a new path from the old path with the new file name.
+    /// The new extension is the one from the new path if given, if none is given (and no extension is not valid in the config), then the old extension is reapplied.
+    /// Then moves the old file to the new location and updates the index.
+    pub fn rename_note_file(
+        &self,
+        index: data::NoteIndexContainer,
+        id: &str,
+        new_name: String,
+    ) -> error::Result<()> {
+        self.ensure_writable()?;
+        // Edits not written yet would restore moved notes or be overwritten.
+        self.save_all_edits()?;
+
+        // Check that the new name isn't empty
+        if new_name.is_empty() {
+            return Err(error::RucolaError::Input(String::from(
+                "Name cannot be empty!",
+            )));
+        }
+
+        // Create a path from the input.
+        let input_path = path::Path::new(&new_name);
+
+        // Check that the user hasn't given a full path
+        if input_path.components().count() > 1 {
+            return Err(error::RucolaError::Input(
+                "File name cannot be a path.".to_owned(),
+            ));
+        }
+
+        // Retrieve the old version from the table
+        // This will not be changed - all changes to the index are handled by the watcher.
+        let index_b = index.borrow_mut();
+        let note = index_b
+            .get(id)
+            .ok_or_else(|| error::RucolaError::NoteNotFound(id.to_owned()))?;
+
+        // Create a new path by combining the name from the input with the rest of the old path.
+        let mut new_path = note.path.clone();
+        new_path.set_file_name(
+            input_path
+                .file_name()
+                .ok_or_else(|| error::RucolaError::Input("New name cannot be empty.".to_owned()))?,
+        );
+
+        // If this new name has not introduced an extension, re-set the previous one.
+        if new_path.extension().is_none() {
+            if let Some(old_extension) = note.path.extension() {
+                new_path.set_extension(old_extension);
+            } else {
+                self.ensure_file_extension(&mut new_path);
+            }
+        }
+
+        // ensure parent directory exists
+        if let Some(parent) = new_path.parent() {
+            if !parent.exists() {
+                fs::create_dir_all(parent)?;
+            }
+        }
+
+        // actual fs copy (early returns if unsuccessfull)
+        fs::rename(&note.path, &new_path)?;
+
+        // === RENAMING ===
+        // Update all links to the old name or id
+        let names = std::collections::HashMap::from([(id.to_owned(), new_name)]);
+        for other_note in index_b
+            // search for references to the old id.
+            .blinks_vec(id)
+            .iter()
+            .filter_map(|(id, _)| index_b.get(id))
+        {
+            // open the file once to read its old content
+            let old_content = self.read_note(&other_note.path)?;
+
+            let res = data::rename_links(&old_content, &names);
+
+            // write new new (mostly old) string into the file
+            if res != old_content {
+                self.write_note(&other_note.path, &res)?;
+            }
+        }
+
+        let new_id = data::name_to_id(&new_path.file_stem().unwrap_or_default().to_string_lossy());
+
+        // Keep the note in its collections
+        if let Some(collections) = &self.collections {
+            collections.rename_note(id, &new_id)?;
+        }
+
+        // Keep the visits of the note
+        if let Some(visits) = &self.visits {
+            visits.rename_note(id, &new_id)?;
+        }
+
+        Ok(())
+    }
+
+    pub fn move_note_file(
+        &self,
+        index: data::NoteIndexContainer,
+        id: &str,
+        new_path_buf: String,
+    ) -> error::Result<()> {
+        self.ensure_writable()?;
+        // Edits not written yet would restore moved notes or be overwritten.
+        self.save_all_edits()?;
+
+        let index_b = index.borrow_mut();
+        // Retrieve the note in question from the table
+        // It will not be changed - all changes to the index are handled by the watcher.
+        let note = index_b
+            .get(id)
+            .ok_or_else(|| error::RucolaError::NoteNotFound(id.to_owned()))?;
+
+        // Create a path from the given buffer (handling the parsing of the path).
+        // Then extend vault path with given path
+        let mut new_path = self.vault_path.join(new_path_buf).join(&note.name);
+
+        // Ensure file extension just to be safe
+        self.ensure_file_extension(&mut new_path);
+
+        // Ensure parent directory exists
+        if let Some(parent) = new_path.parent() {
+            if !parent.exists() {
+                fs::create_dir_all(parent)?;
+            }
+        }
+
+        // actual fs copy (early returns if unsuccessfull)
+        fs::rename(&note.path, &new_path)?;
+
+        Ok(())
+    }
+
+    /// Applies the given migration: First updates the links in all edited notes, then renames the note files.
+    pub fn apply_migration(&self, migration: &data::Migration) -> error::Result<()> {
+        self.ensure_writable()?;
+        // Edits not written yet would restore moved notes or be overwritten.
+        self.save_all_edits()?;
+
+        for edit in &migration.edits {
+            self.write_note(&edit.path, &edit.new)?;
+        }
+
+        for rename in &migration.renames {
+            fs::rename(&rename.from, &rename.to)?;
+        }
+
+        Ok(())
+    }
+
+    /// Writes the created and refreshed maps of content to the vault.
+    pub fn apply_maps_of_content(&self, mocs: &data::MapsOfContent) -> error::Result<()> {
+        self.ensure_writable()?;
+        // Edits not written yet would restore moved notes or be overwritten.
+        self.save_all_edits()?;
+
+        for edit in &mocs.edits {
+            // ensure parent directory exists
+            if let Some(parent) = edit.path.parent() {
+                if !parent.exists() {
+                    fs::create_dir_all(parent)?;
+                }
+            }
+            self.write_note(&edit.path, &edit.new)?;
+        }
+
+        Ok(())
+    }
+
+    /// Follows a notes path and moves it to the trash folder of the vault.
+    /// Within the trash, notes are stored at their path relative to the vault, in a subfolder named after the time of deletion.
+    pub fn delete_note_file(&self, index: data::NoteIndexContainer, id: &str) -> error::Result<()> {
+        self.ensure_writable()?;
+        // Edits not written yet would restore moved notes or be overwritten.
+        self.save_all_edits()?;
+
+        if let Some(note) = index.borrow().get(id) {
+            let vault_path = self
+                .vault_path
+                .canonicalize()
+                .unwrap_or(self.vault_path.clone());
+
+            // Notes outside the vault (e.g. via symlinks) keep only their file name.
+            let relative_path = note
+                .path
+                .strip_prefix(&vault_path)
+                .map(|path| path.to_path_buf())
+                .or_else(|_| note.path.file_name().map(path::PathBuf::from).ok_or(()))
+                .map_err(|_| error::RucolaError::NoteNameCannotBeRead(note.path.clone()))?;
+
+            let trash_path = self
+                .trash_path()
+                .join(chrono::Local::now().timestamp().to_string())
+                .join(relative_path);
+
+            // ensure parent directory exists
+            if let Some(parent) = trash_path.parent() {
+                if !parent.exists() {
+                    fs::create_dir_all(parent)?;
+                }
+            }
+
+            // Follow its path and move it
+            fs::rename(&note.path, &trash_path)?;
+        }
+        Ok(())
+    }
+
+    /// Returns the path of the trash folder of the vault.
+    fn trash_path(&self) -> path::PathBuf {
+        self.vault_path.join(".trash")
+    }
+
+    /// Lists all notes currently in the trash, most recently deleted first.
+    pub fn trash_entries(&self) -> error::Result<Vec<TrashEntry>> {
+        let trash_path = self.trash_path();
+
+        if !trash_path.exists() {
+            return Ok(Vec::new());
+        }
+
+        let mut entries = Vec::new();
+
+        for folder in fs::read_dir(&trash_path)?.flatten() {
+            // Folders in the trash are named after their unix timestamp of deletion, skip anything else.
+            let Some(deleted) = folder
+                .file_name()
+                .to_str()
+                .and_then(|name| name.parse::<i64>().ok())
+                .and_then(|secs| chrono::DateTime::from_timestamp(secs, 0))
+                .map(|time| time.with_timezone(&chrono::Local))
+            else {
+                continue;
+            };
+
+            for file in ignore::WalkBuilder::new(folder.path())
+                .standard_filters(false)
+                .build()
+                .flatten()
+                .filter(|file| file.file_type().is_some_and(|ft| ft.is_file()))
+            {
+                if let Ok(original_path) = file.path().strip_prefix(folder.path()) {
+                    entries.push(TrashEntry {
+                        path: file.path().to_path_buf(),
+                        original_path: original_path.to_path_buf(),
+                        deleted,
+                    });
+                }
+            }
+        }
+
+        entries.sort_by(|a, b| b.deleted.cmp(&a.deleted).then(a.path.cmp(&b.path)));
+
+        Ok(entries)
+    }
+
+    /// Moves a note from the trash back to its original location in the vault.
+    /// Fails if there is already a file at that location.
+    pub fn restore_trash_entry(&self, entry: &TrashEntry) -> error::Result<()> {
+        self.ensure_writable()?;
+
+        let target = self.vault_path.join(&entry.original_path);
+
+        if target.exists() {
+            return Err(error::RucolaError::Input(format!(
+                "Cannot restore note, {} already exists.",
+                entry.original_path.display()
+            )));
+        }
+
+        // ensure parent directory exists
+        if let Some(parent) = target.parent() {
+            if !parent.exists() {
+                fs::create_dir_all(parent)?;
+            }
+        }
+
+        fs::rename(&entry.path, &target)?;
+
+        self.remove_empty_trash_folders()
+    }
+
+    /// Permanently deletes a note from the trash.
+    pub fn purge_trash_entry(&self, entry: &TrashEntry) -> error::Result<()> {
+        self.ensure_writable()?;
+
+        fs::remove_file(&entry.path)?;
+
+        self.remove_empty_trash_folders()
+    }
+
+    /// Lists all notes that have been in the trash for longer than configured, none if they are never removed automatically.
+    pub fn expired_trash_entries(&self) -> error::Result<Vec<TrashEntry>> {
+        let Some(days) = self.trash_purge_days else {
+            return Ok(Vec::new());
+        };
+
+        let cutoff = chrono::Local::now() - chrono::Duration::days(days as i64);
+
+        Ok(self
+            .trash_entries()?
+            .into_iter()
+            .filter(|entry| entry.deleted < cutoff)
+            .collect())
+    }
+
+    /// Permanently deletes all notes that have been in the trash for longer than configured.
+    pub fn purge_old_trash(&self) -> error::Result<()> {
+        self.ensure_writable()?;
+
+        for entry in self.expired_trash_entries()? {
+            fs::remove_file(&entry.path)?;
+        }
+
+        self.remove_empty_trash_folders()
+    }
+
+    /// Removes all folders within the trash that no longer contain any files.
+    pub fn remove_empty_trash_folders(&self) -> error::Result<()> {
+        /// Recursively removes empty folders, returning wether the given folder was removed.
+        fn remove_if_empty(folder: &path::Path) -> std::io::Result<bool> {
+            let mut empty = true;
+            for entry in fs::read_dir(folder)?.flatten() {
+                if !(entry.file_type()?.is_dir() && remove_if_empty(&entry.path())?) {
+                    empty = false;
+                }
+            }
+            if empty {
+                fs::remove_dir(folder)?;
+            }
+            Ok(empty)
+        }
+
+        if self.trash_path().exists() {
+            for folder in fs::read_dir(self.trash_path())?.flatten() {
+                if folder.file_type()?.is_dir() {
+                    remove_if_empty(&folder.path())?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Creates a note of the given name in the file system (relative to the vault) and returns its path.
+    /// Registration in the index is handled centrally by the file watcher of the index itself.
+    pub fn create_note_file(&self, input_path: &str) -> error::Result<path::PathBuf> {
+        let path = self.new_note_path(input_path)?;
+
+        // Write an preliminary input, so the file isn't empty (messed with XDG for some reason).
+        let title = format!(
+            "# {}",
+            path.file_stem()
+                .map(|fs| fs.to_string_lossy().to_string())
+                .unwrap_or_else(|| "note".to_owned())
+        );
+
+        self.write_new_note(&path, &title)?;
+
+        Ok(path)
+    }
+
+    /// Creates a note of the given name (relative to the vault) with the given text, unless a file exists at its path already.
+    /// Returns the path of the created note, or none if it already existed.
+    pub fn create_note_with_text(
+        &self,
+        input_path: &str,
+        text: &str,
+    ) -> error::Result<Option<path::PathBuf>> {
+        let path = self.new_note_path(input_path)?;
+
+        if path.exists() {
+            return Ok(None);
+        }
+
+        self.write_new_note(&path, text)?;
+
+        Ok(Some(path))
+    }
+
+    /// Returns the path of a new note of the given name (relative to the vault), with the default extension if it has none, and creates its folder.
+    fn new_note_path(&self, input_path: &str) -> error::Result<path::PathBuf> {
+        self.ensure_writable()?;
+
+        // Piece together the file path
+        let mut path = self.vault_path.clone();
+        path.push(input_path);
+
+        // If there was no manual extension set, take the default one
+        self.ensure_file_extension(&mut path);
+
+        // ensure parent directory exists
+        if let Some(parent) = path.parent() {
+            if !parent.exists() {
+                fs::create_dir_all(parent)?;
+            }
+        }
+
+        Ok(path)
+    }
+
+    /// Writes a new note with the given text to the given path.
+    /// If configured, its frontmatter contains its creation time, which is added to the frontmatter of the text or to a new one.
+    fn write_new_note(&self, path: &path::Path, text: &str) -> error::Result<()> {
+        let mut fields = Vec::new();
+        if self.update_created || self.update_modified {
+            let timestamp = self.timestamp()?;
+            if self.update_created && frontmatter_field(text, "created").is_none() {
+                fields.push(("created", timestamp.clone()));
+            }
+            if self.update_modified {
+                fields.push(("modified", timestamp));
+            }
+        }
+
+        let content = if text.lines().next().map(str::trim_end) == Some("---") {
+            fields
+                .iter()
+                .fold(text.to_owned(), |content, (key, value)| {
+                    set_frontmatter_field(&content, key, value).unwrap_or(content)
+                })
+        } else if fields.is_empty() {
+            text.to_owned()
+        } else {
+            // start the note with a frontmatter containing its creation time
+            format!(
+                "---\n{}---\n{}",
+                fields
+                    .iter()
+                    .map(|(key, value)| format!("{}: {}\n", key, value))
+                    .collect::<String>(),
+                text
+            )
+        };
+
+        // Create the file
+        super::write_atomically(path, content, self.durability)?;
+
+        Ok(())
+    }
+
+    /// Replaces the mentions of the given suggestions in the note at the given path with wiki links to their targets.
+    /// Mentions are searched for again in the current content, so suggestions whose mentions have disappeared in the meantime are skipped.
+    pub fn insert_links(
+        &self,
+        path: &path::Path,
+        suggestions: &[data::LinkSuggestion],
+    ) -> error::Result<()> {
+        let mut content = self.read_note(path)?;
+
+        // Find all mentions before modifying the content, then replace them back to front so the ranges stay valid.
+        let mentions = suggestions
+            .iter()
+            .map(|suggestion| suggestion.mention.as_str())
+            .collect::<Vec<_>>();
+        let mut replacements = data::find_first_mentions(&content, &mentions)?
+            .into_iter()
+            .zip(suggestions)
+            .flat_map(|(range, suggestion)| range.map(|range| (range, suggestion.to_wikilink())))
+            .collect::<Vec<_>>();
+        replacements.sort_by_key(|(range, _link)| std::cmp::Reverse(range.start));
+
+        let mut last_start = content.len();
+        for (range, link) in replacements {
+            // skip mentions overlapping an already replaced one
+            if range.end <= last_start {
+                last_start = range.start;
+                content.replace_range(range, &link);
+            }
+        }
+
+        self.edit_note(path, content)
+    }
+
+    /// Copies the files referenced by the note of the given plan into the vault and rewrites its links to them.
+    /// Files that have been replaced by other files in the meantime are not overwritten, their links are kept and an error is returned once all other links are rewritten.
+    pub fn apply_import(&self, plan: &super::ImportPlan) -> error::Result<()> {
+        self.ensure_writable()?;
+
+        let mut targets = std::collections::HashMap::new();
+        let mut skipped = Vec::new();
+        for link in &plan.links {
+            if let Some((source, destination)) = &link.copy {
+                if !destination.exists() {
+                    if let Some(parent) = destination.parent() {
+                        fs::create_dir_all(parent)?;
+                    }
+                    fs::copy(source, destination)?;
+                } else if !super::importer::is_same_content(source, destination) {
+                    skipped.push(destination.display().to_string());
+                    continue;
+                }
+            }
+            targets.insert(link.target.clone(), link.rewritten.clone());
+        }
+
+        let content = self.read_note(&plan.path)?;
+        let rewritten = data::rewrite_link_targets(&content, &targets);
+        if rewritten != content {
+            self.write_note(&plan.path, &rewritten)?;
+        }
+
+        match skipped.is_empty() {
+            true => Ok(()),
+            false => Err(error::RucolaError::Input(format!(
+                "Files exist already and were not replaced: {}",
+                skipped.join(", ")
+            ))),
+        }
+    }
+
+    /// Adds the given tags to the `tags` list in the frontmatter of the note at the given path.
+    pub fn add_tags(&self, path: &path::Path, tags: &[String]) -> error::Result<()> {
+        if tags.is_empty() {
+            return Ok(());
+        }
+
+        let content = self.read_note(path)?;
+
+        self.edit_note(path, add_frontmatter_tags(&content, tags))
+    }
+
+    /// Fixes the problems of the given note that the given linter can fix automatically.
+    pub fn fix_note(&self, note: &data::Note, linter: &data::Linter) -> error::Result<()> {
+        let content = self.read_note(&note.path)?;
+        let fixed = linter.fix(note, &content);
+
+        if fixed != content {
+            self.edit_note(&note.path, fixed)?;
+        }
+        Ok(())
+    }
+
+    /// Inserts the given snippet into the given note at the end of the section of the given heading, or at the end of the note without a heading.
+    /// The placeholders `{{title}}`, `{{name}}`, `{{date}}`, `{{time}}` and `{{timestamp}}` are replaced by the title and name of the note and the current date and time.
+    pub fn insert_snippet(
+        &self,
+        note: &data::Note,
+        snippet: &data::Snippet,
+        heading: Option<&data::Heading>,
+    ) -> error::Result<()> {
+        let content = self.read_note(&note.path)?;
+
+        let now = chrono::Utc::now();
+        let date = self.format_time(now, "%Y-%m-%d")?;
+        let time = self.format_time(now, "%H:%M")?;
+        let timestamp = self.format_time(now, &self.timestamp_format)?;
+        let text = snippet.expand(&[
+            ("title", &note.display_name),
+            ("name", &note.name),
+            ("date", &date),
+            ("time", &time),
+            ("timestamp", &timestamp),
+        ]);
+
+        let content = data::insert_snippet(&content, &text, heading).ok_or_else(|| {
+            error::RucolaError::Input(format!(
+                "The heading '{}' no longer exists.",
+                heading
+                    .map(|heading| heading.text.as_str())
+                    .unwrap_or_default()
+            ))
+        })?;
+
+        self.edit_note(&note.path, content)
+    }
+
+    /// Stores the given summary under the `summary` key of the frontmatter of the note at the given path, replacing any previous one.
+    /// Creates a frontmatter if the note has none.
+    pub fn set_summary(&self, path: &path::Path, summary: &str) -> error::Result<()> {
+        let content = self.read_note(path)?;
+
+        // a JSON string is a valid double-quoted YAML string
+        let value = serde_json::to_string(summary)
+            .map_err(|e| error::RucolaError::SummaryError(e.to_string()))?;
+
+        let content = set_frontmatter_field(&content, "summary", &value)
+            .unwrap_or_else(|| format!("---\nsummary: {}\n---\n{}", value, content));
+
+        self.edit_note(path, content)
+    }
+
+    /// Writes the given content to the file at the given path, e.g. an export requested on the command line.
+    pub fn write_file(&self, path: &path::Path, content: &str) -> error::Result<()> {
+        super::write_atomically(path, content, self.durability)?;
+        Ok(())
+    }
+
+    /// Writes the statistics of the given environment to a CSV file at the given path (relative to the vault) and returns its full path.
+    pub fn export_stats(
+        &self,
+        index: data::NoteIndexContainer,
+        stats: &data::EnvironmentStats,
+        path: &path::Path,
+    ) -> error::Result<path::PathBuf> {
+        let path = self.vault_path.join(path);
+
+        let mut csv = Vec::new();
+        stats.to_csv(index, &mut csv)?;
+        super::write_atomically(&path, csv, self.durability)?;
+
+        Ok(path)
+    }
+
+    /// Attempts to create a command to open the file at the given path to edit it.
+    /// Target should be a markdown file.
+    /// Checks:
+    ///  - The config file
+    ///  - The $EDITOR environment variable
+    ///  - the systems default programms
+    ///
+    /// for an applicable program.
+    pub fn create_edit_command(
+        &self,
+        path: &path::PathBuf,
+    ) -> error::Result<std::process::Command> {
+        self.create_edit_command_at(path, None)
+    }
+
+    /// Attempts to create a command to open the file at the given path at the given line, like `create_edit_command`.
+    /// The line is passed to editors from the config file that contain `%l` in an argument, such as `+%l` or `%p:%l`. Without a line, `%l` is replaced by 1.
+    pub fn create_edit_command_at(
+        &self,
+        path: &path::PathBuf,
+        line: Option<usize>,
+    ) -> error::Result<std::process::Command> {
+        // the editor would change the note
+        self.ensure_writable()?;
+
+        // take the editor from the config file
+        self.editor
+            .as_ref()
+            // create a command from it
+            .and_then(|editor_arg_list| {
+                let mut iter = editor_arg_list.iter();
+                if let Some(programm) = iter.next() {
+                    let mut cmd = process::Command::new(programm);
+                    for arg in iter {
+                        if arg == "%p" {
+                            // special argument for the user to indicate where to put the path
+                            cmd.arg(path.canonicalize().as_ref().unwrap_or(path));
+                        } else if arg.contains("%l") {
+                            // the line may be combined with the path in one argument
+                            cmd.arg(
+                                arg.replace(
+                                    "%p",
+                                    &path
+                                        .canonicalize()
+                                        .as_ref()
+                                        .unwrap_or(path)
+                                        .to_string_lossy(),
+                                )
+                                .replace("%l", &line.unwrap_or(1).to_string()),
+                            );
+                        } else {
+                            // all other arguments are appended in order
+                            cmd.arg(arg);
+                        }
+                    }
+                    Some(cmd)
+                } else {
+                    None
+                }
+            })
+            // Try the $EDITOR variable
+            .or_else(|| {
+                std::env::var("EDITOR")
+                    .ok()
+                    .map(|editor| open::with_command(path, editor))
+            })
+            // if it was not there, take the default command
+            .or_else(|| open::commands(path).pop())
+            // if it was also not there, throw an error
+            .ok_or(error::RucolaError::ApplicationMissing)
+    }
+
+    /// Returns the file type of the primary or secondary viewer.
+    /// The secondary viewer falls back to the type of the primary one, which falls back to HTML.
+    pub fn viewer_type(&self, primary: bool) -> config::ViewerType {
+        if primary {
+            self.primary_viewer_type
+        } else {
+            self.secondary_viewer_type.or(self.primary_viewer_type)
+        }
+        .unwrap_or_default()
+    }
+
+    /// Attempts to create a command to open the file at the given path to view it.
+    /// Target should be an html or PDF file, which has to be built beforehand.
+    /// Checks:
+    ///  - The config file
+    ///  - the systems default programms
+    ///
+    /// for an applicable program.
+    ///
+    /// The boolean flag changes between the primary and secondary viewers.
+    pub fn create_view_command(
+        &self,
+        note: &data::Note,
+        primary: bool,
+    ) -> error::Result<std::process::Command> {
+        // generate the appropriate path
+        let path = match self.viewer_type(primary) {
+            config::ViewerType::Html => {
+                super::html_builder::name_to_html_path(&note.name, &self.vault_path)
+            }
+            config::ViewerType::Markdown => note.path.clone(),
+            config::ViewerType::Pdf => {
+                super::pdf_builder::name_to_pdf_path(&note.name, &self.vault_path)
+            }
+        };
+
+        // take the viewer
+        let viewer = if primary {
+            self.primary_viewer.as_ref()
+        } else {
+            self.secondary_viewer.as_ref()
+        };
+
+        viewer
+            // create a command from it
+            .and_then(|viewer_arg_list| {
+                let mut iter = viewer_arg_list.iter();
+                if let Some(programm) = iter.next() {
+                    let mut cmd = process::Command::new(programm);
+                    for arg in iter {
+                        if arg == "%p" {
+                            // special argument for the user to indicate where to put the path
+                            cmd.arg(path.canonicalize().as_ref().unwrap_or(&path));
+                        } else {
+                            // all other arguments are appended in order
+                            cmd.arg(arg);
+                        }
+                    }
+                    Some(cmd)
+                } else {
+                    None
+                }
+            })
+            // if it was not there, take the default command
+            .or_else(|| open::commands(&path).pop())
+            // if it was also not there, throw an error
+            .ok_or(error::RucolaError::ApplicationMissing)
+    }
+
+    /// Attempts to create a command to show the note at the given path in a file manager.
+    /// Checks:
+    ///  - The config file
+    ///  - the file manager of the system, selecting the note on macOS and Windows
+    ///  - the systems default programm for the folder of the note
+    ///
+    /// for an applicable program.
+    pub fn create_reveal_command(&self, path: &path::Path) -> error::Result<std::process::Command> {
+        let path = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+        let folder = path
+            .parent()
+            .map(path::Path::to_path_buf)
+            .unwrap_or_else(|| self.vault_path.clone());
+
+        self.file_manager
+            .clone()
+            .or_else(default_file_manager)
+            // create a command from it
+            .and_then(|file_manager_arg_list| {
+                let mut iter = file_manager_arg_list.iter();
+                if let Some(programm) = iter.next() {
+                    let mut cmd = process::Command::new(programm);
+                    for arg in iter {
+                        if arg == "%p" {
+                            // special argument for the user to indicate where to put the folder
+                            cmd.arg(&folder);
+                        } else if arg.contains("%f") {
+                            // the note file may be combined with an option in one argument
+                            cmd.arg(arg.replace("%f", &path.to_string_lossy()));
+                        } else {
+                            // all other arguments are appended in order
+                            cmd.arg(arg);
+                        }
+                    }
+                    Some(cmd)
+                } else {
+                    None
+                }
+            })
+            // if it was not there, open the folder with the default command
+            .or_else(|| open::commands(&folder).pop())
+            // if it was also not there, throw an error
+            .ok_or(error::RucolaError::ApplicationMissing)
+    }
+}
+
+/// Returns the command to reveal a file in the file manager of the system, if it can select files.
+/// Other systems open the folder of the note with their default program instead.
+fn default_file_manager() -> Option<Vec<String>> {
+    if cfg!(target_os = "macos") {
+        Some(vec![
+            String::from("open"),
+            String::from("-R"),
+            String::from("%f"),
+        ])
+    } else if cfg!(target_os = "windows") {
+        Some(vec![String::from("explorer"), String::from("/select,%f")])
+    } else {
+        None
+    }
+}
+
+/// Returns the value of the given field in the YAML frontmatter of the given content, if there is one.
+fn frontmatter_field<'a>(content: &'a str, key: &str) -> Option<&'a str> {
+    let mut lines = content.lines();
+
+    // The frontmatter has to start in the very first line.
+    if lines.next().map(|line| line.trim_end()) != Some("---") {
+        return None;
+    }
+
+    lines
+        .take_while(|line| line.trim_end() != "---")
+        .find_map(|line| line.strip_prefix(key)?.strip_prefix(':'))
+        .map(|value| value.trim())
+}
+
+/// Sets the given field in the YAML frontmatter of the given content to the given value, adding the field if neccessary.
+/// Returns the modified content, or none if the content has no frontmatter.
+fn set_frontmatter_field(content: &str, key: &str, value: &str) -> Option<String> {
+    let mut lines = content.split_inclusive('\n').collect::<Vec<_>>();
+
+    // The frontmatter has to start in the very first line.
+    if lines.first().map(|line| line.trim_end()) != Some("---") {
+        return None;
+    }
+
+    let end = lines
+        .iter()
+        .skip(1)
+        .position(|line| line.trim_end() == "---")?
+        + 1;
+
+    let entry = format!("{}: {}\n", key, value);
+    let prefix = format!("{}:", key);
+
+    match lines[1..end]
+        .iter()
+        .position(|line| line.starts_with(&prefix))
+    {
+        Some(pos) => lines[pos + 1] = &entry,
+        None => lines.insert(end, &entry),
+    }
+
+    Some(lines.concat())
+}
+
+/// Adds the given tags (with or without the leading `#`) to the `tags` list in the YAML frontmatter of the given content.
+/// Creates the frontmatter or the list if neccessary and keeps the style of an existing list.
+fn add_frontmatter_tags(content: &str, tags: &[String]) -> String {
+    let tags = tags
+        .iter()
+        .map(|tag| tag.trim_start_matches('#'))
+        .collect::<Vec<_>>();
+
+    let mut lines = content
+        .split_inclusive('\n')
+        .map(str::to_owned)
+        .collect::<Vec<_>>();
+
+    // The frontmatter has to start in the very first line.
+    let end = if lines.first().map(|line| line.trim_end()) == Some("---") {
+        lines
+            .iter()
+            .skip(1)
+            .position(|line| line.trim_end() == "---")
+            .map(|pos| pos + 1)
+    } else {
+        None
+    };
+
+    let Some(end) = end else {
+        return format!("---\ntags: [{}]\n---\n{}", tags.join(", "), content);
+    };
+
+    match lines[1..end]
+        .iter()
+        .position(|line| line.starts_with("tags:"))
+        .map(|pos| pos + 1)
+    {
+        None => lines.insert(end, format!("tags: [{}]\n", tags.join(", "))),
+        Some(pos) => {
+            let value = lines[pos]["tags:".len()..].trim().to_owned();
+
+            if value.is_empty() {
+                // A block list: Add the tags after its last item, with the same indentation.
+                let items = lines[pos + 1..end]
+                    .iter()
+                    .take_while(|line| line.trim_start().starts_with('-'))
+                    .count();
+                let indent = lines
+                    .get(pos + 1)
+                    .filter(|_| items > 0)
+                    .map(|line| line[..line.len() - line.trim_start().len()].to_owned())
+                    .unwrap_or_else(|| String::from("  "));
+
+                for (offset, tag) in tags.iter().enumerate() {
+                    lines.insert(pos + 1 + items + offset, format!("{}- {}\n", indent, tag));
+                }
+            } else {
+                // A flow list or a single tag: Write a flow list of all tags.
+                let existing = value
+                    .strip_prefix('[')
+                    .and_then(|value| value.strip_suffix(']'))
+                    .unwrap_or(&value)
+                    .trim();
+                let all = if existing.is_empty() {
+                    tags.join(", ")
+                } else {
+                    format!("{}, {}", existing, tags.join(", "))
+                };
+                lines[pos] = format!("tags: [{}]\n", all);
+            }
+        }
+    }
+
+    lines.concat()
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::path;
+
+    #[test]
+    fn test_edit() {
+        let editor = std::env::var("EDITOR");
+
+        let config = crate::Config::default();
+        let fm = super::FileManager::new(&config, path::PathBuf::from("./tests"));
+        let path = path::Path::new("./tests/common/notes/Books.md");
+
+        if let Ok(_editor) = editor {
+            // if we can unwrap the env variable, then we should be able to create a command
+            fm.create_edit_command(&path.to_path_buf()).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_edit_at_line() {
+        let config = crate::Config {
+            editor: Some(vec![
+                String::from("hx"),
+                String::from("%p:%l"),
+                String::from("+%l"),
+            ]),
+            ..Default::default()
+        };
+        let fm = super::FileManager::new(&config, path::PathBuf::from("./tests"));
+        let path = path::Path::new("./tests/common/notes/Books.md");
+        let full_path = path.canonicalize().unwrap();
+
+        let command = fm
+            .create_edit_command_at(&path.to_path_buf(), Some(12))
+            .unwrap();
+        assert_eq!(
+            command.get_args().collect::<Vec<_>>(),
+            vec![
+                std::ffi::OsString::from(format!("{}:12", full_path.display())),
+                std::ffi::OsString::from("+12"),
+            ]
+        );
+
+        let command = fm.create_edit_command(&path.to_path_buf()).unwrap();
+        assert_eq!(command.get_args().nth(1), Some(std::ffi::OsStr::new("+1")));
+    }
+
+    #[test]
+    fn test_viewing() {
+        let config = crate::Config::default();
+        let fm = super::FileManager::new(&config, path::PathBuf::from("./tests"));
+        let note = crate::data::Note::from_path(
+            path::Path::new("./tests/common/notes/Books.md"),
+            &Default::default(),
+        )
+        .unwrap();
+
+        fm.create_view_command(&note, true).unwrap();
+        fm.create_view_command(&note, false).unwrap();
+    }
+
+    #[test]
+    fn test_reveal() {
+        let config = crate::Config {
+            file_manager: Some(vec![
+                String::from("nautilus"),
+                String::from("--select=%f"),
+                String::from("%p"),
+            ]),
+            ..Default::default()
+        };
+        let fm = super::FileManager::new(&config, path::PathBuf::from("./tests"));
+        let path = path::Path::new("./tests/common/notes/Books.md");
+        let full_path = path.canonicalize().unwrap();
+
+        let command = fm.create_reveal_command(path).unwrap();
+        assert_eq!(command.get_program(), "nautilus");
+        assert_eq!(
+            command.get_args().collect::<Vec<_>>(),
+            vec![
+                std::ffi::OsString::from(format!("--select={}", full_path.display())),
+                full_path.parent().unwrap().as_os_str().to_owned(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_create() {
+        let tmp = testdir::testdir!();
+
+        let fm = super::FileManager::new(&crate::Config::default(), tmp.clone());
+
+        fm.create_note_file("Lie Group").unwrap();
+        fm.create_note_file("Math/Atlas").unwrap();
+
+        let lg_path = tmp.join(String::from("Lie Group.md"));
+        let at_path = tmp
+            .join(String::from("Math"))
+            .join(String::from("Atlas.md"));
+
+        assert!(lg_path.exists());
+        assert!(at_path.exists());
+
+        // check we can create notes
+        let _lg = crate::data::Note::from_path(&lg_path, &Default::default()).unwrap();
+        let _at = crate::data::Note::from_path(&at_path, &Default::default()).unwrap();
+    }
+
+    #[test]
+    fn test_insert_links() {
+        let tmp = testdir::testdir!();
+
+        let fm = super::FileManager::new(&crate::Config::default(), tmp.clone());
+
+        let path = fm.create_note_file("Lie Group Representation").unwrap();
+
+        assert_eq!(path, tmp.join(String::from("Lie Group Representation.md")));
+
+        std::fs::write(
+            &path,
+            "# Lie Group Representation\nA representation of a lie group on a vector space.",
+        )
+        .unwrap();
+
+        fm.insert_links(
+            &path,
+            &[
+                crate::data::LinkSuggestion {
+                    target_id: String::from("lie-group"),
+                    target_name: String::from("Lie Group"),
+                    mention: String::from("Lie Group"),
+                },
+                crate::data::LinkSuggestion {
+                    target_id: String::from("vector-space"),
+                    target_name: String::from("Vector Space"),
+                    mention: String::from("vector space"),
+                },
+            ],
+        )
+        .unwrap();
+
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap(),
+            "# [[Lie Group]] Representation\nA representation of a lie group on a [[Vector Space|vector space]]."
+        );
+    }
+
+    #[test]
+    fn test_add_tags() {
+        let tmp = testdir::testdir!();
+
+        let fm = super::FileManager::new(&crate::Config::default(), tmp.clone());
+
+        let path = fm.create_note_file("Manifold").unwrap();
+        std::fs::write(&path, "# Manifold\nA topological space.").unwrap();
+
+        fm.add_tags(&path, &[String::from("#topology"), String::from("#space")])
+            .unwrap();
+
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap(),
+            "---\ntags: [topology, space]\n---\n# Manifold\nA topological space."
+        );
+
+        let note = crate::data::Note::from_path(&path, &Default::default()).unwrap();
+        assert_eq!(note.tags, vec!["#topology", "#space"]);
+    }
+
+    #[test]
+    fn test_autosave() {
+        let tmp = testdir::testdir!();
+
+        let fm = super::FileManager::new(&crate::Config::default(), tmp.clone()).with_autosave(
+            super::super::Autosave::new(std::time::Duration::from_secs(60)),
+        );
+
+        let path = fm.create_note_file("Manifold").unwrap();
+        std::fs::write(&path, "# Manifold\nA topological space.").unwrap();
+
+        // edits are kept until they are due, later edits building on earlier ones
+        fm.add_tags(&path, &[String::from("#topology")]).unwrap();
+        fm.set_summary(&path, "A space.").unwrap();
+
+        assert!(fm.has_unsaved_edits());
+        assert!(fm.next_save_in().is_some());
+        assert!(!fm.save_due_edits().unwrap());
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap(),
+            "# Manifold\nA topological space."
+        );
+
+        fm.save_all_edits().unwrap();
+
+        assert!(!fm.has_unsaved_edits());
+        assert_eq!(fm.next_save_in(), None);
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap(),
+            "---\ntags: [topology]\nsummary: \"A space.\"\n---\n# Manifold\nA topological space."
+        );
+
+        // edits are written before notes are moved, so they do not restore them at their old path
+        fm.add_tags(&path, &[String::from("#space")]).unwrap();
+
+        let config = crate::Config::default();
+        let tracker = super::super::FileTracker::new(&config, tmp.clone()).unwrap();
+        let builder = super::super::HtmlBuilder::new(&config, tmp.clone());
+        let index = std::rc::Rc::new(std::cell::RefCell::new(
+            crate::data::NoteIndex::new(tracker, builder, Default::default()).0,
+        ));
+        fm.rename_note_file(index, "manifold", String::from("Manifolds"))
+            .unwrap();
+
+        assert!(!fm.has_unsaved_edits());
+        assert!(!path.exists());
+        assert!(std::fs::read_to_string(tmp.join("Manifolds.md"))
+            .unwrap()
+            .contains("tags: [topology, space]"));
+    }
+
+    #[test]
+    fn test_insert_snippet() {
+        let tmp = testdir::testdir!();
+
+        let fm = super::FileManager::new(&crate::Config::default(), tmp.clone());
+
+        let path = fm.create_note_file("Manifold").unwrap();
+        std::fs::write(&path, "# Manifold\n## Examples\nSpheres.\n## Charts\n").unwrap();
+        let note = crate::data::Note::from_path(&path, &Default::default()).unwrap();
+
+        let snippet = crate::data::Snippet {
+            name: String::from("example"),
+            text: String::from("- Example of [[{{name}}]]"),
+        };
+        let headings = crate::data::headings(&std::fs::read_to_string(&path).unwrap());
+
+        fm.insert_snippet(&note, &snippet, Some(&headings[1]))
+            .unwrap();
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap(),
+            "# Manifold\n## Examples\nSpheres.\n\n- Example of [[Manifold]]\n\n## Charts\n"
+        );
+
+        // headings that were removed are reported
+        std::fs::write(&path, "# Manifold\n").unwrap();
+        assert!(fm
+            .insert_snippet(&note, &snippet, Some(&headings[1]))
+            .is_err());
+    }
+
+    #[test]
+    fn test_set_summary() {
+        let tmp = testdir::testdir!();
+
+        let fm = super::FileManager::new(&crate::Config::default(), tmp.clone());
+
+        let path = fm.create_note_file("Manifold").unwrap();
+        std::fs::write(&path, "# Manifold\nA topological space.").unwrap();
+
+        // without frontmatter, one is created
+        fm.set_summary(&path, "A space that is \"locally\" Euclidean.")
+            .unwrap();
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap(),
+            "---\nsummary: \"A space that is \\\"locally\\\" Euclidean.\"\n---\n# Manifold\nA topological space."
+        );
+
+        // an existing summary is replaced
+        fm.set_summary(&path, "A topological space.").unwrap();
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap(),
+            "---\nsummary: \"A topological space.\"\n---\n# Manifold\nA topological space."
+        );
+        assert!(crate::data::Note::from_path(&path, &Default::default()).is_ok());
+    }
+
+    #[test]
+    fn test_add_frontmatter_tags() {
+        let tags = [String::from("#topology"), String::from("geo/diff")];
+
+        // without a tags field
+        assert_eq!(
+            super::add_frontmatter_tags("---\ntitle: Chart\n---\n# Chart", &tags),
+            "---\ntitle: Chart\ntags: [topology, geo/diff]\n---\n# Chart"
+        );
+        // with a flow list
+        assert_eq!(
+            super::add_frontmatter_tags("---\ntags: [math]\n---\n# Chart", &tags),
+            "---\ntags: [math, topology, geo/diff]\n---\n# Chart"
+        );
+        // with a single tag
+        assert_eq!(
+            super::add_frontmatter_tags("---\ntags: math\n---\n# Chart", &tags),
+            "---\ntags: [math, topology, geo/diff]\n---\n# Chart"
+        );
+        // with a block list
+        let content = super::add_frontmatter_tags(
+            "---\ntags:\n    - math\ntitle: Chart\n---\n# Chart",
+            &tags,
+        );
+        assert_eq!(
+            content,
+            "---\ntags:\n    - math\n    - topology\n    - geo/diff\ntitle: Chart\n---\n# Chart"
+        );
+
+        let note = crate::data::Note::from_content(
+            path::Path::new("Chart.md"),
+            &content,
+            &Default::default(),
+        )
+        .unwrap();
+        assert_eq!(note.tags, vec!["#math", "#topology", "#geo/diff"]);
+    }
+
+    #[test]
+    fn test_update_modified() {
+        let tmp = testdir::testdir!();
+
+        let fm = super::FileManager::new(
+            &crate::Config {
+                timezone: Some(String::from("Asia/Tokyo")),
+                timestamp_format: String::from("%H:%M%:z"),
+                update_modified: true,
+                ..Default::default()
+            },
+            tmp.clone(),
+        );
+
+        let timestamp = fm.timestamp().unwrap();
+        assert!(timestamp.ends_with("+09:00"));
+
+        let path = tmp.join("Lie Group.md");
+        std::fs::write(
+            &path,
+            "---\ntitle: Lie Groups\n---\nA lie group is a manifold.",
+        )
+        .unwrap();
+
+        fm.insert_links(
+            &path,
+            &[crate::data::LinkSuggestion {
+                target_id: String::from("manifold"),
+                target_name: String::from("Manifold"),
+                mention: String::from("manifold"),
+            }],
+        )
+        .unwrap();
+
+        let content = std::fs::read_to_string(&path).unwrap();
+        assert!(content.starts_with("---\ntitle: Lie Groups\nmodified: "));
+        assert!(content.ends_with("+09:00\n---\nA lie group is a [[Manifold|manifold]]."));
+
+        // unknown timezones are reported
+        let fm = super::FileManager::new(
+            &crate::Config {
+                timezone: Some(String::from("Mars/Olympus Mons")),
+                ..Default::default()
+            },
+            tmp.clone(),
+        );
+        assert!(fm.timestamp().is_err());
+    }
+
+    #[test]
+    fn test_update_created() {
+        let tmp = testdir::testdir!();
+
+        let fm = super::FileManager::new(
+            &crate::Config {
+                update_created: true,
+                ..Default::default()
+            },
+            tmp.clone(),
+        );
+
+        // new notes start with a creation date
+        let path = fm.create_note_file("Atlas").unwrap();
+        let content = std::fs::read_to_string(&path).unwrap();
+        assert!(content.starts_with("---\ncreated: "));
+        assert!(content.ends_with("\n---\n# Atlas"));
+        assert!(super::frontmatter_field(&content, "modified").is_none());
+
+        // existing creation dates are kept
+        let path = tmp.join("Chart.md");
+        std::fs::write(
+            &path,
+            "---\ncreated: yesterday\n---\nA chart on a manifold.",
+        )
+        .unwrap();
+        fm.insert_links(
+            &path,
+            &[crate::data::LinkSuggestion {
+                target_id: String::from("manifold"),
+                target_name: String::from("Manifold"),
+                mention: String::from("manifold"),
+            }],
+        )
+        .unwrap();
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap(),
+            "---\ncreated: yesterday\n---\nA chart on a [[Manifold|manifold]]."
+        );
+    }
+
+    #[test]
+    fn test_frontmatter_field() {
+        assert_eq!(super::frontmatter_field("# Note", "title"), None);
+        assert_eq!(
+            super::frontmatter_field("---\ntitle: Note\ncreated: today\n---\n", "created"),
+            Some("today")
+        );
+        assert_eq!(
+            super::frontmatter_field("---\ntitle: Note\n---\ncreated: today\n", "created"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_set_frontmatter_field() {
+        assert_eq!(
+            super::set_frontmatter_field("# Note", "modified", "today"),
+            None
+        );
+        assert_eq!(
+            super::set_frontmatter_field("---\ntitle: Note\n---\n# Note", "modified", "today"),
+            Some(String::from(
+                "---\ntitle: Note\nmodified: today\n---\n# Note"
+            ))
+        );
+        assert_eq!(
+            super::set_frontmatter_field(
+                "---\nmodified: yesterday\ntitle: Note\n---\n# Note\n---\n",
+                "modified",
+                "today"
+            ),
+            Some(String::from(
+                "---\nmodified: today\ntitle: Note\n---\n# Note\n---\n"
+            ))
+        );
+    }
+
+    #[test]
+    fn test_create_other_suffix() {
+        let tmp = testdir::testdir!();
+
+        let fm = super::FileManager::new(
+            &crate::Config {
+                default_extension: String::from("txt"),
+                file_types: vec![String::from("txt")],
+                ..Default::default()
+            },
+            tmp.clone(),
+        );
+
+        fm.create_note_file("Lie Group").unwrap();
+        fm.create_note_file("Math/Atlas").unwrap();
+
+        let lg_path = tmp.join(String::from("Lie Group.txt"));
+        let at_path = tmp
+            .join(String::from("Math"))
+            .join(String::from("Atlas.txt"));
+
+        assert!(lg_path.exists());
+        assert!(at_path.exists());
+
+        // check we can create notes
+        let _lg = crate::data::Note::from_path(&lg_path, &Default::default()).unwrap();
+        let _at = crate::data::Note::from_path(&at_path, &Default::default()).unwrap();
+    }
+
+    #[test]
+    fn test_create_with_text() {
+        let tmp = testdir::testdir!();
+
+        let fm = super::FileManager::new(
+            &crate::Config {
+                update_created: true,
+                ..Default::default()
+            },
+            tmp.clone(),
+        );
+
+        // the creation date is added to the frontmatter of the text
+        let path = fm
+            .create_note_with_text("Reviews/Week 3", "---\ntags: [review]\n---\n# Week 3\n")
+            .unwrap()
+            .unwrap();
+        assert_eq!(path, tmp.join("Reviews").join("Week 3.md"));
+        let content = std::fs::read_to_string(&path).unwrap();
+        assert!(content.starts_with("---\ntags: [review]\ncreated: "));
+        assert!(content.ends_with("\n---\n# Week 3\n"));
+
+        // existing notes are left alone
+        std::fs::write(&path, "Already written.").unwrap();
+        assert_eq!(
+            fm.create_note_with_text("Reviews/Week 3", "# Week 3\n")
+                .unwrap(),
+            None
+        );
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "Already written.");
+    }
+
+    #[test]
+    fn test_delete() {
+        let tmp = testdir::testdir!();
+
+        let config = crate::Config::default();
+        let fm = super::FileManager::new(&config, tmp.clone());
+
+        fm.create_note_file("Lie Group").unwrap();
+        fm.create_note_file("Math/Atlas").unwrap();
+
+        let lg_path = tmp.join(String::from("Lie Group.md"));
+        let at_path = tmp
+            .join(String::from("Math"))
+            .join(String::from("Atlas.md"));
+
+        assert!(lg_path.exists());
+        assert!(at_path.exists());
+
+        let tracker = crate::io::FileTracker::new(&config, tmp.clone()).unwrap();
+        let builder = crate::io::HtmlBuilder::new(&config, tmp.clone());
+        let index = crate::data::NoteIndex::new(tracker, builder, Default::default()).0;
+        let index_con = std::rc::Rc::new(std::cell::RefCell::new(index));
+
+        fm.delete_note_file(index_con.clone(), "lie-group").unwrap();
+        assert!(!lg_path.exists());
+        assert!(at_path.exists());
+
+        fm.delete_note_file(index_con.clone(), "atlas").unwrap();
+        assert!(!lg_path.exists());
+        assert!(!at_path.exists());
+    }
+
+    #[test]
+    fn test_trash() {
+        let tmp = testdir::testdir!();
+
+        let config = crate::Config::default();
+        let fm = super::FileManager::new(&config, tmp.clone());
+
+        fm.create_note_file("Lie Group").unwrap();
+        fm.create_note_file("Math/Atlas").unwrap();
+
+        let lg_path = tmp.join("Lie Group.md");
+        let at_path = tmp.join("Math").join("Atlas.md");
+
+        let tracker = crate::io::FileTracker::new(&config, tmp.clone()).unwrap();
+        let builder = crate::io::HtmlBuilder::new(&config, tmp.clone());
+        let index = crate::data::NoteIndex::new(tracker, builder, Default::default()).0;
+        let index_con = std::rc::Rc::new(std::cell::RefCell::new(index));
+
+        assert!(fm.trash_entries().unwrap().is_empty());
+
+        fm.delete_note_file(index_con.clone(), "lie-group").unwrap();
+        fm.delete_note_file(index_con.clone(), "atlas").unwrap();
+
+        let entries = fm.trash_entries().unwrap();
+        assert_eq!(entries.len(), 2);
+        assert!(entries.iter().all(|entry| entry.path.exists()));
+
+        let atlas = entries
+            .iter()
+            .find(|entry| entry.original_path == path::Path::new("Math/Atlas.md"))
+            .unwrap();
+        let lie_group = entries
+            .iter()
+            .find(|entry| entry.original_path == path::Path::new("Lie Group.md"))
+            .unwrap();
+
+        // restore a note
+        fm.restore_trash_entry(atlas).unwrap();
+        assert!(at_path.exists());
+        assert!(!atlas.path.exists());
+
+        // restoring fails if the location has been taken in the meantime
+        fm.create_note_file("Lie Group").unwrap();
+        assert!(fm.restore_trash_entry(lie_group).is_err());
+
+        // purge a note
+        fm.purge_trash_entry(lie_group).unwrap();
+        assert!(!lie_group.path.exists());
+        assert!(lg_path.exists());
+
+        assert!(fm.trash_entries().unwrap().is_empty());
+        assert_eq!(std::fs::read_dir(tmp.join(".trash")).unwrap().count(), 0);
+    }
+
+    #[test]
+    fn test_read_only() {
+        let tmp = testdir::testdir!();
+
+        let lock = crate::io::VaultLock::acquire(&tmp, false).unwrap();
+        let other = crate::io::VaultLock::acquire(&tmp, false).unwrap();
+
+        let fm = super::FileManager::new(&crate::Config::default(), tmp.clone()).with_lock(other);
+        assert!(matches!(
+            fm.create_note_file("Atlas"),
+            Err(crate::error::RucolaError::ReadOnly(_))
+        ));
+        assert!(!tmp.join("Atlas.md").exists());
+
+        let fm = fm.with_lock(lock);
+        fm.create_note_file("Atlas").unwrap();
+        assert!(tmp.join("Atlas.md").exists());
+    }
+
+    #[test]
+    fn test_purge_old_trash() {
+        let tmp = testdir::testdir!();
+
+        let fm = super::FileManager::new(&crate::Config::default(), tmp.clone());
+
+        // fake a note that was deleted 40 days ago, and one deleted just now
+        let old = chrono::Local::now() - chrono::Duration::days(40);
+        let old_path = tmp
+            .join(".trash")
+            .join(old.timestamp().to_string())
+            .join("Old.md");
+        let new_path = tmp
+            .join(".trash")
+            .join(chrono::Local::now().timestamp().to_string())
+            .join("New.md");
+
+        for path in [&old_path, &new_path] {
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, "# Note").unwrap();
+        }
+
+        assert_eq!(fm.trash_entries().unwrap().len(), 2);
+
+        fm.purge_old_trash().unwrap();
+
+        assert!(!old_path.exists());
+        assert!(new_path.exists());
+        assert_eq!(fm.trash_entries().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_rename() {
+        let tmp = testdir::testdir!();
+
+        let config = crate::Config::default();
+        let fm = super::FileManager::new(&config, tmp.clone());
+
+        let lg_path = tmp.join(String::from("Lie Group.md"));
+        let at_path = tmp
+            .join(String::from("Math"))
+            .join(String::from("Atlas.md"));
+        // not in subfolder
+        let lg_path_after = tmp.join(String::from("Lie Soup.md"));
+        // in subfolder
+        let at_path_after = tmp
+            .join(String::from("Math"))
+            .join(String::from("Atlantis.md"));
+
+        fm.create_note_file("Lie Group").unwrap();
+        fm.create_note_file("Math/Atlas").unwrap();
+
+        let tracker = crate::io::FileTracker::new(&config, tmp.clone()).unwrap();
+        let builder = crate::io::HtmlBuilder::new(&config, tmp.clone());
+        let index = crate::data::NoteIndex::new(tracker, builder, Default::default()).0;
+
+        assert!(index.get("atlas").is_some());
+        assert!(index.get("lie-group").is_some());
+
+        let index_con = std::rc::Rc::new(std::cell::RefCell::new(index));
+
+        assert!(lg_path.exists());
+        assert!(at_path.exists());
+
+        fm.rename_note_file(index_con.clone(), "lie-group", String::from("Lie Soup"))
+            .unwrap();
+        fm.rename_note_file(index_con.clone(), "atlas", String::from("Atlantis"))
+            .unwrap();
+
+        assert!(lg_path_after.exists());
+        assert!(at_path_after.exists());
+    }
+
+    #[test]
+    fn test_rename_updates_links() {
+        let tmp = testdir::testdir!();
+
+        let config = crate::Config::default();
+        let fm = super::FileManager::new(&config, tmp.clone());
+
+        let at_path = tmp.join(String::from("Atlas.md"));
+        let ma_path = tmp.join(String::from("Manifold.md"));
+        let to_path = tmp.join(String::from("Topology.md"));
+
+        fm.create_note_file("Atlas").unwrap();
+        fm.create_note_file("Manifold").unwrap();
+        fm.create_note_file("Topology").unwrap();
+
+        std::fs::copy(
+            path::Path::new("./tests/common/notes/math/Atlas.md"),
+            &at_path,
+        )
+        .unwrap();
+        std::fs::copy(
+            path::Path::new("./tests/common/notes/math/Manifold.md"),
+            &ma_path,
+        )
+        .unwrap();
+        std::fs::copy(
+            path::Path::new("./tests/common/notes/math/Topology.md"),
+            &to_path,
+        )
+        .unwrap();
+
+        let tracker = crate::io::FileTracker::new(&config, tmp.clone()).unwrap();
+        let builder = crate::io::HtmlBuilder::new(&config, tmp.clone());
+        let index = crate::data::NoteIndex::new(tracker, builder, Default::default()).0;
+
+        let index_con = std::rc::Rc::new(std::cell::RefCell::new(index));
+
+        assert!(at_path.exists());
+        assert!(ma_path.exists());
+        assert!(to_path.exists());
+
+        let ma_content = std::fs::read_to_string(&ma_path).unwrap();
+
+        assert!(ma_content.contains("[[Atlas]]"));
+        assert!(!ma_content.contains("[[Atlantis]]"));
+        assert!(ma_content.contains("[[Topology|topological space]]"));
+        assert!(!ma_content.contains("[[Anthology|topological space]]"));
+
+        fm.rename_note_file(index_con.clone(), "topology", String::from("Anthology"))
+            .unwrap();
+
+        // since we are not updating the index in between, topology must be done before atlas
+        fm.rename_note_file(index_con.clone(), "atlas", String::from("Atlantis"))
+            .unwrap();
+
+        let ma_content = std::fs::read_to_string(&ma_path).unwrap();
+        assert!(!ma_content.contains("[[Atlas]]"));
+        assert!(ma_content.contains("[[Atlantis]]"));
+        assert!(!ma_content.contains("[[Topology|topological space]]"));
+        assert!(ma_content.contains("[[Anthology|topological space]]"));
+    }
+
+    #[test]
+    fn test_move() {
+        let tmp = testdir::testdir!();
+
+        let config = crate::Config::default();
+        let fm = super::FileManager::new(&config, tmp.clone());
+
+        let lg_path = tmp.join(String::from("Lie Group.md"));
+        let at_path = tmp
+            .join(String::from("Math"))
+            .join(String::from("Atlas.md"));
+        // without renaming
+        let lg_path_after = tmp
+            .join(String::from("Topology"))
+            .join(String::from("Lie Group.md"));
+        // with renaming -> should error
+        let at_path_after = tmp
+            .join(String::from("Topology"))
+            .join(String::from("Atlantis"))
+            .join(String::from("Atlas.md"));
+
+        fm.create_note_file("Lie Group").unwrap();
+        fm.create_note_file("Math/Atlas").unwrap();
+
+        let tracker = crate::io::FileTracker::new(&config, tmp.clone()).unwrap();
+        let builder = crate::io::HtmlBuilder::new(&config, tmp.clone());
+        let index = crate::data::NoteIndex::new(tracker, builder, Default::default()).0;
+
+        let index_con = std::rc::Rc::new(std::cell::RefCell::new(index));
+
+        assert!(lg_path.exists());
+        assert!(at_path.exists());
+
+        fm.move_note_file(index_con.clone(), "lie-group", String::from("Topology/"))
+            .unwrap();
+        fm.move_note_file(
+            index_con.clone(),
+            "atlas",
+            String::from("Topology/Atlantis"),
+        )
+        .unwrap();
+
+        assert!(lg_path_after.exists());
+        assert!(!lg_path.exists());
+        assert!(at_path_after.exists());
+        assert!(!at_path.exists());
+    }
+
+    #[test]
+    fn test_file_endings() {
+        let md_ending_tar = path::PathBuf::from("./tests/common/test.md");
+        let txt_ending_tar = path::PathBuf::from("./tests/common/test.txt");
+
+        let config = crate::Config::default();
+        let fm = super::FileManager::new(&config, path::PathBuf::from("./tests"));
+
+        let mut no_ending = path::PathBuf::from("./tests/common/test");
+        let mut md_ending = path::PathBuf::from("./tests/common/test.md");
+        let mut txt_ending = path::PathBuf::from("./tests/common/test.txt");
+
+        fm.ensure_file_extension(&mut no_ending);
+        fm.ensure_file_extension(&mut md_ending);
+        fm.ensure_file_extension(&mut txt_ending);
+
+        assert_eq!(no_ending, md_ending_tar);
+        assert_eq!(md_ending, md_ending_tar);
+        assert_eq!(txt_ending, txt_ending_tar);
+    }
+}
@@ -14,9 +14,12 @@ pub struct FileTracker {
     /// File types to consider notes
     file_types: ignore::types::Types,
     /// Watcher that checks for file changes in the vault directory and needs to be kept alive with this index.
-    /// Can be unused because it is just here for RAII.
-    #[allow(unused)]
-    watcher: notify::RecommendedWatcher,
+    /// Missing if it could not be created, in which case creating it is retried when watching is initialized.
+    watcher: Option<notify::RecommendedWatcher>,
+    /// Wether the watcher is watching the vault already.
+    watching: bool,
+    /// Sending end of the file change channel, handed to the watcher once it is created.
+    file_change_sender: mpsc::Sender<Result<notify::Event, notify::Error>>,
     /// Channel from which file change events in the vault directory are deposited by the watcher and can be requested.
     file_change_channel: mpsc::Receiver<Result<notify::Event, notify::Error>>,
 }
@@ -26,7 +29,7 @@ impl Default for FileTracker {
             &crate::Config::default(),
             std::env::current_dir().expect("Current directory to exist and be accessible."),
         )
-        .expect("Watcher to be created and pre-defined file types to work.")
+        .expect("Pre-defined file types to work.")
     }
 }
 
@@ -43,30 +46,57 @@ impl FileTracker {
         let (sender, receiver) = mpsc::channel();
 
         // Create watcher so we can store it in the file, delaying its drop (which stops its function) until the end of the lifetime of this index.
-        let watcher = notify::recommended_watcher(move |res| {
-            // ignore errors
-            let _ = sender.send(res);
-        })?;
+        // Failures are reported once watching is initialized.
+        let watcher = Self::create_watcher(&sender).ok();
 
         Ok(Self {
             vault_path,
             file_types: types_builder.build()?,
             watcher,
+            watching: false,
+            file_change_sender: sender,
             file_change_channel: receiver,
         })
     }
 
     /// Start watching the vault path.
     /// This action is delayed until now so the watcher is not active while the initial indexing creates a ton of HTML files, which would trigger a ton of file events and a significant hangup.
+    /// If watching fails, the tracker keeps working without file events and this function can be called again later.
     pub fn initialize_watching(&mut self) -> Result<(), notify::Error> {
-        self.watcher.watch(
+        if self.watching {
+            return Ok(());
+        }
+
+        let watcher = match &mut self.watcher {
+            Some(watcher) => watcher,
+            None => self
+                .watcher
+                .insert(Self::create_watcher(&self.file_change_sender)?),
+        };
+
+        watcher.watch(
             self.vault_path
                 .canonicalize()
                 .as_ref()
                 .unwrap_or(&self.vault_path)
                 .as_path(),
             notify::RecursiveMode::Recursive,
-        )
+        )?;
+
+        self.watching = true;
+        Ok(())
+    }
+
+    /// Creates a watcher that deposits its file events in the given channel.
+    fn create_watcher(
+        sender: &mpsc::Sender<Result<notify::Event, notify::Error>>,
+    ) -> Result<notify::RecommendedWatcher, notify::Error> {
+        let sender = sender.clone();
+        notify::recommended_watcher(move |res| {
+            // ignore errors
+            let _ = sender.send(res);
+            super::wake();
+        })
     }
 
     /// Returns a file walker that iterates over all notes to index.
@@ -87,6 +117,17 @@ impl FileTracker {
         })
     }
 
+    /// Wether a file at the given path, which need not exist, would be considered a note.
+    /// Checks the file type and skips hidden folders, but does not consult ignore files.
+    pub fn is_note_file(&self, path: &path::Path) -> bool {
+        let relative = path.strip_prefix(&self.vault_path).unwrap_or(path);
+
+        !relative.components().any(|component| match component {
+            path::Component::Normal(name) => name.to_string_lossy().starts_with('.'),
+            _ => false,
+        }) && self.file_types.matched(path, false).is_whitelist()
+    }
+
     /// Returns an iterator over all events found by this tracker since the last check.
     pub fn try_events_iter(&self) -> TryIter<'_, Result<notify::Event, notify::Error>> {
         self.file_change_channel.try_iter()
@@ -191,7 +232,7 @@ mod tests {
     //     let fm = crate::io::FileManager::new(&config, tmp.clone());
     //     let tracker = crate::io::FileTracker::new(&config, tmp.clone()).unwrap();
     //     let builder = crate::io::HtmlBuilder::new(&config, tmp.clone());
-    //     let mut index = crate::data::NoteIndex::new(tracker, builder).0;
+    //     let mut index = crate::data::NoteIndex::new(tracker, builder, Default::default()).0;
 
     //     assert!(index.get("atlas").is_none());
     //     assert!(index.get("lie-group").is_none());
@@ -228,7 +269,7 @@ mod tests {
 
         let tracker = crate::io::FileTracker::new(&config, tmp.clone()).unwrap();
         let builder = crate::io::HtmlBuilder::new(&config, tmp.clone());
-        let index = crate::data::NoteIndex::new(tracker, builder).0;
+        let index = crate::data::NoteIndex::new(tracker, builder, Default::default()).0;
         let index_con = std::rc::Rc::new(std::cell::RefCell::new(index));
 
         assert!(index_con.borrow().get("atlas").is_some());
@@ -280,7 +321,7 @@ mod tests {
 
     //     let tracker = crate::io::FileTracker::new(&config, tmp.clone()).unwrap();
     //     let builder = crate::io::HtmlBuilder::new(&config, tmp.clone());
-    //     let index = crate::data::NoteIndex::new(tracker, builder).0;
+    //     let index = crate::data::NoteIndex::new(tracker, builder, Default::default()).0;
     //     let mut index_con = std::rc::Rc::new(std::cell::RefCell::new(index));
 
     //     assert!(index_con.borrow().get("atlas").is_some());
@@ -332,7 +373,7 @@ mod tests {
 
     //     let tracker = crate::io::FileTracker::new(&config, tmp.clone()).unwrap();
     //     let builder = crate::io::HtmlBuilder::new(&config, tmp.clone());
-    //     let index = crate::data::NoteIndex::new(tracker, builder).0;
+    //     let index = crate::data::NoteIndex::new(tracker, builder, Default::default()).0;
     //     let index_con = std::rc::Rc::new(std::cell::RefCell::new(index));
 
     //     assert!(index_con.borrow().get("atlas").is_some());
@@ -0,0 +1,359 @@
+use std::{path, rc};
+
+use crate::error;
+
+/// A commit in the history of the repository the vault is stored in.
+#[derive(Debug, Clone)]
+pub struct CommitInfo {
+    /// The id of the commit.
+    pub id: git2::Oid,
+    /// The first line of the commit message.
+    pub summary: String,
+    /// The time the commit was made.
+    pub time: chrono::DateTime<chrono::Local>,
+}
+
+/// Manages interaction with the `git2` library.
+#[derive(Clone)]
+pub struct GitManager {
+    /// The git repository the vault is stored in.
+    git_repo: rc::Rc<git2::Repository>,
+    /// Path to the vault, which may be a subfolder of the repository.
+    vault_path: path::PathBuf,
+}
+
+impl GitManager {
+    /// Checks if the given path is contained in a git repository, and if yes, creates an object managing that repository.
+    pub fn new(vault_path: path::PathBuf) -> Option<Self> {
+        git2::Repository::discover(&vault_path)
+            .map(|git_repo| Self {
+                git_repo: rc::Rc::new(git_repo),
+                vault_path,
+            })
+            .ok()
+    }
+
+    /// Lists the latest commits reachable from the current HEAD, most recent first.
+    pub fn commits(&self, limit: usize) -> error::Result<Vec<CommitInfo>> {
+        let mut revwalk = self.git_repo.revwalk()?;
+        revwalk.push_head()?;
+        revwalk.set_sorting(git2::Sort::TIME)?;
+
+        revwalk
+            .take(limit)
+            .map(|id| Ok(Self::commit_info(&self.git_repo.find_commit(id?)?)))
+            .collect()
+    }
+
+    /// Lists all commits reachable from the current HEAD that changed the file at the given path, most recent first.
+    pub fn file_history(&self, path: &path::Path) -> error::Result<Vec<CommitInfo>> {
+        let repo_path = self.repo_path(path)?;
+
+        let mut revwalk = self.git_repo.revwalk()?;
+        revwalk.push_head()?;
+        revwalk.set_sorting(git2::Sort::TIME)?;
+
+        let mut history = Vec::new();
+
+        for id in revwalk {
+            let commit = self.git_repo.find_commit(id?)?;
+
+            let blob_id = |commit: &git2::Commit| {
+                commit
+                    .tree()
+                    .and_then(|tree| tree.get_path(&repo_path))
+                    .map(|entry| entry.id())
+                    .ok()
+            };
+
+            let current = blob_id(&commit);
+            let parent = commit.parent(0).ok().and_then(|parent| blob_id(&parent));
+
+            // Only list commits in which the file exists and differs from the first parent.
+            if current.is_some() && current != parent {
+                history.push(Self::commit_info(&commit));
+            }
+        }
+
+        Ok(history)
+    }
+
+    /// Reads the content of the file at the given path as it was at the given commit.
+    pub fn file_at(&self, commit: git2::Oid, path: &path::Path) -> error::Result<String> {
+        let entry = self
+            .git_repo
+            .find_commit(commit)?
+            .tree()?
+            .get_path(&self.repo_path(path)?)?;
+        let blob = self.git_repo.find_blob(entry.id())?;
+
+        Ok(String::from_utf8_lossy(blob.content()).into_owned())
+    }
+
+    /// Converts a commit to its summarizing information.
+    fn commit_info(commit: &git2::Commit) -> CommitInfo {
+        CommitInfo {
+            id: commit.id(),
+            summary: commit.summary().unwrap_or_default().to_owned(),
+            time: chrono::DateTime::from_timestamp(commit.time().seconds(), 0)
+                .unwrap_or_default()
+                .with_timezone(&chrono::Local),
+        }
+    }
+
+    /// Converts a path in the file system to the corresponding path relative to the root of the repository.
+    /// Fails if the path is not located within the working directory of the repository.
+    fn repo_path(&self, path: &path::Path) -> error::Result<path::PathBuf> {
+        let workdir = self
+            .git_repo
+            .workdir()
+            .and_then(|workdir| workdir.canonicalize().ok())
+            .ok_or_else(|| git2::Error::from_str("Repository has no working directory."))?;
+
+        path.canonicalize()?
+            .strip_prefix(&workdir)
+            .map(|relative| relative.to_path_buf())
+            .map_err(|_| {
+                git2::Error::from_str(&format!(
+                    "{} is not located in the repository at {}.",
+                    path.display(),
+                    workdir.display()
+                ))
+                .into()
+            })
+    }
+
+    /// Reads all files within the vault as they were at the given commit, directly from the repository.
+    /// Returns their paths, as they would be located in the vault, and their contents.
+    /// Files that are not valid UTF-8 are skipped.
+    pub fn files_at(&self, commit: git2::Oid) -> error::Result<Vec<(path::PathBuf, String)>> {
+        let tree = self.git_repo.find_commit(commit)?.tree()?;
+
+        // Find out where the vault is located within the repository.
+        let vault_prefix = self.repo_path(&self.vault_path)?;
+
+        let mut files = Vec::new();
+
+        tree.walk(git2::TreeWalkMode::PreOrder, |root, entry| {
+            if entry.kind() == Some(git2::ObjectType::Blob) {
+                let repo_path = path::Path::new(root).join(entry.name().unwrap_or_default());
+                if let Ok(vault_relative) = repo_path.strip_prefix(&vault_prefix) {
+                    if let Ok(blob) = self.git_repo.find_blob(entry.id()) {
+                        if let Ok(content) = std::str::from_utf8(blob.content()) {
+                            files.push((self.vault_path.join(vault_relative), content.to_owned()));
+                        }
+                    }
+                }
+            }
+            git2::TreeWalkResult::Ok
+        })?;
+
+        Ok(files)
+    }
+
+    /// Calculates how many commits the current branch is ahead/behind compared to its origin.
+    /// TODO: remove unwraps
+    pub fn calculate_ahead_behind(&self) -> (usize, usize) {
+        let head = self.git_repo.head().unwrap();
+        let head_id = head.target().unwrap();
+
+        let branch = self
+            .git_repo
+            .find_branch(head.shorthand().unwrap(), git2::BranchType::Local)
+            .unwrap();
+
+        let upstream = branch.upstream().unwrap();
+
+        let upstream_id = upstream.get().target().unwrap();
+
+        self.git_repo
+            .graph_ahead_behind(head_id, upstream_id)
+            .unwrap()
+    }
+
+    /// Checks if there are any untracked or uncommited changes in the repository at the current time.
+    pub fn changes(&self) -> (bool, bool) {
+        let mut status_options = git2::StatusOptions::new();
+
+        let statuses = self.git_repo.statuses(Some(
+            status_options
+                .include_untracked(true)
+                .recurse_untracked_dirs(true)
+                .include_ignored(false)
+                .exclude_submodules(true),
+        ));
+
+        if statuses.is_err() {
+            return (false, false);
+        }
+
+        let statuses = statuses.unwrap();
+
+        (
+            statuses.iter().any(|entry| {
+                let status = entry.status();
+                status.is_wt_modified()
+                    || status.is_wt_deleted()
+                    || status.is_wt_new()
+                    || status.is_wt_typechange()
+                    || status.is_wt_renamed()
+            }),
+            statuses.iter().any(|entry| {
+                let status = entry.status();
+                status.is_index_modified()
+                    || status.is_index_deleted()
+                    || status.is_index_new()
+                    || status.is_index_typechange()
+                    || status.is_index_renamed()
+            }),
+        )
+    }
+}
+
+/// Compares two versions of a text line by line.
+/// Returns the lines of both versions side by side, aligned such that unchanged lines are on the same row.
+/// Lines only present in one of the versions are paired with `None` on the other side.
+pub fn side_by_side_diff(
+    old: &str,
+    new: &str,
+) -> error::Result<Vec<(Option<String>, Option<String>)>> {
+    let mut options = git2::DiffOptions::new();
+    // Include the entire text as context.
+    options.context_lines(u32::MAX);
+
+    let patch = git2::Patch::from_buffers(
+        old.as_bytes(),
+        None,
+        new.as_bytes(),
+        None,
+        Some(&mut options),
+    )?;
+
+    // Without any changes, there are no hunks to read the lines from.
+    if patch.num_hunks() == 0 {
+        return Ok(new
+            .lines()
+            .map(|line| (Some(line.to_owned()), Some(line.to_owned())))
+            .collect());
+    }
+
+    let mut rows = Vec::new();
+
+    for hunk in 0..patch.num_hunks() {
+        for line in 0..patch.num_lines_in_hunk(hunk)? {
+            let line = patch.line_in_hunk(hunk, line)?;
+            let content = String::from_utf8_lossy(line.content())
+                .trim_end_matches(['\n', '\r'])
+                .to_owned();
+            match line.origin() {
+                ' ' => rows.push((Some(content.clone()), Some(content))),
+                '-' => rows.push((Some(content), None)),
+                '+' => rows.push((None, Some(content))),
+                // Markers such as missing newlines at the end of file.
+                _ => {}
+            }
+        }
+    }
+
+    Ok(rows)
+}
+
+#[cfg(test)]
+mod tests {
+    /// Commits all files in the given repository with the given message.
+    fn commit_all(repo: &git2::Repository, message: &str) -> git2::Oid {
+        let mut index = repo.index().unwrap();
+        index
+            .add_all(["*"], git2::IndexAddOption::DEFAULT, None)
+            .unwrap();
+        index.update_all(["*"], None).unwrap();
+        index.write().unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let signature = git2::Signature::now("rucola", "rucola@example.com").unwrap();
+        let parents = repo
+            .head()
+            .ok()
+            .and_then(|head| head.peel_to_commit().ok())
+            .into_iter()
+            .collect::<Vec<_>>();
+        repo.commit(
+            Some("HEAD"),
+            &signature,
+            &signature,
+            message,
+            &tree,
+            &parents.iter().collect::<Vec<_>>(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_index_at_commit() {
+        let tmp = testdir::testdir!();
+        let repo = git2::Repository::init(&tmp).unwrap();
+
+        let vault = tmp.join("notes");
+        std::fs::create_dir_all(&vault).unwrap();
+        std::fs::write(tmp.join("README.md"), "# Outside the vault").unwrap();
+        std::fs::write(vault.join("Atlas.md"), "An atlas of [[Chart]]s.").unwrap();
+        std::fs::write(vault.join("Chart.md"), "# Chart").unwrap();
+        let first = commit_all(&repo, "Add atlas and chart");
+
+        std::fs::write(vault.join("Manifold.md"), "Has an [[Atlas]].").unwrap();
+        std::fs::remove_file(vault.join("Chart.md")).unwrap();
+        commit_all(&repo, "Replace chart with manifold");
+
+        let git_manager = super::GitManager::new(vault.clone()).unwrap();
+
+        let commits = git_manager.commits(10).unwrap();
+        assert_eq!(commits.len(), 2);
+        assert_eq!(commits[1].id, first);
+        assert_eq!(commits[1].summary, "Add atlas and chart");
+
+        let config = crate::Config::default();
+        let tracker = crate::io::FileTracker::new(&config, vault.clone()).unwrap();
+        let builder = crate::io::HtmlBuilder::new(&config, vault.clone());
+        let index = crate::data::NoteIndex::new(tracker, builder, Default::default()).0;
+
+        assert!(index.get("manifold").is_some());
+        assert!(index.get("chart").is_none());
+
+        let past = index.at_commit(&git_manager, first).unwrap();
+
+        assert!(past.get("manifold").is_none());
+        assert!(past.get("readme").is_none());
+        assert_eq!(past.get("atlas").unwrap().links, vec!["chart"]);
+        assert_eq!(past.links_vec("atlas").len(), 1);
+
+        let history = git_manager.file_history(&vault.join("Atlas.md")).unwrap();
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].id, first);
+        assert_eq!(
+            git_manager.file_at(first, &vault.join("Atlas.md")).unwrap(),
+            "An atlas of [[Chart]]s."
+        );
+
+        // paths outside of the repository are refused instead of being read from its root
+        assert!(git_manager
+            .file_at(first, &std::env::current_dir().unwrap())
+            .is_err());
+    }
+
+    #[test]
+    fn test_side_by_side_diff() {
+        let diff = super::side_by_side_diff("a\nb\nc\n", "a\nc\nd\n").unwrap();
+
+        assert_eq!(
+            diff,
+            vec![
+                (Some("a".to_owned()), Some("a".to_owned())),
+                (Some("b".to_owned()), None),
+                (Some("c".to_owned()), Some("c".to_owned())),
+                (None, Some("d".to_owned())),
+            ]
+        );
+
+        let unchanged = super::side_by_side_diff("a\nb", "a\nb").unwrap();
+        assert_eq!(unchanged.len(), 2);
+    }
+}
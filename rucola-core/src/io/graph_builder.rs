@@ -0,0 +1,178 @@
+use std::{collections::HashSet, fmt::Write, path};
+
+use itertools::Itertools;
+use ratatui::style::Color;
+
+use crate::{data, error};
+
+/// The fill colors of notes without a color of their own, assigned by their first tag.
+const TAG_PALETTE: [&str; 8] = [
+    "#9bcfc8", "#a5b8e0", "#e0c290", "#d7a5c9", "#b6d79a", "#e5a59b", "#c7b9e8", "#e8e09b",
+];
+
+/// Writes the link graph of notes to Graphviz DOT files, with a node for every note and an edge for every link between them.
+#[derive(Debug, Clone)]
+pub struct GraphBuilder {
+    /// Path to the vault, which relative paths of graph files start at.
+    vault_path: path::PathBuf,
+    /// How thoroughly to write graph files.
+    durability: super::Durability,
+}
+
+impl GraphBuilder {
+    /// Creates a new graph builder for the given vault.
+    pub fn new(config: &crate::Config, vault_path: path::PathBuf) -> Self {
+        Self {
+            vault_path,
+            durability: config.write_durability,
+        }
+    }
+
+    /// Writes the graph of the notes with the given ids to a DOT file at the given path (relative to the vault) and returns its full path.
+    pub fn create_graph(
+        &self,
+        index: &data::NoteIndex,
+        ids: &[String],
+        path: &path::Path,
+    ) -> error::Result<path::PathBuf> {
+        let path = self.vault_path.join(path);
+        super::write_atomically(&path, to_dot(index, ids), self.durability)?;
+        Ok(path)
+    }
+}
+
+/// Returns the graph of the notes with the given ids in the DOT language.
+/// Nodes are labeled with the titles of their notes and filled with the color of their note, or a color picked by their first tag.
+/// Only links between the given notes become edges, each at most once.
+fn to_dot(index: &data::NoteIndex, ids: &[String]) -> String {
+    let included = ids.iter().map(String::as_str).collect::<HashSet<_>>();
+    let notes = ids
+        .iter()
+        .unique()
+        .filter_map(|id| index.get(id).map(|note| (id, note)))
+        .collect_vec();
+
+    let mut dot = String::from(
+        "digraph rucola {\n  node [shape=box, style=\"rounded,filled\", fillcolor=\"#ffffff\"];\n",
+    );
+
+    for (id, note) in &notes {
+        let _ = write!(dot, "  {} [label={}", quote(id), quote(&note.display_name));
+        if let Some(color) = note_color(note) {
+            let _ = write!(dot, ", fillcolor={}", quote(&color));
+        }
+        if !note.tags.is_empty() {
+            let _ = write!(dot, ", tooltip={}", quote(&note.tags.join(" ")));
+        }
+        dot.push_str("];\n");
+    }
+
+    for (id, note) in &notes {
+        for link in note
+            .links
+            .iter()
+            .unique()
+            .filter(|link| included.contains(link.as_str()))
+        {
+            let _ = writeln!(dot, "  {} -> {};", quote(id), quote(link));
+        }
+    }
+
+    dot.push_str("}\n");
+    dot
+}
+
+/// Returns the fill color of the given note: Its own color, if it is one rucola recognizes, otherwise one from the palette picked by its first tag.
+fn note_color(note: &data::Note) -> Option<String> {
+    let own = note
+        .color
+        .as_deref()
+        .and_then(|color| color.parse::<Color>().ok())
+        .and_then(to_hex);
+
+    own.or_else(|| {
+        note.tags.first().map(|tag| {
+            let hash = tag.bytes().fold(0usize, |hash, byte| {
+                hash.wrapping_mul(31).wrapping_add(byte as usize)
+            });
+            TAG_PALETTE[hash % TAG_PALETTE.len()].to_owned()
+        })
+    })
+}
+
+/// Converts the given color to a hex code Graphviz understands. Indexed and reset colors have none.
+fn to_hex(color: Color) -> Option<String> {
+    let (r, g, b) = match color {
+        Color::Rgb(r, g, b) => (r, g, b),
+        Color::Black => (0x00, 0x00, 0x00),
+        Color::Red => (0x80, 0x00, 0x00),
+        Color::Green => (0x00, 0x80, 0x00),
+        Color::Yellow => (0x80, 0x80, 0x00),
+        Color::Blue => (0x00, 0x00, 0x80),
+        Color::Magenta => (0x80, 0x00, 0x80),
+        Color::Cyan => (0x00, 0x80, 0x80),
+        Color::Gray => (0xc0, 0xc0, 0xc0),
+        Color::DarkGray => (0x80, 0x80, 0x80),
+        Color::LightRed => (0xff, 0x00, 0x00),
+        Color::LightGreen => (0x00, 0xff, 0x00),
+        Color::LightYellow => (0xff, 0xff, 0x00),
+        Color::LightBlue => (0x00, 0x00, 0xff),
+        Color::LightMagenta => (0xff, 0x00, 0xff),
+        Color::LightCyan => (0x00, 0xff, 0xff),
+        Color::White => (0xff, 0xff, 0xff),
+        Color::Indexed(_) | Color::Reset => return None,
+    };
+    Some(format!("#{:02x}{:02x}{:02x}", r, g, b))
+}
+
+/// Quotes the given text as a DOT string.
+fn quote(text: &str) -> String {
+    format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_dot() {
+        let vault = testdir::testdir!();
+        std::fs::write(
+            vault.join("Rome.md"),
+            "---\ncolor: \"#ff8800\"\n---\n# Rome\n[[Paris]] [[Paris]] [[Milan]] [[Nowhere]]\n",
+        )
+        .unwrap();
+        std::fs::write(
+            vault.join("Paris.md"),
+            "---\ntitle: 'The \"City\"'\n---\n#travel [[Rome]]\n",
+        )
+        .unwrap();
+        std::fs::write(vault.join("Milan.md"), "# Milan\n[[Rome]]\n").unwrap();
+
+        let config = crate::Config::default();
+        let tracker = crate::io::FileTracker::new(&config, vault.clone()).unwrap();
+        let builder = crate::io::HtmlBuilder::new(&config, vault.clone());
+        let index = data::NoteIndex::new(tracker, builder, Default::default()).0;
+
+        let dot = to_dot(&index, &["rome".to_owned(), "paris".to_owned()]);
+        assert!(dot.starts_with("digraph rucola {\n"));
+        assert!(dot.contains("  \"rome\" [label=\"Rome\", fillcolor=\"#ff8800\"];\n"));
+        assert!(dot.contains(&format!(
+            "  \"paris\" [label=\"The \\\"City\\\"\", fillcolor={}, tooltip=\"#travel\"];\n",
+            quote(&note_color(index.get("paris").unwrap()).unwrap())
+        )));
+        // links to notes outside the graph are left out, links within it drawn once
+        assert_eq!(dot.matches("\"rome\" -> \"paris\";").count(), 1);
+        assert!(dot.contains("\"paris\" -> \"rome\";"));
+        assert!(!dot.contains("milan"));
+        assert!(!dot.contains("nowhere"));
+
+        let path = GraphBuilder::new(&config, vault.clone())
+            .create_graph(&index, &["rome".to_owned()], path::Path::new("graph.dot"))
+            .unwrap();
+        assert_eq!(path, vault.join("graph.dot"));
+        assert!(std::fs::read_to_string(path)
+            .unwrap()
+            .contains("\"rome\" [label=\"Rome\""));
+    }
+}
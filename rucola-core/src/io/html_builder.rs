@@ -0,0 +1,1036 @@
+use std::{cell::RefCell, collections::HashMap, fs, io::Write, path, rc::Rc};
+
+use itertools::Itertools;
+
+use crate::{data, error};
+
+/// Marks HTML files that only redirect to the page of a note, so they are never mistaken for the page of a note themselves.
+const REDIRECT_MARKER: &str = r#"<meta name="generator" content="rucola-redirect">"#;
+/// Marks HTML files standing in for notes that are linked to but do not exist.
+const PLACEHOLDER_MARKER: &str = r#"<meta name="generator" content="rucola-placeholder">"#;
+/// The stylesheets of reveal.js, which slide decks are shown with.
+const REVEAL_STYLESHEETS: &str = r#"<link rel="stylesheet" href="https://cdn.jsdelivr.net/npm/reveal.js@5.1.0/dist/reveal.css">
+<link rel="stylesheet" href="https://cdn.jsdelivr.net/npm/reveal.js@5.1.0/dist/theme/white.css">
+"#;
+/// The scripts of reveal.js, turning the sections of a slide deck into slides.
+const REVEAL_SCRIPTS: &str = r#"<script src="https://cdn.jsdelivr.net/npm/reveal.js@5.1.0/dist/reveal.js"></script>
+<script>Reveal.initialize({ hash: true });</script>
+"#;
+/// Styles print-friendly HTML and PDF files, unless another print stylesheet is configured.
+pub const PRINT_STYLESHEET: &str = r#"@page { margin: 2cm; }
+nav, .rucola-nav { display: none; }
+h1 { break-before: page; }
+h1:first-of-type { break-before: avoid; }
+h1, h2, h3, h4 { break-after: avoid; }
+pre, blockquote, table, img { break-inside: avoid; }
+a { color: inherit; text-decoration: none; }
+.footnotes { font-size: 0.85em; }
+"#;
+
+/// Everything needed to render notes that does not depend on a single note.
+/// Prepared once and shared between all clones of a builder, so regenerating a note after a change only parses and writes that note.
+#[derive(Debug)]
+struct RenderContext {
+    /// The options used to parse and render markdown.
+    options: comrak::Options<'static>,
+    /// The options used to parse and render print-friendly markdown, which also renders footnotes.
+    print_options: comrak::Options<'static>,
+    /// The scripts rendering math with the configured macros, if math is rendered with KaTeX.
+    math_preamble: Option<String>,
+    /// Matches the ids of blocks in rendered HTML, capturing the id in the first or second group and what follows it in the third.
+    block_regex: regex::Regex,
+    /// The paths of the note files of the vault by their id, collected on the first lookup and again once a note is missing.
+    note_paths: RefCell<HashMap<String, path::PathBuf>>,
+}
+
+/// Struct that keeps configuration details for the creation of HTML files from markdown files.
+#[derive(Debug, Clone)]
+pub struct HtmlBuilder {
+    /// Path to the vault to index.
+    vault_path: path::PathBuf,
+    /// When set to true, HTML files are mass-created on start and continuously kept up to date with file changes instead of being created on-demand.
+    enable_html: bool,
+    /// The resolved path to the css file, if there is one
+    css_path: Option<path::PathBuf>,
+    /// The resolved path to the css file styling print-friendly files, if there is one.
+    print_css_path: Option<path::PathBuf>,
+    /// String to prepend to all generated html documents (e.g. for MathJax)
+    html_prepend: Option<String>,
+    /// Wether HTML files load the search index written with HTML exports.
+    search: bool,
+    /// The prepared rendering context, shared between clones.
+    context: Rc<RenderContext>,
+    /// File types to consider notes when looking for the targets of block references.
+    file_types: Option<ignore::types::Types>,
+}
+
+impl Default for HtmlBuilder {
+    fn default() -> Self {
+        Self::new(
+            &crate::Config::default(),
+            std::env::current_dir().expect("Current directory to exist and be accessible."),
+        )
+    }
+}
+
+impl HtmlBuilder {
+    pub fn new(config: &crate::Config, vault_path: path::PathBuf) -> Self {
+        // Resolve css path
+        let css_path = config.css.as_deref().and_then(resolve_css).inspect(|css| {
+            // ensure that a file exists
+            if !css.exists() {
+                let _ = std::fs::File::create(css);
+            }
+        });
+
+        // Pre-calculate allowed file types
+        let mut types_builder = ignore::types::TypesBuilder::new();
+        types_builder.add_defaults();
+        for name in config.file_types.iter() {
+            types_builder.select(name);
+        }
+
+        let options = comrak::Options {
+            extension: comrak::ExtensionOptions::builder()
+                .wikilinks_title_after_pipe(true)
+                .math_dollars(true)
+                .table(true)
+                .build(),
+            ..Default::default()
+        };
+        let mut print_options = options.clone();
+        print_options.extension.footnotes = true;
+
+        let context = RenderContext {
+            options,
+            print_options,
+            math_preamble: config
+                .katex
+                .then(|| math_preamble(&config.math_replacements)),
+            block_regex: regex::Regex::new(
+                r"<p>\^([[:alnum:]-]+)</p>|\s\^([[:alnum:]-]+)(\s*</p>|\s*</li>|\n)",
+            )
+            .expect("Pre-defined regex to be valid."),
+            note_paths: RefCell::new(HashMap::new()),
+        };
+
+        Self {
+            vault_path,
+            file_types: types_builder.build().ok(),
+            enable_html: config.enable_html,
+            css_path,
+            print_css_path: config
+                .print_css
+                .as_deref()
+                .and_then(resolve_css)
+                .filter(|css| css.exists()),
+            html_prepend: config.html_prepend.clone(),
+            search: config.html_search,
+            context: Rc::new(context),
+        }
+    }
+
+    /// Returns the path of the vault HTML files are created for.
+    pub fn vault_path(&self) -> &path::Path {
+        &self.vault_path
+    }
+
+    /// Creates the HTML file of the given note, if HTML files are enabled and the note is exported or if forced to.
+    /// Query blocks within the note are replaced by their results in the given index, if there is one.
+    pub fn create_html(
+        &self,
+        note: &data::Note,
+        index: Option<&data::NoteIndex>,
+        force: bool,
+    ) -> error::Result<()> {
+        if (!self.enable_html || !note.export.export) && !force {
+            return Ok(());
+        }
+
+        self.render(note, index, false)?;
+        self.create_redirects(note)
+    }
+
+    /// Creates the print-friendly HTML file of the given note, in which pages break before top headings and the URLs of links are listed in footnotes.
+    /// Query blocks within the note are replaced by their results in the given index, if there is one.
+    pub fn create_print_html(
+        &self,
+        note: &data::Note,
+        index: Option<&data::NoteIndex>,
+    ) -> error::Result<()> {
+        self.render(note, index, true)
+    }
+
+    /// Renders the given note into its HTML file, or its print-friendly HTML file if `print` is set.
+    fn render(
+        &self,
+        note: &data::Note,
+        index: Option<&data::NoteIndex>,
+        print: bool,
+    ) -> error::Result<()> {
+        let content = read_content(note, index)?;
+        let content = if print {
+            data::footnote_links(&content).into_owned()
+        } else {
+            content
+        };
+        let options = if print {
+            &self.context.print_options
+        } else {
+            &self.context.options
+        };
+
+        let (html, contains_math, contains_code) = self.to_html(note, &content, options)?;
+
+        let tar_path = if print {
+            name_to_print_path(&note.name, &self.vault_path)
+        } else {
+            name_to_html_path(&note.name, &self.vault_path)
+        };
+
+        // ensure parent exists
+        if let Some(parent) = tar_path.parent() {
+            if !parent.exists() {
+                fs::create_dir_all(parent)?;
+            }
+        }
+
+        // get file (creates it if it doesn't exist)
+        let mut tar_file = fs::File::create(&tar_path)?;
+
+        writeln!(tar_file, "<!DOCTYPE html>")?;
+        writeln!(tar_file, "<title>{}</title>", note.name)?;
+        // use the css file specified in the note's frontmatter, if it exists
+        let css_path = note
+            .export
+            .css
+            .as_deref()
+            .and_then(resolve_css)
+            .filter(|css| css.exists())
+            .or_else(|| self.css_path.clone());
+
+        self.add_preamble(
+            &mut tar_file,
+            css_path.as_deref(),
+            contains_math,
+            contains_code,
+        )?;
+        if print {
+            match &self.print_css_path {
+                Some(css) => writeln!(
+                    tar_file,
+                    "<link rel=\"stylesheet\" href=\"file://{}\">",
+                    css.to_string_lossy()
+                )?,
+                None => writeln!(tar_file, "<style>\n{}</style>", PRINT_STYLESHEET)?,
+            }
+        } else if self.search {
+            writeln!(
+                tar_file,
+                "<script src=\"{}\" defer></script>",
+                super::search_index::SEARCH_SCRIPT_NAME
+            )?;
+        }
+
+        // show the icon and color of the note in a header above its content, if it has any
+        if note.icon.is_some() || note.color.is_some() {
+            let style = note
+                .color
+                .as_deref()
+                // only plain names and hex codes, so the color cannot break out of the attribute
+                .filter(|color| color.chars().all(|c| c.is_ascii_alphanumeric() || c == '#'))
+                .map(|color| format!(" style=\"color: {}\"", color))
+                .unwrap_or_default();
+            let icon = note
+                .icon
+                .as_deref()
+                .map(|icon| format!("<span class=\"rucola-icon\">{}</span> ", icon))
+                .unwrap_or_default();
+            writeln!(
+                tar_file,
+                "<header class=\"rucola-header\"{}>{}{}</header>",
+                style, icon, note.display_name
+            )?;
+        }
+
+        tar_file.write_all(html.as_bytes())?;
+        Ok(())
+    }
+
+    /// Creates a reveal.js slide deck of the given note, split into slides by rules or top headings.
+    /// Query blocks within the note are replaced by their results in the given index, if there is one.
+    pub fn create_slides(
+        &self,
+        note: &data::Note,
+        index: Option<&data::NoteIndex>,
+    ) -> error::Result<()> {
+        let content = read_content(note, index)?;
+
+        let mut sections = Vec::new();
+        let mut contains_math = false;
+        let mut contains_code = false;
+        for slide in data::split_slides(&content) {
+            let (html, math, code) = self.to_html(note, &slide, &self.context.options)?;
+            contains_math |= math;
+            contains_code |= code;
+            sections.push(html);
+        }
+
+        let tar_path = name_to_slides_path(&note.name, &self.vault_path);
+        if let Some(parent) = tar_path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let mut tar_file = fs::File::create(&tar_path)?;
+
+        writeln!(tar_file, "<!DOCTYPE html>")?;
+        writeln!(tar_file, "<meta charset=\"utf-8\">")?;
+        writeln!(tar_file, "<title>{}</title>", note.name)?;
+        tar_file.write_all(REVEAL_STYLESHEETS.as_bytes())?;
+        self.add_preamble(&mut tar_file, None, contains_math, contains_code)?;
+        writeln!(tar_file, "<div class=\"reveal\"><div class=\"slides\">")?;
+        for section in sections {
+            writeln!(tar_file, "<section>\n{}</section>", section)?;
+        }
+        writeln!(tar_file, "</div></div>")?;
+        tar_file.write_all(REVEAL_SCRIPTS.as_bytes())?;
+
+        Ok(())
+    }
+
+    /// Renders the given markdown of the given note into HTML.
+    /// Also returns wether it contains math and code, which need scripts to be shown.
+    fn to_html(
+        &self,
+        note: &data::Note,
+        content: &str,
+        options: &comrak::Options,
+    ) -> error::Result<(String, bool, bool)> {
+        // Parse markdown into AST
+        let arena = comrak::Arena::new();
+        let root = comrak::parse_document(&arena, content, options);
+
+        let mut contains_math = false;
+        let mut contains_code = false;
+        let mut references = Vec::new();
+
+        for node in root.descendants() {
+            // correct id urls for wiki links
+            match node.data.borrow_mut().value {
+                comrak::nodes::NodeValue::WikiLink(ref mut link) => match link.url.split_once("#^")
+                {
+                    // references to blocks link to their anchor and show the referenced block
+                    Some((target, block)) => {
+                        let id = data::name_to_id(if target.is_empty() {
+                            &note.name
+                        } else {
+                            target
+                        });
+                        let text = if id == data::name_to_id(&note.name) {
+                            data::find_block(content, block)
+                        } else {
+                            self.find_note(&id)
+                                .and_then(|path| fs::read_to_string(path).ok())
+                                .and_then(|target| data::find_block(&target, block))
+                        };
+                        if let Some(text) = text {
+                            references.push((node, link.url.clone(), text));
+                        }
+                        link.url = format!("{}.html#^{}", id, block);
+                    }
+                    None => {
+                        link.url = format!("{}.html", data::name_to_id(&link.url));
+                    }
+                },
+                comrak::nodes::NodeValue::Link(ref mut link) => {
+                    if !link.url.contains('/') && !link.url.contains('.') {
+                        link.url = format!("{}.html", data::name_to_id(&link.url));
+                    }
+                }
+                comrak::nodes::NodeValue::Math(ref mut math) => {
+                    contains_math = true;
+                    let x = &mut math.literal;
+                    // re-insert the dollar at beginning and end to make mathjax pick it up
+                    x.insert(0, '$');
+                    x.push('$');
+                    // if display math, do it again.
+                    if math.display_math {
+                        x.insert(0, '$');
+                        x.push('$');
+                    }
+                }
+                comrak::nodes::NodeValue::CodeBlock(ref _code) => {
+                    contains_code = true;
+                }
+                _ => {}
+            }
+        }
+
+        // links to blocks without a title of their own are titled with the referenced block
+        for (node, url, text) in references {
+            for child in node.children() {
+                if let comrak::nodes::NodeValue::Text(ref mut literal) =
+                    child.data.borrow_mut().value
+                {
+                    if *literal == url {
+                        *literal = text.lines().map(str::trim).join(" ");
+                    }
+                }
+            }
+        }
+
+        let mut html = Vec::new();
+        comrak::format_html(root, options, &mut html)?;
+
+        // replace the ids of blocks by anchors references can link to
+        let html = String::from_utf8_lossy(&html);
+        let html =
+            self.context.block_regex.replace_all(
+                &html,
+                |captures: &regex::Captures| match captures.get(1).or_else(|| captures.get(2)) {
+                    Some(block) if note.blocks.iter().any(|id| id == block.as_str()) => format!(
+                        "<span id=\"^{}\"></span>{}",
+                        block.as_str(),
+                        captures.get(3).map_or("", |end| end.as_str())
+                    ),
+                    _ => captures[0].to_owned(),
+                },
+            );
+
+        Ok((html.into_owned(), contains_math, contains_code))
+    }
+
+    /// Returns the path of the note file with the given id.
+    /// Paths are looked up in the vault only once, and again if the note is not found at its known path.
+    fn find_note(&self, id: &str) -> Option<path::PathBuf> {
+        let known = self.context.note_paths.borrow().get(id).cloned();
+        if let Some(path) = known.filter(|path| path.is_file()) {
+            return Some(path);
+        }
+
+        // the note is new, was moved or does not exist, so look through the vault again
+        let paths = self.note_paths();
+        let path = paths.get(id).cloned();
+        *self.context.note_paths.borrow_mut() = paths;
+        path
+    }
+
+    /// Returns the paths of all note files of the vault by their id.
+    fn note_paths(&self) -> HashMap<String, path::PathBuf> {
+        let mut walker = ignore::WalkBuilder::new(&self.vault_path);
+        if let Some(file_types) = &self.file_types {
+            walker.types(file_types.clone());
+        }
+
+        walker
+            .build()
+            .flatten()
+            .map(|entry| entry.into_path())
+            .filter(|path| path.is_file())
+            .filter_map(|path| {
+                let id = data::name_to_id(&path.file_stem()?.to_string_lossy());
+                Some((id, path))
+            })
+            .collect()
+    }
+
+    /// Creates a page for every alias of the given note that redirects to the page of the note, so links to former names keep working.
+    /// Pages of other notes are never overwritten by redirects.
+    fn create_redirects(&self, note: &data::Note) -> error::Result<()> {
+        let id = data::name_to_id(&note.name);
+        let target = format!("{}.html", id);
+
+        for alias in &note.aliases {
+            if data::name_to_id(alias) == id {
+                continue;
+            }
+
+            let path = name_to_html_path(alias, &self.vault_path);
+            if !is_replaceable(&path) {
+                continue;
+            }
+
+            fs::write(
+                &path,
+                format!(
+                    "<!DOCTYPE html>\n{}\n<title>{}</title>\n<link rel=\"canonical\" href=\"{}\">\n<meta http-equiv=\"refresh\" content=\"0; url={}\">\n<p>This page has moved to <a href=\"{}\">{}</a>.</p>\n",
+                    REDIRECT_MARKER, note.name, target, target, target, note.display_name
+                ),
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// Creates a placeholder page for the missing note with the given id, listing the given notes linking to it, so their links do not break.
+    /// Pages of existing notes are never overwritten by placeholders.
+    pub fn create_placeholder(&self, id: &str, linked_from: &[&data::Note]) -> error::Result<()> {
+        let path = name_to_html_path(id, &self.vault_path);
+        if !is_replaceable(&path) {
+            return Ok(());
+        }
+
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let mut file = fs::File::create(&path)?;
+
+        writeln!(file, "<!DOCTYPE html>")?;
+        writeln!(file, "{}", PLACEHOLDER_MARKER)?;
+        writeln!(file, "<title>{}</title>", id)?;
+        self.add_preamble(&mut file, self.css_path.as_deref(), false, false)?;
+        writeln!(file, "<h1>{}</h1>", id)?;
+        writeln!(
+            file,
+            "<p>This note does not exist yet. It is linked from:</p>"
+        )?;
+        writeln!(file, "<ul>")?;
+        for note in linked_from {
+            writeln!(
+                file,
+                "<li><a href=\"{}.html\">{}</a></li>",
+                data::name_to_id(&note.name),
+                note.display_name
+            )?;
+        }
+        writeln!(file, "</ul>")?;
+
+        Ok(())
+    }
+
+    /// Writes the search index of the given notes next to their HTML files, if searching is enabled.
+    pub fn create_search_index(&self, notes: &[data::Note]) -> error::Result<()> {
+        if !self.search {
+            return Ok(());
+        }
+
+        let html_folder = self.vault_path.join(".html");
+        fs::create_dir_all(&html_folder)?;
+        fs::write(
+            html_folder.join(super::search_index::SEARCH_SCRIPT_NAME),
+            super::search_index::search_script(notes),
+        )?;
+        Ok(())
+    }
+
+    /// Prepends relevant data to a generated html file
+    pub fn add_preamble(
+        &self,
+        html: &mut impl std::io::Write,
+        css_path: Option<&path::Path>,
+        contains_math: bool,
+        contains_code: bool,
+    ) -> error::Result<()> {
+        // Let small screens render the page at their own width instead of zooming out
+        writeln!(
+            html,
+            "<meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">"
+        )?;
+        // Prepend css location
+        if let Some(css) = css_path {
+            writeln!(
+                html,
+                "<link rel=\"stylesheet\" href=\"file://{}\">",
+                css.to_string_lossy()
+            )?;
+        }
+        // Prepend katex code
+        if let Some(math_preamble) = self
+            .context
+            .math_preamble
+            .as_ref()
+            .filter(|_| contains_math)
+        {
+            html.write_all(math_preamble.as_bytes())?;
+        }
+
+        if contains_code {
+            writeln!(
+                html,
+                r##"<link rel="stylesheet" href="https://cdnjs.cloudflare.com/ajax/libs/highlight.js/11.9.0/styles/default.min.css">"##
+            )?;
+            writeln!(
+                html,
+                r##"<script src="https://cdnjs.cloudflare.com/ajax/libs/highlight.js/11.9.0/highlight.min.js"></script>"##
+            )?;
+            writeln!(
+                html,
+                r##"<script src="https://cdnjs.cloudflare.com/ajax/libs/highlight.js/11.9.0/languages/go.min.js"></script>"##
+            )?;
+            writeln!(html, r##"<script>hljs.highlightAll();</script>"##)?;
+        }
+
+        // Prepend all other manual configured prefixes
+        if let Some(prep) = &self.html_prepend {
+            html.write_all(prep.as_bytes())?;
+        }
+        Ok(())
+    }
+}
+
+/// Reads the content of the given note, replacing query blocks by their results in the given index, if there is one.
+fn read_content(note: &data::Note, index: Option<&data::NoteIndex>) -> error::Result<String> {
+    let content = fs::read_to_string(&note.path)?;
+    Ok(match index {
+        Some(index) => {
+            data::expand_queries(&content, index, &data::name_to_id(&note.name)).into_owned()
+        }
+        None => content,
+    })
+}
+
+/// Wether the HTML file at the given path may be replaced by a redirect or placeholder, i.e. does not exist or is one itself.
+fn is_replaceable(path: &path::Path) -> bool {
+    !path.exists()
+        || fs::read_to_string(path).is_ok_and(|content| {
+            content.contains(REDIRECT_MARKER) || content.contains(PLACEHOLDER_MARKER)
+        })
+}
+
+/// Resolves the name of a css file to its path within the rucola config folder.
+pub(crate) fn resolve_css(css: &str) -> Option<path::PathBuf> {
+    confy::get_configuration_file_path(
+        "rucola",
+        // remove css at the end, so no matter if the user included it or not, we always have the same format. If we left the css, confy would append .toml and we would end up with .css.css
+        css.trim_end_matches(".css"),
+    )
+    .ok()
+    .map(|mut css| {
+        // confy will append .toml (as this is the expected extension for config files)
+        // so replace that with .css in any case.
+        css.set_extension("css");
+        css
+    })
+}
+
+/// Returns the stylesheets and scripts rendering math with KaTeX, using the given replacements as macros.
+fn math_preamble(math_replacements: &HashMap<String, String>) -> String {
+    format!(
+        r##"<link rel="stylesheet" href="https://cdn.jsdelivr.net/npm/katex@0.16.10/dist/katex.min.css" integrity="sha384-wcIxkf4k558AjM3Yz3BBFQUbk/zgIYC2R0QpeeYb+TwlBVMrlgLqwRjRtGZiK7ww" crossorigin="anonymous">
+<script defer src="https://cdn.jsdelivr.net/npm/katex@0.16.10/dist/katex.min.js" integrity="sha384-hIoBPJpTUs74ddyc4bFZSM1TVlQDA60VBbJS0oA934VSz82sBx1X7kSx2ATBDIyd" crossorigin="anonymous"></script>
+<script defer src="https://cdn.jsdelivr.net/npm/katex@0.16.10/dist/contrib/auto-render.min.js" integrity="sha384-43gviWU0YVjaDtb/GhzOouOXtZMP/7XUzwPTstBeZFe/+rCMvRwr4yROQP43s0Xk" crossorigin="anonymous"></script>
+<script>
+    document.addEventListener("DOMContentLoaded", function() {{
+        renderMathInElement(document.body, {{
+          delimiters: [
+              {{left: '$$', right: '$$', display: true}},
+              {{left: '$', right: '$', display: false}},
+          ],
+          macros: {},
+          throwOnError : false
+        }});
+    }});
+</script>
+"##,
+        serde_json::to_string(math_replacements).unwrap()
+    )
+}
+
+/// For a given note id, returns the path its HTML representation _would_ be stored at.
+/// Makes no guarantees if that representation currently exists.
+pub fn name_to_html_path(name: &str, vault_path: &path::Path) -> path::PathBuf {
+    // calculate target path
+    let mut tar_path = vault_path.to_path_buf();
+    tar_path.push(".html/");
+
+    tar_path.set_file_name(format!(".html/{}", &data::name_to_id(name)));
+    tar_path.set_extension("html");
+    tar_path
+}
+
+/// For a given note name, returns the path its print-friendly HTML representation _would_ be stored at.
+/// Makes no guarantees if that representation currently exists.
+pub fn name_to_print_path(name: &str, vault_path: &path::Path) -> path::PathBuf {
+    vault_path
+        .join(".print")
+        .join(format!("{}.html", data::name_to_id(name)))
+}
+
+/// For a given note name, returns the path its reveal.js slide deck _would_ be stored at.
+/// Makes no guarantees if that representation currently exists.
+pub fn name_to_slides_path(name: &str, vault_path: &path::Path) -> path::PathBuf {
+    vault_path
+        .join(".slides")
+        .join(format!("{}.html", data::name_to_id(name)))
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::path::{Path, PathBuf};
+
+    #[test]
+    fn test_create_html_no_panic() {
+        let config = crate::Config::default();
+        let hb = super::HtmlBuilder::new(&config, PathBuf::from("./tests"));
+
+        let os = crate::data::Note::from_path(
+            Path::new("./tests/common/notes/Operating Systems.md"),
+            &Default::default(),
+        )
+        .unwrap();
+
+        hb.create_html(&os, None, true).unwrap();
+    }
+
+    #[test]
+    fn test_create_html_no_panic_math() {
+        let config = crate::Config::default();
+        let hb = super::HtmlBuilder::new(&config, PathBuf::from("./tests"));
+
+        // with math
+        let smooth_map = crate::data::Note::from_path(
+            Path::new("./tests/common/notes/math/Smooth Map.md"),
+            &Default::default(),
+        )
+        .unwrap();
+
+        hb.create_html(&smooth_map, None, true).unwrap();
+    }
+
+    #[test]
+    fn test_name_to_html_path() {
+        // let config = crate::Config::default();
+        let vault_path = PathBuf::from("./tests");
+
+        assert_eq!(
+            super::name_to_html_path("Lie Group", &vault_path),
+            PathBuf::from("./tests/.html/lie-group.html")
+        );
+        assert_eq!(
+            super::name_to_html_path("lie-group", &vault_path),
+            PathBuf::from("./tests/.html/lie-group.html")
+        );
+        assert_eq!(
+            super::name_to_html_path("books", &vault_path),
+            PathBuf::from("./tests/.html/books.html")
+        );
+    }
+
+    #[test]
+    fn test_create_html_creates_files() {
+        let config = crate::Config::default();
+        let vault_path = PathBuf::from("./tests");
+        let b_path = super::name_to_html_path("Books", &vault_path);
+        let hb = super::HtmlBuilder::new(&config, vault_path);
+
+        let books = crate::data::Note::from_path(
+            Path::new("./tests/common/notes/Books.md"),
+            &Default::default(),
+        )
+        .unwrap();
+
+        if b_path.exists() {
+            std::fs::remove_file(&b_path).unwrap();
+        }
+
+        // assert!(!b_path.exists());
+
+        hb.create_html(&books, None, true).unwrap();
+
+        assert!(b_path.exists());
+    }
+
+    #[test]
+    fn test_create_html_creates_files_with_math() {
+        let config = crate::Config::default();
+        let vault_path = PathBuf::from("./tests");
+        let lg_path = super::name_to_html_path("Lie Group", &vault_path);
+        let hb = super::HtmlBuilder::new(&config, vault_path);
+
+        // with math
+        let liegroup = crate::data::Note::from_path(
+            Path::new("./tests/common/notes/math/Lie Group.md"),
+            &Default::default(),
+        )
+        .unwrap();
+
+        if Path::new(&lg_path).exists() {
+            std::fs::remove_file(&lg_path).unwrap();
+        }
+
+        // assert!(!lg_path.exists());
+
+        hb.create_html(&liegroup, None, true).unwrap();
+
+        assert!(lg_path.exists());
+    }
+
+    #[test]
+    fn test_create_html_creates_redirects() {
+        let config = crate::Config::default();
+        let vault_path = testdir::testdir!();
+        let path = vault_path.join("Lie Group.md");
+        std::fs::write(
+            &path,
+            "---\naliases: [Continuous Group, Atlas]\n---\n# Lie Group\n",
+        )
+        .unwrap();
+        let hb = super::HtmlBuilder::new(&config, vault_path.clone());
+
+        // the page of another note is kept
+        let atlas_path = super::name_to_html_path("Atlas", &vault_path);
+        std::fs::create_dir_all(atlas_path.parent().unwrap()).unwrap();
+        std::fs::write(&atlas_path, "<h1>Atlas</h1>").unwrap();
+
+        let lie_group = crate::data::Note::from_path(&path, &Default::default()).unwrap();
+        hb.create_html(&lie_group, None, true).unwrap();
+
+        let redirect =
+            std::fs::read_to_string(super::name_to_html_path("Continuous Group", &vault_path))
+                .unwrap();
+        assert!(redirect.contains(super::REDIRECT_MARKER));
+        assert!(redirect.contains("url=lie-group.html"));
+        assert_eq!(
+            std::fs::read_to_string(&atlas_path).unwrap(),
+            "<h1>Atlas</h1>"
+        );
+    }
+
+    #[test]
+    fn test_create_html_block_references() {
+        let config = crate::Config::default();
+        let vault_path = testdir::testdir!();
+        std::fs::write(
+            vault_path.join("Chart.md"),
+            "# Chart\nA chart maps\nan open set. ^def\n",
+        )
+        .unwrap();
+        std::fs::write(
+            vault_path.join("Atlas.md"),
+            "# Atlas\nCharts: [[Chart#^def]], see [[Chart#^def|there]].\n",
+        )
+        .unwrap();
+        let hb = super::HtmlBuilder::new(&config, vault_path.clone());
+
+        for name in ["Chart", "Atlas"] {
+            let note = crate::data::Note::from_path(
+                &vault_path.join(format!("{}.md", name)),
+                &Default::default(),
+            )
+            .unwrap();
+            hb.create_html(&note, None, true).unwrap();
+        }
+
+        // referenced blocks get an anchor instead of their id
+        let chart =
+            std::fs::read_to_string(super::name_to_html_path("Chart", &vault_path)).unwrap();
+        assert!(chart.contains("an open set.<span id=\"^def\"></span></p>"));
+
+        // references show the block unless they have a title
+        let atlas =
+            std::fs::read_to_string(super::name_to_html_path("Atlas", &vault_path)).unwrap();
+        assert!(atlas.contains(
+            "<a href=\"chart.html#%5Edef\" data-wikilink=\"true\">A chart maps an open set.</a>"
+        ));
+        assert!(atlas.contains("<a href=\"chart.html#%5Edef\" data-wikilink=\"true\">there</a>"));
+    }
+
+    #[test]
+    fn test_render_context() {
+        let config = crate::Config::default();
+        let vault_path = testdir::testdir!();
+        std::fs::write(vault_path.join("Chart.md"), "# Chart\nA chart. ^def\n").unwrap();
+        let atlas_path = vault_path.join("Atlas.md");
+        std::fs::write(&atlas_path, "# Atlas\nCharts: [[Chart#^def]] ^atlas\n").unwrap();
+        let atlas = crate::data::Note::from_path(&atlas_path, &Default::default()).unwrap();
+        let hb = super::HtmlBuilder::new(&config, vault_path.clone());
+
+        // clones share the context and thus the paths of notes
+        hb.clone().create_html(&atlas, None, true).unwrap();
+        assert_eq!(
+            hb.context.note_paths.borrow().get("chart"),
+            Some(&vault_path.join("Chart.md"))
+        );
+
+        // moved notes are looked up again
+        std::fs::create_dir(vault_path.join("maps")).unwrap();
+        std::fs::rename(
+            vault_path.join("Chart.md"),
+            vault_path.join("maps/Chart.md"),
+        )
+        .unwrap();
+        hb.create_html(&atlas, None, true).unwrap();
+        assert_eq!(
+            hb.context.note_paths.borrow().get("chart"),
+            Some(&vault_path.join("maps/Chart.md"))
+        );
+
+        // only ids of blocks of the note become anchors
+        let html = std::fs::read_to_string(super::name_to_html_path("Atlas", &vault_path)).unwrap();
+        assert!(html.contains(">A chart.</a><span id=\"^atlas\"></span></p>"));
+        std::fs::write(&atlas_path, "# Atlas\nNot a block: ^atlas\n").unwrap();
+        let mut atlas = atlas;
+        atlas.blocks.clear();
+        hb.create_html(&atlas, None, true).unwrap();
+        let html = std::fs::read_to_string(super::name_to_html_path("Atlas", &vault_path)).unwrap();
+        assert!(html.contains("Not a block: ^atlas</p>"));
+    }
+
+    #[test]
+    fn test_create_html_tables() {
+        let config = crate::Config::default();
+        let vault_path = testdir::testdir!();
+        let path = vault_path.join("Groups.md");
+        std::fs::write(
+            &path,
+            "# Groups\n| Group | Order |\n| :--- | ---: |\n| Klein | 4 |\n",
+        )
+        .unwrap();
+        let hb = super::HtmlBuilder::new(&config, vault_path.clone());
+
+        let groups = crate::data::Note::from_path(&path, &Default::default()).unwrap();
+        hb.create_html(&groups, None, true).unwrap();
+
+        let html =
+            std::fs::read_to_string(super::name_to_html_path("Groups", &vault_path)).unwrap();
+        assert!(html.contains("<table>"));
+        assert!(html.contains("<th align=\"left\">Group</th>"));
+        assert!(html.contains("<td align=\"right\">4</td>"));
+    }
+
+    #[test]
+    fn test_create_html_queries() {
+        let config = crate::Config::default();
+        let vault_path = testdir::testdir!();
+        std::fs::write(vault_path.join("Atlas.md"), "# Atlas\n#maps").unwrap();
+        std::fs::write(vault_path.join("Chart.md"), "# Chart\n#maps").unwrap();
+        let path = vault_path.join("Maps.md");
+        std::fs::write(
+            &path,
+            "# Maps\n#maps\n```rucola query tag:#maps\n```\n```rust\nlet maps = 2;\n```\n",
+        )
+        .unwrap();
+        let hb = super::HtmlBuilder::new(&config, vault_path.clone());
+        let tracker = crate::io::FileTracker::new(&config, vault_path.clone()).unwrap();
+        let index = crate::data::NoteIndex::new(tracker, hb.clone(), Default::default()).0;
+
+        let maps = index.get("maps").unwrap();
+        hb.create_html(maps, Some(&index), true).unwrap();
+
+        // the query is replaced by links to the other notes, other code blocks are kept
+        let html = std::fs::read_to_string(super::name_to_html_path("Maps", &vault_path)).unwrap();
+        assert!(html.contains("<li><a href=\"atlas.html\" data-wikilink=\"true\">Atlas</a></li>"));
+        assert!(html.contains("<li><a href=\"chart.html\" data-wikilink=\"true\">Chart</a></li>"));
+        assert!(!html.contains("maps.html"));
+        assert!(!html.contains("rucola query"));
+        assert!(html.contains("let maps = 2;"));
+    }
+
+    #[test]
+    fn test_create_html_header() {
+        let config = crate::Config::default();
+        let vault_path = testdir::testdir!();
+        let path = vault_path.join("Dune.md");
+        std::fs::write(
+            &path,
+            "---\ntitle: Dune\nicon: 🏜️\ncolor: sandybrown\n---\nA book.\n",
+        )
+        .unwrap();
+        let hb = super::HtmlBuilder::new(&config, vault_path.clone());
+
+        let dune = crate::data::Note::from_path(&path, &Default::default()).unwrap();
+        hb.create_html(&dune, None, true).unwrap();
+
+        let html = std::fs::read_to_string(super::name_to_html_path("Dune", &vault_path)).unwrap();
+        assert!(html.contains(
+            "<header class=\"rucola-header\" style=\"color: sandybrown\"><span class=\"rucola-icon\">🏜️</span> Dune</header>"
+        ));
+    }
+
+    #[test]
+    fn test_create_print_html() {
+        let config = crate::Config::default();
+        let vault_path = testdir::testdir!();
+        let path = vault_path.join("Dune.md");
+        std::fs::write(
+            &path,
+            "# Dune\nBy [Frank Herbert](https://herbert.org), see [[Arrakis]].\n# Sequels\n",
+        )
+        .unwrap();
+        let hb = super::HtmlBuilder::new(&config, vault_path.clone());
+
+        let dune = crate::data::Note::from_path(&path, &Default::default()).unwrap();
+        hb.create_print_html(&dune, None).unwrap();
+
+        // the print-friendly file is kept apart from the regular one
+        let print_path = super::name_to_print_path("Dune", &vault_path);
+        assert_eq!(print_path, vault_path.join(".print/dune.html"));
+        assert!(!super::name_to_html_path("Dune", &vault_path).exists());
+
+        let html = std::fs::read_to_string(print_path).unwrap();
+        assert!(html.contains(super::PRINT_STYLESHEET));
+        assert!(html.contains("<a href=\"arrakis.html\""));
+        assert!(html.contains(
+            "<a href=\"https://herbert.org\">Frank Herbert</a><sup class=\"footnote-ref\">"
+        ));
+        assert!(html.contains("<section class=\"footnotes\""));
+    }
+
+    #[test]
+    fn test_create_slides() {
+        let config = crate::Config::default();
+        let vault_path = testdir::testdir!();
+        let path = vault_path.join("Dune.md");
+        std::fs::write(&path, "# Dune\nSee [[Arrakis]].\n## Sequels\n$x$\n").unwrap();
+        let hb = super::HtmlBuilder::new(&config, vault_path.clone());
+
+        let dune = crate::data::Note::from_path(&path, &Default::default()).unwrap();
+        hb.create_slides(&dune, None).unwrap();
+
+        let slides_path = super::name_to_slides_path("Dune", &vault_path);
+        assert_eq!(slides_path, vault_path.join(".slides/dune.html"));
+        assert!(!super::name_to_html_path("Dune", &vault_path).exists());
+
+        let html = std::fs::read_to_string(slides_path).unwrap();
+        assert!(html.contains(super::REVEAL_SCRIPTS));
+        assert_eq!(html.matches("<section>").count(), 2);
+        assert!(html.contains("<a href=\"arrakis.html\""));
+        assert!(html.contains("$x$"));
+    }
+
+    #[test]
+    fn test_create_placeholder() {
+        let config = crate::Config::default();
+        let vault_path = testdir::testdir!();
+        std::fs::write(vault_path.join("Chart.md"), "# Chart\n[[Diffeomorphism]]").unwrap();
+        let hb = super::HtmlBuilder::new(&config, vault_path.clone());
+
+        let chart = crate::data::Note::from_path(&vault_path.join("Chart.md"), &Default::default())
+            .unwrap();
+        hb.create_placeholder("diffeomorphism", &[&chart]).unwrap();
+
+        let placeholder_path = super::name_to_html_path("diffeomorphism", &vault_path);
+        let placeholder = std::fs::read_to_string(&placeholder_path).unwrap();
+        assert!(placeholder.contains(super::PLACEHOLDER_MARKER));
+        assert!(placeholder.contains("<a href=\"chart.html\">Chart</a>"));
+
+        // once the note exists, its page replaces the placeholder and is kept
+        std::fs::write(&placeholder_path, "<h1>Diffeomorphism</h1>").unwrap();
+        hb.create_placeholder("diffeomorphism", &[&chart]).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(&placeholder_path).unwrap(),
+            "<h1>Diffeomorphism</h1>"
+        );
+    }
+
+    #[test]
+    fn test_create_html_respects_export() {
+        let config = crate::Config::default();
+        let vault_path = testdir::testdir!();
+        let path = vault_path.join("Private.md");
+        std::fs::write(&path, "---\nexport: false\n---\n# Private\n").unwrap();
+        let html_path = super::name_to_html_path("Private", &vault_path);
+        let hb = super::HtmlBuilder::new(&config, vault_path);
+
+        let private = crate::data::Note::from_path(&path, &Default::default()).unwrap();
+
+        hb.create_html(&private, None, false).unwrap();
+
+        assert!(!html_path.exists());
+
+        // viewing a note explicitly still creates its HTML
+        hb.create_html(&private, None, true).unwrap();
+
+        assert!(html_path.exists());
+    }
+}
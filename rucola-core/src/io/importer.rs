@@ -0,0 +1,275 @@
+use std::{collections::HashMap, fs, path};
+
+use crate::data;
+
+/// A link of an imported note to a file, which is rewritten to stay valid within the vault.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImportedLink {
+    /// The target of the link as written in the note.
+    pub target: String,
+    /// The new target of the link, relative to the folder of the note.
+    pub rewritten: String,
+    /// The file outside the vault the link refers to and the path within the vault it is copied to, if it has to be copied.
+    pub copy: Option<(path::PathBuf, path::PathBuf)>,
+}
+
+/// The links of a note copied into the vault that refer to files by absolute paths or outside the vault.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImportPlan {
+    /// The id of the note.
+    pub id: String,
+    /// The path of the note.
+    pub path: path::PathBuf,
+    /// The links to rewrite.
+    pub links: Vec<ImportedLink>,
+}
+
+impl ImportPlan {
+    /// Returns the number of files copied into the vault.
+    pub fn copies(&self) -> usize {
+        self.links.iter().filter(|link| link.copy.is_some()).count()
+    }
+}
+
+/// Finds links of notes copied into the vault that only work at their original location: Absolute paths and relative paths leaving the vault.
+/// Files they refer to are copied into a folder of the vault, and the links are rewritten to be relative to the note.
+#[derive(Debug, Clone)]
+pub struct Importer {
+    /// The vault, canonicalized to compare it with the paths of notes.
+    vault_path: path::PathBuf,
+    /// The folder within the vault referenced files are copied to.
+    assets_folder: path::PathBuf,
+}
+
+impl Importer {
+    /// Creates an importer for the given vault, copying files to the configured folder.
+    pub fn new(config: &crate::Config, vault_path: &path::Path) -> Self {
+        let vault_path = vault_path
+            .canonicalize()
+            .unwrap_or_else(|_| vault_path.to_path_buf());
+        Self {
+            assets_folder: vault_path.join(&config.import_assets_folder),
+            vault_path,
+        }
+    }
+
+    /// Plans the rewriting of the links of the given notes, given by their ids and paths.
+    /// Notes without links to rewrite are left out. Files referenced by several notes are copied once, different files of the same name get numbered names.
+    pub fn plan(&self, notes: &[(String, path::PathBuf)]) -> Vec<ImportPlan> {
+        // the paths files are copied to, by the file they are copied from
+        let mut copies: HashMap<path::PathBuf, path::PathBuf> = HashMap::new();
+
+        notes
+            .iter()
+            .filter_map(|(id, path)| {
+                let content = fs::read_to_string(path).ok()?;
+                let folder = path.parent()?;
+
+                let links = data::local_link_targets(&content)
+                    .into_iter()
+                    .filter_map(|target| self.plan_link(target, folder, &mut copies))
+                    .collect::<Vec<_>>();
+
+                (!links.is_empty()).then(|| ImportPlan {
+                    id: id.clone(),
+                    path: path.clone(),
+                    links,
+                })
+            })
+            .collect()
+    }
+
+    /// Plans the rewriting of the given link target of a note in the given folder, if it needs to be rewritten.
+    fn plan_link(
+        &self,
+        target: String,
+        folder: &path::Path,
+        copies: &mut HashMap<path::PathBuf, path::PathBuf>,
+    ) -> Option<ImportedLink> {
+        // keep anchors like the page of a PDF
+        let (file, anchor) = match target.find('#') {
+            Some(index) => target.split_at(index),
+            None => (target.as_str(), ""),
+        };
+        let file = percent_decode(file.strip_prefix("file://").unwrap_or(file));
+        let absolute = path::Path::new(&file).is_absolute();
+        let source = normalize(&folder.join(&file));
+
+        let (destination, copy) = if source.starts_with(&self.vault_path) {
+            // files within the vault only need a relative link if they were linked absolutely
+            if !absolute {
+                return None;
+            }
+            (source, None)
+        } else {
+            let source = source
+                .canonicalize()
+                .ok()
+                .filter(|source| source.is_file())?;
+            if source.starts_with(&self.vault_path) {
+                return None;
+            }
+            let destination = match copies.get(&source) {
+                Some(destination) => destination.clone(),
+                None => {
+                    let destination = self.free_destination(&source, copies);
+                    copies.insert(source.clone(), destination.clone());
+                    destination
+                }
+            };
+            (destination.clone(), Some((source, destination)))
+        };
+
+        Some(ImportedLink {
+            rewritten: format!("{}{}", relative_link(folder, &destination), anchor),
+            target,
+            copy,
+        })
+    }
+
+    /// Returns a path within the assets folder to copy the given file to, which is not taken by another file.
+    /// A file with the same content may be reused.
+    fn free_destination(
+        &self,
+        source: &path::Path,
+        copies: &HashMap<path::PathBuf, path::PathBuf>,
+    ) -> path::PathBuf {
+        let stem = source
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+            .unwrap_or_else(|| String::from("file"));
+        let extension = source
+            .extension()
+            .map(|extension| format!(".{}", extension.to_string_lossy()))
+            .unwrap_or_default();
+
+        (1..)
+            .map(|number| {
+                self.assets_folder.join(match number {
+                    1 => format!("{}{}", stem, extension),
+                    number => format!("{} {}{}", stem, number, extension),
+                })
+            })
+            .find(|destination| {
+                !copies.values().any(|other| other == destination)
+                    && (!destination.exists() || is_same_content(source, destination))
+            })
+            .expect("Infinitely many names to contain a free one.")
+    }
+}
+
+/// Wether the given files have the same content.
+pub(super) fn is_same_content(a: &path::Path, b: &path::Path) -> bool {
+    match (fs::read(a), fs::read(b)) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => false,
+    }
+}
+
+/// Resolves `.` and `..` in the given path without accessing the file system.
+fn normalize(path: &path::Path) -> path::PathBuf {
+    let mut normalized = path::PathBuf::new();
+    for component in path.components() {
+        match component {
+            path::Component::CurDir => {}
+            path::Component::ParentDir => {
+                normalized.pop();
+            }
+            component => normalized.push(component),
+        }
+    }
+    normalized
+}
+
+/// Returns a link from a note in the given folder to the given file, both within the vault, using forward slashes.
+fn relative_link(folder: &path::Path, file: &path::Path) -> String {
+    let folder = folder.components().collect::<Vec<_>>();
+    let file = file.components().collect::<Vec<_>>();
+    let common = folder.iter().zip(&file).take_while(|(a, b)| a == b).count();
+
+    std::iter::repeat_n(String::from(".."), folder.len() - common)
+        .chain(
+            file[common..]
+                .iter()
+                .map(|component| component.as_os_str().to_string_lossy().to_string()),
+        )
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Decodes percent-encoded characters such as `%20` in the given link target.
+fn percent_decode(target: &str) -> String {
+    let bytes = target.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = bytes
+            .get(i + 1..i + 3)
+            .and_then(|hex| std::str::from_utf8(hex).ok())
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match (bytes[i], hex) {
+            (b'%', Some(byte)) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            (byte, _) => {
+                decoded.push(byte);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_import() {
+        let tmp = testdir::testdir!().canonicalize().unwrap();
+        let vault = tmp.join("vault");
+        let outside = tmp.join("Downloads");
+        std::fs::create_dir_all(vault.join("trips")).unwrap();
+        std::fs::create_dir_all(outside.join("other")).unwrap();
+        std::fs::write(outside.join("map.png"), "map").unwrap();
+        std::fs::write(outside.join("other").join("map.png"), "other map").unwrap();
+        std::fs::write(vault.join("cover.png"), "cover").unwrap();
+
+        let note = vault.join("trips").join("Rome.md");
+        std::fs::write(
+            &note,
+            format!(
+                "# Rome\n![Map]({}) ![Other](../../Downloads/other/map.png \"Other\")\n[Cover]({}) [Here](../cover.png) [Gone](/nowhere/gone.png) [Web](https://rome.it)\n",
+                outside.join("map.png").display(),
+                vault.join("cover.png").display(),
+            ),
+        )
+        .unwrap();
+
+        let importer = Importer::new(&crate::Config::default(), &vault);
+        let plans = importer.plan(&[(String::from("rome"), note.clone())]);
+        assert_eq!(plans.len(), 1);
+        assert_eq!(plans[0].links.len(), 3);
+        assert_eq!(plans[0].copies(), 2);
+
+        let manager = super::super::FileManager::new(&crate::Config::default(), vault.clone());
+        manager.apply_import(&plans[0]).unwrap();
+
+        assert_eq!(
+            std::fs::read_to_string(&note).unwrap(),
+            "# Rome\n![Map](../assets/map.png) ![Other](<../assets/map 2.png> \"Other\")\n[Cover](../cover.png) [Here](../cover.png) [Gone](/nowhere/gone.png) [Web](https://rome.it)\n"
+        );
+        assert_eq!(
+            std::fs::read_to_string(vault.join("assets").join("map 2.png")).unwrap(),
+            "other map"
+        );
+        // the original files are kept
+        assert!(outside.join("map.png").exists());
+
+        // links within the vault need no rewriting
+        assert!(importer
+            .plan(&[(String::from("rome"), note.clone())])
+            .is_empty());
+    }
+}
@@ -0,0 +1,270 @@
+use std::{collections::HashMap, fmt, fs, path, time};
+
+use itertools::Itertools;
+use sha2::Digest;
+
+use crate::{error, io};
+
+/// The file the checksums of the notes are stored in, relative to the vault.
+const STATE_FILE: &str = ".rucola-checksums.json";
+
+/// The stored checksum of a single note.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+struct Checksum {
+    /// The SHA-256 hash of the content of the note.
+    hash: String,
+    /// The time of the last modification of the note file, in milliseconds since the unix epoch.
+    modified: u64,
+    /// The size of the note file in bytes.
+    size: u64,
+}
+
+/// The stored checksums of all notes of a vault, by their paths relative to the vault.
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+struct Checksums {
+    notes: HashMap<path::PathBuf, Checksum>,
+}
+
+impl io::VersionedState for Checksums {
+    const VERSION: u32 = 1;
+}
+
+/// How a note differs from its stored checksum.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum IntegrityIssue {
+    /// The content changed, but the modification time and size did not, which hints at silent corruption.
+    Corrupted,
+    /// The note was changed since its checksum was recorded, e.g. by another program while rucola was not running.
+    Changed,
+    /// The note was deleted or moved since its checksum was recorded.
+    Missing,
+    /// The note was created since the checksums were recorded.
+    Added,
+}
+
+impl fmt::Display for IntegrityIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // padded, so the issues can be aligned
+        f.pad(match self {
+            IntegrityIssue::Corrupted => "corrupted",
+            IntegrityIssue::Changed => "changed",
+            IntegrityIssue::Missing => "missing",
+            IntegrityIssue::Added => "added",
+        })
+    }
+}
+
+/// The outcome of an integrity check.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct IntegrityReport {
+    /// The number of notes that were checked.
+    pub checked: usize,
+    /// Wether there were stored checksums to compare with.
+    pub recorded: bool,
+    /// The notes that differ from their stored checksums, by their path relative to the vault, most severe issues first.
+    pub issues: Vec<(path::PathBuf, IntegrityIssue)>,
+}
+
+/// Records checksums of all notes of a vault, to find notes that were corrupted or changed unexpectedly since.
+pub struct IntegrityChecker {
+    /// The tracker listing the note files of the vault.
+    tracker: io::FileTracker,
+    /// The file the checksums are stored in.
+    state_file: io::StateFile<Checksums>,
+    /// Path to the vault.
+    vault_path: path::PathBuf,
+}
+
+impl IntegrityChecker {
+    /// Creates a new integrity checker for the given vault. The stored checksums are only read when checking or recording.
+    pub fn new(config: &crate::Config, vault_path: path::PathBuf) -> error::Result<Self> {
+        Ok(Self {
+            state_file: io::StateFile::new(vault_path.join(STATE_FILE)),
+            tracker: io::FileTracker::new(config, vault_path.clone())?,
+            vault_path,
+        })
+    }
+
+    /// Compares all notes of the vault with their stored checksums.
+    /// Returns an error if the stored checksums could not be read, in which case all notes count as added.
+    pub fn check(&self) -> error::Result<IntegrityReport> {
+        let (stored, error) = self.state_file.load();
+        if let Some(e) = error {
+            return Err(e);
+        }
+
+        let mut report = IntegrityReport {
+            recorded: self.state_file_exists(),
+            ..Default::default()
+        };
+        let mut remaining = stored.notes;
+
+        for (relative, path) in self.notes() {
+            report.checked += 1;
+            let issue = match remaining.remove(&relative) {
+                Some(stored) => {
+                    let current = checksum(&path)?;
+                    if current.hash == stored.hash {
+                        None
+                    } else if current.modified == stored.modified && current.size == stored.size {
+                        Some(IntegrityIssue::Corrupted)
+                    } else {
+                        Some(IntegrityIssue::Changed)
+                    }
+                }
+                None => Some(IntegrityIssue::Added),
+            };
+            report.issues.extend(issue.map(|issue| (relative, issue)));
+        }
+
+        report.issues.extend(
+            remaining
+                .into_keys()
+                .map(|relative| (relative, IntegrityIssue::Missing)),
+        );
+        report
+            .issues
+            .sort_by(|(path1, issue1), (path2, issue2)| issue1.cmp(issue2).then(path1.cmp(path2)));
+
+        Ok(report)
+    }
+
+    /// Records the checksums of all notes of the vault, so later checks compare with their current state.
+    /// If `all` is not set, only notes whose modification time or size changed are hashed again, so corruption found before keeps being reported.
+    pub fn record(&self, all: bool) -> error::Result<()> {
+        let (mut stored, _error) = self.state_file.load();
+
+        let notes = self
+            .notes()
+            .map(|(relative, path)| {
+                let checksum = match stored.notes.remove(&relative) {
+                    Some(stored) if !all && metadata(&path)? == (stored.modified, stored.size) => {
+                        stored
+                    }
+                    _ => checksum(&path)?,
+                };
+                Ok((relative, checksum))
+            })
+            .collect::<error::Result<HashMap<_, _>>>()?;
+
+        self.state_file.save(&Checksums { notes })
+    }
+
+    /// Lists the note files of the vault, by their path relative to the vault and their full path.
+    fn notes(&self) -> impl Iterator<Item = (path::PathBuf, path::PathBuf)> + '_ {
+        self.tracker
+            .get_walker()
+            .flatten()
+            .filter(|entry| entry.file_type().is_some_and(|ft| ft.is_file()))
+            .filter_map(|entry| {
+                let relative = entry.path().strip_prefix(&self.vault_path).ok()?.to_owned();
+                Some((relative, entry.into_path()))
+            })
+            .sorted()
+    }
+
+    /// Wether checksums have been recorded for this vault before.
+    fn state_file_exists(&self) -> bool {
+        self.vault_path.join(STATE_FILE).exists()
+    }
+}
+
+/// Returns the modification time (in milliseconds since the unix epoch) and size of the file at the given path.
+fn metadata(path: &path::Path) -> error::Result<(u64, u64)> {
+    let metadata = fs::metadata(path)?;
+    let modified = metadata
+        .modified()?
+        .duration_since(time::UNIX_EPOCH)
+        .map(|modified| modified.as_millis() as u64)
+        .unwrap_or_default();
+    Ok((modified, metadata.len()))
+}
+
+/// Calculates the checksum of the file at the given path.
+fn checksum(path: &path::Path) -> error::Result<Checksum> {
+    let (modified, size) = metadata(path)?;
+    let hash = sha2::Sha256::digest(fs::read(path)?)
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect();
+    Ok(Checksum {
+        hash,
+        modified,
+        size,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_integrity() {
+        let vault = testdir::testdir!().join("vault");
+        std::fs::create_dir_all(&vault).unwrap();
+        for name in ["Atlas", "Chart", "Group", "Map"] {
+            std::fs::write(vault.join(format!("{}.md", name)), format!("# {}", name)).unwrap();
+        }
+
+        let checker = IntegrityChecker::new(&Default::default(), vault.clone()).unwrap();
+
+        // without recorded checksums, all notes are new
+        let report = checker.check().unwrap();
+        assert!(!report.recorded);
+        assert_eq!(report.issues.len(), 4);
+
+        checker.record(false).unwrap();
+        let report = checker.check().unwrap();
+        assert!(report.recorded);
+        assert_eq!(report.checked, 4);
+        assert!(report.issues.is_empty());
+
+        let modified = |name: &str| {
+            std::fs::metadata(vault.join(name))
+                .unwrap()
+                .modified()
+                .unwrap()
+        };
+        let set_modified = |name: &str, time: time::SystemTime| {
+            std::fs::File::options()
+                .write(true)
+                .open(vault.join(name))
+                .unwrap()
+                .set_modified(time)
+                .unwrap();
+        };
+
+        // a regular change
+        let chart_modified = modified("Chart.md");
+        std::fs::write(vault.join("Chart.md"), "# Chart\nWith text.").unwrap();
+        set_modified("Chart.md", chart_modified + time::Duration::from_secs(10));
+
+        // a silent change of the content, keeping time and size
+        let atlas_modified = modified("Atlas.md");
+        std::fs::write(vault.join("Atlas.md"), "# Atlaz").unwrap();
+        set_modified("Atlas.md", atlas_modified);
+
+        std::fs::remove_file(vault.join("Group.md")).unwrap();
+        std::fs::write(vault.join("Shelf.md"), "# Shelf").unwrap();
+
+        let report = checker.check().unwrap();
+        assert_eq!(
+            report.issues,
+            vec![
+                (path::PathBuf::from("Atlas.md"), IntegrityIssue::Corrupted),
+                (path::PathBuf::from("Chart.md"), IntegrityIssue::Changed),
+                (path::PathBuf::from("Group.md"), IntegrityIssue::Missing),
+                (path::PathBuf::from("Shelf.md"), IntegrityIssue::Added),
+            ]
+        );
+
+        // recording keeps reporting corruption until all notes are hashed again
+        checker.record(false).unwrap();
+        assert_eq!(
+            checker.check().unwrap().issues,
+            vec![(path::PathBuf::from("Atlas.md"), IntegrityIssue::Corrupted)]
+        );
+        checker.record(true).unwrap();
+        assert!(checker.check().unwrap().issues.is_empty());
+    }
+}
@@ -0,0 +1,218 @@
+use std::{collections::HashMap, path, time};
+
+use fuzzy_matcher::FuzzyMatcher;
+use itertools::Itertools;
+
+use crate::{data, error, io};
+
+/// The file the completion cache is stored in, relative to the vault.
+pub(super) const STATE_FILE: &str = ".rucola-completions.json";
+
+/// A note links can be completed to.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct Completion {
+    /// The id of the note, which links to it can use.
+    pub id: String,
+    /// The file name of the note, without extension.
+    pub name: String,
+    /// The title the note is displayed with.
+    pub title: String,
+    /// The time of the last modification of the note file, in milliseconds since the unix epoch.
+    pub modified: u64,
+}
+
+/// The stored completions of all notes of a vault, by the path of their file.
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+struct CompletionCache {
+    notes: HashMap<path::PathBuf, Completion>,
+}
+
+impl io::VersionedState for CompletionCache {
+    const VERSION: u32 = 1;
+}
+
+/// Completes links for external editors without indexing the whole vault.
+/// The names and titles of all notes are cached in the vault, so only notes modified since the last completion have to be parsed again.
+pub struct LinkCompleter {
+    /// The tracker listing the note files of the vault.
+    tracker: io::FileTracker,
+    /// The options to parse changed notes with.
+    options: data::ParseOptions,
+    /// The file the cache is stored in.
+    state_file: io::StateFile<CompletionCache>,
+    /// The cached completions.
+    cache: CompletionCache,
+}
+
+impl LinkCompleter {
+    /// Creates a new link completer for the given vault. The cache is only read once it is refreshed.
+    pub fn new(config: &crate::Config, vault_path: path::PathBuf) -> error::Result<Self> {
+        Ok(Self {
+            state_file: io::StateFile::new(vault_path.join(STATE_FILE)),
+            tracker: io::FileTracker::new(config, vault_path)?,
+            options: data::ParseOptions::new(config)?,
+            cache: CompletionCache::default(),
+        })
+    }
+
+    /// Loads the cache and brings it up to date with the vault: Notes modified since they were cached are parsed again, deleted notes are dropped.
+    /// The cache is stored again if anything changed.
+    /// Returns the errors of notes that could not be parsed and of the cache itself, which never prevent completion.
+    pub fn refresh(&mut self) -> Vec<error::RucolaError> {
+        let (mut cached, error) = self.state_file.load();
+        let mut errors = error.into_iter().collect_vec();
+
+        let mut notes = HashMap::new();
+        let mut changed = false;
+        for entry in self.tracker.get_walker().flatten() {
+            let Some(modified) = entry
+                .metadata()
+                .ok()
+                .filter(|metadata| metadata.is_file())
+                .and_then(|metadata| metadata.modified().ok())
+                .and_then(|modified| modified.duration_since(time::UNIX_EPOCH).ok())
+                .map(|modified| modified.as_millis() as u64)
+            else {
+                continue;
+            };
+            let path = entry.into_path();
+
+            let completion = match cached.notes.remove(&path) {
+                Some(completion) if completion.modified == modified => completion,
+                _ => match data::Note::from_path(&path, &self.options) {
+                    Ok(note) => {
+                        changed = true;
+                        Completion {
+                            id: data::name_to_id(&note.name),
+                            name: note.name,
+                            title: note.display_name,
+                            modified,
+                        }
+                    }
+                    Err(e) => {
+                        errors.push(e);
+                        continue;
+                    }
+                },
+            };
+            notes.insert(path, completion);
+        }
+
+        self.cache.notes = notes;
+
+        // Notes left in the old cache no longer exist.
+        if changed || !cached.notes.is_empty() {
+            if let Err(e) = self.state_file.save(&self.cache) {
+                errors.push(e);
+            }
+        }
+
+        errors
+    }
+
+    /// Returns the number of cached completions of notes that no longer exist, without parsing any notes.
+    pub fn stale_entries(&self) -> usize {
+        let (cached, _error) = self.state_file.load();
+        cached.notes.keys().filter(|path| !path.exists()).count()
+    }
+
+    /// Drops the cached completions of notes that no longer exist without parsing any notes, and stores the cache if anything was dropped.
+    /// Returns the number of dropped completions.
+    pub fn prune(&mut self) -> error::Result<usize> {
+        let (mut cached, error) = self.state_file.load();
+        if let Some(e) = error {
+            return Err(e);
+        }
+
+        let before = cached.notes.len();
+        cached.notes.retain(|path, _| path.exists());
+        let dropped = before - cached.notes.len();
+
+        if dropped > 0 {
+            self.state_file.save(&cached)?;
+        }
+        self.cache = cached;
+
+        Ok(dropped)
+    }
+
+    /// Returns the notes whose name or title fuzzily matches the given prefix, best match first.
+    /// Equally good matches are sorted by their last modification, most recent first.
+    /// An empty prefix matches all notes.
+    pub fn complete(&self, prefix: &str) -> Vec<&Completion> {
+        let matcher = fuzzy_matcher::skim::SkimMatcherV2::default();
+
+        self.cache
+            .notes
+            .values()
+            .flat_map(|completion| {
+                if prefix.is_empty() {
+                    return Some((0, completion));
+                }
+                matcher
+                    .fuzzy_match(&completion.name, prefix)
+                    .max(matcher.fuzzy_match(&completion.title, prefix))
+                    .map(|score| (score, completion))
+            })
+            .sorted_by(|(score1, c1), (score2, c2)| {
+                score2
+                    .cmp(score1)
+                    .then(c2.modified.cmp(&c1.modified))
+                    .then(c1.id.cmp(&c2.id))
+            })
+            .map(|(_score, completion)| completion)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_complete() {
+        let vault = testdir::testdir!().join("vault");
+        std::fs::create_dir_all(&vault).unwrap();
+        std::fs::write(vault.join("Smooth Map.md"), "# Smooth Map").unwrap();
+        std::fs::write(
+            vault.join("Atlas.md"),
+            "---\ntitle: Smooth Atlas\n---\nCharts.",
+        )
+        .unwrap();
+        std::fs::write(vault.join("Group.md"), "# Group").unwrap();
+
+        let mut completer = LinkCompleter::new(&Default::default(), vault.clone()).unwrap();
+        assert!(completer.refresh().is_empty());
+        assert!(vault.join(STATE_FILE).exists());
+
+        let ids = |completer: &LinkCompleter, prefix: &str| {
+            completer
+                .complete(prefix)
+                .into_iter()
+                .map(|completion| completion.id.clone())
+                .collect_vec()
+        };
+
+        // titles are matched as well as names
+        let mut smooth = ids(&completer, "smoo");
+        smooth.sort();
+        assert_eq!(smooth, vec!["atlas", "smooth-map"]);
+        assert_eq!(ids(&completer, "grp"), vec!["group"]);
+        assert_eq!(ids(&completer, "").len(), 3);
+
+        // deleted notes disappear from the cache
+        std::fs::remove_file(vault.join("Group.md")).unwrap();
+        let mut completer = LinkCompleter::new(&Default::default(), vault.clone()).unwrap();
+        assert!(completer.refresh().is_empty());
+        assert!(ids(&completer, "grp").is_empty());
+        assert_eq!(ids(&completer, "").len(), 2);
+
+        // pruning only drops deleted notes, without parsing changed ones
+        std::fs::remove_file(vault.join("Atlas.md")).unwrap();
+        let mut completer = LinkCompleter::new(&Default::default(), vault.clone()).unwrap();
+        assert_eq!(completer.stale_entries(), 1);
+        assert_eq!(completer.prune().unwrap(), 1);
+        assert_eq!(completer.stale_entries(), 0);
+        assert_eq!(ids(&completer, ""), vec!["smooth-map"]);
+    }
+}
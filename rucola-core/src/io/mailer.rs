@@ -0,0 +1,603 @@
+use std::{
+    fs,
+    io::{Read, Write},
+    net, path,
+    sync::{mpsc, Arc},
+    time,
+};
+
+use base64::Engine;
+
+use crate::{data, error, io};
+
+/// Environment variable the password for the SMTP server is read from.
+const PASSWORD_VARIABLE: &str = "RUCOLA_SMTP_PASSWORD";
+
+/// How long to wait for the SMTP server to respond.
+const TIMEOUT: time::Duration = time::Duration::from_secs(30);
+
+/// How the connection to the SMTP server is secured.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum SmtpSecurity {
+    /// Connect without encryption, then upgrade the connection with the STARTTLS command (usually port 587).
+    #[default]
+    StartTls,
+    /// Encrypt the connection right away (usually port 465).
+    Tls,
+    /// Never encrypt the connection, e.g. for a relay on the same machine.
+    None,
+}
+
+/// Where mails are sent through.
+#[derive(Debug, Clone)]
+enum Backend {
+    /// A sendmail-style command receiving the message on stdin.
+    Command(Vec<String>),
+    /// An SMTP server.
+    Smtp {
+        /// The host name of the server.
+        host: String,
+        /// The port of the server.
+        port: u16,
+        /// How the connection is secured.
+        security: SmtpSecurity,
+        /// User name and password to authenticate with, if any.
+        credentials: Option<(String, String)>,
+        /// Wether the credentials may be sent over an unencrypted connection.
+        allow_insecure_auth: bool,
+    },
+}
+
+/// A note prepared to be sent by mail: Its HTML file as the body and the note file itself as an attachment.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mail {
+    /// The sender, such as `Jane Doe <jane@example.com>`.
+    from: String,
+    /// The addresses of the recipients.
+    to: Vec<String>,
+    /// The subject, the title of the note.
+    subject: String,
+    /// The rendered HTML of the note, shown as the body.
+    html: String,
+    /// The file name and content of the attached note file.
+    attachment: (String, Vec<u8>),
+}
+
+impl Mail {
+    /// Checks that the sender and all recipients are valid mailboxes, see [check_mailbox].
+    fn check(&self) -> error::Result<()> {
+        std::iter::once(&self.from)
+            .chain(&self.to)
+            .try_for_each(|mailbox| check_mailbox(mailbox))
+    }
+
+    /// Formats the mail as a MIME message with the given date and an id derived from it, with lines ending in CRLF.
+    fn message(&self, date: chrono::DateTime<chrono::Utc>) -> String {
+        let stamp = date.timestamp_nanos_opt().unwrap_or_default();
+        let boundary = format!("rucola-{:x}", stamp);
+        let (file_name, content) = &self.attachment;
+
+        let mut lines = vec![
+            format!("From: {}", self.from),
+            format!("To: {}", self.to.join(", ")),
+            format!("Subject: {}", encode_header(&self.subject)),
+            format!("Date: {}", date.to_rfc2822()),
+            format!("Message-ID: <{:x}.{}@rucola>", stamp, std::process::id()),
+            String::from("MIME-Version: 1.0"),
+            format!("Content-Type: multipart/mixed; boundary=\"{}\"", boundary),
+            String::new(),
+            format!("--{}", boundary),
+            String::from("Content-Type: text/html; charset=utf-8"),
+            String::from("Content-Transfer-Encoding: base64"),
+            String::new(),
+        ];
+        lines.extend(encode_body(self.html.as_bytes()));
+        lines.extend([
+            format!("--{}", boundary),
+            format!(
+                "Content-Type: {}; charset=utf-8; name=\"{}\"",
+                content_type(file_name),
+                encode_header(file_name)
+            ),
+            format!(
+                "Content-Disposition: attachment; filename=\"{}\"",
+                encode_header(file_name)
+            ),
+            String::from("Content-Transfer-Encoding: base64"),
+            String::new(),
+        ]);
+        lines.extend(encode_body(content));
+        lines.push(format!("--{}--", boundary));
+
+        lines.join("\r\n") + "\r\n"
+    }
+}
+
+/// Sends single notes by mail through a sendmail-style command or an SMTP server, to share them with people without access to the vault.
+#[derive(Debug, Clone)]
+pub struct Mailer {
+    /// The command or server to send mails through.
+    backend: Backend,
+    /// The sender of all mails.
+    from: String,
+}
+
+impl Mailer {
+    /// Creates a new mailer, if a sender and a command or a server are configured. The command takes precedence.
+    pub fn new(config: &crate::Config) -> Option<Self> {
+        let backend = match (&config.mail_command, &config.smtp_server) {
+            (Some(command), _) if !command.is_empty() => Backend::Command(command.clone()),
+            (_, Some(host)) => Backend::Smtp {
+                host: host.clone(),
+                port: config.smtp_port,
+                security: config.smtp_security,
+                credentials: config
+                    .smtp_user
+                    .clone()
+                    .map(|user| (user, std::env::var(PASSWORD_VARIABLE).unwrap_or_default())),
+                allow_insecure_auth: config.smtp_allow_insecure_auth,
+            },
+            _ => return None,
+        };
+
+        Some(Self {
+            backend,
+            from: config.mail_from.clone()?,
+        })
+    }
+
+    /// Prepares a mail of the given note to the given recipients, building its HTML file first.
+    pub fn compose(
+        &self,
+        note: &data::Note,
+        builder: &io::HtmlBuilder,
+        index: &data::NoteIndex,
+        to: Vec<String>,
+    ) -> error::Result<Mail> {
+        if to.is_empty() {
+            return Err(error::RucolaError::MailError(String::from(
+                "There are no recipients.",
+            )));
+        }
+
+        builder.create_html(note, Some(index), true)?;
+        let html = fs::read_to_string(super::html_builder::name_to_html_path(
+            &note.name,
+            builder.vault_path(),
+        ))?;
+
+        let file_name = note
+            .path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .ok_or_else(|| error::RucolaError::NoteNameCannotBeRead(note.path.clone()))?;
+
+        Ok(Mail {
+            from: self.from.clone(),
+            to,
+            subject: note.display_name.clone(),
+            html,
+            attachment: (file_name, fs::read(&note.path)?),
+        })
+    }
+
+    /// Sends the given mail on a separate thread, sending the outcome through the returned channel once done.
+    pub fn send_in_background(&self, mail: Mail) -> mpsc::Receiver<Result<(), String>> {
+        let (sender, receiver) = mpsc::channel();
+        let mailer = self.clone();
+        std::thread::spawn(move || {
+            let _ = sender.send(mailer.send(&mail).map_err(|e| e.to_string()));
+            super::wake();
+        });
+        receiver
+    }
+
+    /// Sends the given mail.
+    pub fn send(&self, mail: &Mail) -> error::Result<()> {
+        mail.check()?;
+        let message = mail.message(chrono::Utc::now());
+        match &self.backend {
+            Backend::Command(command) => {
+                // `%r` stands for the addresses of all recipients
+                let command = command
+                    .iter()
+                    .flat_map(|arg| match arg.as_str() {
+                        "%r" => mail.to.iter().map(|to| address(to).to_owned()).collect(),
+                        _ => vec![arg.clone()],
+                    })
+                    .collect::<Vec<_>>();
+                super::summarizer::run_command(&command, None, &message)?;
+                Ok(())
+            }
+            Backend::Smtp {
+                host,
+                port,
+                security,
+                credentials,
+                allow_insecure_auth,
+            } => {
+                if credentials.is_some() && *security == SmtpSecurity::None && !allow_insecure_auth
+                {
+                    return Err(error::RucolaError::MailError(String::from(
+                        "Refusing to send the SMTP credentials without encryption. Use another smtp_security or set smtp_allow_insecure_auth.",
+                    )));
+                }
+                send_smtp(host, *port, *security, credentials.as_ref(), mail, &message).map_err(
+                    |e| match e {
+                        error::RucolaError::IO(e) => error::RucolaError::MailError(e.to_string()),
+                        e => e,
+                    },
+                )
+            }
+        }
+    }
+}
+
+/// A connection to an SMTP server, encrypted or not.
+trait Stream: Read + Write {}
+
+impl<T: Read + Write> Stream for T {}
+
+/// Sends the given message of the given mail through the SMTP server at the given host and port.
+fn send_smtp(
+    host: &str,
+    port: u16,
+    security: SmtpSecurity,
+    credentials: Option<&(String, String)>,
+    mail: &Mail,
+    message: &str,
+) -> error::Result<()> {
+    let tcp = net::TcpStream::connect((host, port))?;
+    tcp.set_read_timeout(Some(TIMEOUT))?;
+    tcp.set_write_timeout(Some(TIMEOUT))?;
+
+    let mut stream: Box<dyn Stream> = match security {
+        SmtpSecurity::Tls => Box::new(encrypt(host, tcp)?),
+        SmtpSecurity::StartTls | SmtpSecurity::None => Box::new(tcp),
+    };
+
+    expect(&mut stream, 220)?;
+    command(&mut stream, "EHLO localhost", 250)?;
+
+    if security == SmtpSecurity::StartTls {
+        command(&mut stream, "STARTTLS", 220)?;
+        stream = Box::new(encrypt(host, stream)?);
+        command(&mut stream, "EHLO localhost", 250)?;
+    }
+
+    if let Some((user, password)) = credentials {
+        let token =
+            base64::engine::general_purpose::STANDARD.encode(format!("\0{}\0{}", user, password));
+        command(&mut stream, &format!("AUTH PLAIN {}", token), 235)?;
+    }
+
+    command(
+        &mut stream,
+        &format!("MAIL FROM:<{}>", address(&mail.from)),
+        250,
+    )?;
+    for to in &mail.to {
+        command(&mut stream, &format!("RCPT TO:<{}>", address(to)), 250)?;
+    }
+    command(&mut stream, "DATA", 354)?;
+
+    // lines starting with a dot are escaped by another one
+    for line in message.split_inclusive("\r\n") {
+        if line.starts_with('.') {
+            stream.write_all(b".")?;
+        }
+        stream.write_all(line.as_bytes())?;
+    }
+    command(&mut stream, ".", 250)?;
+
+    // the mail is sent already, so a failing goodbye does not matter
+    let _ = command(&mut stream, "QUIT", 221);
+    Ok(())
+}
+
+/// Encrypts the given connection to the given host with TLS, verifying the certificate of the host.
+fn encrypt<T: Read + Write>(
+    host: &str,
+    stream: T,
+) -> error::Result<rustls::StreamOwned<rustls::ClientConnection, T>> {
+    let tls_error = |e: rustls::Error| error::RucolaError::MailError(e.to_string());
+
+    let config = rustls::ClientConfig::builder_with_provider(Arc::new(
+        rustls::crypto::ring::default_provider(),
+    ))
+    .with_safe_default_protocol_versions()
+    .map_err(tls_error)?
+    .with_root_certificates(rustls::RootCertStore::from_iter(
+        webpki_roots::TLS_SERVER_ROOTS.iter().cloned(),
+    ))
+    .with_no_client_auth();
+
+    let name = rustls::pki_types::ServerName::try_from(host.to_owned())
+        .map_err(|e| error::RucolaError::MailError(e.to_string()))?;
+    let connection = rustls::ClientConnection::new(Arc::new(config), name).map_err(tls_error)?;
+
+    Ok(rustls::StreamOwned::new(connection, stream))
+}
+
+/// Sends the given command to the SMTP server and checks that it replies with the given code.
+fn command<S: Read + Write + ?Sized>(stream: &mut S, line: &str, code: u16) -> error::Result<()> {
+    stream.write_all(format!("{}\r\n", line).as_bytes())?;
+    stream.flush()?;
+    expect(stream, code)
+}
+
+/// Reads the next reply of the SMTP server, which may span several lines, and checks that it has the given code.
+fn expect<S: Read + ?Sized>(stream: &mut S, code: u16) -> error::Result<()> {
+    loop {
+        let line = read_line(stream)?;
+        // all but the last line of a reply have a dash after the code
+        if line.get(3..4) == Some("-") {
+            continue;
+        }
+        return match line.get(..3).and_then(|reply| reply.parse::<u16>().ok()) {
+            Some(reply) if reply == code => Ok(()),
+            _ => Err(error::RucolaError::MailError(format!(
+                "The server replied: {}",
+                line
+            ))),
+        };
+    }
+}
+
+/// Reads a single line from the SMTP server, without the line break.
+/// Reads byte by byte, so nothing is read ahead of a reply before the connection is encrypted.
+fn read_line<S: Read + ?Sized>(stream: &mut S) -> error::Result<String> {
+    let mut line = Vec::new();
+    let mut byte = [0];
+    while !line.ends_with(b"\r\n") {
+        if stream.read(&mut byte)? == 0 {
+            return Err(error::RucolaError::MailError(String::from(
+                "The server closed the connection.",
+            )));
+        }
+        line.push(byte[0]);
+    }
+    Ok(String::from_utf8_lossy(&line).trim_end().to_owned())
+}
+
+/// Extracts the bare address from a mailbox such as `Jane Doe <jane@example.com>`.
+fn address(mailbox: &str) -> &str {
+    mailbox
+        .rsplit_once('<')
+        .and_then(|(_name, rest)| rest.split_once('>'))
+        .map(|(address, _rest)| address)
+        .unwrap_or(mailbox)
+        .trim()
+}
+
+/// Checks that the given mailbox can be used in the headers of a mail and the commands sent to an SMTP server.
+/// Line breaks and other control characters would start further headers or commands, so they are rejected, as are addresses containing angle brackets or whitespace.
+fn check_mailbox(mailbox: &str) -> error::Result<()> {
+    let address = address(mailbox);
+
+    if mailbox.chars().any(char::is_control)
+        || mailbox.matches(['<', '>']).count() > 2
+        || address.is_empty()
+        || address.contains(['<', '>'])
+        || address.contains(char::is_whitespace)
+    {
+        return Err(error::RucolaError::MailError(format!(
+            "Invalid mail address: {:?}",
+            mailbox
+        )));
+    }
+    Ok(())
+}
+
+/// Encodes text for a header, as an encoded word if it is not plain ASCII.
+fn encode_header(text: &str) -> String {
+    if text
+        .chars()
+        .all(|c| c.is_ascii() && !c.is_ascii_control() && c != '"')
+    {
+        text.to_owned()
+    } else {
+        format!(
+            "=?utf-8?B?{}?=",
+            base64::engine::general_purpose::STANDARD.encode(text)
+        )
+    }
+}
+
+/// Encodes content in base64, split into lines of 76 characters.
+fn encode_body(content: &[u8]) -> Vec<String> {
+    base64::engine::general_purpose::STANDARD
+        .encode(content)
+        .as_bytes()
+        .chunks(76)
+        .map(|chunk| String::from_utf8_lossy(chunk).to_string())
+        .collect()
+}
+
+/// Returns the content type of the note file with the given name.
+fn content_type(file_name: &str) -> &'static str {
+    match path::Path::new(file_name)
+        .extension()
+        .and_then(|extension| extension.to_str())
+    {
+        Some("md" | "markdown") => "text/markdown",
+        _ => "text/plain",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A mail of a short note to two recipients.
+    fn mail() -> Mail {
+        Mail {
+            from: String::from("Jane Doe <jane@example.com>"),
+            to: vec![
+                String::from("max@example.com"),
+                String::from("Erika <erika@example.com>"),
+            ],
+            subject: String::from("Über Atlanten"),
+            html: String::from("<h1>Atlas</h1>\n.\n"),
+            attachment: (String::from("Atlas.md"), b"# Atlas\n".to_vec()),
+        }
+    }
+
+    #[test]
+    fn test_message() {
+        let date = chrono::DateTime::from_timestamp(1_700_000_000, 0).unwrap();
+        let message = mail().message(date);
+
+        assert!(message.starts_with(
+            "From: Jane Doe <jane@example.com>\r\nTo: max@example.com, Erika <erika@example.com>\r\nSubject: =?utf-8?B?w5xiZXIgQXRsYW50ZW4=?=\r\nDate: Tue, 14 Nov 2023 22:13:20 +0000\r\n"
+        ));
+        assert!(message.contains("Content-Type: text/markdown; charset=utf-8; name=\"Atlas.md\""));
+        assert!(message.contains("Content-Disposition: attachment; filename=\"Atlas.md\""));
+        // the bodies are encoded, so no line of the message is too long or starts with a dot
+        assert!(message
+            .contains(&base64::engine::general_purpose::STANDARD.encode("<h1>Atlas</h1>\n.\n")));
+        assert!(message.contains(&base64::engine::general_purpose::STANDARD.encode("# Atlas\n")));
+        assert!(message.ends_with("--\r\n"));
+        assert!(message.split("\r\n").all(|line| line.len() <= 998));
+
+        assert_eq!(address("Erika <erika@example.com>"), "erika@example.com");
+        assert_eq!(address(" max@example.com "), "max@example.com");
+        assert_eq!(encode_header("Atlas.md"), "Atlas.md");
+    }
+
+    #[test]
+    fn test_smtp() {
+        // A server accepting a single mail, replying to every command and recording them.
+        let listener = net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let server = std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            stream.write_all(b"220 localhost ready\r\n").unwrap();
+            let mut commands = Vec::new();
+            let mut in_data = false;
+            loop {
+                let line = read_line(&mut stream).unwrap();
+                let reply: &[u8] = if in_data {
+                    if line != "." {
+                        continue;
+                    }
+                    in_data = false;
+                    b"250 queued\r\n"
+                } else if line.starts_with("EHLO") {
+                    b"250-localhost\r\n250 AUTH PLAIN\r\n"
+                } else if line.starts_with("AUTH") {
+                    b"235 ok\r\n"
+                } else if line == "DATA" {
+                    in_data = true;
+                    b"354 go ahead\r\n"
+                } else if line == "QUIT" {
+                    stream.write_all(b"221 bye\r\n").unwrap();
+                    commands.push(line);
+                    return commands;
+                } else {
+                    b"250 ok\r\n"
+                };
+                commands.push(line);
+                stream.write_all(reply).unwrap();
+            }
+        });
+
+        let mailer = Mailer {
+            backend: Backend::Smtp {
+                host: String::from("127.0.0.1"),
+                port,
+                security: SmtpSecurity::None,
+                credentials: Some((String::from("jane"), String::from("secret"))),
+                allow_insecure_auth: true,
+            },
+            from: String::from("Jane Doe <jane@example.com>"),
+        };
+        mailer.send(&mail()).unwrap();
+
+        assert_eq!(
+            server.join().unwrap(),
+            vec![
+                "EHLO localhost",
+                "AUTH PLAIN AGphbmUAc2VjcmV0",
+                "MAIL FROM:<jane@example.com>",
+                "RCPT TO:<max@example.com>",
+                "RCPT TO:<erika@example.com>",
+                "DATA",
+                ".",
+                "QUIT",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_smtp_refused() {
+        // Nothing listens on this port, so the mails are refused before connecting to a server.
+        let mut mailer = Mailer {
+            backend: Backend::Smtp {
+                host: String::from("127.0.0.1"),
+                port: 9,
+                security: SmtpSecurity::None,
+                credentials: Some((String::from("jane"), String::from("secret"))),
+                allow_insecure_auth: false,
+            },
+            from: String::from("Jane Doe <jane@example.com>"),
+        };
+
+        // credentials are not sent in cleartext
+        assert!(matches!(
+            mailer.send(&mail()),
+            Err(error::RucolaError::MailError(message)) if message.contains("smtp_allow_insecure_auth")
+        ));
+
+        mailer.backend = Backend::Command(vec![String::from("false")]);
+
+        // line breaks in addresses would inject further commands or headers
+        for mailbox in [
+            "max@example.com>\r\nRCPT TO:<eve@example.com",
+            "Max\r\nBcc: eve@example.com <max@example.com>",
+            "max@example.com\nDATA",
+            "<max<@example.com>",
+            "max @example.com",
+            "",
+        ] {
+            let mut mail = mail();
+            mail.to.push(mailbox.to_owned());
+            assert!(matches!(
+                mailer.send(&mail),
+                Err(error::RucolaError::MailError(message)) if message.starts_with("Invalid mail address")
+            ));
+        }
+
+        let mut mail = mail();
+        mail.from = String::from("jane@example.com\r\nRCPT TO:<eve@example.com>");
+        assert!(mailer.send(&mail).is_err());
+
+        assert!(check_mailbox("Erika Mustermann <erika@example.com>").is_ok());
+        assert!(check_mailbox("max@example.com").is_ok());
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_command() {
+        let out = testdir::testdir!().join("mail.eml");
+        let mailer = Mailer {
+            backend: Backend::Command(vec![
+                String::from("sh"),
+                String::from("-c"),
+                format!(
+                    "cat > '{}'; echo \"$@\" >> '{}'",
+                    out.display(),
+                    out.display()
+                ),
+                String::from("sendmail"),
+                String::from("%r"),
+            ]),
+            from: String::from("jane@example.com"),
+        };
+        mailer.send(&mail()).unwrap();
+
+        let written = std::fs::read_to_string(&out).unwrap();
+        assert!(written.starts_with("From: Jane Doe <jane@example.com>\r\n"));
+        assert!(written.ends_with("max@example.com erika@example.com\n"));
+    }
+}
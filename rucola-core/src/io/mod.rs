@@ -0,0 +1,113 @@
+mod file_manager;
+pub use file_manager::FileManager;
+pub use file_manager::TrashEntry;
+
+mod file_tracker;
+pub use file_tracker::FileTracker;
+
+mod html_builder;
+pub use html_builder::HtmlBuilder;
+
+mod search_index;
+
+mod graph_builder;
+pub use graph_builder::GraphBuilder;
+
+mod pdf_builder;
+pub use pdf_builder::PdfBuilder;
+
+mod typst;
+
+mod process_pool;
+pub use process_pool::ProcessPool;
+
+mod batch_export;
+pub use batch_export::BatchExport;
+pub use batch_export::ExportKind;
+
+mod recurring_notes;
+pub use recurring_notes::Frequency;
+pub use recurring_notes::RecurringNote;
+pub use recurring_notes::RecurringNotes;
+
+mod export_profile;
+pub use export_profile::ExportFormat;
+pub use export_profile::ExportProfile;
+pub use export_profile::ExportProfiles;
+
+mod backup_manager;
+pub use backup_manager::BackupFormat;
+pub use backup_manager::BackupManager;
+
+mod autosave;
+pub use autosave::Autosave;
+
+mod atomic_write;
+pub use atomic_write::write_atomically;
+pub use atomic_write::Durability;
+
+mod vault_lock;
+pub use vault_lock::VaultLock;
+
+mod state_file;
+pub use state_file::StateFile;
+pub use state_file::VersionedState;
+
+mod sync_manager;
+pub use sync_manager::SyncManager;
+pub use sync_manager::SyncReport;
+pub use sync_manager::SyncStatus;
+
+mod s3_publisher;
+pub use s3_publisher::PublishReport;
+pub use s3_publisher::S3Publisher;
+
+mod cleanup;
+pub use cleanup::format_size;
+pub use cleanup::Cleanup;
+
+mod integrity;
+pub use integrity::IntegrityChecker;
+pub use integrity::IntegrityIssue;
+
+mod importer;
+pub use importer::ImportPlan;
+pub use importer::ImportedLink;
+pub use importer::Importer;
+
+mod link_completer;
+pub use link_completer::LinkCompleter;
+
+mod collections;
+pub use collections::Collections;
+
+mod visits;
+pub use visits::Visits;
+
+mod mailer;
+pub use mailer::Mail;
+pub use mailer::Mailer;
+pub use mailer::SmtpSecurity;
+
+mod summarizer;
+pub use summarizer::Summarizer;
+
+mod spellchecker;
+pub use spellchecker::Spellchecker;
+
+#[cfg(feature = "semantic-search")]
+mod semantic_search;
+#[cfg(feature = "semantic-search")]
+pub use semantic_search::SemanticSearch;
+
+mod content_search;
+pub use content_search::ContentSearch;
+
+mod wakeup;
+pub use wakeup::set_wakeup;
+pub use wakeup::wake;
+
+mod git_manager;
+pub use git_manager::side_by_side_diff;
+pub use git_manager::CommitInfo;
+pub use git_manager::GitManager;
@@ -0,0 +1,265 @@
+use std::{borrow, fs, path, process, time};
+
+use crate::{data, error, io};
+
+/// Struct that keeps configuration details for the creation of PDF files from markdown files with an external program.
+#[derive(Debug, Clone)]
+pub struct PdfBuilder {
+    /// Path to the vault to index.
+    vault_path: path::PathBuf,
+    /// The command used to convert a note into a PDF file.
+    pdf_command: Vec<String>,
+    /// Argument added to the command for notes that specify a PDF template in their frontmatter.
+    pdf_template_arg: Option<String>,
+    /// Argument added to the command for print-friendly PDF files.
+    pdf_print_arg: Option<String>,
+    /// The resolved path to the css file styling print-friendly files, if there is one.
+    print_css_path: Option<path::PathBuf>,
+    /// The command used to compile a typst slide deck into a PDF file.
+    slides_command: Vec<String>,
+    /// The maximum number of commands running at once.
+    build_jobs: usize,
+    /// The time after which a command is stopped, if any.
+    build_timeout: Option<time::Duration>,
+}
+
+impl PdfBuilder {
+    pub fn new(config: &crate::Config, vault_path: path::PathBuf) -> Self {
+        Self {
+            vault_path,
+            pdf_command: config.pdf_command.clone(),
+            pdf_template_arg: config.pdf_template_arg.clone(),
+            pdf_print_arg: config.pdf_print_arg.clone(),
+            print_css_path: config
+                .print_css
+                .as_deref()
+                .and_then(super::html_builder::resolve_css)
+                .filter(|css| css.exists()),
+            slides_command: config.slides_command.clone(),
+            build_jobs: config.build_jobs,
+            build_timeout: config.build_timeout.map(time::Duration::from_secs),
+        }
+    }
+
+    /// Creates a pool to run the commands of this builder in, with the configured parallelism and timeout.
+    pub fn pool(&self) -> io::ProcessPool {
+        io::ProcessPool::new(self.build_jobs, self.build_timeout)
+    }
+
+    /// Prepares the command creating a PDF file from the given note and returns it with the path of the PDF file.
+    /// Within the command, `%p` is replaced by the path of the note and `%o` by the path of the PDF file.
+    /// If the note contains query blocks and an index is given, `%p` is instead replaced by the path of a copy of the note with the results of the queries, next to the PDF file.
+    /// Print-friendly PDF files are built from a copy listing the URLs of links in footnotes, with the print stylesheet passed by the print argument.
+    /// Creates the folder of the PDF file, if necessary.
+    pub fn command(
+        &self,
+        note: &data::Note,
+        index: Option<&data::NoteIndex>,
+        print: bool,
+    ) -> error::Result<(process::Command, path::PathBuf)> {
+        let tar_path = if print {
+            name_to_print_pdf_path(&note.name, &self.vault_path)
+        } else {
+            name_to_pdf_path(&note.name, &self.vault_path)
+        };
+
+        // ensure parent exists
+        if let Some(parent) = tar_path.parent() {
+            if !parent.exists() {
+                fs::create_dir_all(parent)?;
+            }
+        }
+
+        // replace queries by their results and links by footnotes in a copy of the note
+        let source = if index.is_some() || print {
+            let content = fs::read_to_string(&note.path)?;
+            let mut expanded = match index {
+                Some(index) => data::expand_queries(&content, index, &data::name_to_id(&note.name)),
+                None => borrow::Cow::Borrowed(content.as_str()),
+            };
+            if print {
+                if let borrow::Cow::Owned(footnoted) = data::footnote_links(&expanded) {
+                    expanded = borrow::Cow::Owned(footnoted);
+                }
+            }
+            match expanded {
+                borrow::Cow::Owned(expanded) => {
+                    let copy = tar_path.with_extension("md");
+                    fs::write(&copy, expanded)?;
+                    copy
+                }
+                borrow::Cow::Borrowed(_) => note.path.clone(),
+            }
+        } else {
+            note.path.clone()
+        };
+
+        let mut iter = self.pdf_command.iter();
+        let programm = iter.next().ok_or(error::RucolaError::ApplicationMissing)?;
+
+        let mut cmd = process::Command::new(programm);
+        for arg in iter {
+            match arg.as_str() {
+                "%p" => cmd.arg(source.canonicalize().as_ref().unwrap_or(&source)),
+                "%o" => cmd.arg(&tar_path),
+                _ => cmd.arg(arg),
+            };
+        }
+
+        // use the template specified in the note's frontmatter
+        if let (Some(template), Some(template_arg)) =
+            (&note.export.pdf_template, &self.pdf_template_arg)
+        {
+            cmd.arg(template_arg.replace("%t", template));
+        }
+
+        // pass the print stylesheet, writing the built-in one next to the PDF file if none is configured
+        if let Some(print_arg) = self.pdf_print_arg.as_ref().filter(|_| print) {
+            let css = match &self.print_css_path {
+                Some(css) => css.clone(),
+                None => {
+                    let css = tar_path.with_file_name("print.css");
+                    fs::write(&css, super::html_builder::PRINT_STYLESHEET)?;
+                    css
+                }
+            };
+            cmd.arg(print_arg.replace("%c", &css.to_string_lossy()));
+        }
+
+        // run next to the note, so relative paths (e.g. of images) resolve
+        if let Some(parent) = note.path.parent().filter(|parent| parent.is_dir()) {
+            cmd.current_dir(parent);
+        }
+
+        Ok((cmd, tar_path))
+    }
+
+    /// Writes a typst slide deck of the given note, split into slides by rules or top headings, and prepares the command compiling it.
+    /// Returns the command with the path of the PDF file. Within the command, `%p` is replaced by the path of the deck, `%o` by the path of the PDF file and `%v` by the path of the vault.
+    /// Query blocks within the note are replaced by their results in the given index, if there is one.
+    pub fn slides_command(
+        &self,
+        note: &data::Note,
+        index: Option<&data::NoteIndex>,
+    ) -> error::Result<(process::Command, path::PathBuf)> {
+        let tar_path = name_to_slides_pdf_path(&note.name, &self.vault_path);
+        if let Some(parent) = tar_path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let content = fs::read_to_string(&note.path)?;
+        let content = match index {
+            Some(index) => data::expand_queries(&content, index, &data::name_to_id(&note.name)),
+            None => borrow::Cow::Borrowed(content.as_str()),
+        };
+        let deck = tar_path.with_extension("typ");
+        fs::write(
+            &deck,
+            super::typst::slide_deck(
+                &data::split_slides(&content),
+                note.path.parent().unwrap_or(&self.vault_path),
+                &self.vault_path,
+            ),
+        )?;
+
+        let mut iter = self.slides_command.iter();
+        let programm = iter.next().ok_or(error::RucolaError::ApplicationMissing)?;
+
+        let mut cmd = process::Command::new(programm);
+        for arg in iter {
+            match arg.as_str() {
+                "%p" => cmd.arg(&deck),
+                "%o" => cmd.arg(&tar_path),
+                "%v" => cmd.arg(&self.vault_path),
+                _ => cmd.arg(arg),
+            };
+        }
+
+        Ok((cmd, tar_path))
+    }
+}
+
+/// For a given note name, returns the path its PDF representation _would_ be stored at.
+/// Makes no guarantees if that representation currently exists.
+pub fn name_to_pdf_path(name: &str, vault_path: &path::Path) -> path::PathBuf {
+    vault_path
+        .join(".pdf")
+        .join(format!("{}.pdf", data::name_to_id(name)))
+}
+
+/// For a given note name, returns the path its print-friendly PDF representation _would_ be stored at.
+/// Makes no guarantees if that representation currently exists.
+pub fn name_to_print_pdf_path(name: &str, vault_path: &path::Path) -> path::PathBuf {
+    vault_path
+        .join(".print")
+        .join(format!("{}.pdf", data::name_to_id(name)))
+}
+
+/// For a given note name, returns the path its compiled typst slide deck _would_ be stored at.
+/// Makes no guarantees if that representation currently exists.
+pub fn name_to_slides_pdf_path(name: &str, vault_path: &path::Path) -> path::PathBuf {
+    vault_path
+        .join(".slides")
+        .join(format!("{}.pdf", data::name_to_id(name)))
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    #[cfg(unix)]
+    fn test_pdf_command() {
+        let tmp = testdir::testdir!();
+        let note_path = tmp.join("Atlas.md");
+        std::fs::write(&note_path, "---\npdf-template: handout\n---\n# Atlas").unwrap();
+        let note = crate::data::Note::from_path(&note_path, &Default::default()).unwrap();
+
+        // Use a plain copy as the 'conversion', recording the template argument in another file.
+        let config = crate::Config {
+            pdf_command: ["sh", "-c", "cp \"$0\" \"$1\" && touch \"$2\"", "%p", "%o"]
+                .map(String::from)
+                .to_vec(),
+            pdf_template_arg: Some("%t.template".to_owned()),
+            ..Default::default()
+        };
+
+        let builder = super::PdfBuilder::new(&config, tmp.clone());
+        let (mut command, pdf) = builder.command(&note, None, false).unwrap();
+        assert!(command.status().unwrap().success());
+
+        assert_eq!(pdf, super::name_to_pdf_path("Atlas", &tmp));
+        assert_eq!(pdf, tmp.join(".pdf/atlas.pdf"));
+        assert!(pdf.exists());
+        assert!(tmp.join("handout.template").exists());
+
+        // Queries are answered in a copy of the note, which is converted instead.
+        let shelf_path = tmp.join("Shelf.md");
+        let shelf_content =
+            "---\npdf-template: handout\n---\n# Shelf\n```rucola query #maps\n```\n";
+        std::fs::write(&shelf_path, shelf_content).unwrap();
+        std::fs::write(tmp.join("Chart.md"), "# Chart\n#maps").unwrap();
+        let tracker = crate::io::FileTracker::new(&config, tmp.clone()).unwrap();
+        let index = crate::data::NoteIndex::new(
+            tracker,
+            crate::io::HtmlBuilder::new(&config, tmp.clone()),
+            Default::default(),
+        )
+        .0;
+        let shelf = index.get("shelf").unwrap();
+        let (mut command, pdf) = builder.command(shelf, Some(&index), false).unwrap();
+        assert!(command.status().unwrap().success());
+        assert_eq!(
+            std::fs::read_to_string(pdf).unwrap(),
+            "---\npdf-template: handout\n---\n# Shelf\n- [[Chart]]\n"
+        );
+        assert_eq!(std::fs::read_to_string(&shelf_path).unwrap(), shelf_content);
+
+        // Without a program, there is no command.
+        let config = crate::Config {
+            pdf_command: vec![],
+            ..Default::default()
+        };
+        assert!(super::PdfBuilder::new(&config, tmp.clone())
+            .command(&note, None, false)
+            .is_err());
+    }
+}
@@ -0,0 +1,326 @@
+use std::{collections::VecDeque, io::Read, process, sync::mpsc, thread, time};
+
+use crate::error;
+
+/// How long to wait for the rest of the error output of a process that exited.
+const STDERR_WAIT: time::Duration = time::Duration::from_millis(100);
+
+/// A job waiting for a free slot in the pool.
+struct QueuedJob {
+    /// Identifies the job, e.g. the id of the note it builds a file for.
+    key: String,
+    /// The command to run.
+    command: process::Command,
+}
+
+/// A job whose process is running.
+struct RunningJob {
+    /// Identifies the job.
+    key: String,
+    /// The name of the program running, for error messages.
+    program: String,
+    /// The running process.
+    child: process::Child,
+    /// When the process was started.
+    started: time::Instant,
+    /// Receives the error output of the process, which is read on a separate thread so the process cannot block on a full pipe.
+    stderr: Option<mpsc::Receiver<String>>,
+}
+
+/// A job whose process exited, ran into the timeout or could not be started.
+#[derive(Debug)]
+pub struct FinishedJob {
+    /// Identifies the job.
+    pub key: String,
+    /// Wether the process succeeded, describing the failure otherwise.
+    pub result: error::Result<()>,
+    /// The full error output of the process, e.g. the messages of a compiler.
+    pub stderr: String,
+}
+
+/// Runs external commands such as PDF converters in parallel, with at most a configured number of processes at once.
+/// Every process is waited for once it exits, killed once it runs longer than the timeout and killed when the pool is dropped,
+/// so no processes are left behind.
+pub struct ProcessPool {
+    /// The maximum number of processes running at once.
+    jobs: usize,
+    /// The time after which a process is killed, if any.
+    timeout: Option<time::Duration>,
+    /// Jobs waiting for a free slot, oldest first.
+    queue: VecDeque<QueuedJob>,
+    /// Jobs whose processes are running.
+    running: Vec<RunningJob>,
+}
+
+impl ProcessPool {
+    /// Creates a new pool running at most the given number of processes at once, at least one.
+    pub fn new(jobs: usize, timeout: Option<time::Duration>) -> Self {
+        Self {
+            jobs: jobs.max(1),
+            timeout,
+            queue: VecDeque::new(),
+            running: Vec::new(),
+        }
+    }
+
+    /// Queues the given command. The output of the command is discarded, its error output is used to describe failures.
+    /// A job with the same key that is still waiting or running is cancelled, as its result would be outdated.
+    pub fn submit(&mut self, key: &str, mut command: process::Command) {
+        self.cancel(key);
+
+        command
+            .stdin(process::Stdio::null())
+            .stdout(process::Stdio::null())
+            .stderr(process::Stdio::piped());
+
+        self.queue.push_back(QueuedJob {
+            key: key.to_owned(),
+            command,
+        });
+    }
+
+    /// Cancels the job with the given key, killing its process if it is running.
+    /// Returns wether there was such a job.
+    pub fn cancel(&mut self, key: &str) -> bool {
+        let queued = self.queue.len();
+        self.queue.retain(|job| job.key != key);
+
+        let (cancelled, running): (Vec<_>, Vec<_>) = std::mem::take(&mut self.running)
+            .into_iter()
+            .partition(|job| job.key == key);
+        self.running = running;
+
+        let found = queued != self.queue.len() || !cancelled.is_empty();
+        cancelled.into_iter().for_each(kill);
+        found
+    }
+
+    /// Cancels all jobs, killing all running processes.
+    pub fn cancel_all(&mut self) {
+        self.queue.clear();
+        std::mem::take(&mut self.running).into_iter().for_each(kill);
+    }
+
+    /// Wether the job with the given key is waiting or running.
+    pub fn contains(&self, key: &str) -> bool {
+        self.queue.iter().any(|job| job.key == key) || self.running.iter().any(|job| job.key == key)
+    }
+
+    /// Returns the number of jobs waiting or running.
+    pub fn len(&self) -> usize {
+        self.queue.len() + self.running.len()
+    }
+
+    /// Wether there are no jobs waiting or running.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Collects the results of all finished jobs, kills processes that ran into the timeout and starts waiting jobs in free slots.
+    /// Does not block.
+    pub fn poll(&mut self) -> Vec<FinishedJob> {
+        let mut finished = Vec::new();
+
+        for mut job in std::mem::take(&mut self.running) {
+            match job.child.try_wait() {
+                Ok(Some(status)) => {
+                    let stderr = job
+                        .stderr
+                        .take()
+                        .and_then(|receiver| receiver.recv_timeout(STDERR_WAIT).ok())
+                        .unwrap_or_default();
+                    let result = if status.success() {
+                        Ok(())
+                    } else {
+                        Err(error::RucolaError::ExternalCommand(format!(
+                            "{} exited with {}: {}",
+                            job.program,
+                            status,
+                            stderr.lines().next().unwrap_or_default()
+                        )))
+                    };
+                    finished.push(FinishedJob {
+                        key: job.key,
+                        result,
+                        stderr,
+                    });
+                }
+                Ok(None)
+                    if self
+                        .timeout
+                        .is_some_and(|timeout| job.started.elapsed() > timeout) =>
+                {
+                    let message = format!(
+                        "{} did not finish within {} seconds.",
+                        job.program,
+                        self.timeout.unwrap_or_default().as_secs()
+                    );
+                    let key = job.key.clone();
+                    kill(job);
+                    finished.push(FinishedJob {
+                        key,
+                        result: Err(error::RucolaError::ExternalCommand(message)),
+                        stderr: String::new(),
+                    });
+                }
+                Ok(None) => self.running.push(job),
+                Err(e) => {
+                    let key = job.key.clone();
+                    kill(job);
+                    finished.push(FinishedJob {
+                        key,
+                        result: Err(e.into()),
+                        stderr: String::new(),
+                    });
+                }
+            }
+        }
+
+        while self.running.len() < self.jobs {
+            let Some(QueuedJob { key, mut command }) = self.queue.pop_front() else {
+                break;
+            };
+            match command.spawn() {
+                Ok(mut child) => {
+                    let stderr = child.stderr.take().map(|mut stderr| {
+                        let (sender, receiver) = mpsc::channel();
+                        thread::spawn(move || {
+                            let mut output = String::new();
+                            let _ = stderr.read_to_string(&mut output);
+                            let _ = sender.send(output);
+                        });
+                        receiver
+                    });
+                    self.running.push(RunningJob {
+                        key,
+                        program: command.get_program().to_string_lossy().into_owned(),
+                        child,
+                        started: time::Instant::now(),
+                        stderr,
+                    });
+                }
+                Err(e) => finished.push(FinishedJob {
+                    key,
+                    result: Err(e.into()),
+                    stderr: String::new(),
+                }),
+            }
+        }
+
+        finished
+    }
+}
+
+impl Drop for ProcessPool {
+    fn drop(&mut self) {
+        self.cancel_all();
+    }
+}
+
+/// Kills the process of the given job and waits for it, so it does not linger as a zombie.
+fn kill(mut job: RunningJob) {
+    let _ = job.child.kill();
+    let _ = job.child.wait();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Creates a shell command running the given script.
+    fn sh(script: &str) -> process::Command {
+        let mut command = process::Command::new("sh");
+        command.arg("-c").arg(script);
+        command
+    }
+
+    /// Polls the pool until all jobs finished and returns their results by key.
+    fn run(pool: &mut ProcessPool) -> Vec<(String, bool)> {
+        let mut results = Vec::new();
+        while !pool.is_empty() {
+            results.extend(
+                pool.poll()
+                    .into_iter()
+                    .map(|job| (job.key, job.result.is_ok())),
+            );
+            thread::sleep(time::Duration::from_millis(5));
+        }
+        results.sort();
+        results
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_process_pool() {
+        let tmp = testdir::testdir!();
+        let mut pool = ProcessPool::new(2, None);
+
+        for name in ["a", "b", "c"] {
+            pool.submit(name, sh(&format!("touch {}", tmp.join(name).display())));
+        }
+        pool.submit("fail", sh("echo broken >&2; exit 3"));
+        assert_eq!(pool.len(), 4);
+
+        // only two processes run at once
+        pool.poll();
+        assert_eq!(pool.running.len(), 2);
+
+        assert_eq!(
+            run(&mut pool),
+            vec![
+                (String::from("a"), true),
+                (String::from("b"), true),
+                (String::from("c"), true),
+                (String::from("fail"), false),
+            ]
+        );
+        assert!(tmp.join("c").exists());
+
+        // the full error output of failed jobs is kept
+        pool.submit("fail", sh("echo first >&2; echo second >&2; exit 1"));
+        let mut finished = Vec::new();
+        while !pool.is_empty() {
+            finished.extend(pool.poll());
+            thread::sleep(time::Duration::from_millis(5));
+        }
+        assert_eq!(finished[0].stderr, "first\nsecond\n");
+        assert!(finished[0]
+            .result
+            .as_ref()
+            .unwrap_err()
+            .to_string()
+            .contains("first"));
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_process_pool_cancellation() {
+        let tmp = testdir::testdir!();
+        let mut pool = ProcessPool::new(1, Some(time::Duration::from_millis(200)));
+
+        // submitting the same key again replaces the running job
+        pool.submit(
+            "note",
+            sh(&format!("sleep 5; touch {}", tmp.join("old").display())),
+        );
+        pool.poll();
+        pool.submit("note", sh(&format!("touch {}", tmp.join("new").display())));
+        assert_eq!(pool.len(), 1);
+        assert_eq!(run(&mut pool), vec![(String::from("note"), true)]);
+        assert!(tmp.join("new").exists());
+        assert!(!tmp.join("old").exists());
+
+        // jobs running into the timeout fail
+        pool.submit("slow", sh("sleep 5"));
+        let start = time::Instant::now();
+        assert_eq!(run(&mut pool), vec![(String::from("slow"), false)]);
+        assert!(start.elapsed() < time::Duration::from_secs(5));
+
+        // cancelled jobs do not report results
+        pool.submit("cancelled", sh("sleep 5"));
+        pool.poll();
+        assert!(pool.cancel("cancelled"));
+        assert!(pool.is_empty());
+        assert!(pool.poll().is_empty());
+    }
+}
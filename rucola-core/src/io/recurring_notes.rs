@@ -0,0 +1,361 @@
+use std::{collections::BTreeMap, fmt::Write, path};
+
+use chrono::Datelike;
+
+use crate::{data, error, io};
+
+/// The file the dates of the last created instances are stored in, relative to the vault.
+const STATE_FILE: &str = ".rucola-recurring.json";
+
+/// The format dates are stored and inserted into snippets in.
+const DATE_FORMAT: &str = "%Y-%m-%d";
+
+/// How often a recurring note is due.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum Frequency {
+    /// Every day.
+    #[default]
+    Daily,
+    /// Every week on the configured weekday.
+    Weekly,
+    /// Every month on the configured day.
+    Monthly,
+    /// Every year on the configured day of the configured month.
+    Yearly,
+}
+
+/// A note created regularly from a snippet, as set in the `recurring_notes` of the config.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct RecurringNote {
+    /// How often the note is due.
+    pub every: Frequency,
+    /// The weekday weekly notes are due on, in English, e.g. `Monday` or `mon`.
+    pub weekday: String,
+    /// The day of the month monthly and yearly notes are due on, the last day in shorter months.
+    pub day: u32,
+    /// The month yearly notes are due in, from 1 to 12.
+    pub month: u32,
+    /// The path of the note relative to the vault, formatted with the date it is due on, e.g. `reviews/%G-W%V`.
+    pub path: String,
+    /// The name of the snippet the note is created from, or an empty note with a title if none.
+    pub snippet: Option<String>,
+}
+
+impl Default for RecurringNote {
+    fn default() -> Self {
+        Self {
+            every: Frequency::Daily,
+            weekday: String::from("Monday"),
+            day: 1,
+            month: 1,
+            path: String::new(),
+            snippet: None,
+        }
+    }
+}
+
+impl RecurringNote {
+    /// Returns a description of what is wrong with this rule, if anything.
+    /// Unknown snippets are only detected when the note is created.
+    pub fn problem(&self) -> Option<String> {
+        if self.path.trim().is_empty() {
+            Some(String::from("It sets no path."))
+        } else if chrono::format::StrftimeItems::new(&self.path)
+            .any(|item| item == chrono::format::Item::Error)
+        {
+            Some(format!(
+                "Its path \"{}\" is no valid date format.",
+                self.path
+            ))
+        } else if self.every == Frequency::Weekly
+            && self.weekday.parse::<chrono::Weekday>().is_err()
+        {
+            Some(format!("Unknown weekday \"{}\".", self.weekday))
+        } else if !(1..=31).contains(&self.day) {
+            Some(format!("The day {} is not between 1 and 31.", self.day))
+        } else if !(1..=12).contains(&self.month) {
+            Some(format!("The month {} is not between 1 and 12.", self.month))
+        } else {
+            None
+        }
+    }
+
+    /// Returns the date of the most recent instance of this note on or before the given date.
+    fn due(&self, today: chrono::NaiveDate) -> chrono::NaiveDate {
+        match self.every {
+            Frequency::Daily => today,
+            Frequency::Weekly => {
+                let weekday = self
+                    .weekday
+                    .parse::<chrono::Weekday>()
+                    .unwrap_or(chrono::Weekday::Mon);
+                let days_since = (today.weekday().num_days_from_monday() + 7
+                    - weekday.num_days_from_monday())
+                    % 7;
+                today - chrono::Days::new(days_since as u64)
+            }
+            Frequency::Monthly => {
+                let this_month = day_of_month(today.year(), today.month(), self.day);
+                if this_month <= today {
+                    this_month
+                } else {
+                    let previous = today.with_day(1).unwrap_or(today) - chrono::Days::new(1);
+                    day_of_month(previous.year(), previous.month(), self.day)
+                }
+            }
+            Frequency::Yearly => {
+                let this_year = day_of_month(today.year(), self.month, self.day);
+                if this_year <= today {
+                    this_year
+                } else {
+                    day_of_month(today.year() - 1, self.month, self.day)
+                }
+            }
+        }
+    }
+}
+
+/// Returns the given day of the given month, or the last day of the month if it is shorter.
+fn day_of_month(year: i32, month: u32, day: u32) -> chrono::NaiveDate {
+    (1..=day.clamp(1, 31))
+        .rev()
+        .find_map(|day| chrono::NaiveDate::from_ymd_opt(year, month.clamp(1, 12), day))
+        .unwrap_or_default()
+}
+
+/// The date of the last created instance of every recurring note, by the name of its rule.
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+struct CreatedInstances {
+    created: BTreeMap<String, String>,
+}
+
+impl io::VersionedState for CreatedInstances {
+    const VERSION: u32 = 1;
+}
+
+/// Creates the notes due according to the rules configured by the user, such as a weekly review every monday.
+/// Every instance is created once: Instances that exist already are skipped and deleted ones are not created again.
+#[derive(Debug, Clone)]
+pub struct RecurringNotes {
+    /// The rules with their names, sorted by name.
+    rules: Vec<(String, RecurringNote)>,
+    /// The snippets the notes are created from.
+    snippets: Vec<data::Snippet>,
+    /// The file the dates of the last created instances are stored in.
+    state_file: io::StateFile<CreatedInstances>,
+}
+
+impl RecurringNotes {
+    /// Creates the recurring notes of the given vault from the given config.
+    pub fn new(config: &crate::Config, vault_path: &path::Path) -> Self {
+        let mut rules = config
+            .recurring_notes
+            .iter()
+            .map(|(name, rule)| (name.clone(), rule.clone()))
+            .collect::<Vec<_>>();
+        rules.sort_by(|(a, _), (b, _)| a.cmp(b));
+        Self {
+            rules,
+            snippets: data::Snippet::from_config(config),
+            state_file: io::StateFile::new(vault_path.join(STATE_FILE)),
+        }
+    }
+
+    /// Wether any recurring notes are configured.
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// Creates all instances due on or before the given date that have not been created yet, using the given file manager.
+    /// Returns the paths of the created notes and the errors of the rules that could not be applied.
+    pub fn create_due(
+        &self,
+        manager: &io::FileManager,
+        today: chrono::NaiveDate,
+    ) -> (Vec<path::PathBuf>, Vec<error::RucolaError>) {
+        let mut created = Vec::new();
+        let mut errors = Vec::new();
+
+        if self.rules.is_empty() {
+            return (created, errors);
+        }
+
+        let (mut state, error) = self.state_file.load();
+        errors.extend(error);
+
+        for (name, rule) in &self.rules {
+            let due = rule.due(today);
+            let date = due.format(DATE_FORMAT).to_string();
+            if state.created.get(name).is_some_and(|last| *last >= date) {
+                continue;
+            }
+
+            match self.create_instance(manager, name, rule, due) {
+                Ok(path) => {
+                    created.extend(path);
+                    state.created.insert(name.clone(), date);
+                }
+                Err(e) => errors.push(e),
+            }
+        }
+
+        errors.extend(self.state_file.save(&state).err());
+
+        (created, errors)
+    }
+
+    /// Creates the instance of the given rule due on the given date, returning its path unless it existed already.
+    fn create_instance(
+        &self,
+        manager: &io::FileManager,
+        name: &str,
+        rule: &RecurringNote,
+        due: chrono::NaiveDate,
+    ) -> error::Result<Option<path::PathBuf>> {
+        if let Some(problem) = rule.problem() {
+            return Err(error::RucolaError::ConfigProblem(format!(
+                "The recurring note `{}` cannot be created: {}",
+                name, problem
+            )));
+        }
+
+        let mut input_path = String::new();
+        write!(input_path, "{}", due.format(&rule.path)).map_err(|_| {
+            error::RucolaError::ConfigProblem(format!(
+                "The recurring note `{}` cannot be created: Its path \"{}\" is no valid date format.",
+                name, rule.path
+            ))
+        })?;
+
+        let title = path::Path::new(&input_path)
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+            .unwrap_or_default();
+
+        let text = match &rule.snippet {
+            Some(snippet) => self
+                .snippets
+                .iter()
+                .find(|other| other.name == *snippet)
+                .ok_or_else(|| {
+                    error::RucolaError::ConfigProblem(format!(
+                        "The recurring note `{}` uses the unknown snippet `{}`.",
+                        name, snippet
+                    ))
+                })?
+                .expand(&[
+                    ("title", &title),
+                    ("name", &title),
+                    ("date", &due.format(DATE_FORMAT).to_string()),
+                ]),
+            None => format!("# {}\n", title),
+        };
+
+        manager.create_note_with_text(&input_path, &text)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(year: i32, month: u32, day: u32) -> chrono::NaiveDate {
+        chrono::NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn test_due() {
+        let weekly = RecurringNote {
+            every: Frequency::Weekly,
+            weekday: String::from("wed"),
+            ..Default::default()
+        };
+        // 2024-03-13 is a wednesday
+        assert_eq!(weekly.due(date(2024, 3, 13)), date(2024, 3, 13));
+        assert_eq!(weekly.due(date(2024, 3, 19)), date(2024, 3, 13));
+
+        let monthly = RecurringNote {
+            every: Frequency::Monthly,
+            day: 31,
+            ..Default::default()
+        };
+        assert_eq!(monthly.due(date(2024, 2, 29)), date(2024, 2, 29));
+        assert_eq!(monthly.due(date(2024, 3, 30)), date(2024, 2, 29));
+        assert_eq!(monthly.due(date(2024, 1, 5)), date(2023, 12, 31));
+
+        let yearly = RecurringNote {
+            every: Frequency::Yearly,
+            month: 6,
+            day: 15,
+            ..Default::default()
+        };
+        assert_eq!(yearly.due(date(2024, 6, 14)), date(2023, 6, 15));
+        assert_eq!(yearly.due(date(2024, 12, 1)), date(2024, 6, 15));
+
+        assert_eq!(
+            RecurringNote::default().due(date(2024, 3, 1)),
+            date(2024, 3, 1)
+        );
+    }
+
+    #[test]
+    fn test_create_due() {
+        let vault = testdir::testdir!();
+        let config: crate::Config = toml::from_str(
+            r##"
+            [snippets]
+            review = "# Review of {{date}}\n## Done\n"
+
+            [recurring_notes.review]
+            every = "Weekly"
+            path = "reviews/%G-W%V"
+            snippet = "review"
+
+            [recurring_notes.budget]
+            every = "Monthly"
+            path = "budget/%Y-%m"
+
+            [recurring_notes.broken]
+            path = "daily/%Y-%m-%d"
+            snippet = "missing"
+            "##,
+        )
+        .unwrap();
+        let manager = io::FileManager::new(&config, vault.clone());
+        let recurring = RecurringNotes::new(&config, &vault);
+
+        // 2024-03-13 is the wednesday of week 11
+        let (created, errors) = recurring.create_due(&manager, date(2024, 3, 13));
+        assert_eq!(
+            created,
+            vec![
+                vault.join("budget").join("2024-03.md"),
+                vault.join("reviews").join("2024-W11.md"),
+            ]
+        );
+        assert_eq!(errors.len(), 1);
+        assert_eq!(
+            std::fs::read_to_string(vault.join("reviews").join("2024-W11.md")).unwrap(),
+            "# Review of 2024-03-11\n## Done\n"
+        );
+        assert_eq!(
+            std::fs::read_to_string(vault.join("budget").join("2024-03.md")).unwrap(),
+            "# 2024-03\n"
+        );
+
+        // deleted instances are not created again, but the next ones are
+        std::fs::remove_file(vault.join("budget").join("2024-03.md")).unwrap();
+        let (created, _errors) = recurring.create_due(&manager, date(2024, 3, 18));
+        assert_eq!(created, vec![vault.join("reviews").join("2024-W12.md")]);
+
+        // existing notes are never overwritten
+        std::fs::write(vault.join("budget").join("2024-04.md"), "Spent it all.").unwrap();
+        let (created, _errors) = recurring.create_due(&manager, date(2024, 4, 2));
+        assert_eq!(created, vec![vault.join("reviews").join("2024-W14.md")]);
+        assert_eq!(
+            std::fs::read_to_string(vault.join("budget").join("2024-04.md")).unwrap(),
+            "Spent it all."
+        );
+    }
+}
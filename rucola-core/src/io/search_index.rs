@@ -0,0 +1,145 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::data;
+
+/// The name of the script within the HTML folder that contains the search index and adds a search box to the pages loading it.
+pub const SEARCH_SCRIPT_NAME: &str = "rucola-search.js";
+
+/// Searches the index assigned to `rucolaSearchIndex` above and shows the results below a search box at the top of the page.
+/// Every word of the query has to match the title, a tag or the start of a term of a note. Matches in titles weigh most, then tags, then terms.
+const SEARCH_UI: &str = r#"
+(function () {
+  var index = rucolaSearchIndex;
+
+  function search(query) {
+    var words = query.toLowerCase().split(/\s+/).filter(function (word) { return word.length > 0; });
+    if (words.length === 0) {
+      return [];
+    }
+    var scores = index.notes.map(function () { return 0; });
+    var matched = index.notes.map(function () { return 0; });
+    words.forEach(function (word) {
+      var found = index.notes.map(function () { return 0; });
+      index.notes.forEach(function (note, i) {
+        if (note[1].toLowerCase().indexOf(word) >= 0) { found[i] += 10; }
+        if (note[2].some(function (tag) { return tag.toLowerCase().indexOf(word) >= 0; })) { found[i] += 5; }
+      });
+      Object.keys(index.terms).forEach(function (term) {
+        if (term.lastIndexOf(word, 0) === 0) {
+          index.terms[term].forEach(function (i) { found[i] += term === word ? 2 : 1; });
+        }
+      });
+      found.forEach(function (score, i) {
+        if (score > 0) { scores[i] += score; matched[i] += 1; }
+      });
+    });
+    return index.notes
+      .map(function (note, i) { return [note, scores[i], matched[i]]; })
+      .filter(function (result) { return result[2] === words.length; })
+      .sort(function (a, b) { return b[1] - a[1]; })
+      .slice(0, 20)
+      .map(function (result) { return result[0]; });
+  }
+
+  function show() {
+    var container = document.createElement("nav");
+    container.className = "rucola-search";
+    var input = document.createElement("input");
+    input.type = "search";
+    input.placeholder = "Search";
+    input.setAttribute("aria-label", "Search notes");
+    var results = document.createElement("ul");
+    input.addEventListener("input", function () {
+      results.innerHTML = "";
+      search(input.value).forEach(function (note) {
+        var item = document.createElement("li");
+        var link = document.createElement("a");
+        link.href = note[0];
+        link.textContent = note[1];
+        item.appendChild(link);
+        if (note[3]) {
+          var snippet = document.createElement("div");
+          snippet.textContent = note[3];
+          item.appendChild(snippet);
+        }
+        results.appendChild(item);
+      });
+    });
+    container.appendChild(input);
+    container.appendChild(results);
+    document.body.insertBefore(container, document.body.firstChild);
+  }
+
+  if (document.readyState === "loading") {
+    document.addEventListener("DOMContentLoaded", show);
+  } else {
+    show();
+  }
+})();
+"#;
+
+/// Returns the search script for the given notes, containing their index and the search box.
+/// For each note, the index contains its page, title, tags and snippet. The terms of the notes map to the positions of the notes containing them.
+pub(super) fn search_script(notes: &[data::Note]) -> String {
+    let entries = notes
+        .iter()
+        .map(|note| {
+            (
+                format!("{}.html", data::name_to_id(&note.name)),
+                note.display_name.as_str(),
+                note.tags
+                    .iter()
+                    .map(|tag| tag.trim_start_matches('#'))
+                    .collect::<Vec<_>>(),
+                note.snippet.as_str(),
+            )
+        })
+        .collect::<Vec<_>>();
+
+    // sorted, so unchanged notes give the same script
+    let mut terms: BTreeMap<&str, BTreeSet<usize>> = BTreeMap::new();
+    for (position, note) in notes.iter().enumerate() {
+        for term in note.terms.keys() {
+            terms.entry(term).or_default().insert(position);
+        }
+    }
+
+    format!(
+        "var rucolaSearchIndex = {};\n{}",
+        serde_json::json!({ "notes": entries, "terms": terms }),
+        SEARCH_UI
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn test_search_script() {
+        let note = crate::data::Note::from_content(
+            std::path::Path::new("Lie Theory.md"),
+            "---\ntitle: Lie groups\ntags: [math]\n---\nA manifold with a group structure.\n",
+            &Default::default(),
+        )
+        .unwrap();
+
+        let script = super::search_script(&[note]);
+        let json = script
+            .strip_prefix("var rucolaSearchIndex = ")
+            .and_then(|rest| rest.split_once(";\n"))
+            .map(|(json, _)| json)
+            .unwrap();
+        let index: serde_json::Value = serde_json::from_str(json).unwrap();
+
+        assert_eq!(
+            index["notes"][0],
+            serde_json::json!([
+                "lie-theory.html",
+                "Lie groups",
+                ["math"],
+                "A manifold with a group structure."
+            ])
+        );
+        assert_eq!(index["terms"]["manifold"], serde_json::json!([0]));
+        assert!(script.contains("function search(query)"));
+    }
+}
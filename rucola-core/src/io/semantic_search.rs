@@ -0,0 +1,357 @@
+use std::{collections::HashMap, fs, path};
+
+use sha2::Digest;
+
+use crate::{data, error, io};
+
+/// The file the embeddings of the notes are stored in, relative to the vault.
+pub(super) const STATE_FILE: &str = ".rucola-embeddings.json";
+
+/// Where embeddings are requested from.
+#[derive(Debug, Clone)]
+enum Backend {
+    /// An external command receiving a text on stdin and writing its embedding to stdout.
+    Command(Vec<String>),
+    /// An HTTP endpoint following the embed API of ollama.
+    Endpoint {
+        /// The URL to post requests to.
+        url: String,
+        /// The model to request the embeddings from.
+        model: String,
+    },
+}
+
+impl Backend {
+    /// Describes the backend, so stored embeddings are rebuilt when it changes.
+    fn describe(&self) -> String {
+        match self {
+            Backend::Command(command) => command.join(" "),
+            Backend::Endpoint { url, model } => format!("{} {}", url, model),
+        }
+    }
+
+    /// Returns the embedding of the given text, optionally read from the file at the given path.
+    fn embed(&self, path: Option<&path::Path>, text: &str) -> error::Result<Vec<f32>> {
+        let embedding = match self {
+            Backend::Command(command) => {
+                parse_vector(&super::summarizer::run_command(command, path, text)?)
+            }
+            Backend::Endpoint { url, model } => {
+                let response = super::summarizer::post_json(
+                    url,
+                    serde_json::json!({
+                        "model": model,
+                        "input": text,
+                    }),
+                    error::RucolaError::EmbeddingError,
+                )?;
+                // accept both the current and the legacy response format
+                response
+                    .get("embeddings")
+                    .and_then(|embeddings| embeddings.get(0))
+                    .or_else(|| response.get("embedding"))
+                    .and_then(|embedding| serde_json::from_value(embedding.clone()).ok())
+            }
+        };
+
+        embedding
+            .filter(|embedding: &Vec<f32>| !embedding.is_empty())
+            .ok_or_else(|| {
+                error::RucolaError::EmbeddingError(String::from("The model returned no embedding."))
+            })
+    }
+}
+
+/// The stored embeddings of the notes of a vault.
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+struct Embeddings {
+    /// Describes the command or model the embeddings were created with.
+    backend: String,
+    /// The embedding of each note by id, with the hash of the content it was created from.
+    notes: HashMap<String, (String, Vec<f32>)>,
+}
+
+impl io::VersionedState for Embeddings {
+    const VERSION: u32 = 1;
+}
+
+/// Ranks notes by how similar their meaning is to a query, using vector embeddings created by a configurable local model or command.
+/// Embeddings are stored in the vault and only recreated for notes that changed.
+#[derive(Debug, Clone)]
+pub struct SemanticSearch {
+    /// The command or endpoint to request embeddings from.
+    backend: Backend,
+    /// The file the embeddings are stored in.
+    state_file: io::StateFile<Embeddings>,
+    /// The embeddings known so far.
+    embeddings: Embeddings,
+    /// The notes whose embeddings still have to be created, with the content and its hash.
+    pending: Vec<(String, path::PathBuf, String, String)>,
+    /// The number of notes whose embeddings had to be created.
+    total: usize,
+    /// The notes whose embeddings could not be created, with the reason.
+    failures: Vec<(String, String)>,
+}
+
+impl SemanticSearch {
+    /// Creates a new semantic search for the given vault, if an embedding command or endpoint is configured. The command takes precedence.
+    pub fn new(config: &crate::Config, vault_path: &path::Path) -> Option<Self> {
+        let backend = match (&config.embedding_command, &config.embedding_endpoint) {
+            (Some(command), _) if !command.is_empty() => Backend::Command(command.clone()),
+            (_, Some(url)) => Backend::Endpoint {
+                url: url.to_owned(),
+                model: config.embedding_model.clone(),
+            },
+            _ => return None,
+        };
+
+        Some(Self {
+            backend,
+            state_file: io::StateFile::new(vault_path.join(STATE_FILE)),
+            embeddings: Embeddings::default(),
+            pending: Vec::new(),
+            total: 0,
+            failures: Vec::new(),
+        })
+    }
+
+    /// Loads the stored embeddings and determines the notes of the given index whose embeddings are missing or outdated.
+    /// Embeddings of notes no longer in the index are dropped.
+    /// Returns an error if the stored embeddings could not be read, in which case they are rebuilt.
+    pub fn prepare(&mut self, index: &data::NoteIndex) -> Option<error::RucolaError> {
+        let (mut embeddings, error) = self.state_file.load();
+
+        // embeddings of different models cannot be compared
+        let backend = self.backend.describe();
+        if embeddings.backend != backend {
+            embeddings = Embeddings {
+                backend,
+                ..Default::default()
+            };
+        }
+
+        let mut ids = Vec::new();
+        self.pending.clear();
+        self.failures.clear();
+        for note in index.notes() {
+            let id = data::name_to_id(&note.name);
+            match fs::read_to_string(&note.path) {
+                Ok(content) => {
+                    let hash = hash(&content);
+                    if embeddings
+                        .notes
+                        .get(&id)
+                        .is_none_or(|(stored, _)| *stored != hash)
+                    {
+                        self.pending
+                            .push((id.clone(), note.path.clone(), content, hash));
+                    }
+                }
+                Err(e) => self
+                    .failures
+                    .push((note.display_name.clone(), e.to_string())),
+            }
+            ids.push(id);
+        }
+        embeddings.notes.retain(|id, _| ids.contains(id));
+
+        self.embeddings = embeddings;
+        self.total = self.pending.len();
+
+        // without notes to embed, only removed notes might have to be dropped from the stored embeddings
+        if self.pending.is_empty() {
+            if let Err(e) = self.state_file.save(&self.embeddings) {
+                self.failures.push((STATE_FILE.to_owned(), e.to_string()));
+            }
+        }
+
+        error
+    }
+
+    /// Returns the number of stored embeddings of notes no longer in the given index.
+    pub fn stale_entries(&self, index: &data::NoteIndex) -> usize {
+        let (embeddings, _error) = self.state_file.load();
+        embeddings
+            .notes
+            .keys()
+            .filter(|id| index.get(id).is_none())
+            .count()
+    }
+
+    /// Drops the stored embeddings of notes no longer in the given index without creating any, and stores the rest if anything was dropped.
+    /// Returns the number of dropped embeddings.
+    pub fn prune(&mut self, index: &data::NoteIndex) -> error::Result<usize> {
+        let (mut embeddings, error) = self.state_file.load();
+        if let Some(e) = error {
+            return Err(e);
+        }
+
+        let before = embeddings.notes.len();
+        embeddings.notes.retain(|id, _| index.get(id).is_some());
+        let dropped = before - embeddings.notes.len();
+
+        if dropped > 0 {
+            self.state_file.save(&embeddings)?;
+        }
+        self.embeddings = embeddings;
+
+        Ok(dropped)
+    }
+
+    /// Creates the embedding of the next pending note. Stores all embeddings once the last one is created.
+    /// Returns wether there is work left.
+    pub fn step(&mut self) -> bool {
+        let Some((id, path, content, hash)) = self.pending.pop() else {
+            return false;
+        };
+
+        match self.backend.embed(Some(&path), &content) {
+            Ok(embedding) => {
+                self.embeddings.notes.insert(id, (hash, embedding));
+            }
+            Err(e) => self.failures.push((id, e.to_string())),
+        }
+
+        if self.pending.is_empty() {
+            if let Err(e) = self.state_file.save(&self.embeddings) {
+                self.failures.push((STATE_FILE.to_owned(), e.to_string()));
+            }
+        }
+
+        !self.pending.is_empty()
+    }
+
+    /// Returns the number of notes whose embeddings were created so far and the number of notes that needed one.
+    pub fn progress(&self) -> (usize, usize) {
+        (self.total - self.pending.len(), self.total)
+    }
+
+    /// Wether all embeddings have been created.
+    pub fn is_ready(&self) -> bool {
+        self.pending.is_empty()
+    }
+
+    /// Returns the notes whose embeddings could not be created, with the reason.
+    pub fn failures(&self) -> &[(String, String)] {
+        &self.failures
+    }
+
+    /// Returns the ids of all notes with an embedding, ranked by the cosine similarity of their embedding to that of the query, most similar first.
+    pub fn search(&self, query: &str) -> error::Result<Vec<(String, f32)>> {
+        let query = self.backend.embed(None, query)?;
+
+        let mut ranking = self
+            .embeddings
+            .notes
+            .iter()
+            .map(|(id, (_hash, embedding))| (id.to_owned(), cosine_similarity(&query, embedding)))
+            .collect::<Vec<_>>();
+
+        ranking.sort_by(|(id1, s1), (id2, s2)| s2.total_cmp(s1).then(id1.cmp(id2)));
+        Ok(ranking)
+    }
+}
+
+/// Returns the hash of the given content, to recognize changed notes.
+fn hash(content: &str) -> String {
+    sha2::Sha256::digest(content.as_bytes())
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+/// Reads a vector from the output of a command, either as a JSON array or as numbers separated by whitespace or commas.
+fn parse_vector(output: &str) -> Option<Vec<f32>> {
+    serde_json::from_str(output.trim()).ok().or_else(|| {
+        output
+            .split(|c: char| c.is_whitespace() || c == ',')
+            .filter(|number| !number.is_empty())
+            .map(|number| number.parse().ok())
+            .collect()
+    })
+}
+
+/// Returns the cosine of the angle between two vectors, or 0 if it is undefined.
+fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
+    if a.len() != b.len() {
+        return 0.0;
+    }
+
+    let dot = a.iter().zip(b).map(|(x, y)| x * y).sum::<f32>();
+    let norms =
+        a.iter().map(|x| x * x).sum::<f32>().sqrt() * b.iter().map(|y| y * y).sum::<f32>().sqrt();
+
+    if norms == 0.0 {
+        0.0
+    } else {
+        dot / norms
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cosine_similarity() {
+        assert!((cosine_similarity(&[1.0, 0.0], &[2.0, 0.0]) - 1.0).abs() < 1e-6);
+        assert!(cosine_similarity(&[1.0, 0.0], &[0.0, 3.0]).abs() < 1e-6);
+        assert!((cosine_similarity(&[1.0, 1.0], &[-1.0, -1.0]) + 1.0).abs() < 1e-6);
+        // undefined cases
+        assert_eq!(cosine_similarity(&[0.0, 0.0], &[1.0, 0.0]), 0.0);
+        assert_eq!(cosine_similarity(&[1.0], &[1.0, 0.0]), 0.0);
+    }
+
+    #[test]
+    fn test_parse_vector() {
+        assert_eq!(
+            parse_vector("[0.5, -1, 2e-1]\n"),
+            Some(vec![0.5, -1.0, 0.2])
+        );
+        assert_eq!(parse_vector("0.5 -1\n0.2"), Some(vec![0.5, -1.0, 0.2]));
+        assert_eq!(parse_vector("0.5,-1,0.2"), Some(vec![0.5, -1.0, 0.2]));
+        assert_eq!(parse_vector("no vector"), None);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_semantic_search() {
+        let vault = testdir::testdir!().join("vault");
+        fs::create_dir_all(&vault).unwrap();
+        fs::write(vault.join("Atlas.md"), "1 0 0").unwrap();
+        fs::write(vault.join("Chart.md"), "0.9 0.1 0").unwrap();
+        fs::write(vault.join("Group.md"), "0 0 1").unwrap();
+
+        // an "embedding model" that reads the vectors written into the notes
+        let config = crate::Config {
+            embedding_command: Some(vec![String::from("cat")]),
+            ..Default::default()
+        };
+        let tracker = io::FileTracker::new(&config, vault.clone()).unwrap();
+        let builder = io::HtmlBuilder::new(&config, vault.clone());
+        let index = data::NoteIndex::new(tracker, builder, Default::default()).0;
+
+        let mut search = SemanticSearch::new(&config, &vault).unwrap();
+        assert!(search.prepare(&index).is_none());
+        assert_eq!(search.progress(), (0, 3));
+        while search.step() {}
+        assert!(search.is_ready());
+        assert!(search.failures().is_empty());
+        assert!(vault.join(STATE_FILE).exists());
+
+        let ranking = search.search("1 0.05 0").unwrap();
+        assert_eq!(
+            ranking
+                .iter()
+                .map(|(id, _)| id.as_str())
+                .collect::<Vec<_>>(),
+            vec!["atlas", "chart", "group"]
+        );
+
+        // unchanged notes are not embedded again
+        fs::write(vault.join("Group.md"), "0 1 0").unwrap();
+        let mut search = SemanticSearch::new(&config, &vault).unwrap();
+        search.prepare(&index);
+        assert_eq!(search.progress(), (0, 1));
+    }
+}
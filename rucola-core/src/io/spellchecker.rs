@@ -0,0 +1,351 @@
+use std::{
+    collections::{BTreeSet, HashMap, HashSet},
+    fs, path,
+    rc::Rc,
+};
+
+use itertools::Itertools;
+
+use crate::{data, error, io};
+
+/// The file the ignored words are stored in, relative to the vault.
+const STATE_FILE: &str = ".rucola-spelling.json";
+
+/// The words of a vault that are never reported as misspelled.
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+struct IgnoreList {
+    words: BTreeSet<String>,
+}
+
+impl io::VersionedState for IgnoreList {
+    const VERSION: u32 = 1;
+}
+
+/// What words are checked against.
+#[derive(Debug, Clone)]
+enum Backend {
+    /// An external command like `hunspell -l`, receiving words on stdin and writing the misspelled ones to stdout.
+    Command(Vec<String>),
+    /// All forms of the words of a hunspell dictionary, in lowercase, shared between clones as it can be large.
+    Dictionary(Rc<HashSet<String>>),
+}
+
+/// Checks the spelling of notes with a user-configured command or hunspell dictionary.
+/// Words can be ignored for a vault, they are stored in the vault and have to be loaded before checking.
+#[derive(Debug, Clone)]
+pub struct Spellchecker {
+    /// The command or dictionary to check words with.
+    backend: Backend,
+    /// The file the ignored words are stored in.
+    state_file: io::StateFile<IgnoreList>,
+    /// The ignored words.
+    ignored: IgnoreList,
+}
+
+impl Spellchecker {
+    /// Creates a new spellchecker for the given vault, if a command or a dictionary is configured. The command takes precedence.
+    /// Reads the dictionary, if one is configured.
+    pub fn new(config: &crate::Config, vault_path: &path::Path) -> error::Result<Option<Self>> {
+        let backend = match (&config.spellcheck_command, &config.spellcheck_dictionary) {
+            (Some(command), _) if !command.is_empty() => Backend::Command(command.clone()),
+            (_, Some(dictionary)) => Backend::Dictionary(Rc::new(
+                read_dictionary(dictionary).map_err(|e| e.in_file(dictionary, None))?,
+            )),
+            _ => return Ok(None),
+        };
+
+        Ok(Some(Self {
+            backend,
+            state_file: io::StateFile::new(vault_path.join(STATE_FILE)),
+            ignored: IgnoreList::default(),
+        }))
+    }
+
+    /// Loads the words ignored in the vault, which may have been changed since they were last loaded.
+    pub fn load_ignored(&mut self) -> error::Result<()> {
+        let (ignored, error) = self.state_file.load();
+        self.ignored = ignored;
+        error.map_or(Ok(()), Err)
+    }
+
+    /// Returns all occurrences of misspelled words in the given content of a note, leaving out ignored words.
+    pub fn check(&self, content: &str) -> error::Result<Vec<data::Misspelling>> {
+        let words = data::distinct_words(content)
+            .into_iter()
+            .filter(|word| !self.ignored.words.contains(&word.to_lowercase()))
+            .collect_vec();
+
+        if words.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let misspelled = match &self.backend {
+            Backend::Command(command) => {
+                io::summarizer::run_command(command, None, &words.join("\n"))?
+                    .lines()
+                    .map(str::trim)
+                    .filter(|word| !word.is_empty())
+                    .map(str::to_owned)
+                    .collect_vec()
+            }
+            Backend::Dictionary(dictionary) => words
+                .into_iter()
+                .filter(|word| !is_known(dictionary, word))
+                .map(str::to_owned)
+                .collect_vec(),
+        };
+
+        Ok(data::Misspelling::find_all(content, &misspelled))
+    }
+
+    /// Ignores the given word in all notes of the vault from now on.
+    pub fn ignore(&mut self, word: &str) -> error::Result<()> {
+        self.ignored.words.insert(word.to_lowercase());
+        self.state_file.save(&self.ignored)
+    }
+}
+
+/// Checks if the given word is in the given dictionary, ignoring its case and a possessive `'s`.
+fn is_known(dictionary: &HashSet<String>, word: &str) -> bool {
+    let word = word.to_lowercase();
+    dictionary.contains(&word)
+        || word
+            .strip_suffix("'s")
+            .is_some_and(|stem| dictionary.contains(stem))
+}
+
+/// How the flags of words and affixes are written in a hunspell dictionary.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FlagFormat {
+    /// Every character is a flag.
+    Char,
+    /// Every two characters are a flag (`FLAG long`).
+    Long,
+    /// Flags are numbers separated by commas (`FLAG num`).
+    Number,
+}
+
+impl FlagFormat {
+    /// Splits the given flags into single flags.
+    fn split(self, flags: &str) -> Vec<String> {
+        match self {
+            FlagFormat::Char => flags.chars().map(String::from).collect(),
+            FlagFormat::Long => flags
+                .chars()
+                .chunks(2)
+                .into_iter()
+                .map(|chunk| chunk.collect())
+                .collect(),
+            FlagFormat::Number => flags.split(',').map(str::to_owned).collect(),
+        }
+    }
+}
+
+/// A prefix or suffix rule of a hunspell affix file.
+#[derive(Debug, Clone)]
+struct Affix {
+    /// Wether the affix is added to the start of words rather than their end.
+    prefix: bool,
+    /// Wether the affix can be combined with affixes of the other kind.
+    cross: bool,
+    /// The text removed from the word before adding the affix.
+    strip: String,
+    /// The text of the affix.
+    add: String,
+    /// The condition words have to match for the affix to apply.
+    condition: regex::Regex,
+}
+
+impl Affix {
+    /// Applies the affix to the given word, if it matches its condition.
+    fn apply(&self, word: &str) -> Option<String> {
+        if !self.condition.is_match(word) {
+            return None;
+        }
+
+        if self.prefix {
+            word.strip_prefix(self.strip.as_str())
+                .map(|rest| format!("{}{}", self.add, rest))
+        } else {
+            word.strip_suffix(self.strip.as_str())
+                .map(|rest| format!("{}{}", rest, self.add))
+        }
+    }
+}
+
+/// Reads all words of the hunspell dictionary at the given path, in lowercase.
+/// The affix rules of the `.aff` file next to it are applied to the words, if there is one.
+fn read_dictionary(path: &path::Path) -> error::Result<HashSet<String>> {
+    let dictionary = String::from_utf8_lossy(&fs::read(path)?).into_owned();
+    let affixes = fs::read(path.with_extension("aff"))
+        .map(|affixes| String::from_utf8_lossy(&affixes).into_owned())
+        .unwrap_or_default();
+
+    let (format, affixes) = parse_affixes(&affixes)?;
+    Ok(expand_dictionary(&dictionary, format, &affixes))
+}
+
+/// Parses the flag format and the affix rules of a hunspell affix file, by their flag.
+fn parse_affixes(content: &str) -> error::Result<(FlagFormat, HashMap<String, Vec<Affix>>)> {
+    let mut format = FlagFormat::Char;
+    let mut affixes: HashMap<String, Vec<Affix>> = HashMap::new();
+    // Wether the affixes of each flag can be combined, from their header line.
+    let mut cross = HashMap::new();
+
+    for line in content.lines() {
+        let fields = line.split_whitespace().collect_vec();
+        match fields.as_slice() {
+            ["FLAG", "long", ..] => format = FlagFormat::Long,
+            ["FLAG", "num", ..] => format = FlagFormat::Number,
+            // The header of a group of rules, e.g. `SFX D Y 4`.
+            [kind @ ("PFX" | "SFX"), flag, combine, count]
+                if count.chars().all(|c| c.is_ascii_digit()) =>
+            {
+                cross.insert((*kind, flag.to_string()), *combine == "Y");
+            }
+            // A rule, e.g. `SFX D y ied [^aeiou]y`.
+            [kind @ ("PFX" | "SFX"), flag, strip, add, condition, ..] => {
+                let prefix = *kind == "PFX";
+                let condition = match *condition {
+                    "." => String::new(),
+                    condition if prefix => format!("^{}", condition),
+                    condition => format!("{}$", condition),
+                };
+                // continuation flags of the affix itself are not supported
+                let add = add.split('/').next().unwrap_or_default();
+
+                affixes.entry(flag.to_string()).or_default().push(Affix {
+                    prefix,
+                    cross: cross
+                        .get(&(*kind, flag.to_string()))
+                        .copied()
+                        .unwrap_or_default(),
+                    // `0` stands for nothing
+                    strip: strip.replace('0', ""),
+                    add: add.replace('0', ""),
+                    condition: regex::Regex::new(&condition)?,
+                });
+            }
+            _ => {}
+        }
+    }
+
+    Ok((format, affixes))
+}
+
+/// Returns all words of the given hunspell dictionary in lowercase, together with the forms created by their affixes.
+fn expand_dictionary(
+    content: &str,
+    format: FlagFormat,
+    affixes: &HashMap<String, Vec<Affix>>,
+) -> HashSet<String> {
+    let mut words = HashSet::new();
+
+    // The first line is the number of words.
+    for line in content.lines().skip(1) {
+        let Some(entry) = line.split_whitespace().next() else {
+            continue;
+        };
+        let (word, flags) = entry.split_once('/').unwrap_or((entry, ""));
+
+        let rules = format
+            .split(flags)
+            .iter()
+            .flat_map(|flag| affixes.get(flag))
+            .flatten()
+            .collect_vec();
+
+        let suffixed = rules
+            .iter()
+            .filter(|affix| !affix.prefix)
+            .flat_map(|affix| affix.apply(word).map(|form| (form, affix.cross)))
+            .collect_vec();
+
+        for affix in rules.iter().filter(|affix| affix.prefix) {
+            words.extend(affix.apply(word).map(|form| form.to_lowercase()));
+            // prefixes that can be combined with suffixes apply to suffixed forms as well
+            if affix.cross {
+                words.extend(
+                    suffixed
+                        .iter()
+                        .filter(|(_, cross)| *cross)
+                        .flat_map(|(form, _)| affix.apply(form))
+                        .map(|form| form.to_lowercase()),
+                );
+            }
+        }
+
+        words.extend(suffixed.into_iter().map(|(form, _)| form.to_lowercase()));
+        words.insert(word.to_lowercase());
+    }
+
+    words
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const AFFIXES: &str = "SET UTF-8\n\nPFX U Y 1\nPFX U 0 un .\n\nSFX D Y 2\nSFX D y ied [^aeiou]y\nSFX D 0 ed [^y]\n\nSFX S N 1\nSFX S 0 s .\n";
+    const DICTIONARY: &str = "4\nchart/S\ncarry/D\nlock/UD\nRiemann\n";
+
+    #[test]
+    fn test_expand_dictionary() {
+        let (format, affixes) = parse_affixes(AFFIXES).unwrap();
+        let words = expand_dictionary(DICTIONARY, format, &affixes);
+
+        for word in [
+            "chart", "charts", "carry", "carried", "lock", "locked", "unlock", "unlocked",
+            "riemann",
+        ] {
+            assert!(words.contains(word), "{} is missing", word);
+        }
+        assert!(!words.contains("carryed"));
+        assert_eq!(words.len(), 9);
+    }
+
+    #[test]
+    fn test_check() {
+        let vault = testdir::testdir!();
+        std::fs::write(vault.join("en.dic"), DICTIONARY).unwrap();
+        std::fs::write(vault.join("en.aff"), AFFIXES).unwrap();
+
+        let config = crate::Config {
+            spellcheck_dictionary: Some(vault.join("en.dic")),
+            ..Default::default()
+        };
+        let mut spellchecker = Spellchecker::new(&config, &vault).unwrap().unwrap();
+        spellchecker.load_ignored().unwrap();
+
+        let content = "Charts carried by Riemann's atlsa.\n\nUnlocked atlsa, `atlsa`.";
+        let misspelled = |spellchecker: &Spellchecker| {
+            spellchecker
+                .check(content)
+                .unwrap()
+                .into_iter()
+                .map(|misspelling| (misspelling.word, misspelling.line))
+                .collect_vec()
+        };
+        assert_eq!(
+            misspelled(&spellchecker),
+            vec![
+                (String::from("by"), 1),
+                (String::from("atlsa"), 1),
+                (String::from("atlsa"), 3),
+            ]
+        );
+
+        // ignored words are stored in the vault
+        spellchecker.ignore("By").unwrap();
+        spellchecker.ignore("atlsa").unwrap();
+        let mut spellchecker = Spellchecker::new(&config, &vault).unwrap().unwrap();
+        assert_eq!(misspelled(&spellchecker).len(), 3);
+        spellchecker.load_ignored().unwrap();
+        assert!(misspelled(&spellchecker).is_empty());
+
+        // without a command or dictionary, there is no spellchecker
+        assert!(Spellchecker::new(&Default::default(), &vault)
+            .unwrap()
+            .is_none());
+    }
+}
@@ -0,0 +1,240 @@
+use std::{fs, marker::PhantomData, path};
+
+use crate::{error, io};
+
+/// State rucola persists between sessions, stored with a version number so it can be migrated when its format changes.
+pub trait VersionedState: Default + serde::Serialize + serde::de::DeserializeOwned {
+    /// The version of the current format. Increase whenever the format changes and add a migration from the previous one.
+    const VERSION: u32;
+
+    /// Converts data stored in the given version into the format of the next version.
+    /// Files written before versioning was introduced have version 0.
+    /// Returns `None` if there is no migration, in which case the state is rebuilt from scratch.
+    fn migrate(version: u32, data: serde_json::Value) -> Option<serde_json::Value> {
+        let _ = (version, data);
+        None
+    }
+}
+
+/// The format the state is written in, wrapping the actual data.
+#[derive(serde::Serialize)]
+struct Envelope<'a, T> {
+    version: u32,
+    data: &'a T,
+}
+
+/// A file storing versioned state.
+/// Loading never fails: State that cannot be read or migrated is moved to a backup file and rebuilt from scratch.
+#[derive(Debug, Clone)]
+pub struct StateFile<T> {
+    /// The path of the file.
+    path: path::PathBuf,
+    /// The type of the stored state.
+    state: PhantomData<T>,
+}
+
+impl<T: VersionedState> StateFile<T> {
+    pub fn new(path: path::PathBuf) -> Self {
+        Self {
+            path,
+            state: PhantomData,
+        }
+    }
+
+    /// Loads the stored state, migrating it from older versions if necessary.
+    /// If there is no stored state, returns the default.
+    /// If the stored state cannot be read, it is moved to a backup file and the default is returned together with an error describing what happened.
+    pub fn load(&self) -> (T, Option<error::RucolaError>) {
+        if !self.path.exists() {
+            return (T::default(), None);
+        }
+
+        match self.read() {
+            Ok(state) => (state, None),
+            Err(reason) => {
+                let backup = self.backup_path();
+                let message = match fs::rename(&self.path, &backup) {
+                    Ok(()) => format!(
+                        "{} Rebuilt it from scratch, the old file was kept at {}.",
+                        reason,
+                        backup.display()
+                    ),
+                    Err(e) => format!(
+                        "{} Rebuilt it from scratch, but could not keep the old file: {}",
+                        reason, e
+                    ),
+                };
+                (
+                    T::default(),
+                    Some(error::RucolaError::StateFile(self.path.clone(), message)),
+                )
+            }
+        }
+    }
+
+    /// Writes the state in the current version.
+    /// Writes to a temporary file first, so an interrupted write cannot corrupt the stored state.
+    pub fn save(&self, state: &T) -> error::Result<()> {
+        let content = serde_json::to_string_pretty(&Envelope {
+            version: T::VERSION,
+            data: state,
+        })
+        .map_err(|e| error::RucolaError::StateFile(self.path.clone(), e.to_string()))?;
+
+        // each process writes its own temporary file, so concurrent writes cannot mix
+        io::write_atomically(&self.path, content, io::Durability::default())?;
+        Ok(())
+    }
+
+    /// Reads and migrates the stored state, describing the problem if that is not possible.
+    fn read(&self) -> Result<T, String> {
+        let content = fs::read_to_string(&self.path).map_err(|e| e.to_string())?;
+        let value = serde_json::from_str::<serde_json::Value>(&content)
+            .map_err(|e| format!("The file is corrupted ({}).", e))?;
+
+        // Files without a version predate versioning.
+        let (mut version, mut data) = match value {
+            serde_json::Value::Object(mut object)
+                if object.contains_key("data")
+                    && object.get("version").is_some_and(|v| v.is_u64()) =>
+            {
+                (
+                    object
+                        .get("version")
+                        .and_then(|version| version.as_u64())
+                        .unwrap_or_default() as u32,
+                    object.remove("data").unwrap_or_default(),
+                )
+            }
+            value => (0, value),
+        };
+
+        if version > T::VERSION {
+            return Err(format!(
+                "The file was written by a newer version of rucola (format {}, supported up to {}).",
+                version,
+                T::VERSION
+            ));
+        }
+
+        while version < T::VERSION {
+            data = T::migrate(version, data)
+                .ok_or_else(|| format!("There is no migration from format {}.", version))?;
+            version += 1;
+        }
+
+        serde_json::from_value(data).map_err(|e| format!("The file is invalid ({}).", e))
+    }
+
+    /// Returns a path for the backup of the current file that does not exist yet.
+    fn backup_path(&self) -> path::PathBuf {
+        let timestamp = chrono::Local::now().format("%Y-%m-%d-%H%M%S").to_string();
+        let mut backup = self.sibling(&format!("{}.bak", timestamp));
+        let mut counter = 1;
+        while backup.exists() {
+            backup = self.sibling(&format!("{}-{}.bak", timestamp, counter));
+            counter += 1;
+        }
+        backup
+    }
+
+    /// Returns the path of the file with the given suffix appended to its name.
+    fn sibling(&self, suffix: &str) -> path::PathBuf {
+        let mut name = self.path.file_name().unwrap_or_default().to_os_string();
+        name.push(".");
+        name.push(suffix);
+        self.path.with_file_name(name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{StateFile, VersionedState};
+
+    /// A state whose first version stored only a list of names.
+    #[derive(Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+    struct Names {
+        names: Vec<String>,
+        count: usize,
+    }
+
+    impl VersionedState for Names {
+        const VERSION: u32 = 2;
+
+        fn migrate(version: u32, data: serde_json::Value) -> Option<serde_json::Value> {
+            match version {
+                0 => Some(serde_json::json!({ "names": data })),
+                1 => {
+                    let count = data.get("names")?.as_array()?.len();
+                    let mut data = data;
+                    data.as_object_mut()?.insert("count".into(), count.into());
+                    Some(data)
+                }
+                _ => None,
+            }
+        }
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let file = StateFile::<Names>::new(testdir::testdir!().join("names.json"));
+
+        // Without a file, the default is used.
+        let (state, error) = file.load();
+        assert_eq!(state, Names::default());
+        assert!(error.is_none());
+
+        let state = Names {
+            names: vec!["Atlas".to_owned()],
+            count: 1,
+        };
+        file.save(&state).unwrap();
+        let (loaded, error) = file.load();
+        assert_eq!(loaded, state);
+        assert!(error.is_none());
+    }
+
+    #[test]
+    fn test_migration() {
+        let path = testdir::testdir!().join("names.json");
+        std::fs::write(&path, r#"["Atlas", "Chart"]"#).unwrap();
+
+        let (state, error) = StateFile::<Names>::new(path).load();
+        assert!(error.is_none());
+        assert_eq!(
+            state,
+            Names {
+                names: vec!["Atlas".to_owned(), "Chart".to_owned()],
+                count: 2
+            }
+        );
+    }
+
+    #[test]
+    fn test_rebuild() {
+        let dir = testdir::testdir!();
+        let backups = |dir: &std::path::Path| {
+            std::fs::read_dir(dir)
+                .unwrap()
+                .flatten()
+                .filter(|entry| entry.file_name().to_string_lossy().ends_with(".bak"))
+                .count()
+        };
+
+        // Corrupted files are kept as a backup.
+        let path = dir.join("names.json");
+        std::fs::write(&path, "{ not json").unwrap();
+        let (state, error) = StateFile::<Names>::new(path.clone()).load();
+        assert_eq!(state, Names::default());
+        assert!(error.is_some());
+        assert!(!path.exists());
+        assert_eq!(backups(&dir), 1);
+
+        // So are files of newer versions.
+        std::fs::write(&path, r#"{"version": 3, "data": {}}"#).unwrap();
+        let (state, error) = StateFile::<Names>::new(path.clone()).load();
+        assert_eq!(state, Names::default());
+        assert!(error.is_some());
+        assert_eq!(backups(&dir), 2);
+    }
+}
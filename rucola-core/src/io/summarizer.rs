@@ -0,0 +1,233 @@
+use std::{io::Write, path, process, sync::mpsc};
+
+use crate::error;
+
+/// Where summaries are requested from.
+#[derive(Debug, Clone)]
+enum Backend {
+    /// An external command receiving the prompt and the note on stdin and writing the summary to stdout.
+    Command(Vec<String>),
+    /// An HTTP endpoint following the generate API of ollama.
+    Endpoint {
+        /// The URL to post requests to.
+        url: String,
+        /// The model to request the summary from.
+        model: String,
+    },
+}
+
+/// Requests summaries of notes from a user-configured command or local HTTP endpoint, such as a language model run with ollama.
+/// Rucola itself never contacts any service unless one is configured.
+#[derive(Debug, Clone)]
+pub struct Summarizer {
+    /// The command or endpoint to request summaries from.
+    backend: Backend,
+    /// The instruction placed before the content of the note.
+    prompt: String,
+}
+
+impl Summarizer {
+    /// Creates a new summarizer, if a command or an endpoint is configured. The command takes precedence.
+    pub fn new(config: &crate::Config) -> Option<Self> {
+        let backend = match (&config.summary_command, &config.summary_endpoint) {
+            (Some(command), _) if !command.is_empty() => Backend::Command(command.clone()),
+            (_, Some(url)) => Backend::Endpoint {
+                url: url.to_owned(),
+                model: config.summary_model.clone(),
+            },
+            _ => return None,
+        };
+
+        Some(Self {
+            backend,
+            prompt: config.summary_prompt.clone(),
+        })
+    }
+
+    /// Summarizes the note at the given path with the given content on a separate thread.
+    /// The summary (or a description of the failure) is sent through the returned channel once it is ready.
+    pub fn summarize_in_background(
+        &self,
+        path: path::PathBuf,
+        content: String,
+    ) -> mpsc::Receiver<Result<String, String>> {
+        let (sender, receiver) = mpsc::channel();
+        let summarizer = self.clone();
+        std::thread::spawn(move || {
+            let _ = sender.send(
+                summarizer
+                    .summarize(&path, &content)
+                    .map_err(|e| e.to_string()),
+            );
+            super::wake();
+        });
+        receiver
+    }
+
+    /// Summarizes the note at the given path with the given content, returning the summary as a single line.
+    pub fn summarize(&self, path: &path::Path, content: &str) -> error::Result<String> {
+        let input = if self.prompt.is_empty() {
+            content.to_owned()
+        } else {
+            format!("{}\n\n{}", self.prompt, content)
+        };
+
+        let summary = match &self.backend {
+            Backend::Command(command) => run_command(command, Some(path), &input)?,
+            Backend::Endpoint { url, model } => post_json(
+                url,
+                serde_json::json!({
+                    "model": model,
+                    "prompt": input,
+                    "stream": false,
+                }),
+                error::RucolaError::SummaryError,
+            )?
+            .get("response")
+            .and_then(|response| response.as_str())
+            .map(str::to_owned)
+            .ok_or_else(|| {
+                error::RucolaError::SummaryError(String::from(
+                    "The response of the endpoint contains no summary.",
+                ))
+            })?,
+        };
+
+        let summary = clean(&summary);
+        if summary.is_empty() {
+            return Err(error::RucolaError::SummaryError(String::from(
+                "The summary is empty.",
+            )));
+        }
+        Ok(summary)
+    }
+}
+
+/// Runs the given command with the input on stdin and returns its output. `%p` is replaced by the path of the note, if any.
+pub(super) fn run_command(
+    command: &[String],
+    path: Option<&path::Path>,
+    input: &str,
+) -> error::Result<String> {
+    let mut iter = command.iter();
+    let programm = iter.next().ok_or(error::RucolaError::ApplicationMissing)?;
+
+    let mut cmd = process::Command::new(programm);
+    for arg in iter {
+        match arg.as_str() {
+            "%p" => match path {
+                Some(path) => cmd.arg(path.canonicalize().as_deref().unwrap_or(path)),
+                None => &mut cmd,
+            },
+            _ => cmd.arg(arg),
+        };
+    }
+
+    let mut child = cmd
+        .stdin(process::Stdio::piped())
+        .stdout(process::Stdio::piped())
+        .stderr(process::Stdio::piped())
+        .spawn()?;
+
+    // write on a separate thread, so large notes cannot block on a full output pipe
+    let mut stdin = child.stdin.take();
+    let input = input.to_owned();
+    let writer = std::thread::spawn(move || {
+        if let Some(stdin) = stdin.as_mut() {
+            let _ = stdin.write_all(input.as_bytes());
+        }
+    });
+
+    let output = child.wait_with_output()?;
+    let _ = writer.join();
+
+    if !output.status.success() {
+        return Err(error::RucolaError::ExternalCommand(format!(
+            "{} exited with {}: {}",
+            programm,
+            output.status,
+            String::from_utf8_lossy(&output.stderr)
+                .lines()
+                .next()
+                .unwrap_or_default()
+        )));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+/// Posts the given JSON body to the given URL and returns the JSON response.
+/// Failures are described by the given error variant.
+pub(super) fn post_json(
+    url: &str,
+    body: serde_json::Value,
+    error: fn(String) -> error::RucolaError,
+) -> error::Result<serde_json::Value> {
+    let response = ureq::post(url)
+        .set("content-type", "application/json")
+        .send_string(&body.to_string())
+        .map_err(|e| error(e.to_string()))?
+        .into_string()?;
+
+    serde_json::from_str(&response)
+        .map_err(|e| error(format!("The response of the endpoint is invalid ({}).", e)))
+}
+
+/// Turns the raw output of a model into a single line, dropping its reasoning if it is wrapped in `<think>` tags.
+fn clean(raw: &str) -> String {
+    let raw = match raw.rfind("</think>") {
+        Some(end) => &raw[end + "</think>".len()..],
+        None => raw,
+    };
+
+    raw.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clean() {
+        assert_eq!(
+            clean("  A chart maps\nan open set\n\nto Euclidean space.  "),
+            "A chart maps an open set to Euclidean space."
+        );
+        assert_eq!(
+            clean("<think>\nThe user wants...\n</think>\nCharts are local."),
+            "Charts are local."
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_summarize_command() {
+        let config = crate::Config {
+            summary_command: Some(vec![String::from("head"), String::from("-n1")]),
+            summary_prompt: String::from("Summarize:"),
+            ..Default::default()
+        };
+        let summarizer = Summarizer::new(&config).unwrap();
+
+        // the prompt comes first
+        assert_eq!(
+            summarizer
+                .summarize(path::Path::new("Chart.md"), "# Chart")
+                .unwrap(),
+            "Summarize:"
+        );
+
+        // failing commands are reported
+        let config = crate::Config {
+            summary_command: Some(vec![String::from("false")]),
+            ..Default::default()
+        };
+        assert!(Summarizer::new(&config)
+            .unwrap()
+            .summarize(path::Path::new("Chart.md"), "# Chart")
+            .is_err());
+
+        // without a command or endpoint, there is no summarizer
+        assert!(Summarizer::new(&Default::default()).is_none());
+    }
+}
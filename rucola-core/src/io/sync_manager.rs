@@ -0,0 +1,657 @@
+use std::{collections::HashMap, fs, path, sync::mpsc};
+
+use crate::{error, i18n, io};
+
+/// Name of the file in the vault that stores the state of all files at the last synchronization.
+const STATE_FILE: &str = ".rucola-sync.json";
+
+/// Environment variable the password for the WebDAV server is read from.
+const PASSWORD_VARIABLE: &str = "RUCOLA_SYNC_PASSWORD";
+
+/// The state of a single file at the last synchronization.
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+struct SyncEntry {
+    /// The etag of the remote file.
+    etag: Option<String>,
+    /// The modification time of the local file, in milliseconds since the epoch.
+    modified: Option<u64>,
+}
+
+/// The state of all files at the last synchronization, by their paths relative to the vault.
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+struct SyncState {
+    files: HashMap<String, SyncEntry>,
+}
+
+impl io::VersionedState for SyncState {
+    const VERSION: u32 = 1;
+
+    fn migrate(version: u32, data: serde_json::Value) -> Option<serde_json::Value> {
+        match version {
+            // Before versioning, the file only contained the map of files.
+            0 => Some(serde_json::json!({ "files": data })),
+            _ => None,
+        }
+    }
+}
+
+/// What to do with a single file during a synchronization.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SyncAction {
+    /// Only the local file changed: Upload it.
+    Upload,
+    /// Only the remote file changed: Download it.
+    Download,
+    /// The local file was deleted and the remote file did not change: Delete it as well.
+    DeleteRemote,
+    /// The remote file was deleted and the local file did not change: Delete it as well.
+    DeleteLocal,
+    /// Both files changed: Keep the local file and store the remote version next to it.
+    Conflict,
+    /// Both files were deleted: Only remove the stored state.
+    Forget,
+}
+
+/// The outcome of a completed synchronization.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SyncReport {
+    /// The number of files uploaded to the server.
+    pub uploaded: usize,
+    /// The number of files downloaded from the server.
+    pub downloaded: usize,
+    /// The number of files deleted locally or on the server.
+    pub deleted: usize,
+    /// The paths (relative to the vault) of files that were changed on both sides.
+    /// For each of them, the remote version was stored in a conflict copy next to the local file.
+    pub conflicts: Vec<String>,
+    /// Problems that did not stop the synchronization, such as a stored state that had to be rebuilt.
+    pub warnings: Vec<String>,
+}
+
+/// The current state of the synchronization, as shown in the UI.
+#[derive(Debug, Clone, Default)]
+pub enum SyncStatus {
+    /// No synchronization was started in this session.
+    #[default]
+    Idle,
+    /// A synchronization is running in the background.
+    Running,
+    /// The last synchronization completed at the given time.
+    Done(chrono::DateTime<chrono::Local>, SyncReport),
+    /// The last synchronization failed.
+    Failed,
+}
+
+impl std::fmt::Display for SyncStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SyncStatus::Idle => write!(f, "{}", i18n::tr("sync-idle")),
+            SyncStatus::Running => write!(f, "{}", i18n::tr("sync-running")),
+            SyncStatus::Done(time, report) => write!(
+                f,
+                "{}",
+                i18n::tr_args(
+                    "sync-done",
+                    &[
+                        ("time", time.format("%H:%M").to_string().into()),
+                        ("conflicts", report.conflicts.len().into()),
+                    ]
+                )
+            ),
+            SyncStatus::Failed => write!(f, "{}", i18n::tr("sync-failed")),
+        }
+    }
+}
+
+/// Synchronizes the vault with a folder on a WebDAV server, such as Nextcloud.
+/// Changes are detected by comparing the etags of remote files and the modification times of local files to those at the last synchronization.
+#[derive(Debug, Clone)]
+pub struct SyncManager {
+    /// Path to the vault to synchronize.
+    vault_path: path::PathBuf,
+    /// URL of the remote folder, ending in a slash.
+    url: String,
+    /// User name and password to authenticate with, if any.
+    credentials: Option<(String, String)>,
+    /// How carefully downloaded files are flushed to the disk.
+    durability: io::Durability,
+}
+
+impl SyncManager {
+    /// Creates a new sync manager, if a WebDAV server is configured.
+    pub fn new(config: &crate::Config, vault_path: path::PathBuf) -> Option<Self> {
+        let url = config.sync_url.as_ref()?;
+        Some(Self {
+            vault_path,
+            url: format!("{}/", url.trim_end_matches('/')),
+            credentials: config
+                .sync_user
+                .clone()
+                .map(|user| (user, std::env::var(PASSWORD_VARIABLE).unwrap_or_default())),
+            durability: config.write_durability,
+        })
+    }
+
+    /// Synchronizes the vault on a separate thread, sending the outcome through the returned channel once done.
+    pub fn sync_in_background(&self) -> mpsc::Receiver<Result<SyncReport, String>> {
+        let (sender, receiver) = mpsc::channel();
+        let manager = self.clone();
+        std::thread::spawn(move || {
+            let _ = sender.send(manager.sync().map_err(|e| e.to_string()));
+            super::wake();
+        });
+        receiver
+    }
+
+    /// Synchronizes the vault with the server: Uploads local changes, downloads remote changes and applies deletions on either side.
+    pub fn sync(&self) -> error::Result<SyncReport> {
+        let agent = ureq::Agent::new();
+        let state_file = io::StateFile::<SyncState>::new(self.vault_path.join(STATE_FILE));
+        let (SyncState { files: mut state }, warning) = state_file.load();
+        let local = self.local_files()?;
+        let remote = self.remote_files(&agent)?;
+
+        let mut report = SyncReport {
+            warnings: warning.into_iter().map(|e| e.to_string()).collect(),
+            ..Default::default()
+        };
+
+        for (relative, action) in plan(&local, &remote, &state) {
+            let local_path = self.vault_path.join(&relative);
+            match action {
+                SyncAction::Upload => {
+                    let etag = self.upload(&agent, &relative)?;
+                    state.insert(relative, self.entry(&local_path, etag));
+                    report.uploaded += 1;
+                }
+                SyncAction::Download => {
+                    if let Some(parent) = local_path.parent() {
+                        fs::create_dir_all(parent)?;
+                    }
+                    io::write_atomically(
+                        &local_path,
+                        self.download(&agent, &relative)?,
+                        self.durability,
+                    )?;
+                    state.insert(
+                        relative.clone(),
+                        self.entry(&local_path, remote.get(&relative).cloned()),
+                    );
+                    report.downloaded += 1;
+                }
+                SyncAction::DeleteRemote => {
+                    self.send(&agent, "DELETE", &relative, &[404])?;
+                    state.remove(&relative);
+                    report.deleted += 1;
+                }
+                SyncAction::DeleteLocal => {
+                    fs::remove_file(&local_path)?;
+                    state.remove(&relative);
+                    report.deleted += 1;
+                }
+                SyncAction::Conflict => {
+                    let remote_content = self.download(&agent, &relative)?;
+                    // Identical changes on both sides are no conflict.
+                    if fs::read(&local_path)? != remote_content {
+                        io::write_atomically(
+                            &conflict_path(&local_path),
+                            remote_content,
+                            self.durability,
+                        )?;
+                        report.conflicts.push(relative.clone());
+                    }
+                    let etag = self.upload(&agent, &relative)?;
+                    state.insert(relative, self.entry(&local_path, etag));
+                }
+                SyncAction::Forget => {
+                    state.remove(&relative);
+                }
+            }
+        }
+
+        state_file.save(&SyncState { files: state })?;
+
+        Ok(report)
+    }
+
+    /// Creates the stored state of a file after it was synchronized.
+    fn entry(&self, local_path: &path::Path, etag: Option<String>) -> SyncEntry {
+        SyncEntry {
+            etag,
+            modified: modification_time(local_path),
+        }
+    }
+
+    /// Lists all non-hidden files in the vault with their modification times, by their paths relative to the vault.
+    fn local_files(&self) -> error::Result<HashMap<String, u64>> {
+        let mut files = HashMap::new();
+
+        for entry in ignore::WalkBuilder::new(&self.vault_path)
+            .git_ignore(false)
+            .git_global(false)
+            .git_exclude(false)
+            .build()
+        {
+            let entry = entry?;
+            if !entry
+                .file_type()
+                .is_some_and(|file_type| file_type.is_file())
+            {
+                continue;
+            }
+
+            if let (Ok(relative), Some(modified)) = (
+                entry.path().strip_prefix(&self.vault_path),
+                modification_time(entry.path()),
+            ) {
+                files.insert(
+                    relative
+                        .components()
+                        .map(|component| component.as_os_str().to_string_lossy())
+                        .collect::<Vec<_>>()
+                        .join("/"),
+                    modified,
+                );
+            }
+        }
+
+        Ok(files)
+    }
+
+    /// Lists all non-hidden files in the remote folder with their etags, by their paths relative to the folder.
+    fn remote_files(&self, agent: &ureq::Agent) -> error::Result<HashMap<String, String>> {
+        let mut files = HashMap::new();
+        let mut folders = vec![String::new()];
+
+        while let Some(folder) = folders.pop() {
+            let response = self.request(agent, "PROPFIND", &folder)
+                .set("Depth", "1")
+                .set("Content-Type", "application/xml")
+                .send_string(
+                    r#"<?xml version="1.0"?><d:propfind xmlns:d="DAV:"><d:prop><d:getetag/><d:resourcetype/></d:prop></d:propfind>"#,
+                )
+                .map_err(request_error)?
+                .into_string()?;
+
+            for (href, etag, is_folder) in parse_multistatus(&response)? {
+                let Some(relative) = self.relative_path(&href) else {
+                    continue;
+                };
+                // The folder itself is part of the response, hidden files are not synchronized.
+                if relative == folder
+                    || relative.is_empty()
+                    || relative.split('/').any(|segment| segment.starts_with('.'))
+                {
+                    continue;
+                }
+
+                if is_folder {
+                    folders.push(relative);
+                } else {
+                    files.insert(relative, etag.unwrap_or_default());
+                }
+            }
+        }
+
+        Ok(files)
+    }
+
+    /// Uploads the local file at the given relative path, creating missing folders, and returns the new etag if the server sent one.
+    fn upload(&self, agent: &ureq::Agent, relative: &str) -> error::Result<Option<String>> {
+        // create parent folders from the top, ignoring those that already exist
+        let segments = relative.split('/').collect::<Vec<_>>();
+        for depth in 1..segments.len() {
+            self.send(agent, "MKCOL", &segments[..depth].join("/"), &[405])?;
+        }
+
+        let response = self
+            .request(agent, "PUT", relative)
+            .send_bytes(&fs::read(self.vault_path.join(relative))?)
+            .map_err(request_error)?;
+
+        Ok(response.header("ETag").map(normalize_etag))
+    }
+
+    /// Downloads the remote file at the given relative path.
+    fn download(&self, agent: &ureq::Agent, relative: &str) -> error::Result<Vec<u8>> {
+        let mut content = Vec::new();
+        std::io::Read::read_to_end(
+            &mut self
+                .request(agent, "GET", relative)
+                .call()
+                .map_err(request_error)?
+                .into_reader(),
+            &mut content,
+        )?;
+        Ok(content)
+    }
+
+    /// Sends a request without body, accepting the given error codes as success.
+    fn send(
+        &self,
+        agent: &ureq::Agent,
+        method: &str,
+        relative: &str,
+        accepted: &[u16],
+    ) -> error::Result<()> {
+        match self.request(agent, method, relative).call() {
+            Ok(_) => Ok(()),
+            Err(ureq::Error::Status(code, _)) if accepted.contains(&code) => Ok(()),
+            Err(e) => Err(request_error(e)),
+        }
+    }
+
+    /// Prepares an authenticated request for the given path relative to the remote folder.
+    fn request(&self, agent: &ureq::Agent, method: &str, relative: &str) -> ureq::Request {
+        let url = format!(
+            "{}{}",
+            self.url,
+            relative
+                .split('/')
+                .map(percent_encode)
+                .collect::<Vec<_>>()
+                .join("/")
+        );
+        let request = agent.request(method, &url);
+        match &self.credentials {
+            Some((user, password)) => request.set(
+                "Authorization",
+                &format!(
+                    "Basic {}",
+                    base64::Engine::encode(
+                        &base64::engine::general_purpose::STANDARD,
+                        format!("{}:{}", user, password)
+                    )
+                ),
+            ),
+            None => request,
+        }
+    }
+
+    /// Converts a href of a PROPFIND response into a path relative to the remote folder.
+    fn relative_path(&self, href: &str) -> Option<String> {
+        let href = percent_decode(href_path(href));
+        let base = percent_decode(href_path(&self.url));
+        href.strip_prefix(&base)
+            .map(|relative| relative.trim_end_matches('/').to_owned())
+    }
+}
+
+/// Decides what to do with every file, based on the local files with their modification times, the remote files with their etags and the state at the last synchronization.
+fn plan(
+    local: &HashMap<String, u64>,
+    remote: &HashMap<String, String>,
+    state: &HashMap<String, SyncEntry>,
+) -> Vec<(String, SyncAction)> {
+    let mut paths = local
+        .keys()
+        .chain(remote.keys())
+        .chain(state.keys())
+        .collect::<Vec<_>>();
+    paths.sort();
+    paths.dedup();
+
+    paths
+        .into_iter()
+        .filter_map(|path| {
+            let entry = state.get(path);
+            let local_changed = local
+                .get(path)
+                .is_some_and(|modified| entry.is_none_or(|e| e.modified != Some(*modified)));
+            let remote_changed = remote
+                .get(path)
+                .is_some_and(|etag| entry.is_none_or(|e| e.etag.as_ref() != Some(etag)));
+
+            let action = match (local.contains_key(path), remote.contains_key(path)) {
+                (false, false) => SyncAction::Forget,
+                _ if local_changed && remote_changed => SyncAction::Conflict,
+                _ if local_changed => SyncAction::Upload,
+                _ if remote_changed => SyncAction::Download,
+                (false, true) => SyncAction::DeleteRemote,
+                (true, false) => SyncAction::DeleteLocal,
+                (true, true) => return None,
+            };
+
+            Some((path.clone(), action))
+        })
+        .collect()
+}
+
+/// Parses a WebDAV multistatus response into the hrefs, etags and wether the resource is a folder.
+fn parse_multistatus(xml: &str) -> error::Result<Vec<(String, Option<String>, bool)>> {
+    let document = roxmltree::Document::parse(xml)
+        .map_err(|e| error::RucolaError::SyncError(e.to_string()))?;
+
+    /// Finds the first descendant of a node with the given name in the DAV namespace.
+    fn find<'a, 'input>(
+        node: roxmltree::Node<'a, 'input>,
+        name: &str,
+    ) -> Option<roxmltree::Node<'a, 'input>> {
+        node.descendants().find(|child| {
+            child.tag_name().name() == name && child.tag_name().namespace() == Some("DAV:")
+        })
+    }
+
+    Ok(document
+        .root()
+        .descendants()
+        .filter(|node| {
+            node.tag_name().name() == "response" && node.tag_name().namespace() == Some("DAV:")
+        })
+        .filter_map(|response| {
+            let href = find(response, "href")?.text()?.trim().to_owned();
+            let etag = find(response, "getetag")
+                .and_then(|etag| etag.text())
+                .map(normalize_etag);
+            let is_folder = find(response, "collection").is_some();
+            Some((href, etag, is_folder))
+        })
+        .collect())
+}
+
+/// Removes quotes and weakness markers from an etag.
+fn normalize_etag(etag: &str) -> String {
+    etag.trim()
+        .trim_start_matches("W/")
+        .trim_matches('"')
+        .to_owned()
+}
+
+/// Returns the path component of a URL, or the given string if it is no full URL.
+fn href_path(href: &str) -> &str {
+    match href.split_once("://") {
+        Some((_scheme, rest)) => rest.find('/').map(|index| &rest[index..]).unwrap_or("/"),
+        None => href,
+    }
+}
+
+/// Percent-encodes a single path segment.
+fn percent_encode(segment: &str) -> String {
+    segment
+        .bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                (byte as char).to_string()
+            }
+            _ => format!("%{:02X}", byte),
+        })
+        .collect()
+}
+
+/// Decodes all percent-encoded bytes in a path.
+fn percent_decode(path: &str) -> String {
+    let bytes = path.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+    while index < bytes.len() {
+        match (bytes[index], path.get(index + 1..index + 3)) {
+            (b'%', Some(hex)) if u8::from_str_radix(hex, 16).is_ok() => {
+                decoded.push(u8::from_str_radix(hex, 16).unwrap_or_default());
+                index += 3;
+            }
+            (byte, _) => {
+                decoded.push(byte);
+                index += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+/// Returns the path to store the remote version of a conflicting file at, next to the local file.
+fn conflict_path(path: &path::Path) -> path::PathBuf {
+    let stem = path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let timestamp = chrono::Local::now().format("%Y-%m-%d-%H%M%S");
+    path.with_file_name(match path.extension() {
+        Some(extension) => format!(
+            "{} (conflict {}).{}",
+            stem,
+            timestamp,
+            extension.to_string_lossy()
+        ),
+        None => format!("{} (conflict {})", stem, timestamp),
+    })
+}
+
+/// Returns the modification time of a file in milliseconds since the epoch.
+fn modification_time(path: &path::Path) -> Option<u64> {
+    fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+        .and_then(|modified| modified.duration_since(std::time::UNIX_EPOCH).ok())
+        .map(|duration| duration.as_millis() as u64)
+}
+
+/// Converts a failed request into an error.
+fn request_error(e: ureq::Error) -> error::RucolaError {
+    error::RucolaError::SyncError(match e {
+        ureq::Error::Status(code, response) => {
+            format!(
+                "{} {} ({})",
+                code,
+                response.status_text(),
+                response.get_url()
+            )
+        }
+        ureq::Error::Transport(transport) => transport.to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::{SyncAction, SyncEntry};
+
+    #[test]
+    fn test_plan() {
+        let entry = |etag: &str, modified: u64| SyncEntry {
+            etag: Some(etag.to_owned()),
+            modified: Some(modified),
+        };
+
+        let state = HashMap::from([
+            ("same.md".to_owned(), entry("a", 1)),
+            ("local.md".to_owned(), entry("a", 1)),
+            ("remote.md".to_owned(), entry("a", 1)),
+            ("both.md".to_owned(), entry("a", 1)),
+            ("deleted-local.md".to_owned(), entry("a", 1)),
+            ("deleted-remote.md".to_owned(), entry("a", 1)),
+            ("deleted-both.md".to_owned(), entry("a", 1)),
+            ("deleted-changed.md".to_owned(), entry("a", 1)),
+        ]);
+
+        let local = HashMap::from([
+            ("same.md".to_owned(), 1),
+            ("local.md".to_owned(), 2),
+            ("remote.md".to_owned(), 1),
+            ("both.md".to_owned(), 2),
+            ("deleted-remote.md".to_owned(), 1),
+            ("new-local.md".to_owned(), 1),
+        ]);
+
+        let remote = HashMap::from([
+            ("same.md".to_owned(), "a".to_owned()),
+            ("local.md".to_owned(), "a".to_owned()),
+            ("remote.md".to_owned(), "b".to_owned()),
+            ("both.md".to_owned(), "b".to_owned()),
+            ("deleted-local.md".to_owned(), "a".to_owned()),
+            ("deleted-changed.md".to_owned(), "b".to_owned()),
+            ("new-remote.md".to_owned(), "a".to_owned()),
+        ]);
+
+        let plan = super::plan(&local, &remote, &state)
+            .into_iter()
+            .collect::<HashMap<_, _>>();
+
+        assert_eq!(
+            plan,
+            HashMap::from([
+                ("local.md".to_owned(), SyncAction::Upload),
+                ("remote.md".to_owned(), SyncAction::Download),
+                ("both.md".to_owned(), SyncAction::Conflict),
+                ("deleted-local.md".to_owned(), SyncAction::DeleteRemote),
+                ("deleted-remote.md".to_owned(), SyncAction::DeleteLocal),
+                ("deleted-both.md".to_owned(), SyncAction::Forget),
+                ("deleted-changed.md".to_owned(), SyncAction::Download),
+                ("new-local.md".to_owned(), SyncAction::Upload),
+                ("new-remote.md".to_owned(), SyncAction::Download),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_state_migration() {
+        // State files written before versioning only contained the map of files.
+        let path = testdir::testdir!().join(super::STATE_FILE);
+        std::fs::write(&path, r#"{"Atlas.md": {"etag": "a", "modified": 1}}"#).unwrap();
+
+        let (state, error) = crate::io::StateFile::<super::SyncState>::new(path).load();
+        assert!(error.is_none());
+        assert_eq!(
+            state.files,
+            HashMap::from([(
+                "Atlas.md".to_owned(),
+                SyncEntry {
+                    etag: Some("a".to_owned()),
+                    modified: Some(1)
+                }
+            )])
+        );
+    }
+
+    #[test]
+    fn test_parse_multistatus() {
+        let xml = r#"<?xml version="1.0"?>
+<d:multistatus xmlns:d="DAV:">
+  <d:response>
+    <d:href>/remote.php/dav/files/user/notes/</d:href>
+    <d:propstat><d:prop><d:resourcetype><d:collection/></d:resourcetype></d:prop></d:propstat>
+  </d:response>
+  <d:response>
+    <d:href>/remote.php/dav/files/user/notes/My%20Note.md</d:href>
+    <d:propstat><d:prop><d:getetag>"5f3a"</d:getetag><d:resourcetype/></d:prop></d:propstat>
+  </d:response>
+</d:multistatus>"#;
+
+        let entries = super::parse_multistatus(xml).unwrap();
+        assert_eq!(entries.len(), 2);
+        assert!(entries[0].2);
+        assert_eq!(entries[1].1.as_deref(), Some("5f3a"));
+
+        let config = crate::Config {
+            sync_url: Some("https://cloud.example.com/remote.php/dav/files/user/notes".to_owned()),
+            ..Default::default()
+        };
+        let manager = super::SyncManager::new(&config, std::path::PathBuf::new()).unwrap();
+        assert_eq!(
+            manager.relative_path(&entries[1].0).as_deref(),
+            Some("My Note.md")
+        );
+        assert_eq!(manager.relative_path(&entries[0].0).as_deref(), Some(""));
+        assert_eq!(super::percent_encode("My Note.md"), "My%20Note.md");
+    }
+}
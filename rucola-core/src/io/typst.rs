@@ -0,0 +1,241 @@
+use std::path;
+
+use comrak::nodes::{AstNode, ListType, NodeValue};
+
+/// The start of every typst slide deck, setting up polylux.
+const PREAMBLE: &str = r#"#import "@preview/polylux:0.4.0": *
+
+#set page(paper: "presentation-16-9", margin: 2cm)
+#set text(size: 22pt)
+"#;
+
+/// Imported by slide decks containing math, as notes write math in LaTeX.
+const MATH_IMPORT: &str = "#import \"@preview/mitex:0.2.5\": *\n";
+
+/// Characters that have a meaning in typst markup and are escaped within text.
+const SPECIAL: &str = "\\#*_`$<>@[]~/=-+";
+
+/// Writes a typst slide deck using polylux, with a slide for each of the given slides in markdown.
+/// Images are referenced from the root of the typst project, which has to be the given vault.
+/// Relative paths of images are resolved from the given folder of the note.
+pub fn slide_deck(slides: &[String], note_folder: &path::Path, vault_path: &path::Path) -> String {
+    let options = comrak::Options {
+        extension: comrak::ExtensionOptions::builder()
+            .wikilinks_title_after_pipe(true)
+            .math_dollars(true)
+            .table(true)
+            .build(),
+        ..Default::default()
+    };
+
+    let mut writer = Writer {
+        math: false,
+        note_folder,
+        vault_path,
+    };
+
+    let mut deck = String::new();
+    for slide in slides {
+        let arena = comrak::Arena::new();
+        let root = comrak::parse_document(&arena, slide, &options);
+        deck.push_str(&format!("#slide[\n{}\n]\n\n", writer.block(root)));
+    }
+
+    format!(
+        "{}{}\n{}",
+        PREAMBLE,
+        if writer.math { MATH_IMPORT } else { "" },
+        deck
+    )
+}
+
+/// Converts markdown into typst markup.
+struct Writer<'a> {
+    /// Wether any math has been written, which needs another package.
+    math: bool,
+    /// The folder of the note, which relative paths start from.
+    note_folder: &'a path::Path,
+    /// The vault, which is the root of the typst project.
+    vault_path: &'a path::Path,
+}
+
+impl Writer<'_> {
+    /// Writes the given block and the blocks within it.
+    fn block<'a>(&mut self, node: &'a AstNode<'a>) -> String {
+        match &node.data.borrow().value {
+            NodeValue::Document => self.blocks(node, "\n\n"),
+            NodeValue::Heading(heading) => format!(
+                "{} {}",
+                "=".repeat(heading.level as usize),
+                self.inlines(node)
+            ),
+            NodeValue::Paragraph => self.inlines(node),
+            NodeValue::List(list) => {
+                let marker = match list.list_type {
+                    ListType::Bullet => "- ",
+                    ListType::Ordered => "+ ",
+                };
+                node.children()
+                    .map(|item| {
+                        // the content of an item continues on indented lines
+                        format!(
+                            "{}{}",
+                            marker,
+                            self.blocks(item, "\n").replace('\n', "\n  ")
+                        )
+                    })
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            }
+            NodeValue::CodeBlock(code) => {
+                let fence = "`".repeat(longest_run(&code.literal, '`').max(2) + 1);
+                format!(
+                    "{}{}\n{}{}",
+                    fence,
+                    code.info.split_whitespace().next().unwrap_or_default(),
+                    code.literal,
+                    fence
+                )
+            }
+            NodeValue::BlockQuote => {
+                format!("#quote(block: true)[\n{}\n]", self.blocks(node, "\n\n"))
+            }
+            NodeValue::Table(table) => {
+                let cells = node
+                    .children()
+                    .flat_map(|row| row.children())
+                    .map(|cell| format!("[{}]", self.inlines(cell)))
+                    .collect::<Vec<_>>();
+                format!(
+                    "#table(columns: {}, {})",
+                    table.alignments.len(),
+                    cells.join(", ")
+                )
+            }
+            _ => String::new(),
+        }
+    }
+
+    /// Writes the blocks within the given node, separated by the given separator.
+    fn blocks<'a>(&mut self, node: &'a AstNode<'a>, separator: &str) -> String {
+        node.children()
+            .map(|child| self.block(child))
+            .filter(|block| !block.is_empty())
+            .collect::<Vec<_>>()
+            .join(separator)
+    }
+
+    /// Writes the inline content within the given node.
+    fn inlines<'a>(&mut self, node: &'a AstNode<'a>) -> String {
+        node.children().map(|child| self.inline(child)).collect()
+    }
+
+    /// Writes the given inline element and the elements within it.
+    fn inline<'a>(&mut self, node: &'a AstNode<'a>) -> String {
+        match &node.data.borrow().value {
+            NodeValue::Text(text) => escape(text),
+            NodeValue::SoftBreak => String::from(" "),
+            NodeValue::LineBreak => String::from(" \\\n"),
+            NodeValue::Code(code) if code.literal.contains('`') => {
+                format!("#raw({})", string(&code.literal))
+            }
+            NodeValue::Code(code) => format!("`{}`", code.literal),
+            NodeValue::Emph => format!("#emph[{}]", self.inlines(node)),
+            NodeValue::Strong => format!("#strong[{}]", self.inlines(node)),
+            NodeValue::Link(link) => {
+                format!("#link({})[{}]", string(&link.url), self.inlines(node))
+            }
+            NodeValue::Image(image) => match self.image_path(&image.url) {
+                Some(path) => format!("#image({})", string(&path)),
+                None => self.inlines(node),
+            },
+            NodeValue::Math(math) => {
+                self.math = true;
+                if math.display_math {
+                    format!("#mitex({})", string(&math.literal))
+                } else {
+                    format!("#mi({})", string(&math.literal))
+                }
+            }
+            NodeValue::HtmlInline(_) => String::new(),
+            _ => self.inlines(node),
+        }
+    }
+
+    /// Returns the path of the image at the given URL from the root of the typst project, if it is a file within the vault.
+    fn image_path(&self, url: &str) -> Option<String> {
+        if url.contains("://") {
+            return None;
+        }
+
+        // resolve the path within the vault, which it must not leave
+        let path = self.note_folder.join(url);
+        let mut parts = Vec::new();
+        for component in path.strip_prefix(self.vault_path).ok()?.components() {
+            match component {
+                path::Component::Normal(part) => parts.push(part.to_string_lossy()),
+                path::Component::ParentDir => {
+                    parts.pop()?;
+                }
+                _ => {}
+            }
+        }
+
+        Some(parts.iter().map(|part| format!("/{}", part)).collect())
+    }
+}
+
+/// Escapes all characters of the given text that have a meaning in typst markup.
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if SPECIAL.contains(c) {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// Writes the given text as a typst string literal.
+fn string(text: &str) -> String {
+    format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// Returns the length of the longest run of the given character in the given text.
+fn longest_run(text: &str, c: char) -> usize {
+    text.split(|other| other != c)
+        .map(str::len)
+        .max()
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_slide_deck() {
+        let vault = path::Path::new("vault");
+        let deck = slide_deck(
+            &[
+                String::from("# Groups\nA *group* is a [set](https://sets.org) with $a \\cdot b$.\n\n![Cayley](../img/cayley.png)"),
+                String::from("## Examples\n- the integers `Z`\n- [[Symmetric Group|permutations]]\n  1. of #3 elements\n\n```rust\nlet x = 1;\n```"),
+            ],
+            &vault.join("algebra"),
+            vault,
+        );
+
+        assert!(deck.starts_with(PREAMBLE));
+        assert!(deck.contains(MATH_IMPORT));
+        assert!(deck.contains(
+            "#slide[\n= Groups\n\nA #emph[group] is a #link(\"https://sets.org\")[set] with #mi(\"a \\\\cdot b\").\n\n#image(\"/img/cayley.png\")\n]\n"
+        ));
+        assert!(deck.contains(
+            "#slide[\n== Examples\n\n- the integers `Z`\n- permutations\n  + of \\#3 elements\n\n```rust\nlet x = 1;\n```\n]\n"
+        ));
+
+        // without math, the package for it is left out
+        assert!(!slide_deck(&[String::from("# Plain")], vault, vault).contains(MATH_IMPORT));
+    }
+}
@@ -0,0 +1,335 @@
+use std::{
+    fmt, fs,
+    io::Write,
+    path,
+    sync::{atomic, Arc, Mutex, Weak},
+    thread, time,
+};
+
+use crate::error;
+
+/// The file in the vault that names the instance of rucola allowed to write to it.
+const LOCK_FILE: &str = ".rucola.lock";
+
+/// How often the instance holding the lock confirms that it is still running.
+const REFRESH_INTERVAL: time::Duration = time::Duration::from_secs(15);
+
+/// Number of seconds after which a lock that was not confirmed is considered abandoned, e.g. by a crashed instance.
+const STALE_AFTER: i64 = 60;
+
+/// Counts the locks created by this process, so each has its own id.
+static COUNTER: atomic::AtomicUsize = atomic::AtomicUsize::new(0);
+
+/// The instance of rucola holding the lock, as stored in the lock file.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+struct Owner {
+    /// Identifies the instance.
+    id: String,
+    /// The process id of the instance.
+    pid: u32,
+    /// When the instance acquired the lock, in seconds since the unix epoch.
+    since: i64,
+    /// When the instance last confirmed that it is still running, in seconds since the unix epoch.
+    refreshed: i64,
+}
+
+impl Owner {
+    /// Wether the owner stopped confirming that it is still running.
+    fn is_stale(&self) -> bool {
+        chrono::Local::now().timestamp() - self.refreshed > STALE_AFTER
+    }
+}
+
+impl fmt::Display for Owner {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "process {}", self.pid)?;
+        if let Some(since) = chrono::DateTime::from_timestamp(self.since, 0) {
+            write!(
+                f,
+                " since {}",
+                since.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M")
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// The state shared by all clones of a lock and the thread refreshing it.
+#[derive(Debug)]
+struct Inner {
+    /// The path of the lock file.
+    path: path::PathBuf,
+    /// This instance, as written to the lock file when holding the lock.
+    owner: Owner,
+    /// Wether this instance holds the lock and may write to the vault.
+    writable: atomic::AtomicBool,
+    /// Wether the lock was taken over by another instance since this was last checked.
+    lost: atomic::AtomicBool,
+    /// The other instance holding the lock, if this one does not.
+    holder: Mutex<Option<Owner>>,
+}
+
+impl Inner {
+    /// Reads the owner of the lock from the lock file, if there is a valid one.
+    fn read(&self) -> Option<Owner> {
+        fs::read_to_string(&self.path)
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+    }
+
+    /// Writes this instance to the lock file, confirming it is still running.
+    /// Writes atomically, so other instances never read a partial lock file.
+    fn write(&self) -> error::Result<()> {
+        let owner = Owner {
+            refreshed: chrono::Local::now().timestamp(),
+            ..self.owner.clone()
+        };
+        let content = serde_json::to_string(&owner)
+            .map_err(|e| error::RucolaError::StateFile(self.path.clone(), e.to_string()))?;
+
+        super::write_atomically(&self.path, content, super::Durability::default())?;
+        Ok(())
+    }
+
+    /// Tries to create the lock file, failing if it already exists, so only one of several starting instances acquires the lock.
+    /// Returns wether the lock file was created.
+    fn create(&self) -> error::Result<bool> {
+        let owner = Owner {
+            refreshed: chrono::Local::now().timestamp(),
+            ..self.owner.clone()
+        };
+        let content = serde_json::to_string(&owner)
+            .map_err(|e| error::RucolaError::StateFile(self.path.clone(), e.to_string()))?;
+
+        match fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&self.path)
+        {
+            Ok(mut file) => {
+                file.write_all(content.as_bytes())?;
+                Ok(true)
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => Ok(false),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Marks this instance as holding the lock, or as read-only because the given other instance holds it.
+    fn set_holder(&self, holder: Option<Owner>) {
+        let writable = holder.is_none();
+        if self.writable.swap(writable, atomic::Ordering::SeqCst) && !writable {
+            self.lost.store(true, atomic::Ordering::SeqCst);
+        }
+        if let Ok(mut current) = self.holder.lock() {
+            // the holder is shown in the ui, which is woken up to show changes
+            if current.as_ref().map(|owner| &owner.id) != holder.as_ref().map(|owner| &owner.id) {
+                super::wake();
+            }
+            *current = holder;
+        }
+    }
+
+    /// Brings the state of this instance up to date with the lock file:
+    ///  - While holding the lock, confirms that this instance is still running, or becomes read-only if another instance took over.
+    ///  - While read-only, acquires the lock once the other instance released or abandoned it.
+    fn check(&self) -> error::Result<()> {
+        match self.read() {
+            Some(owner) if owner.id == self.owner.id => {
+                self.write()?;
+                self.set_holder(None);
+            }
+            Some(owner) if !owner.is_stale() => self.set_holder(Some(owner)),
+            // abandoned locks are replaced, released ones created anew
+            Some(_) => {
+                self.write()?;
+                self.set_holder(None);
+            }
+            None => {
+                if self.create()? {
+                    self.set_holder(None);
+                } else {
+                    self.set_holder(self.read());
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Drop for Inner {
+    fn drop(&mut self) {
+        // only release the lock if no other instance took it over in the meantime
+        if self.read().is_some_and(|owner| owner.id == self.owner.id) {
+            let _ = fs::remove_file(&self.path);
+        }
+    }
+}
+
+/// An advisory lock on a vault, so only one of several running instances of rucola writes to it at a time.
+/// The lock is stored in a file in the vault, which the instance holding it refreshes regularly. Locks that are no longer refreshed are taken over.
+/// Instances that do not hold the lock are read-only until the lock is released or they take it over.
+/// The lock is released once all clones of it are dropped.
+#[derive(Debug, Clone)]
+pub struct VaultLock {
+    inner: Arc<Inner>,
+}
+
+impl VaultLock {
+    /// Acquires the lock of the given vault, unless another running instance holds it.
+    /// In that case, the lock is only taken over if requested, and read-only otherwise.
+    pub fn acquire(vault_path: &path::Path, take_over: bool) -> error::Result<Self> {
+        let now = chrono::Local::now();
+        let pid = std::process::id();
+        let inner = Arc::new(Inner {
+            path: vault_path.join(LOCK_FILE),
+            owner: Owner {
+                id: format!(
+                    "{}-{}-{}",
+                    pid,
+                    now.timestamp_subsec_nanos(),
+                    COUNTER.fetch_add(1, atomic::Ordering::SeqCst)
+                ),
+                pid,
+                since: now.timestamp(),
+                refreshed: now.timestamp(),
+            },
+            writable: atomic::AtomicBool::new(false),
+            lost: atomic::AtomicBool::new(false),
+            holder: Mutex::new(None),
+        });
+
+        if take_over {
+            inner.write()?;
+            inner.set_holder(None);
+        } else {
+            inner.check()?;
+        }
+
+        // refresh the lock in the background, so it is kept while e.g. an editor blocks the ui
+        let weak = Arc::downgrade(&inner);
+        thread::spawn(move || Self::refresh_in_background(weak));
+
+        Ok(Self { inner })
+    }
+
+    /// Refreshes the lock regularly until all clones of it are dropped.
+    fn refresh_in_background(inner: Weak<Inner>) {
+        loop {
+            thread::sleep(REFRESH_INTERVAL);
+            match inner.upgrade() {
+                Some(inner) => {
+                    let _ = inner.check();
+                }
+                None => return,
+            }
+        }
+    }
+
+    /// Wether this instance holds the lock and may write to the vault.
+    pub fn is_writable(&self) -> bool {
+        self.inner.writable.load(atomic::Ordering::SeqCst)
+    }
+
+    /// Describes the other instance holding the lock, if this one does not.
+    pub fn holder(&self) -> Option<String> {
+        self.inner
+            .holder
+            .lock()
+            .ok()
+            .and_then(|holder| holder.as_ref().map(|holder| holder.to_string()))
+    }
+
+    /// Returns an error naming the instance holding the lock if this one does not.
+    pub fn ensure_writable(&self) -> error::Result<()> {
+        if self.is_writable() {
+            Ok(())
+        } else {
+            Err(error::RucolaError::ReadOnly(
+                self.holder()
+                    .unwrap_or_else(|| String::from("another instance")),
+            ))
+        }
+    }
+
+    /// Takes over the lock from the instance holding it, which becomes read-only once it notices.
+    pub fn take_over(&self) -> error::Result<()> {
+        self.inner.write()?;
+        self.inner.set_holder(None);
+        Ok(())
+    }
+
+    /// Returns an error naming the instance that took over the lock from this one, once after it happened.
+    pub fn take_lost(&self) -> error::Result<()> {
+        if self.inner.lost.swap(false, atomic::Ordering::SeqCst) {
+            self.ensure_writable()
+        } else {
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lock() {
+        let vault = testdir::testdir!();
+
+        let first = VaultLock::acquire(&vault, false).unwrap();
+        assert!(first.is_writable());
+        assert!(vault.join(LOCK_FILE).exists());
+
+        // a second instance is read-only while the first is running
+        let second = VaultLock::acquire(&vault, false).unwrap();
+        assert!(!second.is_writable());
+        assert!(second
+            .holder()
+            .unwrap()
+            .starts_with(&format!("process {}", std::process::id())));
+        assert!(matches!(
+            second.ensure_writable(),
+            Err(error::RucolaError::ReadOnly(_))
+        ));
+
+        // taking over makes the first instance read-only once it checks the lock
+        second.take_over().unwrap();
+        assert!(second.is_writable());
+        first.inner.check().unwrap();
+        assert!(!first.is_writable());
+        assert!(first.take_lost().is_err());
+        assert!(first.take_lost().is_ok());
+
+        // the read-only instance does not release the lock of the other one
+        drop(first);
+        assert!(vault.join(LOCK_FILE).exists());
+
+        // once released, the lock is acquired by the next instance
+        drop(second);
+        assert!(!vault.join(LOCK_FILE).exists());
+        assert!(VaultLock::acquire(&vault, false).unwrap().is_writable());
+    }
+
+    #[test]
+    fn test_stale_lock() {
+        let vault = testdir::testdir!();
+
+        // a lock left behind by a crashed instance
+        let crashed = Owner {
+            id: String::from("crashed"),
+            pid: 1,
+            since: 0,
+            refreshed: chrono::Local::now().timestamp() - 2 * STALE_AFTER,
+        };
+        std::fs::write(
+            vault.join(LOCK_FILE),
+            serde_json::to_string(&crashed).unwrap(),
+        )
+        .unwrap();
+
+        let lock = VaultLock::acquire(&vault, false).unwrap();
+        assert!(lock.is_writable());
+        assert!(lock.holder().is_none());
+    }
+}
@@ -0,0 +1,122 @@
+use std::{
+    cell::RefCell,
+    collections::{BTreeMap, HashMap},
+    path,
+    rc::Rc,
+};
+
+use crate::{error, io};
+
+/// The file the visit counts are stored in, relative to the vault.
+const STATE_FILE: &str = ".rucola-visits.json";
+
+/// How often each note of a vault has been opened, by the id of the note.
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+struct VisitCounts {
+    visits: BTreeMap<String, usize>,
+}
+
+impl io::VersionedState for VisitCounts {
+    const VERSION: u32 = 1;
+}
+
+/// Counts how often each note has been opened, so frequently used notes can be ranked higher.
+/// The counts are stored in the vault and shared between clones.
+#[derive(Debug, Clone)]
+pub struct Visits {
+    /// The file the counts are stored in.
+    state_file: io::StateFile<VisitCounts>,
+    /// The counts, shared between clones.
+    counts: Rc<RefCell<VisitCounts>>,
+}
+
+impl Visits {
+    /// Loads the visit counts of the given vault.
+    /// If they cannot be read, no note has been visited and the error describing what happened is returned as well.
+    pub fn load(vault_path: &path::Path) -> (Self, Option<error::RucolaError>) {
+        let state_file = io::StateFile::new(vault_path.join(STATE_FILE));
+        let (counts, error) = state_file.load();
+        (
+            Self {
+                state_file,
+                counts: Rc::new(RefCell::new(counts)),
+            },
+            error,
+        )
+    }
+
+    /// Counts a visit of the note with the given id and stores the counts.
+    pub fn record(&self, id: &str) -> error::Result<()> {
+        *self
+            .counts
+            .borrow_mut()
+            .visits
+            .entry(id.to_owned())
+            .or_default() += 1;
+        self.state_file.save(&self.counts.borrow())
+    }
+
+    /// Returns how often the note with the given id has been opened.
+    pub fn count(&self, id: &str) -> usize {
+        self.counts
+            .borrow()
+            .visits
+            .get(id)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Returns the visit counts of all notes that have been opened, by their id.
+    pub fn counts(&self) -> HashMap<String, usize> {
+        self.counts
+            .borrow()
+            .visits
+            .iter()
+            .map(|(id, count)| (id.clone(), *count))
+            .collect()
+    }
+
+    /// Moves the visits of the note with the given old id to its new id after it was renamed or moved.
+    pub fn rename_note(&self, old_id: &str, new_id: &str) -> error::Result<()> {
+        if old_id == new_id {
+            return Ok(());
+        }
+        let mut counts = self.counts.borrow_mut();
+        let Some(count) = counts.visits.remove(old_id) else {
+            return Ok(());
+        };
+        *counts.visits.entry(new_id.to_owned()).or_default() += count;
+        drop(counts);
+        self.state_file.save(&self.counts.borrow())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_visits() {
+        let vault = testdir::testdir!();
+        let (visits, error) = Visits::load(&vault);
+        assert!(error.is_none());
+        assert_eq!(visits.count("atlas"), 0);
+
+        visits.record("atlas").unwrap();
+        visits.record("atlas").unwrap();
+        visits.clone().record("chart").unwrap();
+        assert_eq!(visits.count("atlas"), 2);
+        assert_eq!(visits.count("chart"), 1);
+
+        visits.rename_note("chart", "map").unwrap();
+        assert_eq!(visits.count("chart"), 0);
+
+        // the counts are stored in the vault
+        let (loaded, error) = Visits::load(&vault);
+        assert!(error.is_none());
+        assert_eq!(
+            loaded.counts(),
+            HashMap::from([(String::from("atlas"), 2), (String::from("map"), 1)])
+        );
+    }
+}
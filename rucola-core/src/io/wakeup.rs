@@ -0,0 +1,18 @@
+use std::sync::OnceLock;
+
+/// The function waking up the main loop of the user interface, see [`set_wakeup`].
+static WAKEUP: OnceLock<Box<dyn Fn() + Send + Sync>> = OnceLock::new();
+
+/// Sets the function waking up the main loop of the user interface while it waits for events.
+/// Work in the background, such as the file watcher or a synchronization, calls it once it has results to show.
+/// Only the first function set is used.
+pub fn set_wakeup(wakeup: impl Fn() + Send + Sync + 'static) {
+    let _ = WAKEUP.set(Box::new(wakeup));
+}
+
+/// Wakes up the main loop of the user interface, if a function to do so is set.
+pub fn wake() {
+    if let Some(wakeup) = WAKEUP.get() {
+        wakeup();
+    }
+}
@@ -0,0 +1,63 @@
+// Copyright (C) 2024 Linus Mussmaecher <linus.mussmaecher@gmail.com>
+//! The engine behind the rucola note manager, usable without its TUI:
+//!  - Loading the config with [`Config::load`].
+//!  - Indexing a vault with [`NoteIndex`], which parses all notes into [`Note`]s and keeps them up to date.
+//!  - Querying the index with [`Filter`], [`data::EnvironmentStats`] and [`data::expand_queries`].
+//!  - Rendering notes to HTML and PDF files with [`HtmlBuilder`] and [`io::PdfBuilder`].
+//!  - Changing the vault with [`io::FileManager`].
+//!
+//! ```
+//! use rucola_core::{io, Config, Filter, HtmlBuilder, NoteIndex};
+//!
+//! let vault = std::env::temp_dir().join(format!("rucola-core-doc-{}", std::process::id()));
+//! std::fs::create_dir_all(&vault)?;
+//! std::fs::write(vault.join("Atlas.md"), "# Atlas\n#topology\nA collection of [[Chart]]s.")?;
+//! std::fs::write(vault.join("Chart.md"), "# Chart\n#topology #analysis")?;
+//!
+//! // index
+//! let config = Config::default();
+//! let (index, errors) = NoteIndex::new(
+//!     io::FileTracker::new(&config, vault.clone())?,
+//!     HtmlBuilder::new(&config, vault.clone()),
+//!     Default::default(),
+//! );
+//! assert!(errors.is_empty());
+//! assert_eq!(index.backlinks("chart"), ["atlas"]);
+//!
+//! // query
+//! let filter = Filter::new("#analysis", false);
+//! let names = index
+//!     .notes()
+//!     .filter(|note| filter.apply(note, &index).is_some())
+//!     .map(|note| note.display_name.as_str())
+//!     .collect::<Vec<_>>();
+//! assert_eq!(names, ["Chart"]);
+//!
+//! // render
+//! let builder = HtmlBuilder::new(&config, vault.clone());
+//! builder.create_html(index.get("atlas").unwrap(), Some(&index), true)?;
+//! # std::fs::remove_dir_all(&vault)?;
+//! # Ok::<(), rucola_core::error::RucolaError>(())
+//! ```
+//!
+//! The `rucola` binary is a thin frontend on top of this library.
+
+/// Config file.
+pub mod config;
+/// Data manipulation: Reading, parsing and manipulating note files and calculating statistics.
+pub mod data;
+/// Error enum and handling.
+pub mod error;
+/// Synthetic vaults of configurable size and shape, for tests and benchmarks.
+#[cfg(any(test, feature = "fixtures"))]
+pub mod fixtures;
+/// The strings shown to the user, in their language.
+pub mod i18n;
+/// Interaction with the file system & configuration.
+pub mod io;
+/// Styles and widgets shared by the tables built from the data and the screens showing them.
+pub mod styles;
+/// Initial config file loaded from file and used to create other configuration structs.
+pub use config::Config;
+pub use data::{Filter, Note, NoteIndex};
+pub use io::HtmlBuilder;
@@ -0,0 +1,146 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use ratatui::widgets::Block;
+
+/// Wether the UI is drawn for screen readers, see [`set_screen_reader`].
+static SCREEN_READER: AtomicBool = AtomicBool::new(false);
+
+/// The icons used for keys, with the ids of the UI strings naming them for screen readers.
+const KEY_ICONS: &[(char, &str)] = &[
+    ('\u{eb6e}', "key-down"),
+    ('\u{eb71}', "key-up"),
+    ('\u{eb6f}', "key-left"),
+    ('\u{eb70}', "key-right"),
+    ('\u{f0311}', "key-enter"),
+];
+
+/// Enables or disables the simplified rendering for screen readers.
+/// It draws no borders, separates hints by commas instead of lines, names the keys shown as icons and marks the selected rows of lists.
+pub fn set_screen_reader(enabled: bool) {
+    SCREEN_READER.store(enabled, Ordering::Relaxed);
+}
+
+/// Wether the UI is drawn for screen readers.
+pub fn screen_reader() -> bool {
+    SCREEN_READER.load(Ordering::Relaxed)
+}
+
+/// Creates a block with borders on all sides, or without borders for screen readers, which read out the box-drawing characters.
+/// Titles are shown in both cases.
+pub fn bordered() -> Block<'static> {
+    if screen_reader() {
+        Block::new()
+    } else {
+        Block::bordered()
+    }
+}
+
+/// Returns the symbol in front of the selected row of tables and lists.
+/// Only screen readers need it, as they cannot tell the highlighted row by its color.
+pub fn selection_marker() -> &'static str {
+    if screen_reader() {
+        "> "
+    } else {
+        ""
+    }
+}
+
+/// Returns the separator of hints in one line: A line continuing the border, or a comma for screen readers.
+pub fn separator() -> &'static str {
+    if screen_reader() {
+        ", "
+    } else {
+        "──"
+    }
+}
+
+/// Rewrites hints such as `[J]/[]: Down──[V]iew` so screen readers can read them, see [`crate::i18n::hotkeys`]:
+/// Hints are separated by commas, keys shown as icons are named, and keys within a word are repeated after it, e.g. `[J] or [Down]: Down, View ([V])`.
+pub(crate) fn spoken_hotkeys(text: &str) -> String {
+    text.split("──")
+        .flat_map(|hints| hints.split('─'))
+        .filter(|hint| !hint.is_empty())
+        .map(spoken_hint)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Rewrites a single hint, see [`spoken_hotkeys`].
+fn spoken_hint(hint: &str) -> String {
+    let mut spoken = String::new();
+    let mut embedded = Vec::new();
+
+    let mut rest = hint;
+    while let Some((before, key, after)) = rest.split_once('[').and_then(|(before, rest)| {
+        rest.split_once(']')
+            .map(|(key, after)| (before, key, after))
+    }) {
+        let key = spoken_key(key);
+        // keys within a word, such as the V of [V]iew, are read as part of it and repeated after it
+        let within_word = before.chars().last().is_some_and(char::is_alphanumeric)
+            || after.chars().next().is_some_and(char::is_alphanumeric);
+
+        spoken.push_str(before);
+        if within_word {
+            spoken.push_str(&key);
+            embedded.push(key);
+        } else {
+            // keys separated by slashes are alternatives
+            if spoken.ends_with("]/") {
+                spoken.pop();
+                spoken.push_str(&format!(" {} ", crate::i18n::tr("key-or")));
+            }
+            spoken.push_str(&format!("[{}]", key));
+        }
+        rest = after;
+    }
+    spoken.push_str(rest);
+
+    if !embedded.is_empty() {
+        spoken.push_str(&format!(
+            " ({})",
+            embedded
+                .iter()
+                .map(|key| format!("[{}]", key))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ));
+    }
+    spoken
+}
+
+/// Names the key if it is shown as an icon, e.g. `Alt+Left` instead of `Alt+`.
+fn spoken_key(key: &str) -> String {
+    key.chars()
+        .map(|c| {
+            KEY_ICONS
+                .iter()
+                .find(|(icon, _)| *icon == c)
+                .map(|(_, id)| crate::i18n::tr(id))
+                .unwrap_or_else(|| c.to_string())
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_spoken_hotkeys() {
+        assert_eq!(
+            spoken_hotkeys("[J]/[\u{eb6e}]: Down──[V]iew──N[a]me──[Alt+\u{eb6f}]: Back"),
+            "[J] or [Down]: Down, View ([V]), Name ([a]), [Alt+Left]: Back"
+        );
+        assert_eq!(
+            spoken_hotkeys("[\u{f0311}]: Delete─[Other]: Abort"),
+            "[Enter]: Delete, [Other]: Abort"
+        );
+        assert_eq!(
+            spoken_hotkeys("[Read-only:] the vault is locked──[Alt+L]: Take over"),
+            "[Read-only:] the vault is locked, [Alt+L]: Take over"
+        );
+        assert_eq!(spoken_hotkeys("──[Alt+T]: Review"), "[Alt+T]: Review");
+        assert_eq!(spoken_hotkeys("E[x]port"), "Export ([x])");
+    }
+}
@@ -0,0 +1,14 @@
+mod uistyles;
+pub use uistyles::UiStyles;
+
+mod tag_styles;
+pub use tag_styles::TagStyle;
+pub use tag_styles::TagStyles;
+
+mod accessibility;
+pub use accessibility::bordered;
+pub use accessibility::screen_reader;
+pub use accessibility::selection_marker;
+pub use accessibility::separator;
+pub use accessibility::set_screen_reader;
+pub(crate) use accessibility::spoken_hotkeys;
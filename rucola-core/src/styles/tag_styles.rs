@@ -0,0 +1,88 @@
+use ratatui::prelude::*;
+
+/// The style of a tag as specified in the config, only overriding what is given.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct TagStyle {
+    /// The foreground color of the tag.
+    fg: Option<Color>,
+    /// The background color of the tag.
+    bg: Option<Color>,
+    /// Modifiers such as `BOLD` to add to the tag.
+    add_modifier: Modifier,
+}
+
+impl TagStyle {
+    /// Applies this tag style on top of the given style.
+    fn patch(&self, style: Style) -> Style {
+        let style = style.add_modifier(self.add_modifier);
+        let style = match self.fg {
+            Some(fg) => style.fg(fg),
+            None => style,
+        };
+        match self.bg {
+            Some(bg) => style.bg(bg),
+            None => style,
+        }
+    }
+}
+
+/// The styles of tags configured by the user, matched by exact tags (`#urgent`) or by prefixes ending in a star (`#project/*`).
+#[derive(Clone, Debug, Default)]
+pub struct TagStyles {
+    /// Styles of exactly matching tags, including the leading `#`.
+    exact: Vec<(String, TagStyle)>,
+    /// Styles of tags starting with a prefix, including the leading `#`, longest prefixes first.
+    prefixes: Vec<(String, TagStyle)>,
+}
+
+impl TagStyles {
+    /// Creates the tag styles from the given config.
+    pub fn new(config: &crate::Config) -> Self {
+        let mut res = Self::default();
+        for (pattern, style) in config.tag_styles.iter() {
+            // allow the user to specify tags with or without the leading `#`
+            let pattern = format!("#{}", pattern.trim_start_matches('#'));
+            match pattern.strip_suffix('*') {
+                Some(prefix) => res.prefixes.push((prefix.to_owned(), *style)),
+                None => res.exact.push((pattern, *style)),
+            }
+        }
+        res.prefixes
+            .sort_by_key(|(prefix, _)| std::cmp::Reverse(prefix.len()));
+        res
+    }
+
+    /// Returns the configured style of the given tag, preferring exact matches over the longest matching prefix.
+    fn find(&self, tag: &str) -> Option<&TagStyle> {
+        self.exact
+            .iter()
+            .find(|(exact, _)| exact == tag)
+            .or_else(|| {
+                self.prefixes
+                    .iter()
+                    .find(|(prefix, _)| tag.starts_with(prefix.as_str()))
+            })
+            .map(|(_, style)| style)
+    }
+
+    /// Returns the given style with the configured style of the given tag applied on top of it.
+    pub fn style(&self, tag: &str, style: Style) -> Style {
+        self.find(tag)
+            .map(|tag_style| tag_style.patch(style))
+            .unwrap_or(style)
+    }
+
+    /// Returns the given tag in its configured style, applied on top of the given style.
+    pub fn span(&self, tag: &str, style: Style) -> Span<'static> {
+        Span::styled(tag.to_owned(), self.style(tag, style))
+    }
+
+    /// Returns the given style with the configured style of the first of the given tags that has one applied on top of it.
+    pub fn first_style(&self, tags: &[String], style: Style) -> Style {
+        tags.iter()
+            .find_map(|tag| self.find(tag))
+            .map(|tag_style| tag_style.patch(style))
+            .unwrap_or(style)
+    }
+}
@@ -0,0 +1,80 @@
+//! Tests of the public API of the library, using it like a tool embedding the vault engine would.
+
+use rucola_core::{data, io, Config, Filter, HtmlBuilder, NoteIndex};
+use std::{cell::RefCell, path::PathBuf, rc::Rc};
+
+/// Indexes the vault at the given path with the default config.
+fn index(vault: PathBuf) -> NoteIndex {
+    let config = Config::default();
+    let (index, errors) = NoteIndex::new(
+        io::FileTracker::new(&config, vault.clone()).unwrap(),
+        HtmlBuilder::new(&config, vault),
+        Default::default(),
+    );
+    assert!(errors.is_empty());
+    index
+}
+
+#[test]
+fn test_index() {
+    let index = index(PathBuf::from("./tests"));
+
+    assert_eq!(index.notes().count(), 12);
+
+    let lie_group = index.get("lie-group").unwrap();
+    assert_eq!(lie_group.display_name, "Lie Group");
+    assert_eq!(lie_group.links, ["manifold", "smooth-map", "topology"]);
+
+    assert_eq!(index.backlinks("lie-group"), ["manifold"]);
+    assert_eq!(index.backlink_count("lie-group"), 1);
+    assert!(index.to_json().contains("\"id\":\"operating-systems\""));
+}
+
+#[test]
+fn test_query() {
+    let index = Rc::new(RefCell::new(index(PathBuf::from("./tests"))));
+
+    let filter = Filter::new("#os", false);
+    let mut env = data::EnvironmentStats::new_with_filter(&index, filter.clone());
+    env.sort(
+        index.clone(),
+        data::SortingMode::Name,
+        true,
+        &Default::default(),
+    );
+
+    let names = (0..env.len())
+        .map(|position| env.get_selected(position).unwrap().id.clone())
+        .collect::<Vec<_>>();
+    assert_eq!(names, ["linux", "operating-systems", "osx", "windows"]);
+
+    // the filter matches the same notes on its own
+    let index = index.borrow();
+    assert_eq!(
+        index
+            .notes()
+            .filter(|note| filter.apply(note, &index).is_some())
+            .count(),
+        4
+    );
+}
+
+#[test]
+fn test_render() {
+    let vault = testdir::testdir!();
+    std::fs::write(
+        vault.join("Atlas.md"),
+        "# Atlas\nA collection of [[Chart]]s.",
+    )
+    .unwrap();
+    std::fs::write(vault.join("Chart.md"), "# Chart\n").unwrap();
+
+    let index = index(vault.clone());
+    let builder = HtmlBuilder::new(&Config::default(), vault.clone());
+    builder
+        .create_html(index.get("atlas").unwrap(), Some(&index), true)
+        .unwrap();
+
+    let html = std::fs::read_to_string(vault.join(".html").join("atlas.html")).unwrap();
+    assert!(html.contains("chart.html"));
+}
@@ -1,8 +1,11 @@
-use super::{data, error, io, ui, ui::Screen};
+use super::{data, error, error::ErrorContext, io, ui, ui::Screen};
+use ratatui::crossterm::event::{KeyCode, KeyModifiers};
 use ratatui::prelude::*;
 
 /// The main state of the application.
 /// Consists of a select screen that is always existent, a stack of notes the user has navigated through and that he can navigate through by popping, reversing its navigation. Lastly, there is a display screen of the currently displayed note, which should always correspond to the top of the stack.
+/// Auxiliary screens such as the trash can be overlayed on top of all of these.
+/// Independently of the display stack, all visited notes are recorded in a navigation history that can be moved through like that of a browser.
 pub struct App {
     // === UI ===
     /// The current select screen (might be overlayed by a display screen and thus not rendered).
@@ -11,6 +14,8 @@ pub struct App {
     display: Option<ui::screen::DisplayScreen>,
     /// The ids of note on the display stack
     display_stack: Vec<String>,
+    /// An auxiliary screen shown on top of the select and display screens, if present.
+    overlay: Option<Box<dyn ui::Screen>>,
 
     // === DATA ===
     /// Index note data
@@ -19,12 +24,103 @@ pub struct App {
     // === CONFIG ===
     /// The file manager this app's screens use to enact the user's file system requests on the file system.
     manager: io::FileManager,
+    /// The lock coordinating this app with other instances of rucola running on the same vault, if it could be created.
+    lock: Option<io::VaultLock>,
+    /// The checksums of the notes, recorded when the app exits to find unexpected changes later on, if the vault could be read.
+    integrity: Option<io::IntegrityChecker>,
     /// The git repository the vault is stored in, if any.
     git_manager: Option<io::GitManager>,
     /// The HtmlBuider this app's screens use to continuously build html files.
     builder: io::HtmlBuilder,
+    /// The PdfBuilder used to create PDF files for batch exports and the PDF viewer.
+    pdf_builder: io::PdfBuilder,
+    /// The BackupManager used to create backups of the vault.
+    backup_manager: io::BackupManager,
+    /// The GraphBuilder used to export the link graph of the vault.
+    graph_builder: io::GraphBuilder,
+    /// The bucket the HTML files are published to, if any.
+    publisher: Option<io::S3Publisher>,
+    /// The WebDAV server the vault is synchronized with, if any.
+    sync_manager: Option<io::SyncManager>,
+    /// The rules flagging notes not modified for too long.
+    staleness: data::Staleness,
+    /// The linter checking notes for problems.
+    linter: data::Linter,
+    /// The spellchecker checking notes along with the linter, if a command or dictionary is configured.
+    spellchecker: Option<io::Spellchecker>,
+    /// The command or endpoint notes are summarized with, if any.
+    summarizer: Option<io::Summarizer>,
+    /// The command or SMTP server notes are sent by mail with, if any.
+    mailer: Option<io::Mailer>,
+    /// The semantic search over the vault, if an embedding command or endpoint is configured.
+    #[cfg(feature = "semantic-search")]
+    semantic_search: Option<io::SemanticSearch>,
     /// The styles used by this app's screens.
     styles: ui::UiStyles,
+    /// The styles of tags configured by the user.
+    tag_styles: ui::TagStyles,
+    /// Maximum width of the text column in the reading mode.
+    reading_width: u16,
+    /// Wether to suggest tags for notes that changed.
+    suggest_tags: bool,
+    /// Wether to offer rewriting the links of notes copied into the vault to files outside of it.
+    suggest_imports: bool,
+    /// Plans the rewriting of links of notes copied into the vault.
+    importer: io::Importer,
+    /// Number of notes above which building their HTML or PDF files at once has to be confirmed.
+    export_confirm_threshold: usize,
+    /// The tag of notes whose flashcards are exported for Anki, with a leading '#'.
+    flashcard_tag: String,
+    /// The snippets that can be inserted into notes, sorted by name.
+    snippets: Vec<data::Snippet>,
+    /// The collections of the vault, shared with the select screen.
+    collections: io::Collections,
+    /// The remembered ways to export collections and filters.
+    export_profiles: io::ExportProfiles,
+    /// The notes created regularly from snippets.
+    recurring_notes: io::RecurringNotes,
+    /// The order of names when sorting notes by name.
+    collation: data::Collation,
+
+    // === SESSION ===
+    /// The headings folded in the reading mode, kept while the app is running.
+    folds: ui::screen::FoldState,
+    /// The notes opened in this session, shared with the select screen to rank them higher when filtering.
+    recent: ui::screen::RecentNotes,
+    /// How often each note has been opened, shared with the screens to rank frequently used notes higher.
+    visits: io::Visits,
+    /// All notes visited in this session, oldest first, with the last position within their display screen.
+    navigation: Vec<(String, ui::screen::DisplayPosition)>,
+    /// The index of the currently displayed entry of the navigation history.
+    navigation_index: usize,
+    /// The state of the synchronization with the WebDAV server.
+    sync_status: io::SyncStatus,
+    /// Receives the outcome of the synchronization running in the background, if any.
+    sync_receiver: Option<std::sync::mpsc::Receiver<Result<io::SyncReport, String>>>,
+    /// Wether to synchronize the vault when the TUI starts, see [`App::start_background_tasks`].
+    sync_on_start: bool,
+    /// The id and path of the note being summarized in the background, if any.
+    summarizing: Option<(String, std::path::PathBuf)>,
+    /// Receives the summary requested in the background, if any.
+    summary_receiver: Option<std::sync::mpsc::Receiver<Result<String, String>>>,
+    /// The id of the note being sent by mail in the background, if any.
+    mailing: Option<String>,
+    /// Receives the outcome of sending a note by mail in the background, if any.
+    mail_receiver: Option<std::sync::mpsc::Receiver<Result<(), String>>>,
+    /// The date the due recurring notes were last created on, to create the next ones once the date changes.
+    recurring_checked: Option<chrono::NaiveDate>,
+    /// The id of the most recently changed note and the tags suggested for it, if there are any to review.
+    tag_prompt: Option<(String, Vec<data::TagSuggestion>)>,
+    /// The tags already suggested to the user, by note id, so they are not suggested again.
+    dismissed_tags: std::collections::HashSet<(String, String)>,
+    /// The notes copied into the vault with links to files outside of it, to copy the files and rewrite the links of.
+    import_prompt: Vec<io::ImportPlan>,
+    /// The links of notes already offered to be rewritten, by note id and target, so they are not offered again.
+    dismissed_imports: std::collections::HashSet<(String, String)>,
+    /// Wether anything shown changed since the app was last drawn, see [`App::take_redraw`].
+    redraw: bool,
+    /// The holder of the lock of the vault when the app was last updated, to notice when another instance takes it over.
+    lock_holder: Option<String>,
 }
 
 impl App {
@@ -34,26 +130,65 @@ impl App {
     ///  - Creating an initial select screen and empty display stack
     ///
     /// Also returns all errors that happened during creation that did not prevent the creation.
+    ///
+    /// Takes the lock of the vault, but changes nothing yet: Background work is started by [`App::start_background_tasks`].
     pub fn new<F: FnMut(&str) -> error::Result<()>>(
         args: crate::Arguments,
+        loading_screen_callback: F,
+    ) -> (Self, Vec<error::RucolaError>) {
+        Self::create(args, true, loading_screen_callback)
+    }
+
+    /// Creates a new application state for commands that only read the vault, such as dry runs.
+    /// Unlike [`App::new`], the lock of the vault is not taken, so these commands also work while another instance changes the vault.
+    pub fn read_only(args: crate::Arguments) -> (Self, Vec<error::RucolaError>) {
+        Self::create(args, false, |_message| Ok(()))
+    }
+
+    /// Creates a new application state, taking the lock of the vault if `lock_vault` is set.
+    fn create<F: FnMut(&str) -> error::Result<()>>(
+        args: crate::Arguments,
+        lock_vault: bool,
         mut loading_screen_callback: F,
     ) -> (Self, Vec<error::RucolaError>) {
         // Gather errors
         let mut errors = Vec::new();
 
+        let take_over = args.take_over;
+
         // Load configuration
-        errors.extend(loading_screen_callback("Loading configuration...").err());
+        errors.extend(loading_screen_callback(&ui::tr("loading-config")).err());
 
-        let (config, vault_path) = match crate::Config::load(args) {
-            Ok(config_data) => config_data,
+        let (config, vault_path) = match crate::Config::load(args.into()) {
+            Ok(config_data) => {
+                // Report problems that did not prevent loading, such as misspelled options.
+                let mut problems = crate::config::check_files(&config_data.1).into_iter();
+                if let Some(first) = problems.next() {
+                    errors.push(match problems.count() {
+                        0 => first.into(),
+                        more => error::RucolaError::ConfigProblem(format!(
+                            "{} (and {} more)",
+                            first, more
+                        )),
+                    });
+                }
+                config_data
+            }
             Err(e) => {
                 errors.push(e);
                 Default::default()
             }
         };
 
+        // Translate the UI into the configured language
+        ui::set_catalog(ui::Catalog::new(&config).unwrap_or_else(|e| {
+            errors.push(e);
+            Default::default()
+        }));
+        ui::set_screen_reader(config.screen_reader);
+
         // Load the style file specified in the configuration
-        errors.extend(loading_screen_callback("Loading styles...").err());
+        errors.extend(loading_screen_callback(&ui::tr("loading-styles")).err());
 
         let styles = match ui::UiStyles::load(&config) {
             Ok(config) => config,
@@ -64,40 +199,119 @@ impl App {
         };
 
         // Use the config file to create managers & trackers
-        errors.extend(loading_screen_callback("Creating managers & trackers...").err());
+        errors.extend(loading_screen_callback(&ui::tr("loading-managers")).err());
 
         let builder = io::HtmlBuilder::new(&config, vault_path.clone());
 
-        let manager = io::FileManager::new(&config, vault_path.clone());
+        let pdf_builder = io::PdfBuilder::new(&config, vault_path.clone());
+
+        let backup_manager = io::BackupManager::new(&config, vault_path.clone());
+
+        let graph_builder = io::GraphBuilder::new(&config, vault_path.clone());
+
+        let sync_manager = io::SyncManager::new(&config, vault_path.clone());
+
+        let publisher = io::S3Publisher::new(&config, vault_path.clone());
+
+        let summarizer = io::Summarizer::new(&config);
+
+        let mailer = io::Mailer::new(&config);
+
+        let linter = data::Linter::new(&config);
+
+        let spellchecker = io::Spellchecker::new(&config, &vault_path).unwrap_or_else(|e| {
+            errors.push(e);
+            None
+        });
+
+        let snippets = data::Snippet::from_config(&config);
+
+        let (collections, collections_error) = io::Collections::load(&vault_path);
+        errors.extend(collections_error);
+
+        let (visits, visits_error) = io::Visits::load(&vault_path);
+        errors.extend(visits_error);
+
+        let collation = data::Collation::new(&config).unwrap_or_else(|e| {
+            errors.push(e);
+            Default::default()
+        });
+
+        #[cfg(feature = "semantic-search")]
+        let semantic_search = io::SemanticSearch::new(&config, &vault_path);
+
+        // Only one instance of rucola may change the vault at a time, others are read-only
+        let lock = match lock_vault.then(|| io::VaultLock::acquire(&vault_path, take_over)) {
+            Some(Ok(lock)) => Some(lock),
+            Some(Err(e)) => {
+                errors.push(e);
+                None
+            }
+            None => None,
+        };
+
+        let manager = io::FileManager::new(&config, vault_path.clone())
+            .with_collections(collections.clone())
+            .with_visits(visits.clone());
+        let manager = match &lock {
+            Some(lock) => manager.with_lock(lock.clone()),
+            None => manager,
+        };
+        let manager = match config.autosave_delay {
+            0 => manager,
+            delay => {
+                manager.with_autosave(io::Autosave::new(std::time::Duration::from_millis(delay)))
+            }
+        };
+
+        // Report an unknown timezone now, recurring notes are created on the following days
+        let recurring_notes = io::RecurringNotes::new(&config, &vault_path);
+        if !recurring_notes.is_empty() {
+            errors.extend(manager.today().err());
+        }
+
+        // Errors creating the tracker are reported when indexing
+        let integrity = io::IntegrityChecker::new(&config, vault_path.clone()).ok();
 
         let git_manager = io::GitManager::new(vault_path.clone());
 
+        let parse_options = match data::ParseOptions::new(&config) {
+            Ok(parse_options) => parse_options,
+            Err(e) => {
+                errors.push(e);
+                Default::default()
+            }
+        };
+
         let tracker = match io::FileTracker::new(&config, vault_path.clone()) {
             Ok(tracker) => tracker,
             Err(e) => {
                 errors.push(e);
-                Default::default()
+                // fall back to the default file types, but keep indexing the vault
+                io::FileTracker::new(&Default::default(), vault_path.clone()).unwrap_or_default()
             }
         };
 
         // Print error message based on current directory
-        let mut msg = "Indexing...";
+        let mut msg = ui::tr("loading-index");
         if let Some(user_dirs) = directories::UserDirs::new() {
             if vault_path == directories::UserDirs::home_dir(&user_dirs) {
-                msg = "Indexing...\n\nYou are running rucola in your home directory. This might take a while.\nConsider running in your notes directory instead.";
+                msg = format!("{}\n\n{}", msg, ui::tr("loading-index-home"));
             }
         }
 
-        errors.extend(loading_screen_callback(msg).err());
+        errors.extend(loading_screen_callback(&msg).err());
 
         // Index all files in path
-        let (index, index_errors) = data::NoteIndex::new(tracker, builder.clone());
+        let (index, index_errors) = data::NoteIndex::new(tracker, builder.clone(), parse_options);
         errors.extend(index_errors);
 
         let index = std::rc::Rc::new(std::cell::RefCell::new(index));
 
         // Use the config file to create managers & trackers
-        errors.extend(loading_screen_callback("Initiliazing app state...").err());
+        errors.extend(loading_screen_callback(&ui::tr("loading-app")).err());
+
+        let recent = ui::screen::RecentNotes::default();
 
         // Initialize app state
         (
@@ -107,21 +321,683 @@ impl App {
                     manager.clone(),
                     git_manager.clone(),
                     builder.clone(),
+                    recent.clone(),
+                    visits.clone(),
+                    collections.clone(),
                     styles,
-                    config.stats_show,
+                    collation.clone(),
+                    &config,
                 ),
                 display: None,
                 display_stack: Vec::new(),
+                overlay: None,
                 index,
                 styles,
+                tag_styles: ui::TagStyles::new(&config),
+                reading_width: config.reading_width,
+                suggest_tags: config.suggest_tags,
+                suggest_imports: config.suggest_imports,
+                importer: io::Importer::new(&config, &vault_path),
+                export_confirm_threshold: config.export_confirm_threshold,
+                flashcard_tag: format!("#{}", config.flashcard_tag.trim_start_matches('#')),
+                snippets,
+                collections,
+                export_profiles: io::ExportProfiles::new(&config),
+                staleness: data::Staleness::new(&config),
+                recurring_notes,
+                collation,
+                folds: Default::default(),
+                recent,
+                visits,
+                navigation: Vec::new(),
+                navigation_index: 0,
                 manager,
+                lock,
+                integrity,
                 git_manager,
                 builder,
+                pdf_builder,
+                backup_manager,
+                graph_builder,
+                publisher,
+                sync_receiver: None,
+                sync_status: io::SyncStatus::Idle,
+                sync_manager,
+                sync_on_start: config.sync_on_start,
+                linter,
+                spellchecker,
+                summarizer,
+                mailer,
+                #[cfg(feature = "semantic-search")]
+                semantic_search,
+                summarizing: None,
+                summary_receiver: None,
+                mailing: None,
+                mail_receiver: None,
+                recurring_checked: None,
+                tag_prompt: None,
+                dismissed_tags: Default::default(),
+                import_prompt: Vec::new(),
+                dismissed_imports: Default::default(),
+                redraw: true,
+                lock_holder: None,
             },
             errors,
         )
     }
 
+    /// Starts the work the TUI does in the background of a session and returns the errors that happened:
+    ///  - Removes notes that have been in the trash for too long, or tells the user why nothing can be changed.
+    ///  - Creates the recurring notes due since the last start.
+    ///  - Synchronizes the vault with the WebDAV server, if configured to on start.
+    ///
+    /// Commands run from the command line never start it, so they change nothing unasked and cannot exit in the middle of a synchronization.
+    pub fn start_background_tasks(&mut self) -> Vec<error::RucolaError> {
+        let mut errors = Vec::new();
+
+        errors.extend(match self.manager.ensure_writable() {
+            Ok(()) => self.manager.purge_old_trash().err(),
+            Err(e) => Some(e),
+        });
+        errors.extend(self.poll_recurring().err());
+        if self.sync_on_start && self.sync_manager.is_some() {
+            errors.extend(self.start_sync().err());
+        }
+
+        errors
+    }
+
+    /// Tries again to load the files that could not be loaded and to watch the vault for changes.
+    /// Closes the list of failures if nothing fails anymore.
+    pub fn retry_failures(&mut self) -> error::Result<()> {
+        let (modifications, errors) = self.index.borrow_mut().retry_failures();
+
+        if modifications {
+            self.select.refresh_env_stats();
+        }
+
+        if !self.index.borrow().is_degraded() {
+            self.overlay = None;
+        }
+
+        // the remaining failures are listed, so only the most recent error needs to be shown
+        match errors.into_iter().next_back() {
+            Some(e) => Err(e),
+            None => Ok(()),
+        }
+    }
+
+    /// Suggests tags of the vault for the note with the given id, replacing any previous prompt.
+    /// Tags already suggested for the note before are left out.
+    fn prompt_tags(&mut self, id: &str) {
+        let suggestions = self
+            .index
+            .borrow()
+            .vault_tag_suggestions(id)
+            .into_iter()
+            .filter(|suggestion| {
+                !self
+                    .dismissed_tags
+                    .contains(&(id.to_owned(), suggestion.tag.to_lowercase()))
+            })
+            .collect::<Vec<_>>();
+
+        if !suggestions.is_empty() {
+            self.tag_prompt = Some((id.to_owned(), suggestions));
+        }
+    }
+
+    /// Removes the current tag prompt and remembers its suggestions, so they are not made again.
+    fn take_tag_prompt(&mut self) -> Option<(String, Vec<data::TagSuggestion>)> {
+        let (id, suggestions) = self.tag_prompt.take()?;
+        self.dismissed_tags.extend(
+            suggestions
+                .iter()
+                .map(|suggestion| (id.clone(), suggestion.tag.to_lowercase())),
+        );
+        Some((id, suggestions))
+    }
+
+    /// Offers to rewrite the links of the notes with the given ids that were copied into the vault and refer to files outside of it.
+    /// Replaces earlier offers for the same notes, links offered before are left out.
+    fn prompt_imports(&mut self, ids: &[String]) {
+        let notes = ids
+            .iter()
+            .filter_map(|id| {
+                self.index
+                    .borrow()
+                    .get(id)
+                    .map(|note| (id.clone(), note.path.clone()))
+            })
+            .collect::<Vec<_>>();
+
+        let plans = self
+            .importer
+            .plan(&notes)
+            .into_iter()
+            .map(|mut plan| {
+                plan.links.retain(|link| {
+                    !self
+                        .dismissed_imports
+                        .contains(&(plan.id.clone(), link.target.clone()))
+                });
+                plan
+            })
+            .filter(|plan| !plan.links.is_empty())
+            .collect::<Vec<_>>();
+
+        self.import_prompt.retain(|plan| !ids.contains(&plan.id));
+        self.import_prompt.extend(plans);
+    }
+
+    /// Removes the current import prompt and remembers its links, so they are not offered again.
+    fn take_import_prompt(&mut self) -> Vec<io::ImportPlan> {
+        let plans = std::mem::take(&mut self.import_prompt);
+        self.dismissed_imports.extend(plans.iter().flat_map(|plan| {
+            plan.links
+                .iter()
+                .map(|link| (plan.id.clone(), link.target.clone()))
+        }));
+        plans
+    }
+
+    /// Writes the statistics of all notes in the vault to a CSV file at the given path.
+    pub fn export_stats(&self, path: &std::path::Path) -> error::Result<()> {
+        let mut stats =
+            data::EnvironmentStats::new_with_filter(&self.index, data::Filter::default());
+        stats.sort(
+            self.index.clone(),
+            data::SortingMode::Name,
+            true,
+            &self.collation,
+        );
+
+        self.manager
+            .export_stats(self.index.clone(), &stats, path)?;
+
+        Ok(())
+    }
+
+    /// Describes all notes of the vault for external scripts: As a JSON array if `json` is set, otherwise with one line per note containing its id, title and path, separated by tabs.
+    pub fn describe_index(&self, json: bool) -> String {
+        let index = self.index.borrow();
+        if json {
+            return format!("{}\n", index.to_json());
+        }
+
+        let mut lines = index
+            .notes()
+            .map(|note| {
+                format!(
+                    "{}\t{}\t{}\n",
+                    data::name_to_id(&note.name),
+                    note.display_name,
+                    note.path.display()
+                )
+            })
+            .collect::<Vec<_>>();
+        lines.sort();
+        lines.concat()
+    }
+
+    /// Orders the notes matching the given filter for reading, after everything they depend on. Without a filter, all notes depending on others are ordered.
+    /// Returns the order and the order as a numbered markdown list of links.
+    pub fn reading_order(&self, filter: &str) -> (data::ReadingOrder, String) {
+        let ids = if filter.trim().is_empty() {
+            self.index
+                .borrow()
+                .notes()
+                .filter(|note| !note.dependencies.is_empty())
+                .map(|note| data::name_to_id(&note.name))
+                .collect()
+        } else {
+            data::EnvironmentStats::new_with_filter(
+                &self.index,
+                data::Filter::new(filter, false).with_collections(&self.collections),
+            )
+            .ids()
+        };
+
+        let index = self.index.borrow();
+        let order = data::ReadingOrder::new(&index, &ids);
+        let markdown = order.to_markdown(&index);
+        (order, markdown)
+    }
+
+    /// Writes the given content, e.g. a reading order, to the file at the given path.
+    pub fn write_file(&self, path: &std::path::Path, content: &str) -> error::Result<()> {
+        self.manager.write_file(path, content)
+    }
+
+    /// Writes the link graph of all notes in the vault to a Graphviz DOT file at the given path.
+    pub fn export_graph(&self, path: &std::path::Path) -> error::Result<()> {
+        let mut stats =
+            data::EnvironmentStats::new_with_filter(&self.index, data::Filter::default());
+        stats.sort(
+            self.index.clone(),
+            data::SortingMode::Name,
+            true,
+            &self.collation,
+        );
+
+        self.graph_builder
+            .create_graph(&self.index.borrow(), &stats.ids(), path)?;
+
+        Ok(())
+    }
+
+    /// Plans the migration of all notes of the vault to the given naming schemes.
+    /// Returns the plan, a description of its changes and how it changes the links between the notes.
+    pub fn plan_migration(
+        &self,
+        schemes: &[data::NamingScheme],
+    ) -> error::Result<(data::Migration, String, data::GraphImpact)> {
+        let index = self.index.borrow();
+        let migration = data::Migration::plan(&index, schemes)?;
+        let diff = migration.diff(&index);
+        let impact = migration.impact(&index)?;
+        Ok((migration, diff, impact))
+    }
+
+    /// Applies the given migration to the notes of the vault.
+    pub fn apply_migration(&self, migration: &data::Migration) -> error::Result<()> {
+        self.manager.apply_migration(migration)
+    }
+
+    /// Plans creating or refreshing the maps of content of the given targets, or of all existing ones if none are given.
+    /// Returns the plan, a description of its changes and how it changes the links between the notes.
+    pub fn plan_maps_of_content(
+        &self,
+        targets: &[data::MocTarget],
+    ) -> error::Result<(data::MapsOfContent, String, data::GraphImpact)> {
+        let index = self.index.borrow();
+        let mocs = data::MapsOfContent::plan(&index, targets, |relative| {
+            let mut path = self.manager.vault_path().join(relative);
+            self.manager.ensure_file_extension(&mut path);
+            path
+        })?;
+        let diff = mocs.diff(&index);
+        let impact = mocs.impact(&index)?;
+        Ok((mocs, diff, impact))
+    }
+
+    /// Writes the given maps of content to the vault.
+    pub fn apply_maps_of_content(&self, mocs: &data::MapsOfContent) -> error::Result<()> {
+        self.manager.apply_maps_of_content(mocs)
+    }
+
+    /// Records the checksums of the notes changed in this session, unless another instance of rucola holds the lock of the vault.
+    pub fn record_checksums(&self) -> error::Result<()> {
+        match &self.integrity {
+            Some(integrity) if self.manager.ensure_writable().is_ok() => integrity.record(false),
+            _ => Ok(()),
+        }
+    }
+
+    /// Writes a backup archive of the vault and returns its path.
+    pub fn create_backup(&self) -> error::Result<std::path::PathBuf> {
+        self.backup_manager.create_backup()
+    }
+
+    /// Builds the HTML files of all exported notes and uploads new and changed ones to the configured bucket.
+    pub fn publish(&self) -> error::Result<io::PublishReport> {
+        let publisher = self.publisher()?;
+        let ids = self
+            .index
+            .borrow()
+            .notes()
+            .map(|note| data::name_to_id(&note.name))
+            .collect::<Vec<_>>();
+        self.build_pages(&ids)?;
+        publisher.publish()
+    }
+
+    /// Builds the HTML files of the notes with the given ids that are exported and uploads new and changed ones to the configured bucket.
+    /// The files are published at the given prefix instead of the configured one, if set.
+    pub fn publish_notes(
+        &self,
+        ids: &[String],
+        prefix: Option<&str>,
+    ) -> error::Result<io::PublishReport> {
+        let publisher = match prefix {
+            Some(prefix) => self.publisher()?.clone().with_prefix(prefix),
+            None => self.publisher()?.clone(),
+        };
+        let pages = self.build_pages(ids)?;
+        publisher.publish_notes(&pages)
+    }
+
+    /// Returns the configured bucket to publish to.
+    fn publisher(&self) -> error::Result<&io::S3Publisher> {
+        self.publisher
+            .as_ref()
+            .ok_or_else(|| error::RucolaError::PublishError(ui::tr("error-no-bucket")))
+    }
+
+    /// Builds the HTML files of the notes with the given ids that are exported, and placeholder pages for the missing notes they link to.
+    /// Returns the ids of all pages built.
+    fn build_pages(&self, ids: &[String]) -> error::Result<Vec<String>> {
+        let index = self.index.borrow();
+        let mut pages = Vec::new();
+        for note in ids.iter().filter_map(|id| index.get(id)) {
+            if note.export.export {
+                self.builder.create_html(note, Some(&index), true)?;
+                pages.push(data::name_to_id(&note.name));
+            }
+        }
+
+        for (id, linked_from) in self.placeholders(&pages) {
+            self.builder
+                .create_placeholder(&id, &linked_from.iter().collect::<Vec<_>>())?;
+            pages.push(id);
+        }
+
+        Ok(pages)
+    }
+
+    /// Returns the remembered ways to export collections and filters.
+    pub fn export_profiles(&self) -> &io::ExportProfiles {
+        &self.export_profiles
+    }
+
+    /// Returns the export profile with the given name and the ids of the notes it exports, sorted.
+    pub fn profile_notes(&self, name: &str) -> error::Result<(io::ExportProfile, Vec<String>)> {
+        let profile = self.export_profiles.get(name).cloned().ok_or_else(|| {
+            error::RucolaError::ConfigProblem(ui::tr_args(
+                "error-no-export-profile",
+                &[("name", name.into())],
+            ))
+        })?;
+        let filter = data::Filter::new(
+            &profile.filter_string().ok_or_else(|| {
+                error::RucolaError::ConfigProblem(ui::tr_args(
+                    "error-empty-export-profile",
+                    &[("name", name.into())],
+                ))
+            })?,
+            false,
+        )
+        .with_collections(&self.collections);
+
+        let index = self.index.borrow();
+        let mut ids = index
+            .notes()
+            .filter(|note| filter.apply(note, &index).is_some())
+            .map(|note| data::name_to_id(&note.name))
+            .collect::<Vec<_>>();
+        ids.sort();
+
+        Ok((profile, ids))
+    }
+
+    /// Prepares the export of the notes of the export profile with the given name, returned along with the profile.
+    pub fn profile_export(
+        &self,
+        name: &str,
+    ) -> error::Result<(io::BatchExport, io::ExportProfile)> {
+        let (profile, ids) = self.profile_notes(name)?;
+        let export = self
+            .batch_export(profile.kind(name), &ids)?
+            .with_template(profile.template.clone())
+            .with_output(profile.output_folder());
+        Ok((export, profile))
+    }
+
+    /// Prepares the export of the notes with the given ids in the given way.
+    fn batch_export(&self, kind: io::ExportKind, ids: &[String]) -> error::Result<io::BatchExport> {
+        let index = self.index.borrow();
+        let notes = ids
+            .iter()
+            .filter_map(|id| index.get(id))
+            // only notes meant for spaced repetition are searched for flashcards
+            .filter(|note| !matches!(kind, io::ExportKind::Anki(_)) || self.has_flashcards(note))
+            .cloned()
+            .collect();
+        drop(index);
+        let placeholders = match kind {
+            io::ExportKind::Html => self.placeholders(ids),
+            _ => Vec::new(),
+        };
+        Ok(io::BatchExport::new(
+            kind,
+            notes,
+            self.manager.vault_path().to_path_buf(),
+            self.builder.clone(),
+            self.pdf_builder.clone(),
+        )?
+        .with_placeholders(placeholders)
+        .with_index(self.index.clone()))
+    }
+
+    /// Wether the given note has the flashcard tag or one of its subtags.
+    fn has_flashcards(&self, note: &data::Note) -> bool {
+        note.tags.iter().any(|tag| {
+            tag.eq_ignore_ascii_case(&self.flashcard_tag)
+                || tag
+                    .to_lowercase()
+                    .starts_with(&format!("{}/", self.flashcard_tag.to_lowercase()))
+        })
+    }
+
+    /// Returns the ids of the notes missing from the index that the notes with the given ids link to, together with the notes among them linking there.
+    /// Used to create placeholder pages instead of broken links when exporting HTML files.
+    fn placeholders(&self, ids: &[String]) -> Vec<(String, Vec<data::Note>)> {
+        self.index
+            .borrow()
+            .dangling_links()
+            .into_iter()
+            .map(|(id, linked_from)| {
+                (
+                    id,
+                    linked_from
+                        .into_iter()
+                        .filter(|note| ids.contains(&data::name_to_id(&note.name)))
+                        .cloned()
+                        .collect::<Vec<_>>(),
+                )
+            })
+            .filter(|(_id, linked_from)| !linked_from.is_empty())
+            .collect()
+    }
+
+    /// Starts a synchronization with the WebDAV server in the background, unless one is already running.
+    fn start_sync(&mut self) -> error::Result<()> {
+        let sync_manager = self
+            .sync_manager
+            .as_ref()
+            .ok_or_else(|| error::RucolaError::SyncError(ui::tr("error-no-webdav")))?;
+        if self.sync_receiver.is_none() {
+            self.sync_receiver = Some(sync_manager.sync_in_background());
+            self.sync_status = io::SyncStatus::Running;
+        }
+        Ok(())
+    }
+
+    /// Checks if the synchronization running in the background has completed and updates its status.
+    fn poll_sync(&mut self) -> error::Result<()> {
+        let result = match &self.sync_receiver {
+            Some(receiver) => match receiver.try_recv() {
+                Ok(result) => result,
+                Err(std::sync::mpsc::TryRecvError::Empty) => return Ok(()),
+                Err(std::sync::mpsc::TryRecvError::Disconnected) => {
+                    Err("Synchronization stopped unexpectedly.".to_string())
+                }
+            },
+            None => return Ok(()),
+        };
+
+        self.sync_receiver = None;
+        self.redraw = true;
+        match result {
+            Ok(report) => {
+                let warnings = report.warnings.join(" ");
+                self.sync_status = io::SyncStatus::Done(chrono::Local::now(), report);
+                if warnings.is_empty() {
+                    Ok(())
+                } else {
+                    Err(error::RucolaError::SyncError(warnings))
+                }
+            }
+            Err(e) => {
+                self.sync_status = io::SyncStatus::Failed;
+                Err(error::RucolaError::SyncError(e))
+            }
+        }
+    }
+
+    /// Starts summarizing the note with the given id in the background, unless another note is being summarized.
+    fn start_summary(&mut self, id: &str) -> error::Result<()> {
+        let summarizer = self
+            .summarizer
+            .as_ref()
+            .ok_or_else(|| error::RucolaError::SummaryError(ui::tr("error-no-summarizer")))?;
+        if self.summary_receiver.is_some() {
+            return Err(error::RucolaError::Input(ui::tr("error-summary-running")));
+        }
+
+        let path = self
+            .index
+            .borrow()
+            .get(id)
+            .map(|note| note.path.clone())
+            .ok_or_else(|| error::RucolaError::NoteNotFound(id.to_owned()))?;
+        let content = std::fs::read_to_string(&path)?;
+
+        self.summary_receiver = Some(summarizer.summarize_in_background(path.clone(), content));
+        self.summarizing = Some((id.to_owned(), path));
+        Ok(())
+    }
+
+    /// Checks if the summary requested in the background is ready and writes it to the frontmatter of its note.
+    fn poll_summary(&mut self) -> error::Result<()> {
+        let result = match &self.summary_receiver {
+            Some(receiver) => match receiver.try_recv() {
+                Ok(result) => result,
+                Err(std::sync::mpsc::TryRecvError::Empty) => return Ok(()),
+                Err(std::sync::mpsc::TryRecvError::Disconnected) => {
+                    Err("Summarization stopped unexpectedly.".to_string())
+                }
+            },
+            None => return Ok(()),
+        };
+
+        self.summary_receiver = None;
+        self.redraw = true;
+        if let Some((_id, path)) = self.summarizing.take() {
+            let summary = result.map_err(error::RucolaError::SummaryError)?;
+            self.manager
+                .set_summary(&path, &summary)
+                .in_file(&path, None)?;
+        }
+        Ok(())
+    }
+
+    /// Starts sending the note with the given id to the given recipients in the background, unless another note is being sent.
+    fn start_mail(&mut self, id: &str, recipients: Vec<String>) -> error::Result<()> {
+        let mailer = self
+            .mailer
+            .as_ref()
+            .ok_or_else(|| error::RucolaError::MailError(ui::tr("error-no-mailer")))?;
+        if self.mail_receiver.is_some() {
+            return Err(error::RucolaError::Input(ui::tr("error-mail-running")));
+        }
+
+        let index = self.index.borrow();
+        let note = index
+            .get(id)
+            .ok_or_else(|| error::RucolaError::NoteNotFound(id.to_owned()))?;
+        let mail = mailer.compose(note, &self.builder, &index, recipients)?;
+        drop(index);
+
+        self.mail_receiver = Some(mailer.send_in_background(mail));
+        self.mailing = Some(id.to_owned());
+        Ok(())
+    }
+
+    /// Checks if the note sent by mail in the background has been sent.
+    fn poll_mail(&mut self) -> error::Result<()> {
+        let result = match &self.mail_receiver {
+            Some(receiver) => match receiver.try_recv() {
+                Ok(result) => result,
+                Err(std::sync::mpsc::TryRecvError::Empty) => return Ok(()),
+                Err(std::sync::mpsc::TryRecvError::Disconnected) => {
+                    Err("Sending stopped unexpectedly.".to_string())
+                }
+            },
+            None => return Ok(()),
+        };
+
+        self.mail_receiver = None;
+        self.mailing = None;
+        self.redraw = true;
+        result.map_err(error::RucolaError::MailError)
+    }
+
+    /// Creates the recurring notes due today once the date changed since they were last created, if the vault can be changed.
+    /// The created notes are indexed by the file watcher.
+    fn poll_recurring(&mut self) -> error::Result<()> {
+        if self.recurring_notes.is_empty() || self.manager.ensure_writable().is_err() {
+            return Ok(());
+        }
+        // an unknown timezone has been reported when the app was created
+        let Ok(today) = self.manager.today() else {
+            return Ok(());
+        };
+        if self.recurring_checked == Some(today) {
+            return Ok(());
+        }
+
+        self.recurring_checked = Some(today);
+        match self
+            .recurring_notes
+            .create_due(&self.manager, today)
+            .1
+            .into_iter()
+            .next()
+        {
+            Some(e) => Err(e),
+            None => Ok(()),
+        }
+    }
+
+    /// Opens the semantic search, which first creates the embeddings of all new and changed notes.
+    #[cfg(feature = "semantic-search")]
+    fn open_semantic_search(&mut self) -> error::Result<()> {
+        let mut search = self
+            .semantic_search
+            .clone()
+            .ok_or_else(|| error::RucolaError::EmbeddingError(ui::tr("error-no-embeddings")))?;
+        let error = search.prepare(&self.index.borrow());
+
+        self.overlay = Some(Box::new(ui::screen::SemanticSearchScreen::new(
+            search,
+            self.index.clone(),
+            self.styles,
+        )));
+
+        // the stored embeddings are rebuilt, so the search can still be used
+        match error {
+            Some(e) => Err(e),
+            None => Ok(()),
+        }
+    }
+
+    /// Semantic search is only available when rucola is built with the `semantic-search` feature.
+    #[cfg(not(feature = "semantic-search"))]
+    fn open_semantic_search(&mut self) -> error::Result<()> {
+        Err(error::RucolaError::EmbeddingError(ui::tr(
+            "error-no-semantic-search",
+        )))
+    }
+
+    /// Returns the git manager of the vault, or an error if the vault is not stored in a git repository.
+    fn require_git_manager(&self) -> error::Result<io::GitManager> {
+        Ok(self
+            .git_manager
+            .clone()
+            .ok_or_else(|| git2::Error::from_str("Vault is not stored in a repository."))?)
+    }
+
     /// Reads the top of the display stack, creates a new display screen from it and sets that as the currently active display screen.
     /// If the display stack is empty, clears the display screen.
     fn set_display_to_top(&mut self) -> error::Result<()> {
@@ -131,28 +1007,149 @@ impl App {
                 self.index.clone(),
                 self.manager.clone(),
                 self.builder.clone(),
+                &self.visits,
                 self.styles,
+                self.tag_styles.clone(),
             )?),
             None => None,
         };
         Ok(())
     }
 
+    /// Remembers the position within the current display screen in the navigation history.
+    fn save_position(&mut self) {
+        if let (Some(display), Some((id, position))) = (
+            &self.display,
+            self.navigation.get_mut(self.navigation_index),
+        ) {
+            if display.note_id() == *id {
+                *position = display.position();
+            }
+        }
+    }
+
+    /// Records a visit of the note with the given id in the navigation history, discarding all entries after the current one.
+    fn visit(&mut self, id: &str) {
+        self.save_position();
+        self.navigation
+            .truncate(self.navigation_index.saturating_add(1));
+        self.navigation.push((id.to_owned(), Default::default()));
+        self.navigation_index = self.navigation.len() - 1;
+
+        let mut recent = self.recent.borrow_mut();
+        recent.retain(|recent| recent != id);
+        recent.push(id.to_owned());
+    }
+
+    /// Displays the entry of the navigation history with the given index by replacing the top of the display stack.
+    fn navigate(&mut self, index: usize) -> error::Result<()> {
+        let Some((id, position)) = self.navigation.get(index).cloned() else {
+            return Ok(());
+        };
+
+        self.save_position();
+        self.navigation_index = index;
+
+        self.display_stack.pop();
+        self.display_stack.push(id);
+        self.set_display_to_top()?;
+
+        if let Some(display) = &mut self.display {
+            display.set_position(position);
+        }
+
+        Ok(())
+    }
+
+    /// Removes all entries with the given id from the navigation history, keeping the current entry selected if possible.
+    fn forget_navigation(&mut self, removed_id: &str) {
+        let removed_before = self
+            .navigation
+            .iter()
+            .take(self.navigation_index)
+            .filter(|(id, _position)| id == removed_id)
+            .count();
+
+        self.navigation.retain(|(id, _position)| id != removed_id);
+        self.navigation_index = self
+            .navigation_index
+            .saturating_sub(removed_before)
+            .min(self.navigation.len().saturating_sub(1));
+    }
+
     // Updates the app with the given key.
     pub fn update(
         &mut self,
         key: Option<ratatui::crossterm::event::KeyEvent>,
     ) -> error::Result<ui::TerminalMessage> {
+        // Check if another instance took over the lock of the vault
+        if let Some(lock) = &self.lock {
+            // the lock is refreshed in the background, so its holder shown in the banner changes at any time
+            let holder = lock.holder();
+            if holder != self.lock_holder {
+                self.lock_holder = holder;
+                self.redraw = true;
+            }
+            lock.take_lost()?;
+        }
+        // Write edits made from the UI that have not been changed for a while
+        self.redraw |= self.manager.save_due_edits()?;
+        // Check for a completed synchronization
+        self.poll_sync()?;
+        // Check for a completed summary
+        self.poll_summary()?;
+        // Check for a note sent by mail
+        self.poll_mail()?;
+        // Check for recurring notes that became due
+        self.poll_recurring()?;
+        // Check for notes found by a search through their full text
+        self.redraw |= self.select.poll_search();
+
         // Check for file changes
         let mut index = self.index.borrow_mut();
         let (modifications, id_changes) = index.handle_file_events()?;
+        let changed = index.take_changed();
+        let created = index.take_created();
+        let touched = index.take_touched();
         drop(index);
 
+        if modifications || !changed.is_empty() || !id_changes.is_empty() {
+            self.redraw = true;
+        }
+
+        // Restart work of the overlay on notes that changed in the meantime
+        if let Some(overlay) = &mut self.overlay {
+            overlay.notes_changed(&changed);
+        }
+
+        // Suggest tags for the notes that changed
+        if self.suggest_tags {
+            for id in changed {
+                self.prompt_tags(&id);
+            }
+        }
+
         // synchronize display stack with id changes from file events
         for changed_id in id_changes {
+            // suggestions for removed notes can no longer be accepted
+            if self
+                .tag_prompt
+                .as_ref()
+                .is_some_and(|(id, _suggestions)| *id == changed_id)
+            {
+                self.tag_prompt = None;
+            }
+            self.import_prompt.retain(|plan| plan.id != changed_id);
             // if an id was deleted or modified, remove all such displays from the stack
             self.display_stack
                 .retain(|display_id| *display_id != changed_id);
+            // and from the navigation history
+            self.forget_navigation(&changed_id);
+        }
+
+        // Offer to rewrite links of notes copied into the vault, once notes removed in the meantime are forgotten
+        if self.suggest_imports && !created.is_empty() {
+            self.prompt_imports(&created);
         }
 
         // remove 'empty' ids, indicating that
@@ -160,8 +1157,8 @@ impl App {
             .retain(|display_id| !display_id.is_empty());
 
         if modifications {
-            // if anything happened in the file system, better refresh the filters
-            self.select.refresh_env_stats();
+            // if anything happened in the file system, update the stats of the notes involved
+            self.select.notes_changed(&touched);
             // also refresh the display by setting it to none
             self.set_display_to_top()?;
         }
@@ -169,11 +1166,66 @@ impl App {
         let key = if let Some(key) = key {
             key
         } else {
-            return Ok(ui::TerminalMessage::None);
+            // Without user input, let the overlay continue its work, which shows its progress.
+            let msg = match &mut self.overlay {
+                Some(overlay) => {
+                    self.redraw |= overlay.busy();
+                    overlay.tick()?
+                }
+                None => ui::Message::None,
+            };
+            self.redraw |= !matches!(msg, ui::Message::None);
+            match &msg {
+                // An overlay that finished its work by opening an external program is no longer needed.
+                ui::Message::OpenExternalCommand(_) => self.overlay = None,
+                // An export that finished may be published right away.
+                ui::Message::PublishNotes(ids, prefix) => {
+                    self.publish_notes(ids, prefix.as_deref())?;
+                }
+                _ => {}
+            }
+            return Ok(msg.into());
         };
 
+        // Any key may change what is shown
+        self.redraw = true;
+
         // Update appropriate screen
-        let msg = if let Some(display) = &mut self.display {
+        let msg = if let Some(overlay) = &mut self.overlay {
+            overlay.update(key)
+        } else if key.modifiers.contains(KeyModifiers::ALT) {
+            // Alt+Arrows move through the navigation history from any screen, Alt+J opens it.
+            match key.code {
+                KeyCode::Left if self.navigation_index > 0 && !self.navigation.is_empty() => {
+                    Ok(ui::Message::JumpTo(self.navigation_index - 1))
+                }
+                KeyCode::Right if self.navigation_index + 1 < self.navigation.len() => {
+                    Ok(ui::Message::JumpTo(self.navigation_index + 1))
+                }
+                KeyCode::Char('j' | 'J') => Ok(ui::Message::OpenJumpList),
+                // Alt+T and Alt+X review or dismiss suggested tags.
+                KeyCode::Char('t' | 'T') if self.tag_prompt.is_some() => {
+                    Ok(ui::Message::ReviewTagSuggestions)
+                }
+                KeyCode::Char('x' | 'X') if self.tag_prompt.is_some() => {
+                    Ok(ui::Message::DismissTagSuggestions)
+                }
+                // Alt+I and Alt+K rewrite or keep the links of notes copied into the vault.
+                KeyCode::Char('i' | 'I') if !self.import_prompt.is_empty() => {
+                    Ok(ui::Message::ApplyImports)
+                }
+                KeyCode::Char('k' | 'K') if !self.import_prompt.is_empty() => {
+                    Ok(ui::Message::DismissImports)
+                }
+                // Alt+L takes over the lock of the vault, if another instance holds it.
+                KeyCode::Char('l' | 'L')
+                    if self.lock.as_ref().is_some_and(|lock| !lock.is_writable()) =>
+                {
+                    Ok(ui::Message::TakeOverLock)
+                }
+                _ => Ok(ui::Message::None),
+            }
+        } else if let Some(display) = &mut self.display {
             display.update(key)
         } else {
             self.select.update(key)
@@ -191,14 +1243,270 @@ impl App {
                 self.display = None;
             }
             ui::Message::DisplayStackPop => {
+                self.save_position();
                 // Pop the top of the stack - which should correspond to the currently displayed note.
                 self.display_stack.pop();
                 self.set_display_to_top()?;
+                // If this returned to the previous note of the navigation history, move there as well.
+                if let (Some(top), Some(previous)) = (
+                    self.display_stack.last(),
+                    self.navigation_index.checked_sub(1),
+                ) {
+                    if self.navigation[previous].0 == *top {
+                        self.navigation_index = previous;
+                        if let Some(display) = &mut self.display {
+                            display.set_position(self.navigation[previous].1);
+                        }
+                    }
+                }
             }
             ui::Message::DisplayStackPush(new_id) => {
+                // Opening a note from an overlay replaces it.
+                self.overlay = None;
+                self.visit(new_id);
                 // Push a new id on top of the display stack.
                 self.display_stack.push(new_id.clone());
                 self.set_display_to_top()?;
+                self.visits.record(new_id)?;
+            }
+            ui::Message::OpenJumpList => {
+                self.save_position();
+                let index = self.index.borrow();
+                let names = self
+                    .navigation
+                    .iter()
+                    .map(|(id, _position)| {
+                        index
+                            .get(id)
+                            .map(|note| note.display_name.clone())
+                            .unwrap_or_else(|| id.clone())
+                    })
+                    .collect();
+                drop(index);
+                self.overlay = Some(Box::new(ui::screen::JumpListScreen::new(
+                    names,
+                    self.navigation_index,
+                    self.styles,
+                )));
+            }
+            ui::Message::JumpTo(index) => {
+                self.overlay = None;
+                self.navigate(*index)?;
+            }
+            ui::Message::OpenTrash => {
+                self.overlay = Some(Box::new(ui::screen::TrashScreen::new(
+                    self.manager.clone(),
+                    self.styles,
+                )?));
+            }
+            ui::Message::OpenHistory => {
+                self.overlay = Some(Box::new(ui::screen::HistoryScreen::new(
+                    self.index.clone(),
+                    self.require_git_manager()?,
+                    self.styles,
+                )?));
+            }
+            ui::Message::OpenVersions(id) => {
+                self.overlay = Some(Box::new(ui::screen::VersionsScreen::new(
+                    id,
+                    self.index.clone(),
+                    self.manager.clone(),
+                    self.require_git_manager()?,
+                    self.styles,
+                )?));
+            }
+            ui::Message::OpenPreview(id) => {
+                self.overlay = Some(Box::new(ui::screen::PreviewScreen::new(
+                    id,
+                    self.index.clone(),
+                    self.folds.clone(),
+                    self.styles,
+                    self.reading_width,
+                )?));
+            }
+            ui::Message::CreateBackup => {
+                self.create_backup()?;
+            }
+            ui::Message::Sync => {
+                self.start_sync()?;
+            }
+            ui::Message::Publish => {
+                self.publish()?;
+            }
+            ui::Message::BatchExport(kind, ids) => {
+                self.overlay = Some(Box::new(ui::screen::ExportScreen::new(
+                    self.batch_export(kind.clone(), ids)?,
+                    self.styles,
+                    self.export_confirm_threshold,
+                )));
+            }
+            ui::Message::RunExportProfile(name) => {
+                let (export, profile) = self.profile_export(name)?;
+                let screen = ui::screen::ExportScreen::new(
+                    export,
+                    self.styles,
+                    self.export_confirm_threshold,
+                );
+                self.overlay = Some(Box::new(if profile.publish {
+                    screen.with_publish(profile.publish_prefix)
+                } else {
+                    screen
+                }));
+            }
+            ui::Message::PublishNotes(ids, prefix) => {
+                self.publish_notes(ids, prefix.as_deref())?;
+            }
+            ui::Message::ViewPdf(id, primary) => {
+                let note = self
+                    .index
+                    .borrow()
+                    .get(id)
+                    .cloned()
+                    .ok_or_else(|| error::RucolaError::NoteNotFound(id.clone()))?;
+                self.overlay = Some(Box::new(ui::screen::PdfBuildScreen::new(
+                    note,
+                    *primary,
+                    self.index.clone(),
+                    self.pdf_builder.clone(),
+                    self.manager.clone(),
+                    self.styles,
+                )?));
+            }
+            ui::Message::OpenFailures => {
+                self.overlay = Some(Box::new(ui::screen::FailuresScreen::new(
+                    self.index.clone(),
+                    self.manager.vault_path().to_path_buf(),
+                    self.styles,
+                )));
+            }
+            ui::Message::RetryFailures => {
+                self.retry_failures()?;
+            }
+            ui::Message::ReviewTagSuggestions => {
+                if let Some((id, suggestions)) = self.take_tag_prompt() {
+                    if let Some(note) = self.index.borrow().get(&id) {
+                        self.overlay = Some(Box::new(ui::screen::TagSuggestionsScreen::new(
+                            note,
+                            suggestions,
+                            self.manager.clone(),
+                            self.styles,
+                        )));
+                    }
+                }
+            }
+            ui::Message::DismissTagSuggestions => {
+                self.take_tag_prompt();
+            }
+            ui::Message::ApplyImports => {
+                // apply all plans, even if some fail
+                let errors = self
+                    .take_import_prompt()
+                    .iter()
+                    .filter_map(|plan| self.manager.apply_import(plan).err())
+                    .collect::<Vec<_>>();
+                if let Some(e) = errors.into_iter().next() {
+                    return Err(e);
+                }
+            }
+            ui::Message::DismissImports => {
+                self.take_import_prompt();
+            }
+            ui::Message::TakeOverLock => {
+                if let Some(lock) = &self.lock {
+                    lock.take_over()?;
+                }
+            }
+            ui::Message::Summarize(id) => {
+                self.start_summary(id)?;
+            }
+            ui::Message::EmailNote(id, recipients) => {
+                self.start_mail(id, recipients.clone())?;
+            }
+            ui::Message::OpenSnippets(id) => {
+                if self.snippets.is_empty() {
+                    return Err(error::RucolaError::Input(ui::tr("error-no-snippets")));
+                }
+                let note = self
+                    .index
+                    .borrow()
+                    .get(id)
+                    .cloned()
+                    .ok_or_else(|| error::RucolaError::NoteNotFound(id.clone()))?;
+                self.overlay = Some(Box::new(ui::screen::SnippetScreen::new(
+                    note,
+                    self.snippets.clone(),
+                    self.manager.clone(),
+                    self.styles,
+                )?));
+            }
+            ui::Message::OpenProblems(id) => {
+                let mut screen = ui::screen::ProblemsScreen::new(
+                    self.index.clone(),
+                    self.manager.clone(),
+                    self.linter.clone(),
+                    self.spellchecker.clone(),
+                    id.clone(),
+                    self.styles,
+                );
+                // show the problems found even if the spellchecker failed
+                let result = screen.refresh();
+                self.overlay = Some(Box::new(screen));
+                result?;
+            }
+            ui::Message::OpenTagUsage(tag) => {
+                self.overlay = Some(Box::new(ui::screen::TagUsageScreen::new(
+                    self.index.clone(),
+                    self.manager.clone(),
+                    tag.clone(),
+                    self.styles,
+                    self.tag_styles.clone(),
+                )));
+            }
+            ui::Message::OpenBrokenLinks => {
+                self.overlay = Some(Box::new(ui::screen::BrokenLinksScreen::new(
+                    self.index.clone(),
+                    self.manager.clone(),
+                    self.styles,
+                )));
+            }
+            ui::Message::OpenStaleNotes => {
+                self.overlay = Some(Box::new(ui::screen::StaleNotesScreen::new(
+                    self.index.clone(),
+                    self.manager.clone(),
+                    self.staleness.clone(),
+                    self.styles,
+                )));
+            }
+            ui::Message::OpenPeople(person) => {
+                self.overlay = Some(Box::new(ui::screen::PeopleScreen::new(
+                    self.index.clone(),
+                    self.manager.clone(),
+                    person.clone(),
+                    self.styles,
+                )));
+            }
+            ui::Message::OpenSemanticSearch => {
+                self.open_semantic_search()?;
+            }
+            ui::Message::OpenCollections(id) => {
+                self.overlay = Some(Box::new(ui::screen::CollectionsScreen::new(
+                    self.collections.clone(),
+                    self.export_profiles.clone(),
+                    self.index.clone(),
+                    id.clone(),
+                    self.styles,
+                )));
+            }
+            ui::Message::ShowCollection(name) => {
+                self.overlay = None;
+                self.display_stack.clear();
+                self.display = None;
+                self.select.show_collection(name);
+            }
+            ui::Message::CloseOverlay => {
+                self.overlay = None;
+                // notes may have been added to collections the select screen filters for
+                self.select.collections_changed();
             }
         }
 
@@ -206,10 +1514,258 @@ impl App {
     }
 
     pub fn draw(&self, area: Rect, buf: &mut Buffer) {
+        // Screen readers start at the top, so the first line announces where the user is and what is selected.
+        let area = if ui::screen_reader() {
+            let [announcement_area, area] =
+                Layout::vertical([Constraint::Length(1), Constraint::Fill(1)]).areas(area);
+            let announcement = match (&self.overlay, &self.display) {
+                (Some(overlay), _) => overlay.announcement(),
+                (None, Some(display)) => display.announcement(),
+                (None, None) => self.select.announcement(),
+            };
+            Widget::render(
+                Line::styled(announcement.unwrap_or_default(), self.styles.text_style),
+                announcement_area,
+                buf,
+            );
+            area
+        } else {
+            area
+        };
+
+        if let Some(overlay) = &self.overlay {
+            overlay.draw(area, buf);
+            self.draw_unsaved_marker(area, buf);
+            return;
+        }
+
+        // Show banners above the current screen:
+        //  - If parts of the vault could not be loaded, above the select screen.
+        //  - If there are tags suggested for a changed note, above all screens.
+        //  - If another instance holds the lock of the vault, above all screens.
+        let mut banners = Vec::new();
+        if let Some(holder) = self.lock.as_ref().and_then(|lock| lock.holder()) {
+            banners.push(
+                Line::from(ui::hotkeys(
+                    &ui::tr_args("banner-locked", &[("holder", holder.to_string().into())]),
+                    self.styles.hotkey_style,
+                    self.styles.text_style,
+                ))
+                .centered(),
+            );
+        }
+        if self.display.is_none() && self.index.borrow().is_degraded() {
+            banners.push(self.degraded_banner());
+        }
+        if let Some((id, suggestions)) = &self.tag_prompt {
+            banners.push(self.tag_banner(id, suggestions));
+        }
+        if !self.import_prompt.is_empty() {
+            banners.push(self.import_banner());
+        }
+        if let Some((id, _path)) = &self.summarizing {
+            banners.push(
+                Line::from(vec![
+                    Span::styled(ui::tr("banner-summarizing") + " ", self.styles.text_style),
+                    Span::styled(
+                        self.index
+                            .borrow()
+                            .get(id)
+                            .map(|note| note.display_name.clone())
+                            .unwrap_or_else(|| id.to_owned()),
+                        self.styles.subtitle_style,
+                    ),
+                    Span::styled("...", self.styles.text_style),
+                ])
+                .centered(),
+            );
+        }
+        if let Some(id) = &self.mailing {
+            banners.push(
+                Line::from(vec![
+                    Span::styled(ui::tr("banner-sending") + " ", self.styles.text_style),
+                    Span::styled(
+                        self.index
+                            .borrow()
+                            .get(id)
+                            .map(|note| note.display_name.clone())
+                            .unwrap_or_else(|| id.to_owned()),
+                        self.styles.subtitle_style,
+                    ),
+                    Span::styled("...", self.styles.text_style),
+                ])
+                .centered(),
+            );
+        }
+
+        let [banner_area, area] = Layout::vertical([
+            Constraint::Length(banners.len() as u16),
+            Constraint::Fill(1),
+        ])
+        .areas(area);
+        Widget::render(Text::from(banners), banner_area, buf);
+
         if let Some(display) = &self.display {
             display.draw(area, buf);
         } else {
             self.select.draw(area, buf);
+
+            // Show the state of the synchronization in the top left corner of the select screen.
+            if self.sync_manager.is_some() {
+                Widget::render(
+                    Line::from(Span::styled(
+                        self.sync_status.to_string(),
+                        match self.sync_status {
+                            io::SyncStatus::Failed => self.styles.hotkey_style,
+                            _ => self.styles.subtitle_style,
+                        },
+                    ))
+                    .left_aligned(),
+                    area,
+                    buf,
+                );
+            }
+        }
+
+        self.draw_unsaved_marker(area, buf);
+    }
+
+    /// Shows in the top right corner that there are edits made from the UI that have not been written yet.
+    fn draw_unsaved_marker(&self, area: Rect, buf: &mut Buffer) {
+        if self.manager.has_unsaved_edits() {
+            Widget::render(
+                Line::from(Span::styled(
+                    ui::tr("unsaved-edits"),
+                    self.styles.hotkey_style,
+                ))
+                .right_aligned(),
+                area,
+                buf,
+            );
+        }
+    }
+
+    /// Describes what could not be loaded and how to review it.
+    fn degraded_banner(&self) -> Line<'static> {
+        let index = self.index.borrow();
+
+        let mut problems = Vec::new();
+        if !index.failures().is_empty() {
+            problems.push(ui::tr_args(
+                "banner-failures",
+                &[("count", index.failures().len().into())],
+            ));
         }
+        if index.watcher_failure().is_some() {
+            problems.push(ui::tr("banner-watcher"));
+        }
+
+        Line::from(ui::hotkeys(
+            &ui::tr_args(
+                "banner-degraded",
+                &[("problems", problems.join(", ").into())],
+            ),
+            self.styles.hotkey_style,
+            self.styles.text_style,
+        ))
+        .centered()
+    }
+
+    /// Names the notes copied into the vault with links to files outside of it and how to rewrite them.
+    fn import_banner(&self) -> Line<'static> {
+        let names = self
+            .import_prompt
+            .iter()
+            .map(|plan| {
+                self.index
+                    .borrow()
+                    .get(&plan.id)
+                    .map(|note| note.display_name.clone())
+                    .unwrap_or_else(|| plan.id.clone())
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+        let links = self
+            .import_prompt
+            .iter()
+            .map(|plan| plan.links.len())
+            .sum::<usize>();
+
+        let mut spans = vec![
+            Span::styled(
+                ui::tr_args(
+                    "banner-imports",
+                    &[
+                        ("notes", self.import_prompt.len().into()),
+                        ("links", links.into()),
+                    ],
+                ) + " ",
+                self.styles.text_style,
+            ),
+            Span::styled(names, self.styles.subtitle_style),
+        ];
+        spans.extend(ui::tr_hotkeys(
+            "banner-imports-hints",
+            self.styles.hotkey_style,
+            self.styles.text_style,
+        ));
+
+        Line::from(spans).centered()
+    }
+
+    /// Names the tags suggested for a changed note and how to review them.
+    fn tag_banner(&self, id: &str, suggestions: &[data::TagSuggestion]) -> Line<'static> {
+        let name = self
+            .index
+            .borrow()
+            .get(id)
+            .map(|note| note.display_name.clone())
+            .unwrap_or_else(|| id.to_owned());
+
+        let mut spans = vec![
+            Span::styled(
+                ui::tr("tag-suggestions-title") + " ",
+                self.styles.text_style,
+            ),
+            Span::styled(name, self.styles.subtitle_style),
+            Span::styled(": ", self.styles.text_style),
+            Span::styled(
+                suggestions
+                    .iter()
+                    .map(|suggestion| suggestion.tag.as_str())
+                    .collect::<Vec<_>>()
+                    .join(" "),
+                self.styles.text_style,
+            ),
+        ];
+        spans.extend(ui::tr_hotkeys(
+            "banner-tags-hints",
+            self.styles.hotkey_style,
+            self.styles.text_style,
+        ));
+
+        Line::from(spans).centered()
+    }
+
+    /// Writes all edits made from the UI that have not been written yet.
+    pub fn save_edits(&mut self) -> error::Result<()> {
+        self.redraw |= self.manager.has_unsaved_edits();
+        self.manager.save_all_edits()
+    }
+
+    /// Returns how long the app can wait for user input before edits made from the UI have to be written.
+    pub fn next_save_in(&self) -> Option<std::time::Duration> {
+        self.manager.next_save_in()
+    }
+
+    /// Checks if the app has work in progress and should be updated again without waiting for user input.
+    pub fn busy(&self) -> bool {
+        self.overlay.as_ref().is_some_and(|overlay| overlay.busy())
+    }
+
+    /// Checks if anything shown changed since the last call, so the app needs to be drawn again.
+    /// Drawing is skipped otherwise, as it builds all widgets anew.
+    pub fn take_redraw(&mut self) -> bool {
+        std::mem::take(&mut self.redraw)
     }
 }
@@ -1,11 +0,0 @@
-mod file_manager;
-pub use file_manager::FileManager;
-
-mod file_tracker;
-pub use file_tracker::FileTracker;
-
-mod html_builder;
-pub use html_builder::HtmlBuilder;
-
-mod git_manager;
-pub use git_manager::GitManager;
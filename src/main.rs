@@ -6,18 +6,12 @@ use std::panic;
 
 /// The actual application, combining the ui and data management.
 mod app;
-/// Config file.
-mod config;
-/// Data manipulation: Reading, parsing and manipulating note files and calculating statistics.
-mod data;
-/// Error enum and handling.
-mod error;
-/// Interaction with the file system & configuration.
-mod io;
 /// The ui of the app.
 mod ui;
-/// Initial config file loaded from file and used to create other configuration structs.
-pub use config::Config;
+use rucola_core::{config, data, error, io, Config};
+
+/// The shortest time between two frames while the app is busy and changes constantly, i.e. at most 30 frames per second.
+const FRAME_INTERVAL: std::time::Duration = std::time::Duration::from_millis(33);
 
 /// Command line arguments for the Rucola markdown note management program.
 /// This program comes with ABSOLUTELY NO WARRANTY.
@@ -32,15 +26,139 @@ pub struct Arguments {
     /// A path to a file (relative to the config directory) containing the styles to use for the UI
     #[arg(short, long)]
     style: Option<String>,
+    /// Override an option of the config file, e.g. `--set reading_width=100`. Can be given multiple times.
+    /// Options can also be set by environment variables such as `RUCOLA_READING_WIDTH=100`, which are overridden by this argument.
+    #[arg(long, value_name = "KEY=VALUE")]
+    set: Vec<String>,
     /// Output the license and warranty.
     #[arg(short, long)]
     license: bool,
+    /// Write the statistics of all notes in the vault to the given CSV file and exit without opening the TUI.
+    #[arg(long, value_name = "FILE")]
+    export_stats: Option<std::path::PathBuf>,
+    /// Write the link graph of all notes in the vault to the given Graphviz DOT file and exit without opening the TUI.
+    #[arg(long, value_name = "FILE")]
+    export_graph: Option<std::path::PathBuf>,
+    /// Write a timestamped backup archive of the vault to the configured backup location and exit without opening the TUI.
+    #[arg(long)]
+    backup: bool,
+    /// Build the HTML files of all notes, upload new and changed ones to the configured S3 bucket and exit without opening the TUI.
+    #[arg(long)]
+    publish: bool,
+    /// Take over the lock of the vault from another running instance, which becomes read-only. Without it, rucola opens the vault read-only while another instance is running.
+    #[arg(long, global = true)]
+    take_over: bool,
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+impl From<Arguments> for config::ConfigArgs {
+    fn from(args: Arguments) -> Self {
+        Self {
+            target_folder: args.target_folder,
+            style: args.style,
+            set: args.set,
+        }
+    }
+}
+
+/// Commands that run without opening the TUI.
+#[derive(clap::Subcommand)]
+enum Command {
+    /// Work with the config file.
+    Config {
+        #[command(subcommand)]
+        action: ConfigCommand,
+    },
+    /// Print the notes whose name or title matches the given prefix, best match first, e.g. to complete links in an editor.
+    /// Each line contains the id and the title of a note, separated by a tab.
+    CompleteLink {
+        /// The beginning of the link to complete. Matched fuzzily, so it may skip characters.
+        #[arg(default_value = "")]
+        prefix: String,
+        /// The maximum number of notes to print.
+        #[arg(long, default_value_t = 20)]
+        limit: usize,
+        /// Print the completions as a JSON array, including the file names and modification times of the notes.
+        #[arg(long)]
+        json: bool,
+    },
+    /// Rename all notes to other naming schemes and update the links to them.
+    /// Prints all changes and asks for confirmation, then writes a backup of the vault before applying them.
+    Migrate {
+        /// The naming schemes to apply to the names of the notes, in the given order.
+        #[arg(required = true, value_enum)]
+        schemes: Vec<data::NamingScheme>,
+        /// Only print the changes without applying them.
+        #[arg(long)]
+        dry_run: bool,
+        /// Apply the changes without asking for confirmation.
+        #[arg(short, long)]
+        yes: bool,
+    },
+    /// Create or refresh maps of content: Notes listing links to all notes with a tag or within a folder.
+    /// The lists are kept between marker comments, so text around them is preserved.
+    Moc {
+        /// The tags (starting with #) or folders to list the notes of. Without any, all existing lists are refreshed.
+        targets: Vec<data::MocTarget>,
+        /// Only print the changes without applying them.
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Compare all notes with the checksums recorded when rucola last exited and list the notes that were corrupted, changed, deleted or created since.
+    /// Exits with an error code if any note was corrupted or changed.
+    Audit {
+        /// Record the current state of all notes as intact, so they are no longer reported.
+        #[arg(long)]
+        accept: bool,
+    },
+    /// Export the notes of an export profile of the config, such as a collection as PDF handouts.
+    /// Without a profile, the configured profiles are listed along with the notes they export.
+    Export {
+        /// The name of the export profile to run.
+        profile: Option<String>,
+    },
+    /// Print all notes of the vault with their ids, titles and paths, one per line and separated by tabs.
+    Index {
+        /// Print the notes as a JSON array instead, including their tags, links, word counts and character counts.
+        #[arg(long)]
+        json: bool,
+    },
+    /// Print the notes matching the given filter as a numbered list of links in the order to read them, each after the notes it depends on.
+    /// Dependencies are listed in the frontmatter field set as `dependency_key` in the config. Exits with an error code if notes depend on each other in a cycle.
+    ReadingOrder {
+        /// The filter the notes to read have to match, in the syntax of the filter box. Without one, all notes depending on others are ordered.
+        filter: Vec<String>,
+        /// Write the list to the given file instead of printing it.
+        #[arg(long, value_name = "FILE")]
+        output: Option<std::path::PathBuf>,
+    },
+    /// Create the recurring notes of the config that are due today and have not been created yet, e.g. from a cron job or systemd timer.
+    /// Prints the paths of the created notes.
+    Recur,
+    /// Remove HTML and PDF files of deleted notes, stale cache entries, empty folders and notes that have been in the trash for longer than configured.
+    /// Prints everything to remove and asks for confirmation.
+    Clean {
+        /// Only print what would be removed without removing it.
+        #[arg(long)]
+        dry_run: bool,
+        /// Remove everything without asking for confirmation.
+        #[arg(short, long)]
+        yes: bool,
+    },
+}
+
+/// Commands acting on the config file.
+#[derive(clap::Subcommand)]
+enum ConfigCommand {
+    /// Check the config file and the config file of the vault for unknown options and invalid values.
+    Check,
 }
 
 /// Main function
 fn main() -> error::Result<()> {
     // === Read command line arguments
-    let args = Arguments::parse();
+    let mut args = Arguments::parse();
 
     // === Help Notices etc. ===
     if args.license {
@@ -48,6 +166,440 @@ fn main() -> error::Result<()> {
         return Ok(());
     }
 
+    // === Non-interactive commands ===
+    if let Some(Command::Config {
+        action: ConfigCommand::Check,
+    }) = args.command
+    {
+        // Syntax errors in the config are reported by the check itself.
+        let vault_path = match Config::load(args.into()) {
+            Ok((_config, vault_path)) => vault_path,
+            Err(_) => std::env::current_dir()?,
+        };
+
+        let problems = config::check_files(&vault_path);
+        for problem in &problems {
+            println!("{}", problem);
+            if let Some(suggestion) = &problem.suggestion {
+                println!("    {}", suggestion);
+            }
+        }
+
+        if problems.is_empty() {
+            println!("No problems found.");
+            return Ok(());
+        }
+        println!(
+            "Found {} problem{}.",
+            problems.len(),
+            if problems.len() == 1 { "" } else { "s" }
+        );
+        std::process::exit(1);
+    }
+
+    if let Some(Command::CompleteLink {
+        prefix,
+        limit,
+        json,
+    }) = args
+        .command
+        .take_if(|command| matches!(command, Command::CompleteLink { .. }))
+    {
+        // Only load the config, the completer does not need the full index.
+        let (config, vault_path) = Config::load(args.into())?;
+        let mut completer = io::LinkCompleter::new(&config, vault_path)?;
+        for e in completer.refresh() {
+            eprintln!("{}", e);
+        }
+
+        let completions = completer
+            .complete(&prefix)
+            .into_iter()
+            .take(limit)
+            .collect::<Vec<_>>();
+        if json {
+            println!(
+                "{}",
+                serde_json::to_string(&completions).map_err(std::io::Error::from)?
+            );
+        } else {
+            for completion in completions {
+                println!("{}\t{}", completion.id, completion.title);
+            }
+        }
+        return Ok(());
+    }
+
+    if let Some(Command::Migrate {
+        schemes,
+        dry_run,
+        yes,
+    }) = args
+        .command
+        .take_if(|command| matches!(command, Command::Migrate { .. }))
+    {
+        // Dry runs change nothing, so they do not need the lock of the vault.
+        let (app, errors) = if dry_run {
+            app::App::read_only(args)
+        } else {
+            app::App::new(args, |_message| Ok(()))
+        };
+        for e in errors {
+            eprintln!("{}", e);
+        }
+
+        let (migration, diff, impact) = app.plan_migration(&schemes)?;
+        if migration.is_empty() {
+            println!("All notes already follow the given naming schemes.");
+            return Ok(());
+        }
+        print!("{}", diff);
+        println!();
+        print!("{}", impact.describe());
+        println!(
+            "Renaming {} note{} and updating links in {} note{}.",
+            migration.renames.len(),
+            if migration.renames.len() == 1 {
+                ""
+            } else {
+                "s"
+            },
+            migration.edits.len(),
+            if migration.edits.len() == 1 { "" } else { "s" }
+        );
+
+        if dry_run {
+            return Ok(());
+        }
+        if !yes {
+            print!("Apply these changes? A backup of the vault is written first. [y/N] ");
+            std::io::Write::flush(&mut std::io::stdout())?;
+            let mut answer = String::new();
+            std::io::stdin().read_line(&mut answer)?;
+            if !matches!(answer.trim(), "y" | "Y" | "yes") {
+                println!("Nothing was changed.");
+                return Ok(());
+            }
+        }
+
+        println!("Backup written to {}", app.create_backup()?.display());
+        app.apply_migration(&migration)?;
+        println!("Done.");
+        return Ok(());
+    }
+
+    if let Some(Command::Moc { targets, dry_run }) = args
+        .command
+        .take_if(|command| matches!(command, Command::Moc { .. }))
+    {
+        let (app, errors) = if dry_run {
+            app::App::read_only(args)
+        } else {
+            app::App::new(args, |_message| Ok(()))
+        };
+        for e in errors {
+            eprintln!("{}", e);
+        }
+
+        let (mocs, diff, impact) = app.plan_maps_of_content(&targets)?;
+        if mocs.is_empty() {
+            println!("All maps of content are up to date.");
+            return Ok(());
+        }
+        print!("{}", diff);
+        println!();
+        print!("{}", impact.describe());
+        println!(
+            "Writing {} map{} of content.",
+            mocs.edits.len(),
+            if mocs.edits.len() == 1 { "" } else { "s" }
+        );
+
+        if !dry_run {
+            app.apply_maps_of_content(&mocs)?;
+            println!("Done.");
+        }
+        return Ok(());
+    }
+
+    if let Some(Command::Index { json }) = args
+        .command
+        .take_if(|command| matches!(command, Command::Index { .. }))
+    {
+        let (app, errors) = app::App::read_only(args);
+        for e in errors {
+            eprintln!("{}", e);
+        }
+
+        print!("{}", app.describe_index(json));
+        return Ok(());
+    }
+
+    if let Some(Command::ReadingOrder { filter, output }) = args
+        .command
+        .take_if(|command| matches!(command, Command::ReadingOrder { .. }))
+    {
+        let (app, errors) = app::App::read_only(args);
+        for e in errors {
+            eprintln!("{}", e);
+        }
+
+        let (order, markdown) = app.reading_order(&filter.join(" "));
+        match output {
+            Some(path) => app.write_file(&path, &markdown)?,
+            None => print!("{}", markdown),
+        }
+
+        if !order.cycles.is_empty() {
+            for cycle in &order.cycles {
+                eprintln!(
+                    "Cycle of dependencies: {} -> {}",
+                    cycle.join(" -> "),
+                    cycle[0]
+                );
+            }
+            eprintln!("Notes within or depending on these cycles were left out.");
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+
+    if let Some(Command::Audit { accept }) = args
+        .command
+        .take_if(|command| matches!(command, Command::Audit { .. }))
+    {
+        let take_over = args.take_over;
+        let (config, vault_path) = Config::load(args.into())?;
+        let integrity = io::IntegrityChecker::new(&config, vault_path.clone())?;
+
+        let report = integrity.check()?;
+        if accept || !report.recorded {
+            let lock = io::VaultLock::acquire(&vault_path, take_over)?;
+            lock.ensure_writable()?;
+            integrity.record(true)?;
+            println!("Recorded the checksums of {} notes.", report.checked);
+            return Ok(());
+        }
+
+        for (path, issue) in &report.issues {
+            println!("{:<10} {}", issue, path.display());
+        }
+        let damaged = report
+            .issues
+            .iter()
+            .filter(|(_, issue)| {
+                matches!(
+                    issue,
+                    io::IntegrityIssue::Corrupted | io::IntegrityIssue::Changed
+                )
+            })
+            .count();
+        println!(
+            "Checked {} notes, {} corrupted or changed, {} other difference{}.",
+            report.checked,
+            damaged,
+            report.issues.len() - damaged,
+            if report.issues.len() - damaged == 1 {
+                ""
+            } else {
+                "s"
+            }
+        );
+        if damaged > 0 {
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+
+    if let Some(Command::Export { profile }) = args
+        .command
+        .take_if(|command| matches!(command, Command::Export { .. }))
+    {
+        let (app, errors) = app::App::read_only(args);
+        for e in errors {
+            eprintln!("{}", e);
+        }
+
+        let Some(profile) = profile else {
+            for (name, profile) in app.export_profiles().iter() {
+                println!("{}\t{}", name, profile.filter_string().unwrap_or_default());
+            }
+            return Ok(());
+        };
+
+        let (mut export, settings) = app.profile_export(&profile)?;
+        println!("{}", export.kind());
+        while export.step() {}
+
+        for (name, reason) in export.failures() {
+            println!("{:<20} {}", name, reason);
+        }
+        let (total, failed) = (export.progress().1, export.failures().len());
+        println!(
+            "Exported {} note{}, {} failed.",
+            total.saturating_sub(failed),
+            if total.saturating_sub(failed) == 1 {
+                ""
+            } else {
+                "s"
+            },
+            failed
+        );
+
+        if settings.publish {
+            let report = app.publish_notes(&export.ids(), settings.publish_prefix.as_deref())?;
+            println!(
+                "Uploaded {} files, {} unchanged.",
+                report.uploaded, report.unchanged
+            );
+        }
+        if failed > 0 {
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+
+    if let Some(Command::Recur) = args
+        .command
+        .take_if(|command| matches!(command, Command::Recur))
+    {
+        // Only load the config, creating notes does not need the index.
+        let take_over = args.take_over;
+        let (config, vault_path) = Config::load(args.into())?;
+        let manager = io::FileManager::new(&config, vault_path.clone())
+            .with_lock(io::VaultLock::acquire(&vault_path, take_over)?);
+        manager.ensure_writable()?;
+
+        let recurring = io::RecurringNotes::new(&config, &vault_path);
+        if recurring.is_empty() {
+            println!("No recurring notes configured.");
+            return Ok(());
+        }
+
+        let (created, errors) = recurring.create_due(&manager, manager.today()?);
+        for path in &created {
+            println!("{}", path.display());
+        }
+        for e in &errors {
+            eprintln!("{}", e);
+        }
+        if !errors.is_empty() {
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+
+    if let Some(Command::Clean { dry_run, yes }) = args
+        .command
+        .take_if(|command| matches!(command, Command::Clean { .. }))
+    {
+        // Build the index without the app, the cleanup only needs the index and the file manager.
+        let take_over = args.take_over;
+        let (config, vault_path) = Config::load(args.into())?;
+        let manager = io::FileManager::new(&config, vault_path.clone());
+        let (index, errors) = data::NoteIndex::new(
+            io::FileTracker::new(&config, vault_path.clone())?,
+            io::HtmlBuilder::new(&config, vault_path.clone()),
+            data::ParseOptions::new(&config)?,
+        );
+        for e in errors {
+            eprintln!("{}", e);
+        }
+
+        let mut cleanup = io::Cleanup::plan(&config, &index, &manager)?;
+        if cleanup.is_empty() {
+            println!("Nothing to clean up.");
+            return Ok(());
+        }
+        print!("{}", cleanup.describe());
+        println!(
+            "Removing {} file{} and folder{} ({}) and {} stale cache entr{}.",
+            cleanup.leftovers.len(),
+            if cleanup.leftovers.len() == 1 {
+                ""
+            } else {
+                "s"
+            },
+            if cleanup.leftovers.len() == 1 {
+                ""
+            } else {
+                "s"
+            },
+            io::format_size(cleanup.size()),
+            cleanup.stale_entries(),
+            if cleanup.stale_entries() == 1 {
+                "y"
+            } else {
+                "ies"
+            }
+        );
+
+        if dry_run {
+            return Ok(());
+        }
+        if !yes {
+            print!("Remove these files? They cannot be restored. [y/N] ");
+            std::io::Write::flush(&mut std::io::stdout())?;
+            let mut answer = String::new();
+            std::io::stdin().read_line(&mut answer)?;
+            if !matches!(answer.trim(), "y" | "Y" | "yes") {
+                println!("Nothing was removed.");
+                return Ok(());
+            }
+        }
+
+        // Only removing the leftovers needs the lock, a dry run also works while rucola is open.
+        let manager = manager.with_lock(io::VaultLock::acquire(&vault_path, take_over)?);
+        manager.ensure_writable()?;
+        let freed = cleanup.apply(&index, &manager)?;
+        println!("Freed {}.", io::format_size(freed));
+        return Ok(());
+    }
+
+    if let Some(path) = args.export_stats.clone() {
+        // Resolve the path now, as it is relative to the working directory, not the vault.
+        let path = std::env::current_dir()?.join(path);
+        let (app, errors) = app::App::read_only(args);
+        for e in errors {
+            eprintln!("{}", e);
+        }
+        app.export_stats(&path)?;
+        return Ok(());
+    }
+
+    if let Some(path) = args.export_graph.clone() {
+        // Resolve the path now, as it is relative to the working directory, not the vault.
+        let path = std::env::current_dir()?.join(path);
+        let (app, errors) = app::App::read_only(args);
+        for e in errors {
+            eprintln!("{}", e);
+        }
+        app.export_graph(&path)?;
+        return Ok(());
+    }
+
+    if args.backup {
+        let (app, errors) = app::App::read_only(args);
+        for e in errors {
+            eprintln!("{}", e);
+        }
+        println!("{}", app.create_backup()?.display());
+        return Ok(());
+    }
+
+    if args.publish {
+        let (app, errors) = app::App::read_only(args);
+        for e in errors {
+            eprintln!("{}", e);
+        }
+        let report = app.publish()?;
+        println!(
+            "Uploaded {} files, {} unchanged.",
+            report.uploaded, report.unchanged
+        );
+        return Ok(());
+    }
+
     // === Actual programm ===
 
     // Initialize hooks & terminal (ratatui boilerplate)
@@ -56,56 +608,118 @@ fn main() -> error::Result<()> {
 
     // create a call back for the loading screen
     // Create the app state
-    let (mut app, errors) =
+    let (mut app, mut errors) =
         app::App::new(args, |message| draw_loading_screen(&mut terminal, message));
+    errors.extend(app.start_background_tasks());
 
     // Displayed error
     let mut current_error: Option<error::RucolaError> = errors.into_iter().next_back();
 
+    // Wether the screen needs to be drawn again, and when it was last drawn
+    let mut redraw = true;
+    let mut last_draw = std::time::Instant::now();
+
+    // The main loop waits for events of the terminal and for background work to wake it up.
+    // A wakeup is only sent if none is pending, so e.g. a burst of file events wakes the loop only once.
+    let (sender, receiver) = std::sync::mpsc::channel();
+    let wakeup_pending = std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false));
+    io::set_wakeup({
+        let sender = sender.clone();
+        let wakeup_pending = wakeup_pending.clone();
+        move || {
+            if !wakeup_pending.swap(true, std::sync::atomic::Ordering::SeqCst) {
+                let _ = sender.send(LoopEvent::Wakeup);
+            }
+        }
+    });
+    let handled = spawn_input_thread(sender);
+
     // Main loop
     'main: loop {
-        // Draw the current screen.
-        terminal.draw(|frame: &mut Frame| {
-            let area = frame.area();
-            let buf = frame.buffer_mut();
-
-            // Make sure area is large enough or show error
-            if area.width < 90 || area.height < 25 {
-                // area too small and no error -> show area error
-                current_error = Some(error::RucolaError::SmallArea);
-            }
+        // Draw the current screen, if anything changed.
+        // While the app is busy, it changes constantly, so frames are drawn at a limited rate.
+        if redraw && (!app.busy() || last_draw.elapsed() >= FRAME_INTERVAL) {
+            redraw = false;
+            last_draw = std::time::Instant::now();
+            terminal.draw(|frame: &mut Frame| {
+                let area = frame.area();
+                let buf = frame.buffer_mut();
 
-            let app_area = match &current_error {
-                // If there is an error to be displayed
-                Some(e) => {
-                    // Separate the usual app area into a small bottom line for the area and a big area for what can be displayed of the app.
-                    let areas =
-                        Layout::vertical([Constraint::Fill(1), Constraint::Length(1)]).split(area);
+                // Make sure area is large enough or show error
+                if area.width < 90 || area.height < 25 {
+                    // area too small and no error -> show area error
+                    current_error = Some(error::RucolaError::SmallArea);
+                }
 
-                    // Render the error to the bottom.
-                    Widget::render(e.to_ratatui(), areas[1], buf);
+                let app_area = match &current_error {
+                    // If there is an error to be displayed
+                    Some(e) => {
+                        // Separate the usual app area into two small bottom lines for the error and a big area for what can be displayed of the app.
+                        let areas = Layout::vertical([Constraint::Fill(1), Constraint::Length(2)])
+                            .split(area);
 
-                    // Return the rest of the area for the app to render in.
-                    areas[0]
-                }
-                // No error => App can render in the entire area.
-                None => area,
-            };
+                        // Render the error to the bottom.
+                        Widget::render(e.to_ratatui(), areas[1], buf);
 
-            Widget::render(ratatui::widgets::Clear, app_area, buf);
+                        // Return the rest of the area for the app to render in.
+                        areas[0]
+                    }
+                    // No error => App can render in the entire area.
+                    None => area,
+                };
 
-            // Draw the actual application
-            app.draw(app_area, buf);
-        })?;
+                Widget::render(ratatui::widgets::Clear, app_area, buf);
+
+                // Draw the actual application
+                app.draw(app_area, buf);
+            })?;
+        }
 
         // Inform the app of events
-        let maybe_keypress = if event::poll(std::time::Duration::from_millis(500))? {
-            // Some event => reset current error
-            current_error = None;
+        // When the app is busy, only check for events instead of waiting for them
+        // With unsaved edits, wait at most until they are due to be written
+        let loop_event = if app.busy() {
+            receiver.try_recv().ok()
+        } else if let Some(timeout) = app.next_save_in() {
+            receiver.recv_timeout(timeout).ok()
+        } else {
+            receiver.recv().ok()
+        };
+        let terminal_event = match loop_event {
+            Some(LoopEvent::Terminal(terminal_event)) => Some(terminal_event?),
+            Some(LoopEvent::Wakeup) => {
+                wakeup_pending.store(false, std::sync::atomic::Ordering::SeqCst);
+                None
+            }
+            None => None,
+        };
+        let was_terminal_event = terminal_event.is_some();
+        let maybe_keypress = if let Some(terminal_event) = terminal_event {
+            // Any event, such as a resize, may change what is shown
+            redraw = true;
             // Check if the event was a keypress
-            match event::read()? {
-                event::Event::Key(key) if key.kind == event::KeyEventKind::Press => Some(key),
-                _ => None,
+            match terminal_event {
+                // Ctrl+Y => copy the details of the current error and keep showing it
+                event::Event::Key(key)
+                    if key.kind == event::KeyEventKind::Press
+                        && key.code == event::KeyCode::Char('y')
+                        && key.modifiers.contains(event::KeyModifiers::CONTROL)
+                        && current_error.is_some() =>
+                {
+                    if let Some(e) = &current_error {
+                        copy_to_clipboard(&e.details())?;
+                    }
+                    None
+                }
+                // Some other event => reset current error
+                event::Event::Key(key) if key.kind == event::KeyEventKind::Press => {
+                    current_error = None;
+                    Some(key)
+                }
+                _ => {
+                    current_error = None;
+                    None
+                }
             }
         } else {
             None
@@ -118,24 +732,69 @@ fn main() -> error::Result<()> {
             }
             Ok(ui::TerminalMessage::None) => {}
             Ok(ui::TerminalMessage::OpenExternalCommand(mut cmd)) => {
+                // The external program has to see all edits made so far
+                if let Err(e) = app.save_edits() {
+                    current_error = Some(e);
+                }
                 // Restore the terminal
                 restore_terminal()?;
                 // Execute the given command
                 cmd.status()?;
                 // Re-enter the tui state
                 terminal = init_terminal()?;
+                redraw = true;
+            }
+            Err(e) => {
+                current_error = Some(e);
+                redraw = true;
             }
-            Err(e) => current_error = Some(e),
+        }
+        redraw |= app.take_redraw();
+
+        // Let the input thread read the next event, now that external programs are done with the terminal
+        if was_terminal_event {
+            let _ = handled.send(());
         }
     }
 
     //Restore previous terminal state
     restore_terminal()?;
 
+    // Write the edits that have not been saved yet
+    app.save_edits()?;
+
+    // Remember the state of the notes, to notice unexpected changes before the next start
+    app.record_checksums()?;
+
     // Return the right OK
     Ok(())
 }
 
+/// An event the main loop waits for.
+enum LoopEvent {
+    /// An event of the terminal, such as a key press or a resize, or the error reading it.
+    Terminal(std::io::Result<event::Event>),
+    /// Work in the background has results to show, see [`io::set_wakeup`].
+    Wakeup,
+}
+
+/// Reads the events of the terminal on a separate thread and sends them to the main loop.
+/// The returned sender tells the thread that the last event was handled. Only then it reads the next, so it does not take the input of external programs such as editors opened in the meantime.
+fn spawn_input_thread(sender: std::sync::mpsc::Sender<LoopEvent>) -> std::sync::mpsc::Sender<()> {
+    let (handled_sender, handled_receiver) = std::sync::mpsc::channel();
+    std::thread::spawn(move || loop {
+        let terminal_event = event::read();
+        let failed = terminal_event.is_err();
+        if sender.send(LoopEvent::Terminal(terminal_event)).is_err()
+            || failed
+            || handled_receiver.recv().is_err()
+        {
+            return;
+        }
+    });
+    handled_sender
+}
+
 /// Ratatui boilerplate to set up panic hooks
 fn init_hooks() -> error::Result<()> {
     // Get a default panic hook
@@ -165,6 +824,18 @@ fn restore_terminal() -> std::io::Result<()> {
     Ok(())
 }
 
+/// Copies the given text to the clipboard of the terminal emulator, using the OSC 52 escape sequence.
+fn copy_to_clipboard(text: &str) -> std::io::Result<()> {
+    use std::io::Write;
+    let mut stdout = std::io::stdout();
+    write!(
+        stdout,
+        "\x1b]52;c;{}\x07",
+        base64::Engine::encode(&base64::engine::general_purpose::STANDARD, text)
+    )?;
+    stdout.flush()
+}
+
 /// Prints license information to the console.
 fn print_license() {
     print!("Rucola is released under the GNU General Public License v3, available at <https://www.gnu.org/licenses/gpl-3.0>.
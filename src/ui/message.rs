@@ -13,6 +13,70 @@ pub enum Message {
     DisplayStackPush(String),
     /// Restore the terminal, execute the given command and re-enter
     OpenExternalCommand(Box<std::process::Command>),
+    /// Opens the trash screen on top of the current screen.
+    OpenTrash,
+    /// Opens the git history screen on top of the current screen.
+    OpenHistory,
+    /// Opens the version browser for the note with the given id on top of the current screen.
+    OpenVersions(String),
+    /// Opens the reading mode for the note with the given id on top of the current screen.
+    OpenPreview(String),
+    /// Opens the list of notes visited in this session on top of the current screen.
+    OpenJumpList,
+    /// Displays the entry of the navigation history with the given index.
+    JumpTo(usize),
+    /// Writes a backup archive of the vault.
+    CreateBackup,
+    /// Synchronizes the vault with the WebDAV server in the background.
+    Sync,
+    /// Uploads the HTML files of all notes to the configured bucket.
+    Publish,
+    /// Exports the notes with the given ids in the given way, showing the progress on top of the current screen.
+    BatchExport(crate::io::ExportKind, Vec<String>),
+    /// Exports the notes of the export profile with the given name, showing the progress on top of the current screen.
+    RunExportProfile(String),
+    /// Uploads the HTML files of the notes with the given ids to the configured bucket, at the given prefix if set.
+    PublishNotes(Vec<String>, Option<String>),
+    /// Builds the PDF file of the note with the given id and opens it in the primary (true) or secondary viewer once it is done.
+    ViewPdf(String, bool),
+    /// Opens the list of files that could not be loaded on top of the current screen.
+    OpenFailures,
+    /// Tries again to load the files that could not be loaded and to watch the vault.
+    RetryFailures,
+    /// Opens the tags suggested for the most recently changed note on top of the current screen.
+    ReviewTagSuggestions,
+    /// Hides the tags suggested for the most recently changed note.
+    DismissTagSuggestions,
+    /// Copies the files linked by notes copied into the vault into it and rewrites the links to them.
+    ApplyImports,
+    /// Keeps the links of notes copied into the vault as they are.
+    DismissImports,
+    /// Takes over the lock of the vault from the other instance of rucola holding it.
+    TakeOverLock,
+    /// Summarizes the note with the given id in the background and stores the summary in its frontmatter.
+    Summarize(String),
+    /// Sends the note with the given id by mail to the given addresses in the background.
+    EmailNote(String, Vec<String>),
+    /// Opens the snippet picker to insert a snippet into the note with the given id.
+    OpenSnippets(String),
+    /// Opens the problems found in the note with the given id, or in all notes of the vault, on top of the current screen.
+    OpenProblems(Option<String>),
+    /// Opens the tags of the vault and where they are used, with the given tag selected, on top of the current screen.
+    OpenTagUsage(Option<String>),
+    /// Opens the links of the vault to notes that do not exist on top of the current screen.
+    OpenBrokenLinks,
+    /// Opens the notes not modified for longer than the stale rules allow on top of the current screen.
+    OpenStaleNotes,
+    /// Opens the people mentioned in the vault and where they are mentioned, with the person with the given id selected, on top of the current screen.
+    OpenPeople(Option<String>),
+    /// Opens the semantic search on top of the current screen.
+    OpenSemanticSearch,
+    /// Opens the collections of the vault on top of the current screen, allowing to add the note with the given id to them.
+    OpenCollections(Option<String>),
+    /// Shows the notes of the collection with the given name in the select screen.
+    ShowCollection(String),
+    /// Closes the screen currently shown on top of the select and display screens.
+    CloseOverlay,
 }
 
 /// Messages sent from the application to the terminal.
@@ -32,7 +96,39 @@ impl From<Message> for TerminalMessage {
             Message::None
             | Message::DisplayStackClear
             | Message::DisplayStackPop
-            | Message::DisplayStackPush(_) => Self::None,
+            | Message::DisplayStackPush(_)
+            | Message::OpenTrash
+            | Message::OpenHistory
+            | Message::OpenVersions(_)
+            | Message::OpenPreview(_)
+            | Message::OpenJumpList
+            | Message::JumpTo(_)
+            | Message::BatchExport(..)
+            | Message::RunExportProfile(_)
+            | Message::PublishNotes(..)
+            | Message::ViewPdf(..)
+            | Message::CreateBackup
+            | Message::Sync
+            | Message::Publish
+            | Message::OpenFailures
+            | Message::RetryFailures
+            | Message::ReviewTagSuggestions
+            | Message::DismissTagSuggestions
+            | Message::ApplyImports
+            | Message::DismissImports
+            | Message::TakeOverLock
+            | Message::Summarize(_)
+            | Message::EmailNote(..)
+            | Message::OpenSnippets(_)
+            | Message::OpenSemanticSearch
+            | Message::OpenProblems(_)
+            | Message::OpenTagUsage(_)
+            | Message::OpenBrokenLinks
+            | Message::OpenStaleNotes
+            | Message::OpenPeople(_)
+            | Message::OpenCollections(_)
+            | Message::ShowCollection(_)
+            | Message::CloseOverlay => Self::None,
             Message::Quit => Self::Quit,
             Message::OpenExternalCommand(cmd) => Self::OpenExternalCommand(cmd),
         }
@@ -6,5 +6,17 @@ pub mod screen;
 
 pub use screen::Screen;
 
-mod uistyles;
-pub use uistyles::UiStyles;
+pub use rucola_core::styles::bordered;
+pub use rucola_core::styles::screen_reader;
+pub use rucola_core::styles::selection_marker;
+pub use rucola_core::styles::separator;
+pub use rucola_core::styles::set_screen_reader;
+pub use rucola_core::styles::TagStyles;
+pub use rucola_core::styles::UiStyles;
+
+pub use rucola_core::i18n::hotkeys;
+pub use rucola_core::i18n::set_catalog;
+pub use rucola_core::i18n::tr;
+pub use rucola_core::i18n::tr_args;
+pub use rucola_core::i18n::tr_hotkeys;
+pub use rucola_core::i18n::Catalog;
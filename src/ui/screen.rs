@@ -1,12 +1,67 @@
 use ratatui::{buffer, layout};
 
 mod select_screen;
+pub use select_screen::RecentNotes;
 pub use select_screen::SelectScreen;
-pub use select_screen::StatsShow;
 
 mod display_screen;
+pub use display_screen::DisplayPosition;
 pub use display_screen::DisplayScreen;
 
+mod trash_screen;
+pub use trash_screen::TrashScreen;
+
+mod history_screen;
+pub use history_screen::HistoryScreen;
+
+mod versions_screen;
+pub use versions_screen::VersionsScreen;
+
+mod preview_screen;
+pub use preview_screen::FoldState;
+pub use preview_screen::PreviewScreen;
+
+mod jump_list_screen;
+pub use jump_list_screen::JumpListScreen;
+
+mod export_screen;
+pub use export_screen::ExportScreen;
+
+mod pdf_build_screen;
+pub use pdf_build_screen::PdfBuildScreen;
+
+mod failures_screen;
+pub use failures_screen::FailuresScreen;
+
+#[cfg(feature = "semantic-search")]
+mod semantic_search_screen;
+#[cfg(feature = "semantic-search")]
+pub use semantic_search_screen::SemanticSearchScreen;
+
+mod problems_screen;
+pub use problems_screen::ProblemsScreen;
+
+mod snippet_screen;
+pub use snippet_screen::SnippetScreen;
+
+mod tag_suggestions_screen;
+pub use tag_suggestions_screen::TagSuggestionsScreen;
+
+mod tag_usage_screen;
+pub use tag_usage_screen::TagUsageScreen;
+
+mod broken_links_screen;
+pub use broken_links_screen::BrokenLinksScreen;
+
+mod stale_notes_screen;
+pub use stale_notes_screen::StaleNotesScreen;
+
+mod people_screen;
+pub use people_screen::PeopleScreen;
+
+mod collections_screen;
+pub use collections_screen::CollectionsScreen;
+
 use crate::{error, ui};
 
 /// A trait that is implemented by different screens within the application.
@@ -16,6 +71,26 @@ pub trait Screen {
 
     /// Informs the screen of user messages and possibly modifies the content.
     fn update(&mut self, key: ratatui::crossterm::event::KeyEvent) -> error::Result<ui::Message>;
+
+    /// Lets the screen continue work in progress while there is no user input.
+    /// Returns a message once finished work should have an effect, e.g. opening a viewer.
+    fn tick(&mut self) -> error::Result<ui::Message> {
+        Ok(ui::Message::None)
+    }
+
+    /// Informs the screen of the ids of notes whose files changed, so it can restart work on them.
+    fn notes_changed(&mut self, _ids: &[String]) {}
+
+    /// Wether the screen has work in progress and wants to be ticked as soon as possible.
+    fn busy(&self) -> bool {
+        false
+    }
+
+    /// Describes where the user is and what is selected, announced to screen readers in the first line.
+    /// Returns `None` if the screen has nothing to announce beyond its titles.
+    fn announcement(&self) -> Option<String> {
+        None
+    }
 }
 
 // Clears a text area and returns the contained string, if any.
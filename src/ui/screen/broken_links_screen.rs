@@ -0,0 +1,173 @@
+use crate::{data, error, io, ui};
+
+use itertools::Itertools;
+use ratatui::crossterm::event::KeyCode;
+use ratatui::{prelude::*, widgets::*};
+
+/// The broken links screen lists all links of the vault to notes that do not exist, so they can be fixed or the notes created.
+pub struct BrokenLinksScreen {
+    // === CONFIG ===
+    /// The used styles.
+    styles: ui::UiStyles,
+    /// The file manager used to open notes at the line of a link.
+    manager: io::FileManager,
+
+    // === DATA ===
+    /// The index whose links are checked.
+    index: data::NoteIndexContainer,
+    /// The broken links, with the name of their note.
+    links: Vec<(String, data::BrokenLink)>,
+
+    // === UI ===
+    /// The index of the link selected in the table.
+    selected: usize,
+}
+
+impl BrokenLinksScreen {
+    /// Creates a new broken links screen for the given index.
+    pub fn new(
+        index: data::NoteIndexContainer,
+        manager: io::FileManager,
+        styles: ui::UiStyles,
+    ) -> Self {
+        let mut res = Self {
+            styles,
+            manager,
+            index,
+            links: Vec::new(),
+            selected: 0,
+        };
+        res.find_links();
+        res
+    }
+
+    /// Finds the broken links of the vault again.
+    fn find_links(&mut self) {
+        let index = self.index.borrow();
+        self.links = data::broken_links(&index)
+            .into_iter()
+            .map(|link| {
+                let name = index
+                    .get(&link.id)
+                    .map(|note| note.display_name.clone())
+                    .unwrap_or_else(|| link.id.clone());
+                (name, link)
+            })
+            .collect();
+        self.selected = self.selected.min(self.links.len().saturating_sub(1));
+    }
+}
+
+impl super::Screen for BrokenLinksScreen {
+    fn draw(&self, area: layout::Rect, buf: &mut buffer::Buffer) {
+        let title = Line::from(vec![Span::styled(
+            ui::tr("broken-links-title"),
+            self.styles.title_style,
+        )]);
+
+        let count = Line::from(vec![Span::styled(
+            ui::tr_args("broken-links-count", &[("count", self.links.len().into())]),
+            self.styles.text_style,
+        )])
+        .right_aligned();
+
+        let instructions = Line::from(ui::tr_hotkeys(
+            "broken-links-hints",
+            self.styles.hotkey_style,
+            self.styles.text_style,
+        ))
+        .left_aligned();
+
+        let rows = self
+            .links
+            .iter()
+            .map(|(name, link)| {
+                Row::new(vec![
+                    Span::styled(name.as_str(), self.styles.subtitle_style),
+                    Span::styled(
+                        link.line.map(|line| line.to_string()).unwrap_or_default(),
+                        self.styles.text_style,
+                    ),
+                    Span::styled(link.target.as_str(), self.styles.text_style),
+                ])
+            })
+            .collect_vec();
+
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Percentage(30),
+                Constraint::Length(5),
+                Constraint::Fill(1),
+            ],
+        )
+        .column_spacing(2)
+        .header(Row::new(vec![
+            Span::styled(ui::tr("column-note"), self.styles.subtitle_style),
+            Span::styled(
+                ui::tr("broken-links-column-line"),
+                self.styles.subtitle_style,
+            ),
+            Span::styled(
+                ui::tr("broken-links-column-link"),
+                self.styles.subtitle_style,
+            ),
+        ]))
+        .highlight_symbol(ui::selection_marker())
+        .row_highlight_style(self.styles.selected_style)
+        .block(
+            ui::bordered()
+                .title_top(title)
+                .title_bottom(instructions)
+                .title_bottom(count),
+        );
+
+        let mut state = TableState::new()
+            .with_offset(self.selected.saturating_sub(area.height as usize / 3))
+            .with_selected(Some(self.selected));
+
+        StatefulWidget::render(table, area, buf, &mut state);
+    }
+
+    fn update(&mut self, key: ratatui::crossterm::event::KeyEvent) -> error::Result<ui::Message> {
+        match key.code {
+            KeyCode::Char('q' | 'Q') => return Ok(ui::Message::Quit),
+            // Return to the previous screen
+            KeyCode::Esc | KeyCode::Left | KeyCode::Char('h' | 'H') => {
+                return Ok(ui::Message::CloseOverlay);
+            }
+            KeyCode::Char('j' | 'J') | KeyCode::Down => {
+                self.selected = self
+                    .selected
+                    .saturating_add(1)
+                    .min(self.links.len().saturating_sub(1));
+            }
+            KeyCode::Char('k' | 'K') | KeyCode::Up => {
+                self.selected = self.selected.saturating_sub(1);
+            }
+            // Enter: Edit the note of the selected link at its line
+            KeyCode::Enter => {
+                if let Some((_, link)) = self.links.get(self.selected) {
+                    if let Some(note) = self.index.borrow().get(&link.id) {
+                        return Ok(ui::Message::OpenExternalCommand(Box::new(
+                            self.manager.create_edit_command_at(&note.path, link.line)?,
+                        )));
+                    }
+                }
+            }
+            // O: Open the note of the selected link
+            KeyCode::Char('o' | 'O') => {
+                if let Some((_, link)) = self.links.get(self.selected) {
+                    return Ok(ui::Message::DisplayStackPush(link.id.clone()));
+                }
+            }
+            _ => {}
+        }
+
+        Ok(ui::Message::None)
+    }
+
+    fn notes_changed(&mut self, _ids: &[String]) {
+        self.find_links();
+    }
+}
@@ -0,0 +1,410 @@
+use crate::{data, error, io, ui};
+
+use itertools::Itertools;
+use ratatui::crossterm::event::KeyCode;
+use ratatui::{prelude::*, widgets::*};
+use tui_textarea::TextArea;
+
+/// The collections screen lists the collections of the vault and their notes, and adds notes to them or removes them.
+pub struct CollectionsScreen {
+    // === CONFIG ===
+    /// The used styles.
+    styles: ui::UiStyles,
+
+    // === DATA ===
+    /// The collections of the vault, shared with the select screen.
+    collections: io::Collections,
+    /// The export profiles, used to export collections they are attached to.
+    export_profiles: io::ExportProfiles,
+    /// The index the notes are named from.
+    index: data::NoteIndexContainer,
+    /// The id of the note that can be added to or removed from the collections, if the screen was opened for one.
+    note: Option<String>,
+    /// The names of all collections and the number of their notes.
+    names: Vec<(String, usize)>,
+    /// The ids and names of the notes in the selected collection, names being `None` for notes that no longer exist.
+    notes: Vec<(String, Option<String>)>,
+
+    // === UI ===
+    /// The text area to enter the name of a new collection.
+    name_area: TextArea<'static>,
+    /// Wether keys are entered into the name of a new collection.
+    creating: bool,
+    /// The index of the selected collection.
+    selected_collection: usize,
+    /// The index of the selected note.
+    selected_note: usize,
+    /// Wether the notes rather than the collections are focused.
+    notes_focused: bool,
+}
+
+impl CollectionsScreen {
+    /// Creates a new collections screen. If opened for a note, it can be added to or removed from the collections.
+    pub fn new(
+        collections: io::Collections,
+        export_profiles: io::ExportProfiles,
+        index: data::NoteIndexContainer,
+        note: Option<String>,
+        styles: ui::UiStyles,
+    ) -> Self {
+        let mut name_area = TextArea::default();
+        name_area.set_style(styles.input_style);
+        name_area.set_cursor_line_style(styles.input_style);
+        name_area.set_block(ui::bordered().title(Span::styled(
+            ui::tr("collections-prompt"),
+            styles.title_style,
+        )));
+
+        let mut res = Self {
+            styles,
+            collections,
+            export_profiles,
+            index,
+            note,
+            names: Vec::new(),
+            notes: Vec::new(),
+            name_area,
+            creating: false,
+            selected_collection: 0,
+            selected_note: 0,
+            notes_focused: false,
+        };
+        res.reload();
+        res
+    }
+
+    /// Reads the collections and the notes of the selected one again after they changed.
+    fn reload(&mut self) {
+        self.names = self.collections.names();
+        self.selected_collection = self
+            .selected_collection
+            .min(self.names.len().saturating_sub(1));
+        self.find_notes();
+    }
+
+    /// Lists the notes of the selected collection again.
+    fn find_notes(&mut self) {
+        let index = self.index.borrow();
+        self.notes = self
+            .selected_name()
+            .map(|name| self.collections.notes(name))
+            .unwrap_or_default()
+            .into_iter()
+            .map(|id| {
+                let name = index.get(&id).map(|note| note.display_name.clone());
+                (id, name)
+            })
+            .collect();
+        self.selected_note = self.selected_note.min(self.notes.len().saturating_sub(1));
+    }
+
+    /// Returns the name of the selected collection, if there is one.
+    fn selected_name(&self) -> Option<&str> {
+        self.names
+            .get(self.selected_collection)
+            .map(|(name, _count)| name.as_str())
+    }
+
+    /// Moves the selection within the focused list down or up by one entry.
+    fn move_selection(&mut self, down: bool) {
+        if self.notes_focused {
+            self.selected_note = if down {
+                self.selected_note
+                    .saturating_add(1)
+                    .min(self.notes.len().saturating_sub(1))
+            } else {
+                self.selected_note.saturating_sub(1)
+            };
+        } else {
+            let selected = if down {
+                self.selected_collection
+                    .saturating_add(1)
+                    .min(self.names.len().saturating_sub(1))
+            } else {
+                self.selected_collection.saturating_sub(1)
+            };
+            if selected != self.selected_collection {
+                self.selected_collection = selected;
+                self.selected_note = 0;
+                self.find_notes();
+            }
+        }
+    }
+}
+
+impl super::Screen for CollectionsScreen {
+    fn draw(&self, area: layout::Rect, buf: &mut buffer::Buffer) {
+        let [name_area, area] = Layout::vertical([
+            Constraint::Length(if self.creating { 3 } else { 0 }),
+            Constraint::Fill(1),
+        ])
+        .areas(area);
+        let [collections_area, notes_area] =
+            Layout::horizontal([Constraint::Percentage(30), Constraint::Fill(1)]).areas(area);
+
+        Widget::render(&self.name_area, name_area, buf);
+
+        // === Collections ===
+        let rows = self
+            .names
+            .iter()
+            .map(|(name, count)| {
+                let mark = match &self.note {
+                    Some(id) if self.collections.contains(name, id) => "[x]",
+                    Some(_) => "[ ]",
+                    None => "",
+                };
+                Row::new(vec![
+                    Span::styled(mark, self.styles.text_style),
+                    Span::styled(name.as_str(), self.styles.subtitle_style),
+                    Span::styled(format!("{:5}", count), self.styles.text_style),
+                ])
+            })
+            .collect_vec();
+
+        let collections_table = Table::new(
+            rows,
+            [
+                Constraint::Length(if self.note.is_some() { 3 } else { 0 }),
+                Constraint::Fill(1),
+                Constraint::Length(5),
+            ],
+        )
+        .column_spacing(1)
+        .highlight_symbol(ui::selection_marker())
+        .row_highlight_style(if self.notes_focused {
+            self.styles.text_style
+        } else {
+            self.styles.selected_style
+        })
+        .block(ui::bordered().title(Span::styled(
+            ui::tr("collections-title"),
+            self.styles.title_style,
+        )));
+
+        let mut collections_state = TableState::new()
+            .with_offset(
+                self.selected_collection
+                    .saturating_sub(collections_area.height as usize / 3),
+            )
+            .with_selected(Some(self.selected_collection));
+
+        StatefulWidget::render(
+            collections_table,
+            collections_area,
+            buf,
+            &mut collections_state,
+        );
+
+        // === Notes ===
+        let title = Line::from(match self.selected_name() {
+            Some(name) => vec![
+                Span::styled(
+                    ui::tr("collections-title-notes") + " ",
+                    self.styles.title_style,
+                ),
+                Span::styled(name, self.styles.subtitle_style),
+            ],
+            None => vec![Span::styled(
+                ui::tr("collections-title"),
+                self.styles.title_style,
+            )],
+        });
+
+        // the export profile attached to the collection
+        let profile = Line::from(
+            self.selected_name()
+                .and_then(|name| self.export_profiles.for_collection(name))
+                .map(|profile| {
+                    vec![Span::styled(
+                        ui::tr_args("collections-profile", &[("profile", profile.into())]),
+                        self.styles.text_style,
+                    )]
+                })
+                .unwrap_or_default(),
+        )
+        .right_aligned();
+
+        let count = Line::from(vec![Span::styled(
+            ui::tr_args("notes-count", &[("count", self.notes.len().into())]),
+            self.styles.text_style,
+        )])
+        .right_aligned();
+
+        // the hints of the focused list
+        let mut instructions = ui::tr_hotkeys(
+            if self.notes_focused {
+                "collections-notes-hints"
+            } else {
+                "collections-hints"
+            },
+            self.styles.hotkey_style,
+            self.styles.text_style,
+        );
+        if self.note.is_some() && !self.notes_focused {
+            instructions.push(Span::styled(ui::separator(), self.styles.text_style));
+            instructions.extend(ui::tr_hotkeys(
+                "collections-hints-toggle",
+                self.styles.hotkey_style,
+                self.styles.text_style,
+            ));
+        }
+        let instructions = Line::from(instructions).left_aligned();
+
+        let rows = if self.names.is_empty() {
+            vec![Row::new(vec![Span::styled(
+                ui::tr("collections-empty"),
+                self.styles.text_style,
+            )])]
+        } else {
+            self.notes
+                .iter()
+                .map(|(id, name)| {
+                    Row::new(vec![match name {
+                        Some(name) => Span::styled(name.as_str(), self.styles.subtitle_style),
+                        None => Span::styled(
+                            ui::tr_args("collections-missing", &[("id", id.as_str().into())]),
+                            self.styles.text_style,
+                        ),
+                    }])
+                })
+                .collect_vec()
+        };
+
+        let notes_table = Table::new(rows, [Constraint::Fill(1)])
+            .highlight_symbol(ui::selection_marker())
+            .row_highlight_style(if self.notes_focused {
+                self.styles.selected_style
+            } else {
+                self.styles.text_style
+            })
+            .block(
+                ui::bordered()
+                    .title_top(title)
+                    .title_top(profile)
+                    .title_bottom(instructions)
+                    .title_bottom(count),
+            );
+
+        let mut notes_state = TableState::new()
+            .with_offset(
+                self.selected_note
+                    .saturating_sub(notes_area.height as usize / 3),
+            )
+            .with_selected((!self.names.is_empty()).then_some(self.selected_note));
+
+        StatefulWidget::render(notes_table, notes_area, buf, &mut notes_state);
+    }
+
+    fn update(&mut self, key: ratatui::crossterm::event::KeyEvent) -> error::Result<ui::Message> {
+        if self.creating {
+            match key.code {
+                KeyCode::Esc => {
+                    let _ = super::extract_string_and_clear(&mut self.name_area);
+                    self.creating = false;
+                }
+                KeyCode::Enter => {
+                    let name =
+                        super::extract_string_and_clear(&mut self.name_area).unwrap_or_default();
+                    self.creating = false;
+                    self.collections.create(name.trim())?;
+                    self.reload();
+                    // select the new collection
+                    if let Some(position) = self
+                        .names
+                        .iter()
+                        .position(|(other, _count)| other == name.trim())
+                    {
+                        self.selected_collection = position;
+                        self.find_notes();
+                    }
+                }
+                _ => {
+                    self.name_area.input(key);
+                }
+            }
+            return Ok(ui::Message::None);
+        }
+
+        match key.code {
+            KeyCode::Char('q' | 'Q') => return Ok(ui::Message::Quit),
+            KeyCode::Esc => return Ok(ui::Message::CloseOverlay),
+            KeyCode::Char('j' | 'J') | KeyCode::Down => self.move_selection(true),
+            KeyCode::Char('k' | 'K') | KeyCode::Up => self.move_selection(false),
+            // Switch between the collections and their notes
+            KeyCode::Tab | KeyCode::BackTab => {
+                self.notes_focused = !self.notes_focused;
+            }
+            // Go back to the collections, or close the screen if they are focused already
+            KeyCode::Left | KeyCode::Char('h' | 'H') => {
+                if !self.notes_focused {
+                    return Ok(ui::Message::CloseOverlay);
+                }
+                self.notes_focused = false;
+            }
+            KeyCode::Right | KeyCode::Char('l' | 'L') => {
+                self.notes_focused = true;
+            }
+            // N: Create a new collection
+            KeyCode::Char('n' | 'N') => {
+                self.creating = true;
+            }
+            // D: Delete the selected collection, keeping its notes
+            KeyCode::Char('d' | 'D') if !self.notes_focused => {
+                if let Some(name) = self.selected_name().map(str::to_owned) {
+                    self.collections.delete(&name)?;
+                    self.reload();
+                }
+            }
+            // Space: Add the note the screen was opened for to the selected collection, or remove it
+            KeyCode::Char(' ') => {
+                if let (Some(id), Some(name)) = (&self.note, self.selected_name()) {
+                    self.collections.toggle(name, id)?;
+                    self.reload();
+                }
+            }
+            // R: Remove the selected note from the collection
+            KeyCode::Char('r' | 'R') | KeyCode::Delete if self.notes_focused => {
+                if let (Some((id, _name)), Some(name)) =
+                    (self.notes.get(self.selected_note), self.selected_name())
+                {
+                    self.collections.toggle(name, id)?;
+                    self.reload();
+                }
+            }
+            // X: Export the notes of the selected collection with its export profile, or to a zip archive named after it
+            KeyCode::Char('x' | 'X') => {
+                if let Some(name) = self.selected_name() {
+                    if let Some(profile) = self.export_profiles.for_collection(name) {
+                        return Ok(ui::Message::RunExportProfile(profile.to_owned()));
+                    }
+                    return Ok(ui::Message::BatchExport(
+                        io::ExportKind::Zip(std::path::PathBuf::from(format!("{}.zip", name))),
+                        self.notes
+                            .iter()
+                            .filter(|(_id, name)| name.is_some())
+                            .map(|(id, _name)| id.clone())
+                            .collect(),
+                    ));
+                }
+            }
+            // Enter: Show the notes of the selected collection in the list, or open the selected note
+            KeyCode::Enter => {
+                if self.notes_focused {
+                    if let Some((id, Some(_name))) = self.notes.get(self.selected_note) {
+                        return Ok(ui::Message::DisplayStackPush(id.clone()));
+                    }
+                } else if let Some(name) = self.selected_name() {
+                    return Ok(ui::Message::ShowCollection(name.to_owned()));
+                }
+            }
+            _ => {}
+        }
+
+        Ok(ui::Message::None)
+    }
+
+    fn notes_changed(&mut self, _ids: &[String]) {
+        self.reload();
+    }
+}
@@ -4,6 +4,12 @@ use itertools::Itertools;
 use ratatui::crossterm::event::KeyCode;
 use ratatui::{prelude::*, widgets::*};
 
+/// The number of keywords shown in the statistics of a note.
+const KEYWORD_COUNT: usize = 6;
+
+/// The maximum number of keywords suggested as tags at once.
+const TAG_SUGGESTION_COUNT: usize = 8;
+
 /// Describes the current mode of the UI.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
 enum DisplayMode {
@@ -16,8 +22,27 @@ enum DisplayMode {
     Move,
     /// Confirming delete
     Delete,
+    /// Choosing which keywords to add as tags.
+    TagSuggestions,
 }
 
+/// The position of the user within a display screen, used to return to it later.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub struct DisplayPosition {
+    /// The index of the note selected in each table
+    selected: [usize; 4],
+    /// The index of the primary table currently focused
+    foc_table: usize,
+}
+
+/// The ids of the titles of the link tables, in the order of [`DisplayScreen::links`].
+const LINK_TABLE_TITLES: [&str; 4] = [
+    "display-title-backlinks",
+    "display-title-links",
+    "display-title-backlinks-2",
+    "display-title-links-2",
+];
+
 /// The display screen displays a single note to the user.
 pub struct DisplayScreen {
     // === CONFIG ===
@@ -27,6 +52,8 @@ pub struct DisplayScreen {
     builder: io::HtmlBuilder,
     /// The used styles.
     styles: ui::UiStyles,
+    /// The styles of tags configured by the user.
+    tag_styles: ui::TagStyles,
 
     // === DATA ===
     /// The internal stats of the displayed note.
@@ -39,6 +66,10 @@ pub struct DisplayScreen {
     /// - l2 backlinks
     /// - l2 links
     links: [Vec<(String, String)>; 4],
+    /// The terms most characteristic for the displayed note.
+    keywords: Vec<data::Keyword>,
+    /// Keywords proposed to be added as tags, and wether the user accepted them.
+    tag_suggestions: Vec<(data::Keyword, bool)>,
 
     // === UI ===
     /// The text area used to create new notes.
@@ -49,6 +80,10 @@ pub struct DisplayScreen {
     foc_table: usize,
     /// Current input mode
     mode: DisplayMode,
+    /// The index of the selected tag suggestion.
+    tag_suggestions_selected: usize,
+    /// Wether a preview of the selected link is shown next to its table.
+    link_preview: bool,
 }
 
 impl DisplayScreen {
@@ -58,7 +93,9 @@ impl DisplayScreen {
         index: data::NoteIndexContainer,
         manager: io::FileManager,
         builder: io::HtmlBuilder,
+        visits: &io::Visits,
         styles: ui::UiStyles,
+        tag_styles: ui::TagStyles,
     ) -> error::Result<Self> {
         let index_b = index.borrow();
         // Cache the note
@@ -86,6 +123,9 @@ impl DisplayScreen {
             .unique()
             .collect();
 
+        // Find the keywords of the note
+        let keywords = index_b.keywords(note_id, KEYWORD_COUNT);
+
         // Create input area and style it
 
         let mut name_area = tui_textarea::TextArea::default();
@@ -94,27 +134,64 @@ impl DisplayScreen {
         name_area.set_cursor_line_style(styles.input_style);
 
         let title_top = block::Title::from(Line::from(vec![Span::styled(
-            "Enter note name...",
+            ui::tr("prompt-name"),
             styles.title_style,
         )]));
-        name_area.set_block(Block::bordered().title(title_top));
+        name_area.set_block(ui::bordered().title(title_top));
 
         drop(index_b);
 
+        // Show frequently visited notes first, so they are easier to reach
+        let mut links = [l1blinks, l1links, l2blinks, l2links];
+        for table in links.iter_mut() {
+            table.sort_by_key(|(id, _name)| std::cmp::Reverse(visits.count(id)));
+        }
+
         Ok(Self {
-            links: [l1blinks, l1links, l2blinks, l2links],
+            links,
+            keywords,
+            tag_suggestions: Vec::new(),
             note,
             index,
             manager,
             builder,
             styles,
+            tag_styles,
             name_area,
             selected: [0; 4],
             foc_table: 0,
             mode: DisplayMode::Display,
+            tag_suggestions_selected: 0,
+            link_preview: false,
         })
     }
 
+    /// Returns the id of the displayed note.
+    pub fn note_id(&self) -> String {
+        data::name_to_id(&self.note.name)
+    }
+
+    /// Returns the current position of the user within this screen.
+    pub fn position(&self) -> DisplayPosition {
+        DisplayPosition {
+            selected: self.selected,
+            foc_table: self.foc_table,
+        }
+    }
+
+    /// Returns to a previously saved position, as far as the link tables still allow it.
+    pub fn set_position(&mut self, position: DisplayPosition) {
+        for ((selected, saved), list) in self
+            .selected
+            .iter_mut()
+            .zip(position.selected)
+            .zip(&self.links)
+        {
+            *selected = saved.min(list.len().saturating_sub(1));
+        }
+        self.foc_table = position.foc_table % 4;
+    }
+
     /// Sets the title & content of the name_area block
     fn set_name_area(&mut self, title: &str, content: Option<String>) {
         let title_top = block::Title::from(Line::from(vec![Span::styled(
@@ -122,7 +199,7 @@ impl DisplayScreen {
             self.styles.title_style,
         )]));
 
-        self.name_area.set_block(Block::bordered().title(title_top));
+        self.name_area.set_block(ui::bordered().title(title_top));
         // it is assumed the buffer is empty so far
         if let Some(content) = content {
             self.name_area.insert_str(content);
@@ -139,7 +216,7 @@ impl super::Screen for DisplayScreen {
         // Generate vertical layout
         let vertical = Layout::vertical([
             Constraint::Length(1),
-            Constraint::Length(4),
+            Constraint::Length(5),
             Constraint::Fill(1),
             Constraint::Fill(1),
         ]);
@@ -147,11 +224,10 @@ impl super::Screen for DisplayScreen {
         let [title_area, stats_area, links1_area, links2_area] = vertical.areas(area);
 
         // Title
-        let title = Line::from(vec![Span::styled(
-            self.note.display_name.as_str(),
-            self.styles.title_style,
-        )])
-        .alignment(Alignment::Center);
+        let title = self
+            .note
+            .title_line(self.styles.title_style)
+            .alignment(Alignment::Center);
 
         let version = Line::from(vec![Span::styled(
             format!("rucola v{}", env!("CARGO_PKG_VERSION")),
@@ -159,28 +235,26 @@ impl super::Screen for DisplayScreen {
         )])
         .alignment(Alignment::Right);
 
-        let instructions_bot_right = Line::from(vec![
-            Span::styled("V", self.styles.hotkey_style),
-            Span::styled("iew──", self.styles.text_style),
-            Span::styled("E", self.styles.hotkey_style),
-            Span::styled("dit──", self.styles.text_style),
-            Span::styled("R", self.styles.hotkey_style),
-            Span::styled("ename──", self.styles.text_style),
-            Span::styled("M", self.styles.hotkey_style),
-            Span::styled("ove──", self.styles.text_style),
-            Span::styled("D", self.styles.hotkey_style),
-            Span::styled("elete", self.styles.text_style),
-        ])
+        let instructions_bot_right = Line::from(ui::tr_hotkeys(
+            "display-hints",
+            self.styles.hotkey_style,
+            self.styles.text_style,
+        ))
         .right_aligned();
 
-        let stats = self.note.to_stats_table(&self.styles).block(
-            Block::bordered()
-                .title(style::Styled::set_style(
-                    "Statistics",
-                    self.styles.title_style,
-                ))
-                .title_bottom(instructions_bot_right),
-        );
+        let backlinks = self.index.borrow().backlink_count(&self.note_id());
+
+        let stats = self
+            .note
+            .to_stats_table(&self.styles, &self.tag_styles, backlinks, &self.keywords)
+            .block(
+                ui::bordered()
+                    .title(style::Styled::set_style(
+                        ui::tr("display-title-statistics"),
+                        self.styles.title_style,
+                    ))
+                    .title_bottom(instructions_bot_right),
+            );
 
         // === All the links ===
 
@@ -193,10 +267,20 @@ impl super::Screen for DisplayScreen {
         Widget::render(version, title_area, buf);
         Widget::render(stats, stats_area, buf);
 
-        self.draw_link_table(0, "Backlinks", blinks1, buf);
-        self.draw_link_table(1, "Links", links1, buf);
-        self.draw_link_table(2, "Level 2 Backlinks", blinks2, buf);
-        self.draw_link_table(3, "Level 2 Links", links2, buf);
+        for (index, area) in [blinks1, links1, blinks2, links2].into_iter().enumerate() {
+            self.draw_link_table(index, &ui::tr(LINK_TABLE_TITLES[index]), area, buf);
+        }
+
+        // The preview covers the table next to the focused one, so the selection stays visible.
+        if self.link_preview {
+            let preview_area = match self.foc_table {
+                0 => links1,
+                1 => blinks1,
+                2 => links2,
+                _ => blinks2,
+            };
+            self.draw_link_preview(preview_area, buf);
+        }
 
         if self.mode == DisplayMode::Rename
             || self.mode == DisplayMode::Move
@@ -220,26 +304,29 @@ impl super::Screen for DisplayScreen {
             Widget::render(Clear, center_area, buf);
 
             if self.mode == DisplayMode::Delete {
-                let keys = Line::from(vec![
-                    Span::styled("󰌑", self.styles.hotkey_style),
-                    Span::styled(": Delete─", self.styles.text_style),
-                    Span::styled("Other", self.styles.hotkey_style),
-                    Span::styled(": Abort", self.styles.text_style),
-                ])
+                let keys = Line::from(ui::tr_hotkeys(
+                    "delete-hints",
+                    self.styles.hotkey_style,
+                    self.styles.text_style,
+                ))
                 .centered();
 
                 let del = Paragraph::new(Span::styled(
-                    "Are you sure you want to delete?\n",
+                    ui::tr("display-delete-confirm") + "\n",
                     self.styles.text_style,
                 ))
                 .alignment(Alignment::Center)
-                .block(Block::bordered().title_bottom(keys));
+                .block(ui::bordered().title_bottom(keys));
 
                 Widget::render(del, center_area, buf);
             } else {
                 Widget::render(&self.name_area, center_area, buf);
             }
         }
+
+        if self.mode == DisplayMode::TagSuggestions {
+            self.draw_tag_suggestions(area, buf);
+        }
     }
 
     fn update(&mut self, key: ratatui::crossterm::event::KeyEvent) -> error::Result<ui::Message> {
@@ -274,6 +361,10 @@ impl super::Screen for DisplayScreen {
                         );
                     }
                 }
+                // Toggle the preview of the selected link with Space
+                KeyCode::Char(' ') => {
+                    self.link_preview = !self.link_preview;
+                }
                 // Change list with Tab
                 KeyCode::Tab => {
                     self.foc_table = (self.foc_table.wrapping_add(1)) % 4;
@@ -302,26 +393,93 @@ impl super::Screen for DisplayScreen {
                 }
                 // Open selected item in viewer
                 KeyCode::Char('v' | 'V') => {
-                    self.builder.create_html(&self.note, true)?;
+                    let primary = key.code == KeyCode::Char('v');
+                    // PDF files take a while to build, so the viewer is only opened once they are done
+                    if self.manager.viewer_type(primary) == crate::config::ViewerType::Pdf {
+                        return Ok(ui::Message::ViewPdf(
+                            data::name_to_id(&self.note.name),
+                            primary,
+                        ));
+                    }
+                    self.builder
+                        .create_html(&self.note, Some(&self.index.borrow()), true)?;
+                    return Ok(ui::Message::OpenExternalCommand(Box::new(
+                        self.manager.create_view_command(&self.note, primary)?,
+                    )));
+                }
+                // O: Reveal the note in the file manager
+                KeyCode::Char('o' | 'O') => {
                     return Ok(ui::Message::OpenExternalCommand(Box::new(
-                        self.manager
-                            .create_view_command(&self.note, key.code == KeyCode::Char('v'))?,
+                        self.manager.create_reveal_command(&self.note.path)?,
                     )));
                 }
                 // R: Rename note
                 KeyCode::Char('r' | 'R') => {
                     self.mode = DisplayMode::Rename;
-                    self.set_name_area("Enter new name of note...", Some(self.note.name.clone()));
+                    self.set_name_area(&ui::tr("prompt-rename"), Some(self.note.name.clone()));
                 }
                 // M: Move note
                 KeyCode::Char('m' | 'M') => {
                     self.mode = DisplayMode::Move;
-                    self.set_name_area("Enter new location relative to vault...", None);
+                    self.set_name_area(&ui::tr("prompt-move"), None);
                 }
                 // D: Move note
                 KeyCode::Char('d' | 'D') => {
                     self.mode = DisplayMode::Delete;
                 }
+                // P: Read the note in the reading mode
+                KeyCode::Char('p' | 'P') => {
+                    return Ok(ui::Message::OpenPreview(data::name_to_id(&self.note.name)));
+                }
+                // S: Summarize the note with the configured command or endpoint
+                KeyCode::Char('s' | 'S') => {
+                    return Ok(ui::Message::Summarize(data::name_to_id(&self.note.name)));
+                }
+                // I: Insert a snippet into the note
+                KeyCode::Char('i' | 'I') => {
+                    return Ok(ui::Message::OpenSnippets(data::name_to_id(&self.note.name)));
+                }
+                // C: Check the note for problems, including its spelling
+                KeyCode::Char('c' | 'C') => {
+                    return Ok(ui::Message::OpenProblems(Some(data::name_to_id(
+                        &self.note.name,
+                    ))));
+                }
+                // G: Browse previous versions of the note
+                KeyCode::Char('g' | 'G') => {
+                    return Ok(ui::Message::OpenVersions(data::name_to_id(&self.note.name)));
+                }
+                // U: Show where the first tag of the note is used
+                KeyCode::Char('u' | 'U') => {
+                    return Ok(ui::Message::OpenTagUsage(self.note.tags.first().cloned()));
+                }
+                // @: Show where the person of the note, or the first person it mentions, is mentioned
+                KeyCode::Char('@') => {
+                    return Ok(ui::Message::OpenPeople(if self.note.person {
+                        Some(data::name_to_id(&self.note.name))
+                    } else {
+                        self.note.mentions.first().cloned()
+                    }));
+                }
+                // T: Propose keywords of the note as tags
+                KeyCode::Char('t' | 'T') => {
+                    self.tag_suggestions = self
+                        .index
+                        .borrow()
+                        .tag_suggestions(&self.note_id(), TAG_SUGGESTION_COUNT)
+                        .into_iter()
+                        // all suggestions are accepted by default
+                        .map(|keyword| (keyword, true))
+                        .collect();
+                    self.tag_suggestions_selected = 0;
+
+                    if self.tag_suggestions.is_empty() {
+                        return Err(error::RucolaError::Input(ui::tr(
+                            "display-error-no-keywords",
+                        )));
+                    }
+                    self.mode = DisplayMode::TagSuggestions;
+                }
 
                 _ => {}
             },
@@ -335,9 +493,8 @@ impl super::Screen for DisplayScreen {
                     self.manager.rename_note_file(
                         self.index.clone(),
                         &data::name_to_id(&self.note.name),
-                        super::extract_string_and_clear(&mut self.name_area).ok_or_else(|| {
-                            error::RucolaError::Input("New name is empty.".to_string())
-                        })?,
+                        super::extract_string_and_clear(&mut self.name_area)
+                            .ok_or_else(|| error::RucolaError::Input(ui::tr("error-name-empty")))?,
                     )?;
                 }
                 _ => {
@@ -355,7 +512,7 @@ impl super::Screen for DisplayScreen {
                         self.index.clone(),
                         &data::name_to_id(&self.note.name),
                         super::extract_string_and_clear(&mut self.name_area).ok_or_else(|| {
-                            error::RucolaError::Input("Move location is empty.".to_string())
+                            error::RucolaError::Input(ui::tr("display-error-move-empty"))
                         })?,
                     )?;
                 }
@@ -364,6 +521,49 @@ impl super::Screen for DisplayScreen {
                     self.name_area.input(key);
                 }
             },
+            DisplayMode::TagSuggestions => match key.code {
+                // Navigate the suggestions
+                KeyCode::Char('j' | 'J') | KeyCode::Down => {
+                    self.tag_suggestions_selected = self
+                        .tag_suggestions_selected
+                        .saturating_add(1)
+                        .min(self.tag_suggestions.len().saturating_sub(1));
+                }
+                KeyCode::Char('k' | 'K') | KeyCode::Up => {
+                    self.tag_suggestions_selected = self.tag_suggestions_selected.saturating_sub(1);
+                }
+                // Toggle the selected suggestion
+                KeyCode::Char(' ') => {
+                    if let Some((_, accepted)) =
+                        self.tag_suggestions.get_mut(self.tag_suggestions_selected)
+                    {
+                        *accepted = !*accepted;
+                    }
+                }
+                // Toggle all suggestions at once
+                KeyCode::Char('a' | 'A') => {
+                    let accept = !self.tag_suggestions.iter().all(|(_, accepted)| *accepted);
+                    for (_, accepted) in self.tag_suggestions.iter_mut() {
+                        *accepted = accept;
+                    }
+                }
+                // Add all accepted tags
+                KeyCode::Enter => {
+                    self.mode = DisplayMode::Display;
+                    let accepted = std::mem::take(&mut self.tag_suggestions)
+                        .into_iter()
+                        .filter(|(_, accepted)| *accepted)
+                        .map(|(keyword, _)| keyword.to_tag())
+                        .collect_vec();
+                    self.manager.add_tags(&self.note.path, &accepted)?;
+                }
+                // Dismiss all suggestions
+                KeyCode::Esc => {
+                    self.mode = DisplayMode::Display;
+                    self.tag_suggestions.clear();
+                }
+                _ => {}
+            },
             DisplayMode::Delete => match key.code {
                 KeyCode::Enter => {
                     // delete it from index & filesystem
@@ -379,9 +579,160 @@ impl super::Screen for DisplayScreen {
 
         Ok(ui::Message::None)
     }
+
+    fn announcement(&self) -> Option<String> {
+        let location = ui::tr_args(
+            "announce-note",
+            &[("name", self.note.display_name.clone().into())],
+        );
+        let Some((_id, name)) = self.links[self.foc_table].get(self.selected[self.foc_table])
+        else {
+            return Some(location);
+        };
+        let selection = ui::tr_args(
+            "display-selection",
+            &[
+                ("table", ui::tr(LINK_TABLE_TITLES[self.foc_table]).into()),
+                ("name", name.clone().into()),
+            ],
+        );
+        Some(ui::tr_args(
+            "announce-selection",
+            &[
+                ("location", location.into()),
+                ("selection", selection.into()),
+            ],
+        ))
+    }
 }
 
 impl DisplayScreen {
+    /// Draws a popup showing the title, tags and first paragraph of the note selected in the focused table.
+    fn draw_link_preview(&self, area: Rect, buf: &mut Buffer) {
+        let index = self.index.borrow();
+        let note = self
+            .links
+            .get(self.foc_table)
+            .and_then(|table| table.get(self.selected[self.foc_table]))
+            .and_then(|(id, _name)| index.get(id));
+
+        let text = match note {
+            Some(note) => {
+                let mut lines = vec![note.title_line(self.styles.subtitle_style)];
+                if !note.tags.is_empty() {
+                    lines.push(Line::from(
+                        Itertools::intersperse(
+                            note.tags
+                                .iter()
+                                .unique()
+                                .map(|tag| self.tag_styles.span(tag, self.styles.hotkey_style)),
+                            Span::raw(" "),
+                        )
+                        .collect_vec(),
+                    ));
+                }
+                lines.push(Line::default());
+                lines.push(Line::from(Span::styled(
+                    if note.snippet.is_empty() {
+                        ui::tr("display-preview-empty")
+                    } else {
+                        note.snippet.clone()
+                    },
+                    self.styles.text_style,
+                )));
+                lines
+            }
+            None => vec![Line::from(Span::styled(
+                ui::tr("display-preview-none"),
+                self.styles.text_style,
+            ))],
+        };
+
+        let preview = Paragraph::new(text).wrap(Wrap { trim: true }).block(
+            ui::bordered()
+                .title(style::Styled::set_style(
+                    ui::tr("title-preview"),
+                    self.styles.title_style,
+                ))
+                .title_bottom(
+                    Line::from(ui::tr_hotkeys(
+                        "display-preview-hints",
+                        self.styles.hotkey_style,
+                        self.styles.text_style,
+                    ))
+                    .right_aligned(),
+                ),
+        );
+
+        // Clear the area and then render the preview on top.
+        Widget::render(Clear, area, buf);
+        Widget::render(preview, area, buf);
+    }
+
+    /// Draws the popup listing the keywords proposed as tags.
+    fn draw_tag_suggestions(&self, area: Rect, buf: &mut Buffer) {
+        let rows = self
+            .tag_suggestions
+            .iter()
+            .map(|(keyword, accepted)| {
+                Row::new(vec![
+                    Span::styled(
+                        if *accepted { "[x]" } else { "[ ]" },
+                        self.styles.hotkey_style,
+                    ),
+                    Span::styled(keyword.to_tag(), self.styles.subtitle_style),
+                    Span::styled(format!("{:.3}", keyword.score), self.styles.text_style),
+                ])
+            })
+            .collect_vec();
+
+        let widths = [
+            Constraint::Length(3),
+            Constraint::Fill(1),
+            Constraint::Length(6),
+        ];
+
+        let mut state = TableState::new().with_selected(self.tag_suggestions_selected);
+
+        let table = Table::new(rows, widths)
+            .column_spacing(1)
+            .highlight_symbol(ui::selection_marker())
+            .row_highlight_style(self.styles.selected_style)
+            .block(
+                ui::bordered()
+                    .title(style::Styled::set_style(
+                        ui::tr("display-title-tags"),
+                        self.styles.title_style,
+                    ))
+                    .title_bottom(
+                        Line::from(ui::tr_hotkeys(
+                            "display-tags-hints",
+                            self.styles.hotkey_style,
+                            self.styles.text_style,
+                        ))
+                        .right_aligned(),
+                    ),
+            );
+
+        let popup_areas = Layout::vertical([
+            Constraint::Fill(1),
+            Constraint::Length((self.tag_suggestions.len() as u16 + 2).min(area.height / 2)),
+            Constraint::Fill(1),
+        ])
+        .split(area);
+
+        let center_area = Layout::horizontal([
+            Constraint::Fill(1),
+            Constraint::Percentage(40),
+            Constraint::Fill(1),
+        ])
+        .split(popup_areas[1])[1];
+
+        // Clear the area and then render the suggestions on top.
+        Widget::render(Clear, center_area, buf);
+        StatefulWidget::render(table, center_area, buf, &mut state);
+    }
+
     fn draw_link_table(&self, index: usize, title: &str, area: Rect, buf: &mut Buffer) {
         // Title
         let title = Line::from(vec![Span::styled(title, self.styles.title_style)]).left_aligned();
@@ -394,7 +745,7 @@ impl DisplayScreen {
 
         // Count
         let count = Line::from(vec![Span::styled(
-            format!("{} Note{}", count, if count == 1 { "" } else { "s" }),
+            ui::tr_args("notes-count", &[("count", count.into())]),
             self.styles.text_style,
         )])
         .right_aligned();
@@ -436,56 +787,44 @@ impl DisplayScreen {
                     .saturating_add(2),
             );
 
-        // Rows
+        // Rows, showing the icons and colors of the notes
+        let note_index = self.index.borrow();
         let rows = self
             .links
             .get(index)
             .map(|list| {
                 list.iter()
-                    .map(|(_id, name)| {
-                        Row::new(vec![Span::from(name).style(self.styles.text_style)])
+                    .map(|(id, name)| {
+                        Row::new(vec![note_index
+                            .get(id)
+                            .map(|note| note.label(name, self.styles.text_style))
+                            .unwrap_or_else(|| {
+                                Line::styled(name.clone(), self.styles.text_style)
+                            })])
                     })
                     .collect_vec()
             })
             .unwrap_or_default();
 
         // create default surrounding block
-        let block = Block::bordered().title_top(title).title_bottom(count);
+        let block = ui::bordered().title_top(title).title_bottom(count);
 
         // in some places, add instructions
         let block = match index {
             2 => block.title_bottom(
-                Line::from(vec![
-                    Span::styled("J", self.styles.hotkey_style),
-                    Span::styled("/", self.styles.text_style),
-                    Span::styled("", self.styles.hotkey_style),
-                    Span::styled(": Down──", self.styles.text_style),
-                    Span::styled("K", self.styles.hotkey_style),
-                    Span::styled("/", self.styles.text_style),
-                    Span::styled("", self.styles.hotkey_style),
-                    Span::styled(": Up──", self.styles.text_style),
-                    Span::styled("L", self.styles.hotkey_style),
-                    Span::styled("/", self.styles.text_style),
-                    Span::styled("", self.styles.hotkey_style),
-                    Span::styled("/", self.styles.text_style),
-                    Span::styled("󰌑", self.styles.hotkey_style),
-                    Span::styled(": Open──", self.styles.text_style),
-                    Span::styled("H", self.styles.hotkey_style),
-                    Span::styled("/", self.styles.text_style),
-                    Span::styled("", self.styles.hotkey_style),
-                    Span::styled(": Back──", self.styles.text_style),
-                    Span::styled("F", self.styles.hotkey_style),
-                    Span::styled(": Home", self.styles.text_style),
-                ])
+                Line::from(ui::tr_hotkeys(
+                    "display-links-hints",
+                    self.styles.hotkey_style,
+                    self.styles.text_style,
+                ))
                 .left_aligned(),
             ),
             3 => block.title_bottom(
-                Line::from(vec![
-                    Span::styled("Tab", self.styles.hotkey_style),
-                    Span::styled(": Next Table──", self.styles.text_style),
-                    Span::styled("Shift+Tab", self.styles.hotkey_style),
-                    Span::styled(": Previous Table", self.styles.text_style),
-                ])
+                Line::from(ui::tr_hotkeys(
+                    "display-links-hints-tables",
+                    self.styles.hotkey_style,
+                    self.styles.text_style,
+                ))
                 .right_aligned(),
             ),
             _ => block,
@@ -493,6 +832,7 @@ impl DisplayScreen {
 
         // Table
         let table = Table::new(rows, [Constraint::Min(20)])
+            .highlight_symbol(ui::selection_marker())
             .row_highlight_style(if index == self.foc_table {
                 self.styles.selected_style
             } else {
@@ -0,0 +1,221 @@
+use crate::{error, io, ui};
+
+use itertools::Itertools;
+use ratatui::crossterm::event::KeyCode;
+use ratatui::{prelude::*, widgets::*};
+
+/// How long a single tick may spend exporting before the progress is drawn again.
+const TICK_DURATION: std::time::Duration = std::time::Duration::from_millis(50);
+
+/// The export screen runs a batch export, shows its progress and afterwards summarizes all failures.
+pub struct ExportScreen {
+    // === CONFIG ===
+    /// The used styles.
+    styles: ui::UiStyles,
+
+    // === DATA ===
+    /// The export in progress.
+    export: io::BatchExport,
+    /// The message publishing the exported notes once the export is finished, if they are to be published.
+    publish: Option<ui::Message>,
+
+    // === UI ===
+    /// The index of the failure selected in the table.
+    selected: usize,
+    /// The number of notes with missing or outdated files, while waiting for the user to confirm a large build.
+    pending: Option<usize>,
+}
+
+impl ExportScreen {
+    /// Creates a new export screen for the given export.
+    /// Builds of HTML or PDF files for more than the given number of notes only start once the user confirms them.
+    pub fn new(export: io::BatchExport, styles: ui::UiStyles, confirm_above: usize) -> Self {
+        let pending = export
+            .outdated()
+            .filter(|_| export.progress().1 > confirm_above);
+
+        Self {
+            export,
+            publish: None,
+            styles,
+            selected: 0,
+            pending,
+        }
+    }
+
+    /// Publishes the exported notes once the export is finished, at the given prefix if set.
+    pub fn with_publish(mut self, prefix: Option<String>) -> Self {
+        self.publish = Some(ui::Message::PublishNotes(self.export.ids(), prefix));
+        self
+    }
+}
+
+impl super::Screen for ExportScreen {
+    fn draw(&self, area: layout::Rect, buf: &mut buffer::Buffer) {
+        let (done, total) = self.export.progress();
+        let failures = self.export.failures();
+
+        let [gauge_area, failures_area] =
+            Layout::vertical([Constraint::Length(3), Constraint::Fill(1)]).areas(area);
+
+        // === Progress ===
+
+        let gauge = Gauge::default()
+            .ratio(if total == 0 {
+                1.0
+            } else {
+                done as f64 / total as f64
+            })
+            .label(Span::styled(
+                ui::tr_args(
+                    "export-progress",
+                    &[("done", done.into()), ("total", total.into())],
+                ),
+                self.styles.text_style,
+            ))
+            .gauge_style(self.styles.selected_style)
+            .block(ui::bordered().title(Span::styled(
+                self.export.kind().to_string(),
+                self.styles.title_style,
+            )));
+
+        Widget::render(gauge, gauge_area, buf);
+
+        // === Summary ===
+
+        let summary = Line::from(vec![Span::styled(
+            if let Some(outdated) = self.pending {
+                ui::tr_args(
+                    "export-outdated",
+                    &[("outdated", outdated.into()), ("total", total.into())],
+                )
+            } else if self.export.is_finished() {
+                ui::tr_args(
+                    "export-done",
+                    &[
+                        ("succeeded", done.saturating_sub(failures.len()).into()),
+                        ("failed", failures.len().into()),
+                    ],
+                )
+            } else {
+                ui::tr_args("export-failed", &[("failed", failures.len().into())])
+            },
+            self.styles.title_style,
+        )]);
+
+        let instructions = Line::from(if self.pending.is_some() {
+            ui::tr_hotkeys(
+                "export-hints-pending",
+                self.styles.hotkey_style,
+                self.styles.text_style,
+            )
+        } else if self.export.is_finished() {
+            ui::tr_hotkeys(
+                "export-hints-finished",
+                self.styles.hotkey_style,
+                self.styles.text_style,
+            )
+        } else {
+            ui::tr_hotkeys(
+                "export-hints-running",
+                self.styles.hotkey_style,
+                self.styles.text_style,
+            )
+        })
+        .left_aligned();
+
+        let rows = failures
+            .iter()
+            .map(|(name, reason)| {
+                Row::new(vec![
+                    Span::styled(name.as_str(), self.styles.subtitle_style),
+                    Span::styled(reason.as_str(), self.styles.text_style),
+                ])
+            })
+            .collect_vec();
+
+        let table = Table::new(rows, [Constraint::Percentage(30), Constraint::Fill(1)])
+            .column_spacing(2)
+            .header(Row::new(vec![
+                Span::styled(ui::tr("column-note"), self.styles.subtitle_style),
+                Span::styled(ui::tr("column-reason"), self.styles.subtitle_style),
+            ]))
+            .highlight_symbol(ui::selection_marker())
+            .row_highlight_style(self.styles.selected_style)
+            .block(ui::bordered().title_top(summary).title_bottom(instructions));
+
+        let mut state = TableState::new()
+            .with_offset(
+                self.selected
+                    .saturating_sub(failures_area.height as usize / 3),
+            )
+            .with_selected(Some(self.selected));
+
+        StatefulWidget::render(table, failures_area, buf, &mut state);
+    }
+
+    fn update(&mut self, key: ratatui::crossterm::event::KeyEvent) -> error::Result<ui::Message> {
+        // Confirm a large build first
+        if self.pending.is_some() {
+            match key.code {
+                KeyCode::Char('a' | 'A') | KeyCode::Enter => {
+                    self.pending = None;
+                }
+                KeyCode::Char('o' | 'O') => {
+                    self.export.skip_up_to_date();
+                    self.pending = None;
+                }
+                KeyCode::Esc => return Ok(ui::Message::CloseOverlay),
+                KeyCode::Char('q' | 'Q') => return Ok(ui::Message::Quit),
+                _ => {}
+            }
+            return Ok(ui::Message::None);
+        }
+
+        match key.code {
+            // Stop a running export, close a finished one
+            KeyCode::Esc | KeyCode::Enter if !self.export.is_finished() => {
+                self.export.cancel();
+                // a cancelled export is not published
+                self.publish = None;
+            }
+            KeyCode::Esc | KeyCode::Enter | KeyCode::Left | KeyCode::Char('h' | 'H') => {
+                return Ok(ui::Message::CloseOverlay);
+            }
+            KeyCode::Char('q' | 'Q') => return Ok(ui::Message::Quit),
+            KeyCode::Char('j' | 'J') | KeyCode::Down => {
+                self.selected = self
+                    .selected
+                    .saturating_add(1)
+                    .min(self.export.failures().len().saturating_sub(1));
+            }
+            KeyCode::Char('k' | 'K') | KeyCode::Up => {
+                self.selected = self.selected.saturating_sub(1);
+            }
+            _ => {}
+        }
+
+        Ok(ui::Message::None)
+    }
+
+    fn tick(&mut self) -> error::Result<ui::Message> {
+        if self.pending.is_none() {
+            let start = std::time::Instant::now();
+            while start.elapsed() < TICK_DURATION && self.export.step() {}
+            if self.export.is_finished() {
+                return Ok(self.publish.take().unwrap_or(ui::Message::None));
+            }
+        }
+        Ok(ui::Message::None)
+    }
+
+    fn notes_changed(&mut self, ids: &[String]) {
+        for id in ids {
+            self.export.source_changed(id);
+        }
+    }
+
+    fn busy(&self) -> bool {
+        self.pending.is_none() && !self.export.is_finished()
+    }
+}
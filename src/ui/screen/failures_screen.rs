@@ -0,0 +1,145 @@
+use crate::{data, error, ui};
+
+use itertools::Itertools;
+use ratatui::crossterm::event::KeyCode;
+use ratatui::{prelude::*, widgets::*};
+
+/// The failures screen lists all parts of the vault that could not be loaded and allows the user to retry loading them.
+pub struct FailuresScreen {
+    // === CONFIG ===
+    /// The used styles.
+    styles: ui::UiStyles,
+    /// The path of the vault, to show the failed files relative to it.
+    vault_path: std::path::PathBuf,
+
+    // === DATA ===
+    /// The index whose failures are listed.
+    index: data::NoteIndexContainer,
+
+    // === UI ===
+    /// The index of the failure selected in the table.
+    selected: usize,
+}
+
+impl FailuresScreen {
+    /// Creates a new failures screen for the given index.
+    pub fn new(
+        index: data::NoteIndexContainer,
+        vault_path: std::path::PathBuf,
+        styles: ui::UiStyles,
+    ) -> Self {
+        Self {
+            styles,
+            vault_path,
+            index,
+            selected: 0,
+        }
+    }
+
+    /// Returns the affected part of the vault and the reason for every failure.
+    fn failures(&self) -> Vec<(String, String)> {
+        let index = self.index.borrow();
+
+        // canonicalize the vault, as the failed paths are canonical as well
+        let vault_path = self
+            .vault_path
+            .canonicalize()
+            .unwrap_or_else(|_| self.vault_path.clone());
+
+        index
+            .watcher_failure()
+            .map(|reason| (ui::tr("failures-watcher"), reason.to_owned()))
+            .into_iter()
+            .chain(index.failures().iter().map(|(path, reason)| {
+                (
+                    path.strip_prefix(&vault_path)
+                        .unwrap_or(path)
+                        .to_string_lossy()
+                        .to_string(),
+                    reason.to_owned(),
+                )
+            }))
+            .collect()
+    }
+}
+
+impl super::Screen for FailuresScreen {
+    fn draw(&self, area: layout::Rect, buf: &mut buffer::Buffer) {
+        let failures = self.failures();
+
+        let title = Line::from(vec![Span::styled(
+            ui::tr("failures-title"),
+            self.styles.title_style,
+        )]);
+
+        let count = Line::from(vec![Span::styled(
+            ui::tr_args("failures-count", &[("count", failures.len().into())]),
+            self.styles.text_style,
+        )])
+        .right_aligned();
+
+        let instructions = Line::from(ui::tr_hotkeys(
+            "failures-hints",
+            self.styles.hotkey_style,
+            self.styles.text_style,
+        ))
+        .left_aligned();
+
+        let rows = failures
+            .iter()
+            .map(|(name, reason)| {
+                Row::new(vec![
+                    Span::styled(name.as_str(), self.styles.subtitle_style),
+                    Span::styled(reason.as_str(), self.styles.text_style),
+                ])
+            })
+            .collect_vec();
+
+        let table = Table::new(rows, [Constraint::Percentage(30), Constraint::Fill(1)])
+            .column_spacing(2)
+            .header(Row::new(vec![
+                Span::styled(ui::tr("column-file"), self.styles.subtitle_style),
+                Span::styled(ui::tr("column-reason"), self.styles.subtitle_style),
+            ]))
+            .highlight_symbol(ui::selection_marker())
+            .row_highlight_style(self.styles.selected_style)
+            .block(
+                ui::bordered()
+                    .title_top(title)
+                    .title_bottom(instructions)
+                    .title_bottom(count),
+            );
+
+        let selected = self.selected.min(failures.len().saturating_sub(1));
+
+        let mut state = TableState::new()
+            .with_offset(selected.saturating_sub(area.height as usize / 3))
+            .with_selected(Some(selected));
+
+        StatefulWidget::render(table, area, buf, &mut state);
+    }
+
+    fn update(&mut self, key: ratatui::crossterm::event::KeyEvent) -> error::Result<ui::Message> {
+        match key.code {
+            KeyCode::Char('q' | 'Q') => return Ok(ui::Message::Quit),
+            // Return to the previous screen
+            KeyCode::Esc | KeyCode::Left | KeyCode::Char('h' | 'H') => {
+                return Ok(ui::Message::CloseOverlay);
+            }
+            KeyCode::Char('j' | 'J') | KeyCode::Down => {
+                self.selected = self
+                    .selected
+                    .saturating_add(1)
+                    .min(self.failures().len().saturating_sub(1));
+            }
+            KeyCode::Char('k' | 'K') | KeyCode::Up => {
+                self.selected = self.selected.saturating_sub(1);
+            }
+            // R: Try to load everything again
+            KeyCode::Char('r' | 'R') => return Ok(ui::Message::RetryFailures),
+            _ => {}
+        }
+
+        Ok(ui::Message::None)
+    }
+}
@@ -0,0 +1,279 @@
+use crate::{data, error, io, ui};
+
+use itertools::Itertools;
+use ratatui::crossterm::event::KeyCode;
+use ratatui::{prelude::*, widgets::*};
+
+/// The maximum number of commits listed on the history screen.
+const COMMIT_LIMIT: usize = 500;
+
+/// The state of the vault at a past commit, compared to its current state.
+struct Comparison {
+    /// Short description of the commit the vault was reconstructed at.
+    commit: String,
+    /// Statistics of the vault at that commit.
+    then: data::EnvironmentStats,
+    /// Statistics of the vault now.
+    now: data::EnvironmentStats,
+    /// Display names of the notes that have been created since.
+    added: Vec<String>,
+    /// Display names of the notes that have been removed since.
+    removed: Vec<String>,
+    /// Links that have been added since, as pairs of the display names of their source and target.
+    links_added: Vec<(String, String)>,
+    /// Links that have been removed since, as pairs of the display names of their source and target.
+    links_removed: Vec<(String, String)>,
+}
+
+/// The history screen lists the commits of the repository the vault is stored in and allows the user to compare the vault at any of them with its current state.
+pub struct HistoryScreen {
+    // === CONFIG ===
+    /// The git repository the vault is stored in.
+    git_manager: io::GitManager,
+    /// The used styles.
+    styles: ui::UiStyles,
+
+    // === DATA ===
+    /// The current index of notes.
+    index: data::NoteIndexContainer,
+    /// The latest commits of the repository.
+    commits: Vec<io::CommitInfo>,
+    /// The comparison with the last chosen commit, if any.
+    comparison: Option<Comparison>,
+
+    // === UI ===
+    /// The index of the commit selected in the table.
+    selected: usize,
+}
+
+impl HistoryScreen {
+    /// Creates a new history screen, listing the latest commits of the repository.
+    pub fn new(
+        index: data::NoteIndexContainer,
+        git_manager: io::GitManager,
+        styles: ui::UiStyles,
+    ) -> error::Result<Self> {
+        Ok(Self {
+            commits: git_manager.commits(COMMIT_LIMIT)?,
+            git_manager,
+            styles,
+            index,
+            comparison: None,
+            selected: 0,
+        })
+    }
+
+    /// Reconstructs the index at the selected commit and compares it to the current index.
+    fn compare_selected(&mut self) -> error::Result<()> {
+        let Some(commit) = self.commits.get(self.selected) else {
+            return Ok(());
+        };
+
+        let past = self
+            .index
+            .borrow()
+            .at_commit(&self.git_manager, commit.id)?;
+
+        let (added, removed, links_added, links_removed) = {
+            let now = self.index.borrow();
+            (
+                now.notes_not_in(&past),
+                past.notes_not_in(&now),
+                now.links_not_in(&past),
+                past.links_not_in(&now),
+            )
+        };
+
+        let past = std::rc::Rc::new(std::cell::RefCell::new(past));
+
+        self.comparison = Some(Comparison {
+            commit: format!(
+                "{} ({})",
+                commit.summary,
+                commit.time.format("%Y-%m-%d %H:%M")
+            ),
+            then: data::EnvironmentStats::new_with_filter(&past, data::Filter::default()),
+            now: data::EnvironmentStats::new_with_filter(&self.index, data::Filter::default()),
+            added,
+            removed,
+            links_added,
+            links_removed,
+        });
+
+        Ok(())
+    }
+}
+
+impl super::Screen for HistoryScreen {
+    fn draw(&self, area: layout::Rect, buf: &mut buffer::Buffer) {
+        let [commits_area, comparison_area] =
+            Layout::horizontal([Constraint::Percentage(40), Constraint::Fill(1)]).areas(area);
+
+        // === Commit list ===
+
+        let title = Line::from(vec![Span::styled(
+            ui::tr("history-title"),
+            self.styles.title_style,
+        )]);
+
+        let instructions = Line::from(ui::tr_hotkeys(
+            "history-hints",
+            self.styles.hotkey_style,
+            self.styles.text_style,
+        ))
+        .left_aligned();
+
+        let rows = self
+            .commits
+            .iter()
+            .map(|commit| {
+                Row::new(vec![
+                    Span::styled(
+                        commit.time.format("%Y-%m-%d %H:%M").to_string(),
+                        self.styles.subtitle_style,
+                    ),
+                    Span::styled(commit.summary.clone(), self.styles.text_style),
+                ])
+            })
+            .collect_vec();
+
+        let table = Table::new(rows, [Constraint::Length(16), Constraint::Fill(1)])
+            .column_spacing(2)
+            .header(Row::new(vec![
+                Span::styled(ui::tr("history-column-date"), self.styles.subtitle_style),
+                Span::styled(ui::tr("history-column-commit"), self.styles.subtitle_style),
+            ]))
+            .highlight_symbol(ui::selection_marker())
+            .row_highlight_style(self.styles.selected_style)
+            .block(ui::bordered().title_top(title).title_bottom(instructions));
+
+        let mut state = TableState::new()
+            .with_offset(self.selected.saturating_sub(area.height as usize / 3))
+            .with_selected(Some(self.selected));
+
+        StatefulWidget::render(table, commits_area, buf, &mut state);
+
+        // === Comparison ===
+
+        let Some(comparison) = &self.comparison else {
+            let hint = Paragraph::new(Span::styled(ui::tr("history-hint"), self.styles.text_style))
+                .wrap(Wrap { trim: true })
+                .block(ui::bordered().title(Span::styled(
+                    ui::tr("history-title-comparison"),
+                    self.styles.title_style,
+                )));
+            Widget::render(hint, comparison_area, buf);
+            return;
+        };
+
+        let [then_area, now_area, changes_area] = Layout::vertical([
+            Constraint::Length(5),
+            Constraint::Length(5),
+            Constraint::Fill(1),
+        ])
+        .areas(comparison_area);
+
+        let then = comparison
+            .then
+            .to_global_stats_table(&self.styles)
+            .block(ui::bordered().title(Span::styled(
+                ui::tr_args(
+                    "history-title-then",
+                    &[("commit", comparison.commit.clone().into())],
+                ),
+                self.styles.title_style,
+            )));
+
+        let now = comparison
+            .now
+            .to_global_stats_table(&self.styles)
+            .block(ui::bordered().title(Span::styled(
+                ui::tr("history-title-now"),
+                self.styles.title_style,
+            )));
+
+        let [notes_area, links_area] =
+            Layout::vertical([Constraint::Fill(1), Constraint::Fill(1)]).areas(changes_area);
+
+        let [added_area, removed_area] =
+            Layout::horizontal([Constraint::Fill(1), Constraint::Fill(1)]).areas(notes_area);
+
+        let [links_added_area, links_removed_area] =
+            Layout::horizontal([Constraint::Fill(1), Constraint::Fill(1)]).areas(links_area);
+
+        let link_names = |links: &[(String, String)]| {
+            links
+                .iter()
+                .map(|(source, target)| format!("{} → {}", source, target))
+                .collect_vec()
+        };
+
+        let note_list = |title: &str, names: &[String]| {
+            List::new(
+                names
+                    .iter()
+                    .map(|name| Span::styled(name.clone(), self.styles.text_style)),
+            )
+            .block(ui::bordered().title(Span::styled(
+                format!("{} ({})", title, names.len()),
+                self.styles.subtitle_style,
+            )))
+        };
+
+        Widget::render(then, then_area, buf);
+        Widget::render(now, now_area, buf);
+        Widget::render(
+            note_list(&ui::tr("history-title-added"), &comparison.added),
+            added_area,
+            buf,
+        );
+        Widget::render(
+            note_list(&ui::tr("history-title-removed"), &comparison.removed),
+            removed_area,
+            buf,
+        );
+        Widget::render(
+            note_list(
+                &ui::tr("history-title-links-added"),
+                &link_names(&comparison.links_added),
+            ),
+            links_added_area,
+            buf,
+        );
+        Widget::render(
+            note_list(
+                &ui::tr("history-title-links-removed"),
+                &link_names(&comparison.links_removed),
+            ),
+            links_removed_area,
+            buf,
+        );
+    }
+
+    fn update(&mut self, key: ratatui::crossterm::event::KeyEvent) -> error::Result<ui::Message> {
+        match key.code {
+            // Quit with Q
+            KeyCode::Char('q' | 'Q') => return Ok(ui::Message::Quit),
+            // Return to the previous screen
+            KeyCode::Esc | KeyCode::Left | KeyCode::Char('h' | 'H') => {
+                return Ok(ui::Message::CloseOverlay);
+            }
+            KeyCode::Char('j' | 'J') | KeyCode::Down => {
+                self.selected = self
+                    .selected
+                    .saturating_add(1)
+                    .min(self.commits.len().saturating_sub(1));
+            }
+            KeyCode::Char('k' | 'K') | KeyCode::Up => {
+                self.selected = self.selected.saturating_sub(1);
+            }
+            // Enter: Compare the selected commit with the current state
+            KeyCode::Enter => {
+                self.compare_selected()?;
+            }
+            _ => {}
+        }
+
+        Ok(ui::Message::None)
+    }
+}
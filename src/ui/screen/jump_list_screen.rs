@@ -0,0 +1,108 @@
+use crate::{error, ui};
+
+use itertools::Itertools;
+use ratatui::crossterm::event::KeyCode;
+use ratatui::{prelude::*, widgets::*};
+
+/// The jump list screen shows the navigation history of this session and allows the user to return to any note in it.
+pub struct JumpListScreen {
+    // === CONFIG ===
+    /// The used styles.
+    styles: ui::UiStyles,
+
+    // === DATA ===
+    /// The names of all notes in the navigation history, oldest first.
+    names: Vec<String>,
+    /// The index of the entry of the navigation history that is currently displayed.
+    current: usize,
+
+    // === UI ===
+    /// The index of the entry selected in the table, counting from the most recent.
+    selected: usize,
+}
+
+impl JumpListScreen {
+    /// Creates a new jump list screen from the names of the visited notes, with the currently displayed entry selected.
+    pub fn new(names: Vec<String>, current: usize, styles: ui::UiStyles) -> Self {
+        Self {
+            selected: names.len().saturating_sub(current + 1),
+            names,
+            current,
+            styles,
+        }
+    }
+}
+
+impl super::Screen for JumpListScreen {
+    fn draw(&self, area: layout::Rect, buf: &mut buffer::Buffer) {
+        let title = Line::from(vec![Span::styled(
+            ui::tr("jump-list-title"),
+            self.styles.title_style,
+        )]);
+
+        let instructions = Line::from(ui::tr_hotkeys(
+            "jump-list-hints",
+            self.styles.hotkey_style,
+            self.styles.text_style,
+        ))
+        .left_aligned();
+
+        // Show the most recent visit first.
+        let rows = self
+            .names
+            .iter()
+            .enumerate()
+            .rev()
+            .map(|(index, name)| {
+                Row::new(vec![
+                    Span::styled(
+                        if index == self.current { ">" } else { "" },
+                        self.styles.hotkey_style,
+                    ),
+                    Span::styled(name.as_str(), self.styles.text_style),
+                ])
+            })
+            .collect_vec();
+
+        let table = Table::new(rows, [Constraint::Length(1), Constraint::Fill(1)])
+            .column_spacing(1)
+            .highlight_symbol(ui::selection_marker())
+            .row_highlight_style(self.styles.selected_style)
+            .block(ui::bordered().title_top(title).title_bottom(instructions));
+
+        let mut state = TableState::new()
+            .with_offset(self.selected.saturating_sub(area.height as usize / 3))
+            .with_selected(Some(self.selected));
+
+        StatefulWidget::render(table, area, buf, &mut state);
+    }
+
+    fn update(&mut self, key: ratatui::crossterm::event::KeyEvent) -> error::Result<ui::Message> {
+        match key.code {
+            // Quit with Q
+            KeyCode::Char('q' | 'Q') => return Ok(ui::Message::Quit),
+            // Return to the previous screen
+            KeyCode::Esc | KeyCode::Left | KeyCode::Char('h' | 'H') => {
+                return Ok(ui::Message::CloseOverlay);
+            }
+            KeyCode::Char('j' | 'J') | KeyCode::Down => {
+                self.selected = self
+                    .selected
+                    .saturating_add(1)
+                    .min(self.names.len().saturating_sub(1));
+            }
+            KeyCode::Char('k' | 'K') | KeyCode::Up => {
+                self.selected = self.selected.saturating_sub(1);
+            }
+            // Enter: Return to the selected note
+            KeyCode::Enter | KeyCode::Right | KeyCode::Char('l' | 'L')
+                if !self.names.is_empty() =>
+            {
+                return Ok(ui::Message::JumpTo(self.names.len() - 1 - self.selected));
+            }
+            _ => {}
+        }
+
+        Ok(ui::Message::None)
+    }
+}
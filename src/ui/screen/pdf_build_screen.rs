@@ -0,0 +1,210 @@
+use crate::{data, error, io, ui};
+
+use ratatui::crossterm::event::KeyCode;
+use ratatui::{prelude::*, widgets::*};
+
+/// How long a single tick may wait for the build before the spinner is drawn again.
+const TICK_DURATION: std::time::Duration = std::time::Duration::from_millis(100);
+/// How long to wait between checks of the build.
+const POLL_INTERVAL: std::time::Duration = std::time::Duration::from_millis(10);
+/// The frames of the spinner shown while building.
+const SPINNER: [&str; 10] = ["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
+
+/// The state of the build.
+enum BuildState {
+    /// The PDF converter is running.
+    Building,
+    /// The PDF converter failed with the given message and error output.
+    Failed(String),
+    /// The PDF file was built and the viewer opened.
+    Done,
+}
+
+/// The PDF build screen builds the PDF file of a note, waiting for the converter before the viewer is opened.
+/// If the build fails, the full error output of the converter is shown instead.
+pub struct PdfBuildScreen {
+    // === CONFIG ===
+    /// The used styles.
+    styles: ui::UiStyles,
+    /// Wether the primary or the secondary viewer is opened.
+    primary: bool,
+
+    // === DATA ===
+    /// The note whose PDF file is built.
+    note: data::Note,
+    /// The index queries within the note are answered from.
+    index: data::NoteIndexContainer,
+    /// The builder creating the command converting the note.
+    pdf_builder: io::PdfBuilder,
+    /// The file manager creating the command of the viewer.
+    manager: io::FileManager,
+    /// The pool running the converter, killing it when the screen is closed.
+    pool: io::ProcessPool,
+    /// The state of the build.
+    state: BuildState,
+    /// When the build started.
+    started: std::time::Instant,
+
+    // === UI ===
+    /// The line the error output is scrolled to.
+    scroll: u16,
+}
+
+impl PdfBuildScreen {
+    /// Creates a new PDF build screen and starts building the PDF file of the given note.
+    pub fn new(
+        note: data::Note,
+        primary: bool,
+        index: data::NoteIndexContainer,
+        pdf_builder: io::PdfBuilder,
+        manager: io::FileManager,
+        styles: ui::UiStyles,
+    ) -> error::Result<Self> {
+        let mut screen = Self {
+            styles,
+            primary,
+            pool: pdf_builder.pool(),
+            note,
+            index,
+            pdf_builder,
+            manager,
+            state: BuildState::Building,
+            started: std::time::Instant::now(),
+            scroll: 0,
+        };
+        screen.build()?;
+        Ok(screen)
+    }
+
+    /// (Re-)starts the build of the PDF file.
+    fn build(&mut self) -> error::Result<()> {
+        let (command, _pdf) =
+            self.pdf_builder
+                .command(&self.note, Some(&self.index.borrow()), false)?;
+        self.pool
+            .submit(&data::name_to_id(&self.note.name), command);
+        self.state = BuildState::Building;
+        self.started = std::time::Instant::now();
+        self.scroll = 0;
+        Ok(())
+    }
+}
+
+impl super::Screen for PdfBuildScreen {
+    fn draw(&self, area: layout::Rect, buf: &mut buffer::Buffer) {
+        let title = Line::from(vec![Span::styled(
+            ui::tr_args(
+                "pdf-title",
+                &[("name", self.note.display_name.clone().into())],
+            ),
+            self.styles.title_style,
+        )]);
+
+        let instructions = Line::from(match self.state {
+            BuildState::Failed(_) => ui::tr_hotkeys(
+                "pdf-hints-failed",
+                self.styles.hotkey_style,
+                self.styles.text_style,
+            ),
+            BuildState::Building => ui::tr_hotkeys(
+                "pdf-hints-building",
+                self.styles.hotkey_style,
+                self.styles.text_style,
+            ),
+            BuildState::Done => ui::tr_hotkeys(
+                "pdf-hints-done",
+                self.styles.hotkey_style,
+                self.styles.text_style,
+            ),
+        })
+        .left_aligned();
+
+        let block = ui::bordered().title_top(title).title_bottom(instructions);
+
+        let paragraph = match &self.state {
+            BuildState::Building => {
+                let elapsed = self.started.elapsed();
+                let frame = SPINNER[(elapsed.as_millis() / 100) as usize % SPINNER.len()];
+                Paragraph::new(Line::from(vec![
+                    Span::styled(frame, self.styles.hotkey_style),
+                    Span::styled(
+                        format!(
+                            " {}",
+                            ui::tr_args("pdf-building", &[("seconds", elapsed.as_secs().into())])
+                        ),
+                        self.styles.text_style,
+                    ),
+                ]))
+            }
+            BuildState::Failed(output) => Paragraph::new(
+                output
+                    .lines()
+                    .map(|line| Line::from(Span::styled(line, self.styles.text_style)))
+                    .collect::<Vec<_>>(),
+            )
+            .wrap(Wrap { trim: false })
+            .scroll((self.scroll, 0)),
+            BuildState::Done => Paragraph::new(Line::from(Span::styled(
+                ui::tr("pdf-done"),
+                self.styles.text_style,
+            ))),
+        };
+
+        Widget::render(paragraph.block(block), area, buf);
+    }
+
+    fn update(&mut self, key: ratatui::crossterm::event::KeyEvent) -> error::Result<ui::Message> {
+        match key.code {
+            // Closing the screen stops a running build
+            KeyCode::Esc | KeyCode::Enter | KeyCode::Left | KeyCode::Char('h' | 'H') => {
+                return Ok(ui::Message::CloseOverlay);
+            }
+            KeyCode::Char('q' | 'Q') => return Ok(ui::Message::Quit),
+            KeyCode::Char('r' | 'R') if !matches!(self.state, BuildState::Building) => {
+                self.build()?;
+            }
+            KeyCode::Char('j' | 'J') | KeyCode::Down => {
+                self.scroll = self.scroll.saturating_add(1);
+            }
+            KeyCode::Char('k' | 'K') | KeyCode::Up => {
+                self.scroll = self.scroll.saturating_sub(1);
+            }
+            _ => {}
+        }
+
+        Ok(ui::Message::None)
+    }
+
+    fn tick(&mut self) -> error::Result<ui::Message> {
+        let start = std::time::Instant::now();
+        while matches!(self.state, BuildState::Building) && start.elapsed() < TICK_DURATION {
+            let Some(job) = self.pool.poll().pop() else {
+                std::thread::sleep(POLL_INTERVAL);
+                continue;
+            };
+
+            match job.result {
+                Ok(()) => {
+                    self.state = BuildState::Done;
+                    return Ok(ui::Message::OpenExternalCommand(Box::new(
+                        self.manager.create_view_command(&self.note, self.primary)?,
+                    )));
+                }
+                Err(e) => {
+                    let stderr = job.stderr.trim_end();
+                    self.state = BuildState::Failed(if stderr.is_empty() {
+                        e.to_string()
+                    } else {
+                        format!("{}\n\n{}", e, stderr)
+                    });
+                }
+            }
+        }
+
+        Ok(ui::Message::None)
+    }
+
+    fn busy(&self) -> bool {
+        matches!(self.state, BuildState::Building)
+    }
+}
@@ -0,0 +1,320 @@
+use crate::{data, error, io, ui};
+
+use itertools::Itertools;
+use ratatui::crossterm::event::KeyCode;
+use ratatui::{prelude::*, widgets::*};
+
+/// The people screen lists everyone mentioned in the vault or described by a person note, and shows where the selected person is mentioned.
+pub struct PeopleScreen {
+    // === CONFIG ===
+    /// The used styles.
+    styles: ui::UiStyles,
+    /// The file manager used to open notes at the line of a mention.
+    manager: io::FileManager,
+
+    // === DATA ===
+    /// The index whose notes are searched.
+    index: data::NoteIndexContainer,
+    /// All people of the vault.
+    people: Vec<data::Person>,
+    /// The mentions of the selected person, with the name of their note.
+    occurrences: Vec<(String, data::MentionOccurrence)>,
+
+    // === UI ===
+    /// The index of the selected person.
+    selected_person: usize,
+    /// The index of the selected mention.
+    selected_occurrence: usize,
+    /// Wether the mentions rather than the people are focused.
+    occurrences_focused: bool,
+}
+
+impl PeopleScreen {
+    /// Creates a new people screen with the person with the given id selected, or the first person of the vault.
+    pub fn new(
+        index: data::NoteIndexContainer,
+        manager: io::FileManager,
+        person: Option<String>,
+        styles: ui::UiStyles,
+    ) -> Self {
+        let people = data::people(&index.borrow());
+        let selected_person = person
+            .and_then(|id| people.iter().position(|other| other.id == id))
+            .unwrap_or_default();
+
+        let mut res = Self {
+            styles,
+            manager,
+            index,
+            people,
+            occurrences: Vec::new(),
+            selected_person,
+            selected_occurrence: 0,
+            occurrences_focused: false,
+        };
+        res.find_occurrences();
+        res
+    }
+
+    /// Finds the mentions of the selected person again.
+    fn find_occurrences(&mut self) {
+        let index = self.index.borrow();
+        self.occurrences = self
+            .people
+            .get(self.selected_person)
+            .map(|person| data::mention_occurrences(&index, person))
+            .unwrap_or_default()
+            .into_iter()
+            .map(|occurrence| {
+                let name = index
+                    .get(&occurrence.id)
+                    .map(|note| note.display_name.clone())
+                    .unwrap_or_else(|| occurrence.id.clone());
+                (name, occurrence)
+            })
+            .collect();
+        self.selected_occurrence = self
+            .selected_occurrence
+            .min(self.occurrences.len().saturating_sub(1));
+    }
+
+    /// Moves the selection within the focused list down or up by one entry.
+    fn move_selection(&mut self, down: bool) {
+        if self.occurrences_focused {
+            self.selected_occurrence = if down {
+                self.selected_occurrence
+                    .saturating_add(1)
+                    .min(self.occurrences.len().saturating_sub(1))
+            } else {
+                self.selected_occurrence.saturating_sub(1)
+            };
+        } else {
+            let selected = if down {
+                self.selected_person
+                    .saturating_add(1)
+                    .min(self.people.len().saturating_sub(1))
+            } else {
+                self.selected_person.saturating_sub(1)
+            };
+            if selected != self.selected_person {
+                self.selected_person = selected;
+                self.selected_occurrence = 0;
+                self.find_occurrences();
+            }
+        }
+    }
+}
+
+impl super::Screen for PeopleScreen {
+    fn draw(&self, area: layout::Rect, buf: &mut buffer::Buffer) {
+        let [people_area, occurrences_area] =
+            Layout::horizontal([Constraint::Percentage(25), Constraint::Fill(1)]).areas(area);
+
+        // === People ===
+        let rows = self
+            .people
+            .iter()
+            .map(|person| {
+                Row::new(vec![
+                    // people without a note are only known by their mentions
+                    if person.has_note {
+                        Line::styled(person.name.as_str(), self.styles.subtitle_style)
+                    } else {
+                        Line::styled(format!("@{}", person.name), self.styles.text_style)
+                    },
+                    Line::styled(
+                        format!("{:5}", person.mentioned_in.len()),
+                        self.styles.text_style,
+                    ),
+                ])
+            })
+            .collect_vec();
+
+        let people_table = Table::new(rows, [Constraint::Fill(1), Constraint::Length(5)])
+            .column_spacing(1)
+            .highlight_symbol(ui::selection_marker())
+            .row_highlight_style(if self.occurrences_focused {
+                self.styles.text_style
+            } else {
+                self.styles.selected_style
+            })
+            .block(
+                ui::bordered()
+                    .title(Span::styled(
+                        ui::tr("people-title"),
+                        self.styles.title_style,
+                    ))
+                    .title_bottom(
+                        Line::from(vec![Span::styled(
+                            ui::tr_args("people-count", &[("count", self.people.len().into())]),
+                            self.styles.text_style,
+                        )])
+                        .right_aligned(),
+                    ),
+            );
+
+        let mut people_state = TableState::new()
+            .with_offset(
+                self.selected_person
+                    .saturating_sub(people_area.height as usize / 3),
+            )
+            .with_selected(Some(self.selected_person));
+
+        StatefulWidget::render(people_table, people_area, buf, &mut people_state);
+
+        // === Mentions ===
+        let title = Line::from(match self.people.get(self.selected_person) {
+            Some(person) => vec![
+                Span::styled(
+                    ui::tr("people-title-mentions") + " ",
+                    self.styles.title_style,
+                ),
+                Span::styled(person.name.as_str(), self.styles.subtitle_style),
+            ],
+            None => vec![Span::styled(
+                ui::tr("people-title-none"),
+                self.styles.title_style,
+            )],
+        });
+
+        let count = Line::from(vec![Span::styled(
+            ui::tr_args(
+                "tag-usage-lines",
+                &[("count", self.occurrences.len().into())],
+            ),
+            self.styles.text_style,
+        )])
+        .right_aligned();
+
+        let instructions = Line::from(ui::tr_hotkeys(
+            "people-hints",
+            self.styles.hotkey_style,
+            self.styles.text_style,
+        ))
+        .left_aligned();
+
+        let rows = self
+            .occurrences
+            .iter()
+            .map(|(name, occurrence)| {
+                Row::new(vec![
+                    Span::styled(name.as_str(), self.styles.subtitle_style),
+                    Span::styled(occurrence.line.to_string(), self.styles.text_style),
+                    Span::styled(occurrence.context.as_str(), self.styles.text_style),
+                ])
+            })
+            .collect_vec();
+
+        let occurrences_table = Table::new(
+            rows,
+            [
+                Constraint::Percentage(25),
+                Constraint::Length(5),
+                Constraint::Fill(1),
+            ],
+        )
+        .column_spacing(2)
+        .header(Row::new(vec![
+            Span::styled(ui::tr("column-note"), self.styles.subtitle_style),
+            Span::styled(ui::tr("tag-usage-column-line"), self.styles.subtitle_style),
+            Span::styled(
+                ui::tr("tag-usage-column-context"),
+                self.styles.subtitle_style,
+            ),
+        ]))
+        .highlight_symbol(ui::selection_marker())
+        .row_highlight_style(if self.occurrences_focused {
+            self.styles.selected_style
+        } else {
+            self.styles.text_style
+        })
+        .block(
+            ui::bordered()
+                .title_top(title)
+                .title_bottom(instructions)
+                .title_bottom(count),
+        );
+
+        let mut occurrences_state = TableState::new()
+            .with_offset(
+                self.selected_occurrence
+                    .saturating_sub(occurrences_area.height as usize / 3),
+            )
+            .with_selected(Some(self.selected_occurrence));
+
+        StatefulWidget::render(
+            occurrences_table,
+            occurrences_area,
+            buf,
+            &mut occurrences_state,
+        );
+    }
+
+    fn update(&mut self, key: ratatui::crossterm::event::KeyEvent) -> error::Result<ui::Message> {
+        match key.code {
+            KeyCode::Char('q' | 'Q') => return Ok(ui::Message::Quit),
+            KeyCode::Esc => return Ok(ui::Message::CloseOverlay),
+            KeyCode::Char('j' | 'J') | KeyCode::Down => self.move_selection(true),
+            KeyCode::Char('k' | 'K') | KeyCode::Up => self.move_selection(false),
+            // Switch between the people and their mentions
+            KeyCode::Tab | KeyCode::BackTab => {
+                self.occurrences_focused = !self.occurrences_focused;
+            }
+            // Go back to the people, or close the screen if they are focused already
+            KeyCode::Left | KeyCode::Char('h' | 'H') => {
+                if !self.occurrences_focused {
+                    return Ok(ui::Message::CloseOverlay);
+                }
+                self.occurrences_focused = false;
+            }
+            KeyCode::Right | KeyCode::Char('l' | 'L') => {
+                self.occurrences_focused = true;
+            }
+            // Enter: Edit the note of the selected mention at its line
+            KeyCode::Enter => {
+                if !self.occurrences_focused {
+                    self.occurrences_focused = true;
+                } else if let Some((_, occurrence)) = self.occurrences.get(self.selected_occurrence)
+                {
+                    if let Some(note) = self.index.borrow().get(&occurrence.id) {
+                        return Ok(ui::Message::OpenExternalCommand(Box::new(
+                            self.manager
+                                .create_edit_command_at(&note.path, Some(occurrence.line))?,
+                        )));
+                    }
+                }
+            }
+            // O: Open the note of the selected mention, or the note of the selected person
+            KeyCode::Char('o' | 'O') => {
+                if self.occurrences_focused {
+                    if let Some((_, occurrence)) = self.occurrences.get(self.selected_occurrence) {
+                        return Ok(ui::Message::DisplayStackPush(occurrence.id.clone()));
+                    }
+                } else if let Some(person) = self
+                    .people
+                    .get(self.selected_person)
+                    .filter(|person| person.has_note)
+                {
+                    return Ok(ui::Message::DisplayStackPush(person.id.clone()));
+                }
+            }
+            _ => {}
+        }
+
+        Ok(ui::Message::None)
+    }
+
+    fn notes_changed(&mut self, _ids: &[String]) {
+        // keep the selected person selected, even if people were added or removed before them
+        let selected = self
+            .people
+            .get(self.selected_person)
+            .map(|person| person.id.clone());
+        self.people = data::people(&self.index.borrow());
+        self.selected_person = selected
+            .and_then(|id| self.people.iter().position(|other| other.id == id))
+            .unwrap_or(self.selected_person)
+            .min(self.people.len().saturating_sub(1));
+        self.find_occurrences();
+    }
+}
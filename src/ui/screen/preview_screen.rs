@@ -0,0 +1,1129 @@
+use crate::{data, error, ui};
+
+use ratatui::crossterm::event::KeyCode;
+use ratatui::{prelude::*, widgets::*};
+use std::cell::{Cell, RefCell};
+use std::collections::{HashMap, HashSet};
+use std::ops::Range;
+use std::rc::Rc;
+
+/// The headings folded in the reading mode, by note id.
+/// Shared between all preview screens, so a note keeps its folds when it is read again within the same session.
+pub type FoldState = Rc<RefCell<HashMap<String, HashSet<String>>>>;
+
+/// A row of the wrapped text: the index of its line, its text, its style, the heading level of its line and the byte range of the line it displays.
+type TextRow = (usize, String, Style, Option<usize>, Option<Range<usize>>);
+
+/// Describes the current mode of the UI.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+enum PreviewMode {
+    /// Reading the note.
+    #[default]
+    Read,
+    /// Typing into the search box.
+    Search,
+}
+
+/// The data table of a note, shown sortable by its columns.
+struct SortableTable {
+    /// The structured data of the note.
+    table: data::DataTable,
+    /// The index of the first line of the table.
+    start: usize,
+    /// The lines of the table as written in the note, shown when it is not sorted.
+    original: Vec<String>,
+    /// The column the table is sorted by and wether ascending, if it is sorted.
+    sorting: Option<(usize, bool)>,
+}
+
+/// The preview screen is a distraction-free reading mode for a single note.
+/// The text of the note is displayed in a centered column without any surrounding chrome.
+pub struct PreviewScreen {
+    // === CONFIG ===
+    /// The used styles.
+    styles: ui::UiStyles,
+    /// Maximum width of the text column.
+    reading_width: u16,
+
+    // === DATA ===
+    /// The id of the displayed note.
+    note_id: String,
+    /// The lines of the note's text, without its frontmatter, followed by the blocks they reference.
+    lines: Vec<String>,
+    /// The indices of the lines showing referenced blocks rather than the note's own text.
+    embedded: HashSet<usize>,
+    /// The markdown tables among the lines, by the index of their first line, with the index after their last line.
+    tables: HashMap<usize, usize>,
+    /// The folded headings of all notes.
+    folds: FoldState,
+    /// The data table of the note, if it is one or has a CSV file next to it.
+    data: Option<SortableTable>,
+
+    // === UI ===
+    /// The first displayed row of the wrapped text.
+    scroll: usize,
+    /// Width and height of the text column when it was last drawn, used to determine how far the text can be scrolled.
+    column: Cell<(u16, u16)>,
+    /// The text area to enter search terms.
+    search_area: tui_textarea::TextArea<'static>,
+    /// The current search term in lowercase, if any.
+    query: Option<String>,
+    /// The index of the match currently navigated to.
+    current_match: usize,
+    /// Current input mode
+    mode: PreviewMode,
+}
+
+impl PreviewScreen {
+    /// Creates a new preview screen for the note with the given id.
+    pub fn new(
+        note_id: &str,
+        index: data::NoteIndexContainer,
+        folds: FoldState,
+        styles: ui::UiStyles,
+        reading_width: u16,
+    ) -> error::Result<Self> {
+        let (path, data) = index
+            .borrow()
+            .get(note_id)
+            .map(|note| (note.path.clone(), note.data.clone()))
+            .ok_or_else(|| error::RucolaError::NoteNotFound(note_id.to_owned()))?;
+
+        let content = std::fs::read_to_string(path)?;
+        let (mut lines, embedded) = embed_blocks(&content, &index.borrow());
+
+        // Data from a CSV file is shown as a table after the text of the note.
+        if let Some(table) = data.as_ref().filter(|table| table.file.is_some()) {
+            lines.push(String::new());
+            lines.extend(table.to_markdown(None));
+        }
+
+        let tables = find_tables(&lines, &embedded);
+
+        // The data table is the last table of the note, and can only be sorted if all its rows were recognized.
+        let data = data.and_then(|table| {
+            let (start, end) = tables.iter().max_by_key(|(start, _end)| **start)?;
+            (*end == start + 2 + table.rows.len()).then(|| SortableTable {
+                original: lines[*start..*end].to_vec(),
+                start: *start,
+                table,
+                sorting: None,
+            })
+        });
+
+        let mut search_area = tui_textarea::TextArea::default();
+        search_area.set_style(styles.input_style);
+        search_area.set_cursor_line_style(styles.input_style);
+        search_area.set_block(ui::bordered().title(Span::styled(
+            ui::tr("preview-prompt-search"),
+            styles.title_style,
+        )));
+
+        Ok(Self {
+            styles,
+            reading_width,
+            note_id: note_id.to_owned(),
+            lines,
+            embedded,
+            tables,
+            folds,
+            data,
+            scroll: 0,
+            column: Cell::new((reading_width, 0)),
+            search_area,
+            query: None,
+            current_match: 0,
+            mode: PreviewMode::Read,
+        })
+    }
+
+    /// Determines the style of each line and, for headings, their level.
+    fn line_kinds(&self) -> Vec<(Style, Option<usize>)> {
+        let mut in_code = false;
+
+        self.lines
+            .iter()
+            .enumerate()
+            .map(|(index, line)| {
+                if self.embedded.contains(&index) {
+                    (self.styles.subtitle_style, None)
+                } else if line.trim_start().starts_with("```") {
+                    in_code = !in_code;
+                    (self.styles.subtitle_style, None)
+                } else if in_code {
+                    (self.styles.subtitle_style, None)
+                } else if let Some(level) = heading_level(line) {
+                    (self.styles.title_style, Some(level))
+                } else {
+                    (self.styles.text_style, None)
+                }
+            })
+            .collect()
+    }
+
+    /// Checks if the given heading line is folded.
+    fn is_folded(&self, line: &str) -> bool {
+        self.folds
+            .borrow()
+            .get(&self.note_id)
+            .is_some_and(|folded| folded.contains(line))
+    }
+
+    /// Wraps all lines of the text not hidden by a fold to the given width.
+    /// Returns the resulting rows together with the index of the line they belong to, the style they are to be displayed in, the heading level of that line, if any, and the byte range of the line they display.
+    /// Rows of tables do not display a part of their line as written and have no range.
+    fn rows(&self, width: u16) -> Vec<TextRow> {
+        let mut rows = Vec::new();
+        // The level of the heading whose section is currently being hidden.
+        let mut folded_level = None;
+        // Tables are rendered as a whole, every line of a table becoming one row.
+        let tables = self
+            .tables
+            .iter()
+            .flat_map(|(start, end)| (*start..).zip(render_table(&self.lines[*start..*end], width)))
+            .collect::<HashMap<_, _>>();
+
+        for (index, (line, (style, level))) in self.lines.iter().zip(self.line_kinds()).enumerate()
+        {
+            // A heading of the same or a higher level ends the folded section.
+            if let (Some(level), Some(folded)) = (level, folded_level) {
+                if level <= folded {
+                    folded_level = None;
+                }
+            }
+
+            if folded_level.is_some() {
+                continue;
+            }
+
+            if let Some(row) = tables.get(&index) {
+                // the header is the first line of a table
+                let style = if self.tables.contains_key(&index) {
+                    self.styles.title_style
+                } else {
+                    style
+                };
+                rows.push((index, row.clone(), style, level, None));
+                continue;
+            }
+
+            let text = if level.is_some() && self.is_folded(line) {
+                folded_level = level;
+                format!("{} …", line)
+            } else {
+                line.clone()
+            };
+
+            rows.extend(
+                wrap(&text, width as usize)
+                    .into_iter()
+                    .map(|(source, row)| (index, row, style, level, Some(source))),
+            );
+        }
+
+        rows
+    }
+
+    /// Returns the rows of the text column as it was last drawn.
+    fn current_rows(&self) -> Vec<TextRow> {
+        self.rows(self.column.get().0)
+    }
+
+    /// Returns the index of the line displayed in the first row of the text column.
+    fn top_line(&self) -> Option<usize> {
+        let rows = self.current_rows();
+        rows.get(self.scroll.min(self.max_scroll()))
+            .or(rows.last())
+            .map(|(index, ..)| *index)
+    }
+
+    /// Scrolls such that the given line is displayed in the first row, if possible.
+    fn scroll_to_line(&mut self, line: usize) {
+        if let Some(row) = self
+            .current_rows()
+            .iter()
+            .position(|(index, ..)| *index == line)
+        {
+            self.scroll = row.min(self.max_scroll());
+        }
+    }
+
+    /// Folds or unfolds the section the first displayed row belongs to.
+    fn toggle_current_fold(&mut self) {
+        let Some(top) = self.top_line() else {
+            return;
+        };
+
+        // Find the closest visible heading at or above the top row.
+        let Some(heading) = self
+            .current_rows()
+            .into_iter()
+            .rev()
+            .find(|(index, _, _, level, _)| *index <= top && level.is_some())
+            .map(|(index, ..)| index)
+        else {
+            return;
+        };
+
+        let line = self.lines[heading].clone();
+        {
+            let mut folds = self.folds.borrow_mut();
+            let folded = folds.entry(self.note_id.clone()).or_default();
+            if !folded.remove(&line) {
+                folded.insert(line);
+            }
+        }
+
+        self.scroll_to_line(heading);
+    }
+
+    /// Unfolds all sections if any are folded, otherwise folds all of them.
+    fn toggle_all_folds(&mut self) {
+        let headings = self
+            .lines
+            .iter()
+            .zip(self.line_kinds())
+            .filter(|(_line, (_style, level))| level.is_some())
+            .map(|(line, _)| line.clone())
+            .collect::<HashSet<_>>();
+
+        let mut folds = self.folds.borrow_mut();
+        let folded = folds.entry(self.note_id.clone()).or_default();
+        if folded.is_empty() {
+            *folded = headings;
+        } else {
+            folded.clear();
+        }
+        drop(folds);
+
+        self.scroll = 0;
+    }
+
+    /// Scrolls to the next (or previous) visible heading.
+    fn jump_to_heading(&mut self, forward: bool) {
+        let Some(top) = self.top_line() else {
+            return;
+        };
+
+        let headings = self
+            .current_rows()
+            .into_iter()
+            .filter(|(_, _, _, level, _)| level.is_some())
+            .map(|(index, ..)| index)
+            .collect::<Vec<_>>();
+
+        let target = if forward {
+            headings.into_iter().find(|index| *index > top)
+        } else {
+            headings.into_iter().rev().find(|index| *index < top)
+        };
+
+        if let Some(target) = target {
+            self.scroll_to_line(target);
+        }
+    }
+
+    /// Returns the index of the line of every match of the current search term, once per match.
+    fn matches(&self) -> Vec<usize> {
+        let Some(query) = &self.query else {
+            return Vec::new();
+        };
+
+        self.lines
+            .iter()
+            .enumerate()
+            .flat_map(|(index, line)| {
+                std::iter::repeat_n(index, line.to_lowercase().matches(query.as_str()).count())
+            })
+            .collect()
+    }
+
+    /// Unfolds all sections containing the given line, so it is displayed.
+    fn reveal_line(&mut self, line: usize) {
+        let kinds = self.line_kinds();
+        // Only headings of a lower level than all headings seen so far contain the line.
+        let mut max_level = kinds[line].1.unwrap_or(7);
+
+        let mut folds = self.folds.borrow_mut();
+        let Some(folded) = folds.get_mut(&self.note_id) else {
+            return;
+        };
+
+        for index in (0..line).rev() {
+            if let Some(level) = kinds[index].1.filter(|level| *level < max_level) {
+                folded.remove(&self.lines[index]);
+                max_level = level;
+            }
+        }
+    }
+
+    /// Scrolls to the match with the given index, wrapping around at the ends.
+    fn go_to_match(&mut self, index: isize) {
+        let matches = self.matches();
+        if matches.is_empty() {
+            return;
+        }
+
+        self.current_match = index.rem_euclid(matches.len() as isize) as usize;
+        let line = matches[self.current_match];
+        self.reveal_line(line);
+        self.scroll_to_line(line);
+    }
+
+    /// Returns the byte ranges of all matches of the current search term in the given line.
+    fn match_ranges(&self, line: &str) -> Vec<Range<usize>> {
+        let lowercase = line.to_lowercase();
+        match &self.query {
+            // Byte offsets of matches can only be transferred if lowercasing kept them.
+            Some(query) if !query.is_empty() && lowercase.len() == line.len() => lowercase
+                .match_indices(query.as_str())
+                .map(|(start, query)| start..start + query.len())
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// Highlights all matches of the current search term in a row displaying the given range of the given line.
+    /// Matches are searched for in the whole line, so matches spanning several rows are highlighted in all of them.
+    /// Rows without a range are searched on their own.
+    fn highlight(
+        &self,
+        line: &str,
+        row: String,
+        style: Style,
+        source: Option<Range<usize>>,
+    ) -> Line<'static> {
+        // Map the matches within the line to the row, which may start with an indentation.
+        let ranges = match source {
+            Some(source) => {
+                let indent = row.len() - source.len();
+                self.match_ranges(line)
+                    .into_iter()
+                    .filter(|range| range.start < source.end && source.start < range.end)
+                    .map(|range| {
+                        range.start.max(source.start) - source.start + indent
+                            ..range.end.min(source.end) - source.start + indent
+                    })
+                    .collect()
+            }
+            None => self.match_ranges(&row),
+        };
+
+        let mut spans = Vec::new();
+        let mut last = 0;
+        for range in ranges {
+            spans.push(Span::styled(row[last..range.start].to_owned(), style));
+            spans.push(Span::styled(
+                row[range.clone()].to_owned(),
+                self.styles.selected_style,
+            ));
+            last = range.end;
+        }
+        spans.push(Span::styled(row[last..].to_owned(), style));
+
+        Line::from(spans)
+    }
+
+    /// Returns the largest sensible scroll offset for the text column as it was last drawn.
+    /// Sorts the data table by the next column, or in the other direction, showing it unsorted after the last column.
+    fn sort_table(&mut self, next_column: bool) {
+        let Some(data) = &mut self.data else {
+            return;
+        };
+
+        data.sorting = match (data.sorting, next_column) {
+            (None, true) => Some((0, true)),
+            (Some((column, _)), true) if column + 1 < data.table.columns.len() => {
+                Some((column + 1, true))
+            }
+            (Some(_), true) => None,
+            (Some((column, ascending)), false) => Some((column, !ascending)),
+            (None, false) => None,
+        };
+
+        let lines = match data.sorting {
+            Some((column, ascending)) => {
+                let mut table = data.table.clone();
+                table.sort(column, ascending);
+                let mut lines = table.to_markdown(data.sorting);
+                // keep the alignments of the columns
+                lines[1] = data.original[1].clone();
+                lines
+            }
+            None => data.original.clone(),
+        };
+
+        self.lines
+            .splice(data.start..data.start + lines.len(), lines);
+    }
+
+    fn max_scroll(&self) -> usize {
+        self.current_rows()
+            .len()
+            .saturating_sub(self.column.get().1 as usize)
+    }
+}
+
+impl super::Screen for PreviewScreen {
+    fn draw(&self, area: layout::Rect, buf: &mut buffer::Buffer) {
+        let [column_area] = Layout::horizontal([Constraint::Length(self.reading_width)])
+            .flex(layout::Flex::Center)
+            .areas(area);
+
+        self.column.set((column_area.width, column_area.height));
+
+        let text = self
+            .rows(column_area.width)
+            .into_iter()
+            .skip(self.scroll.min(self.max_scroll()))
+            .take(column_area.height as usize)
+            .map(|(index, row, style, _level, source)| {
+                self.highlight(&self.lines[index], row, style, source)
+            })
+            .collect::<Vec<_>>();
+
+        Widget::render(Paragraph::new(text), column_area, buf);
+
+        let [_, bottom_area] =
+            Layout::vertical([Constraint::Fill(1), Constraint::Length(3)]).areas(column_area);
+
+        if self.mode == PreviewMode::Search {
+            Widget::render(Clear, bottom_area, buf);
+            Widget::render(&self.search_area, bottom_area, buf);
+        } else if self.query.is_some() {
+            // Show the position within the matches in the last row.
+            let matches = self.matches().len();
+            let status = Line::from(ui::hotkeys(
+                &ui::tr_args(
+                    "preview-matches",
+                    &[
+                        ("current", (self.current_match + 1).into()),
+                        ("count", matches.into()),
+                    ],
+                ),
+                self.styles.hotkey_style,
+                self.styles.text_style,
+            ))
+            .right_aligned();
+
+            let status_area = Rect {
+                y: bottom_area.bottom().saturating_sub(1),
+                height: 1,
+                ..bottom_area
+            };
+            Widget::render(Clear, status_area, buf);
+            Widget::render(status, status_area, buf);
+        }
+    }
+
+    fn update(&mut self, key: ratatui::crossterm::event::KeyEvent) -> error::Result<ui::Message> {
+        if self.mode == PreviewMode::Search {
+            match key.code {
+                KeyCode::Esc => {
+                    super::extract_string_and_clear(&mut self.search_area);
+                    self.mode = PreviewMode::Read;
+                }
+                KeyCode::Enter => {
+                    self.mode = PreviewMode::Read;
+                    self.query = super::extract_string_and_clear(&mut self.search_area)
+                        .filter(|query| !query.is_empty())
+                        .map(|query| query.to_lowercase());
+
+                    // Start with the first match from the current position on.
+                    let top = self.top_line().unwrap_or_default();
+                    let first = self
+                        .matches()
+                        .iter()
+                        .position(|line| *line >= top)
+                        .unwrap_or_default();
+                    self.go_to_match(first as isize);
+                }
+                _ => {
+                    self.search_area.input(key);
+                }
+            }
+            return Ok(ui::Message::None);
+        }
+
+        let page = self.column.get().1.saturating_sub(1).max(1) as usize;
+
+        self.scroll = match key.code {
+            // Quit with Q
+            KeyCode::Char('q' | 'Q') => return Ok(ui::Message::Quit),
+            // Esc: Clear the search, if any
+            KeyCode::Esc if self.query.is_some() => {
+                self.query = None;
+                self.scroll
+            }
+            // Return to the previous screen
+            KeyCode::Esc | KeyCode::Left | KeyCode::Char('h' | 'H') => {
+                return Ok(ui::Message::CloseOverlay);
+            }
+            // /: Search within the note
+            KeyCode::Char('/') => {
+                self.mode = PreviewMode::Search;
+                self.scroll
+            }
+            // n/N: Go to the next or previous match
+            KeyCode::Char('n') => {
+                self.go_to_match(self.current_match as isize + 1);
+                self.scroll
+            }
+            KeyCode::Char('N') => {
+                self.go_to_match(self.current_match as isize - 1);
+                self.scroll
+            }
+            KeyCode::Char('j' | 'J') | KeyCode::Down => self.scroll.saturating_add(1),
+            KeyCode::Char('k' | 'K') | KeyCode::Up => self.scroll.saturating_sub(1),
+            KeyCode::PageDown | KeyCode::Char(' ') => self.scroll.saturating_add(page),
+            KeyCode::PageUp => self.scroll.saturating_sub(page),
+            KeyCode::Home | KeyCode::Char('g') => 0,
+            KeyCode::End | KeyCode::Char('G') => usize::MAX,
+            // Tab: Fold or unfold the current section
+            KeyCode::Tab => {
+                self.toggle_current_fold();
+                self.scroll
+            }
+            // Shift+Tab: Fold or unfold all sections
+            KeyCode::BackTab => {
+                self.toggle_all_folds();
+                self.scroll
+            }
+            // ]/[: Jump to the next or previous heading
+            KeyCode::Char(']') => {
+                self.jump_to_heading(true);
+                self.scroll
+            }
+            KeyCode::Char('[') => {
+                self.jump_to_heading(false);
+                self.scroll
+            }
+            // s/S: Sort the data table by the next column or in the other direction
+            KeyCode::Char('s') => {
+                self.sort_table(true);
+                self.scroll
+            }
+            KeyCode::Char('S') => {
+                self.sort_table(false);
+                self.scroll
+            }
+            _ => self.scroll,
+        }
+        .min(self.max_scroll());
+
+        Ok(ui::Message::None)
+    }
+}
+
+/// Splits the given content of a note into its lines without its frontmatter, inserting the blocks referenced by a line (`[[note#^id]]`) as quotes after it.
+/// Also returns the indices of the inserted lines.
+fn embed_blocks(content: &str, index: &data::NoteIndex) -> (Vec<String>, HashSet<usize>) {
+    let mut lines = Vec::new();
+    let mut embedded = HashSet::new();
+    let mut in_code = false;
+
+    for line in strip_frontmatter(content).lines() {
+        lines.push(line.to_owned());
+
+        if line.trim_start().starts_with("```") {
+            in_code = !in_code;
+        }
+        if in_code {
+            continue;
+        }
+
+        for (target, block) in data::block_references(line) {
+            // references within the note leave out its name
+            let text = if target.is_empty() {
+                data::find_block(content, &block)
+            } else {
+                index
+                    .get(&data::name_to_id(&target))
+                    .and_then(|note| std::fs::read_to_string(&note.path).ok())
+                    .and_then(|target| data::find_block(&target, &block))
+            };
+
+            for quoted in text.iter().flat_map(|text| text.lines()) {
+                embedded.insert(lines.len());
+                lines.push(format!("│ {}", quoted));
+            }
+        }
+    }
+
+    (lines, embedded)
+}
+
+/// Finds the markdown tables among the given lines, outside of code blocks and embedded blocks.
+/// Returns the index of the first line of each table, its header, and the index after its last line.
+fn find_tables(lines: &[String], embedded: &HashSet<usize>) -> HashMap<usize, usize> {
+    let is_row = |index: usize| {
+        !embedded.contains(&index)
+            && lines[index].contains('|')
+            && !lines[index].trim_start().starts_with("```")
+    };
+
+    let mut tables = HashMap::new();
+    let mut in_code = false;
+    let mut index = 0;
+    while index < lines.len() {
+        if lines[index].trim_start().starts_with("```") {
+            in_code = !in_code;
+        } else if !in_code
+            && index + 1 < lines.len()
+            && is_row(index)
+            && is_row(index + 1)
+            && table_alignments(&lines[index + 1]).is_some_and(|alignments| {
+                alignments.len() == data::table_cells(&lines[index]).len()
+            })
+        {
+            // The header and delimiter row are followed by all consecutive rows.
+            let mut end = index + 2;
+            while end < lines.len() && is_row(end) {
+                end += 1;
+            }
+            tables.insert(index, end);
+            index = end;
+            continue;
+        }
+        index += 1;
+    }
+
+    tables
+}
+
+/// Returns the alignments of the columns if the given line is the delimiter row of a markdown table, like `| :--- | :---: | ---: |`.
+fn table_alignments(line: &str) -> Option<Vec<Alignment>> {
+    data::table_cells(line)
+        .iter()
+        .map(|cell| {
+            let dashes = cell.trim_start_matches(':').trim_end_matches(':');
+            (!dashes.is_empty() && dashes.chars().all(|c| c == '-')).then_some(
+                match (cell.starts_with(':'), cell.ends_with(':')) {
+                    (true, true) => Alignment::Center,
+                    (false, true) => Alignment::Right,
+                    _ => Alignment::Left,
+                },
+            )
+        })
+        .collect()
+}
+
+/// Renders the given lines of a markdown table, its header, delimiter row and body, as a table of the given width.
+/// Returns one row for every line, the delimiter row becoming a rule below the header.
+fn render_table(lines: &[String], width: u16) -> Vec<String> {
+    let alignments = table_alignments(&lines[1]).unwrap_or_default();
+    let header = data::table_cells(&lines[0]);
+    let body = lines[2..]
+        .iter()
+        .map(|line| data::table_cells(line))
+        .collect::<Vec<_>>();
+
+    // Every column is as wide as its widest cell.
+    let widths = (0..alignments.len()).map(|column| {
+        std::iter::once(&header)
+            .chain(&body)
+            .flat_map(|cells| cells.get(column))
+            .map(|cell| Line::from(cell.as_str()).width() as u16)
+            .max()
+            .map(Constraint::Length)
+            .unwrap_or_default()
+    });
+
+    let row = |cells: &Vec<String>| {
+        Row::new(alignments.iter().enumerate().map(|(column, alignment)| {
+            ratatui::widgets::Cell::from(
+                Text::from(cells.get(column).cloned().unwrap_or_default()).alignment(*alignment),
+            )
+        }))
+    };
+
+    let table = Table::new(body.iter().map(row), widths)
+        .header(row(&header))
+        .column_spacing(2);
+
+    let area = Rect::new(0, 0, width, (lines.len() - 1) as u16);
+    let mut buffer = Buffer::empty(area);
+    Widget::render(table, area, &mut buffer);
+
+    let mut rows = (area.top()..area.bottom())
+        .map(|y| {
+            let mut row = String::new();
+            let mut x = area.left();
+            // Cells hidden by wide characters before them are skipped.
+            while x < area.right() {
+                let symbol = buffer[(x, y)].symbol();
+                row.push_str(symbol);
+                x += (Line::from(symbol).width() as u16).max(1);
+            }
+            row.trim_end().to_owned()
+        })
+        .collect::<Vec<_>>();
+
+    let rule = "─".repeat(
+        rows.iter()
+            .map(|row| Line::from(row.as_str()).width())
+            .max()
+            .unwrap_or_default(),
+    );
+    rows.insert(1.min(rows.len()), rule);
+
+    rows
+}
+
+/// Removes a YAML frontmatter from the start of a note's content, if there is one.
+fn strip_frontmatter(content: &str) -> &str {
+    content
+        .strip_prefix("---\n")
+        .and_then(|rest| rest.split_once("\n---\n"))
+        .map(|(_frontmatter, body)| body)
+        .unwrap_or(content)
+}
+
+/// Returns the level of a markdown heading, or `None` if the line is not a heading.
+fn heading_level(line: &str) -> Option<usize> {
+    let level = line.chars().take_while(|c| *c == '#').count();
+    let rest = &line[level..];
+
+    ((1..=6).contains(&level) && (rest.is_empty() || rest.starts_with(' '))).then_some(level)
+}
+
+/// Soft-wraps a line at word boundaries such that no row is longer than the given width.
+/// Words longer than the width are split.
+/// Returns every row together with the byte range of the line it displays, which excludes the indentation at the start of the first row.
+fn wrap(line: &str, width: usize) -> Vec<(Range<usize>, String)> {
+    let width = width.max(1);
+
+    // Keep the indentation of the line, e.g. for nested lists.
+    let text = line.trim_start_matches(' ');
+    let mut offset = line.len() - text.len();
+    let indent = offset.min(width - 1);
+    let mut rows = vec![(offset..offset, " ".repeat(indent))];
+
+    for (i, word) in text.split(' ').enumerate() {
+        let (source, row) = rows.last_mut().unwrap();
+        let row_len = row.chars().count();
+        let word_len = word.chars().count();
+
+        if i > 0 && row_len + 1 + word_len > width {
+            rows.push((offset..offset, String::new()));
+        } else if i > 0 {
+            row.push(' ');
+            source.end += 1;
+        }
+
+        // Split words that do not fit into a row on their own.
+        for c in word.chars() {
+            if rows
+                .last()
+                .is_some_and(|(_, row)| row.chars().count() >= width)
+            {
+                rows.push((offset..offset, String::new()));
+            }
+            let (source, row) = rows.last_mut().unwrap();
+            row.push(c);
+            source.end += c.len_utf8();
+            offset += c.len_utf8();
+        }
+
+        // skip the space after the word
+        offset += 1;
+    }
+
+    rows
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A note with nested sections and a code block containing a line that looks like a heading.
+    const NOTE: &str = "---\ntitle: Sections\n---\n# Top\nIntro\n## First\nText one\n### Nested\nNested text\n## Second\n```sh\n# not a heading\n```\nText two\n# Next\nEnd\n";
+
+    /// Creates a preview screen showing the given content in a text column of the given size.
+    fn screen(content: &str, width: u16, height: u16) -> PreviewScreen {
+        let lines = strip_frontmatter(content)
+            .lines()
+            .map(str::to_owned)
+            .collect::<Vec<_>>();
+        let embedded = HashSet::new();
+
+        PreviewScreen {
+            styles: ui::UiStyles::default(),
+            reading_width: width,
+            note_id: String::from("sections"),
+            tables: find_tables(&lines, &embedded),
+            lines,
+            embedded,
+            folds: FoldState::default(),
+            data: None,
+            scroll: 0,
+            column: Cell::new((width, height)),
+            search_area: tui_textarea::TextArea::default(),
+            query: None,
+            current_match: 0,
+            mode: PreviewMode::Read,
+        }
+    }
+
+    /// Returns the indices of the lines displayed by the given screen, once per line.
+    fn visible_lines(screen: &PreviewScreen) -> Vec<usize> {
+        let mut lines = screen
+            .current_rows()
+            .into_iter()
+            .map(|(index, ..)| index)
+            .collect::<Vec<_>>();
+        lines.dedup();
+        lines
+    }
+
+    /// Folds the given heading lines of the given screen.
+    fn fold(screen: &PreviewScreen, headings: &[&str]) {
+        screen.folds.borrow_mut().insert(
+            screen.note_id.clone(),
+            headings.iter().map(|heading| heading.to_string()).collect(),
+        );
+    }
+
+    #[test]
+    fn test_strip_frontmatter() {
+        assert_eq!(
+            strip_frontmatter("---\ntitle: Atlas\ntags: [math]\n---\n# Atlas\nText\n"),
+            "# Atlas\nText\n"
+        );
+        assert_eq!(strip_frontmatter("# Atlas\nText\n"), "# Atlas\nText\n");
+        // unclosed frontmatter and rules later in the text are kept
+        assert_eq!(
+            strip_frontmatter("---\ntitle: Atlas\n# Atlas\n"),
+            "---\ntitle: Atlas\n# Atlas\n"
+        );
+        assert_eq!(
+            strip_frontmatter("Text\n---\nMore\n---\n"),
+            "Text\n---\nMore\n---\n"
+        );
+    }
+
+    #[test]
+    fn test_heading_level() {
+        assert_eq!(heading_level("# Atlas"), Some(1));
+        assert_eq!(heading_level("### Charts and Atlases"), Some(3));
+        assert_eq!(heading_level("###### Six"), Some(6));
+        assert_eq!(heading_level("##"), Some(2));
+        assert_eq!(heading_level("####### Seven"), None);
+        assert_eq!(heading_level("#math"), None);
+        assert_eq!(heading_level("Text # no heading"), None);
+        assert_eq!(heading_level(" # Indented"), None);
+        assert_eq!(heading_level(""), None);
+    }
+
+    /// Wraps the given line, checking that every row displays the range of the line it claims to.
+    fn wrapped(line: &str, width: usize) -> Vec<String> {
+        wrap(line, width)
+            .into_iter()
+            .map(|(source, row)| {
+                assert!(row.ends_with(&line[source]));
+                row
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_wrap() {
+        assert_eq!(
+            wrapped("The quick brown fox", 10),
+            vec!["The quick", "brown fox"]
+        );
+        assert_eq!(wrapped("", 10), vec![""]);
+
+        // words longer than the width are split
+        assert_eq!(
+            wrapped("a supercalifragilistic word", 8),
+            vec!["a", "supercal", "ifragili", "stic", "word"]
+        );
+
+        // the width is counted in characters, not bytes
+        assert_eq!(
+            wrapped("Über größere Flüsse", 7),
+            vec!["Über", "größere", "Flüsse"]
+        );
+        assert_eq!(wrapped("äöüäöüäöü", 4), vec!["äöüä", "öüäö", "ü"]);
+
+        // the indentation of the first row is kept
+        assert_eq!(wrapped("  - item one two", 8), vec!["  - item", "one two"]);
+        assert_eq!(wrapped("      deep", 4), vec!["   d", "eep"]);
+
+        // a width of zero is treated as one
+        assert_eq!(wrapped("ab", 0), vec!["a", "b"]);
+
+        assert_eq!(
+            wrap("  Über größere", 7),
+            vec![
+                (2..7, String::from("  Über")),
+                (8..17, String::from("größere"))
+            ]
+        );
+    }
+
+    #[test]
+    fn test_line_kinds() {
+        let screen = screen(NOTE, 40, 100);
+
+        assert_eq!(
+            screen
+                .line_kinds()
+                .into_iter()
+                .map(|(_style, level)| level)
+                .collect::<Vec<_>>(),
+            vec![
+                Some(1),
+                None,
+                Some(2),
+                None,
+                Some(3),
+                None,
+                Some(2),
+                None,
+                // the heading within the code block is not recognized
+                None,
+                None,
+                None,
+                Some(1),
+                None,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_folds() {
+        let screen = screen(NOTE, 40, 100);
+        assert_eq!(visible_lines(&screen), (0..13).collect::<Vec<_>>());
+
+        // a fold hides everything down to the next heading of the same or a higher level
+        fold(&screen, &["## First"]);
+        assert_eq!(
+            visible_lines(&screen),
+            vec![0, 1, 2, 6, 7, 8, 9, 10, 11, 12]
+        );
+        assert_eq!(screen.current_rows()[2].1, "## First …");
+
+        fold(&screen, &["# Top"]);
+        assert_eq!(visible_lines(&screen), vec![0, 11, 12]);
+
+        // headings within code blocks do not end a fold
+        fold(&screen, &["## Second"]);
+        assert_eq!(visible_lines(&screen), vec![0, 1, 2, 3, 4, 5, 6, 11, 12]);
+
+        // nested folds
+        fold(&screen, &["### Nested"]);
+        assert_eq!(
+            visible_lines(&screen),
+            vec![0, 1, 2, 3, 4, 6, 7, 8, 9, 10, 11, 12]
+        );
+
+        fold(&screen, &["## First", "### Nested"]);
+        assert_eq!(
+            visible_lines(&screen),
+            vec![0, 1, 2, 6, 7, 8, 9, 10, 11, 12]
+        );
+
+        fold(&screen, &["# Top", "## First", "### Nested"]);
+        assert_eq!(visible_lines(&screen), vec![0, 11, 12]);
+    }
+
+    #[test]
+    fn test_toggle_folds() {
+        let mut screen = screen(NOTE, 40, 3);
+
+        // the current section is the one of the closest heading above the first displayed row
+        screen.scroll_to_line(5);
+        screen.toggle_current_fold();
+        assert!(screen.is_folded("### Nested"));
+        assert!(!screen.is_folded("## First"));
+        assert_eq!(
+            visible_lines(&screen),
+            vec![0, 1, 2, 3, 4, 6, 7, 8, 9, 10, 11, 12]
+        );
+        assert_eq!(screen.top_line(), Some(4));
+
+        screen.toggle_current_fold();
+        assert!(!screen.is_folded("### Nested"));
+        assert_eq!(visible_lines(&screen), (0..13).collect::<Vec<_>>());
+
+        // folds all headings, but not the line within the code block
+        screen.toggle_all_folds();
+        for heading in ["# Top", "## First", "### Nested", "## Second", "# Next"] {
+            assert!(screen.is_folded(heading));
+        }
+        assert!(!screen.is_folded("# not a heading"));
+        assert_eq!(visible_lines(&screen), vec![0, 11]);
+        assert_eq!(screen.scroll, 0);
+
+        // unfolds all headings if any is folded
+        fold(&screen, &["## Second"]);
+        screen.toggle_all_folds();
+        assert_eq!(visible_lines(&screen), (0..13).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_matches() {
+        let mut screen = screen(NOTE, 40, 100);
+        assert!(screen.matches().is_empty());
+
+        screen.query = Some(String::from("text"));
+        assert_eq!(screen.matches(), vec![3, 5, 10]);
+
+        // every match is listed, also within code blocks
+        screen.query = Some(String::from("e"));
+        assert_eq!(
+            screen.matches(),
+            vec![3, 3, 4, 4, 5, 5, 5, 6, 8, 10, 11, 12]
+        );
+
+        screen.query = Some(String::from("atlas"));
+        assert!(screen.matches().is_empty());
+    }
+
+    #[test]
+    fn test_highlight() {
+        // "Nested text" is wrapped after "Nested"
+        let mut screen = screen(NOTE, 10, 100);
+        screen.query = Some(String::from("ed te"));
+
+        let highlighted = screen
+            .current_rows()
+            .into_iter()
+            .filter(|(index, ..)| *index == 5)
+            .map(|(index, row, style, _level, source)| {
+                screen
+                    .highlight(&screen.lines[index], row, style, source)
+                    .spans
+                    .into_iter()
+                    .filter(|span| span.style == screen.styles.selected_style)
+                    .map(|span| span.content.into_owned())
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+
+        // the match spanning both rows is highlighted in each of them
+        assert_eq!(highlighted, vec![vec!["ed"], vec!["te"]]);
+    }
+
+    #[test]
+    fn test_reveal_line() {
+        let mut screen = screen(NOTE, 40, 3);
+        fold(&screen, &["# Top", "## First", "### Nested", "## Second"]);
+
+        // unfolds all sections containing the line, but no others
+        screen.reveal_line(5);
+        assert!(!screen.is_folded("# Top"));
+        assert!(!screen.is_folded("## First"));
+        assert!(!screen.is_folded("### Nested"));
+        assert!(screen.is_folded("## Second"));
+        assert_eq!(visible_lines(&screen), vec![0, 1, 2, 3, 4, 5, 6, 11, 12]);
+
+        // navigating to a match reveals it
+        screen.query = Some(String::from("two"));
+        screen.go_to_match(0);
+        assert!(!screen.is_folded("## Second"));
+        assert_eq!(screen.top_line(), Some(10));
+    }
+}
@@ -0,0 +1,298 @@
+use crate::{data, error, io, ui};
+
+use itertools::Itertools;
+use ratatui::crossterm::event::KeyCode;
+use ratatui::{prelude::*, widgets::*};
+
+/// The problems screen lists the problems the linter and spellchecker find in the notes of the vault, or a single note, and fixes them on request.
+pub struct ProblemsScreen {
+    // === CONFIG ===
+    /// The used styles.
+    styles: ui::UiStyles,
+    /// The file manager used to write fixes.
+    manager: io::FileManager,
+    /// The linter checking the notes.
+    linter: data::Linter,
+    /// The spellchecker checking the notes, if one is configured.
+    spellchecker: Option<io::Spellchecker>,
+
+    // === DATA ===
+    /// The index whose notes are checked.
+    index: data::NoteIndexContainer,
+    /// The id of the only note checked, if not all notes are.
+    note: Option<String>,
+    /// The problems found, with the id and name of their note.
+    problems: Vec<(String, String, data::Diagnostic)>,
+
+    // === UI ===
+    /// The index of the problem selected in the table.
+    selected: usize,
+}
+
+impl ProblemsScreen {
+    /// Creates a new problems screen for the note with the given id or, without one, all notes of the given index.
+    /// The notes are only checked once the screen is refreshed.
+    pub fn new(
+        index: data::NoteIndexContainer,
+        manager: io::FileManager,
+        linter: data::Linter,
+        spellchecker: Option<io::Spellchecker>,
+        note: Option<String>,
+        styles: ui::UiStyles,
+    ) -> Self {
+        Self {
+            styles,
+            manager,
+            linter,
+            spellchecker,
+            index,
+            note,
+            problems: Vec::new(),
+            selected: 0,
+        }
+    }
+
+    /// Checks the notes again.
+    /// Returns the first error of the spellchecker, if any, after the problems it did find are listed.
+    pub fn refresh(&mut self) -> error::Result<()> {
+        let loaded = match &mut self.spellchecker {
+            Some(spellchecker) => spellchecker.load_ignored(),
+            None => Ok(()),
+        };
+
+        let index = self.index.borrow();
+        let (problems, errors) =
+            self.linter
+                .lint_index(&index, self.note.as_deref(), self.spellchecker.as_ref());
+        self.problems = problems
+            .into_iter()
+            .flat_map(|(id, diagnostics)| {
+                let name = index
+                    .get(&id)
+                    .map(|note| note.display_name.clone())
+                    .unwrap_or_else(|| id.clone());
+                diagnostics
+                    .into_iter()
+                    .map(move |diagnostic| (id.clone(), name.clone(), diagnostic))
+            })
+            .collect();
+        self.selected = self.selected.min(self.problems.len().saturating_sub(1));
+
+        loaded.and(errors.into_iter().next().map_or(Ok(()), Err))
+    }
+
+    /// Fixes the problems of the notes with the given ids that can be fixed automatically, then checks all notes again.
+    fn fix(&mut self, ids: &[&str]) -> error::Result<()> {
+        let index = self.index.borrow();
+        let result = ids
+            .iter()
+            .flat_map(|id| index.get(id))
+            .map(|note| {
+                self.manager
+                    .fix_note(note, &self.linter)
+                    .map_err(|e| e.in_file(&note.path, None))
+            })
+            .find(Result::is_err)
+            .unwrap_or(Ok(()));
+        drop(index);
+
+        self.refresh()?;
+        result
+    }
+}
+
+impl super::Screen for ProblemsScreen {
+    fn draw(&self, area: layout::Rect, buf: &mut buffer::Buffer) {
+        let title = Line::from(match &self.note {
+            Some(id) => vec![
+                Span::styled(ui::tr("problems-title-note") + " ", self.styles.title_style),
+                Span::styled(
+                    self.index
+                        .borrow()
+                        .get(id)
+                        .map(|note| note.display_name.clone())
+                        .unwrap_or_else(|| id.clone()),
+                    self.styles.subtitle_style,
+                ),
+            ],
+            None => vec![Span::styled(
+                ui::tr("problems-title"),
+                self.styles.title_style,
+            )],
+        });
+
+        let counts = self
+            .problems
+            .iter()
+            .map(|(_, _, diagnostic)| diagnostic.severity)
+            .counts();
+        let count = Line::from(vec![Span::styled(
+            [
+                data::Severity::Error,
+                data::Severity::Warning,
+                data::Severity::Hint,
+            ]
+            .iter()
+            .map(|severity| {
+                let count = counts.get(severity).copied().unwrap_or_default();
+                ui::tr_args(
+                    &format!("problems-count-{}", severity_id(severity)),
+                    &[("count", count.into())],
+                )
+            })
+            .join(", "),
+            self.styles.text_style,
+        )])
+        .right_aligned();
+
+        let instructions = Line::from(ui::tr_hotkeys(
+            if self.spellchecker.is_some() {
+                "problems-hints-spelling"
+            } else {
+                "problems-hints"
+            },
+            self.styles.hotkey_style,
+            self.styles.text_style,
+        ))
+        .left_aligned();
+
+        let rows = self
+            .problems
+            .iter()
+            .map(|(_id, name, diagnostic)| {
+                Row::new(vec![
+                    Span::styled(
+                        ui::tr(&format!("severity-{}", severity_id(&diagnostic.severity))),
+                        match diagnostic.severity {
+                            data::Severity::Error => self.styles.hotkey_style,
+                            data::Severity::Warning => self.styles.subtitle_style,
+                            _ => self.styles.text_style,
+                        },
+                    ),
+                    Span::styled(name.as_str(), self.styles.subtitle_style),
+                    Span::styled(
+                        diagnostic
+                            .line
+                            .map(|line| line.to_string())
+                            .unwrap_or_default(),
+                        self.styles.text_style,
+                    ),
+                    Span::styled(
+                        if diagnostic.rule.fixable() {
+                            ui::tr_args(
+                                "problems-fixable",
+                                &[("message", diagnostic.message.clone().into())],
+                            )
+                        } else {
+                            diagnostic.message.clone()
+                        },
+                        self.styles.text_style,
+                    ),
+                ])
+            })
+            .collect_vec();
+
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Length(7),
+                Constraint::Percentage(25),
+                Constraint::Length(5),
+                Constraint::Fill(1),
+            ],
+        )
+        .column_spacing(2)
+        .header(Row::new(vec![
+            Span::styled(ui::tr("problems-column-level"), self.styles.subtitle_style),
+            Span::styled(ui::tr("column-note"), self.styles.subtitle_style),
+            Span::styled(ui::tr("problems-column-line"), self.styles.subtitle_style),
+            Span::styled(
+                ui::tr("problems-column-problem"),
+                self.styles.subtitle_style,
+            ),
+        ]))
+        .highlight_symbol(ui::selection_marker())
+        .row_highlight_style(self.styles.selected_style)
+        .block(
+            ui::bordered()
+                .title_top(title)
+                .title_bottom(instructions)
+                .title_bottom(count),
+        );
+
+        let mut state = TableState::new()
+            .with_offset(self.selected.saturating_sub(area.height as usize / 3))
+            .with_selected(Some(self.selected));
+
+        StatefulWidget::render(table, area, buf, &mut state);
+    }
+
+    fn update(&mut self, key: ratatui::crossterm::event::KeyEvent) -> error::Result<ui::Message> {
+        match key.code {
+            KeyCode::Char('q' | 'Q') => return Ok(ui::Message::Quit),
+            // Return to the previous screen
+            KeyCode::Esc | KeyCode::Left | KeyCode::Char('h' | 'H') => {
+                return Ok(ui::Message::CloseOverlay);
+            }
+            KeyCode::Char('j' | 'J') | KeyCode::Down => {
+                self.selected = self
+                    .selected
+                    .saturating_add(1)
+                    .min(self.problems.len().saturating_sub(1));
+            }
+            KeyCode::Char('k' | 'K') | KeyCode::Up => {
+                self.selected = self.selected.saturating_sub(1);
+            }
+            // Enter: Open the note of the selected problem
+            KeyCode::Enter | KeyCode::Right | KeyCode::Char('l' | 'L') => {
+                if let Some((id, _name, _diagnostic)) = self.problems.get(self.selected) {
+                    return Ok(ui::Message::DisplayStackPush(id.to_owned()));
+                }
+            }
+            // F: Fix the note of the selected problem
+            KeyCode::Char('f' | 'F') => {
+                if let Some((id, _name, _diagnostic)) = self.problems.get(self.selected) {
+                    let id = id.clone();
+                    self.fix(&[&id])?;
+                }
+            }
+            // A: Fix all notes
+            KeyCode::Char('a' | 'A') => {
+                let ids = self
+                    .problems
+                    .iter()
+                    .filter(|(_, _, diagnostic)| diagnostic.rule.fixable())
+                    .map(|(id, _, _)| id.clone())
+                    .unique()
+                    .collect_vec();
+                self.fix(&ids.iter().map(String::as_str).collect_vec())?;
+            }
+            // R: Check all notes again, e.g. after editing them
+            KeyCode::Char('r' | 'R') => self.refresh()?,
+            // I: Ignore the misspelled word of the selected problem in all notes
+            KeyCode::Char('i' | 'I') => {
+                let word = self
+                    .problems
+                    .get(self.selected)
+                    .and_then(|(_, _, diagnostic)| diagnostic.word.clone());
+                if let (Some(spellchecker), Some(word)) = (&mut self.spellchecker, word) {
+                    spellchecker.ignore(&word)?;
+                    self.refresh()?;
+                }
+            }
+            _ => {}
+        }
+
+        Ok(ui::Message::None)
+    }
+}
+
+/// Returns the part of the ids of UI strings naming the given severity.
+fn severity_id(severity: &data::Severity) -> &'static str {
+    match severity {
+        data::Severity::Off => "off",
+        data::Severity::Hint => "hint",
+        data::Severity::Warning => "warning",
+        data::Severity::Error => "error",
+    }
+}
@@ -1,10 +1,14 @@
-use crate::{data, error, io, ui};
+use crate::{config::StatsShow, data, error, io, ui};
 use itertools::Itertools;
 use ratatui::crossterm::event::KeyCode;
 use ratatui::{prelude::*, widgets::*};
 
 use tui_textarea::TextArea;
 
+/// The ids of the notes opened in this session, oldest first, without duplicates.
+/// Shared with the app, which records every opened note, so they can be ranked higher when filtering.
+pub type RecentNotes = std::rc::Rc<std::cell::RefCell<Vec<String>>>;
+
 /// Describes the current mode of the UI.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
 enum SelectMode {
@@ -17,6 +21,8 @@ enum SelectMode {
     SubmenuSorting,
     /// Git submenu
     SubmenuGit,
+    /// Export submenu
+    SubmenuExport,
     /// Typing into the filter box.
     Filter,
     /// Show the help screen for the filter box.
@@ -27,18 +33,18 @@ enum SelectMode {
     Rename,
     /// Typing into the create box to move a note.
     Move,
-}
-
-/// Describes when to show a which stats area.
-#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, Default)]
-pub enum StatsShow {
-    // Always shows both stats
-    #[default]
-    Both,
-    // Shows local stats when filtering and nothing otherwise
-    Relevant,
-    // Always shows only local stats
-    Local,
+    /// Typing into the create box the folder to copy all notes to.
+    ExportCopy,
+    /// Typing into the create box the zip archive to write all notes to.
+    ExportZip,
+    /// Typing into the create box the Anki import file to write the flashcards of all notes to.
+    ExportAnki,
+    /// Typing into the create box the addresses to send the selected note to.
+    Email,
+    /// Choosing which of the suggested links to insert into a newly created note.
+    LinkSuggestions,
+    /// Showing the distributions of note statistics in the current environment.
+    Distributions,
 }
 
 /// The select screen shows the user statistical information about their notes and allows them to select one for display.
@@ -46,10 +52,14 @@ pub struct SelectScreen {
     // === DATA ===
     /// A reference to the index of all notes
     index: data::NoteIndexContainer,
+    /// The ids of the notes not modified for longer than the stale rules allow, marked in the list.
+    stale: std::collections::HashSet<String>,
     /// The currently displayed statistics for all notes.
     local_stats: data::EnvironmentStats,
     /// The currently displayed statistics for all notes matching the current filter.
     global_stats: data::EnvironmentStats,
+    /// The search through the full text of notes adding them to the local stats as they are found, while it is running.
+    search: Option<io::ContentSearch>,
 
     // === Config ===
     /// The file manager this screen uses to enact the user's file system requests on the file system.
@@ -58,8 +68,22 @@ pub struct SelectScreen {
     git_manager: Option<io::GitManager>,
     /// The HtmlBuider this screen uses to continuously build html files.
     builder: io::HtmlBuilder,
+    /// The notes recently opened, ranked higher when filtering.
+    recent: RecentNotes,
+    /// How often each note has been opened, ranking frequently used notes higher and sorting by it.
+    visits: io::Visits,
+    /// The collections of the vault, looked up when filtering for them.
+    collections: io::Collections,
+    /// The builder writing the link graph of the current environment.
+    graph_builder: io::GraphBuilder,
+    /// The rules flagging notes not modified for too long.
+    staleness: data::Staleness,
+    /// The export profiles, offered in the export menu for the filters they are attached to.
+    export_profiles: io::ExportProfiles,
     /// The used styles.
     styles: ui::UiStyles,
+    /// The styles of tags configured by the user.
+    tag_styles: ui::TagStyles,
 
     // === UI ===
     /// The text area to type in filters.
@@ -68,6 +92,8 @@ pub struct SelectScreen {
     name_area: TextArea<'static>,
     /// Current input mode
     mode: SelectMode,
+    /// Wether the export menu builds print-friendly HTML and PDF files.
+    print_export: bool,
     /// Current state of the list
     ///
     /// This is saved as a simple usize from which the ListState to use with ratatui is constructed in immediate mode.
@@ -81,26 +107,59 @@ pub struct SelectScreen {
     sorting: data::SortingMode,
     /// Sort ascedingly.
     sorting_asc: bool,
+    /// The order of names when sorting by name.
+    collation: data::Collation,
     /// How to display the two stats blocks.
     stats_show: StatsShow,
+    /// The filter applied on startup and when resetting the filter.
+    default_filter: String,
+    /// The sorting mode and direction set on startup and when resetting the filter.
+    default_sorting: (data::SortingMode, bool),
+    /// The tags of notes hidden unless the filter asks for them.
+    excluded_tags: Vec<String>,
+    /// The filters narrowing down the notes all statistics are computed over, outermost first, with their labels for the breadcrumb.
+    scope: Vec<(String, data::Filter)>,
+
+    // === Link suggestions ===
+    /// The path of the newly created note links are suggested for.
+    suggestions_path: std::path::PathBuf,
+    /// The suggested links for a newly created note, and wether the user has accepted them.
+    suggestions: Vec<(data::LinkSuggestion, bool)>,
+    /// The index of the suggestion currently selected.
+    suggestions_selected: usize,
 }
 
 impl SelectScreen {
-    /// Creates a new stats screen, with no filter applied by default
+    /// Creates a new stats screen, with the default filter and sorting of the config applied.
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         index: data::NoteIndexContainer,
         manager: io::FileManager,
         git_manager: Option<io::GitManager>,
         builder: io::HtmlBuilder,
+        recent: RecentNotes,
+        visits: io::Visits,
+        collections: io::Collections,
         styles: ui::UiStyles,
-        stats_show: StatsShow,
+        collation: data::Collation,
+        config: &crate::Config,
     ) -> Self {
         let mut res = Self {
             local_stats: data::EnvironmentStats::new_with_filter(&index, data::Filter::default()),
             global_stats: data::EnvironmentStats::new_with_filter(&index, data::Filter::default()),
             index: index.clone(),
+            search: None,
             styles,
+            tag_styles: ui::TagStyles::new(config),
+            graph_builder: io::GraphBuilder::new(config, builder.vault_path().to_path_buf()),
             builder,
+            recent,
+            visits,
+            collections,
+            staleness: data::Staleness::new(config),
+            stale: std::collections::HashSet::new(),
+            export_profiles: io::ExportProfiles::new(config),
+            print_export: false,
             manager,
             git_manager,
             filter_area: TextArea::default(),
@@ -109,45 +168,68 @@ impl SelectScreen {
             any_conditions: false,
             sorting: data::SortingMode::Name,
             sorting_asc: true,
+            collation,
             selected: 0,
-            stats_show,
+            stats_show: config.stats_show.clone(),
+            default_filter: config.default_filter.clone().unwrap_or_default(),
+            default_sorting: (config.default_sorting, config.default_sorting_ascending),
+            excluded_tags: config.excluded_tags.clone(),
+            scope: Vec::new(),
+            suggestions_path: std::path::PathBuf::new(),
+            suggestions: Vec::new(),
+            suggestions_selected: 0,
         };
 
-        res.local_stats.sort(index, data::SortingMode::Name, true);
-
-        res.style_text_area();
+        res.reset_filter();
 
         res
     }
 
+    /// Replaces the filter and the sorting by the default ones of the config.
+    fn reset_filter(&mut self) {
+        self.filter_area = TextArea::from([self.default_filter.clone()]);
+        self.filter_area.move_cursor(tui_textarea::CursorMove::End);
+        self.style_text_area();
+
+        self.filter(self.filter_from_input());
+        let (sorting, ascending) = self.default_sorting;
+        self.set_mode_and_maybe_sort(sorting, ascending);
+    }
+
     /// Styling of TextArea extracted from constructor to keep it clean.
     fn style_text_area(&mut self) {
         // === Filter ===
 
         // The actual title
-        let title_top = Line::from(vec![
-            Span::styled("F", self.styles.hotkey_style),
-            Span::styled("ilter", self.styles.title_style),
-        ])
+        let title_top = Line::from(ui::tr_hotkeys(
+            "select-filter-title",
+            self.styles.hotkey_style,
+            self.styles.title_style,
+        ))
         .left_aligned();
 
         // The hotkey instructions at the bottom.
-        let instructions = Line::from(vec![
-            Span::styled("C", self.styles.hotkey_style),
-            Span::styled("lear filter", self.styles.text_style),
-        ])
+        // Only offer to reset the filter if there is a default one
+        let instructions = Line::from(ui::tr_hotkeys(
+            if self.default_filter.trim().is_empty() {
+                "select-filter-hints"
+            } else {
+                "select-filter-hints-reset"
+            },
+            self.styles.hotkey_style,
+            self.styles.text_style,
+        ))
         .right_aligned();
 
-        let instructions_bot = Line::from(vec![
-            Span::styled("A", self.styles.hotkey_style),
-            Span::styled(
-                if self.any_conditions { "ny" } else { "ll" },
-                self.styles.text_style,
-            ),
-            Span::styled(" Conditions──", self.styles.text_style),
-            Span::styled("H", self.styles.hotkey_style),
-            Span::styled("elp", self.styles.text_style),
-        ])
+        let instructions_bot = Line::from(ui::tr_hotkeys(
+            if self.any_conditions {
+                "select-filter-hints-any"
+            } else {
+                "select-filter-hints-all"
+            },
+            self.styles.hotkey_style,
+            self.styles.text_style,
+        ))
         .right_aligned();
 
         // Apply default self.styles to the filter area
@@ -157,7 +239,7 @@ impl SelectScreen {
             .set_cursor_line_style(self.styles.input_style);
 
         self.filter_area.set_block(
-            Block::bordered()
+            ui::bordered()
                 .title_top(title_top)
                 .title_top(instructions)
                 .title_bottom(instructions_bot),
@@ -166,7 +248,7 @@ impl SelectScreen {
         // === Create ===
         // The title
         let title_top = block::Title::from(Line::from(vec![Span::styled(
-            "Enter note name...",
+            ui::tr("prompt-name"),
             self.styles.title_style,
         )]));
 
@@ -176,7 +258,7 @@ impl SelectScreen {
         self.name_area
             .set_cursor_line_style(self.styles.input_style);
 
-        self.name_area.set_block(Block::bordered().title(title_top));
+        self.name_area.set_block(ui::bordered().title(title_top));
     }
 
     /// Sets the title & content of the name_area block
@@ -186,7 +268,7 @@ impl SelectScreen {
             self.styles.title_style,
         )]));
 
-        self.name_area.set_block(Block::bordered().title(title_top));
+        self.name_area.set_block(ui::bordered().title(title_top));
         // it is assumed the buffer is empty so far
         if let Some(content) = content {
             self.name_area.insert_str(content);
@@ -216,35 +298,199 @@ impl SelectScreen {
             .first()
             .map(|l| data::Filter::new(l, self.any_conditions))
             .unwrap_or_default()
+            .with_recent(&self.recent.borrow())
+            .with_visits(&self.visits)
+            .with_excluded_tags(&self.excluded_tags)
+            .with_collections(&self.collections)
+            .with_scope(&self.scope_filters())
+    }
+
+    /// Returns the name of the export profile attached to the current content of the filter area, if any.
+    fn export_profile(&self) -> Option<&str> {
+        self.filter_area
+            .lines()
+            .first()
+            .and_then(|filter| self.export_profiles.for_filter(filter))
+    }
+
+    /// Returns the filters of the current scope, with the current notes of the collections they look in.
+    fn scope_filters(&self) -> Vec<data::Filter> {
+        self.scope
+            .iter()
+            .map(|(_label, filter)| filter.clone().with_collections(&self.collections))
+            .collect()
+    }
+
+    /// Shows the notes of the collection with the given name, replacing the filter.
+    pub fn show_collection(&mut self, name: &str) {
+        self.filter_area = TextArea::from([format!("in:{}", name)]);
+        self.filter_area.move_cursor(tui_textarea::CursorMove::End);
+        self.style_text_area();
+        self.mode = SelectMode::Select;
+        self.filter(self.filter_from_input());
+    }
+
+    /// Updates the stats after notes were added to or removed from collections, if the filter or the scope looks in any.
+    pub fn collections_changed(&mut self) {
+        if self
+            .scope_filters()
+            .iter()
+            .chain(std::iter::once(&self.filter_from_input()))
+            .any(|filter| filter.uses_collections())
+        {
+            self.refresh_env_stats();
+        }
+    }
+
+    /// Narrows the scope of all statistics down to the notes matching the given filter and clears the filter area.
+    fn push_scope(&mut self, label: String, filter: data::Filter) {
+        self.scope.push((label, filter));
+        let _ = super::extract_string_and_clear(&mut self.filter_area);
+        self.refresh_env_stats();
+        self.selected = 0;
     }
 
     /// Reloads the displayed statistics, showing stats for only those elements of the index matching the specified filter.
     /// Every filtering neccessarily triggers a non-stable resort.
+    /// Searches through the full text of notes in the background, so the notes are listed as they are found.
     fn filter(&mut self, filter: data::Filter) {
-        // actual filtering
-        self.local_stats = data::EnvironmentStats::new_with_filter(&self.index, filter);
+        // a search for the previous filter is cancelled
+        self.search = None;
+        // actual filtering, only recalculating the stats of notes entering or leaving the filter
+        match filter.full_text.clone() {
+            Some(text) => {
+                let pending = self.local_stats.refilter_streaming(&self.index, filter);
+                if !pending.is_empty() {
+                    self.search = Some(io::ContentSearch::start(text, pending));
+                }
+            }
+            None => self.local_stats.refilter(&self.index, filter),
+        }
         // reset sorting
         self.sorting_asc = false;
         self.sorting = data::SortingMode::Score;
-        self.local_stats
-            .sort(self.index.clone(), self.sorting, self.sorting_asc);
+        self.local_stats.sort(
+            self.index.clone(),
+            self.sorting,
+            self.sorting_asc,
+            &self.collation,
+        );
         // on a new filter, select the first element
         self.selected = 0;
     }
 
+    /// Adds the notes found by the search through the full text of notes since the last call to the local stats.
+    /// Returns wether the search made progress, so it needs to be drawn.
+    pub fn poll_search(&mut self) -> bool {
+        let Some(search) = &mut self.search else {
+            return false;
+        };
+        let progress = search.progress();
+        let found = search.take_found();
+        let finished = search.is_finished();
+        let progressed = !found.is_empty() || finished || search.progress() != progress;
+
+        if !found.is_empty() {
+            self.local_stats.text_found(&self.index, &found);
+            self.local_stats.sort(
+                self.index.clone(),
+                self.sorting,
+                self.sorting_asc,
+                &self.collation,
+            );
+        }
+        if finished {
+            self.local_stats.search_finished();
+            self.search = None;
+        }
+        progressed
+    }
+
     /// Re-creates the global and local stats from the index.
     /// To be performed after file management operations.
     pub fn refresh_env_stats(&mut self) {
-        // Refresh global stats
-        self.global_stats =
-            data::EnvironmentStats::new_with_filter(&self.index, data::Filter::default());
+        // the new local stats are complete
+        self.search = None;
+        // Refresh global stats, which cover the current scope
+        self.global_stats = data::EnvironmentStats::new_with_filter(
+            &self.index,
+            data::Filter::default().with_scope(&self.scope_filters()),
+        );
         // Refresh local stats
         self.local_stats =
             data::EnvironmentStats::new_with_filter(&self.index, self.filter_from_input());
 
         // Refresh sorting
-        self.local_stats
-            .sort(self.index.clone(), self.sorting, self.sorting_asc);
+        self.local_stats.sort(
+            self.index.clone(),
+            self.sorting,
+            self.sorting_asc,
+            &self.collation,
+        );
+
+        // Refresh stale notes
+        self.stale = self
+            .staleness
+            .stale_notes(&self.index.borrow(), chrono::Local::now())
+            .into_iter()
+            .map(|stale| stale.id)
+            .collect();
+    }
+
+    /// Updates the global and local stats after the links or contents of the notes with the given ids changed, see [`data::NoteIndex::take_touched`].
+    /// Unlike refreshing them, only the stats of these notes and their neighbours are recalculated.
+    pub fn notes_changed(&mut self, ids: &[String]) {
+        self.global_stats.notes_changed(&self.index, ids);
+        self.local_stats.notes_changed(&self.index, ids);
+
+        // Refresh sorting
+        self.local_stats.sort(
+            self.index.clone(),
+            self.sorting,
+            self.sorting_asc,
+            &self.collation,
+        );
+
+        // Check the changed notes again
+        let index = self.index.borrow();
+        let now = chrono::Local::now();
+        for id in ids {
+            match index
+                .get(id)
+                .and_then(|note| self.staleness.check(id, note, &index, now))
+            {
+                Some(_) => self.stale.insert(id.clone()),
+                None => self.stale.remove(id),
+            };
+        }
+    }
+
+    /// Scans the newly created note at the given path for mentions of other notes.
+    /// If there are any, switches to the link suggestion mode so the user can choose which of them to turn into links.
+    fn suggest_links(&mut self, path: std::path::PathBuf) -> error::Result<()> {
+        let content = std::fs::read_to_string(&path)?;
+
+        let id = path
+            .file_stem()
+            .map(|stem| data::name_to_id(&stem.to_string_lossy()))
+            .unwrap_or_default();
+
+        self.suggestions = self
+            .index
+            .borrow()
+            .link_suggestions(&id, &content)?
+            .into_iter()
+            // all suggestions are accepted by default
+            .map(|suggestion| (suggestion, true))
+            .collect();
+        self.suggestions_selected = 0;
+        self.suggestions_path = path;
+
+        if !self.suggestions.is_empty() {
+            self.mode = SelectMode::LinkSuggestions;
+        }
+
+        Ok(())
     }
 
     /// Sets a new sorting mode and direction.
@@ -259,8 +505,12 @@ impl SelectScreen {
         if new_mode != self.sorting || new_asc != self.sorting_asc {
             self.sorting = new_mode;
             self.sorting_asc = new_asc;
-            self.local_stats
-                .sort(self.index.clone(), self.sorting, self.sorting_asc);
+            self.local_stats.sort(
+                self.index.clone(),
+                self.sorting,
+                self.sorting_asc,
+                &self.collation,
+            );
             self.selected = 0;
         }
     }
@@ -287,6 +537,46 @@ impl super::Screen for SelectScreen {
                 KeyCode::Char('s' | 'S') => {
                     self.mode = SelectMode::SubmenuSorting;
                 }
+                // X: Got to export submenu
+                KeyCode::Char('x' | 'X') => {
+                    self.mode = SelectMode::SubmenuExport;
+                }
+                // !: Review the files that could not be loaded
+                KeyCode::Char('!') if self.index.borrow().is_degraded() => {
+                    return Ok(ui::Message::OpenFailures);
+                }
+                // P: Review the problems found in the notes
+                KeyCode::Char('p' | 'P') => {
+                    return Ok(ui::Message::OpenProblems(None));
+                }
+                // T: Browse the tags of the vault and where they are used
+                KeyCode::Char('t' | 'T') => {
+                    return Ok(ui::Message::OpenTagUsage(None));
+                }
+                // B: Review the links to notes that do not exist
+                KeyCode::Char('b' | 'B') => {
+                    return Ok(ui::Message::OpenBrokenLinks);
+                }
+                // N: Review the notes not modified for too long
+                KeyCode::Char('n' | 'N') => {
+                    return Ok(ui::Message::OpenStaleNotes);
+                }
+                // @: Browse the people mentioned in the vault and where they are mentioned
+                KeyCode::Char('@') => {
+                    return Ok(ui::Message::OpenPeople(None));
+                }
+                // I: Manage the collections and add the selected note to them
+                KeyCode::Char('i' | 'I') => {
+                    return Ok(ui::Message::OpenCollections(
+                        self.local_stats
+                            .get_selected(self.selected)
+                            .map(|env_stats| env_stats.id.clone()),
+                    ));
+                }
+                // ~: Search notes by meaning
+                KeyCode::Char('~') => {
+                    return Ok(ui::Message::OpenSemanticSearch);
+                }
                 // F: or /: Go to filter mode
                 KeyCode::Char('f' | 'F' | '/') => {
                     self.mode = SelectMode::Filter;
@@ -295,10 +585,58 @@ impl super::Screen for SelectScreen {
                 KeyCode::Char('?' | 'h' | 'H') => {
                     self.mode = SelectMode::FilterHelp;
                 }
+                // D: Show distributions
+                KeyCode::Char('d' | 'D') => {
+                    self.mode = SelectMode::Distributions;
+                }
                 // C: Clear filter
                 KeyCode::Char('c' | 'C') => {
                     let _ = super::extract_string_and_clear(&mut self.filter_area);
-                    self.filter(data::Filter::default());
+                    self.filter(self.filter_from_input());
+                }
+                // O: Narrow the scope down to the folder of the selected note
+                KeyCode::Char('o' | 'O') => {
+                    let folder = self
+                        .local_stats
+                        .get_selected(self.selected)
+                        .and_then(|env_stats| {
+                            let index = self.index.borrow();
+                            index.get(&env_stats.id).and_then(|note| {
+                                index
+                                    .relative_path(&note.path)
+                                    .parent()
+                                    .map(std::path::Path::to_path_buf)
+                            })
+                        })
+                        .filter(|folder| !folder.as_os_str().is_empty());
+
+                    if let Some(folder) = folder {
+                        let label = folder
+                            .components()
+                            .map(|component| component.as_os_str().to_string_lossy())
+                            .join("/");
+                        self.push_scope(label, data::Filter::folder(folder));
+                    }
+                }
+                // Z: Narrow the scope down to the notes matching the filter
+                KeyCode::Char('z' | 'Z') => {
+                    let filter_string = self.filter_area.lines().first().cloned();
+                    if let Some(filter_string) =
+                        filter_string.filter(|filter_string| !filter_string.trim().is_empty())
+                    {
+                        let filter = data::Filter::new(&filter_string, self.any_conditions);
+                        self.push_scope(filter_string.trim().to_owned(), filter);
+                    }
+                }
+                // U: Widen the scope again
+                KeyCode::Char('u' | 'U') if !self.scope.is_empty() => {
+                    self.scope.pop();
+                    self.refresh_env_stats();
+                    self.selected = 0;
+                }
+                // R: Reset to the default filter and sorting
+                KeyCode::Char('r' | 'R') => {
+                    self.reset_filter();
                 }
                 // T: Change all/any words requirement
                 KeyCode::Char('a' | 'A') => {
@@ -332,11 +670,16 @@ impl super::Screen for SelectScreen {
                 KeyCode::Char('v' | 'V') => {
                     self.mode = SelectMode::Select;
                     if let Some(env_stats) = self.local_stats.get_selected(self.selected) {
-                        if let Some(note) = self.index.borrow().get(&env_stats.id) {
-                            self.builder.create_html(note, true)?;
+                        let primary = key.code == KeyCode::Char('v');
+                        // PDF files take a while to build, so the viewer is only opened once they are done
+                        if self.manager.viewer_type(primary) == crate::config::ViewerType::Pdf {
+                            return Ok(ui::Message::ViewPdf(env_stats.id.clone(), primary));
+                        }
+                        let index = self.index.borrow();
+                        if let Some(note) = index.get(&env_stats.id) {
+                            self.builder.create_html(note, Some(&index), true)?;
                             return Ok(ui::Message::OpenExternalCommand(Box::new(
-                                self.manager
-                                    .create_view_command(note, key.code == KeyCode::Char('v'))?,
+                                self.manager.create_view_command(note, primary)?,
                             )));
                         }
                     }
@@ -391,6 +734,16 @@ impl super::Screen for SelectScreen {
                     _ => {}
                 };
             }
+            SelectMode::Distributions => {
+                match key.code {
+                    // Escape or D: Back to main mode
+                    KeyCode::Esc | KeyCode::Char('c' | 'C' | 'd' | 'D') => {
+                        self.mode = SelectMode::Select;
+                    }
+                    // All other key events are ignored
+                    _ => {}
+                };
+            }
             // File mode: Wait for second input
             SelectMode::SubmenuFile => {
                 match key.code {
@@ -412,7 +765,7 @@ impl super::Screen for SelectScreen {
                     // N: Create note
                     KeyCode::Char('n' | 'N') => {
                         self.mode = SelectMode::Create;
-                        self.set_name_area("Enter name of new note...", None);
+                        self.set_name_area(&ui::tr("select-prompt-create"), None);
                     }
                     // R: Rename note
                     KeyCode::Char('r' | 'R') => {
@@ -430,12 +783,73 @@ impl super::Screen for SelectScreen {
                                     .map(|note| note.name.clone())
                             });
 
-                        self.set_name_area("Enter new name of note...", name);
+                        self.set_name_area(&ui::tr("prompt-rename"), name);
                     }
                     // M: Move note
                     KeyCode::Char('m' | 'M') => {
                         self.mode = SelectMode::Move;
-                        self.set_name_area("Enter new location relative to vault...", None);
+                        self.set_name_area(&ui::tr("prompt-move"), None);
+                    }
+                    // E: Send note by mail
+                    KeyCode::Char('e' | 'E') => {
+                        self.mode = SelectMode::Email;
+                        self.set_name_area(&ui::tr("select-prompt-email"), None);
+                    }
+                    // X: Export statistics of the current environment
+                    KeyCode::Char('x' | 'X') => {
+                        self.mode = SelectMode::Select;
+                        self.manager.export_stats(
+                            self.index.clone(),
+                            &self.local_stats,
+                            std::path::Path::new("rucola-statistics.csv"),
+                        )?;
+                    }
+                    // G: Export the link graph of the current environment
+                    KeyCode::Char('g' | 'G') => {
+                        self.mode = SelectMode::Select;
+                        self.graph_builder.create_graph(
+                            &self.index.borrow(),
+                            &self.local_stats.ids(),
+                            std::path::Path::new("rucola-graph.dot"),
+                        )?;
+                    }
+                    // B: Back up the vault
+                    KeyCode::Char('b' | 'B') => {
+                        self.mode = SelectMode::Select;
+                        return Ok(ui::Message::CreateBackup);
+                    }
+                    // W: Synchronize with the WebDAV server
+                    KeyCode::Char('w' | 'W') => {
+                        self.mode = SelectMode::Select;
+                        return Ok(ui::Message::Sync);
+                    }
+                    // P: Publish the HTML files
+                    KeyCode::Char('p' | 'P') => {
+                        self.mode = SelectMode::Select;
+                        return Ok(ui::Message::Publish);
+                    }
+                    // T: Open trash
+                    KeyCode::Char('t' | 'T') => {
+                        self.mode = SelectMode::Select;
+                        return Ok(ui::Message::OpenTrash);
+                    }
+                    // O: Reveal the selected note in the file manager
+                    KeyCode::Char('o' | 'O') => {
+                        self.mode = SelectMode::Select;
+                        if let Some(path) =
+                            self.local_stats
+                                .get_selected(self.selected)
+                                .and_then(|env_stats| {
+                                    self.index
+                                        .borrow()
+                                        .get(&env_stats.id)
+                                        .map(|note| note.path.clone())
+                                })
+                        {
+                            return Ok(ui::Message::OpenExternalCommand(Box::new(
+                                self.manager.create_reveal_command(&path)?,
+                            )));
+                        }
                     }
                     // Back to select mode
                     KeyCode::Esc => {
@@ -444,8 +858,87 @@ impl super::Screen for SelectScreen {
                     _ => {}
                 }
             }
+            // Export mode: Wait for second input
+            SelectMode::SubmenuExport => match key.code {
+                // H: Build the HTML files of all notes in the current environment
+                KeyCode::Char('h' | 'H') => {
+                    self.mode = SelectMode::Select;
+                    return Ok(ui::Message::BatchExport(
+                        if self.print_export {
+                            io::ExportKind::PrintHtml
+                        } else {
+                            io::ExportKind::Html
+                        },
+                        self.local_stats.ids(),
+                    ));
+                }
+                // P: Build the PDF files of all notes in the current environment
+                KeyCode::Char('p' | 'P') => {
+                    self.mode = SelectMode::Select;
+                    return Ok(ui::Message::BatchExport(
+                        if self.print_export {
+                            io::ExportKind::PrintPdf
+                        } else {
+                            io::ExportKind::Pdf
+                        },
+                        self.local_stats.ids(),
+                    ));
+                }
+                // C: Copy all notes in the current environment to a folder
+                KeyCode::Char('c' | 'C') => {
+                    self.mode = SelectMode::ExportCopy;
+                    self.set_name_area(&ui::tr("select-prompt-copy"), None);
+                }
+                // Z: Zip all notes in the current environment
+                KeyCode::Char('z' | 'Z') => {
+                    self.mode = SelectMode::ExportZip;
+                    self.set_name_area(&ui::tr("select-prompt-zip"), None);
+                }
+                // A: Write the flashcards of all notes in the current environment to an Anki import file
+                KeyCode::Char('a' | 'A') => {
+                    self.mode = SelectMode::ExportAnki;
+                    self.set_name_area(&ui::tr("select-prompt-anki"), None);
+                }
+                // S: Build reveal.js slide decks of all notes in the current environment
+                KeyCode::Char('s' | 'S') => {
+                    self.mode = SelectMode::Select;
+                    return Ok(ui::Message::BatchExport(
+                        io::ExportKind::RevealSlides,
+                        self.local_stats.ids(),
+                    ));
+                }
+                // T: Build typst slide decks of all notes in the current environment
+                KeyCode::Char('t' | 'T') => {
+                    self.mode = SelectMode::Select;
+                    return Ok(ui::Message::BatchExport(
+                        io::ExportKind::TypstSlides,
+                        self.local_stats.ids(),
+                    ));
+                }
+                // V: Switch between regular and print-friendly HTML and PDF files
+                KeyCode::Char('v' | 'V') => {
+                    self.print_export = !self.print_export;
+                }
+                // R: Export the notes with the export profile attached to the filter
+                KeyCode::Char('r' | 'R') => {
+                    if let Some(profile) = self.export_profile().map(str::to_owned) {
+                        self.mode = SelectMode::Select;
+                        return Ok(ui::Message::RunExportProfile(profile));
+                    }
+                }
+                KeyCode::Esc | KeyCode::Char('x' | 'X') => {
+                    self.mode = SelectMode::Select;
+                }
+                _ => {}
+            },
             // Modes that require input in the text box.
-            SelectMode::Create | SelectMode::Rename | SelectMode::Move => {
+            SelectMode::Create
+            | SelectMode::Rename
+            | SelectMode::Move
+            | SelectMode::ExportCopy
+            | SelectMode::ExportZip
+            | SelectMode::ExportAnki
+            | SelectMode::Email => {
                 match key.code {
                     // Escape: Back to main mode, clear the buffer
                     KeyCode::Esc => {
@@ -460,16 +953,18 @@ impl super::Screen for SelectScreen {
                         match mode {
                             SelectMode::Create => {
                                 // Create & register the note
-                                self.manager.create_note_file(
+                                let path = self.manager.create_note_file(
                                     &super::extract_string_and_clear(&mut self.name_area)
                                         .ok_or_else(|| {
-                                            error::RucolaError::Input(String::from(
-                                                "New note may not be empty.",
+                                            error::RucolaError::Input(ui::tr(
+                                                "select-error-create-empty",
                                             ))
                                         })?,
                                 )?;
                                 // if successfull, refresh the ui
                                 self.refresh_env_stats();
+                                // then propose links to already existing notes
+                                self.suggest_links(path)?;
                             }
                             SelectMode::Rename => {
                                 // Get the id of currently selected, then delegate to note_file::rename.
@@ -481,9 +976,9 @@ impl super::Screen for SelectScreen {
                                         &env_stats.id,
                                         super::extract_string_and_clear(&mut self.name_area)
                                             .ok_or_else(|| {
-                                                error::RucolaError::Input(
-                                                    "New name is empty.".to_string(),
-                                                )
+                                                error::RucolaError::Input(ui::tr(
+                                                    "error-name-empty",
+                                                ))
                                             })?,
                                     )?;
                                     // if successfull, refresh the ui
@@ -500,15 +995,56 @@ impl super::Screen for SelectScreen {
                                         &env_stats.id,
                                         super::extract_string_and_clear(&mut self.name_area)
                                             .ok_or_else(|| {
-                                                error::RucolaError::Input(
-                                                    "Move target is empty.".to_string(),
-                                                )
+                                                error::RucolaError::Input(ui::tr(
+                                                    "select-error-move-empty",
+                                                ))
                                             })?,
                                     )?;
                                     // if successfull, refresh the ui
                                     self.refresh_env_stats();
                                 }
                             }
+                            SelectMode::ExportCopy
+                            | SelectMode::ExportZip
+                            | SelectMode::ExportAnki => {
+                                let target = std::path::PathBuf::from(
+                                    super::extract_string_and_clear(&mut self.name_area)
+                                        .ok_or_else(|| {
+                                            error::RucolaError::Input(ui::tr(
+                                                "select-error-export-empty",
+                                            ))
+                                        })?,
+                                );
+                                return Ok(ui::Message::BatchExport(
+                                    match mode {
+                                        SelectMode::ExportCopy => io::ExportKind::Copy(target),
+                                        SelectMode::ExportZip => io::ExportKind::Zip(target),
+                                        _ => io::ExportKind::Anki(target),
+                                    },
+                                    self.local_stats.ids(),
+                                ));
+                            }
+                            SelectMode::Email => {
+                                let recipients =
+                                    super::extract_string_and_clear(&mut self.name_area)
+                                        .ok_or_else(|| {
+                                            error::RucolaError::Input(ui::tr(
+                                                "select-error-email-empty",
+                                            ))
+                                        })?;
+                                if let Some(env_stats) =
+                                    self.local_stats.get_selected(self.selected)
+                                {
+                                    return Ok(ui::Message::EmailNote(
+                                        env_stats.id.clone(),
+                                        recipients
+                                            .split(|c: char| c == ',' || c.is_whitespace())
+                                            .filter(|recipient| !recipient.is_empty())
+                                            .map(str::to_owned)
+                                            .collect(),
+                                    ));
+                                }
+                            }
                             _ => {
                                 //This should NOT happen
                             }
@@ -521,6 +1057,49 @@ impl super::Screen for SelectScreen {
                     }
                 };
             }
+            SelectMode::LinkSuggestions => match key.code {
+                // Navigate the suggestions
+                KeyCode::Char('j' | 'J') | KeyCode::Down => {
+                    self.suggestions_selected = self
+                        .suggestions_selected
+                        .saturating_add(1)
+                        .min(self.suggestions.len().saturating_sub(1));
+                }
+                KeyCode::Char('k' | 'K') | KeyCode::Up => {
+                    self.suggestions_selected = self.suggestions_selected.saturating_sub(1);
+                }
+                // Toggle the selected suggestion
+                KeyCode::Char(' ') => {
+                    if let Some((_, accepted)) = self.suggestions.get_mut(self.suggestions_selected)
+                    {
+                        *accepted = !*accepted;
+                    }
+                }
+                // Toggle all suggestions at once
+                KeyCode::Char('a' | 'A') => {
+                    let accept = !self.suggestions.iter().all(|(_, accepted)| *accepted);
+                    for (_, accepted) in self.suggestions.iter_mut() {
+                        *accepted = accept;
+                    }
+                }
+                // Insert all accepted links
+                KeyCode::Enter => {
+                    self.mode = SelectMode::Select;
+                    let accepted = std::mem::take(&mut self.suggestions)
+                        .into_iter()
+                        .filter(|(_, accepted)| *accepted)
+                        .map(|(suggestion, _)| suggestion)
+                        .collect_vec();
+                    self.manager
+                        .insert_links(&self.suggestions_path, &accepted)?;
+                }
+                // Dismiss all suggestions
+                KeyCode::Esc => {
+                    self.mode = SelectMode::Select;
+                    self.suggestions.clear();
+                }
+                _ => {}
+            },
             SelectMode::SubmenuGit => match key.code {
                 KeyCode::Char('c' | 'C') => {
                     self.mode = SelectMode::Select;
@@ -534,6 +1113,11 @@ impl super::Screen for SelectScreen {
                 KeyCode::Char('u' | 'U') => {
                     self.mode = SelectMode::Select;
                 }
+                // H: Open history
+                KeyCode::Char('h' | 'H') => {
+                    self.mode = SelectMode::Select;
+                    return Ok(ui::Message::OpenHistory);
+                }
                 KeyCode::Esc | KeyCode::Char('g' | 'G') => {
                     self.mode = SelectMode::Select;
                 }
@@ -573,6 +1157,22 @@ impl super::Screen for SelectScreen {
                     self.set_mode_and_maybe_sort(data::SortingMode::Broken, false);
                     self.mode = SelectMode::Select;
                 }
+                KeyCode::Char('d' | 'D') => {
+                    self.set_mode_and_maybe_sort(data::SortingMode::Date, false);
+                    self.mode = SelectMode::Select;
+                }
+                KeyCode::Char('e' | 'E') => {
+                    self.set_mode_and_maybe_sort(data::SortingMode::Created, false);
+                    self.mode = SelectMode::Select;
+                }
+                KeyCode::Char('m' | 'M') => {
+                    self.set_mode_and_maybe_sort(data::SortingMode::Modified, false);
+                    self.mode = SelectMode::Select;
+                }
+                KeyCode::Char('v' | 'V') => {
+                    self.set_mode_and_maybe_sort(data::SortingMode::Visits, false);
+                    self.mode = SelectMode::Select;
+                }
                 KeyCode::Char('r' | 'R') => {
                     self.set_mode_and_maybe_sort(None, !self.sorting_asc);
                     self.mode = SelectMode::Select;
@@ -603,12 +1203,28 @@ impl super::Screen for SelectScreen {
         let [title_area, global_stats_area, local_stats_area, filter_area, table_area] =
             vertical.areas(area);
 
-        // Title
-        let title = Line::from(vec![Span::styled(
+        // Title, followed by the breadcrumb of the scope
+        let mut title = vec![Span::styled(
             self.manager.get_vault_title(),
             self.styles.title_style,
-        )])
-        .alignment(Alignment::Center);
+        )];
+        for (label, _filter) in &self.scope {
+            title.push(Span::styled(" › ", self.styles.text_style));
+            title.push(Span::styled(label.as_str(), self.styles.subtitle_style));
+        }
+        let title = Line::from(title).alignment(Alignment::Center);
+
+        // Instructions to change the scope
+        let scope_instructions = Line::from(ui::tr_hotkeys(
+            if self.scope.is_empty() {
+                "select-scope-hints"
+            } else {
+                "select-scope-hints-up"
+            },
+            self.styles.hotkey_style,
+            self.styles.text_style,
+        ))
+        .alignment(Alignment::Left);
 
         let version = Line::from(vec![Span::styled(
             format!("rucola v{}", env!("CARGO_PKG_VERSION")),
@@ -620,80 +1236,92 @@ impl super::Screen for SelectScreen {
         let global_stats =
             self.global_stats
                 .to_global_stats_table(&self.styles)
-                .block(Block::bordered().title(style::Styled::set_style(
-                    "Global Statistics",
+                .block(ui::bordered().title(style::Styled::set_style(
+                    ui::tr(if self.scope.is_empty() {
+                        "select-title-global"
+                    } else {
+                        "select-title-scope"
+                    }),
                     self.styles.title_style,
                 )));
 
         let local_stats = self
             .local_stats
             .to_local_stats_table(&self.global_stats, &self.styles)
-            .block(Block::bordered().title(style::Styled::set_style(
-                "Local Statistics",
-                self.styles.title_style,
-            )));
+            .block(
+                ui::bordered()
+                    .title(style::Styled::set_style(
+                        ui::tr("select-title-local"),
+                        self.styles.title_style,
+                    ))
+                    .title(
+                        Line::styled(
+                            self.search
+                                .as_ref()
+                                .map(|search| {
+                                    ui::tr_args(
+                                        "select-searching",
+                                        &[("percent", search.progress().into())],
+                                    )
+                                })
+                                .unwrap_or_default(),
+                            self.styles.text_style,
+                        )
+                        .right_aligned(),
+                    ),
+            );
 
         // === Table Area ===
 
-        // Generate state from selected element
+        // Calculate the first visible row
+        let offset = self
+            .selected
+            // try to keep element at above 1/3rd of the total height
+            .saturating_sub(table_area.height as usize / 3)
+            .min(
+                // but when reaching the end of the list, still scroll down
+                self.local_stats
+                    .len()
+                    // correct for table edges
+                    .saturating_add(3)
+                    .saturating_sub(table_area.height as usize),
+            );
+
+        // Generate state from selected element, relative to the visible rows the table is made of
         let mut state = TableState::new()
-            .with_offset(
-                self.selected
-                    // try to keep element at above 1/3rd of the total height
-                    .saturating_sub(table_area.height as usize / 3)
-                    .min(
-                        // but when reaching the end of the list, still scroll down
-                        self.local_stats
-                            .len()
-                            // correct for table edges
-                            .saturating_add(3)
-                            .saturating_sub(table_area.height as usize),
-                    ),
-            )
             // In certain modes, show a selected element
             .with_selected(match self.mode {
                 SelectMode::Select
                 | SelectMode::Rename
                 | SelectMode::Move
+                | SelectMode::Email
                 | SelectMode::SubmenuFile
                 | SelectMode::SubmenuGit
-                | SelectMode::SubmenuSorting => Some(self.selected),
-                SelectMode::Filter | SelectMode::FilterHelp | SelectMode::Create => None,
+                | SelectMode::SubmenuSorting => Some(self.selected - offset),
+                SelectMode::Filter
+                | SelectMode::FilterHelp
+                | SelectMode::Create
+                | SelectMode::SubmenuExport
+                | SelectMode::ExportCopy
+                | SelectMode::ExportZip
+                | SelectMode::ExportAnki
+                | SelectMode::LinkSuggestions
+                | SelectMode::Distributions => None,
             });
 
         // Instructions at the bottom of the page
-        let instructions_bot_left = Line::from(vec![
-            Span::styled("J", self.styles.hotkey_style),
-            Span::styled("/", self.styles.text_style),
-            Span::styled("", self.styles.hotkey_style),
-            Span::styled(": Down──", self.styles.text_style),
-            Span::styled("K", self.styles.hotkey_style),
-            Span::styled("/", self.styles.text_style),
-            Span::styled("", self.styles.hotkey_style),
-            Span::styled(": Up──", self.styles.text_style),
-            Span::styled("L", self.styles.hotkey_style),
-            Span::styled("/", self.styles.text_style),
-            Span::styled("", self.styles.hotkey_style),
-            Span::styled("/", self.styles.text_style),
-            Span::styled("󰌑", self.styles.hotkey_style),
-            Span::styled(": Open──", self.styles.text_style),
-        ])
+        let instructions_bot_left = Line::from(ui::tr_hotkeys(
+            "select-hints-navigation",
+            self.styles.hotkey_style,
+            self.styles.text_style,
+        ))
         .left_aligned();
 
-        let instructions_bot_right = Line::from(vec![
-            Span::styled("E", self.styles.hotkey_style),
-            Span::styled("dit──", self.styles.text_style),
-            Span::styled("V", self.styles.hotkey_style),
-            Span::styled("iew──", self.styles.text_style),
-            Span::styled("S", self.styles.hotkey_style),
-            Span::styled("orting──", self.styles.text_style),
-            Span::styled("G", self.styles.hotkey_style),
-            Span::styled("it─", self.styles.text_style),
-            Span::styled("M", self.styles.hotkey_style),
-            Span::styled("anage Files──", self.styles.text_style),
-            Span::styled("Q", self.styles.hotkey_style),
-            Span::styled("uit", self.styles.text_style),
-        ])
+        let instructions_bot_right = Line::from(ui::tr_hotkeys(
+            "select-hints-actions",
+            self.styles.hotkey_style,
+            self.styles.text_style,
+        ))
         .right_aligned();
 
         let table_heading_key_style = if self.mode == SelectMode::SubmenuSorting {
@@ -705,54 +1333,62 @@ impl super::Screen for SelectScreen {
         // Finally generate the table from the generated row and width data
         let table = self
             .local_stats
-            .to_note_table(self.index.clone(), &self.styles)
+            .to_note_table(
+                self.index.clone(),
+                &self.styles,
+                &self.tag_styles,
+                &self.stale,
+                offset..offset + table_area.height as usize,
+            )
             // Add Headers
-            .header(Row::new(vec![
-                Line::from(vec![
-                    Span::styled("N", self.styles.subtitle_style),
-                    Span::styled("a", table_heading_key_style),
-                    Span::styled("me", self.styles.subtitle_style),
-                ]),
-                Line::from(vec![
-                    Span::styled("  ", self.styles.subtitle_style),
-                    Span::styled("W", table_heading_key_style),
-                    Span::styled("ords", self.styles.subtitle_style),
-                ]),
-                Line::from(vec![
-                    Span::styled("  ", self.styles.subtitle_style),
-                    Span::styled("C", table_heading_key_style),
-                    Span::styled("hars", self.styles.subtitle_style),
-                ]),
-                Line::from(vec![
-                    Span::styled("Global", self.styles.subtitle_style),
-                    Span::styled("O", table_heading_key_style),
-                    Span::styled("ut", self.styles.subtitle_style),
-                ]),
-                Line::from(vec![
-                    Span::styled("LocalO", self.styles.subtitle_style),
-                    Span::styled("u", table_heading_key_style),
-                    Span::styled("t", self.styles.subtitle_style),
-                ]),
-                Line::from(vec![
-                    Span::styled("Global", self.styles.subtitle_style),
-                    Span::styled("I", table_heading_key_style),
-                    Span::styled("n", self.styles.subtitle_style),
-                ]),
-                Line::from(vec![
-                    Span::styled("LocalI", self.styles.subtitle_style),
-                    Span::styled("n", table_heading_key_style),
-                ]),
-            ]))
+            .header({
+                let headings = [
+                    ("", "select-column-name"),
+                    ("  ", "select-column-words"),
+                    ("  ", "select-column-chars"),
+                    ("", "select-column-global-out"),
+                    ("", "select-column-local-out"),
+                    ("", "select-column-global-in"),
+                    ("", "select-column-local-in"),
+                ]
+                .map(|(indent, id)| {
+                    let mut heading = vec![Span::styled(indent, self.styles.subtitle_style)];
+                    heading.extend(ui::tr_hotkeys(
+                        id,
+                        table_heading_key_style,
+                        self.styles.subtitle_style,
+                    ));
+                    Line::from(heading)
+                });
+                // the rows of screen readers have a single column, see `to_note_table`
+                if ui::screen_reader() {
+                    Row::new([Line::from(
+                        Itertools::intersperse(
+                            headings.into_iter().map(|heading| heading.spans),
+                            vec![Span::styled(ui::separator(), self.styles.subtitle_style)],
+                        )
+                        .flatten()
+                        .collect::<Vec<_>>(),
+                    )])
+                } else {
+                    Row::new(headings)
+                }
+            })
+            .highlight_symbol(ui::selection_marker())
             .row_highlight_style(self.styles.selected_style)
             // Add Instructions and a title
             .block(
-                Block::bordered()
-                    .title_top(style::Styled::set_style("Notes", self.styles.title_style))
+                ui::bordered()
+                    .title_top(style::Styled::set_style(
+                        ui::tr("select-title-notes"),
+                        self.styles.title_style,
+                    ))
                     .title_bottom(instructions_bot_left)
                     .title_bottom(instructions_bot_right),
             );
 
         // === Rendering ===
+        Widget::render(scope_instructions, title_area, buf);
         Widget::render(title, title_area, buf);
         Widget::render(version, title_area, buf);
 
@@ -765,37 +1401,76 @@ impl super::Screen for SelectScreen {
 
         // Render possible pop-ups
         match self.mode {
-            SelectMode::SubmenuFile | SelectMode::SubmenuSorting | SelectMode::SubmenuGit => {
+            SelectMode::SubmenuFile
+            | SelectMode::SubmenuSorting
+            | SelectMode::SubmenuGit
+            | SelectMode::SubmenuExport => {
                 let mut contents = if self.mode == SelectMode::SubmenuFile {
                     vec![
-                        ("N", "New note"),
-                        ("R", "Rename selected note"),
-                        ("M", "Move selected note"),
-                        ("D", "Delete selected note"),
+                        ("N", "select-file-new"),
+                        ("R", "select-file-rename"),
+                        ("M", "select-file-move"),
+                        ("D", "select-file-delete"),
+                        ("X", "select-file-statistics"),
+                        ("G", "select-file-graph"),
+                        ("T", "select-file-trash"),
+                        ("B", "select-file-backup"),
+                        ("W", "select-file-sync"),
+                        ("P", "select-file-publish"),
+                        ("E", "select-file-email"),
+                        ("O", "select-file-reveal"),
                     ]
                 } else if self.mode == SelectMode::SubmenuGit {
                     vec![
-                        ("A", "Add All"),
-                        ("C", "Commit"),
-                        ("P", "Push"),
-                        ("F", "Fetch"),
-                        ("U", "Pull"),
+                        ("A", "select-git-add"),
+                        ("C", "select-git-commit"),
+                        ("P", "select-git-push"),
+                        ("F", "select-git-fetch"),
+                        ("U", "select-git-pull"),
+                        ("H", "select-git-history"),
+                    ]
+                } else if self.mode == SelectMode::SubmenuExport {
+                    vec![
+                        if self.print_export {
+                            ("H", "select-export-print-html")
+                        } else {
+                            ("H", "select-export-html")
+                        },
+                        if self.print_export {
+                            ("P", "select-export-print-pdf")
+                        } else {
+                            ("P", "select-export-pdf")
+                        },
+                        ("C", "select-export-copy"),
+                        ("Z", "select-export-zip"),
+                        ("A", "select-export-anki"),
+                        ("S", "select-export-reveal-slides"),
+                        ("T", "select-export-typst-slides"),
+                        if self.print_export {
+                            ("V", "select-export-regular")
+                        } else {
+                            ("V", "select-export-print")
+                        },
                     ]
                 } else {
                     vec![
-                        ("A", "Sort by name"),
-                        ("W", "Sort by words"),
-                        ("C", "Sort by characters"),
-                        ("O", "Sort by global outlinks"),
-                        ("U", "Sort by local outlinks"),
-                        ("I", "Sort by global inlinks"),
-                        ("N", "Sort by local inlinks"),
-                        ("B", "Sort by broken links"),
-                        ("R", "Reverse sorting"),
+                        ("A", "select-sort-name"),
+                        ("W", "select-sort-words"),
+                        ("C", "select-sort-chars"),
+                        ("O", "select-sort-global-out"),
+                        ("U", "select-sort-local-out"),
+                        ("I", "select-sort-global-in"),
+                        ("N", "select-sort-local-in"),
+                        ("B", "select-sort-broken"),
+                        ("D", "select-sort-date"),
+                        ("E", "select-sort-created"),
+                        ("M", "select-sort-modified"),
+                        ("V", "select-sort-visits"),
+                        ("R", "select-sort-reverse"),
                     ]
                 }
                 .iter()
-                .map(|(a, b)| (a.to_string(), b.to_string()))
+                .map(|(key, id)| (key.to_string(), ui::tr(id)))
                 .collect_vec();
 
                 // If in git mode, calculate & display some information.
@@ -809,11 +1484,7 @@ impl super::Screen for SelectScreen {
                                 0,
                                 (
                                     String::new(),
-                                    format!(
-                                        "{} commit{} ahead",
-                                        ahead,
-                                        if ahead > 1 { "s" } else { "" }
-                                    ),
+                                    ui::tr_args("select-git-ahead", &[("count", ahead.into())]),
                                 ),
                             );
                         }
@@ -823,29 +1494,35 @@ impl super::Screen for SelectScreen {
                                 0,
                                 (
                                     String::new(),
-                                    format!(
-                                        "{} commit{} behind",
-                                        behind,
-                                        if behind > 1 { "s" } else { "" }
-                                    ),
+                                    ui::tr_args("select-git-behind", &[("count", behind.into())]),
                                 ),
                             );
                         }
 
                         if ahead == 0 && behind == 0 {
-                            contents.insert(0, (String::new(), "Up to Date".to_string()));
+                            contents.insert(0, (String::new(), ui::tr("select-git-up-to-date")));
                         }
 
                         if untracked {
-                            contents.insert(0, (String::new(), "Untracked changes".to_string()));
+                            contents.insert(0, (String::new(), ui::tr("select-git-untracked")));
                         }
 
                         if uncommited {
-                            contents.insert(0, (String::new(), "Uncommited changes".to_string()));
+                            contents.insert(0, (String::new(), ui::tr("select-git-uncommited")));
                         }
                     }
                 }
 
+                // If in export mode, offer the export profile attached to the filter.
+                if self.mode == SelectMode::SubmenuExport {
+                    if let Some(profile) = self.export_profile() {
+                        contents.push((
+                            String::from("R"),
+                            ui::tr_args("select-export-profile", &[("profile", profile.into())]),
+                        ));
+                    }
+                }
+
                 let popup_areas = Layout::vertical([
                     Constraint::Fill(1),
                     Constraint::Length(contents.len() as u16 + 2),
@@ -880,7 +1557,7 @@ impl super::Screen for SelectScreen {
                 let widths = [Constraint::Length(2), Constraint::Fill(1)];
 
                 let popup_table = Table::new(rows, widths)
-                    .block(Block::bordered())
+                    .block(ui::bordered())
                     .column_spacing(1);
 
                 // Clear the area and then render the widget on top.
@@ -888,7 +1565,13 @@ impl super::Screen for SelectScreen {
                 Widget::render(popup_table, br_area, buf);
             }
             SelectMode::Filter | SelectMode::Select => {}
-            SelectMode::Create | SelectMode::Rename | SelectMode::Move => {
+            SelectMode::Create
+            | SelectMode::Rename
+            | SelectMode::Move
+            | SelectMode::ExportCopy
+            | SelectMode::ExportZip
+            | SelectMode::ExportAnki
+            | SelectMode::Email => {
                 let popup_areas = Layout::vertical([
                     Constraint::Fill(1),
                     Constraint::Length(3),
@@ -907,76 +1590,138 @@ impl super::Screen for SelectScreen {
                 Widget::render(Clear, center_area, buf);
                 Widget::render(&self.name_area, center_area, buf);
             }
+            SelectMode::LinkSuggestions => {
+                let rows = self
+                    .suggestions
+                    .iter()
+                    .map(|(suggestion, accepted)| {
+                        Row::new(vec![
+                            Span::styled(
+                                if *accepted { "[x]" } else { "[ ]" },
+                                self.styles.hotkey_style,
+                            ),
+                            Span::styled(suggestion.mention.as_str(), self.styles.text_style),
+                            Span::styled(suggestion.to_wikilink(), self.styles.subtitle_style),
+                        ])
+                    })
+                    .collect_vec();
+
+                let widths = [
+                    Constraint::Length(3),
+                    Constraint::Fill(1),
+                    Constraint::Fill(1),
+                ];
+
+                let mut state = TableState::new().with_selected(self.suggestions_selected);
+
+                let suggestions_table = Table::new(rows, widths)
+                    .column_spacing(1)
+                    .highlight_symbol(ui::selection_marker())
+                    .row_highlight_style(self.styles.selected_style)
+                    .block(
+                        ui::bordered()
+                            .title(style::Styled::set_style(
+                                ui::tr("select-title-suggestions"),
+                                self.styles.title_style,
+                            ))
+                            .title_bottom(
+                                Line::from(ui::tr_hotkeys(
+                                    "select-suggestions-hints",
+                                    self.styles.hotkey_style,
+                                    self.styles.text_style,
+                                ))
+                                .right_aligned(),
+                            ),
+                    );
+
+                let popup_areas = Layout::vertical([
+                    Constraint::Fill(1),
+                    Constraint::Length((self.suggestions.len() as u16 + 2).min(area.height / 2)),
+                    Constraint::Fill(1),
+                ])
+                .split(area);
+
+                let center_area = Layout::horizontal([
+                    Constraint::Fill(1),
+                    Constraint::Percentage(60),
+                    Constraint::Fill(1),
+                ])
+                .split(popup_areas[1])[1];
+
+                // Clear the area and then render the suggestions on top.
+                Widget::render(Clear, center_area, buf);
+                StatefulWidget::render(suggestions_table, center_area, buf, &mut state);
+            }
             SelectMode::FilterHelp => {
-                let help_widths = [Constraint::Length(9), Constraint::Min(0)];
+                let help_widths = [Constraint::Length(12), Constraint::Min(0)];
 
                 let help_rows = [
+                    ("select-help-enter-key", "select-help-enter"),
+                    ("select-help-exit-key", "select-help-exit"),
+                    ("", ""),
+                    ("#[tag]", "select-help-tag"),
+                    ("!#[tag]", "select-help-not-tag"),
+                    (">[note]", "select-help-linking"),
+                    ("<[note]", "select-help-linked"),
+                    ("!>[note]", "select-help-not-linking"),
+                    ("!<[note]", "select-help-not-linked"),
+                    ("path:[f]", "select-help-path"),
+                    ("!path:[f]", "select-help-not-path"),
+                    ("in:[c]", "select-help-collection"),
+                    ("!in:[c]", "select-help-not-collection"),
+                    ("text:[w]", "select-help-content"),
+                    ("!text:[w]", "select-help-not-content"),
+                    (".[c]>[v]", "select-help-table"),
+                    (" ", "select-help-table-operators"),
+                    ("words>[n]", "select-help-counts"),
+                    ("modified>[d]", "select-help-dates"),
+                    ("-", "select-help-negation"),
+                    ("OR", "select-help-or"),
+                    ("(...)", "select-help-group"),
+                    ("!(...)", "select-help-not-group"),
+                    ("|", "select-help-full-text"),
+                    (" ", "select-help-title"),
+                    ("~", "select-help-semantic"),
+                ]
+                .map(|(syntax, description)| {
                     Row::new(vec![
-                        Cell::from("/ or F").style(self.styles.subtitle_style),
-                        Cell::from("Enter the filter text box.").style(self.styles.text_style),
-                    ]),
-                    Row::new(vec![
-                        Cell::from("󰌑 or Esc").style(self.styles.subtitle_style),
-                        Cell::from("Exit the filter text box").style(self.styles.text_style),
-                    ]),
-                    Row::new(vec![Cell::from("").style(self.styles.subtitle_style)]),
-                    Row::new(vec![
-                        Cell::from("#[tag]").style(self.styles.subtitle_style),
-                        Cell::from("Show notes with tag [tag].").style(self.styles.text_style),
-                    ]),
-                    Row::new(vec![
-                        Cell::from("!#[tag]").style(self.styles.subtitle_style),
-                        Cell::from("Show notes without tag [tag].").style(self.styles.text_style),
-                    ]),
-                    Row::new(vec![
-                        Cell::from(">[note]").style(self.styles.subtitle_style),
-                        Cell::from("Show notes linking to [note].").style(self.styles.text_style),
-                    ]),
-                    Row::new(vec![
-                        Cell::from("<[note]").style(self.styles.subtitle_style),
-                        Cell::from("Show notes linked to from [note].")
-                            .style(self.styles.text_style),
-                    ]),
-                    Row::new(vec![
-                        Cell::from("!>[note]").style(self.styles.subtitle_style),
-                        Cell::from("Show notes not linking to [note].")
-                            .style(self.styles.text_style),
-                    ]),
-                    Row::new(vec![
-                        Cell::from("!<[note]").style(self.styles.subtitle_style),
-                        Cell::from("Show notes not linked to from [note].")
-                            .style(self.styles.text_style),
-                    ]),
-                    Row::new(vec![
-                        Cell::from("|").style(self.styles.subtitle_style),
-                        Cell::from("All text after | will be searched in the full text.")
-                            .style(self.styles.text_style),
-                    ]),
-                    Row::new(vec![
-                        Cell::from(" ").style(self.styles.subtitle_style),
-                        Cell::from("All other text will be matched against the title.")
-                            .style(self.styles.text_style),
-                    ]),
-                ];
+                        // the keys to enter and exit the filter box are described in words
+                        Cell::from(if syntax.starts_with("select-help") {
+                            ui::tr(syntax)
+                        } else {
+                            syntax.to_owned()
+                        })
+                        .style(self.styles.subtitle_style),
+                        Cell::from(if description.is_empty() {
+                            String::new()
+                        } else {
+                            ui::tr(description)
+                        })
+                        .style(self.styles.text_style),
+                    ])
+                });
+
+                let help_height = help_rows.len() as u16 + 2;
 
                 let help_table = Table::new(help_rows, help_widths).column_spacing(1).block(
-                    Block::bordered()
+                    ui::bordered()
                         .title(style::Styled::set_style(
-                            "Filter Syntax",
+                            ui::tr("select-title-filter-syntax"),
                             self.styles.title_style,
                         ))
                         .title_bottom(
-                            Line::from(vec![
-                                Span::styled("C", self.styles.hotkey_style),
-                                Span::styled("lose", self.styles.text_style),
-                            ])
+                            Line::from(ui::tr_hotkeys(
+                                "hints-close",
+                                self.styles.hotkey_style,
+                                self.styles.text_style,
+                            ))
                             .right_aligned(),
                         ),
                 );
 
                 let popup_areas = Layout::vertical([
                     Constraint::Fill(1),
-                    Constraint::Length(10),
+                    Constraint::Length(help_height),
                     Constraint::Fill(1),
                 ])
                 .split(area);
@@ -992,6 +1737,81 @@ impl super::Screen for SelectScreen {
                 Widget::render(Clear, center_area, buf);
                 Widget::render(help_table, center_area, buf);
             }
+            SelectMode::Distributions => {
+                let popup_areas = Layout::vertical([
+                    Constraint::Fill(1),
+                    Constraint::Percentage(80),
+                    Constraint::Fill(1),
+                ])
+                .split(area);
+
+                let center_area = Layout::horizontal([
+                    Constraint::Fill(1),
+                    Constraint::Percentage(80),
+                    Constraint::Fill(1),
+                ])
+                .split(popup_areas[1])[1];
+
+                let block = ui::bordered()
+                    .title(style::Styled::set_style(
+                        ui::tr("select-title-distributions"),
+                        self.styles.title_style,
+                    ))
+                    .title_bottom(
+                        Line::from(ui::tr_hotkeys(
+                            "hints-close",
+                            self.styles.hotkey_style,
+                            self.styles.text_style,
+                        ))
+                        .right_aligned(),
+                    );
+
+                let chart_areas =
+                    Layout::vertical([Constraint::Fill(1); 3]).split(block.inner(center_area));
+
+                // Clear the area and then render the charts on top.
+                Widget::render(Clear, center_area, buf);
+                Widget::render(block, center_area, buf);
+                for (chart, chart_area) in self
+                    .local_stats
+                    .to_histograms(self.index.clone(), &self.styles)
+                    .into_iter()
+                    .zip(chart_areas.iter())
+                {
+                    Widget::render(chart, *chart_area, buf);
+                }
+            }
         }
     }
+
+    fn announcement(&self) -> Option<String> {
+        let location = ui::tr("announce-select");
+        let Some(name) = self
+            .local_stats
+            .get_selected(self.selected)
+            .and_then(|env_stats| {
+                self.index
+                    .borrow()
+                    .get(&env_stats.id)
+                    .map(|note| note.display_name.clone())
+            })
+        else {
+            return Some(location);
+        };
+        let selection = ui::tr_args(
+            "select-selection",
+            &[
+                ("name", name.into()),
+                ("position", (self.selected + 1).into()),
+                ("count", self.local_stats.len().into()),
+            ],
+        );
+        Some(ui::tr_args(
+            "announce-selection",
+            &[
+                ("location", location.into()),
+                ("selection", selection.into()),
+            ],
+        ))
+    }
 }
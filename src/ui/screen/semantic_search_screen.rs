@@ -0,0 +1,221 @@
+use crate::{data, error, io, ui};
+
+use itertools::Itertools;
+use ratatui::crossterm::event::KeyCode;
+use ratatui::{prelude::*, widgets::*};
+use tui_textarea::TextArea;
+
+/// How long a single tick may spend creating embeddings before the progress is drawn again.
+const TICK_DURATION: std::time::Duration = std::time::Duration::from_millis(50);
+
+/// The semantic search screen creates missing embeddings of the notes and ranks them by their similarity to a query.
+pub struct SemanticSearchScreen {
+    // === CONFIG ===
+    /// The used styles.
+    styles: ui::UiStyles,
+
+    // === DATA ===
+    /// The search, possibly still creating embeddings.
+    search: io::SemanticSearch,
+    /// The index the found notes are named from.
+    index: data::NoteIndexContainer,
+    /// The ids and names of the found notes, with their similarity to the query.
+    results: Vec<(String, String, f32)>,
+
+    // === UI ===
+    /// The text area to enter the query.
+    query_area: TextArea<'static>,
+    /// Wether keys are entered into the query, rather than moving through the results.
+    typing: bool,
+    /// The index of the result selected in the table.
+    selected: usize,
+}
+
+impl SemanticSearchScreen {
+    /// Creates a new semantic search screen with the given prepared search.
+    pub fn new(
+        search: io::SemanticSearch,
+        index: data::NoteIndexContainer,
+        styles: ui::UiStyles,
+    ) -> Self {
+        let mut query_area = TextArea::default();
+        query_area.set_style(styles.input_style);
+        query_area.set_cursor_line_style(styles.input_style);
+        query_area.set_block(
+            ui::bordered().title(Span::styled(ui::tr("semantic-prompt"), styles.title_style)),
+        );
+
+        Self {
+            styles,
+            search,
+            index,
+            results: Vec::new(),
+            query_area,
+            typing: true,
+            selected: 0,
+        }
+    }
+}
+
+impl super::Screen for SemanticSearchScreen {
+    fn draw(&self, area: layout::Rect, buf: &mut buffer::Buffer) {
+        let [query_area, results_area] =
+            Layout::vertical([Constraint::Length(3), Constraint::Fill(1)]).areas(area);
+
+        Widget::render(&self.query_area, query_area, buf);
+
+        let title = Line::from(vec![Span::styled(
+            ui::tr("semantic-title"),
+            self.styles.title_style,
+        )]);
+
+        let (done, total) = self.search.progress();
+        let failures = self.search.failures().len();
+        let status = Line::from(vec![Span::styled(
+            if !self.search.is_ready() {
+                ui::tr_args(
+                    "semantic-embedding",
+                    &[("done", done.into()), ("total", total.into())],
+                )
+            } else if failures > 0 {
+                ui::tr_args(
+                    "semantic-results-failures",
+                    &[
+                        ("count", self.results.len().into()),
+                        ("failures", failures.into()),
+                    ],
+                )
+            } else {
+                ui::tr_args("semantic-results", &[("count", self.results.len().into())])
+            },
+            self.styles.text_style,
+        )])
+        .right_aligned();
+
+        let instructions = Line::from(if self.typing {
+            ui::tr_hotkeys(
+                "semantic-hints-typing",
+                self.styles.hotkey_style,
+                self.styles.text_style,
+            )
+        } else {
+            ui::tr_hotkeys(
+                "semantic-hints",
+                self.styles.hotkey_style,
+                self.styles.text_style,
+            )
+        })
+        .left_aligned();
+
+        let rows = self
+            .results
+            .iter()
+            .map(|(_id, name, similarity)| {
+                Row::new(vec![
+                    Span::styled(format!("{:.2}", similarity), self.styles.text_style),
+                    Span::styled(name.as_str(), self.styles.subtitle_style),
+                ])
+            })
+            .collect_vec();
+
+        let table = Table::new(rows, [Constraint::Length(10), Constraint::Fill(1)])
+            .column_spacing(2)
+            .header(Row::new(vec![
+                Span::styled(
+                    ui::tr("semantic-column-similarity"),
+                    self.styles.subtitle_style,
+                ),
+                Span::styled(ui::tr("column-note"), self.styles.subtitle_style),
+            ]))
+            .highlight_symbol(ui::selection_marker())
+            .row_highlight_style(self.styles.selected_style)
+            .block(
+                ui::bordered()
+                    .title_top(title)
+                    .title_bottom(instructions)
+                    .title_bottom(status),
+            );
+
+        let mut state = TableState::new()
+            .with_offset(
+                self.selected
+                    .saturating_sub(results_area.height as usize / 3),
+            )
+            .with_selected((!self.typing).then_some(self.selected));
+
+        StatefulWidget::render(table, results_area, buf, &mut state);
+    }
+
+    fn update(&mut self, key: ratatui::crossterm::event::KeyEvent) -> error::Result<ui::Message> {
+        if self.typing {
+            match key.code {
+                KeyCode::Esc => return Ok(ui::Message::CloseOverlay),
+                KeyCode::Enter => {
+                    if !self.search.is_ready() {
+                        return Err(error::RucolaError::Input(ui::tr(
+                            "semantic-error-not-ready",
+                        )));
+                    }
+
+                    let query = self.query_area.lines().join(" ");
+                    if query.trim().is_empty() {
+                        return Ok(ui::Message::None);
+                    }
+
+                    let index = self.index.borrow();
+                    self.results = self
+                        .search
+                        .search(&query)?
+                        .into_iter()
+                        .flat_map(|(id, similarity)| {
+                            index
+                                .get(&id)
+                                .map(|note| (id.clone(), note.display_name.clone(), similarity))
+                        })
+                        .collect();
+                    self.selected = 0;
+                    self.typing = false;
+                }
+                _ => {
+                    self.query_area.input(key);
+                }
+            }
+            return Ok(ui::Message::None);
+        }
+
+        match key.code {
+            KeyCode::Char('q' | 'Q') => return Ok(ui::Message::Quit),
+            KeyCode::Esc | KeyCode::Left | KeyCode::Char('h' | 'H') => {
+                return Ok(ui::Message::CloseOverlay);
+            }
+            KeyCode::Char('j' | 'J') | KeyCode::Down => {
+                self.selected = self
+                    .selected
+                    .saturating_add(1)
+                    .min(self.results.len().saturating_sub(1));
+            }
+            KeyCode::Char('k' | 'K') | KeyCode::Up => {
+                self.selected = self.selected.saturating_sub(1);
+            }
+            KeyCode::Char('/') => self.typing = true,
+            KeyCode::Enter | KeyCode::Right | KeyCode::Char('l' | 'L') => {
+                if let Some((id, _name, _similarity)) = self.results.get(self.selected) {
+                    return Ok(ui::Message::DisplayStackPush(id.to_owned()));
+                }
+            }
+            _ => {}
+        }
+
+        Ok(ui::Message::None)
+    }
+
+    fn tick(&mut self) -> error::Result<ui::Message> {
+        let start = std::time::Instant::now();
+        while start.elapsed() < TICK_DURATION && self.search.step() {}
+        Ok(ui::Message::None)
+    }
+
+    fn busy(&self) -> bool {
+        !self.search.is_ready()
+    }
+}
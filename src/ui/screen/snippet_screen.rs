@@ -0,0 +1,198 @@
+use crate::{data, error, io, ui};
+
+use itertools::Itertools;
+use ratatui::crossterm::event::KeyCode;
+use ratatui::{prelude::*, widgets::*};
+
+/// The snippet screen lets the user pick a configured snippet and the place within a note to insert it at.
+pub struct SnippetScreen {
+    // === CONFIG ===
+    /// The used styles.
+    styles: ui::UiStyles,
+    /// The file manager used to insert the snippet.
+    manager: io::FileManager,
+
+    // === DATA ===
+    /// The note to insert the snippet into.
+    note: data::Note,
+    /// The snippets to choose from.
+    snippets: Vec<data::Snippet>,
+    /// The headings of the note, at the end of whose sections the snippet can be inserted.
+    headings: Vec<data::Heading>,
+
+    // === UI ===
+    /// The index of the chosen snippet, once the place to insert it at is being chosen.
+    chosen: Option<usize>,
+    /// The index of the snippet or place selected in the table.
+    selected: usize,
+}
+
+impl SnippetScreen {
+    /// Creates a new snippet screen for the given note, reading its headings from the file system.
+    pub fn new(
+        note: data::Note,
+        snippets: Vec<data::Snippet>,
+        manager: io::FileManager,
+        styles: ui::UiStyles,
+    ) -> error::Result<Self> {
+        let headings = data::headings(&std::fs::read_to_string(&note.path)?);
+
+        Ok(Self {
+            styles,
+            manager,
+            note,
+            snippets,
+            headings,
+            chosen: None,
+            selected: 0,
+        })
+    }
+
+    /// Returns the snippet that is chosen or, while choosing one, selected.
+    fn current_snippet(&self) -> Option<&data::Snippet> {
+        self.snippets.get(self.chosen.unwrap_or(self.selected))
+    }
+
+    /// Returns the number of rows of the table.
+    fn row_count(&self) -> usize {
+        match self.chosen {
+            // the end of the note, then all headings
+            Some(_) => self.headings.len() + 1,
+            None => self.snippets.len(),
+        }
+    }
+}
+
+impl super::Screen for SnippetScreen {
+    fn draw(&self, area: layout::Rect, buf: &mut buffer::Buffer) {
+        let [table_area, preview_area] =
+            Layout::horizontal([Constraint::Percentage(40), Constraint::Fill(1)]).areas(area);
+
+        let title = Line::from(
+            match self.current_snippet().filter(|_| self.chosen.is_some()) {
+                Some(snippet) => Span::styled(
+                    ui::tr_args(
+                        "snippet-title-position",
+                        &[("snippet", snippet.name.as_str().into())],
+                    ),
+                    self.styles.title_style,
+                ),
+                None => Span::styled(
+                    ui::tr_args(
+                        "snippet-title",
+                        &[("note", self.note.display_name.as_str().into())],
+                    ),
+                    self.styles.title_style,
+                ),
+            },
+        );
+
+        let instructions = Line::from(ui::tr_hotkeys(
+            if self.chosen.is_some() {
+                "snippet-hints-position"
+            } else {
+                "snippet-hints"
+            },
+            self.styles.hotkey_style,
+            self.styles.text_style,
+        ))
+        .left_aligned();
+
+        let rows = match self.chosen {
+            Some(_) => std::iter::once(Row::new(vec![Span::styled(
+                ui::tr("snippet-end"),
+                self.styles.subtitle_style,
+            )]))
+            .chain(self.headings.iter().map(|heading| {
+                Row::new(vec![Span::styled(
+                    format!(
+                        "{}{} {}",
+                        "  ".repeat(heading.level - 1),
+                        "#".repeat(heading.level),
+                        heading.text
+                    ),
+                    self.styles.text_style,
+                )])
+            }))
+            .collect_vec(),
+            None => self
+                .snippets
+                .iter()
+                .map(|snippet| {
+                    Row::new(vec![Span::styled(
+                        snippet.name.as_str(),
+                        self.styles.subtitle_style,
+                    )])
+                })
+                .collect_vec(),
+        };
+
+        let table = Table::new(rows, [Constraint::Fill(1)])
+            .highlight_symbol(ui::selection_marker())
+            .row_highlight_style(self.styles.selected_style)
+            .block(ui::bordered().title_top(title).title_bottom(instructions));
+
+        let mut state = TableState::new()
+            .with_offset(self.selected.saturating_sub(table_area.height as usize / 3))
+            .with_selected(Some(self.selected));
+
+        StatefulWidget::render(table, table_area, buf, &mut state);
+
+        let preview = Paragraph::new(
+            self.current_snippet()
+                .map(|snippet| snippet.text.as_str())
+                .unwrap_or_default(),
+        )
+        .style(self.styles.text_style)
+        .wrap(Wrap { trim: false })
+        .block(ui::bordered().title(Span::styled(
+            ui::tr("title-preview"),
+            self.styles.title_style,
+        )));
+
+        Widget::render(preview, preview_area, buf);
+    }
+
+    fn update(&mut self, key: ratatui::crossterm::event::KeyEvent) -> error::Result<ui::Message> {
+        match key.code {
+            KeyCode::Char('q' | 'Q') => return Ok(ui::Message::Quit),
+            // Return to the choice of snippet or the previous screen
+            KeyCode::Esc | KeyCode::Left | KeyCode::Char('h' | 'H') => match self.chosen.take() {
+                Some(chosen) => self.selected = chosen,
+                None => return Ok(ui::Message::CloseOverlay),
+            },
+            KeyCode::Char('j' | 'J') | KeyCode::Down => {
+                self.selected = self
+                    .selected
+                    .saturating_add(1)
+                    .min(self.row_count().saturating_sub(1));
+            }
+            KeyCode::Char('k' | 'K') | KeyCode::Up => {
+                self.selected = self.selected.saturating_sub(1);
+            }
+            // Enter: Choose the selected snippet, then insert it at the selected place
+            KeyCode::Enter | KeyCode::Right | KeyCode::Char('l' | 'L') => match self.chosen {
+                Some(chosen) => {
+                    if let Some(snippet) = self.snippets.get(chosen) {
+                        // the first place is the end of the note
+                        let heading = self
+                            .selected
+                            .checked_sub(1)
+                            .and_then(|i| self.headings.get(i));
+                        self.manager.insert_snippet(&self.note, snippet, heading)?;
+                    }
+                    return Ok(ui::Message::CloseOverlay);
+                }
+                None => {
+                    if self.selected < self.snippets.len() {
+                        self.chosen = Some(self.selected);
+                        self.selected = 0;
+                    }
+                }
+            },
+            _ => {}
+        }
+
+        Ok(ui::Message::None)
+    }
+}
@@ -0,0 +1,186 @@
+use crate::{data, error, io, ui};
+
+use itertools::Itertools;
+use ratatui::crossterm::event::KeyCode;
+use ratatui::{prelude::*, widgets::*};
+
+/// The stale notes screen lists all notes that have not been modified for longer than the stale rules of the config allow, so they can be reviewed.
+pub struct StaleNotesScreen {
+    // === CONFIG ===
+    /// The used styles.
+    styles: ui::UiStyles,
+    /// The file manager used to edit notes.
+    manager: io::FileManager,
+    /// The rules notes are checked against.
+    staleness: data::Staleness,
+
+    // === DATA ===
+    /// The index whose notes are checked.
+    index: data::NoteIndexContainer,
+    /// The stale notes, with their names and the dates of their last modification.
+    notes: Vec<(String, String, data::StaleNote)>,
+
+    // === UI ===
+    /// The index of the note selected in the table.
+    selected: usize,
+}
+
+impl StaleNotesScreen {
+    /// Creates a new stale notes screen for the given index.
+    pub fn new(
+        index: data::NoteIndexContainer,
+        manager: io::FileManager,
+        staleness: data::Staleness,
+        styles: ui::UiStyles,
+    ) -> Self {
+        let mut res = Self {
+            styles,
+            manager,
+            staleness,
+            index,
+            notes: Vec::new(),
+            selected: 0,
+        };
+        res.find_notes();
+        res
+    }
+
+    /// Finds the stale notes of the vault again.
+    fn find_notes(&mut self) {
+        let index = self.index.borrow();
+        self.notes = self
+            .staleness
+            .stale_notes(&index, chrono::Local::now())
+            .into_iter()
+            .filter_map(|stale| {
+                let note = index.get(&stale.id)?;
+                let modified = note
+                    .modified
+                    .map(|modified| modified.format("%Y-%m-%d").to_string())
+                    .unwrap_or_default();
+                Some((note.display_name.clone(), modified, stale))
+            })
+            .collect();
+        self.selected = self.selected.min(self.notes.len().saturating_sub(1));
+    }
+}
+
+impl super::Screen for StaleNotesScreen {
+    fn draw(&self, area: layout::Rect, buf: &mut buffer::Buffer) {
+        let title = Line::from(vec![Span::styled(
+            ui::tr("stale-notes-title"),
+            self.styles.title_style,
+        )]);
+
+        let count = Line::from(vec![Span::styled(
+            ui::tr_args("stale-notes-count", &[("count", self.notes.len().into())]),
+            self.styles.text_style,
+        )])
+        .right_aligned();
+
+        let instructions = Line::from(ui::tr_hotkeys(
+            "stale-notes-hints",
+            self.styles.hotkey_style,
+            self.styles.text_style,
+        ))
+        .left_aligned();
+
+        let rows = self
+            .notes
+            .iter()
+            .map(|(name, modified, stale)| {
+                Row::new(vec![
+                    Span::styled(name.as_str(), self.styles.subtitle_style),
+                    Span::styled(modified.as_str(), self.styles.text_style),
+                    Span::styled(
+                        format!("{:5} / {}", stale.age, stale.days),
+                        self.styles.text_style,
+                    ),
+                    Span::styled(stale.rule.as_str(), self.styles.text_style),
+                ])
+            })
+            .collect_vec();
+
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Percentage(40),
+                Constraint::Length(12),
+                Constraint::Length(14),
+                Constraint::Fill(1),
+            ],
+        )
+        .column_spacing(2)
+        .header(Row::new(vec![
+            Span::styled(ui::tr("column-note"), self.styles.subtitle_style),
+            Span::styled(
+                ui::tr("stale-notes-column-modified"),
+                self.styles.subtitle_style,
+            ),
+            Span::styled(
+                ui::tr("stale-notes-column-days"),
+                self.styles.subtitle_style,
+            ),
+            Span::styled(
+                ui::tr("stale-notes-column-rule"),
+                self.styles.subtitle_style,
+            ),
+        ]))
+        .highlight_symbol(ui::selection_marker())
+        .row_highlight_style(self.styles.selected_style)
+        .block(
+            ui::bordered()
+                .title_top(title)
+                .title_bottom(instructions)
+                .title_bottom(count),
+        );
+
+        let mut state = TableState::new()
+            .with_offset(self.selected.saturating_sub(area.height as usize / 3))
+            .with_selected(Some(self.selected));
+
+        StatefulWidget::render(table, area, buf, &mut state);
+    }
+
+    fn update(&mut self, key: ratatui::crossterm::event::KeyEvent) -> error::Result<ui::Message> {
+        match key.code {
+            KeyCode::Char('q' | 'Q') => return Ok(ui::Message::Quit),
+            // Return to the previous screen
+            KeyCode::Esc | KeyCode::Left | KeyCode::Char('h' | 'H') => {
+                return Ok(ui::Message::CloseOverlay);
+            }
+            KeyCode::Char('j' | 'J') | KeyCode::Down => {
+                self.selected = self
+                    .selected
+                    .saturating_add(1)
+                    .min(self.notes.len().saturating_sub(1));
+            }
+            KeyCode::Char('k' | 'K') | KeyCode::Up => {
+                self.selected = self.selected.saturating_sub(1);
+            }
+            // Enter: Edit the selected note
+            KeyCode::Enter => {
+                if let Some((_, _, stale)) = self.notes.get(self.selected) {
+                    if let Some(note) = self.index.borrow().get(&stale.id) {
+                        return Ok(ui::Message::OpenExternalCommand(Box::new(
+                            self.manager.create_edit_command(&note.path)?,
+                        )));
+                    }
+                }
+            }
+            // O: Open the selected note
+            KeyCode::Char('o' | 'O') => {
+                if let Some((_, _, stale)) = self.notes.get(self.selected) {
+                    return Ok(ui::Message::DisplayStackPush(stale.id.clone()));
+                }
+            }
+            _ => {}
+        }
+
+        Ok(ui::Message::None)
+    }
+
+    fn notes_changed(&mut self, _ids: &[String]) {
+        self.find_notes();
+    }
+}
@@ -0,0 +1,177 @@
+use crate::{data, error, io, ui};
+
+use itertools::Itertools;
+use ratatui::crossterm::event::KeyCode;
+use ratatui::{prelude::*, widgets::*};
+
+/// The tag suggestions screen lists tags of the vault proposed for a changed note and adds the accepted ones to its frontmatter.
+pub struct TagSuggestionsScreen {
+    // === CONFIG ===
+    /// The used styles.
+    styles: ui::UiStyles,
+    /// The file manager used to write the accepted tags.
+    manager: io::FileManager,
+
+    // === DATA ===
+    /// The display name of the note the tags are suggested for.
+    name: String,
+    /// The path of the note the tags are suggested for.
+    path: std::path::PathBuf,
+    /// The suggested tags, and wether they are accepted.
+    suggestions: Vec<(data::TagSuggestion, bool)>,
+
+    // === UI ===
+    /// The index of the suggestion selected in the table.
+    selected: usize,
+}
+
+impl TagSuggestionsScreen {
+    /// Creates a new tag suggestions screen for the given note, with all suggestions accepted.
+    pub fn new(
+        note: &data::Note,
+        suggestions: Vec<data::TagSuggestion>,
+        manager: io::FileManager,
+        styles: ui::UiStyles,
+    ) -> Self {
+        Self {
+            styles,
+            manager,
+            name: note.display_name.clone(),
+            path: note.path.clone(),
+            suggestions: suggestions
+                .into_iter()
+                .map(|suggestion| (suggestion, true))
+                .collect(),
+            selected: 0,
+        }
+    }
+}
+
+impl super::Screen for TagSuggestionsScreen {
+    fn draw(&self, area: layout::Rect, buf: &mut buffer::Buffer) {
+        let title = Line::from(vec![
+            Span::styled(
+                ui::tr("tag-suggestions-title") + " ",
+                self.styles.title_style,
+            ),
+            Span::styled(self.name.as_str(), self.styles.subtitle_style),
+        ]);
+
+        let count = Line::from(vec![Span::styled(
+            ui::tr_args(
+                "tag-suggestions-accepted",
+                &[
+                    (
+                        "accepted",
+                        self.suggestions
+                            .iter()
+                            .filter(|(_, accepted)| *accepted)
+                            .count()
+                            .into(),
+                    ),
+                    ("total", self.suggestions.len().into()),
+                ],
+            ),
+            self.styles.text_style,
+        )])
+        .right_aligned();
+
+        let instructions = Line::from(ui::tr_hotkeys(
+            "tag-suggestions-hints",
+            self.styles.hotkey_style,
+            self.styles.text_style,
+        ))
+        .left_aligned();
+
+        let rows = self
+            .suggestions
+            .iter()
+            .map(|(suggestion, accepted)| {
+                Row::new(vec![
+                    Span::styled(
+                        if *accepted { "[x]" } else { "[ ]" },
+                        self.styles.text_style,
+                    ),
+                    Span::styled(suggestion.tag.as_str(), self.styles.subtitle_style),
+                    Span::styled(suggestion.reason(), self.styles.text_style),
+                ])
+            })
+            .collect_vec();
+
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Length(3),
+                Constraint::Percentage(40),
+                Constraint::Fill(1),
+            ],
+        )
+        .column_spacing(2)
+        .header(Row::new(vec![
+            Span::raw(""),
+            Span::styled(
+                ui::tr("tag-suggestions-column-tag"),
+                self.styles.subtitle_style,
+            ),
+            Span::styled(ui::tr("column-reason"), self.styles.subtitle_style),
+        ]))
+        .highlight_symbol(ui::selection_marker())
+        .row_highlight_style(self.styles.selected_style)
+        .block(
+            ui::bordered()
+                .title_top(title)
+                .title_bottom(instructions)
+                .title_bottom(count),
+        );
+
+        let mut state = TableState::new()
+            .with_offset(self.selected.saturating_sub(area.height as usize / 3))
+            .with_selected(Some(self.selected));
+
+        StatefulWidget::render(table, area, buf, &mut state);
+    }
+
+    fn update(&mut self, key: ratatui::crossterm::event::KeyEvent) -> error::Result<ui::Message> {
+        match key.code {
+            KeyCode::Char('q' | 'Q') => return Ok(ui::Message::Quit),
+            // Return to the previous screen without adding tags
+            KeyCode::Esc | KeyCode::Left | KeyCode::Char('h' | 'H') => {
+                return Ok(ui::Message::CloseOverlay);
+            }
+            KeyCode::Char('j' | 'J') | KeyCode::Down => {
+                self.selected = self
+                    .selected
+                    .saturating_add(1)
+                    .min(self.suggestions.len().saturating_sub(1));
+            }
+            KeyCode::Char('k' | 'K') | KeyCode::Up => {
+                self.selected = self.selected.saturating_sub(1);
+            }
+            KeyCode::Char(' ') => {
+                if let Some((_, accepted)) = self.suggestions.get_mut(self.selected) {
+                    *accepted = !*accepted;
+                }
+            }
+            KeyCode::Char('a' | 'A') => {
+                let accept = !self.suggestions.iter().all(|(_, accepted)| *accepted);
+                for (_, accepted) in self.suggestions.iter_mut() {
+                    *accepted = accept;
+                }
+            }
+            // Enter: Add the accepted tags to the note
+            KeyCode::Enter => {
+                let tags = self
+                    .suggestions
+                    .iter()
+                    .filter(|(_, accepted)| *accepted)
+                    .map(|(suggestion, _)| suggestion.tag.clone())
+                    .collect_vec();
+                self.manager.add_tags(&self.path, &tags)?;
+                return Ok(ui::Message::CloseOverlay);
+            }
+            _ => {}
+        }
+
+        Ok(ui::Message::None)
+    }
+}
@@ -0,0 +1,318 @@
+use crate::{data, error, io, ui};
+
+use itertools::Itertools;
+use ratatui::crossterm::event::KeyCode;
+use ratatui::{prelude::*, widgets::*};
+
+/// The tag usage screen lists all tags of the vault and shows where the selected one is used, so it can be renamed confidently.
+pub struct TagUsageScreen {
+    // === CONFIG ===
+    /// The used styles.
+    styles: ui::UiStyles,
+    /// The styles of tags configured by the user.
+    tag_styles: ui::TagStyles,
+    /// The file manager used to open notes at the line of an occurrence.
+    manager: io::FileManager,
+
+    // === DATA ===
+    /// The index whose notes are searched.
+    index: data::NoteIndexContainer,
+    /// All tags of the vault and the number of notes using each.
+    tags: Vec<(String, usize)>,
+    /// The occurrences of the selected tag, with the name of their note.
+    occurrences: Vec<(String, data::TagOccurrence)>,
+
+    // === UI ===
+    /// The index of the selected tag.
+    selected_tag: usize,
+    /// The index of the selected occurrence.
+    selected_occurrence: usize,
+    /// Wether the occurrences rather than the tags are focused.
+    occurrences_focused: bool,
+}
+
+impl TagUsageScreen {
+    /// Creates a new tag usage screen with the given tag selected, or the first tag of the vault.
+    pub fn new(
+        index: data::NoteIndexContainer,
+        manager: io::FileManager,
+        tag: Option<String>,
+        styles: ui::UiStyles,
+        tag_styles: ui::TagStyles,
+    ) -> Self {
+        let tags = data::tag_counts(&index.borrow());
+        let selected_tag = tag
+            .and_then(|tag| tags.iter().position(|(other, _)| *other == tag))
+            .unwrap_or_default();
+
+        let mut res = Self {
+            styles,
+            tag_styles,
+            manager,
+            index,
+            tags,
+            occurrences: Vec::new(),
+            selected_tag,
+            selected_occurrence: 0,
+            occurrences_focused: false,
+        };
+        res.find_occurrences();
+        res
+    }
+
+    /// Finds the occurrences of the selected tag again.
+    fn find_occurrences(&mut self) {
+        let index = self.index.borrow();
+        self.occurrences = self
+            .tags
+            .get(self.selected_tag)
+            .map(|(tag, _)| data::tag_occurrences(&index, tag))
+            .unwrap_or_default()
+            .into_iter()
+            .map(|occurrence| {
+                let name = index
+                    .get(&occurrence.id)
+                    .map(|note| note.display_name.clone())
+                    .unwrap_or_else(|| occurrence.id.clone());
+                (name, occurrence)
+            })
+            .collect();
+        self.selected_occurrence = self
+            .selected_occurrence
+            .min(self.occurrences.len().saturating_sub(1));
+    }
+
+    /// Moves the selection within the focused list down or up by one entry.
+    fn move_selection(&mut self, down: bool) {
+        if self.occurrences_focused {
+            self.selected_occurrence = if down {
+                self.selected_occurrence
+                    .saturating_add(1)
+                    .min(self.occurrences.len().saturating_sub(1))
+            } else {
+                self.selected_occurrence.saturating_sub(1)
+            };
+        } else {
+            let selected = if down {
+                self.selected_tag
+                    .saturating_add(1)
+                    .min(self.tags.len().saturating_sub(1))
+            } else {
+                self.selected_tag.saturating_sub(1)
+            };
+            if selected != self.selected_tag {
+                self.selected_tag = selected;
+                self.selected_occurrence = 0;
+                self.find_occurrences();
+            }
+        }
+    }
+}
+
+impl super::Screen for TagUsageScreen {
+    fn draw(&self, area: layout::Rect, buf: &mut buffer::Buffer) {
+        let [tags_area, occurrences_area] =
+            Layout::horizontal([Constraint::Percentage(25), Constraint::Fill(1)]).areas(area);
+
+        // === Tags ===
+        let rows = self
+            .tags
+            .iter()
+            .map(|(tag, count)| {
+                Row::new(vec![
+                    Line::from(self.tag_styles.span(tag, self.styles.subtitle_style)),
+                    Line::styled(format!("{:5}", count), self.styles.text_style),
+                ])
+            })
+            .collect_vec();
+
+        let tags_table = Table::new(rows, [Constraint::Fill(1), Constraint::Length(5)])
+            .column_spacing(1)
+            .highlight_symbol(ui::selection_marker())
+            .row_highlight_style(if self.occurrences_focused {
+                self.styles.text_style
+            } else {
+                self.styles.selected_style
+            })
+            .block(
+                ui::bordered()
+                    .title(Span::styled(
+                        ui::tr("tag-usage-title-tags"),
+                        self.styles.title_style,
+                    ))
+                    .title_bottom(
+                        Line::from(vec![Span::styled(
+                            ui::tr_args("tag-usage-tags", &[("count", self.tags.len().into())]),
+                            self.styles.text_style,
+                        )])
+                        .right_aligned(),
+                    ),
+            );
+
+        let mut tags_state = TableState::new()
+            .with_offset(
+                self.selected_tag
+                    .saturating_sub(tags_area.height as usize / 3),
+            )
+            .with_selected(Some(self.selected_tag));
+
+        StatefulWidget::render(tags_table, tags_area, buf, &mut tags_state);
+
+        // === Occurrences ===
+        let title = Line::from(match self.tags.get(self.selected_tag) {
+            Some((tag, _)) => vec![
+                Span::styled(ui::tr("tag-usage-title-tag") + " ", self.styles.title_style),
+                self.tag_styles.span(tag, self.styles.subtitle_style),
+            ],
+            None => vec![Span::styled(
+                ui::tr("tag-usage-title"),
+                self.styles.title_style,
+            )],
+        });
+
+        let count = Line::from(vec![Span::styled(
+            ui::tr_args(
+                "tag-usage-lines",
+                &[("count", self.occurrences.len().into())],
+            ),
+            self.styles.text_style,
+        )])
+        .right_aligned();
+
+        let instructions = Line::from(ui::tr_hotkeys(
+            "tag-usage-hints",
+            self.styles.hotkey_style,
+            self.styles.text_style,
+        ))
+        .left_aligned();
+
+        let rows = self
+            .occurrences
+            .iter()
+            .map(|(name, occurrence)| {
+                Row::new(vec![
+                    Span::styled(name.as_str(), self.styles.subtitle_style),
+                    Span::styled(occurrence.line.to_string(), self.styles.text_style),
+                    Span::styled(
+                        occurrence.source.to_string(),
+                        match occurrence.source {
+                            data::TagSource::Inline | data::TagSource::Frontmatter => {
+                                self.styles.text_style
+                            }
+                            data::TagSource::Code | data::TagSource::Typst => {
+                                self.styles.hotkey_style
+                            }
+                        },
+                    ),
+                    Span::styled(occurrence.context.as_str(), self.styles.text_style),
+                ])
+            })
+            .collect_vec();
+
+        let occurrences_table = Table::new(
+            rows,
+            [
+                Constraint::Percentage(25),
+                Constraint::Length(5),
+                Constraint::Length(11),
+                Constraint::Fill(1),
+            ],
+        )
+        .column_spacing(2)
+        .header(Row::new(vec![
+            Span::styled(ui::tr("column-note"), self.styles.subtitle_style),
+            Span::styled(ui::tr("tag-usage-column-line"), self.styles.subtitle_style),
+            Span::styled(
+                ui::tr("tag-usage-column-source"),
+                self.styles.subtitle_style,
+            ),
+            Span::styled(
+                ui::tr("tag-usage-column-context"),
+                self.styles.subtitle_style,
+            ),
+        ]))
+        .highlight_symbol(ui::selection_marker())
+        .row_highlight_style(if self.occurrences_focused {
+            self.styles.selected_style
+        } else {
+            self.styles.text_style
+        })
+        .block(
+            ui::bordered()
+                .title_top(title)
+                .title_bottom(instructions)
+                .title_bottom(count),
+        );
+
+        let mut occurrences_state = TableState::new()
+            .with_offset(
+                self.selected_occurrence
+                    .saturating_sub(occurrences_area.height as usize / 3),
+            )
+            .with_selected(Some(self.selected_occurrence));
+
+        StatefulWidget::render(
+            occurrences_table,
+            occurrences_area,
+            buf,
+            &mut occurrences_state,
+        );
+    }
+
+    fn update(&mut self, key: ratatui::crossterm::event::KeyEvent) -> error::Result<ui::Message> {
+        match key.code {
+            KeyCode::Char('q' | 'Q') => return Ok(ui::Message::Quit),
+            KeyCode::Esc => return Ok(ui::Message::CloseOverlay),
+            KeyCode::Char('j' | 'J') | KeyCode::Down => self.move_selection(true),
+            KeyCode::Char('k' | 'K') | KeyCode::Up => self.move_selection(false),
+            // Switch between the tags and their occurrences
+            KeyCode::Tab | KeyCode::BackTab => {
+                self.occurrences_focused = !self.occurrences_focused;
+            }
+            // Go back to the tags, or close the screen if they are focused already
+            KeyCode::Left | KeyCode::Char('h' | 'H') => {
+                if !self.occurrences_focused {
+                    return Ok(ui::Message::CloseOverlay);
+                }
+                self.occurrences_focused = false;
+            }
+            KeyCode::Right | KeyCode::Char('l' | 'L') => {
+                self.occurrences_focused = true;
+            }
+            // Enter: Edit the note of the selected occurrence at its line
+            KeyCode::Enter => {
+                if !self.occurrences_focused {
+                    self.occurrences_focused = true;
+                } else if let Some((_, occurrence)) = self.occurrences.get(self.selected_occurrence)
+                {
+                    if let Some(note) = self.index.borrow().get(&occurrence.id) {
+                        return Ok(ui::Message::OpenExternalCommand(Box::new(
+                            self.manager
+                                .create_edit_command_at(&note.path, Some(occurrence.line))?,
+                        )));
+                    }
+                }
+            }
+            // O: Open the note of the selected occurrence
+            KeyCode::Char('o' | 'O') => {
+                if let Some((_, occurrence)) = self.occurrences.get(self.selected_occurrence) {
+                    return Ok(ui::Message::DisplayStackPush(occurrence.id.clone()));
+                }
+            }
+            _ => {}
+        }
+
+        Ok(ui::Message::None)
+    }
+
+    fn notes_changed(&mut self, _ids: &[String]) {
+        // keep the selected tag selected, even if tags were added or removed before it
+        let selected = self.tags.get(self.selected_tag).map(|(tag, _)| tag.clone());
+        self.tags = data::tag_counts(&self.index.borrow());
+        self.selected_tag = selected
+            .and_then(|tag| self.tags.iter().position(|(other, _)| *other == tag))
+            .unwrap_or(self.selected_tag)
+            .min(self.tags.len().saturating_sub(1));
+        self.find_occurrences();
+    }
+}
@@ -0,0 +1,192 @@
+use crate::{error, io, ui};
+
+use itertools::Itertools;
+use ratatui::crossterm::event::KeyCode;
+use ratatui::{prelude::*, widgets::*};
+
+/// Describes the current mode of the UI.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+enum TrashMode {
+    /// Selecting a deleted note from the list.
+    #[default]
+    Select,
+    /// Confirming permanent deletion.
+    Purge,
+}
+
+/// The trash screen lists all deleted notes and allows the user to restore or permanently delete them.
+pub struct TrashScreen {
+    // === CONFIG ===
+    /// The file manager this screen uses to enact the user's file system requests on the file system.
+    manager: io::FileManager,
+    /// The used styles.
+    styles: ui::UiStyles,
+
+    // === DATA ===
+    /// All notes currently in the trash.
+    entries: Vec<io::TrashEntry>,
+
+    // === UI ===
+    /// The index of the entry selected in the table.
+    selected: usize,
+    /// Current input mode
+    mode: TrashMode,
+}
+
+impl TrashScreen {
+    /// Creates a new trash screen, listing the current contents of the trash.
+    pub fn new(manager: io::FileManager, styles: ui::UiStyles) -> error::Result<Self> {
+        Ok(Self {
+            entries: manager.trash_entries()?,
+            manager,
+            styles,
+            selected: 0,
+            mode: TrashMode::Select,
+        })
+    }
+
+    /// Reloads the contents of the trash, keeping the selection in bounds.
+    fn refresh_entries(&mut self) -> error::Result<()> {
+        self.entries = self.manager.trash_entries()?;
+        self.selected = self.selected.min(self.entries.len().saturating_sub(1));
+        Ok(())
+    }
+}
+
+impl super::Screen for TrashScreen {
+    fn draw(&self, area: layout::Rect, buf: &mut buffer::Buffer) {
+        let title = Line::from(vec![Span::styled(
+            ui::tr("trash-title"),
+            self.styles.title_style,
+        )]);
+
+        let count = Line::from(vec![Span::styled(
+            ui::tr_args("notes-count", &[("count", self.entries.len().into())]),
+            self.styles.text_style,
+        )])
+        .right_aligned();
+
+        let instructions = Line::from(ui::tr_hotkeys(
+            "trash-hints",
+            self.styles.hotkey_style,
+            self.styles.text_style,
+        ))
+        .left_aligned();
+
+        let rows = self
+            .entries
+            .iter()
+            .map(|entry| {
+                Row::new(vec![
+                    Span::styled(
+                        entry.deleted.format("%Y-%m-%d %H:%M").to_string(),
+                        self.styles.subtitle_style,
+                    ),
+                    Span::styled(
+                        entry.original_path.to_string_lossy().to_string(),
+                        self.styles.text_style,
+                    ),
+                ])
+            })
+            .collect_vec();
+
+        let table = Table::new(rows, [Constraint::Length(16), Constraint::Fill(1)])
+            .column_spacing(2)
+            .header(Row::new(vec![
+                Span::styled(ui::tr("trash-column-deleted"), self.styles.subtitle_style),
+                Span::styled(ui::tr("column-note"), self.styles.subtitle_style),
+            ]))
+            .highlight_symbol(ui::selection_marker())
+            .row_highlight_style(self.styles.selected_style)
+            .block(
+                ui::bordered()
+                    .title_top(title)
+                    .title_bottom(instructions)
+                    .title_bottom(count),
+            );
+
+        let mut state = TableState::new()
+            .with_offset(self.selected.saturating_sub(area.height as usize / 3))
+            .with_selected(Some(self.selected));
+
+        StatefulWidget::render(table, area, buf, &mut state);
+
+        if self.mode == TrashMode::Purge {
+            let popup_areas = Layout::vertical([
+                Constraint::Fill(1),
+                Constraint::Length(3),
+                Constraint::Fill(1),
+            ])
+            .split(area);
+
+            let center_area = Layout::horizontal([
+                Constraint::Fill(1),
+                Constraint::Percentage(60),
+                Constraint::Fill(1),
+            ])
+            .split(popup_areas[1])[1];
+
+            let keys = Line::from(ui::tr_hotkeys(
+                "delete-hints",
+                self.styles.hotkey_style,
+                self.styles.text_style,
+            ))
+            .centered();
+
+            let del = Paragraph::new(Span::styled(
+                ui::tr("trash-delete-confirm") + "\n",
+                self.styles.text_style,
+            ))
+            .alignment(Alignment::Center)
+            .block(ui::bordered().title_bottom(keys));
+
+            // Clear the area and then render the widget on top.
+            Widget::render(Clear, center_area, buf);
+            Widget::render(del, center_area, buf);
+        }
+    }
+
+    fn update(&mut self, key: ratatui::crossterm::event::KeyEvent) -> error::Result<ui::Message> {
+        match self.mode {
+            TrashMode::Select => match key.code {
+                // Quit with Q
+                KeyCode::Char('q' | 'Q') => return Ok(ui::Message::Quit),
+                // Return to the previous screen
+                KeyCode::Esc | KeyCode::Left | KeyCode::Char('h' | 'H') => {
+                    return Ok(ui::Message::CloseOverlay);
+                }
+                KeyCode::Char('j' | 'J') | KeyCode::Down => {
+                    self.selected = self
+                        .selected
+                        .saturating_add(1)
+                        .min(self.entries.len().saturating_sub(1));
+                }
+                KeyCode::Char('k' | 'K') | KeyCode::Up => {
+                    self.selected = self.selected.saturating_sub(1);
+                }
+                // R: Restore note
+                KeyCode::Char('r' | 'R') => {
+                    if let Some(entry) = self.entries.get(self.selected) {
+                        self.manager.restore_trash_entry(entry)?;
+                        self.refresh_entries()?;
+                    }
+                }
+                // D: Delete note permanently
+                KeyCode::Char('d' | 'D') if !self.entries.is_empty() => {
+                    self.mode = TrashMode::Purge;
+                }
+                _ => {}
+            },
+            TrashMode::Purge => {
+                self.mode = TrashMode::Select;
+                // Only delete on confirmation with Enter
+                if let (KeyCode::Enter, Some(entry)) = (key.code, self.entries.get(self.selected)) {
+                    self.manager.purge_trash_entry(entry)?;
+                    self.refresh_entries()?;
+                }
+            }
+        }
+
+        Ok(ui::Message::None)
+    }
+}
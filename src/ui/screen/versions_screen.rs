@@ -0,0 +1,283 @@
+use crate::{data, error, io, ui};
+
+use itertools::Itertools;
+use ratatui::crossterm::event::KeyCode;
+use ratatui::{prelude::*, widgets::*};
+
+/// Describes the current mode of the UI.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+enum VersionsMode {
+    /// Selecting a version from the list.
+    #[default]
+    Select,
+    /// Confirming the restoration of a version.
+    Restore,
+}
+
+/// The versions screen lists all committed versions of a single note and shows how they differ from its current content.
+pub struct VersionsScreen {
+    // === CONFIG ===
+    /// The file manager this screen uses to enact the user's file system requests on the file system.
+    manager: io::FileManager,
+    /// The git repository the vault is stored in.
+    git_manager: io::GitManager,
+    /// The used styles.
+    styles: ui::UiStyles,
+
+    // === DATA ===
+    /// The note whose versions are displayed.
+    note: data::Note,
+    /// All commits that changed the note, most recent first.
+    versions: Vec<io::CommitInfo>,
+    /// The content of the selected version and the current content, side by side.
+    diff: Vec<(Option<String>, Option<String>)>,
+
+    // === UI ===
+    /// The index of the version selected in the table.
+    selected: usize,
+    /// The first line of the diff shown.
+    scroll: usize,
+    /// Current input mode
+    mode: VersionsMode,
+}
+
+impl VersionsScreen {
+    /// Creates a new versions screen for the note with the given id.
+    pub fn new(
+        note_id: &str,
+        index: data::NoteIndexContainer,
+        manager: io::FileManager,
+        git_manager: io::GitManager,
+        styles: ui::UiStyles,
+    ) -> error::Result<Self> {
+        let note = index
+            .borrow()
+            .get(note_id)
+            .ok_or_else(|| error::RucolaError::NoteNotFound(note_id.to_owned()))
+            .cloned()?;
+
+        let mut screen = Self {
+            versions: git_manager.file_history(&note.path)?,
+            note,
+            manager,
+            git_manager,
+            styles,
+            diff: Vec::new(),
+            selected: 0,
+            scroll: 0,
+            mode: VersionsMode::Select,
+        };
+
+        screen.refresh_diff()?;
+
+        Ok(screen)
+    }
+
+    /// Reads the content of the note at the selected version.
+    fn selected_content(&self) -> error::Result<Option<String>> {
+        self.versions
+            .get(self.selected)
+            .map(|version| self.git_manager.file_at(version.id, &self.note.path))
+            .transpose()
+    }
+
+    /// Compares the selected version with the current content of the note.
+    fn refresh_diff(&mut self) -> error::Result<()> {
+        self.scroll = 0;
+        self.diff = match self.selected_content()? {
+            Some(old) => io::side_by_side_diff(&old, &std::fs::read_to_string(&self.note.path)?)?,
+            None => Vec::new(),
+        };
+        Ok(())
+    }
+}
+
+impl super::Screen for VersionsScreen {
+    fn draw(&self, area: layout::Rect, buf: &mut buffer::Buffer) {
+        let [versions_area, diff_area] =
+            Layout::horizontal([Constraint::Percentage(30), Constraint::Fill(1)]).areas(area);
+
+        // === Version list ===
+
+        let title = Line::from(vec![Span::styled(
+            ui::tr_args(
+                "versions-title",
+                &[("name", self.note.display_name.clone().into())],
+            ),
+            self.styles.title_style,
+        )]);
+
+        let instructions = Line::from(ui::tr_hotkeys(
+            "versions-hints",
+            self.styles.hotkey_style,
+            self.styles.text_style,
+        ))
+        .left_aligned();
+
+        let rows = self
+            .versions
+            .iter()
+            .map(|version| {
+                Row::new(vec![
+                    Span::styled(
+                        version.time.format("%Y-%m-%d %H:%M").to_string(),
+                        self.styles.subtitle_style,
+                    ),
+                    Span::styled(version.summary.clone(), self.styles.text_style),
+                ])
+            })
+            .collect_vec();
+
+        let table = Table::new(rows, [Constraint::Length(16), Constraint::Fill(1)])
+            .column_spacing(2)
+            .highlight_symbol(ui::selection_marker())
+            .row_highlight_style(self.styles.selected_style)
+            .block(ui::bordered().title_top(title).title_bottom(instructions));
+
+        let mut state = TableState::new()
+            .with_offset(self.selected.saturating_sub(area.height as usize / 3))
+            .with_selected(Some(self.selected));
+
+        StatefulWidget::render(table, versions_area, buf, &mut state);
+
+        // === Diff ===
+
+        let scroll_instructions = Line::from(ui::tr_hotkeys(
+            "versions-hints-scroll",
+            self.styles.hotkey_style,
+            self.styles.text_style,
+        ))
+        .right_aligned();
+
+        let [old_area, new_area] =
+            Layout::horizontal([Constraint::Fill(1), Constraint::Fill(1)]).areas(diff_area);
+
+        // Lines only present on one side are highlighted, the missing counterpart is left empty.
+        let side = |line: &Option<String>, other: &Option<String>| match (line, other) {
+            (Some(line), Some(_)) => Line::styled(line.clone(), self.styles.text_style),
+            (Some(line), None) => Line::styled(line.clone(), self.styles.selected_style),
+            (None, _) => Line::default(),
+        };
+
+        let old = Paragraph::new(
+            self.diff
+                .iter()
+                .skip(self.scroll)
+                .map(|(old, new)| side(old, new))
+                .collect_vec(),
+        )
+        .block(
+            ui::bordered().title(Span::styled(
+                self.versions
+                    .get(self.selected)
+                    .map(|version| version.summary.clone())
+                    .unwrap_or_else(|| ui::tr("versions-none")),
+                self.styles.title_style,
+            )),
+        );
+
+        let new = Paragraph::new(
+            self.diff
+                .iter()
+                .skip(self.scroll)
+                .map(|(old, new)| side(new, old))
+                .collect_vec(),
+        )
+        .block(
+            ui::bordered()
+                .title(Span::styled(
+                    ui::tr("versions-title-current"),
+                    self.styles.title_style,
+                ))
+                .title_bottom(scroll_instructions),
+        );
+
+        Widget::render(old, old_area, buf);
+        Widget::render(new, new_area, buf);
+
+        if self.mode == VersionsMode::Restore {
+            let popup_areas = Layout::vertical([
+                Constraint::Fill(1),
+                Constraint::Length(3),
+                Constraint::Fill(1),
+            ])
+            .split(area);
+
+            let center_area = Layout::horizontal([
+                Constraint::Fill(1),
+                Constraint::Percentage(60),
+                Constraint::Fill(1),
+            ])
+            .split(popup_areas[1])[1];
+
+            let keys = Line::from(ui::tr_hotkeys(
+                "versions-restore-hints",
+                self.styles.hotkey_style,
+                self.styles.text_style,
+            ))
+            .centered();
+
+            let restore = Paragraph::new(Span::styled(
+                ui::tr("versions-restore-confirm") + "\n",
+                self.styles.text_style,
+            ))
+            .alignment(Alignment::Center)
+            .block(ui::bordered().title_bottom(keys));
+
+            // Clear the area and then render the widget on top.
+            Widget::render(Clear, center_area, buf);
+            Widget::render(restore, center_area, buf);
+        }
+    }
+
+    fn update(&mut self, key: ratatui::crossterm::event::KeyEvent) -> error::Result<ui::Message> {
+        match self.mode {
+            VersionsMode::Select => match key.code {
+                // Quit with Q
+                KeyCode::Char('q' | 'Q') => return Ok(ui::Message::Quit),
+                // Return to the previous screen
+                KeyCode::Esc | KeyCode::Left | KeyCode::Char('h' | 'H') => {
+                    return Ok(ui::Message::CloseOverlay);
+                }
+                KeyCode::Char('j' | 'J') | KeyCode::Down => {
+                    self.selected = self
+                        .selected
+                        .saturating_add(1)
+                        .min(self.versions.len().saturating_sub(1));
+                    self.refresh_diff()?;
+                }
+                KeyCode::Char('k' | 'K') | KeyCode::Up => {
+                    self.selected = self.selected.saturating_sub(1);
+                    self.refresh_diff()?;
+                }
+                KeyCode::PageDown => {
+                    self.scroll = self
+                        .scroll
+                        .saturating_add(10)
+                        .min(self.diff.len().saturating_sub(1));
+                }
+                KeyCode::PageUp => {
+                    self.scroll = self.scroll.saturating_sub(10);
+                }
+                // R: Restore the selected version
+                KeyCode::Char('r' | 'R') if !self.versions.is_empty() => {
+                    self.mode = VersionsMode::Restore;
+                }
+                _ => {}
+            },
+            VersionsMode::Restore => {
+                self.mode = VersionsMode::Select;
+                // Only restore on confirmation with Enter
+                if key.code == KeyCode::Enter {
+                    if let Some(content) = self.selected_content()? {
+                        self.manager
+                            .restore_note_content(&self.note.path, &content)?;
+                        return Ok(ui::Message::CloseOverlay);
+                    }
+                }
+            }
+        }
+
+        Ok(ui::Message::None)
+    }
+}